@@ -0,0 +1,96 @@
+//! Times the rules over an attribute-heavy document, once with a fresh
+//! [`Analysis`] per rule and once with one shared by all of them, as the
+//! engine runs them. Run with `cargo bench --bench analysis`.
+
+use std::time::{Duration, Instant};
+use wcag_lsp::analysis::Analysis;
+use wcag_lsp::config::Config;
+use wcag_lsp::parser::{self, FileType};
+use wcag_lsp::rules;
+
+const ROUNDS: u32 = 5;
+
+/// A form of `n` radio groups whose inputs carry many attributes.
+fn document(n: usize) -> String {
+    let mut html = String::from("<html lang=\"en\"><body><main><h1>Survey</h1>");
+    for i in 0..n {
+        html.push_str(&format!(
+            "<fieldset id=\"group-{i}\" class=\"group\" data-step=\"{i}\"><legend>Question {i}</legend>"
+        ));
+        for option in 0..4 {
+            html.push_str(&format!(
+                "<label><input type=\"radio\" name=\"q{i}\" value=\"{option}\" id=\"q{i}-{option}\" \
+                 class=\"radio\" data-a=\"1\" data-b=\"2\" data-c=\"3\" data-d=\"4\" data-e=\"5\" \
+                 aria-describedby=\"hint-{i}\" title=\"Option {option}\"> Option {option}</label>"
+            ));
+        }
+        html.push_str(&format!(
+            "<p id=\"hint-{i}\">Pick one.</p><embed src=\"q{i}.pdf\" type=\"application/pdf\"></fieldset>"
+        ));
+    }
+    html.push_str("</main></body></html>");
+    html
+}
+
+fn time(f: impl Fn() -> usize) -> (Duration, usize) {
+    let start = Instant::now();
+    let mut found = 0;
+    for _ in 0..ROUNDS {
+        found = f();
+    }
+    (start.elapsed() / ROUNDS, found)
+}
+
+fn main() {
+    let source = document(500);
+    let mut parser = parser::create_parser(FileType::Html).expect("HTML parser");
+    let tree = parser.parse(&source, None).expect("parsed");
+    let root = tree.root_node();
+    let config = Config::default();
+    let rules = rules::all_rules();
+
+    let (separate, found) = time(|| {
+        rules
+            .iter()
+            .map(|rule| {
+                let analysis = Analysis::new(root, &source, FileType::Html);
+                rule.check_with_analysis(&analysis, &config).len()
+            })
+            .sum()
+    });
+    let (shared, shared_found) = time(|| {
+        let analysis = Analysis::new(root, &source, FileType::Html);
+        rules
+            .iter()
+            .map(|rule| rule.check_with_analysis(&analysis, &config).len())
+            .sum()
+    });
+    assert_eq!(found, shared_found);
+
+    println!(
+        "{} bytes, {} rules, {found} problems",
+        source.len(),
+        rules.len()
+    );
+    println!("analysis per rule: {separate:?}");
+    println!("shared analysis:   {shared:?}");
+    println!(
+        "speedup:           {:.1}x",
+        separate.as_secs_f64() / shared.as_secs_f64()
+    );
+
+    // The slowest rules, on a shared analysis that's already built.
+    let analysis = Analysis::new(root, &source, FileType::Html);
+    analysis.ids();
+    let mut by_rule: Vec<(Duration, &str)> = rules
+        .iter()
+        .map(|rule| {
+            let (elapsed, _) = time(|| rule.check_with_analysis(&analysis, &config).len());
+            (elapsed, rule.metadata().id)
+        })
+        .collect();
+    by_rule.sort_unstable_by(|a, b| b.cmp(a));
+    for (elapsed, id) in by_rule.iter().take(5) {
+        println!("  {id}: {elapsed:?}");
+    }
+}
@@ -16,6 +16,39 @@ let statusBarItem: vscode.StatusBarItem | undefined;
 let configWatchers: vscode.FileSystemWatcher[] = [];
 let restartDebounceTimer: ReturnType<typeof setTimeout> | undefined;
 
+interface ServerStatus {
+  version: string;
+  configPath: string | null;
+  enabledRules: number;
+  totalRules: number;
+  openDocuments: number;
+  lastWorkspaceScan: number | null;
+}
+
+async function refreshStatusTooltip(): Promise<void> {
+  if (!statusBarItem || !client || !client.isRunning()) {
+    return;
+  }
+
+  try {
+    const status = await client.sendRequest<ServerStatus>("wcag/status");
+    const lines = [
+      `WCAG LSP Server v${status.version} — Running`,
+      `Config: ${status.configPath ?? "defaults"}`,
+      `Rules: ${status.enabledRules}/${status.totalRules} enabled`,
+      `Open documents: ${status.openDocuments}`,
+    ];
+    if (status.lastWorkspaceScan !== null) {
+      lines.push(
+        `Last workspace scan: ${new Date(status.lastWorkspaceScan).toLocaleString()}`,
+      );
+    }
+    statusBarItem.tooltip = lines.join("\n");
+  } catch {
+    // Older servers don't implement wcag/status; keep the basic tooltip.
+  }
+}
+
 function updateStatusBar(): void {
   if (!statusBarItem) {
     return;
@@ -58,13 +91,19 @@ async function startClient(serverPath: string): Promise<void> {
   const clientOptions: LanguageClientOptions = {
     documentSelector: [
       { scheme: "file", language: "html" },
+      { scheme: "file", language: "javascript" },
       { scheme: "file", language: "javascriptreact" },
+      { scheme: "file", language: "typescript" },
       { scheme: "file", language: "typescriptreact" },
       { scheme: "file", language: "vue" },
       { scheme: "file", language: "svelte" },
       { scheme: "file", language: "astro" },
       { scheme: "file", language: "php" },
+      { scheme: "file", language: "blade" },
+      { scheme: "file", language: "mjml" },
+      { scheme: "file", language: "liquid" },
       { scheme: "file", language: "erb" },
+      { scheme: "file", language: "markdown" },
     ],
     initializationOptions: {
       configPath: configPath || undefined,
@@ -81,6 +120,7 @@ async function startClient(serverPath: string): Promise<void> {
   updateStatusBar();
   await client.start();
   updateStatusBar();
+  await refreshStatusTooltip();
 }
 
 function isValidConfig(content: string, filePath: string): boolean {
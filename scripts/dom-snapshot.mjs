@@ -0,0 +1,33 @@
+#!/usr/bin/env node
+// Saves the DOM of a page after it has rendered, for `wcag-lsp lint --dom`.
+//
+//   npm install puppeteer
+//   node scripts/dom-snapshot.mjs http://localhost:3000 > snapshot.html
+//   node scripts/dom-snapshot.mjs http://localhost:3000 | wcag-lsp lint --dom - --dynamic
+//
+// An optional second argument is a CSS selector to wait for before saving,
+// e.g. a toast that appears after some interaction the page does on load.
+
+import puppeteer from "puppeteer";
+
+const [url, waitFor] = process.argv.slice(2);
+if (!url) {
+  console.error("Usage: node dom-snapshot.mjs <url> [selector-to-wait-for]");
+  process.exit(1);
+}
+
+const browser = await puppeteer.launch();
+try {
+  const page = await browser.newPage();
+  await page.goto(url, { waitUntil: "networkidle0" });
+  if (waitFor) {
+    await page.waitForSelector(waitFor);
+  }
+  const html = await page.evaluate(() => {
+    const doctype = document.doctype ? "<!DOCTYPE html>\n" : "";
+    return doctype + document.documentElement.outerHTML;
+  });
+  process.stdout.write(html);
+} finally {
+  await browser.close();
+}
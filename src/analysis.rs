@@ -0,0 +1,638 @@
+//! Facts about a document that several rules need: its elements with their
+//! tag names and attributes, ids, landmarks, headings and focusable elements.
+//!
+//! The engine builds one [`Analysis`] per parsed tree and hands it to every
+//! rule through [`Rule::check_with_analysis`](crate::rules::Rule::check_with_analysis).
+//! Each fact is computed on first use and then shared, so the rules that use
+//! it don't each walk the tree again, and the order the rules run in doesn't
+//! matter.
+
+use crate::parser::FileType;
+use crate::rules::html_attrs::{self, Attr};
+use crate::rules::jsx_attrs;
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use tree_sitter::Node;
+
+/// Landmark roles; `region` and `form` only with a name.
+const LANDMARK_ROLES: &[&str] = &[
+    "banner",
+    "complementary",
+    "contentinfo",
+    "form",
+    "main",
+    "navigation",
+    "region",
+    "search",
+];
+
+/// Sectioning content: an `<aside>` in one is only a landmark with a name.
+const SECTIONING_ELEMENTS: &[&str] = &["article", "aside", "nav", "section"];
+
+/// Elements and roles that scope a `<header>` or `<footer>` to themselves,
+/// so it's no banner or contentinfo.
+const SCOPING_ELEMENTS: &[&str] = &["article", "aside", "main", "nav", "section"];
+const SCOPING_ROLES: &[&str] = &["article", "complementary", "main", "navigation", "region"];
+
+/// Elements that take focus without a `tabindex`, unless disabled, if they're
+/// links (`<a>` and `<area>` with an `href`) or not hidden inputs.
+const FOCUSABLE_ELEMENTS: &[&str] = &["button", "input", "select", "textarea"];
+
+const HEADINGS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// An element of the document, in document order.
+#[derive(Debug)]
+pub struct Element<'a> {
+    /// Index of the nearest enclosing element.
+    pub parent: Option<usize>,
+    /// The start tag, or the whole element if it has none.
+    pub tag: Node<'a>,
+    /// Lowercased for HTML, as written for JSX.
+    pub name: String,
+    /// A JSX component, which may render anything.
+    pub component: bool,
+    /// Has spread props, or Vue's `v-bind`, which may set any attribute.
+    pub spread: bool,
+    /// Whether there's a `role`, and its first token, lowercased, if it's
+    /// static. Spread props count as a runtime role.
+    pub role: Option<Option<String>>,
+    /// The static, trimmed `id`.
+    pub id: Option<String>,
+    /// Gets its `id` at runtime, from a bound attribute or spread props.
+    pub runtime_id: bool,
+    /// Has `aria-label` or `aria-labelledby`.
+    pub named: bool,
+    /// Inside hidden content, a `<template>` or a dialog, which isn't part of
+    /// the page as it first shows.
+    pub inert: bool,
+    /// The level of a heading.
+    pub heading: Option<u32>,
+    /// In the tab order.
+    pub focusable: bool,
+    /// Normalized as by [`html_attrs::attrs`]; in JSX, an expression is a
+    /// bound value.
+    pub attrs: Vec<Attr<'a>>,
+}
+
+impl<'a> Element<'a> {
+    /// `inert` is whether the element is inside inert content, or is an
+    /// inert `<template>`.
+    fn new(
+        parent: Option<usize>,
+        tag: Node<'a>,
+        name: String,
+        component: bool,
+        attrs: Vec<Attr<'a>>,
+        spread: bool,
+        inert: bool,
+    ) -> Self {
+        let find = |attr: &str| attrs.iter().find(|a| a.name_eq(attr));
+        let static_value = |attr: &str| {
+            find(attr)
+                .filter(|a| !a.bound)
+                .and_then(|a| a.value.as_deref())
+                .map(str::trim)
+        };
+        let role = find("role")
+            .map(|a| {
+                a.value
+                    .as_deref()
+                    .filter(|_| !a.bound)
+                    .and_then(first_token)
+            })
+            .or(spread.then_some(None));
+        let inert =
+            inert || find("hidden").is_some_and(|a| !a.bound) || is_inert(&name, role.as_ref());
+        // A bound `tabindex` that's a plain number counts as that number.
+        let tabindex = find("tabindex").map(|a| {
+            a.value
+                .as_deref()
+                .and_then(|t| t.trim().parse::<i32>().ok())
+        });
+        let native = !component
+            && match name.as_str() {
+                "a" | "area" => find("href").is_some(),
+                "input" => !static_value("type").is_some_and(|t| t.eq_ignore_ascii_case("hidden")),
+                _ => FOCUSABLE_ELEMENTS.contains(&name.as_str()),
+            }
+            && find("disabled").is_none();
+        let heading = heading_level(&name, role.as_ref(), static_value("aria-level"));
+        let id = static_value("id").map(str::to_string);
+        let runtime_id = spread || find("id").is_some_and(|a| a.bound);
+        let named = find("aria-label").is_some() || find("aria-labelledby").is_some();
+        Element {
+            parent,
+            tag,
+            name,
+            component,
+            spread,
+            role,
+            id,
+            runtime_id,
+            named,
+            inert,
+            heading,
+            focusable: is_focusable(native, tabindex),
+            attrs,
+        }
+    }
+
+    /// The whole element, with its content.
+    pub fn node(&self) -> Node<'a> {
+        match self.tag.kind() {
+            "jsx_self_closing_element" => self.tag,
+            _ => self.tag.parent().unwrap_or(self.tag),
+        }
+    }
+
+    /// The node holding the element's name in its start tag.
+    pub fn name_node(&self) -> Option<Node<'a>> {
+        if let Some(name) = self.tag.child_by_field_name("name") {
+            return Some(name);
+        }
+        let mut cursor = self.tag.walk();
+        self.tag
+            .children(&mut cursor)
+            .find(|child| child.kind() == "tag_name")
+    }
+
+    /// The attribute `name`, compared case-insensitively.
+    pub fn attr(&self, name: &str) -> Option<&Attr<'a>> {
+        self.attrs.iter().find(|a| a.name_eq(name))
+    }
+
+    /// The trimmed value of a static attribute.
+    pub fn static_value(&self, name: &str) -> Option<&str> {
+        self.attr(name)
+            .filter(|a| !a.bound)
+            .and_then(|a| a.value.as_deref())
+            .map(str::trim)
+    }
+}
+
+/// The shared facts about one parsed tree.
+pub struct Analysis<'a> {
+    root: Node<'a>,
+    source: &'a str,
+    file_type: FileType,
+    elements: OnceCell<Vec<Element<'a>>>,
+    ids: OnceCell<HashMap<String, Vec<usize>>>,
+    landmarks: OnceCell<Vec<Option<&'static str>>>,
+    index: OnceCell<HashMap<usize, usize>>,
+}
+
+impl<'a> Analysis<'a> {
+    pub fn new(root: Node<'a>, source: &'a str, file_type: FileType) -> Self {
+        Analysis {
+            root,
+            source,
+            file_type,
+            elements: OnceCell::new(),
+            ids: OnceCell::new(),
+            landmarks: OnceCell::new(),
+            index: OnceCell::new(),
+        }
+    }
+
+    pub fn root(&self) -> &Node<'a> {
+        &self.root
+    }
+
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Every element, in document order.
+    pub fn elements(&self) -> &[Element<'a>] {
+        self.elements.get_or_init(|| {
+            let mut elements = Vec::new();
+            if self.file_type.is_jsx_like() {
+                collect_jsx(&self.root, self.source, None, false, &mut elements);
+            } else {
+                // A Vue file's `<template>`s are what it renders.
+                let templates_inert = self.file_type != FileType::Vue;
+                collect_html(
+                    &self.root,
+                    self.source,
+                    None,
+                    false,
+                    templates_inert,
+                    &mut elements,
+                );
+            }
+            elements
+        })
+    }
+
+    /// The index of the element whose node or tag is `node`.
+    pub fn element_at(&self, node: &Node) -> Option<usize> {
+        let index = self.index.get_or_init(|| {
+            let mut index = HashMap::new();
+            for (i, element) in self.elements().iter().enumerate() {
+                index.insert(element.node().id(), i);
+                index.insert(element.tag.id(), i);
+            }
+            index
+        });
+        index.get(&node.id()).copied()
+    }
+
+    /// Whether the file is a whole page (an HTML document with `<html>` or
+    /// `<body>`) rather than a component or partial.
+    pub fn is_page(&self) -> bool {
+        !self.file_type.is_fragment()
+            && self
+                .elements()
+                .iter()
+                .any(|e| e.name == "html" || e.name == "body")
+    }
+
+    /// The elements with each static id.
+    pub fn ids(&self) -> &HashMap<String, Vec<usize>> {
+        self.ids.get_or_init(|| {
+            let mut ids: HashMap<String, Vec<usize>> = HashMap::new();
+            for (i, element) in self.elements().iter().enumerate() {
+                if let Some(id) = element.id.as_ref().filter(|id| !id.is_empty()) {
+                    ids.entry(id.clone()).or_default().push(i);
+                }
+            }
+            ids
+        })
+    }
+
+    /// Whether some element gets its id at runtime, so an id missing from
+    /// [`Analysis::ids`] may still exist.
+    pub fn runtime_ids(&self) -> bool {
+        self.elements().iter().any(|e| e.runtime_id || e.component)
+    }
+
+    /// The landmark role of element `i`, if it is one.
+    pub fn landmark(&self, i: usize) -> Option<&'static str> {
+        self.landmark_roles()[i]
+    }
+
+    /// The landmarks, in document order, with their roles.
+    pub fn landmarks(&self) -> impl Iterator<Item = (usize, &'static str)> + '_ {
+        self.landmark_roles()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, role)| role.map(|role| (i, role)))
+    }
+
+    /// The headings, in document order, with their levels.
+    pub fn headings(&self) -> impl Iterator<Item = (usize, u32)> + '_ {
+        self.elements()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| e.heading.filter(|_| !e.inert).map(|level| (i, level)))
+    }
+
+    /// The elements in the tab order, in document order.
+    pub fn focusable(&self) -> impl Iterator<Item = usize> + '_ {
+        self.elements()
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.focusable && !e.inert)
+            .map(|(i, _)| i)
+    }
+
+    /// Whether some ancestor of element `i` matches `f`.
+    pub fn within(&self, i: usize, f: impl Fn(&Element) -> bool) -> bool {
+        let elements = self.elements();
+        let mut current = elements[i].parent;
+        while let Some(j) = current {
+            if f(&elements[j]) {
+                return true;
+            }
+            current = elements[j].parent;
+        }
+        false
+    }
+
+    fn landmark_roles(&self) -> &[Option<&'static str>] {
+        self.landmarks.get_or_init(|| {
+            let page = self.is_page();
+            (0..self.elements().len())
+                .map(|i| self.landmark_role(i, page))
+                .collect()
+        })
+    }
+
+    /// `page` is whether the file is a whole page, where `<header>` and
+    /// `<footer>` outside of sections are its banner and contentinfo.
+    fn landmark_role(&self, i: usize, page: bool) -> Option<&'static str> {
+        let element = &self.elements()[i];
+        if element.inert {
+            return None;
+        }
+        let role = match &element.role {
+            // A role only known at runtime may be anything.
+            Some(None) => return None,
+            Some(Some(role)) => LANDMARK_ROLES.iter().find(|r| *r == role).copied()?,
+            None => match element.name.as_str() {
+                "main" => "main",
+                "nav" => "navigation",
+                "search" => "search",
+                "section" => "region",
+                "form" => "form",
+                "aside"
+                    if element.named
+                        || !self.within(i, |e| SECTIONING_ELEMENTS.contains(&e.name.as_str())) =>
+                {
+                    "complementary"
+                }
+                "header" if page && !self.scoped(i) => "banner",
+                "footer" if page && !self.scoped(i) => "contentinfo",
+                _ => return None,
+            },
+        };
+        // Regions and forms are only landmarks with a name.
+        (!matches!(role, "region" | "form") || element.named).then_some(role)
+    }
+
+    /// Whether a `<header>` or `<footer>` belongs to a section rather than
+    /// the page.
+    fn scoped(&self, i: usize) -> bool {
+        self.within(i, |e| match &e.role {
+            Some(Some(role)) => SCOPING_ROLES.contains(&role.as_str()),
+            Some(None) => false,
+            None => SCOPING_ELEMENTS.contains(&e.name.as_str()),
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn collect_html<'a>(
+    node: &Node<'a>,
+    source: &str,
+    parent: Option<usize>,
+    inert: bool,
+    templates_inert: bool,
+    elements: &mut Vec<Element<'a>>,
+) {
+    let mut parent = parent;
+    let mut inert = inert;
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source)
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let attrs = html_attrs::attrs(&tag, source);
+        // `v-bind="attrs"` may set any attribute.
+        let spread = attrs.iter().any(|a| a.name_eq("v-bind"));
+        let template = name == "template" && templates_inert;
+        let element = Element::new(parent, tag, name, false, attrs, spread, inert || template);
+        inert = element.inert;
+        elements.push(element);
+        parent = Some(elements.len() - 1);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_html(&child, source, parent, inert, templates_inert, elements);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn collect_jsx<'a>(
+    node: &Node<'a>,
+    source: &str,
+    parent: Option<usize>,
+    inert: bool,
+    elements: &mut Vec<Element<'a>>,
+) {
+    let mut parent = parent;
+    let mut inert = inert;
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        // `<>` is a fragment, not a component.
+        let component = !name.is_empty()
+            && (!name.starts_with(|c: char| c.is_ascii_lowercase()) || name.contains('.'));
+        let mut attrs = Vec::new();
+        let mut spread = false;
+        let mut cursor = tag.walk();
+        for child in tag.named_children(&mut cursor) {
+            if child.kind() == "jsx_expression" {
+                spread = true;
+            }
+            attrs.extend(jsx_attr(&child, source));
+        }
+        let element = Element::new(
+            parent,
+            tag,
+            name.to_string(),
+            component,
+            attrs,
+            spread,
+            inert,
+        );
+        inert = element.inert;
+        elements.push(element);
+        parent = Some(elements.len() - 1);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_jsx(&child, source, parent, inert, elements);
+    }
+}
+
+/// A JSX attribute as an [`Attr`]. An expression is `bound`, with its text
+/// inside the braces as the value.
+fn jsx_attr<'a>(node: &Node<'a>, source: &str) -> Option<Attr<'a>> {
+    let (name, value) = jsx_attrs::jsx_attribute(node, source)?;
+    let bound = value.is_some_and(|v| v.kind() != "string");
+    let value = value.map(|v| {
+        let text = &source[v.byte_range()];
+        match text.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+            Some(expression) if bound => expression.trim(),
+            _ => text.trim_matches(['"', '\'']),
+        }
+        .to_string()
+    });
+    Some(Attr {
+        node: *node,
+        name: name.to_string(),
+        value,
+        bound,
+        event: jsx_attrs::jsx_event(name).is_some(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn first_token(role: &str) -> Option<String> {
+    role.split_whitespace().next().map(str::to_ascii_lowercase)
+}
+
+/// Whether an element is a dialog, which isn't part of the page until it
+/// opens.
+fn is_inert(name: &str, role: Option<&Option<String>>) -> bool {
+    name == "dialog"
+        || role
+            .and_then(|r| r.as_deref())
+            .is_some_and(|r| r == "dialog" || r == "alertdialog")
+}
+
+/// An `<h1>`–`<h6>` by its number, or `role="heading"` by its `aria-level`
+/// (2 without one).
+fn heading_level(
+    name: &str,
+    role: Option<&Option<String>>,
+    aria_level: Option<&str>,
+) -> Option<u32> {
+    match role {
+        Some(Some(role)) if role == "heading" => {
+            Some(aria_level.and_then(|l| l.parse().ok()).unwrap_or(2))
+        }
+        Some(_) => None,
+        None => HEADINGS
+            .iter()
+            .position(|h| *h == name)
+            .map(|i| i as u32 + 1),
+    }
+}
+
+/// `tabindex` is `None` without one, `Some(None)` for one that isn't a
+/// static number.
+fn is_focusable(native: bool, tabindex: Option<Option<i32>>) -> bool {
+    match tabindex {
+        Some(Some(t)) => t >= 0,
+        // A runtime tabindex is most likely to keep an element focusable.
+        Some(None) => true,
+        None => native,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn with_analysis(source: &str, file_type: FileType, f: impl FnOnce(&Analysis)) {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        f(&Analysis::new(tree.root_node(), source, file_type));
+    }
+
+    fn names<'a>(analysis: &'a Analysis, indices: impl Iterator<Item = usize>) -> Vec<&'a str> {
+        indices
+            .map(|i| analysis.elements()[i].name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_html_page() {
+        let source = r#"<html><body>
+<header><a href="/">Home</a></header>
+<main id="content">
+  <article><header><h1>Post</h1></header></article>
+  <div role="heading" aria-level="3">Related</div>
+  <button disabled>Off</button><span tabindex="0">On</span><a>No href</a>
+  <section>Plain</section><section aria-label="Named"></section>
+</main>
+<dialog><h2>Modal</h2><button>Close</button></dialog>
+<footer id="content"></footer>
+</body></html>"#;
+        with_analysis(source, FileType::Html, |analysis| {
+            assert!(analysis.is_page());
+            let landmarks: Vec<_> = analysis.landmarks().map(|(_, role)| role).collect();
+            assert_eq!(landmarks, ["banner", "main", "region", "contentinfo"]);
+            let headings: Vec<_> = analysis.headings().map(|(_, level)| level).collect();
+            assert_eq!(headings, [1, 3]);
+            assert_eq!(names(analysis, analysis.focusable()), ["a", "span"]);
+            assert_eq!(analysis.ids()["content"].len(), 2);
+            assert!(!analysis.runtime_ids());
+        });
+    }
+
+    #[test]
+    fn test_fragments_have_no_banner() {
+        with_analysis(
+            "<header></header><main></main>",
+            FileType::Html,
+            |analysis| {
+                assert!(!analysis.is_page());
+                let landmarks: Vec<_> = analysis.landmarks().map(|(_, role)| role).collect();
+                assert_eq!(landmarks, ["main"]);
+            },
+        );
+    }
+
+    #[test]
+    fn test_vue_bound_attributes() {
+        let source = r#"<template><div :id="id" :role="role"></div><input type="hidden"><button>Go</button></template>"#;
+        with_analysis(source, FileType::Vue, |analysis| {
+            assert!(analysis.runtime_ids());
+            assert_eq!(analysis.elements()[1].role, Some(None));
+            // The `<template>` of a component is what it renders.
+            assert_eq!(names(analysis, analysis.focusable()), ["button"]);
+        });
+        let source = r#"<body><template><button>Row</button></template></body>"#;
+        with_analysis(source, FileType::Html, |analysis| {
+            assert_eq!(analysis.focusable().count(), 0);
+        });
+    }
+
+    #[test]
+    fn test_cached_attributes() {
+        let source = r#"<label for="q">Search</label><input id="q" :value="query">"#;
+        with_analysis(source, FileType::Vue, |analysis| {
+            let input = &analysis.elements()[1];
+            assert_eq!(input.static_value("ID"), Some("q"));
+            assert!(input.attr("value").is_some_and(|a| a.bound));
+            assert_eq!(analysis.element_at(&input.node()), Some(1));
+            assert_eq!(analysis.element_at(&input.tag), Some(1));
+            assert_eq!(analysis.element_at(analysis.root()), None);
+        });
+        let source = r#"const C = () => <a href="/" onClick={go} tabIndex={ -1 }>Home</a>;"#;
+        with_analysis(source, FileType::Tsx, |analysis| {
+            let link = &analysis.elements()[0];
+            assert_eq!(link.static_value("href"), Some("/"));
+            let on_click = link.attr("onClick").unwrap();
+            assert!(on_click.bound && on_click.event);
+            assert_eq!(on_click.value.as_deref(), Some("go"));
+            assert!(!link.focusable);
+        });
+    }
+
+    #[test]
+    fn test_jsx() {
+        let source = r#"const C = () => (
+  <>
+    <nav aria-label="Main"><a href="/">Home</a></nav>
+    <div tabIndex={0} />
+    <div tabIndex={-1} id="x" />
+    <Button />
+    <h2 hidden>Hidden</h2>
+  </>
+);"#;
+        with_analysis(source, FileType::Tsx, |analysis| {
+            assert_eq!(analysis.landmarks().count(), 1);
+            assert_eq!(names(analysis, analysis.focusable()), ["a", "div"]);
+            assert_eq!(analysis.headings().count(), 0);
+            assert_eq!(analysis.ids()["x"], [4]);
+            assert!(analysis.runtime_ids());
+        });
+    }
+}
@@ -0,0 +1,509 @@
+//! `wcag-lsp annotate` output: the role, accessible name and states of each
+//! element of a template, for design and QA tools to check the structure it
+//! exposes to assistive technology against their own.
+//!
+//! Every element with a role is annotated, whether the role is explicit or
+//! implied by the element, with the index of its nearest annotated ancestor
+//! so the output reads as a tree. Names follow the accessible name
+//! computation in the order that matters for markup: `aria-labelledby`,
+//! `aria-label`, the element's own labelling (`alt`, a `<label>`, a
+//! `<legend>`…), its content for the roles named by it, then `title`.
+//!
+//! Anything only known at runtime (a bound attribute, a JSX expression) is
+//! shown as `{expression}`. Components mapped in `[components]` are annotated
+//! as the element they render, with their name as written in `component`.
+
+use crate::analysis::{Analysis, Element};
+use crate::config::Config;
+use crate::document::Document;
+use crate::engine::{Trees, node_to_range};
+use crate::quickfix;
+use crate::rules::aria_name;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+
+/// The ARIA states and properties reported, without their `aria-` prefix.
+const ARIA_STATES: &[&str] = &[
+    "busy",
+    "checked",
+    "current",
+    "disabled",
+    "expanded",
+    "haspopup",
+    "hidden",
+    "invalid",
+    "level",
+    "modal",
+    "multiselectable",
+    "orientation",
+    "pressed",
+    "readonly",
+    "required",
+    "selected",
+    "sort",
+    "valuemax",
+    "valuemin",
+    "valuenow",
+    "valuetext",
+];
+
+/// Boolean attributes that set a state on their own.
+const NATIVE_STATES: &[&str] = &[
+    "checked", "disabled", "hidden", "multiple", "open", "readonly", "required", "selected",
+];
+
+/// Roles that take their name from their content.
+const NAME_FROM_CONTENT: &[&str] = &[
+    "button",
+    "cell",
+    "checkbox",
+    "columnheader",
+    "gridcell",
+    "heading",
+    "link",
+    "menuitem",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "option",
+    "radio",
+    "row",
+    "rowheader",
+    "switch",
+    "tab",
+    "tooltip",
+    "treeitem",
+];
+
+/// Form fields named by a `<label>`.
+const LABELLED: &[&str] = &["input", "meter", "output", "progress", "select", "textarea"];
+
+/// One annotated element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// Position of its tag in the file, counting from 1.
+    pub line: u32,
+    pub column: u32,
+    /// The element, lowercased for HTML.
+    pub element: String,
+    /// The component as written, if it's mapped to `element`.
+    pub component: Option<String>,
+    pub role: String,
+    pub name: Option<String>,
+    /// By state or property name, without `aria-`.
+    pub states: BTreeMap<String, Value>,
+    /// Index of the nearest annotated ancestor.
+    pub parent: Option<usize>,
+}
+
+/// The annotated elements of `doc`, in document order, with those of its
+/// tagged templates after the file's own.
+pub fn annotate(doc: &Document, config: &Config) -> Vec<Annotation> {
+    let trees = Trees::new(doc, config);
+    let mut annotations = Vec::new();
+    for (analysis, mapped) in trees.analyses() {
+        // Index of each element's annotation, if it has one.
+        let mut annotated: Vec<Option<usize>> = Vec::new();
+        for (i, element) in analysis.elements().iter().enumerate() {
+            let parent = element.parent.and_then(|p| annotated[p]);
+            let Some(role) = role(&analysis, i) else {
+                annotated.push(parent);
+                continue;
+            };
+            let mut range = node_to_range(&element.tag);
+            let mut component = None;
+            if let Some(mapped) = mapped {
+                range = mapped.range(range);
+                component = element
+                    .name_node()
+                    .map(|name| node_to_range(&name))
+                    .filter(|&name| mapped.touches_rename(name))
+                    .and_then(|name| {
+                        let name = mapped.range(name);
+                        let start = quickfix::offset(&doc.source, name.start);
+                        let end = quickfix::offset(&doc.source, name.end);
+                        doc.source.get(start..end).map(str::to_string)
+                    });
+            }
+            annotated.push(Some(annotations.len()));
+            annotations.push(Annotation {
+                line: range.start.line + 1,
+                column: range.start.character + 1,
+                element: element.name.clone(),
+                component,
+                name: name(&analysis, i, &role),
+                states: states(element),
+                role,
+                parent,
+            });
+        }
+    }
+    annotations
+}
+
+/// The annotations of the file at `path` as JSON.
+pub fn json(path: &str, annotations: &[Annotation]) -> Value {
+    let elements: Vec<Value> = annotations
+        .iter()
+        .map(|a| {
+            let mut element = json!({
+                "element": a.element,
+                "role": a.role,
+                "name": a.name,
+                "states": a.states,
+                "line": a.line,
+                "column": a.column,
+                "parent": a.parent,
+            });
+            if let Some(component) = &a.component {
+                element["component"] = json!(component);
+            }
+            element
+        })
+        .collect();
+    json!({ "file": path, "elements": elements })
+}
+
+/// The value of attribute `name`, trimmed, or `{expression}` if it's bound.
+fn value(element: &Element, name: &str) -> Option<String> {
+    let attr = element.attr(name)?;
+    let value = attr.value.as_deref()?.trim();
+    if attr.bound {
+        Some(format!("{{{value}}}"))
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// The explicit role, or the one the element implies.
+fn role(analysis: &Analysis, i: usize) -> Option<String> {
+    let element = &analysis.elements()[i];
+    if let Some(role) = element.attr("role") {
+        if role.bound {
+            return value(element, "role");
+        }
+        if let Some(Some(role)) = &element.role {
+            return Some(role.clone());
+        }
+    }
+    if element.component {
+        return None;
+    }
+    let role = match element.name.as_str() {
+        "a" | "area" if element.attr("href").is_some() => "link",
+        "article" => "article",
+        "aside" | "footer" | "form" | "header" | "section" => analysis.landmark(i)?,
+        "blockquote" => "blockquote",
+        "button" | "summary" => "button",
+        "datalist" => "listbox",
+        "dd" => "definition",
+        "details" | "fieldset" | "optgroup" => "group",
+        "dialog" => "dialog",
+        "dt" => "term",
+        "figure" => "figure",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "hr" => "separator",
+        "img"
+            if element.attr("alt").is_some_and(|a| {
+                !a.bound && a.value.as_deref().is_none_or(|v| v.trim().is_empty())
+            }) =>
+        {
+            "presentation"
+        }
+        "img" => "img",
+        "input" => input_role(element)?,
+        "li" => "listitem",
+        "main" => "main",
+        "menu" | "ol" | "ul" => "list",
+        "meter" => "meter",
+        "nav" => "navigation",
+        "option" => "option",
+        "output" => "status",
+        "p" => "paragraph",
+        "progress" => "progressbar",
+        "select"
+            if element.attr("multiple").is_some()
+                || element
+                    .static_value("size")
+                    .and_then(|s| s.parse::<u32>().ok())
+                    .is_some_and(|s| s > 1) =>
+        {
+            "listbox"
+        }
+        "select" => "combobox",
+        "table" => "table",
+        "tbody" | "tfoot" | "thead" => "rowgroup",
+        "td" => "cell",
+        "textarea" => "textbox",
+        "th" => "columnheader",
+        "tr" => "row",
+        _ => return None,
+    };
+    Some(role.to_string())
+}
+
+fn input_role(element: &Element) -> Option<&'static str> {
+    let kind = element
+        .static_value("type")
+        .unwrap_or("text")
+        .to_ascii_lowercase();
+    Some(match kind.as_str() {
+        "hidden" => return None,
+        "button" | "image" | "reset" | "submit" => "button",
+        "checkbox" => "checkbox",
+        "number" => "spinbutton",
+        "radio" => "radio",
+        "range" => "slider",
+        "search" if element.attr("list").is_none() => "searchbox",
+        _ if element.attr("list").is_some() => "combobox",
+        _ => "textbox",
+    })
+}
+
+/// The accessible name of element `i` with `role`.
+fn name(analysis: &Analysis, i: usize, role: &str) -> Option<String> {
+    let element = &analysis.elements()[i];
+    let name = labelled_by(analysis, element)
+        .or_else(|| value(element, "aria-label").filter(|v| !v.is_empty()))
+        .or_else(|| native_name(analysis, i))
+        .or_else(|| {
+            NAME_FROM_CONTENT
+                .contains(&role)
+                .then(|| aria_name::text(analysis, element.node()))
+        })
+        .filter(|name| !name.is_empty())
+        .or_else(|| value(element, "title"))
+        .or_else(|| value(element, "placeholder"));
+    name.filter(|name| !name.is_empty())
+}
+
+/// The text of the elements `aria-labelledby` points at.
+fn labelled_by(analysis: &Analysis, element: &Element) -> Option<String> {
+    let attr = element.attr("aria-labelledby")?;
+    if attr.bound {
+        return value(element, "aria-labelledby");
+    }
+    let parts: Vec<String> = attr
+        .value
+        .as_deref()?
+        .split_whitespace()
+        .filter_map(|id| analysis.ids().get(id)?.first())
+        .map(|&j| aria_name::text(analysis, analysis.elements()[j].node()))
+        .filter(|text| !text.is_empty())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// The name the element gets from what HTML gives it to label it with.
+fn native_name(analysis: &Analysis, i: usize) -> Option<String> {
+    let element = &analysis.elements()[i];
+    let input_type = element.static_value("type").map(str::to_ascii_lowercase);
+    match (element.name.as_str(), input_type.as_deref()) {
+        ("img" | "area", _) | ("input", Some("image")) => value(element, "alt"),
+        ("input", Some("button" | "reset" | "submit")) => {
+            value(element, "value").or_else(|| match input_type.as_deref() {
+                Some("reset") => Some("Reset".to_string()),
+                Some("submit") => Some("Submit".to_string()),
+                _ => None,
+            })
+        }
+        ("fieldset", _) => child_text(analysis, i, "legend"),
+        ("figure", _) => child_text(analysis, i, "figcaption"),
+        ("table", _) => child_text(analysis, i, "caption"),
+        (name, _) if LABELLED.contains(&name) => label(analysis, i),
+        _ => None,
+    }
+}
+
+/// The text of the first child of element `i` named `child`.
+fn child_text(analysis: &Analysis, i: usize, child: &str) -> Option<String> {
+    let elements = analysis.elements();
+    let j = (i + 1..elements.len())
+        .find(|&j| elements[j].parent == Some(i) && elements[j].name == child)?;
+    Some(aria_name::text(analysis, elements[j].node()))
+}
+
+/// The text of the `<label>` for element `i`, or of the one around it.
+fn label(analysis: &Analysis, i: usize) -> Option<String> {
+    let elements = analysis.elements();
+    if let Some(id) = &elements[i].id
+        && let Some(label) = elements.iter().find(|e| {
+            e.name == "label"
+                && (e.static_value("for") == Some(id) || e.static_value("htmlFor") == Some(id))
+        })
+    {
+        return Some(aria_name::text(analysis, label.node()));
+    }
+    let mut parent = elements[i].parent;
+    while let Some(p) = parent {
+        if elements[p].name == "label" {
+            return Some(aria_name::text(analysis, elements[p].node()));
+        }
+        parent = elements[p].parent;
+    }
+    None
+}
+
+/// The element's states: its ARIA states and properties, the boolean
+/// attributes that set one, and a heading's level.
+fn states(element: &Element) -> BTreeMap<String, Value> {
+    let mut states = BTreeMap::new();
+    for &state in NATIVE_STATES {
+        if let Some(attr) = element.attr(state) {
+            let state_value = match attr.bound {
+                true => value(element, state).map_or(Value::Bool(true), Value::String),
+                false => Value::Bool(true),
+            };
+            states.insert(state.to_string(), state_value);
+        }
+    }
+    if let Some(level) = element.heading {
+        states.insert("level".to_string(), json!(level));
+    }
+    for &state in ARIA_STATES {
+        if let Some(state_value) = value(element, &format!("aria-{state}")) {
+            states.insert(state.to_string(), Value::String(state_value));
+        }
+    }
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::FileType;
+    use crate::scan;
+
+    fn annotate_source(source: &str, file_type: FileType, config: &Config) -> Vec<Annotation> {
+        let doc = scan::document("file:///test", source, file_type, false).unwrap();
+        annotate(&doc, config)
+    }
+
+    fn annotate_html(source: &str) -> Vec<Annotation> {
+        annotate_source(source, FileType::Html, &Config::default())
+    }
+
+    fn summary(annotations: &[Annotation]) -> Vec<(&str, Option<&str>)> {
+        annotations
+            .iter()
+            .map(|a| (a.role.as_str(), a.name.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_roles_and_names() {
+        let source = r#"<nav aria-label="Main">
+  <ul>
+    <li><a href="/">Home <span aria-hidden="true">→</span></a></li>
+    <li><a href="/cart"><img src="cart.svg" alt="Cart"></a></li>
+  </ul>
+</nav>
+<div>
+  <h2 id="t">Sign in</h2>
+  <form aria-labelledby="t">
+    <label for="email">Email</label>
+    <input id="email" type="email" required>
+    <label><input type="checkbox" checked> Remember me</label>
+    <input type="submit">
+  </form>
+</div>"#;
+        let annotations = annotate_html(source);
+        assert_eq!(
+            summary(&annotations),
+            vec![
+                ("navigation", Some("Main")),
+                ("list", None),
+                ("listitem", None),
+                ("link", Some("Home")),
+                ("listitem", None),
+                ("link", Some("Cart")),
+                ("img", Some("Cart")),
+                ("heading", Some("Sign in")),
+                ("form", Some("Sign in")),
+                ("textbox", Some("Email")),
+                ("checkbox", Some("Remember me")),
+                ("button", Some("Submit")),
+            ]
+        );
+        assert_eq!(annotations[1].parent, Some(0));
+        assert_eq!(annotations[3].parent, Some(2));
+        // The `<div>` isn't annotated, so the heading is a root.
+        assert_eq!(annotations[7].parent, None);
+        assert_eq!(annotations[9].parent, Some(8));
+        assert_eq!((annotations[9].line, annotations[9].column), (11, 5));
+        assert_eq!(annotations[7].states["level"], json!(2));
+        assert_eq!(annotations[9].states["required"], json!(true));
+        assert_eq!(annotations[10].states["checked"], json!(true));
+    }
+
+    #[test]
+    fn test_explicit_roles_and_aria_states() {
+        let source = r#"<div role="tablist">
+  <div role="tab" aria-selected="true" aria-controls="p1">Overview</div>
+  <div role="tab" aria-selected="false" title="Details"></div>
+</div>
+<img src="divider.png" alt="">
+<button aria-expanded="false" aria-haspopup="menu" disabled>Menu</button>"#;
+        let annotations = annotate_html(source);
+        assert_eq!(
+            summary(&annotations),
+            vec![
+                ("tablist", None),
+                ("tab", Some("Overview")),
+                ("tab", Some("Details")),
+                ("presentation", None),
+                ("button", Some("Menu")),
+            ]
+        );
+        assert_eq!(annotations[1].states["selected"], json!("true"));
+        let button = &annotations[4].states;
+        assert_eq!(button["expanded"], json!("false"));
+        assert_eq!(button["haspopup"], json!("menu"));
+        assert_eq!(button["disabled"], json!(true));
+    }
+
+    #[test]
+    fn test_runtime_values_as_expressions() {
+        let source = r#"const Item = ({ label, open }) => (
+  <li>
+    <button aria-expanded={open} onClick={toggle}>{label}</button>
+    <Icon role="img" aria-label={t("icon")} />
+    <Card />
+  </li>
+);"#;
+        let annotations = annotate_source(source, FileType::Tsx, &Config::default());
+        assert_eq!(
+            summary(&annotations),
+            vec![
+                ("listitem", None),
+                ("button", Some("{label}")),
+                ("img", Some("{t(\"icon\")}")),
+            ]
+        );
+        assert_eq!(annotations[1].states["expanded"], json!("{open}"));
+        assert_eq!(annotations[2].element, "Icon");
+    }
+
+    #[test]
+    fn test_mapped_components() {
+        let config = Config::parse("[components]\nButton = \"button\"\n");
+        let source = "const A = () => (\n  <div>\n    <Button>Save</Button>\n  </div>\n);";
+        let annotations = annotate_source(source, FileType::Tsx, &config);
+        assert_eq!(summary(&annotations), vec![("button", Some("Save"))]);
+        assert_eq!(annotations[0].element, "button");
+        assert_eq!(annotations[0].component.as_deref(), Some("Button"));
+        assert_eq!((annotations[0].line, annotations[0].column), (3, 5));
+    }
+
+    #[test]
+    fn test_json() {
+        let annotations = annotate_html(r#"<main><h1>Title</h1></main>"#);
+        let value = json("index.html", &annotations);
+        assert_eq!(value["file"], "index.html");
+        assert_eq!(value["elements"][0]["role"], "main");
+        assert_eq!(value["elements"][0]["name"], Value::Null);
+        assert_eq!(value["elements"][1]["name"], "Title");
+        assert_eq!(value["elements"][1]["parent"], 0);
+        assert_eq!(value["elements"][1]["states"]["level"], 1);
+        assert!(value["elements"][0].get("component").is_none());
+    }
+}
@@ -0,0 +1,213 @@
+//! Applying safe quick fixes to files, for `wcag-lsp fix`.
+//!
+//! Uses the same [`Fix`]es the editor gets as code actions, but only those
+//! marked `safe`, and for each diagnostic only its first fix. Locale-dependent
+//! fixes are applied only when `[fixes] locale` is configured, since there is
+//! no editor to ask.
+
+use crate::config::Config;
+use crate::parser::FileType;
+use crate::quickfix::{self, Fix};
+use crate::rules::Rule;
+use crate::scan;
+use tower_lsp_server::ls_types::{Diagnostic, NumberOrString, TextEdit};
+
+/// Re-lint and fix at most this many times: a fix can make another one
+/// possible, but must not loop forever.
+const MAX_PASSES: usize = 10;
+
+/// One fix that was applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Applied {
+    pub rule_id: String,
+    pub title: String,
+    /// 1-based line in the source the fix was computed for.
+    pub line: u32,
+}
+
+/// `source`, read from `uri`, with every safe fix applied, and what was
+/// applied.
+pub fn fix_source(
+    uri: &str,
+    source: &str,
+    file_type: FileType,
+    rules: &[Box<dyn Rule>],
+    config: &Config,
+) -> (String, Vec<Applied>) {
+    let mut current = source.to_string();
+    let mut applied = Vec::new();
+    for _ in 0..MAX_PASSES {
+        let (diagnostics, _) = scan::lint_source(uri, &current, file_type, rules, config);
+        let (next, pass) = apply_pass(&current, &diagnostics, config);
+        if pass.is_empty() {
+            break;
+        }
+        current = next;
+        applied.extend(pass);
+    }
+    (current, applied)
+}
+
+/// Applies the safe fixes of `diagnostics` that don't overlap an earlier one.
+fn apply_pass(source: &str, diagnostics: &[Diagnostic], config: &Config) -> (String, Vec<Applied>) {
+    let mut edits: Vec<TextEdit> = Vec::new();
+    let mut applied = Vec::new();
+    for diagnostic in diagnostics {
+        let Some(fix) = safe_fix(diagnostic, config) else {
+            continue;
+        };
+        let mut candidate = edits.clone();
+        candidate.extend(fix.edits.iter().cloned());
+        if quickfix::apply(source, &candidate).is_none() {
+            continue;
+        }
+        edits = candidate;
+        let rule_id = match &diagnostic.code {
+            Some(NumberOrString::String(id)) => id.clone(),
+            _ => String::new(),
+        };
+        applied.push(Applied {
+            rule_id,
+            title: fix.title,
+            line: diagnostic.range.start.line + 1,
+        });
+    }
+    let fixed = quickfix::apply(source, &edits).unwrap_or_else(|| source.to_string());
+    applied.sort_by_key(|a| a.line);
+    (fixed, applied)
+}
+
+fn safe_fix(diagnostic: &Diagnostic, config: &Config) -> Option<Fix> {
+    let fix = quickfix::fixes(diagnostic).into_iter().next()?;
+    if !fix.safe || fix.snippet {
+        return None;
+    }
+    if fix.per_locale {
+        let configured = config.fix_locale.as_deref()?;
+        let locale = quickfix::candidate_locales(Some(configured), None).remove(0);
+        return Some(quickfix::localize(&fix, &locale));
+    }
+    Some(fix)
+}
+
+/// A unified diff from `old` to `new` with three lines of context.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    const CONTEXT: usize = 3;
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence table, filled from the end.
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // (tag, old line index, new line index) for every line of both files.
+    let mut ops: Vec<(char, usize, usize)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((' ', i, j));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', i, j));
+            i += 1;
+        } else {
+            ops.push(('+', i, j));
+            j += 1;
+        }
+    }
+
+    let mut out = format!("--- a/{path}\n+++ b/{path}\n");
+    let changed: Vec<usize> = (0..ops.len()).filter(|&k| ops[k].0 != ' ').collect();
+    let mut k = 0;
+    while k < changed.len() {
+        // Grow the hunk while the next change is within reach of its context.
+        let start = changed[k].saturating_sub(CONTEXT);
+        let mut end = changed[k];
+        while k + 1 < changed.len() && changed[k + 1] <= end + 2 * CONTEXT + 1 {
+            k += 1;
+            end = changed[k];
+        }
+        let end = (end + CONTEXT + 1).min(ops.len());
+        k += 1;
+
+        let hunk = &ops[start..end];
+        let old_len = hunk.iter().filter(|op| op.0 != '+').count();
+        let new_len = hunk.iter().filter(|op| op.0 != '-').count();
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            hunk[0].1 + 1,
+            old_len,
+            hunk[0].2 + 1,
+            new_len
+        ));
+        for &(tag, i, j) in hunk {
+            let line = if tag == '+' { b[j] } else { a[i] };
+            out.push(tag);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules;
+
+    #[test]
+    fn test_fix_source_applies_safe_fixes_only() {
+        let rules = rules::all_rules();
+        let source = r#"<input type="checkbox" checked aria-checked="true">
+<h1>A</h1><h3>B</h3>
+<button disabled aria-disabled="false">Go</button>
+"#;
+        let (fixed, applied) = fix_source("", source, FileType::Html, &rules, &Config::default());
+        assert_eq!(
+            fixed,
+            r#"<input type="checkbox" checked>
+<h1>A</h1><h3>B</h3>
+<button disabled>Go</button>
+"#
+        );
+        let ids: Vec<&str> = applied.iter().map(|a| a.rule_id.as_str()).collect();
+        assert_eq!(ids, ["no-redundant-aria-state", "no-contradictory-state"]);
+        assert_eq!(applied[1].line, 3);
+    }
+
+    #[test]
+    fn test_lang_fix_needs_configured_locale() {
+        let rules = rules::all_rules();
+        let source = "<html><head><title>T</title></head><body></body></html>";
+        let (fixed, _) = fix_source("", source, FileType::Html, &rules, &Config::default());
+        assert_eq!(fixed, source);
+
+        let config = Config::parse("[fixes]\nlocale = \"de_ch\"\n");
+        let (fixed, applied) = fix_source("", source, FileType::Html, &rules, &config);
+        assert!(fixed.starts_with(r#"<html lang="de-CH">"#));
+        assert_eq!(applied[0].title, r#"Add lang="de-CH""#);
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "a\nb\nc\nd\nE\nf\ng\nh\ni\nj\n";
+        assert_eq!(
+            unified_diff("x.html", old, new),
+            "--- a/x.html\n+++ b/x.html\n@@ -2,7 +2,7 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n"
+        );
+        assert_eq!(
+            unified_diff("x.html", old, old),
+            "--- a/x.html\n+++ b/x.html\n"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! On-disk cache of diagnostics (and element counts, for [`crate::score`])
+//! for unchanged files.
+//!
+//! Entries are keyed by the file's content, its file type, the effective
+//! config and the rule set, so any of those changing is a miss. There is one
+//! entry per file, grouped by monorepo package (see [`crate::workspace`]).
+//!
+//! Lives in `$WCAG_LSP_CACHE_DIR`, `$XDG_CACHE_HOME/wcag-lsp`,
+//! `%LOCALAPPDATA%\wcag-lsp\cache` or `~/.cache/wcag-lsp`, in that order.
+
+use crate::config::Config;
+use crate::parser::FileType;
+use crate::rules::Rule;
+use crate::workspace::fnv1a;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tower_lsp_server::ls_types::Diagnostic;
+
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("WCAG_LSP_CACHE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME") {
+        return Some(PathBuf::from(dir).join("wcag-lsp"));
+    }
+    if cfg!(target_os = "windows")
+        && let Some(dir) = std::env::var_os("LOCALAPPDATA")
+    {
+        return Some(PathBuf::from(dir).join("wcag-lsp").join("cache"));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("wcag-lsp"))
+}
+
+/// Hash of everything that determines a file's diagnostics.
+pub fn cache_key(
+    source: &str,
+    file_type: FileType,
+    config: &Config,
+    rules: &[Box<dyn Rule>],
+) -> String {
+    let rule_ids: Vec<&str> = rules.iter().map(|r| r.metadata().id).collect();
+    let input = format!(
+        "{}\0{:?}\0{}\0{}\0{}",
+        env!("CARGO_PKG_VERSION"),
+        file_type,
+        config.fingerprint(),
+        rule_ids.join(","),
+        source
+    );
+    format!("{:016x}-{:x}", fnv1a(input.as_bytes()), source.len())
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    key: String,
+    diagnostics: Vec<Diagnostic>,
+    /// Missing in entries written before scores existed, which then miss.
+    #[serde(default)]
+    elements: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache in the default location, if there is one.
+    pub fn open() -> Option<Self> {
+        cache_dir().map(Self::new)
+    }
+
+    fn entry_path(&self, package: &str, file: &Path) -> PathBuf {
+        let name = format!("{:016x}.json", fnv1a(file.to_string_lossy().as_bytes()));
+        self.dir.join(package).join(name)
+    }
+
+    /// The diagnostics and element count stored for `file`, if they were
+    /// computed for `key`.
+    pub fn get(&self, package: &str, file: &Path, key: &str) -> Option<(Vec<Diagnostic>, usize)> {
+        let content = std::fs::read_to_string(self.entry_path(package, file)).ok()?;
+        let entry: Entry = serde_json::from_str(&content).ok()?;
+        if entry.key != key {
+            return None;
+        }
+        Some((entry.diagnostics, entry.elements?))
+    }
+
+    /// Stores `diagnostics` and `elements` for `file`, replacing any older
+    /// entry. Failing to write only costs a re-lint next time, so errors are
+    /// ignored.
+    pub fn put(
+        &self,
+        package: &str,
+        file: &Path,
+        key: &str,
+        diagnostics: &[Diagnostic],
+        elements: usize,
+    ) {
+        let path = self.entry_path(package, file);
+        let entry = Entry {
+            key: key.to_string(),
+            diagnostics: diagnostics.to_vec(),
+            elements: Some(elements),
+        };
+        if let (Some(parent), Ok(json)) = (path.parent(), serde_json::to_string(&entry)) {
+            let _ = std::fs::create_dir_all(parent);
+            let _ = std::fs::write(path, json);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules;
+
+    fn diagnostic(message: &str) -> Diagnostic {
+        Diagnostic {
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_put_then_get() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        let file = Path::new("/repo/index.html");
+
+        assert!(cache.get("pkg", file, "k1").is_none());
+        cache.put("pkg", file, "k1", &[diagnostic("missing alt")], 12);
+
+        let (hit, elements) = cache.get("pkg", file, "k1").unwrap();
+        assert_eq!(hit[0].message, "missing alt");
+        assert_eq!(elements, 12);
+        assert!(cache.get("pkg", file, "k2").is_none(), "stale key");
+        assert!(cache.get("other", file, "k1").is_none(), "other package");
+    }
+
+    #[test]
+    fn test_key_changes_with_inputs() {
+        let rules = rules::all_rules();
+        let config = Config::default();
+        let key = cache_key("<p></p>", FileType::Html, &config, &rules);
+
+        assert_eq!(key, cache_key("<p></p>", FileType::Html, &config, &rules));
+        assert_ne!(key, cache_key("<p> </p>", FileType::Html, &config, &rules));
+        assert_ne!(key, cache_key("<p></p>", FileType::Vue, &config, &rules));
+        let strict = Config::parse("[severity]\nAA = \"error\"\n");
+        assert_ne!(key, cache_key("<p></p>", FileType::Html, &strict, &rules));
+        assert_ne!(
+            key,
+            cache_key("<p></p>", FileType::Html, &config, &rules[1..])
+        );
+    }
+}
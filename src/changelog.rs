@@ -0,0 +1,487 @@
+//! Rule behavior changelog.
+//!
+//! Add an entry to [`RULE_CHANGES`] whenever a release adds a rule or changes
+//! what an existing rule reports. After an update the server compares the
+//! version it last ran as with the current one and shows a one-time summary,
+//! so new diagnostics don't come as a surprise.
+
+use crate::state;
+use std::path::Path;
+
+const LAST_VERSION_FILE: &str = "last-version";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+#[derive(Debug)]
+pub struct RuleChange {
+    /// Release that shipped the change (without a `v` prefix).
+    pub version: &'static str,
+    pub rule_id: &'static str,
+    pub kind: ChangeKind,
+    pub summary: &'static str,
+}
+
+/// Newest entries last.
+pub static RULE_CHANGES: &[RuleChange] = &[
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-required-attr",
+        kind: ChangeKind::Changed,
+        summary: "covers menuitemcheckbox/menuitemradio, skips state native elements already expose",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "no-redundant-aria-state",
+        kind: ChangeKind::Added,
+        summary: "flags aria-required/aria-disabled/aria-checked repeating native state",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "no-contradictory-state",
+        kind: ChangeKind::Added,
+        summary: "errors on disabled/hidden/readonly paired with aria-*=\"false\"",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "composite-focus",
+        kind: ChangeKind::Added,
+        summary: "opt-in hint for JSX composite widgets without focus management",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-owns-valid",
+        kind: ChangeKind::Added,
+        summary: "errors on aria-owns cycles, self-ownership and elements with two owners",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-controls-expanded",
+        kind: ChangeKind::Added,
+        summary: "opt-in warning for buttons/menu items with aria-controls but no aria-expanded",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-roledescription",
+        kind: ChangeKind::Added,
+        summary: "warns on aria-roledescription without a role or repeating the role name",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "list-layout",
+        kind: ChangeKind::Added,
+        summary: "opt-in hint for repeated single-item lists and lists of empty <div>s",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "page-title",
+        kind: ChangeKind::Changed,
+        summary: "checks Nuxt <Head>/<Title> and useHead() titles in Vue files",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "page-title",
+        kind: ChangeKind::Changed,
+        summary: "checks <title>, next/head and Helmet head components, and metadata titles in JSX/TSX",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "html-lang",
+        kind: ChangeKind::Changed,
+        summary: "accepts a lang set with useHead({ htmlAttrs }) in Vue files",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "html-lang",
+        kind: ChangeKind::Changed,
+        summary: "checks <html>, next/document's <Html> and Helmet htmlAttributes in JSX/TSX",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "form-association",
+        kind: ChangeKind::Added,
+        summary: "opt-in warning for submit buttons and text inputs outside any form",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "no-multiple-h1",
+        kind: ChangeKind::Added,
+        summary: "warns on a second top-level <h1> in a full HTML document",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "page-title",
+        kind: ChangeKind::Changed,
+        summary: "flags placeholder titles and titles over [page-title] max-length",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "consistent-nav-label",
+        kind: ChangeKind::Added,
+        summary: "opt-in warning for the same navigation labelled differently across files",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "hidden-field-focus",
+        kind: ChangeKind::Added,
+        summary: "warns about visually hidden fields, such as honeypots, that can still be focused",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "captcha-alternative",
+        kind: ChangeKind::Added,
+        summary: "opt-in information on CAPTCHA widgets, for audits of their alternatives",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "autoplay-controls",
+        kind: ChangeKind::Added,
+        summary: "warns about autoplaying video, muted or not, without controls or a pause button",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "animated-image",
+        kind: ChangeKind::Added,
+        summary: "information on GIF/APNG images in headers and hero sections, for a flashing review",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "reduced-motion",
+        kind: ChangeKind::Added,
+        summary: "warns about infinite CSS animations in <style> without a prefers-reduced-motion query",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "no-outline-removal",
+        kind: ChangeKind::Added,
+        summary: "warns when outline: none removes focus outlines with no other :focus style",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "color-contrast",
+        kind: ChangeKind::Added,
+        summary: "warns about text below 4.5:1 (3:1 if large) where inline styles and <style> set its colors",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "live-region",
+        kind: ChangeKind::Added,
+        summary: "warns about toasts and alerts outside a live region in `lint --dom --dynamic` snapshots",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "svg-interactive",
+        kind: ChangeKind::Added,
+        summary: "flags clickable SVG shapes and `<a>` elements that aren't focusable or have no role",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "math-alt",
+        kind: ChangeKind::Added,
+        summary: "lists `<math>` blocks without `alttext` or a labelled wrapper",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "pdf-alternative",
+        kind: ChangeKind::Added,
+        summary: "warns about PDFs in `<object>`/`<embed>` without a link to the document or an alternative",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "svg-img-alt",
+        kind: ChangeKind::Added,
+        summary: "flags `<svg role=\"img\">` without a name and hints at `aria-hidden` for unnamed SVGs",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "audio-transcript",
+        kind: ChangeKind::Added,
+        summary: "warns about `<audio>` without a transcript or transcript link nearby",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "role-img-alt",
+        kind: ChangeKind::Added,
+        summary: "flags `role=\"img\"` elements without `aria-label` or `aria-labelledby`",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-expanded-controls",
+        kind: ChangeKind::Added,
+        summary: "opt-in: warns about `aria-expanded` without `aria-controls` outside native disclosures and comboboxes",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "td-headers-attr",
+        kind: ChangeKind::Added,
+        summary: "flags `headers` ids that are missing, outside the cell's table or not header cells",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "radio-group-name",
+        kind: ChangeKind::Added,
+        summary: "warns about radios without a name or with a different name from the rest of their group",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "layout-table",
+        kind: ChangeKind::Added,
+        summary: "flags `<th>`, `<caption>`, `summary` and `scope` in tables with `role=\"presentation\"` or `role=\"none\"`",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "table-header",
+        kind: ChangeKind::Changed,
+        summary: "skips layout tables with `role=\"presentation\"` or `role=\"none\"`",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "input-list-valid",
+        kind: ChangeKind::Added,
+        summary: "flags `list` attributes without a matching `<datalist>` and `aria-autocomplete` values that deny one",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "bypass-blocks",
+        kind: ChangeKind::Added,
+        summary: "warns about full HTML pages without a skip link, landmarks or headings",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "output-association",
+        kind: ChangeKind::Added,
+        summary: "warns about `<output>` elements outside a form without `for`",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "progress-meter-name",
+        kind: ChangeKind::Added,
+        summary: "flags `<progress>` and `<meter>` without a name, and `<progress value>` without `max`",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "landmark-structure",
+        kind: ChangeKind::Added,
+        summary: "flags duplicate banner, main and contentinfo landmarks, and top-level landmarks nested in others",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "region",
+        kind: ChangeKind::Added,
+        summary: "flags content of a full HTML page that is outside any landmark",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-toggle-field-name",
+        kind: ChangeKind::Added,
+        summary: "flags checkbox, radio, switch and toggle menu item roles without an accessible name",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-meter-name",
+        kind: ChangeKind::Added,
+        summary: "flags role=\"meter\" elements without an accessible name",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-progressbar-name",
+        kind: ChangeKind::Added,
+        summary: "flags role=\"progressbar\" elements without an accessible name",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-tooltip-name",
+        kind: ChangeKind::Added,
+        summary: "flags role=\"tooltip\" elements without an accessible name",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-treeitem-name",
+        kind: ChangeKind::Added,
+        summary: "flags role=\"treeitem\" elements without an accessible name",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "lang-parts",
+        kind: ChangeKind::Added,
+        summary: "flags empty lang attributes and ones repeating the enclosing language",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-props",
+        kind: ChangeKind::Changed,
+        summary: "suggests the attribute a misspelled aria-* attribute was probably meant to be",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-modal-siblings",
+        kind: ChangeKind::Added,
+        summary: "lists focusable siblings of a modal dialog that aren't inert",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "duplicate-id-aria",
+        kind: ChangeKind::Added,
+        summary: "flags aria-labelledby, for, headers and similar references to duplicated ids",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-errormessage",
+        kind: ChangeKind::Added,
+        summary: "flags aria-errormessage without aria-invalid or pointing at a missing element",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "pagination",
+        kind: ChangeKind::Added,
+        summary: "checks the names of pagination links and that the current page has aria-current",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "aria-activedescendant",
+        kind: ChangeKind::Added,
+        summary: "flags aria-activedescendant pointing at a missing element or on an element that can't take focus",
+    },
+    RuleChange {
+        version: "0.6.0",
+        rule_id: "carousel",
+        kind: ChangeKind::Added,
+        summary: "lists carousels that advance without a pause button or leave slides unlabelled",
+    },
+];
+
+fn parse_version(v: &str) -> Option<semver::Version> {
+    semver::Version::parse(v.strip_prefix('v').unwrap_or(v)).ok()
+}
+
+/// Changes shipped after `from` up to and including `to`.
+pub fn changes_between<'a>(changes: &'a [RuleChange], from: &str, to: &str) -> Vec<&'a RuleChange> {
+    let (Some(from), Some(to)) = (parse_version(from), parse_version(to)) else {
+        return Vec::new();
+    };
+    changes
+        .iter()
+        .filter(|c| parse_version(c.version).is_some_and(|v| v > from && v <= to))
+        .collect()
+}
+
+/// The release in which a rule's behavior last changed, if it ever did.
+pub fn behavior_version(rule_id: &str) -> Option<&'static str> {
+    RULE_CHANGES
+        .iter()
+        .rev()
+        .find(|c| c.rule_id == rule_id)
+        .map(|c| c.version)
+}
+
+/// Link to the release notes (including migration notes) for `version`.
+pub fn release_notes_url(version: &str) -> String {
+    format!(
+        "https://github.com/maxischmaxi/wcag-lsp/releases/tag/v{}",
+        version.strip_prefix('v').unwrap_or(version)
+    )
+}
+
+pub fn summarize(changes: &[&RuleChange], version: &str) -> Option<String> {
+    if changes.is_empty() {
+        return None;
+    }
+
+    let mut lines = vec![format!("wcag-lsp updated to v{version}. Rule changes:")];
+    for change in changes {
+        let kind = match change.kind {
+            ChangeKind::Added => "new",
+            ChangeKind::Changed => "changed",
+            ChangeKind::Removed => "removed",
+        };
+        lines.push(format!("- {} ({kind}): {}", change.rule_id, change.summary));
+    }
+    lines.push(format!("Migration notes: {}", release_notes_url(version)));
+    Some(lines.join("\n"))
+}
+
+/// Records `current` as the last seen version in `dir` and returns the notice
+/// to show, if the previous run was an older version with rule changes since.
+/// The very first run only records the version.
+pub fn pending_notice_in(dir: &Path, changes: &[RuleChange], current: &str) -> Option<String> {
+    let last_seen = state::read(dir, LAST_VERSION_FILE);
+    if last_seen.as_deref() == Some(current) {
+        return None;
+    }
+    let _ = state::write(dir, LAST_VERSION_FILE, current);
+
+    let last_seen = last_seen?;
+    summarize(&changes_between(changes, &last_seen, current), current)
+}
+
+/// [`pending_notice_in`] for the user's state directory and this build.
+pub fn pending_notice() -> Option<String> {
+    let dir = state::state_dir()?;
+    pending_notice_in(&dir, RULE_CHANGES, env!("CARGO_PKG_VERSION"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static CHANGES: &[RuleChange] = &[
+        RuleChange {
+            version: "0.5.0",
+            rule_id: "img-alt",
+            kind: ChangeKind::Changed,
+            summary: "old change",
+        },
+        RuleChange {
+            version: "0.6.0",
+            rule_id: "role-img-alt",
+            kind: ChangeKind::Added,
+            summary: "role=\"img\" needs a name",
+        },
+        RuleChange {
+            version: "0.7.0",
+            rule_id: "img-alt",
+            kind: ChangeKind::Changed,
+            summary: "future change",
+        },
+    ];
+
+    #[test]
+    fn test_changes_between() {
+        let changes = changes_between(CHANGES, "0.5.14", "0.6.0");
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].rule_id, "role-img-alt");
+        assert!(changes_between(CHANGES, "0.6.0", "0.6.0").is_empty());
+        assert!(changes_between(CHANGES, "garbage", "0.6.0").is_empty());
+    }
+
+    #[test]
+    fn test_summarize() {
+        let changes = changes_between(CHANGES, "0.5.14", "0.6.0");
+        let msg = summarize(&changes, "0.6.0").unwrap();
+        assert!(msg.contains("role-img-alt (new)"));
+        assert!(msg.contains("releases/tag/v0.6.0"));
+        assert!(summarize(&[], "0.6.0").is_none());
+    }
+
+    #[test]
+    fn test_pending_notice_first_run_records_version() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(pending_notice_in(dir.path(), CHANGES, "0.6.0").is_none());
+        assert_eq!(
+            state::read(dir.path(), LAST_VERSION_FILE).as_deref(),
+            Some("0.6.0")
+        );
+    }
+
+    #[test]
+    fn test_pending_notice_shown_once_after_update() {
+        let dir = tempfile::tempdir().unwrap();
+        state::write(dir.path(), LAST_VERSION_FILE, "0.5.14").unwrap();
+        assert!(pending_notice_in(dir.path(), CHANGES, "0.6.0").is_some());
+        assert!(pending_notice_in(dir.path(), CHANGES, "0.6.0").is_none());
+    }
+}
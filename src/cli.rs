@@ -1,15 +1,23 @@
 use std::collections::BTreeMap;
-
-use tower_lsp_server::ls_types::DiagnosticSeverity;
-
-use crate::config::Config;
-use crate::document::Document;
-use crate::parser::{self, FileType};
-use crate::rules::{self, Rule};
+use std::path::{Path, PathBuf};
+
+use tower_lsp_server::ls_types::{Diagnostic, DiagnosticSeverity};
+
+use crate::annotate;
+use crate::autofix;
+use crate::cache::Cache;
+use crate::config::{self, Config};
+use crate::engine;
+use crate::nav_index::{self, NavIndex};
+use crate::report::{self, OutputFormat};
+use crate::rules::{self, WcagLevel};
+use crate::scan;
+use crate::score::{self, Score, Tally};
+use crate::workspace::{self, PackageConfigs};
 
 struct FileDiagnostic {
-    line: u32,
-    col: u32,
+    /// `line:column`, or `document` for a finding about the whole file.
+    location: String,
     severity: &'static str,
     message: String,
     rule_id: String,
@@ -20,20 +28,321 @@ pub fn run_check(patterns: &[String]) -> i32 {
 }
 
 pub fn run_check_with_config(patterns: &[String], config_path: Option<&str>) -> i32 {
-    let config = if let Some(path) = config_path {
-        Config::from_file(std::path::Path::new(path))
+    run_check_with_options(
+        patterns,
+        &CheckOptions {
+            config_path,
+            ..Default::default()
+        },
+    )
+}
+
+#[derive(Debug, Default)]
+pub struct CheckOptions<'a> {
+    /// Explicit config file; disables per-package configs.
+    pub config_path: Option<&'a str>,
+    /// Only check files inside this package directory.
+    pub package: Option<&'a Path>,
+    /// Workspace root; defaults to the current directory.
+    pub root: Option<&'a Path>,
+    /// Lint every file even if a cached result exists.
+    pub no_cache: bool,
+    /// Worker threads; defaults to [`scan::default_workers`].
+    pub workers: Option<usize>,
+    pub format: OutputFormat,
+    pub gate: Gate,
+}
+
+/// When a `check` or `lint` run fails: by default, on any error.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Gate {
+    /// Errors allowed; 0 by default.
+    pub max_errors: Option<usize>,
+    /// Warnings allowed; any number by default.
+    pub max_warnings: Option<usize>,
+    /// Only problems of rules up to this level count against the limits:
+    /// Level A rules for A, A and AA rules for AA. The others are still
+    /// reported.
+    pub fail_on_level: Option<WcagLevel>,
+    /// Fail if the accessibility score (see [`crate::score`]) is lower.
+    pub min_score: Option<f64>,
+}
+
+impl Gate {
+    /// Why `results`, scoring `score`, fail the gate. Empty if they pass.
+    fn failures(
+        &self,
+        results: &BTreeMap<String, Vec<Diagnostic>>,
+        rules: &[Box<dyn rules::Rule>],
+        score: &Score,
+    ) -> Vec<String> {
+        let counted: Vec<&Diagnostic> = results
+            .values()
+            .flatten()
+            .filter(|d| {
+                let level = score::rule_level(d, rules);
+                self.fail_on_level
+                    .is_none_or(|max| level.is_none_or(|level| level <= max))
+            })
+            .collect();
+        let errors = counted
+            .iter()
+            .filter(|d| d.severity == Some(DiagnosticSeverity::ERROR))
+            .count();
+        let warnings = counted
+            .iter()
+            .filter(|d| matches!(d.severity, Some(DiagnosticSeverity::WARNING) | None))
+            .count();
+        let levels = match self.fail_on_level {
+            Some(WcagLevel::A) => "Level A ",
+            Some(WcagLevel::AA) => "Level A and AA ",
+            _ => "",
+        };
+
+        let mut failures = Vec::new();
+        let max_errors = self.max_errors.unwrap_or(0);
+        if errors > max_errors {
+            failures.push(format!(
+                "{errors} {levels}{} (at most {max_errors} allowed)",
+                if errors == 1 { "error" } else { "errors" },
+            ));
+        }
+        if let Some(max_warnings) = self.max_warnings
+            && warnings > max_warnings
+        {
+            failures.push(format!(
+                "{warnings} {levels}{} (at most {max_warnings} allowed)",
+                if warnings == 1 { "warning" } else { "warnings" },
+            ));
+        }
+        if let Some(min) = self.min_score
+            && score.score < min
+        {
+            failures.push(format!(
+                "accessibility score {:.1} is below the minimum of {min}",
+                score.score
+            ));
+        }
+        failures
+    }
+}
+
+pub fn run_check_with_options(patterns: &[String], options: &CheckOptions) -> i32 {
+    let (root, config, mut packages) = load_configs(options.root, options.config_path);
+    let rules = rules::all_rules();
+    let cache = if options.no_cache {
+        None
     } else {
-        let cwd = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-        Config::from_dir(&cwd)
+        Cache::open()
     };
+    let root_key = workspace::package_key(&root);
+
+    let files = matched_files(patterns, options.package);
+    let jobs = resolve_jobs(files, &config, &mut packages, &root_key);
+    let workers = options.workers.unwrap_or_else(scan::default_workers);
+
+    // `consistent-nav-label` compares files, so it needs all of them first.
+    let mut nav_index = NavIndex::new();
+    if nav_index::is_enabled(
+        jobs.iter()
+            .map(|(path, _)| packages.get(path).unwrap_or(&config)),
+    ) {
+        scan::for_each_parallel(
+            &jobs,
+            workers,
+            |(path, _)| nav_index::landmarks_in_file(path, packages.get(path).unwrap_or(&config)),
+            |(path, _), landmarks| nav_index.insert(&path.to_string_lossy(), landmarks),
+        );
+    }
+
+    let mut results: BTreeMap<String, Vec<Diagnostic>> = BTreeMap::new();
+    let mut tally = Tally::default();
+
+    scan::for_each_parallel(
+        &jobs,
+        workers,
+        |(path, package_key)| {
+            let config = packages.get(path).unwrap_or(&config);
+            let mut result = scan::lint_file(path, config, &rules, cache.as_ref(), package_key);
+            if let Ok(Some((diagnostics, _))) = &mut result {
+                diagnostics.extend(nav_index.diagnostics_for(&path.to_string_lossy(), config));
+                config.present(diagnostics, config::CLI_CLIENT);
+            }
+            result
+        },
+        |(path, _), result| {
+            let diagnostics = match result {
+                Ok(Some((diagnostics, elements))) => {
+                    tally.add(Tally::of(&diagnostics, elements, &rules));
+                    diagnostics
+                }
+                Ok(None) => return,
+                Err(e) => {
+                    eprintln!("Could not read {}: {}", path.display(), e);
+                    return;
+                }
+            };
+            if !diagnostics.is_empty() {
+                results.insert(path.to_string_lossy().to_string(), diagnostics);
+            }
+        },
+    );
+
+    print_report(&results, options.format, &rules, &tally, &options.gate)
+}
+
+#[derive(Debug, Default)]
+pub struct LintDomOptions<'a> {
+    /// Explicit config file; defaults to the one in the current directory.
+    pub config_path: Option<&'a str>,
+    /// Also run the rules for rendered pages.
+    pub dynamic: bool,
+    pub format: OutputFormat,
+    pub gate: Gate,
+}
+
+/// `wcag-lsp lint --dom`: lints a DOM snapshot saved from a browser (or
+/// read from stdin for `-`). Returns the exit code as `check` does.
+pub fn run_lint_dom(snapshot: &str, options: &LintDomOptions) -> i32 {
+    let (_, config, _) = load_configs(None, options.config_path);
     let rules = rules::all_rules();
+    let source = if snapshot == "-" {
+        let mut source = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut source).map(|_| source)
+    } else {
+        std::fs::read_to_string(snapshot)
+    };
+    let source = match source {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Could not read {snapshot}: {e}");
+            return 1;
+        }
+    };
+
+    let (mut diagnostics, _) =
+        scan::lint_snapshot(snapshot, &source, options.dynamic, &rules, &config);
+    config.present(&mut diagnostics, config::CLI_CLIENT);
+    let elements = scan::count_elements(crate::parser::FileType::Html, &source);
+    let tally = Tally::of(&diagnostics, elements, &rules);
+    let mut results = BTreeMap::new();
+    if !diagnostics.is_empty() {
+        results.insert(snapshot.to_string(), diagnostics);
+    }
+    print_report(&results, options.format, &rules, &tally, &options.gate)
+}
 
-    let mut all_files: Vec<std::path::PathBuf> = Vec::new();
+#[derive(Debug, Default)]
+pub struct AnnotateOptions<'a> {
+    /// Explicit config file; disables per-package configs.
+    pub config_path: Option<&'a str>,
+    /// Where to write the JSON; stdout if `None`.
+    pub out: Option<&'a str>,
+}
+
+/// `wcag-lsp annotate`: exports the role, name and states of each element of
+/// the file at `path` as JSON. Returns the exit code: 1 if it couldn't be
+/// read, isn't a supported file type or the output couldn't be written.
+pub fn run_annotate(path: &str, options: &AnnotateOptions) -> i32 {
+    let (_, config, mut packages) = load_configs(None, options.config_path);
+    packages.load(Path::new(path));
+    let config = packages.get(Path::new(path)).unwrap_or(&config);
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Could not read {path}: {e}");
+            return 1;
+        }
+    };
+    let file_type = config.file_type_for(path, None, &source);
+    let Some(doc) = scan::document(path, &source, file_type, false) else {
+        eprintln!("{path} isn't a file type wcag-lsp can parse");
+        return 1;
+    };
+    let annotations = annotate::annotate(&doc, config);
+    let json = serde_json::to_string_pretty(&annotate::json(path, &annotations))
+        .expect("annotations serialize");
+    match options.out {
+        Some(out) => {
+            if let Err(e) = std::fs::write(out, json + "\n") {
+                eprintln!("Could not write {out}: {e}");
+                return 1;
+            }
+            eprintln!("Wrote {} elements to {out}", annotations.len());
+        }
+        None => println!("{json}"),
+    }
+    0
+}
+
+/// Prints `results` and the score of `tally` in `format` and returns the
+/// exit code: 1 if they fail `gate`.
+fn print_report(
+    results: &BTreeMap<String, Vec<Diagnostic>>,
+    format: OutputFormat,
+    rules: &[Box<dyn rules::Rule>],
+    tally: &Tally,
+    gate: &Gate,
+) -> i32 {
+    let all = || results.values().flatten();
+    let total_errors = all()
+        .filter(|d| d.severity == Some(DiagnosticSeverity::ERROR))
+        .count();
+    let total_warnings = all().count() - total_errors;
+    let score = tally.score();
+
+    match format {
+        OutputFormat::Text => {
+            print_results(results, total_errors, total_warnings);
+            // A clean run stays quiet unless a threshold was asked for.
+            if !results.is_empty() || gate.min_score.is_some() {
+                print_score(&score);
+            }
+        }
+        OutputFormat::Json => println!("{:#}", report::json(results, rules, &score)),
+        OutputFormat::Sarif => println!("{:#}", report::sarif(results, rules, &score)),
+        OutputFormat::Html => print!("{}", report::html(results, rules, &score)),
+    }
+
+    let failures = gate.failures(results, rules, &score);
+    // Without a policy, failing on errors needs no explanation.
+    if *gate != Gate::default() {
+        for failure in &failures {
+            eprintln!("Failed: {failure}");
+        }
+    }
+
+    if failures.is_empty() { 0 } else { 1 }
+}
+
+/// The workspace root, its config and the per-package configs below it.
+fn load_configs(
+    root: Option<&Path>,
+    config_path: Option<&str>,
+) -> (PathBuf, Config, PackageConfigs) {
+    let root = root
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."));
+    if let Some(path) = config_path {
+        let config = Config::from_file(Path::new(path));
+        (root, config, PackageConfigs::new(None))
+    } else {
+        let config = Config::from_dir(&root);
+        let packages = PackageConfigs::new(Some(root.clone()));
+        (root, config, packages)
+    }
+}
+
+/// Files matching `patterns`, without duplicates, limited to `package` if
+/// given.
+fn matched_files(patterns: &[String], package: Option<&Path>) -> Vec<PathBuf> {
+    let mut all_files: Vec<PathBuf> = Vec::new();
     for pattern in patterns {
         match glob::glob(pattern) {
             Ok(paths) => {
                 for entry in paths.flatten() {
-                    if !all_files.contains(&entry) {
+                    if !entry.is_dir() && !all_files.contains(&entry) {
                         all_files.push(entry);
                     }
                 }
@@ -44,109 +353,174 @@ pub fn run_check_with_config(patterns: &[String], config_path: Option<&str>) ->
         }
     }
 
-    // Apply ignore patterns
-    all_files.retain(|path| {
+    if let Some(package) = package {
+        let package = std::path::absolute(package).unwrap_or_else(|_| package.to_path_buf());
+        all_files.retain(|path| std::path::absolute(path).is_ok_and(|p| p.starts_with(&package)));
+    }
+    all_files
+}
+
+/// Loads the package config of every file and drops ignored files. Returns
+/// each remaining file with its package's cache key.
+///
+/// Resolving packages touches shared state, so it happens up front; the
+/// workers then only read it.
+fn resolve_jobs(
+    files: Vec<PathBuf>,
+    config: &Config,
+    packages: &mut PackageConfigs,
+    root_key: &str,
+) -> Vec<(PathBuf, String)> {
+    let mut jobs: Vec<(PathBuf, String)> = Vec::new();
+    for path in files {
+        packages.load(&path);
+        let config = packages.get(&path).unwrap_or(config);
         let path_str = path.to_string_lossy();
-        !config
+        if config
             .ignore_patterns
             .iter()
             .any(|pat| glob_match::glob_match(pat, &path_str))
-    });
-
-    let mut results: BTreeMap<String, Vec<FileDiagnostic>> = BTreeMap::new();
-    let mut total_errors: usize = 0;
-    let mut total_warnings: usize = 0;
-
-    for path in &all_files {
-        let ext = match path.extension().and_then(|e| e.to_str()) {
-            Some(e) => e,
-            None => continue,
-        };
-
-        let file_type = FileType::from_extension(ext);
-        if file_type == FileType::Unknown {
+        {
             continue;
         }
+        let package_key = packages
+            .root_of(&path)
+            .map(workspace::package_key)
+            .unwrap_or_else(|| root_key.to_string());
+        jobs.push((path, package_key));
+    }
+    jobs
+}
 
-        let source = match std::fs::read_to_string(path) {
-            Ok(s) => s,
-            Err(e) => {
-                eprintln!("Could not read {}: {}", path.display(), e);
-                continue;
-            }
-        };
+#[derive(Debug, Default)]
+pub struct FixCommandOptions<'a> {
+    /// Explicit config file; disables per-package configs.
+    pub config_path: Option<&'a str>,
+    /// Only fix files inside this package directory.
+    pub package: Option<&'a Path>,
+    /// Workspace root; defaults to the current directory.
+    pub root: Option<&'a Path>,
+    /// Print a unified diff instead of writing the files.
+    pub diff: bool,
+}
 
-        let diagnostics = lint_source(&source, file_type, &rules, &config);
-        if diagnostics.is_empty() {
-            continue;
-        }
+/// A file changed by `fix`: its old and new content and the fixes applied.
+type FixedFile = (String, String, Vec<autofix::Applied>);
 
-        let path_str = path.to_string_lossy().to_string();
-        for diag in diagnostics {
-            if diag.severity == "error" {
-                total_errors += 1;
-            } else {
-                total_warnings += 1;
+/// `wcag-lsp fix`: applies every safe quick fix to the matching files.
+/// Returns the exit code: 1 if a file couldn't be read or written.
+pub fn run_fix(patterns: &[String], options: &FixCommandOptions) -> i32 {
+    let (root, config, mut packages) = load_configs(options.root, options.config_path);
+    let rules = rules::all_rules();
+    let root_key = workspace::package_key(&root);
+    let files = matched_files(patterns, options.package);
+    let jobs = resolve_jobs(files, &config, &mut packages, &root_key);
+
+    let mut failed = false;
+    let mut fixed_files: BTreeMap<String, Vec<autofix::Applied>> = BTreeMap::new();
+    let mut diffs: BTreeMap<String, String> = BTreeMap::new();
+
+    scan::for_each_parallel(
+        &jobs,
+        scan::default_workers(),
+        |(path, _)| -> std::io::Result<Option<FixedFile>> {
+            let config = packages.get(path).unwrap_or(&config);
+            let source = std::fs::read_to_string(path)?;
+            let uri = path.to_string_lossy();
+            let file_type = config.file_type_for(&uri, None, &source);
+            if file_type == crate::parser::FileType::Unknown {
+                return Ok(None);
             }
-            results.entry(path_str.clone()).or_default().push(diag);
-        }
-    }
+            let (fixed, applied) = autofix::fix_source(&uri, &source, file_type, &rules, config);
+            if applied.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some((source, fixed, applied)))
+        },
+        |(path, _), result| {
+            let (source, fixed, applied) = match result {
+                Ok(Some(changed)) => changed,
+                Ok(None) => return,
+                Err(e) => {
+                    eprintln!("Could not read {}: {}", path.display(), e);
+                    failed = true;
+                    return;
+                }
+            };
+            let path_str = path.to_string_lossy().to_string();
+            if options.diff {
+                diffs.insert(
+                    path_str.clone(),
+                    autofix::unified_diff(&path_str, &source, &fixed),
+                );
+            } else if let Err(e) = std::fs::write(path, &fixed) {
+                eprintln!("Could not write {}: {}", path.display(), e);
+                failed = true;
+                return;
+            }
+            fixed_files.insert(path_str, applied);
+        },
+    );
 
-    print_results(&results, total_errors, total_warnings);
+    for diff in diffs.values() {
+        print!("{diff}");
+    }
+    print_fixes(&fixed_files, options.diff);
 
-    if total_errors > 0 { 1 } else { 0 }
+    if failed { 1 } else { 0 }
 }
 
-fn lint_source(
-    source: &str,
-    file_type: FileType,
-    rules: &[Box<dyn Rule>],
-    config: &Config,
-) -> Vec<FileDiagnostic> {
-    let mut parser = match parser::create_parser(file_type) {
-        Some(p) => p,
-        None => return vec![],
-    };
-
-    let tree = match parser.parse(source, None) {
-        Some(t) => t,
-        None => return vec![],
-    };
+fn print_fixes(fixed_files: &BTreeMap<String, Vec<autofix::Applied>>, dry_run: bool) {
+    if fixed_files.is_empty() {
+        eprintln!("Nothing to fix");
+        return;
+    }
 
-    let doc = Document {
-        uri: String::new(),
-        file_type,
-        source: source.to_string(),
-        tree,
-        version: 0,
-    };
+    let mut per_rule: BTreeMap<&str, usize> = BTreeMap::new();
+    for (path, applied) in fixed_files {
+        eprintln!("\n{}", path);
+        for fix in applied {
+            eprintln!("  {}  {}  {}", fix.line, fix.title, fix.rule_id);
+            *per_rule.entry(&fix.rule_id).or_default() += 1;
+        }
+    }
 
-    let diagnostics = crate::engine::run_diagnostics(&doc, rules, config);
+    let total: usize = per_rule.values().sum();
+    let files = fixed_files.len();
+    eprintln!(
+        "\n\u{2714} {} {} {} in {} {}",
+        if dry_run { "Would fix" } else { "Fixed" },
+        total,
+        if total == 1 { "problem" } else { "problems" },
+        files,
+        if files == 1 { "file" } else { "files" },
+    );
+    for (rule_id, count) in per_rule {
+        eprintln!("  {rule_id}: {count}");
+    }
+}
 
-    diagnostics
-        .into_iter()
-        .map(|d| {
-            let severity = match d.severity {
-                Some(DiagnosticSeverity::ERROR) => "error",
-                _ => "warning",
-            };
-            let rule_id = match &d.code {
-                Some(tower_lsp_server::ls_types::NumberOrString::String(s)) => s.clone(),
-                _ => String::new(),
-            };
-            FileDiagnostic {
-                line: d.range.start.line + 1,
-                col: d.range.start.character + 1,
-                severity,
-                message: d.message,
-                rule_id,
-            }
-        })
-        .collect()
+fn to_file_diagnostic(d: Diagnostic) -> FileDiagnostic {
+    let severity = report::severity_name(d.severity);
+    let rule_id = match &d.code {
+        Some(tower_lsp_server::ls_types::NumberOrString::String(s)) => s.clone(),
+        _ => String::new(),
+    };
+    let location = if engine::is_document_level(&d) {
+        "document".to_string()
+    } else {
+        format!("{}:{}", d.range.start.line + 1, d.range.start.character + 1)
+    };
+    FileDiagnostic {
+        location,
+        severity,
+        message: d.message,
+        rule_id,
+    }
 }
 
 fn print_results(
-    results: &BTreeMap<String, Vec<FileDiagnostic>>,
+    results: &BTreeMap<String, Vec<Diagnostic>>,
     total_errors: usize,
     total_warnings: usize,
 ) {
@@ -156,10 +530,13 @@ fn print_results(
 
     for (path, diags) in results {
         eprintln!("\n{}", path);
-        for d in diags {
+        // Findings about the whole file come first.
+        let (document, elements): (Vec<_>, Vec<_>) =
+            diags.iter().cloned().partition(engine::is_document_level);
+        for d in document.into_iter().chain(elements).map(to_file_diagnostic) {
             eprintln!(
-                "  {}:{}  {}  {}  {}",
-                d.line, d.col, d.severity, d.message, d.rule_id
+                "  {}  {}  {}  {}",
+                d.location, d.severity, d.message, d.rule_id
             );
         }
     }
@@ -180,10 +557,47 @@ fn print_results(
     );
 }
 
+fn print_score(score: &Score) {
+    eprintln!(
+        "Accessibility score: {:.1}/100 ({} weighted {} in {} {}, {} {})",
+        score.score,
+        score.weighted_problems,
+        if score.weighted_problems == 1.0 {
+            "problem"
+        } else {
+            "problems"
+        },
+        score.elements,
+        if score.elements == 1 {
+            "element"
+        } else {
+            "elements"
+        },
+        score.files,
+        if score.files == 1 { "file" } else { "files" },
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::parser::FileType;
+    use crate::rules::Rule;
+
+    fn lint_source(
+        source: &str,
+        file_type: FileType,
+        rules: &[Box<dyn Rule>],
+        config: &Config,
+    ) -> Vec<FileDiagnostic> {
+        scan::lint_source("", source, file_type, rules, config)
+            .0
+            .into_iter()
+            .map(to_file_diagnostic)
+            .collect()
+    }
+
     #[test]
     fn test_file_with_violations_returns_exit_1() {
         let dir = tempfile::tempdir().unwrap();
@@ -202,7 +616,7 @@ mod tests {
         let file_path = dir.path().join("good.html");
         std::fs::write(
             &file_path,
-            r#"<html lang="en"><head><title>Test</title></head><body><img src="x.jpg" alt="A cat"></body></html>"#,
+            r#"<html lang="en"><head><title>Test</title></head><body><main><img src="x.jpg" alt="A cat"></main></body></html>"#,
         )
         .unwrap();
 
@@ -212,6 +626,271 @@ mod tests {
         assert_eq!(code, 0);
     }
 
+    #[test]
+    fn test_unusual_extension_is_sniffed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("header.inc"),
+            "<!DOCTYPE html>\n<img src=\"logo.png\">",
+        )
+        .unwrap();
+
+        let pattern = dir.path().join("*.inc").to_string_lossy().to_string();
+        let code = run_check(&[pattern]);
+
+        assert_eq!(code, 1);
+    }
+
+    /// `root/` with `packages/strict` (own config upgrading AA to error) and
+    /// `packages/loose` (no config), each containing an AA-only violation.
+    fn monorepo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("package.json"), "{}").unwrap();
+        for name in ["strict", "loose"] {
+            let pkg = root.join("packages").join(name);
+            std::fs::create_dir_all(&pkg).unwrap();
+            std::fs::write(pkg.join("package.json"), "{}").unwrap();
+            std::fs::write(pkg.join("a.tsx"), "const A = () => <h1></h1>;").unwrap();
+        }
+        std::fs::write(
+            root.join("packages/strict/.wcag.toml"),
+            "[severity]\nAA = \"error\"\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_package_config_applies() {
+        let dir = monorepo();
+        let root = dir.path();
+        let check = |package: &str| {
+            let pattern = root.join("packages/*/a.tsx").to_string_lossy().to_string();
+            run_check_with_options(
+                &[pattern],
+                &CheckOptions {
+                    package: Some(&root.join("packages").join(package)),
+                    root: Some(root),
+                    ..Default::default()
+                },
+            )
+        };
+        // heading-content is AA: an error only under the strict package config.
+        assert_eq!(check("strict"), 1);
+        assert_eq!(check("loose"), 0);
+    }
+
+    #[test]
+    fn test_explicit_config_overrides_package_configs() {
+        let dir = monorepo();
+        let root = dir.path();
+        let config = root.join("loose.toml");
+        std::fs::write(&config, "").unwrap();
+        let pattern = root
+            .join("packages/strict/a.tsx")
+            .to_string_lossy()
+            .to_string();
+        let code = run_check_with_options(
+            &[pattern],
+            &CheckOptions {
+                config_path: Some(&config.to_string_lossy()),
+                root: Some(root),
+                ..Default::default()
+            },
+        );
+        assert_eq!(code, 0);
+    }
+
+    #[test]
+    fn test_cli_client_severity_mapping() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.tsx"), "const A = () => <h1></h1>;").unwrap();
+        let pattern = dir.path().join("*.tsx").to_string_lossy().to_string();
+        let check = |config: &str| {
+            std::fs::write(dir.path().join(".wcag.toml"), config).unwrap();
+            run_check_with_options(
+                std::slice::from_ref(&pattern),
+                &CheckOptions {
+                    root: Some(dir.path()),
+                    no_cache: true,
+                    ..Default::default()
+                },
+            )
+        };
+        assert_eq!(check(""), 0);
+        assert_eq!(check("[clients.editor]\nwarning = \"error\"\n"), 0);
+        assert_eq!(check("[clients.cli]\nwarning = \"error\"\n"), 1);
+    }
+
+    #[test]
+    fn test_nav_labels_compared_across_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let nav = |label: &str| {
+            format!(
+                r#"export const Nav = () => <nav aria-label="{label}"><a href="/">Home</a><a href="/blog">Blog</a></nav>;"#
+            )
+        };
+        std::fs::write(dir.path().join("Header.tsx"), nav("Main")).unwrap();
+        std::fs::write(
+            dir.path().join(".wcag.toml"),
+            "[rules]\nconsistent-nav-label = \"error\"\n",
+        )
+        .unwrap();
+        let pattern = dir.path().join("*.tsx").to_string_lossy().to_string();
+        let check = || {
+            run_check_with_options(
+                std::slice::from_ref(&pattern),
+                &CheckOptions {
+                    root: Some(dir.path()),
+                    no_cache: true,
+                    ..Default::default()
+                },
+            )
+        };
+
+        std::fs::write(dir.path().join("Footer.tsx"), nav("main")).unwrap();
+        assert_eq!(check(), 0);
+        std::fs::write(dir.path().join("Footer.tsx"), nav("Site")).unwrap();
+        assert_eq!(check(), 1);
+    }
+
+    #[test]
+    fn test_no_cache_gives_same_result() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("bad.html"), r#"<img src="photo.jpg">"#).unwrap();
+        let pattern = dir.path().join("*.html").to_string_lossy().to_string();
+        let check = |no_cache| {
+            run_check_with_options(
+                std::slice::from_ref(&pattern),
+                &CheckOptions {
+                    root: Some(dir.path()),
+                    no_cache,
+                    ..Default::default()
+                },
+            )
+        };
+        assert_eq!(check(true), 1);
+        assert_eq!(check(false), 1);
+        assert_eq!(check(false), 1, "cached run");
+    }
+
+    #[test]
+    fn test_fix_writes_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.html");
+        std::fs::write(
+            &file,
+            "<button disabled aria-disabled=\"false\">Go</button>\n",
+        )
+        .unwrap();
+        let pattern = dir.path().join("*.html").to_string_lossy().to_string();
+        let run = |diff| {
+            run_fix(
+                std::slice::from_ref(&pattern),
+                &FixCommandOptions {
+                    root: Some(dir.path()),
+                    diff,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(run(true), 0);
+        assert!(
+            std::fs::read_to_string(&file)
+                .unwrap()
+                .contains("aria-disabled"),
+            "--diff leaves the file alone"
+        );
+
+        assert_eq!(run(false), 0);
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "<button disabled>Go</button>\n"
+        );
+    }
+
+    #[test]
+    fn test_min_score() {
+        let dir = tempfile::tempdir().unwrap();
+        // An empty heading is an AA warning (2 × 0.5) in 20 elements, so a
+        // score of 20 / (20 + 10) = 66.7.
+        let items = "<li>a</li>".repeat(14);
+        std::fs::write(
+            dir.path().join("a.html"),
+            format!(
+                r#"<html lang="en"><head><title>T</title></head><body><main><h1></h1><ul>{items}</ul></main></body></html>"#
+            ),
+        )
+        .unwrap();
+        let pattern = dir.path().join("*.html").to_string_lossy().to_string();
+        let check = |min_score| {
+            run_check_with_options(
+                std::slice::from_ref(&pattern),
+                &CheckOptions {
+                    root: Some(dir.path()),
+                    no_cache: true,
+                    gate: Gate {
+                        min_score,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+        };
+        assert_eq!(check(None), 0);
+        assert_eq!(check(Some(66.0)), 0);
+        assert_eq!(check(Some(70.0)), 1);
+    }
+
+    #[test]
+    fn test_gate_limits_and_levels() {
+        let rules = rules::all_rules();
+        let diagnostic = |code: &str, severity| Diagnostic {
+            code: Some(tower_lsp_server::ls_types::NumberOrString::String(
+                code.to_string(),
+            )),
+            severity: Some(severity),
+            ..Default::default()
+        };
+        // img-alt is Level A, heading-content AA, reduced-motion AAA.
+        let results = BTreeMap::from([(
+            "a.html".to_string(),
+            vec![
+                diagnostic("img-alt", DiagnosticSeverity::WARNING),
+                diagnostic("heading-content", DiagnosticSeverity::WARNING),
+                diagnostic("reduced-motion", DiagnosticSeverity::ERROR),
+                diagnostic("math-alt", DiagnosticSeverity::INFORMATION),
+            ],
+        )]);
+        let score = Tally::default().score();
+        let failures = |gate: Gate| gate.failures(&results, &rules, &score);
+
+        assert_eq!(failures(Gate::default()), ["1 error (at most 0 allowed)"]);
+        let gate = Gate {
+            max_errors: Some(1),
+            max_warnings: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(failures(gate), ["2 warnings (at most 1 allowed)"]);
+        let gate = Gate {
+            fail_on_level: Some(WcagLevel::AA),
+            max_warnings: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            failures(gate),
+            ["2 Level A and AA warnings (at most 1 allowed)"]
+        );
+        let gate = Gate {
+            fail_on_level: Some(WcagLevel::A),
+            max_warnings: Some(1),
+            ..Default::default()
+        };
+        assert!(failures(gate).is_empty());
+    }
+
     #[test]
     fn test_no_matching_files_returns_exit_0() {
         let dir = tempfile::tempdir().unwrap();
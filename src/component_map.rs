@@ -0,0 +1,505 @@
+//! Components and custom elements configured to stand for native elements
+//! (`[components]`).
+//!
+//! The rules recognize elements by their tag and attribute names, so a JSX
+//! `<MyButton>` or a web component `<fancy-button>` that renders a `<button>`
+//! goes unchecked. [`map`] writes a copy of the file with every configured
+//! component renamed to its element and its attribute aliases (`imageAlt` →
+//! `alt`) renamed to the attributes they set, and parses it. The engine runs the rules over that copy and moves
+//! their diagnostics back with [`MappedSource::remap`]. Only names change, and
+//! never across lines, so a position only moves along its line.
+
+use crate::config::ComponentMapping;
+use crate::parser::FileType;
+use crate::quickfix;
+use crate::scan;
+use std::collections::HashMap;
+use tower_lsp_server::ls_types::{Diagnostic, Position, Range};
+use tree_sitter::{Node, Tree};
+
+/// A name replaced in the copy.
+#[derive(Debug)]
+struct Rename {
+    line: u32,
+    /// Column of the name in the file, and in the copy.
+    column: u32,
+    mapped_column: u32,
+    /// Length of the name in the file, and of what replaced it.
+    from: u32,
+    to: u32,
+}
+
+/// The file with its components renamed, and its parse.
+#[derive(Debug)]
+pub struct MappedSource {
+    pub source: String,
+    pub tree: Tree,
+    /// In document order.
+    renames: Vec<Rename>,
+}
+
+/// The file with `components` renamed to their elements, or `None` if it
+/// uses none of them. In markup, where tag names are case-insensitive, so is
+/// the match. With `styled`, the components a JSX file declares with
+/// styled-components or Emotion count as well, unless configured otherwise.
+pub fn map(
+    file_type: FileType,
+    tree: &Tree,
+    source: &str,
+    components: &HashMap<String, ComponentMapping>,
+    styled: bool,
+) -> Option<MappedSource> {
+    let mut replacements = Vec::new();
+    if file_type.is_jsx_like() {
+        let styled = match styled {
+            true => styled_components(&tree.root_node(), source),
+            false => HashMap::new(),
+        };
+        if components.is_empty() && styled.is_empty() {
+            return None;
+        }
+        let lookup =
+            |name: &str| lookup(components, name, false).or_else(|| styled.get(name).cloned());
+        collect_jsx(&tree.root_node(), source, &lookup, &mut replacements);
+    } else {
+        if components.is_empty() {
+            return None;
+        }
+        let lookup = |name: &str| lookup(components, name, true);
+        collect_html(&tree.root_node(), source, &lookup, &mut replacements);
+    }
+    if replacements.is_empty() {
+        return None;
+    }
+
+    let mut mapped = String::with_capacity(source.len());
+    let mut renames: Vec<Rename> = Vec::new();
+    let mut last = 0;
+    for (node, name) in replacements {
+        mapped.push_str(&source[last..node.start_byte()]);
+        mapped.push_str(&name);
+        last = node.end_byte();
+        let start = node.start_position();
+        let (line, column) = (start.row as u32, start.column as u32);
+        // Earlier renames on the line moved this one.
+        let shift: i64 = renames
+            .iter()
+            .filter(|r| r.line == line)
+            .map(|r| r.to as i64 - r.from as i64)
+            .sum();
+        renames.push(Rename {
+            line,
+            column,
+            mapped_column: (column as i64 + shift) as u32,
+            from: node.byte_range().len() as u32,
+            to: name.len() as u32,
+        });
+    }
+    mapped.push_str(&source[last..]);
+
+    let tree = scan::parse(file_type, &mapped)?;
+    Some(MappedSource {
+        source: mapped,
+        tree,
+        renames,
+    })
+}
+
+/// The mapping of the component `name`: its entry, or else the most specific
+/// entry with a `*` that matches it. A `"*"` element is what the `*` matched,
+/// lowercased, so `"motion.*" = "*"` makes `<motion.div>` a `<div>`.
+/// `ignore_case` is for markup, where tag names are case-insensitive.
+fn lookup(
+    components: &HashMap<String, ComponentMapping>,
+    name: &str,
+    ignore_case: bool,
+) -> Option<ComponentMapping> {
+    let exact = components.get(name).or_else(|| {
+        components
+            .iter()
+            .find(|(component, _)| ignore_case && component.eq_ignore_ascii_case(name))
+            .map(|(_, mapping)| mapping)
+    });
+    if let Some(mapping) = exact {
+        return Some(mapping.clone());
+    }
+
+    let fold = |s: &str| match ignore_case {
+        true => s.to_ascii_lowercase(),
+        false => s.to_string(),
+    };
+    let folded = fold(name);
+    components
+        .iter()
+        .filter_map(|(pattern, mapping)| {
+            let (prefix, suffix) = pattern.split_once('*')?;
+            let (prefix, suffix) = (fold(prefix), fold(suffix));
+            let matched = folded
+                .strip_prefix(&prefix)?
+                .strip_suffix(&suffix)
+                .filter(|m| !m.is_empty())?;
+            let element = match mapping.element.as_str() {
+                "*" => {
+                    let element = matched.to_ascii_lowercase();
+                    let valid = element.starts_with(|c: char| c.is_ascii_alphabetic())
+                        && element
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '-');
+                    valid.then_some(element)?
+                }
+                element => element.to_string(),
+            };
+            let mapping = ComponentMapping {
+                element,
+                attributes: mapping.attributes.clone(),
+            };
+            Some((prefix.len() + suffix.len(), pattern, mapping))
+        })
+        // The longest fixed part wins, and the first pattern among equals.
+        .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(a.1)))
+        .map(|(_, _, mapping)| mapping)
+}
+
+/// The components declared with styled-components or Emotion
+/// (`` const Button = styled.button`…` ``, `styled("a")`,
+/// `styled.input.attrs(…)`), mapped to the element they style.
+fn styled_components(node: &Node, source: &str) -> HashMap<String, ComponentMapping> {
+    let mut components = HashMap::new();
+    let mut stack = vec![*node];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "variable_declarator"
+            && let Some(name) = node.child_by_field_name("name")
+            && name.kind() == "identifier"
+            && let Some(element) = node
+                .child_by_field_name("value")
+                .and_then(|value| styled_element(&value, source))
+        {
+            components.insert(
+                source[name.byte_range()].to_string(),
+                ComponentMapping {
+                    element: element.to_string(),
+                    attributes: HashMap::new(),
+                },
+            );
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+    components
+}
+
+/// The native element a `styled` expression styles. `styled(Button)` styles
+/// a component, and gives `None`.
+fn styled_element<'s>(node: &Node, source: &'s str) -> Option<&'s str> {
+    let element = match node.kind() {
+        "call_expression" => {
+            let function = node.child_by_field_name("function")?;
+            if &source[function.byte_range()] != "styled" {
+                return styled_element(&function, source);
+            }
+            let argument = node.child_by_field_name("arguments")?.named_child(0)?;
+            (argument.kind() == "string")
+                .then(|| source[argument.byte_range()].trim_matches(['"', '\'']))?
+        }
+        "member_expression" => {
+            let object = node.child_by_field_name("object")?;
+            if &source[object.byte_range()] != "styled" {
+                return styled_element(&object, source);
+            }
+            &source[node.child_by_field_name("property")?.byte_range()]
+        }
+        _ => return None,
+    };
+    element
+        .starts_with(|c: char| c.is_ascii_lowercase())
+        .then_some(element)
+}
+
+/// The tag names of mapped components and their aliased attribute names
+/// under `node`, in document order, with what replaces them.
+fn collect_jsx<'a>(
+    node: &Node<'a>,
+    source: &str,
+    lookup: &dyn Fn(&str) -> Option<ComponentMapping>,
+    replacements: &mut Vec<(Node<'a>, String)>,
+) {
+    if matches!(
+        node.kind(),
+        "jsx_opening_element" | "jsx_self_closing_element" | "jsx_closing_element"
+    ) && let Some(name) = node.child_by_field_name("name")
+        && let Some(mapping) = lookup(&source[name.byte_range()])
+    {
+        replacements.push((name, mapping.element.clone()));
+        let mut cursor = node.walk();
+        for attr in node.named_children(&mut cursor) {
+            if attr.kind() != "jsx_attribute" {
+                continue;
+            }
+            if let Some(attr_name) = attr.named_child(0)
+                && let Some(to) = mapping.attributes.get(&source[attr_name.byte_range()])
+            {
+                replacements.push((attr_name, to.clone()));
+            }
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_jsx(&child, source, lookup, replacements);
+    }
+}
+
+/// Like [`collect_jsx`], for the tags of an HTML tree.
+fn collect_html<'a>(
+    node: &Node<'a>,
+    source: &str,
+    lookup: &dyn Fn(&str) -> Option<ComponentMapping>,
+    replacements: &mut Vec<(Node<'a>, String)>,
+) {
+    if matches!(node.kind(), "start_tag" | "self_closing_tag" | "end_tag") {
+        let mut cursor = node.walk();
+        let children: Vec<Node<'a>> = node.named_children(&mut cursor).collect();
+        let Some(mapping) = children
+            .iter()
+            .find(|child| child.kind() == "tag_name")
+            .and_then(|name| lookup(&source[name.byte_range()]))
+        else {
+            return;
+        };
+        for child in children {
+            match child.kind() {
+                "tag_name" => replacements.push((child, mapping.element.clone())),
+                "attribute" => {
+                    if let Some(attr_name) = child.named_child(0)
+                        && let Some(to) = mapping.attributes.get(&source[attr_name.byte_range()])
+                    {
+                        replacements.push((attr_name, to.clone()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_html(&child, source, lookup, replacements);
+    }
+}
+
+impl MappedSource {
+    /// Moves a diagnostic on the copy to the file. Its quick fixes are dropped
+    /// if they edit a renamed name, since they'd be written for the element.
+    pub fn remap(&self, diagnostic: &mut Diagnostic) {
+        diagnostic.range = self.range(diagnostic.range);
+        for info in diagnostic.related_information.iter_mut().flatten() {
+            info.location.range = self.range(info.location.range);
+        }
+
+        let mut fixes = quickfix::fixes(diagnostic);
+        if fixes.is_empty() {
+            return;
+        }
+        let touches_rename = fixes
+            .iter()
+            .flat_map(|fix| &fix.edits)
+            .any(|edit| self.touches_rename(edit.range));
+        diagnostic.data = None;
+        if touches_rename {
+            return;
+        }
+        for fix in &mut fixes {
+            for edit in &mut fix.edits {
+                edit.range = self.range(edit.range);
+            }
+        }
+        for fix in fixes {
+            quickfix::attach(diagnostic, fix);
+        }
+    }
+
+    /// The range in the file of `range` in the copy.
+    pub fn range(&self, range: Range) -> Range {
+        Range {
+            start: self.position(range.start),
+            end: self.position(range.end),
+        }
+    }
+
+    /// The position in the file of `position` in the copy. One inside a
+    /// renamed name lands in the original name, clamped to its end.
+    fn position(&self, position: Position) -> Position {
+        let mut character = position.character;
+        let renames = self.renames.iter().filter(|r| r.line == position.line);
+        for rename in renames.rev() {
+            if position.character >= rename.mapped_column + rename.to {
+                character = position.character - rename.mapped_column - rename.to
+                    + rename.column
+                    + rename.from;
+                break;
+            }
+            if position.character >= rename.mapped_column {
+                let offset = (position.character - rename.mapped_column).min(rename.from);
+                character = rename.column + offset;
+                break;
+            }
+        }
+        Position::new(position.line, character)
+    }
+
+    /// Whether `range` in the copy overlaps a renamed name.
+    /// Whether `range` in the copy overlaps a renamed name.
+    pub fn touches_rename(&self, range: Range) -> bool {
+        self.renames.iter().any(|rename| {
+            let start = (rename.line, rename.mapped_column);
+            let end = (rename.line, rename.mapped_column + rename.to);
+            position_key(range.start) <= end && start <= position_key(range.end)
+        })
+    }
+}
+
+fn position_key(position: Position) -> (u32, u32) {
+    (position.line, position.character)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn mapped(source: &str) -> MappedSource {
+        let config = Config::parse(
+            r#"
+[components]
+Btn = "button"
+"UI.Image" = { element = "img", attributes = { imageAlt = "alt", imageSrc = "src" } }
+"#,
+        );
+        let tree = scan::parse(FileType::Tsx, source).unwrap();
+        map(FileType::Tsx, &tree, source, &config.components, false).unwrap()
+    }
+
+    #[test]
+    fn test_components_renamed() {
+        let m = mapped(
+            r#"const A = () => <Btn onClick={go}>Go <UI.Image imageSrc="x.png" imageAlt="" /></Btn>;"#,
+        );
+        assert_eq!(
+            m.source,
+            r#"const A = () => <button onClick={go}>Go <img src="x.png" alt="" /></button>;"#
+        );
+    }
+
+    #[test]
+    fn test_positions_mapped_back() {
+        let m = mapped("<><Btn /><UI.Image imageSrc=\"a\" /></>\n<><Btn /></>");
+        assert_eq!(
+            m.source,
+            "<><button /><img src=\"a\" /></>\n<><button /></>"
+        );
+        let at = |line, character| m.position(Position::new(line, character)).character;
+        assert_eq!(at(0, 0), 0);
+        // Inside `button`, and at its end.
+        assert_eq!(at(0, 4), 4);
+        assert_eq!(at(0, 9), 6);
+        // `<img` and inside `img`.
+        assert_eq!(at(0, 12), 9);
+        assert_eq!(at(0, 14), 11);
+        // Past all three renames on the line.
+        assert_eq!(at(0, 24), 31);
+        assert_eq!(at(1, 10), 7);
+    }
+
+    #[test]
+    fn test_custom_elements_renamed() {
+        let config = Config::parse(
+            r#"
+[components]
+fancy-button = "button"
+x-avatar = { element = "img", attributes = { label = "alt" } }
+"#,
+        );
+        let source =
+            r#"<FANCY-BUTTON type="button"><x-avatar src="a.png" label="Ada"/></fancy-button>"#;
+        let tree = scan::parse(FileType::Html, source).unwrap();
+        let m = map(FileType::Html, &tree, source, &config.components, false).unwrap();
+        assert_eq!(
+            m.source,
+            r#"<button type="button"><img src="a.png" alt="Ada"/></button>"#
+        );
+    }
+
+    #[test]
+    fn test_unmapped_files() {
+        let source = "const A = () => <button />;";
+        let tree = scan::parse(FileType::Tsx, source).unwrap();
+        let config = Config::parse("[components]\nBtn = \"button\"\n");
+        assert!(map(FileType::Tsx, &tree, source, &config.components, true).is_none());
+        let tree = scan::parse(FileType::Html, "<btn-group></btn-group>").unwrap();
+        assert!(
+            map(
+                FileType::Html,
+                &tree,
+                "<btn-group></btn-group>",
+                &config.components,
+                false
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_patterns() {
+        let config = Config::parse(
+            r#"
+[components]
+"motion.*" = "*"
+"Base*" = "*"
+"*Icon" = { element = "svg", attributes = { label = "aria-label" } }
+"BaseLink*" = "a"
+"#,
+        );
+        let source = r#"const A = () => <><motion.div onClick={go} /><BaseButton /><BaseLinkCard /><SearchIcon label="Search" /><Base /><Base.Item /></>;"#;
+        let tree = scan::parse(FileType::Tsx, source).unwrap();
+        let m = map(FileType::Tsx, &tree, source, &config.components, false).unwrap();
+        // `Base.Item` matches `Base*`, but `.item` isn't an element's name.
+        assert_eq!(
+            m.source,
+            r#"const A = () => <><div onClick={go} /><button /><a /><svg aria-label="Search" /><Base /><Base.Item /></>;"#
+        );
+
+        let config = Config::parse(
+            "[components]
+\"ui-*\" = \"*\"\n",
+        );
+        let source = "<UI-Button></ui-button>";
+        let tree = scan::parse(FileType::Html, source).unwrap();
+        let m = map(FileType::Html, &tree, source, &config.components, false).unwrap();
+        assert_eq!(m.source, "<button></button>");
+    }
+
+    #[test]
+    fn test_styled_components() {
+        let source = r#"import styled from "styled-components";
+const Button = styled.button`color: red;`;
+const Link = styled("a")({ color: "blue" });
+const Field = styled.input.attrs({ type: "text" })`border: 0;`;
+const Fancy = styled(Button)`color: blue;`;
+const A = () => <><Button onClick={go} /><Link href="/" /><Field /><Fancy /></>;"#;
+        let tree = scan::parse(FileType::Tsx, source).unwrap();
+        let none = HashMap::new();
+        assert!(map(FileType::Tsx, &tree, source, &none, false).is_none());
+        let m = map(FileType::Tsx, &tree, source, &none, true).unwrap();
+        assert!(
+            m.source
+                .ends_with(r#"<><button onClick={go} /><a href="/" /><input /><Fancy /></>;"#)
+        );
+
+        // A configured mapping wins.
+        let config = Config::parse("[components]\nButton = \"a\"\n");
+        let m = map(FileType::Tsx, &tree, source, &config.components, true).unwrap();
+        assert!(m.source.contains("<a onClick={go} />"));
+    }
+}
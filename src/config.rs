@@ -1,7 +1,14 @@
-use crate::rules::{Severity, WcagLevel};
+use crate::parser::FileType;
+use crate::rules::{Rule, Severity, WcagLevel};
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tower_lsp_server::ls_types::{Diagnostic, DiagnosticSeverity};
+
+/// `[clients]` key for `wcag-lsp check`.
+pub const CLI_CLIENT: &str = "cli";
+/// `[clients]` key for every editor without an entry of its own.
+pub const EDITOR_CLIENT: &str = "editor";
 
 #[derive(Debug, Deserialize, Default)]
 pub struct RawConfig {
@@ -13,6 +20,85 @@ pub struct RawConfig {
     pub rules: HashMap<String, String>,
     #[serde(default)]
     pub ignore: IgnoreConfig,
+    #[serde(default)]
+    pub filetypes: HashMap<String, String>,
+    #[serde(default)]
+    pub clients: HashMap<String, HashMap<String, String>>,
+    #[serde(default)]
+    pub fixes: FixesConfig,
+    #[serde(rename = "page-title", default)]
+    pub page_title: RawPageTitleConfig,
+    #[serde(rename = "color-contrast", default)]
+    pub color_contrast: RawColorContrastConfig,
+    #[serde(rename = "pdf-alternative", default)]
+    pub pdf_alternative: RawPdfAlternativeConfig,
+    #[serde(rename = "audio-transcript", default)]
+    pub audio_transcript: RawAudioTranscriptConfig,
+    #[serde(default)]
+    pub pagination: RawPaginationConfig,
+    #[serde(default)]
+    pub jsx: RawJsxConfig,
+    #[serde(default)]
+    pub components: HashMap<String, RawComponent>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct FixesConfig {
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct RawPageTitleConfig {
+    #[serde(rename = "max-length", default)]
+    pub max_length: Option<usize>,
+    #[serde(default)]
+    pub placeholders: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct RawColorContrastConfig {
+    #[serde(default)]
+    pub stylesheets: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct RawPdfAlternativeConfig {
+    #[serde(default)]
+    pub extensions: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct RawAudioTranscriptConfig {
+    #[serde(default)]
+    pub keywords: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct RawPaginationConfig {
+    #[serde(rename = "bare-numbers", default)]
+    pub bare_numbers: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct RawJsxConfig {
+    #[serde(rename = "spread-props", default)]
+    pub spread_props: Option<String>,
+    #[serde(default)]
+    pub styled: bool,
+}
+
+/// A `[components]` entry: the element's name, or a table with the element
+/// and the component's attribute aliases.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum RawComponent {
+    Element(String),
+    Mapping {
+        element: String,
+        #[serde(default)]
+        attributes: HashMap<String, String>,
+    },
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -21,13 +107,40 @@ pub struct IgnoreConfig {
     pub patterns: Vec<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Config {
     pub severity_a: Option<Severity>,
     pub severity_aa: Option<Severity>,
     pub severity_aaa: Option<Severity>,
     pub rule_overrides: HashMap<String, RuleOverride>,
     pub ignore_patterns: Vec<String>,
+    /// Extension (without the dot, lowercase) → file type overrides.
+    pub file_types: HashMap<String, FileType>,
+    /// Client name (lowercase) → how that client shows errors and warnings.
+    pub client_severities: HashMap<String, SeverityMap>,
+    /// Preferred locale for quick fixes that insert one, e.g. `lang`.
+    pub fix_locale: Option<String>,
+    /// `[page-title]` settings.
+    pub page_title: PageTitleConfig,
+    /// `[color-contrast]` settings.
+    pub color_contrast: ColorContrastConfig,
+    /// `[pdf-alternative]` settings.
+    pub pdf_alternative: PdfAlternativeConfig,
+    /// `[audio-transcript]` settings.
+    pub audio_transcript: AudioTranscriptConfig,
+    /// `[pagination]` settings.
+    pub pagination: PaginationConfig,
+    /// What becomes of a missing prop on a JSX element with a spread.
+    pub spread_props: SpreadPolicy,
+    /// JSX component name → the native element the rules treat it as. A
+    /// name may contain a `*`, and an element of `*` stands for what it
+    /// matched.
+    pub components: HashMap<String, ComponentMapping>,
+    /// Whether JSX components declared with styled-components or Emotion
+    /// count as the element they style.
+    pub styled_components: bool,
+    /// The config file this was loaded from, if any.
+    pub path: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -36,6 +149,130 @@ pub enum RuleOverride {
     Severity(Severity),
 }
 
+/// When the `page-title` rule considers a title too generic or too long.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageTitleConfig {
+    /// Titles longer than this many characters are flagged; 0 allows any
+    /// length.
+    pub max_length: usize,
+    /// Language (lowercase primary subtag) → placeholder titles, replacing
+    /// the rule's built-in list for that language.
+    pub placeholders: HashMap<String, Vec<String>>,
+}
+
+impl Default for PageTitleConfig {
+    fn default() -> Self {
+        Self {
+            max_length: 70,
+            placeholders: HashMap::new(),
+        }
+    }
+}
+
+/// The workspace style sheets the `color-contrast` rule reads besides the
+/// file's own `<style>` blocks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColorContrastConfig {
+    /// Paths as configured, relative to the config file.
+    pub stylesheets: Vec<String>,
+    /// Their text, in the same order, once the config file's location is
+    /// known. Files that can't be read are left out.
+    pub css: Vec<String>,
+}
+
+/// Which embedded documents the `pdf-alternative` rule wants a link next to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfAlternativeConfig {
+    /// File extensions (lowercase, without the dot) of the documents.
+    pub extensions: Vec<String>,
+}
+
+impl Default for PdfAlternativeConfig {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["pdf".to_string()],
+        }
+    }
+}
+
+/// What the `audio-transcript` rule takes for a transcript next to an
+/// `<audio>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioTranscriptConfig {
+    /// Lowercase words whose presence in the markup around the player marks
+    /// a transcript or a link to one.
+    pub keywords: Vec<String>,
+}
+
+impl Default for AudioTranscriptConfig {
+    fn default() -> Self {
+        Self {
+            keywords: vec!["transcript".to_string(), "transkript".to_string()],
+        }
+    }
+}
+
+/// How the `pagination` rule checks page links.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaginationConfig {
+    /// Whether a page link named only by its number, like "2", is flagged.
+    pub bare_numbers: bool,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self { bare_numbers: true }
+    }
+}
+
+/// The native element a JSX component renders, for the rules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentMapping {
+    /// Lowercase element name, e.g. `button`.
+    pub element: String,
+    /// Component prop → the element attribute it sets, e.g. `imageAlt` →
+    /// `alt`.
+    pub attributes: HashMap<String, String>,
+}
+
+/// What the engine does with a diagnostic about a missing prop (`alt`,
+/// an accessible name) on a JSX element with a spread (`<img {...props} />`),
+/// which may provide it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SpreadPolicy {
+    /// Report it as if there were no spread.
+    Report,
+    /// Drop it.
+    Skip,
+    /// Report it as a hint.
+    Hint,
+    /// Look the spread object up in the file: drop it if the object has the
+    /// prop, keep it if it doesn't, and report it as a hint if the object
+    /// isn't a literal in the file.
+    #[default]
+    Resolve,
+}
+
+impl SpreadPolicy {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "report" => Some(Self::Report),
+            "skip" => Some(Self::Skip),
+            "hint" => Some(Self::Hint),
+            "resolve" => Some(Self::Resolve),
+            _ => None,
+        }
+    }
+}
+
+/// The LSP severity a client shows rule errors and warnings as. `None` keeps
+/// the usual mapping.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeverityMap {
+    pub error: Option<DiagnosticSeverity>,
+    pub warning: Option<DiagnosticSeverity>,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -44,6 +281,18 @@ impl Default for Config {
             severity_aaa: Some(Severity::Warning),
             rule_overrides: HashMap::new(),
             ignore_patterns: vec![],
+            file_types: HashMap::new(),
+            client_severities: HashMap::new(),
+            fix_locale: None,
+            page_title: PageTitleConfig::default(),
+            color_contrast: ColorContrastConfig::default(),
+            pdf_alternative: PdfAlternativeConfig::default(),
+            audio_transcript: AudioTranscriptConfig::default(),
+            pagination: PaginationConfig::default(),
+            spread_props: SpreadPolicy::default(),
+            components: HashMap::new(),
+            styled_components: false,
+            path: None,
         }
     }
 }
@@ -55,25 +304,44 @@ impl Config {
             Err(_) => return Self::default(),
         };
 
-        match path.extension().and_then(|e| e.to_str()) {
+        let config = match path.extension().and_then(|e| e.to_str()) {
             Some("json") => Self::parse_json(&content),
             Some("toml") => Self::parse(&content),
-            _ => Self::default(),
-        }
+            _ => return Self::default(),
+        };
+        config.with_path(path)
     }
 
     pub fn from_dir(dir: &Path) -> Self {
+        Self::find_in(dir).unwrap_or_default()
+    }
+
+    /// Loads `.wcag.toml` (or else `.wcag.json`) from `dir`, or `None` if the
+    /// directory has neither.
+    pub fn find_in(dir: &Path) -> Option<Self> {
         let toml_path = dir.join(".wcag.toml");
         if let Ok(content) = std::fs::read_to_string(&toml_path) {
-            return Self::parse(&content);
+            return Some(Self::parse(&content).with_path(&toml_path));
         }
 
         let json_path = dir.join(".wcag.json");
         if let Ok(content) = std::fs::read_to_string(&json_path) {
-            return Self::parse_json(&content);
+            return Some(Self::parse_json(&content).with_path(&json_path));
         }
 
-        Self::default()
+        None
+    }
+
+    fn with_path(mut self, path: &Path) -> Self {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        self.color_contrast.css = self
+            .color_contrast
+            .stylesheets
+            .iter()
+            .filter_map(|sheet| std::fs::read_to_string(dir.join(sheet)).ok())
+            .collect();
+        self.path = Some(path.to_path_buf());
+        self
     }
 
     pub fn parse_json(content: &str) -> Self {
@@ -133,12 +401,114 @@ impl Config {
             rule_overrides.insert(rule_id.clone(), override_val);
         }
 
+        let mut file_types = HashMap::new();
+        for (ext, name) in &raw.filetypes {
+            let file_type = FileType::from_name(name);
+            if file_type == FileType::Unknown {
+                continue;
+            }
+            let ext = ext.trim_start_matches('.').to_ascii_lowercase();
+            file_types.insert(ext, file_type);
+        }
+
+        fn parse_lsp_severity(s: &str) -> Option<DiagnosticSeverity> {
+            match s.to_lowercase().as_str() {
+                "error" => Some(DiagnosticSeverity::ERROR),
+                "warning" | "warn" => Some(DiagnosticSeverity::WARNING),
+                "information" | "info" => Some(DiagnosticSeverity::INFORMATION),
+                "hint" => Some(DiagnosticSeverity::HINT),
+                _ => None,
+            }
+        }
+
+        let mut client_severities = HashMap::new();
+        for (client, mapping) in &raw.clients {
+            let map = SeverityMap {
+                error: mapping.get("error").and_then(|s| parse_lsp_severity(s)),
+                warning: mapping.get("warning").and_then(|s| parse_lsp_severity(s)),
+            };
+            client_severities.insert(client.to_lowercase(), map);
+        }
+
+        let mut page_title = PageTitleConfig::default();
+        if let Some(max_length) = raw.page_title.max_length {
+            page_title.max_length = max_length;
+        }
+        for (locale, titles) in raw.page_title.placeholders {
+            let language = locale.split(['-', '_']).next().unwrap_or_default();
+            page_title
+                .placeholders
+                .insert(language.to_ascii_lowercase(), titles);
+        }
+
+        let mut components = HashMap::new();
+        for (component, raw) in raw.components {
+            let (element, attributes) = match raw {
+                RawComponent::Element(element) => (element, HashMap::new()),
+                RawComponent::Mapping {
+                    element,
+                    attributes,
+                } => (element, attributes),
+            };
+            let element = element.trim().to_ascii_lowercase();
+            if element.is_empty() || component.trim().is_empty() {
+                continue;
+            }
+            components.insert(
+                component.trim().to_string(),
+                ComponentMapping {
+                    element,
+                    attributes,
+                },
+            );
+        }
+
         Config {
             severity_a,
             severity_aa,
             severity_aaa,
             rule_overrides,
             ignore_patterns: raw.ignore.patterns,
+            file_types,
+            client_severities,
+            fix_locale: raw.fixes.locale.filter(|l| !l.trim().is_empty()),
+            page_title,
+            color_contrast: ColorContrastConfig {
+                stylesheets: raw.color_contrast.stylesheets,
+                css: Vec::new(),
+            },
+            pdf_alternative: match raw.pdf_alternative.extensions {
+                Some(extensions) => PdfAlternativeConfig {
+                    extensions: extensions
+                        .iter()
+                        .map(|ext| ext.trim().trim_start_matches('.').to_ascii_lowercase())
+                        .filter(|ext| !ext.is_empty())
+                        .collect(),
+                },
+                None => PdfAlternativeConfig::default(),
+            },
+            audio_transcript: match raw.audio_transcript.keywords {
+                Some(keywords) => AudioTranscriptConfig {
+                    keywords: keywords
+                        .iter()
+                        .map(|keyword| keyword.trim().to_lowercase())
+                        .filter(|keyword| !keyword.is_empty())
+                        .collect(),
+                },
+                None => AudioTranscriptConfig::default(),
+            },
+            pagination: PaginationConfig {
+                bare_numbers: raw.pagination.bare_numbers.unwrap_or(true),
+            },
+            spread_props: raw
+                .jsx
+                .spread_props
+                .as_deref()
+                .and_then(SpreadPolicy::from_name)
+                .unwrap_or_default(),
+            components,
+            styled_components: raw.jsx.styled,
+            path: None,
         }
     }
 
@@ -150,6 +520,40 @@ impl Config {
         }
     }
 
+    /// Resolves the file type for a document: a configured extension mapping
+    /// wins, then the client's `languageId` (if any), then the built-in
+    /// extension table, then content sniffing. Angular and Blade templates
+    /// are the exception: editors report them as plain `html`/`php`, so the
+    /// `.component.html`/`.blade.php` suffix is checked before the
+    /// `languageId`.
+    pub fn file_type_for(&self, uri: &str, language_id: Option<&str>, source: &str) -> FileType {
+        let ext = uri.rsplit('.').next().unwrap_or("").to_ascii_lowercase();
+        if let Some(file_type) = self.file_types.get(&ext) {
+            return *file_type;
+        }
+        if let Some(file_type) = FileType::from_suffix(uri) {
+            return file_type;
+        }
+        if let Some(file_type) = language_id
+            .map(FileType::from_language_id)
+            .filter(|t| *t != FileType::Unknown)
+        {
+            return file_type;
+        }
+        FileType::detect(uri, source)
+    }
+
+    /// The file type the name of `uri` gives: its `[filetypes]` mapping, then
+    /// the built-in suffixes and extensions. `Unknown` means only the content
+    /// can tell.
+    pub fn file_type_from_name(&self, uri: &str) -> FileType {
+        let ext = uri.rsplit('.').next().unwrap_or("").to_ascii_lowercase();
+        match self.file_types.get(&ext) {
+            Some(file_type) => *file_type,
+            None => FileType::from_uri(uri),
+        }
+    }
+
     pub fn is_rule_enabled(&self, rule_id: &str) -> bool {
         self.rule_overrides
             .get(rule_id)
@@ -167,6 +571,101 @@ impl Config {
             None => self.severity_for_level(level),
         }
     }
+
+    /// A stable summary of every setting that affects which diagnostics a
+    /// file gets, for cache keys.
+    pub fn fingerprint(&self) -> String {
+        let mut rules: Vec<String> = self
+            .rule_overrides
+            .iter()
+            .map(|(id, o)| format!("{id}={o:?}"))
+            .collect();
+        rules.sort();
+        let mut file_types: Vec<String> = self
+            .file_types
+            .iter()
+            .map(|(ext, t)| format!("{ext}={t:?}"))
+            .collect();
+        file_types.sort();
+        let mut placeholders: Vec<String> = self
+            .page_title
+            .placeholders
+            .iter()
+            .map(|(language, titles)| format!("{language}={titles:?}"))
+            .collect();
+        placeholders.sort();
+        let mut components: Vec<String> = self
+            .components
+            .iter()
+            .map(|(component, mapping)| {
+                let mut attributes: Vec<_> = mapping.attributes.iter().collect();
+                attributes.sort();
+                format!("{component}={}{attributes:?}", mapping.element)
+            })
+            .collect();
+        components.sort();
+        format!(
+            "{:?};{:?};{:?};{};{};{};{};{:?};{};{};{};{};{};{}",
+            self.severity_a,
+            self.severity_aa,
+            self.severity_aaa,
+            rules.join(","),
+            file_types.join(","),
+            self.page_title.max_length,
+            placeholders.join(","),
+            self.spread_props,
+            components.join(","),
+            self.styled_components,
+            self.color_contrast.css.join("\0"),
+            self.pdf_alternative.extensions.join(","),
+            self.audio_transcript.keywords.join(","),
+            self.pagination.bare_numbers
+        )
+    }
+
+    /// Like [`Config::effective_severity`], but opt-in rules stay disabled
+    /// until a `[rules]` entry gives them a severity.
+    pub fn rule_severity(&self, rule: &dyn Rule) -> Option<Severity> {
+        let meta = rule.metadata();
+        if rule.opt_in() && !self.rule_overrides.contains_key(meta.id) {
+            return None;
+        }
+        self.effective_severity(meta.id, meta.wcag_level)
+    }
+
+    /// The severity `client` shows a rule error or warning as. An editor
+    /// without its own `[clients]` entry uses the `editor` entry; the CLI
+    /// only uses `cli`.
+    pub fn presented_severity(
+        &self,
+        severity: DiagnosticSeverity,
+        client: &str,
+    ) -> DiagnosticSeverity {
+        let client = client.to_lowercase();
+        let map = self.client_severities.get(&client).or_else(|| {
+            (client != CLI_CLIENT)
+                .then(|| self.client_severities.get(EDITOR_CLIENT))
+                .flatten()
+        });
+        let mapped = map.and_then(|m| match severity {
+            DiagnosticSeverity::ERROR => m.error,
+            DiagnosticSeverity::WARNING => m.warning,
+            _ => None,
+        });
+        mapped.unwrap_or(severity)
+    }
+
+    /// Applies [`Config::presented_severity`] to rule diagnostics in place.
+    pub fn present(&self, diagnostics: &mut [Diagnostic], client: &str) {
+        if self.client_severities.is_empty() {
+            return;
+        }
+        for diagnostic in diagnostics {
+            if let Some(severity) = diagnostic.severity {
+                diagnostic.severity = Some(self.presented_severity(severity, client));
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -212,6 +711,177 @@ patterns = ["node_modules/**", "dist/**"]
         assert_eq!(config.ignore_patterns.len(), 2);
     }
 
+    #[test]
+    fn test_opt_in_rule_needs_explicit_severity() {
+        let rule = crate::rules::composite_focus::CompositeFocus;
+        assert_eq!(Config::default().rule_severity(&rule), None);
+
+        let config = Config::parse("[rules]\ncomposite-focus = \"warning\"\n");
+        assert_eq!(config.rule_severity(&rule), Some(Severity::Warning));
+
+        let config = Config::parse("[rules]\ncomposite-focus = \"off\"\n");
+        assert_eq!(config.rule_severity(&rule), None);
+
+        let img_alt = crate::rules::img_alt::ImgAlt;
+        assert_eq!(
+            Config::default().rule_severity(&img_alt),
+            Some(Severity::Error)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_map_order() {
+        let a = Config::parse("[rules]\nimg-alt = \"off\"\nheading-order = \"warn\"\n");
+        let b = Config::parse("[rules]\nheading-order = \"warn\"\nimg-alt = \"off\"\n");
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a.fingerprint(), Config::default().fingerprint());
+    }
+
+    #[test]
+    fn test_client_severity_mapping() {
+        let config = Config::parse(
+            r#"
+[clients.editor]
+error = "information"
+warning = "hint"
+
+[clients.Neovim]
+warning = "info"
+
+[clients.cli]
+warning = "error"
+"#,
+        );
+        let shown = |s, client| config.presented_severity(s, client);
+        assert_eq!(
+            shown(DiagnosticSeverity::ERROR, "Visual Studio Code"),
+            DiagnosticSeverity::INFORMATION
+        );
+        assert_eq!(
+            shown(DiagnosticSeverity::WARNING, "Visual Studio Code"),
+            DiagnosticSeverity::HINT
+        );
+        // A client's own entry replaces the `editor` one, unmapped keys included.
+        assert_eq!(
+            shown(DiagnosticSeverity::WARNING, "neovim"),
+            DiagnosticSeverity::INFORMATION
+        );
+        assert_eq!(
+            shown(DiagnosticSeverity::ERROR, "neovim"),
+            DiagnosticSeverity::ERROR
+        );
+        assert_eq!(
+            shown(DiagnosticSeverity::WARNING, CLI_CLIENT),
+            DiagnosticSeverity::ERROR
+        );
+        assert_eq!(
+            shown(DiagnosticSeverity::ERROR, CLI_CLIENT),
+            DiagnosticSeverity::ERROR
+        );
+    }
+
+    #[test]
+    fn test_cli_ignores_editor_mapping() {
+        let config = Config::parse("[clients.editor]\nerror = \"hint\"\n");
+        assert_eq!(
+            config.presented_severity(DiagnosticSeverity::ERROR, CLI_CLIENT),
+            DiagnosticSeverity::ERROR
+        );
+        assert!(Config::default().client_severities.is_empty());
+    }
+
+    #[test]
+    fn test_fix_locale() {
+        let config = Config::parse("[fixes]\nlocale = \"de-CH\"\n");
+        assert_eq!(config.fix_locale.as_deref(), Some("de-CH"));
+        assert_eq!(Config::parse("[fixes]\nlocale = \"\"\n").fix_locale, None);
+    }
+
+    #[test]
+    fn test_page_title_settings() {
+        let config = Config::parse(
+            "[page-title]\nmax-length = 50\n\n[page-title.placeholders]\nde-CH = [\"Startseite\"]\n",
+        );
+        assert_eq!(config.page_title.max_length, 50);
+        assert_eq!(
+            config.page_title.placeholders.get("de"),
+            Some(&vec!["Startseite".to_string()])
+        );
+        assert_eq!(Config::default().page_title.max_length, 70);
+
+        let json = Config::parse_json(r#"{ "page-title": { "placeholders": { "en": [] } } }"#);
+        assert_eq!(json.page_title.placeholders.get("en"), Some(&vec![]));
+        assert_ne!(json.fingerprint(), Config::default().fingerprint());
+    }
+
+    #[test]
+    fn test_pdf_alternative_extensions() {
+        assert_eq!(Config::default().pdf_alternative.extensions, vec!["pdf"]);
+        let config = Config::parse("[pdf-alternative]\nextensions = [\"pdf\", \".DOCX\", \"\"]\n");
+        assert_eq!(config.pdf_alternative.extensions, vec!["pdf", "docx"]);
+        assert_ne!(config.fingerprint(), Config::default().fingerprint());
+        let none = Config::parse_json(r#"{ "pdf-alternative": { "extensions": [] } }"#);
+        assert!(none.pdf_alternative.extensions.is_empty());
+    }
+
+    #[test]
+    fn test_audio_transcript_keywords() {
+        let config = Config::parse(
+            "[audio-transcript]\nkeywords = [\" Transcripción \", \"Text version\"]\n",
+        );
+        assert_eq!(
+            config.audio_transcript.keywords,
+            vec!["transcripción", "text version"]
+        );
+        assert_ne!(config.fingerprint(), Config::default().fingerprint());
+        assert!(
+            Config::default()
+                .audio_transcript
+                .keywords
+                .contains(&"transcript".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pagination_bare_numbers() {
+        assert!(Config::default().pagination.bare_numbers);
+        let config = Config::parse("[pagination]\nbare-numbers = false\n");
+        assert!(!config.pagination.bare_numbers);
+        assert_ne!(config.fingerprint(), Config::default().fingerprint());
+    }
+
+    #[test]
+    fn test_spread_props_policy() {
+        assert_eq!(Config::default().spread_props, SpreadPolicy::Resolve);
+        let config = Config::parse("[jsx]\nspread-props = \"Skip\"\n");
+        assert_eq!(config.spread_props, SpreadPolicy::Skip);
+        assert_ne!(config.fingerprint(), Config::default().fingerprint());
+        let json = Config::parse_json(r#"{ "jsx": { "spread-props": "sometimes" } }"#);
+        assert_eq!(json.spread_props, SpreadPolicy::Resolve);
+    }
+
+    #[test]
+    fn test_components() {
+        let config = Config::parse(
+            r#"
+[components]
+MyButton = "Button"
+Img = { element = "img", attributes = { imageAlt = "alt" } }
+Empty = ""
+"#,
+        );
+        assert_eq!(config.components.len(), 2);
+        assert_eq!(config.components["MyButton"].element, "button");
+        assert!(config.components["MyButton"].attributes.is_empty());
+        assert_eq!(config.components["Img"].attributes["imageAlt"], "alt");
+        assert_ne!(config.fingerprint(), Config::default().fingerprint());
+
+        let json = Config::parse_json(
+            r#"{ "components": { "Img": { "element": "img", "attributes": { "imageAlt": "alt" } } } }"#,
+        );
+        assert_eq!(json.components["Img"], config.components["Img"]);
+    }
+
     #[test]
     fn test_invalid_toml_returns_defaults() {
         let config = Config::parse("this is not valid toml {{{}}}");
@@ -268,6 +938,7 @@ patterns = ["node_modules/**", "dist/**"]
         .unwrap();
         let config = Config::from_dir(dir.path());
         assert_eq!(config.severity_aa, Some(Severity::Error));
+        assert_eq!(config.path, Some(dir.path().join(".wcag.toml")));
     }
 
     #[test]
@@ -282,12 +953,29 @@ patterns = ["node_modules/**", "dist/**"]
         assert_eq!(config.severity_aa, Some(Severity::Error));
     }
 
+    #[test]
+    fn test_color_contrast_stylesheets_relative_to_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("styles")).unwrap();
+        std::fs::write(dir.path().join("styles/theme.css"), "p { color: #777; }").unwrap();
+        std::fs::write(
+            dir.path().join(".wcag.toml"),
+            "[color-contrast]\nstylesheets = [\"styles/theme.css\", \"missing.css\"]\n",
+        )
+        .unwrap();
+        let config = Config::from_dir(dir.path());
+        assert_eq!(config.color_contrast.stylesheets.len(), 2);
+        assert_eq!(config.color_contrast.css, ["p { color: #777; }"]);
+        assert_ne!(config.fingerprint(), Config::default().fingerprint());
+    }
+
     #[test]
     fn test_from_dir_no_config_returns_defaults() {
         let dir = tempfile::tempdir().unwrap();
         let config = Config::from_dir(dir.path());
         assert_eq!(config.severity_a, Some(Severity::Error));
         assert_eq!(config.severity_aa, Some(Severity::Warning));
+        assert_eq!(config.path, None);
     }
 
     #[test]
@@ -386,6 +1074,78 @@ img-alt = "error"
         );
     }
 
+    #[test]
+    fn test_filetypes_mapping() {
+        let config = Config::parse(
+            r#"
+[filetypes]
+tpl = "html"
+".vto" = "html"
+mdx = "jsx"
+foo = "cobol"
+"#,
+        );
+        assert_eq!(config.file_types.get("tpl"), Some(&FileType::Html));
+        assert_eq!(config.file_types.get("vto"), Some(&FileType::Html));
+        assert_eq!(config.file_types.get("mdx"), Some(&FileType::Jsx));
+        assert!(!config.file_types.contains_key("foo"));
+        assert_eq!(
+            config.file_type_for("file:///a/page.tpl", Some("html"), "{{ title }}"),
+            FileType::Html
+        );
+    }
+
+    #[test]
+    fn test_file_type_for_falls_back_to_sniffing() {
+        let config = Config::default();
+        assert_eq!(
+            config.file_type_for("file:///a/header.inc", None, "<!doctype html><img>"),
+            FileType::Html
+        );
+        assert_eq!(
+            config.file_type_for("file:///a/notes.txt", None, "just text"),
+            FileType::Unknown
+        );
+    }
+
+    #[test]
+    fn test_file_type_for_angular_template_over_html_language_id() {
+        let config = Config::default();
+        assert_eq!(
+            config.file_type_for("file:///a/nav.component.html", Some("html"), "<nav></nav>"),
+            FileType::Angular
+        );
+        assert_eq!(
+            config.file_type_for("file:///a/index.html", Some("html"), "<nav></nav>"),
+            FileType::Html
+        );
+    }
+
+    #[test]
+    fn test_file_type_for_prefers_language_id() {
+        let config = Config::default();
+        // Embedded editors may hand us a URI without a meaningful extension.
+        assert_eq!(
+            config.file_type_for(
+                "vscode-notebook-cell:/x#1",
+                Some("typescriptreact"),
+                "<div/>"
+            ),
+            FileType::Tsx
+        );
+        // A languageId we don't lint falls back to the extension.
+        assert_eq!(
+            config.file_type_for("file:///a/App.jsx", Some("plaintext"), ""),
+            FileType::Jsx
+        );
+        // An explicit [filetypes] mapping beats the languageId.
+        let config = Config::parse("[filetypes]\nhtml = \"vue\"\n");
+        assert_eq!(
+            config.file_type_for("file:///a/index.html", Some("html"), ""),
+            FileType::Vue
+        );
+    }
+
     #[test]
     fn test_json_with_schema_field() {
         let config = Config::parse_json(
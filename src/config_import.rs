@@ -0,0 +1,300 @@
+//! `wcag-lsp config import`: a `.wcag.toml` from another tool's config.
+//!
+//! Reads the `rules` of an ESLint config using `eslint-plugin-jsx-a11y`
+//! (`.eslintrc.json`, comments allowed) or of an axe-core configuration
+//! (`{"rules": [{"id": "image-alt", "enabled": false}]}` or
+//! `{"rules": {"image-alt": {"enabled": false}}}`) and writes the `[rules]`
+//! table that keeps the same checks on, off and at the same severity. Rules
+//! without a wcag-lsp equivalent are listed as comments.
+
+use crate::rules::Rule;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const JSX_A11Y_PREFIX: &str = "jsx-a11y/";
+
+/// wcag-lsp rules checking what each `eslint-plugin-jsx-a11y` rule checks.
+const JSX_A11Y_RULES: &[(&str, &[&str])] = &[
+    (
+        "alt-text",
+        &["img-alt", "area-alt", "input-image-alt", "object-alt"],
+    ),
+    ("anchor-has-content", &["anchor-content"]),
+    ("aria-props", &["aria-props"]),
+    ("aria-proptypes", &["aria-valid-attr-value"]),
+    ("aria-role", &["aria-role"]),
+    ("autocomplete-valid", &["autocomplete-valid"]),
+    (
+        "click-events-have-key-events",
+        &["click-events-have-key-events"],
+    ),
+    ("control-has-associated-label", &["form-label"]),
+    ("heading-has-content", &["heading-content"]),
+    ("html-has-lang", &["html-lang"]),
+    ("iframe-has-title", &["iframe-title"]),
+    ("img-redundant-alt", &["no-redundant-alt"]),
+    ("label-has-associated-control", &["form-label"]),
+    ("label-has-for", &["form-label"]),
+    ("lang", &["lang-valid"]),
+    ("media-has-caption", &["media-captions"]),
+    (
+        "mouse-events-have-key-events",
+        &["mouse-events-have-key-events"],
+    ),
+    ("no-access-key", &["no-access-key"]),
+    ("no-aria-hidden-on-focusable", &["aria-hidden-focus"]),
+    ("no-distracting-elements", &["no-distracting-elements"]),
+    ("no-redundant-roles", &["no-redundant-roles"]),
+    ("role-has-required-aria-props", &["aria-required-attr"]),
+    ("role-supports-aria-props", &["aria-allowed-attr"]),
+    ("scope", &["scope-attr"]),
+    ("tabindex-no-positive", &["no-positive-tabindex"]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Setting {
+    Off,
+    Warning,
+    Error,
+}
+
+impl Setting {
+    fn as_str(self) -> &'static str {
+        match self {
+            Setting::Off => "off",
+            Setting::Warning => "warning",
+            Setting::Error => "error",
+        }
+    }
+
+    /// An ESLint rule setting: `"error"`, `1`, `["warn", {...}]` and so on.
+    fn from_eslint(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(s) => match s.as_str() {
+                "error" => Some(Setting::Error),
+                "warn" => Some(Setting::Warning),
+                "off" => Some(Setting::Off),
+                _ => None,
+            },
+            Value::Number(n) => match n.as_u64()? {
+                2 => Some(Setting::Error),
+                1 => Some(Setting::Warning),
+                0 => Some(Setting::Off),
+                _ => None,
+            },
+            Value::Array(items) => Setting::from_eslint(items.first()?),
+            _ => None,
+        }
+    }
+}
+
+/// The `.wcag.toml` for the config at `path`.
+pub fn import(path: &Path, rules: &[Box<dyn Rule>]) -> Result<String, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let config: Value = serde_json::from_str(&strip_json_comments(&text))
+        .map_err(|e| format!("{} is not a JSON config: {e}", path.display()))?;
+    translate(&config, rules, &path.display().to_string())
+}
+
+/// The `.wcag.toml` for an ESLint or axe-core `config` read from `from`.
+pub fn translate(config: &Value, rules: &[Box<dyn Rule>], from: &str) -> Result<String, String> {
+    let mut settings: BTreeMap<&str, Setting> = BTreeMap::new();
+    let mut unmapped: Vec<String> = Vec::new();
+    let mut set = |id: &'static str, setting: Setting| {
+        let current = settings.entry(id).or_insert(setting);
+        // Several source rules can map to one of ours; the strictest wins.
+        *current = (*current).max(setting);
+    };
+
+    match config.get("rules") {
+        Some(Value::Object(entries)) => {
+            for (name, value) in entries {
+                if let Some(name) = name.strip_prefix(JSX_A11Y_PREFIX) {
+                    let Some(setting) = Setting::from_eslint(value) else {
+                        unmapped.push(format!("{JSX_A11Y_PREFIX}{name}"));
+                        continue;
+                    };
+                    match JSX_A11Y_RULES.iter().find(|(rule, _)| *rule == name) {
+                        Some((_, ids)) => ids.iter().for_each(|id| set(id, setting)),
+                        None => unmapped.push(format!("{JSX_A11Y_PREFIX}{name}")),
+                    }
+                } else if let Some(enabled) = value.get("enabled").and_then(Value::as_bool) {
+                    axe_rule(name, enabled, rules, &mut set, &mut unmapped);
+                }
+            }
+        }
+        Some(Value::Array(entries)) => {
+            for entry in entries {
+                let Some(name) = entry.get("id").and_then(Value::as_str) else {
+                    continue;
+                };
+                let enabled = entry.get("enabled").and_then(Value::as_bool);
+                axe_rule(
+                    name,
+                    enabled.unwrap_or(true),
+                    rules,
+                    &mut set,
+                    &mut unmapped,
+                );
+            }
+        }
+        _ => return Err(format!("{from} has no \"rules\" to import")),
+    }
+
+    let mut out = format!("# Imported from {from} by `wcag-lsp config import`.\n\n[rules]\n");
+    for (id, setting) in &settings {
+        out.push_str(&format!("{id} = \"{}\"\n", setting.as_str()));
+    }
+    if !unmapped.is_empty() {
+        out.push_str("\n# No wcag-lsp equivalent:\n");
+        for name in &unmapped {
+            out.push_str(&format!("# {name}\n"));
+        }
+    }
+    Ok(out)
+}
+
+/// Turns off the wcag-lsp rules the axe-core rule `name` corresponds to, or
+/// turns on those of them that are opt-in.
+fn axe_rule(
+    name: &str,
+    enabled: bool,
+    rules: &[Box<dyn Rule>],
+    set: &mut impl FnMut(&'static str, Setting),
+    unmapped: &mut Vec<String>,
+) {
+    let mut found = false;
+    for rule in rules {
+        let meta = rule.metadata();
+        if !meta.axe_rules.contains(&name) {
+            continue;
+        }
+        found = true;
+        if !enabled {
+            set(meta.id, Setting::Off);
+        } else if rule.opt_in() {
+            set(meta.id, Setting::Warning);
+        }
+    }
+    if !found {
+        unmapped.push(name.to_string());
+    }
+}
+
+/// `text` without `//` and `/* */` comments outside strings, as ESLint
+/// accepts them in `.eslintrc.json`.
+fn strip_json_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules;
+    use serde_json::json;
+
+    fn toml(config: Value) -> String {
+        translate(&config, &rules::all_rules(), ".eslintrc.json").unwrap()
+    }
+
+    #[test]
+    fn test_eslint_jsx_a11y_rules() {
+        let out = toml(json!({
+            "plugins": ["jsx-a11y"],
+            "rules": {
+                "jsx-a11y/alt-text": ["warn", { "img": ["Image"] }],
+                "jsx-a11y/anchor-has-content": 2,
+                "jsx-a11y/no-autofocus": "error",
+                "jsx-a11y/tabindex-no-positive": "off",
+                "no-console": "warn"
+            }
+        }));
+        assert!(out.contains("img-alt = \"warning\"\n"));
+        assert!(out.contains("object-alt = \"warning\"\n"));
+        assert!(out.contains("anchor-content = \"error\"\n"));
+        assert!(out.contains("no-positive-tabindex = \"off\"\n"));
+        assert!(out.contains("# jsx-a11y/no-autofocus\n"));
+        assert!(!out.contains("no-console"));
+        let parsed: toml::Value = toml::from_str(&out).unwrap();
+        assert_eq!(parsed["rules"]["area-alt"].as_str(), Some("warning"));
+    }
+
+    #[test]
+    fn test_strictest_setting_wins() {
+        let out = toml(json!({
+            "rules": {
+                "jsx-a11y/label-has-for": "off",
+                "jsx-a11y/label-has-associated-control": "error"
+            }
+        }));
+        assert!(out.contains("form-label = \"error\"\n"));
+    }
+
+    #[test]
+    fn test_axe_rule_toggles() {
+        let list = toml(json!({
+            "rules": [
+                { "id": "image-alt", "enabled": false },
+                { "id": "color-contrast-enhanced", "enabled": false }
+            ]
+        }));
+        assert!(list.contains("img-alt = \"off\"\n"));
+        assert!(list.contains("# color-contrast-enhanced\n"));
+
+        let map = toml(json!({ "rules": { "link-name": { "enabled": false } } }));
+        assert!(map.contains("anchor-content = \"off\"\n"));
+    }
+
+    #[test]
+    fn test_comments_and_missing_rules() {
+        let text = "{\n  // the a11y rules\n  \"rules\": { /* strict */ \"jsx-a11y/lang\": \"error\", \"x\": \"http://a\" }\n}";
+        let config: Value = serde_json::from_str(&strip_json_comments(text)).unwrap();
+        assert_eq!(config["rules"]["x"], "http://a");
+        assert!(toml(config).contains("lang-valid = \"error\"\n"));
+
+        assert!(translate(&json!({}), &rules::all_rules(), "a.json").is_err());
+    }
+}
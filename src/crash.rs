@@ -0,0 +1,127 @@
+//! Crash reports for rules that panic on unexpected input.
+//!
+//! The engine runs every rule under `catch_unwind`, so one misbehaving rule
+//! doesn't take the server (and the user's editor integration) down with it.
+//! The panic hook installed here remembers the panic message and location so
+//! the report can say where it happened; reports are appended to
+//! `crash.log` in the state directory.
+
+use crate::state;
+use std::cell::RefCell;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const CRASH_LOG_FILE: &str = "crash.log";
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Installs a panic hook that records the panic for [`take_last_panic`] and
+/// then defers to the previous hook (which prints to stderr).
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = match info.location() {
+            Some(loc) => format!(
+                "{} at {}:{}",
+                payload_message(info.payload()),
+                loc.file(),
+                loc.line()
+            ),
+            None => payload_message(info.payload()),
+        };
+        LAST_PANIC.with(|last| *last.borrow_mut() = Some(message));
+        previous(info);
+    }));
+}
+
+/// The message of the most recent panic on this thread, if the hook saw one.
+pub fn take_last_panic() -> Option<String> {
+    LAST_PANIC.with(|last| last.borrow_mut().take())
+}
+
+pub fn payload_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashReport {
+    pub rule_id: String,
+    pub uri: String,
+    pub message: String,
+}
+
+impl CrashReport {
+    pub fn to_log_entry(&self) -> String {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        format!(
+            "[{timestamp}] wcag-lsp v{} rule `{}` panicked on {}: {}\n",
+            env!("CARGO_PKG_VERSION"),
+            self.rule_id,
+            if self.uri.is_empty() {
+                "<unknown>"
+            } else {
+                &self.uri
+            },
+            self.message
+        )
+    }
+}
+
+/// Appends `report` to the crash log in `dir`, returning the log's path.
+pub fn write_report_in(dir: &Path, report: &CrashReport) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(CRASH_LOG_FILE);
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    file.write_all(report.to_log_entry().as_bytes())?;
+    Ok(path)
+}
+
+/// [`write_report_in`] for the user's state directory. Best effort.
+pub fn write_report(report: &CrashReport) -> Option<PathBuf> {
+    let dir = state::state_dir()?;
+    write_report_in(&dir, report).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_message() {
+        let s: Box<dyn std::any::Any + Send> = Box::new("boom");
+        assert_eq!(payload_message(s.as_ref()), "boom");
+        let s: Box<dyn std::any::Any + Send> = Box::new(String::from("bang"));
+        assert_eq!(payload_message(s.as_ref()), "bang");
+        let s: Box<dyn std::any::Any + Send> = Box::new(42);
+        assert_eq!(payload_message(s.as_ref()), "unknown panic");
+    }
+
+    #[test]
+    fn test_write_report_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = CrashReport {
+            rule_id: "img-alt".to_string(),
+            uri: "file:///a.html".to_string(),
+            message: "index out of bounds".to_string(),
+        };
+        let path = write_report_in(dir.path(), &report).unwrap();
+        write_report_in(dir.path(), &report).unwrap();
+        let log = std::fs::read_to_string(path).unwrap();
+        assert_eq!(log.lines().count(), 2);
+        assert!(log.contains("rule `img-alt` panicked on file:///a.html: index out of bounds"));
+    }
+}
@@ -0,0 +1,447 @@
+//! What a diagnostic is about, in its `data`, for clients.
+//!
+//! Next to the quick fixes [`crate::quickfix`] keeps there, every diagnostic's
+//! `data` holds the rule that reported it, the rule's WCAG criterion and
+//! level, whether it has a fix and what kind, and the element and explicit
+//! role it points at. Editors can filter and group diagnostics by them
+//! without parsing messages. [`annotate`] adds them once all the rules have
+//! run.
+//!
+//! Each diagnostic also gets a fingerprint for issue trackers to dedupe
+//! findings by. It hashes the rule, a signature of the element (its name,
+//! identifying attributes and text) and the nearest ancestor that's likely
+//! to stay put (one with an `id`, a landmark, or in JSX the enclosing
+//! component), never a line or column, so it survives edits elsewhere in the
+//! file. Identical elements under the same ancestor are told apart by their
+//! order.
+
+use crate::document::Document;
+use crate::engine;
+use crate::quickfix;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata};
+use crate::workspace::fnv1a;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tower_lsp_server::ls_types::{Diagnostic, NumberOrString, Position};
+use tree_sitter::{Node, Point};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticData {
+    pub rule_id: String,
+    pub wcag_criterion: String,
+    /// `"A"`, `"AA"` or `"AAA"`.
+    pub wcag_level: String,
+    pub fixable: bool,
+    /// How the best of the fixes applies, if there are any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix_kind: Option<FixKind>,
+    /// Tag or component name of the element the diagnostic is on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element: Option<String>,
+    /// Its `role` attribute, if it has a literal one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// About the document as a whole rather than an element (see
+    /// [`engine::DOCUMENT_RANGE`]).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub document: bool,
+    /// 16 hex digits that stay the same while the element and its
+    /// surroundings do, wherever they move in the file.
+    #[serde(default)]
+    pub fingerprint: String,
+}
+
+/// Attributes that identify an element, for its signature.
+const SIGNATURE_ATTRS: &[&str] = &[
+    "id",
+    "name",
+    "type",
+    "role",
+    "href",
+    "src",
+    "for",
+    "htmlFor",
+    "aria-label",
+];
+
+/// How much of an element's own text goes into its signature.
+const SIGNATURE_TEXT_CHARS: usize = 40;
+
+/// Elements that mark out a part of the page, as stable ancestors.
+const LANDMARK_TAGS: &[&str] = &[
+    "aside", "dialog", "footer", "form", "header", "main", "nav", "table",
+];
+
+/// What [`annotate`] reads off the element a diagnostic is on.
+#[derive(Default)]
+struct ElementInfo {
+    name: Option<String>,
+    role: Option<String>,
+    signature: String,
+    ancestor: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixKind {
+    /// Applied by `wcag-lsp fix` without review.
+    Safe,
+    /// Offered as a quick fix to review.
+    Review,
+}
+
+/// Adds [`DiagnosticData`] to the `data` of each of `doc`'s diagnostics,
+/// keeping the fixes already there.
+pub fn annotate(doc: &Document, rules: &[Box<dyn Rule>], diagnostics: &mut [Diagnostic]) {
+    let metadata: HashMap<&str, &RuleMetadata> = rules
+        .iter()
+        .map(|rule| (rule.metadata().id, rule.metadata()))
+        .collect();
+
+    // The identity of each diagnostic, and how many identical ones come
+    // before it in the file.
+    let infos: Vec<ElementInfo> = diagnostics
+        .iter()
+        .map(|d| {
+            // The start of a document-level finding is no element's.
+            (!engine::is_document_level(d))
+                .then(|| element_at(doc, d.range.start))
+                .flatten()
+                .unwrap_or_default()
+        })
+        .collect();
+    let keys: Vec<String> = diagnostics
+        .iter()
+        .zip(&infos)
+        .map(|(diagnostic, info)| {
+            let code = match &diagnostic.code {
+                Some(NumberOrString::String(code)) => code.as_str(),
+                _ => "",
+            };
+            let signature = if info.signature.is_empty() {
+                &diagnostic.message
+            } else {
+                &info.signature
+            };
+            format!("{code}\0{signature}\0{}", info.ancestor)
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..diagnostics.len()).collect();
+    order.sort_by_key(|&i| {
+        (
+            diagnostics[i].range.start.line,
+            diagnostics[i].range.start.character,
+        )
+    });
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    let mut fingerprints = vec![String::new(); diagnostics.len()];
+    for i in order {
+        let occurrence = seen.entry(&keys[i]).or_default();
+        let input = format!("{}\0{occurrence}", keys[i]);
+        fingerprints[i] = format!("{:016x}", fnv1a(input.as_bytes()));
+        *occurrence += 1;
+    }
+
+    for ((diagnostic, info), fingerprint) in diagnostics.iter_mut().zip(infos).zip(fingerprints) {
+        let Some(NumberOrString::String(code)) = &diagnostic.code else {
+            continue;
+        };
+        let Some(meta) = metadata.get(code.as_str()) else {
+            continue;
+        };
+        let fixes = quickfix::fixes(diagnostic);
+        let fix_kind = if fixes.iter().any(|fix| fix.safe) {
+            Some(FixKind::Safe)
+        } else if !fixes.is_empty() {
+            Some(FixKind::Review)
+        } else {
+            None
+        };
+        let data = DiagnosticData {
+            rule_id: meta.id.to_string(),
+            wcag_criterion: meta.wcag_criterion.to_string(),
+            wcag_level: format!("{:?}", meta.wcag_level),
+            fixable: fix_kind.is_some(),
+            fix_kind,
+            element: info.name,
+            role: info.role,
+            document: engine::is_document_level(diagnostic),
+            fingerprint,
+        };
+
+        let mut fields = match diagnostic.data.take() {
+            Some(serde_json::Value::Object(fields)) => fields,
+            _ => serde_json::Map::new(),
+        };
+        if let Ok(serde_json::Value::Object(ours)) = serde_json::to_value(data) {
+            fields.extend(ours);
+        }
+        diagnostic.data = Some(serde_json::Value::Object(fields));
+    }
+}
+
+/// The data of `diagnostic`, if it has any.
+pub fn data(diagnostic: &Diagnostic) -> Option<DiagnosticData> {
+    serde_json::from_value(diagnostic.data.clone()?).ok()
+}
+
+/// What's known of the innermost element at `position`, in the file or else
+/// in its tagged templates.
+fn element_at(doc: &Document, position: Position) -> Option<ElementInfo> {
+    let point = Point::new(position.line as usize, position.character as usize);
+    let in_tree = |root: Node, source: &str| {
+        let mut node = root.descendant_for_point_range(point, point)?;
+        loop {
+            if let Some((name, role)) = element(&node, source) {
+                return Some(ElementInfo {
+                    signature: signature(&node, source, name.as_deref()),
+                    ancestor: stable_ancestor(&node, source),
+                    name,
+                    role,
+                });
+            }
+            node = node.parent()?;
+        }
+    };
+    in_tree(doc.tree.root_node(), &doc.source).or_else(|| {
+        let templates = doc.templates.as_ref()?;
+        in_tree(templates.tree.root_node(), &templates.source)
+    })
+}
+
+/// The name and explicit role of `node`, if it's an element.
+fn element(node: &Node, source: &str) -> Option<(Option<String>, Option<String>)> {
+    let attrs = element_attrs(node, source)?;
+    let name = element_name(node, source);
+    let role = attrs
+        .iter()
+        .find(|(name, _)| name == "role")
+        .and_then(|(_, value)| first_role(value.as_deref()?));
+    Some((name, role))
+}
+
+/// The tag or component name of element `node`.
+fn element_name(node: &Node, source: &str) -> Option<String> {
+    match node.kind() {
+        "jsx_element" | "jsx_self_closing_element" => jsx_tag(node)?
+            .child_by_field_name("name")
+            .map(|name| source[name.byte_range()].to_string()),
+        _ => {
+            let tag = html_attrs::element_tag(node)?;
+            html_attrs::tag_name(&tag, source).map(str::to_ascii_lowercase)
+        }
+    }
+}
+
+/// The literal attributes of element `node`, or `None` if it isn't one.
+/// HTML attribute names are lowercased.
+fn element_attrs(node: &Node, source: &str) -> Option<Vec<(String, Option<String>)>> {
+    match node.kind() {
+        "element" | "script_element" | "style_element" => {
+            let tag = html_attrs::element_tag(node)?;
+            Some(
+                html_attrs::attrs(&tag, source)
+                    .into_iter()
+                    .filter(|attr| !attr.bound)
+                    .map(|attr| (attr.name_lower(), attr.value))
+                    .collect(),
+            )
+        }
+        "jsx_element" | "jsx_self_closing_element" => {
+            let tag = jsx_tag(node)?;
+            let mut cursor = tag.walk();
+            let attrs = tag
+                .named_children(&mut cursor)
+                .filter_map(|attr| jsx_attrs::jsx_attribute(&attr, source))
+                .map(|(name, value)| {
+                    let value = value.filter(|value| value.kind() == "string").map(|value| {
+                        source[value.byte_range()]
+                            .trim_matches(['"', '\''])
+                            .to_string()
+                    });
+                    (name.to_string(), value)
+                })
+                .collect();
+            Some(attrs)
+        }
+        _ => None,
+    }
+}
+
+/// The opening (or self-closing) tag of a JSX element.
+fn jsx_tag<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    if node.kind() == "jsx_element" {
+        node.child(0)
+    } else {
+        Some(*node)
+    }
+}
+
+/// `name[attr=value]…"text"` for element `node`, from the attributes that
+/// identify it and the start of its own text.
+fn signature(node: &Node, source: &str, name: Option<&str>) -> String {
+    let mut signature = name.unwrap_or_default().to_string();
+    for (attr, value) in element_attrs(node, source).unwrap_or_default() {
+        if SIGNATURE_ATTRS.contains(&attr.as_str()) {
+            signature.push_str(&format!("[{attr}={}]", value.unwrap_or_default()));
+        }
+    }
+    let mut cursor = node.walk();
+    let text: Vec<&str> = node
+        .children(&mut cursor)
+        .filter(|child| matches!(child.kind(), "text" | "jsx_text"))
+        .flat_map(|child| source[child.byte_range()].split_whitespace())
+        .collect();
+    let text: String = text.join(" ").chars().take(SIGNATURE_TEXT_CHARS).collect();
+    if !text.is_empty() {
+        signature.push_str(&format!("\"{text}\""));
+    }
+    signature
+}
+
+/// The closest ancestor of `node` that edits are unlikely to move or rename:
+/// `#id` for one with an `id`, the tag name of a landmark, or the JSX
+/// component the element is returned from. Empty if there's none.
+fn stable_ancestor(node: &Node, source: &str) -> String {
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if let Some(attrs) = element_attrs(&ancestor, source) {
+            if let Some((_, Some(id))) = attrs.iter().find(|(name, _)| name == "id") {
+                return format!("#{id}");
+            }
+            if let Some(name) = element_name(&ancestor, source)
+                && LANDMARK_TAGS.contains(&name.as_str())
+            {
+                return name;
+            }
+        }
+        if matches!(
+            ancestor.kind(),
+            "function_declaration"
+                | "variable_declarator"
+                | "method_definition"
+                | "class_declaration"
+        ) && let Some(name) = ancestor.child_by_field_name("name")
+        {
+            return source[name.byte_range()].to_string();
+        }
+        current = ancestor.parent();
+    }
+    String::new()
+}
+
+/// The role that applies of a space-separated fallback list.
+fn first_role(roles: &str) -> Option<String> {
+    roles
+        .split_whitespace()
+        .next()
+        .map(|role| role.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::document::DocumentManager;
+    use crate::rules;
+
+    fn lint(uri: &str, source: &str) -> Vec<Diagnostic> {
+        let mut mgr = DocumentManager::new();
+        let doc = mgr.open(uri.to_string(), source.to_string(), 1).unwrap();
+        engine::run_diagnostics(doc, &rules::all_rules(), &Config::default())
+    }
+
+    fn find<'a>(diagnostics: &'a [Diagnostic], rule_id: &str) -> &'a Diagnostic {
+        diagnostics
+            .iter()
+            .find(|d| d.code == Some(NumberOrString::String(rule_id.to_string())))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_data_describes_rule_and_element() {
+        let diagnostics = lint(
+            "file:///a.html",
+            r#"<div role="navigation menu" aria-label=""><img src="a.png"></div>"#,
+        );
+        let data = data(find(&diagnostics, "img-alt")).unwrap();
+        assert_eq!(data.rule_id, "img-alt");
+        assert_eq!(data.wcag_criterion, "1.1.1");
+        assert_eq!(data.wcag_level, "A");
+        assert_eq!(data.element.as_deref(), Some("img"));
+        assert_eq!(data.role, None);
+    }
+
+    #[test]
+    fn test_document_level_data_names_no_element() {
+        let diagnostics = lint("file:///a.html", "<html><head></head><body></body></html>");
+        let data = data(find(&diagnostics, "page-title")).unwrap();
+        assert!(data.document);
+        assert_eq!(data.element, None);
+        assert!(!self::data(find(&diagnostics, "html-lang")).unwrap().document);
+    }
+
+    #[test]
+    fn test_data_keeps_fixes() {
+        let diagnostics = lint(
+            "file:///a.html",
+            r#"<input role="searchbox" name="q" required aria-required="true">"#,
+        );
+        let diagnostic = find(&diagnostics, "no-redundant-aria-state");
+        let data = data(diagnostic).unwrap();
+        assert_eq!(data.element.as_deref(), Some("input"));
+        assert_eq!(data.role.as_deref(), Some("searchbox"));
+        assert!(data.fixable);
+        assert_eq!(data.fix_kind, Some(FixKind::Safe));
+        assert_eq!(quickfix::fixes(diagnostic).len(), 1);
+    }
+
+    fn fingerprints(uri: &str, source: &str) -> Vec<String> {
+        let mut diagnostics = lint(uri, source);
+        diagnostics.sort_by_key(|d| (d.range.start.line, d.range.start.character));
+        diagnostics
+            .iter()
+            .filter(|d| d.code == Some(NumberOrString::String("img-alt".to_string())))
+            .map(|d| data(d).unwrap().fingerprint)
+            .collect()
+    }
+
+    #[test]
+    fn test_fingerprints_survive_moves() {
+        let source = r#"<main><img src="a.png"><img src="b.png"></main>"#;
+        let before = fingerprints("file:///a.html", source);
+        assert_eq!(before.len(), 2);
+        assert_eq!(before[0].len(), 16);
+        assert_ne!(before[0], before[1]);
+
+        let shifted = format!("<!-- header -->\n<p>Intro</p>\n{source}");
+        assert_eq!(fingerprints("file:///a.html", &shifted), before);
+        let moved = r#"<main><img src="a.png"></main>"#;
+        assert_ne!(fingerprints("file:///a.html", moved)[0], before[1]);
+    }
+
+    #[test]
+    fn test_identical_elements_differ_by_order() {
+        let source = r#"<nav><img src="a.png"><img src="a.png"></nav>"#;
+        let prints = fingerprints("file:///a.html", source);
+        assert_eq!(prints.len(), 2);
+        assert_ne!(prints[0], prints[1]);
+
+        let other = r#"<footer><img src="a.png"><img src="a.png"></footer>"#;
+        assert_ne!(fingerprints("file:///a.html", other)[0], prints[0]);
+    }
+
+    #[test]
+    fn test_jsx_fingerprint_uses_component() {
+        let a = "function Logo() {\n  return <img src=\"a.png\" />;\n}\n";
+        let b = format!("import x from 'x';\n\n{a}");
+        let renamed = a.replace("Logo", "Brand");
+        let prints = fingerprints("file:///a.tsx", a);
+        assert_eq!(prints.len(), 1);
+        assert_eq!(fingerprints("file:///a.tsx", &b), prints);
+        assert_ne!(fingerprints("file:///a.tsx", &renamed), prints);
+    }
+}
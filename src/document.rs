@@ -1,4 +1,5 @@
 use crate::parser::{self, FileType};
+use crate::tagged_templates::{self, TaggedTemplates};
 use std::collections::HashMap;
 use tree_sitter::{Parser, Tree};
 
@@ -8,7 +9,12 @@ pub struct Document {
     pub file_type: FileType,
     pub source: String,
     pub tree: Tree,
+    /// The markup of the `` html`…` `` templates of a JS/TS file.
+    pub templates: Option<TaggedTemplates>,
     pub version: i32,
+    /// Whether this is a DOM snapshot to check with the dynamic rules as
+    /// well.
+    pub rendered: bool,
 }
 
 #[derive(Default)]
@@ -40,15 +46,30 @@ impl DocumentManager {
     }
 
     pub fn open(&mut self, uri: String, text: String, version: i32) -> Option<&Document> {
-        let file_type = FileType::from_uri(&uri);
+        let file_type = FileType::detect(&uri, &text);
+        self.open_as(uri, text, version, file_type)
+    }
+
+    /// Open a document with an already-resolved file type (e.g. from the
+    /// `[filetypes]` config mapping).
+    pub fn open_as(
+        &mut self,
+        uri: String,
+        text: String,
+        version: i32,
+        file_type: FileType,
+    ) -> Option<&Document> {
         let parser = self.get_or_create_parser(file_type)?;
-        let tree = parser.parse(&text, None)?;
+        let tree = parser.parse(parser::parse_input(file_type, &text).as_ref(), None)?;
+        let templates = tagged_templates::parse(file_type, &tree, &text);
         let doc = Document {
             uri: uri.clone(),
             file_type,
             source: text,
             tree,
+            templates,
             version,
+            rendered: false,
         };
         self.documents.insert(uri.clone(), doc);
         self.documents.get(&uri)
@@ -64,11 +85,13 @@ impl DocumentManager {
         }
 
         let parser = self.parsers.get_mut(&file_type)?;
-        let tree = parser.parse(&text, None)?;
+        let tree = parser.parse(parser::parse_input(file_type, &text).as_ref(), None)?;
 
+        let templates = tagged_templates::parse(file_type, &tree, &text);
         let doc = self.documents.get_mut(uri)?;
         doc.source = text;
         doc.tree = tree;
+        doc.templates = templates;
         doc.version = version;
         Some(doc)
     }
@@ -80,6 +103,23 @@ impl DocumentManager {
     pub fn get(&self, uri: &str) -> Option<&Document> {
         self.documents.get(uri)
     }
+
+    /// The URI and version of every open document.
+    pub fn versions(&self) -> Vec<(String, i32)> {
+        self.documents
+            .values()
+            .map(|doc| (doc.uri.clone(), doc.version))
+            .collect()
+    }
+
+    /// Number of parsed documents currently held.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -107,6 +147,40 @@ mod tests {
         assert!(doc.is_none());
     }
 
+    #[test]
+    fn test_open_unusual_extension_sniffs_content() {
+        let mut mgr = DocumentManager::new();
+        let doc = mgr.open(
+            "file:///partials/header.inc".to_string(),
+            "<!DOCTYPE html>\n<header><img src=\"logo.png\"></header>".to_string(),
+            1,
+        );
+        assert_eq!(doc.map(|d| d.file_type), Some(FileType::Html));
+    }
+
+    #[test]
+    fn test_open_as_uses_given_file_type() {
+        let mut mgr = DocumentManager::new();
+        let doc = mgr.open_as(
+            "file:///page.tpl".to_string(),
+            "{{ title }}".to_string(),
+            1,
+            FileType::Html,
+        );
+        assert_eq!(doc.map(|d| d.file_type), Some(FileType::Html));
+    }
+
+    #[test]
+    fn test_open_untitled_document() {
+        let mut mgr = DocumentManager::new();
+        let config = crate::config::Config::default();
+        let uri = "untitled:Untitled-1";
+        let text = "<img src=\"x.png\">";
+        let file_type = config.file_type_for(uri, Some("html"), text);
+        let doc = mgr.open_as(uri.to_string(), text.to_string(), 1, file_type);
+        assert_eq!(doc.map(|d| d.file_type), Some(FileType::Html));
+    }
+
     #[test]
     fn test_update_document() {
         let mut mgr = DocumentManager::new();
@@ -168,7 +242,9 @@ mod tests {
     fn test_close_document() {
         let mut mgr = DocumentManager::new();
         mgr.open("file:///test.html".to_string(), "<img>".to_string(), 1);
+        assert_eq!(mgr.len(), 1);
         mgr.close("file:///test.html");
         assert!(mgr.get("file:///test.html").is_none());
+        assert!(mgr.is_empty());
     }
 }
@@ -1,44 +1,263 @@
+use crate::analysis::Analysis;
+use crate::component_map::{self, MappedSource};
 use crate::config::Config;
+use crate::crash::{self, CrashReport};
+use crate::diagnostic_data;
 use crate::document::Document;
 use crate::inline_directives::InlineDirectives;
+use crate::jsx_spread;
+use crate::parser::FileType;
 use crate::rules::{Rule, Severity};
+use crate::storybook;
+use crate::tagged_templates::TaggedTemplates;
+use std::panic::AssertUnwindSafe;
 use tower_lsp_server::ls_types::*;
 
+/// The trees the rules run over: the file itself, unless it's plain JS/TS,
+/// and the markup of its tagged templates, if any, with their mapped
+/// components renamed to their elements.
+pub struct Trees<'d> {
+    doc: &'d Document,
+    mapped: Option<MappedSource>,
+    templates: Option<(&'d TaggedTemplates, Option<MappedSource>)>,
+}
+
+impl<'d> Trees<'d> {
+    pub fn new(doc: &'d Document, config: &Config) -> Self {
+        let mapped = match doc.file_type.is_script() {
+            true => None,
+            false => component_map::map(
+                doc.file_type,
+                &doc.tree,
+                &doc.source,
+                &config.components,
+                config.styled_components,
+            ),
+        };
+        let templates = doc.templates.as_ref().map(|templates| {
+            let mapped = component_map::map(
+                FileType::HtmlTemplate,
+                &templates.tree,
+                &templates.source,
+                &config.components,
+                false,
+            );
+            (templates, mapped)
+        });
+        Trees {
+            doc,
+            mapped,
+            templates,
+        }
+    }
+
+    /// An analysis of each tree, with the mapping to remap its positions to
+    /// the file by if it's a renamed copy.
+    pub fn analyses(&self) -> Vec<(Analysis<'_>, Option<&MappedSource>)> {
+        let mut analyses = Vec::new();
+        if !self.doc.file_type.is_script() {
+            analyses.push(match &self.mapped {
+                Some(mapped) => (
+                    Analysis::new(mapped.tree.root_node(), &mapped.source, self.doc.file_type),
+                    Some(mapped),
+                ),
+                None => (
+                    Analysis::new(
+                        self.doc.tree.root_node(),
+                        &self.doc.source,
+                        self.doc.file_type,
+                    ),
+                    None,
+                ),
+            });
+        }
+        if let Some((templates, mapped)) = &self.templates {
+            analyses.push(match mapped {
+                Some(mapped) => (
+                    Analysis::new(
+                        mapped.tree.root_node(),
+                        &mapped.source,
+                        FileType::HtmlTemplate,
+                    ),
+                    Some(mapped),
+                ),
+                None => (
+                    Analysis::new(
+                        templates.tree.root_node(),
+                        &templates.source,
+                        FileType::HtmlTemplate,
+                    ),
+                    None,
+                ),
+            });
+        }
+        analyses
+    }
+}
+
 pub fn run_diagnostics(
     doc: &Document,
     rules: &[Box<dyn Rule>],
     config: &Config,
 ) -> Vec<Diagnostic> {
+    let (diagnostics, crashes) = run_diagnostics_checked(doc, rules, config);
+    for crash in &crashes {
+        crash::write_report(crash);
+    }
+    diagnostics
+}
+
+/// Like [`run_diagnostics`], but also returns a report for every rule that
+/// panicked. A panicking rule contributes no diagnostics; the others still run.
+pub fn run_diagnostics_checked(
+    doc: &Document,
+    rules: &[Box<dyn Rule>],
+    config: &Config,
+) -> (Vec<Diagnostic>, Vec<CrashReport>) {
     let mut diagnostics = Vec::new();
+    let mut crashes = Vec::new();
     let directives = InlineDirectives::parse(&doc.source);
+    let story = storybook::is_story_file(&doc.uri);
+    // Shared by the rules, and built as they ask for it.
+    let trees = Trees::new(doc, config);
+    let analyses = trees.analyses();
 
     for rule in rules {
         let meta = rule.metadata();
 
+        if story && rule.page_level() {
+            continue;
+        }
+
+        if rule.dynamic() && !doc.rendered {
+            continue;
+        }
+
         if directives.disables_file_rule(meta.id, meta.wcag_level) {
             continue;
         }
 
-        let severity = match config.effective_severity(meta.id, meta.wcag_level) {
+        let severity = match config.rule_severity(rule.as_ref()) {
             Some(s) => s,
             None => continue,
         };
         let lsp_severity = match severity {
+            _ if rule.informational() => DiagnosticSeverity::INFORMATION,
             Severity::Error => DiagnosticSeverity::ERROR,
             Severity::Warning => DiagnosticSeverity::WARNING,
         };
 
-        let mut rule_diags = rule.check(&doc.tree.root_node(), &doc.source, doc.file_type);
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut diags = Vec::new();
+            for (analysis, mapped) in &analyses {
+                let mut found = rule.check_with_analysis(analysis, config);
+                if let Some(mapped) = mapped {
+                    found.iter_mut().for_each(|diag| mapped.remap(diag));
+                }
+                diags.extend(found);
+            }
+            diags
+        }));
+        let mut rule_diags = match result {
+            Ok(diags) => diags,
+            Err(payload) => {
+                let message = crash::take_last_panic()
+                    .unwrap_or_else(|| crash::payload_message(payload.as_ref()));
+                crashes.push(CrashReport {
+                    rule_id: meta.id.to_string(),
+                    uri: doc.uri.clone(),
+                    message,
+                });
+                continue;
+            }
+        };
         rule_diags.retain(|diag| {
             !directives.disables_line_rule(diag.range.start.line, meta.id, meta.wcag_level)
         });
 
         for diag in &mut rule_diags {
-            diag.severity = Some(lsp_severity);
+            // A rule's secondary suggestions stay hints whatever its severity.
+            if diag.severity != Some(DiagnosticSeverity::HINT) {
+                diag.severity = Some(lsp_severity);
+            }
+            resolve_related(diag, &doc.uri);
         }
         diagnostics.extend(rule_diags);
     }
-    diagnostics
+    if story {
+        storybook::drop_resolved_by_args(&doc.tree.root_node(), &doc.source, &mut diagnostics);
+    }
+    if doc.file_type.is_jsx_like() {
+        jsx_spread::apply(
+            &doc.tree.root_node(),
+            &doc.source,
+            config.spread_props,
+            story,
+            &mut diagnostics,
+        );
+    }
+    diagnostic_data::annotate(doc, rules, &mut diagnostics);
+    (diagnostics, crashes)
+}
+
+/// Placeholder URI of related locations in the checked document itself,
+/// which rules don't know the URI of; the engine swaps in the real one.
+const SAME_DOCUMENT: &str = "wcag-lsp:same-document";
+
+/// A related location at `node` in the document being checked.
+pub fn related_location(node: &tree_sitter::Node, message: &str) -> DiagnosticRelatedInformation {
+    DiagnosticRelatedInformation {
+        location: Location {
+            uri: SAME_DOCUMENT.parse().expect("valid URI"),
+            range: node_to_range(node),
+        },
+        message: message.to_string(),
+    }
+}
+
+/// Points the related locations of `diag` at `uri`, a URI or (from the CLI)
+/// a file path. They're dropped if neither gives a valid URI.
+fn resolve_related(diag: &mut Diagnostic, uri: &str) {
+    let Some(related) = &mut diag.related_information else {
+        return;
+    };
+    let Some(resolved) = document_uri(uri) else {
+        diag.related_information = None;
+        return;
+    };
+    for info in related {
+        if info.location.uri.as_str() == SAME_DOCUMENT {
+            info.location.uri = resolved.clone();
+        }
+    }
+}
+
+/// `uri` as a URI; from the CLI it's a file path.
+pub fn document_uri(uri: &str) -> Option<Uri> {
+    uri.parse::<Uri>()
+        .ok()
+        .or_else(|| std::path::absolute(uri).ok().and_then(Uri::from_file_path))
+}
+
+/// The range of a finding about the document as a whole, such as a missing
+/// `<title>` or no way to skip to the content, rather than about one of its
+/// elements: empty, at the very start. Reports list these apart from the
+/// others, and the data of such a diagnostic names no element.
+pub const DOCUMENT_RANGE: Range = Range {
+    start: Position {
+        line: 0,
+        character: 0,
+    },
+    end: Position {
+        line: 0,
+        character: 0,
+    },
+};
+
+/// Whether `diagnostic` is about the document as a whole (see
+/// [`DOCUMENT_RANGE`]).
+pub fn is_document_level(diagnostic: &Diagnostic) -> bool {
+    diagnostic.range == DOCUMENT_RANGE
 }
 
 pub fn node_to_range(node: &tree_sitter::Node) -> Range {
@@ -55,3 +274,136 @@ pub fn node_to_range(node: &tree_sitter::Node) -> Range {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::DocumentManager;
+    use crate::parser::FileType;
+    use crate::rules::{RuleMetadata, WcagLevel};
+    use tree_sitter::Node;
+
+    struct PanickingRule;
+
+    static PANICKING_METADATA: RuleMetadata = RuleMetadata {
+        id: "test-panics",
+        description: "always panics",
+        wcag_level: WcagLevel::A,
+        wcag_criterion: "4.1.2",
+        wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+        default_severity: Severity::Error,
+        axe_rules: &[],
+    };
+
+    impl Rule for PanickingRule {
+        fn metadata(&self) -> &RuleMetadata {
+            &PANICKING_METADATA
+        }
+
+        fn check(&self, _root: &Node, _source: &str, _file_type: FileType) -> Vec<Diagnostic> {
+            panic!("rule blew up");
+        }
+    }
+
+    #[test]
+    fn test_panicking_rule_is_isolated() {
+        let mut mgr = DocumentManager::new();
+        let doc = mgr
+            .open(
+                "file:///crash.html".to_string(),
+                "<img src=\"x.png\">".to_string(),
+                1,
+            )
+            .unwrap();
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(PanickingRule),
+            Box::new(crate::rules::img_alt::ImgAlt),
+        ];
+
+        let (diagnostics, crashes) = run_diagnostics_checked(doc, &rules, &Config::default());
+
+        assert_eq!(diagnostics.len(), 1, "img-alt must still run");
+        assert_eq!(crashes.len(), 1);
+        assert_eq!(crashes[0].rule_id, "test-panics");
+        assert_eq!(crashes[0].uri, "file:///crash.html");
+        assert!(crashes[0].message.contains("rule blew up"));
+    }
+
+    #[test]
+    fn test_related_locations_point_at_the_document() {
+        let mut mgr = DocumentManager::new();
+        let html = "<body><h1>A</h1><h1>B</h1></body>";
+        let rules = crate::rules::all_rules();
+        let config = Config::default();
+
+        let doc = mgr
+            .open("file:///page.html".to_string(), html.to_string(), 1)
+            .unwrap();
+        let diagnostics = run_diagnostics(doc, &rules, &config);
+        let related = diagnostics
+            .iter()
+            .find_map(|d| d.related_information.as_ref())
+            .unwrap();
+        assert_eq!(related[0].location.uri.as_str(), "file:///page.html");
+
+        let doc = mgr
+            .open_as("page.html".to_string(), html.to_string(), 1, FileType::Html)
+            .unwrap();
+        let diagnostics = run_diagnostics(doc, &rules, &config);
+        let related = diagnostics
+            .iter()
+            .find_map(|d| d.related_information.as_ref())
+            .unwrap();
+        assert!(related[0].location.uri.as_str().starts_with("file:///"));
+        assert!(related[0].location.uri.as_str().ends_with("/page.html"));
+    }
+
+    #[test]
+    fn test_informational_rules_report_information() {
+        let mut mgr = DocumentManager::new();
+        let doc = mgr
+            .open(
+                "file:///signup.html".to_string(),
+                r#"<div class="g-recaptcha" data-sitekey="abc"></div>"#.to_string(),
+                1,
+            )
+            .unwrap();
+        let config = Config::parse("[rules]\ncaptcha-alternative = \"error\"\n");
+
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(
+            crate::rules::captcha_alternative::CaptchaAlternative,
+        )];
+        let diagnostics = run_diagnostics(doc, &rules, &config);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].severity,
+            Some(DiagnosticSeverity::INFORMATION)
+        );
+    }
+
+    #[test]
+    fn test_secondary_hints_keep_their_severity() {
+        let mut mgr = DocumentManager::new();
+        let doc = mgr
+            .open(
+                "file:///icons.html".to_string(),
+                r#"<svg role="img"></svg><svg><path d="M0"/></svg>"#.to_string(),
+                1,
+            )
+            .unwrap();
+        let config = Config::parse("[rules]\nsvg-img-alt = \"warning\"\n");
+
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(crate::rules::svg_img_alt::SvgImgAlt)];
+        let diagnostics = run_diagnostics(doc, &rules, &config);
+
+        let severities: Vec<_> = diagnostics.iter().map(|d| d.severity).collect();
+        assert_eq!(
+            severities,
+            vec![
+                Some(DiagnosticSeverity::WARNING),
+                Some(DiagnosticSeverity::HINT)
+            ]
+        );
+    }
+}
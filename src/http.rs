@@ -0,0 +1,364 @@
+//! `wcag-lsp serve-http`: the linter as a small JSON API, for CMS preview
+//! hooks and browser extensions that can't speak LSP.
+//!
+//! `POST /lint` takes `{"source": "...", "filetype": "html"}` (or a `path`
+//! to pick the file type from, as `check` would) and answers with the same
+//! JSON as `wcag-lsp check --format json`. The server only listens on
+//! localhost and speaks just enough HTTP/1.1 for that one endpoint: one
+//! request per connection, no chunked bodies. Cross-origin requests are
+//! refused unless an origin is allowed with `--allow-origin`.
+
+use crate::config::Config;
+use crate::parser::FileType;
+use crate::report;
+use crate::rules::{self, Rule};
+use crate::scan;
+use crate::score::Tally;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::Duration;
+
+pub const DEFAULT_PORT: u16 = 8787;
+/// `[clients]` key for diagnostics served over HTTP.
+pub const HTTP_CLIENT: &str = "http";
+/// Larger request bodies are refused.
+const MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+/// Longer request or header lines are refused.
+const MAX_LINE_BYTES: u64 = 8 * 1024;
+/// Larger header blocks are refused.
+const MAX_HEADER_BYTES: u64 = 64 * 1024;
+/// A client that sends or reads nothing for this long is dropped.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Deserialize)]
+struct LintRequest {
+    source: String,
+    #[serde(default)]
+    filetype: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: Option<Value>,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Response {
+            status,
+            body: Some(json!({ "error": message })),
+        }
+    }
+}
+
+/// Listens on `127.0.0.1:port` until the process is stopped, linting with
+/// `config`. Requests are handled by a fixed pool of workers, so a flood of
+/// connections waits in the accept queue instead of spawning threads.
+/// `allow_origin` is the one origin a browser may call from, if any.
+pub fn serve(port: u16, config: Config, allow_origin: Option<String>) -> std::io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    eprintln!("wcag-lsp listening on http://127.0.0.1:{port}");
+    let config = Arc::new(config);
+    let rules: Arc<Vec<Box<dyn Rule>>> = Arc::new(rules::all_rules());
+    let allow_origin = Arc::new(allow_origin);
+    let workers = scan::default_workers();
+    let (tx, rx) = mpsc::sync_channel::<TcpStream>(workers);
+    let rx = Arc::new(Mutex::new(rx));
+    for _ in 0..workers {
+        let (config, rules, allow_origin) = (
+            Arc::clone(&config),
+            Arc::clone(&rules),
+            Arc::clone(&allow_origin),
+        );
+        let rx = Arc::clone(&rx);
+        std::thread::spawn(move || {
+            loop {
+                let Ok(stream) = rx.lock().unwrap().recv() else {
+                    break;
+                };
+                if let Err(e) = handle(stream, &config, &rules, allow_origin.as_deref()) {
+                    eprintln!("Request failed: {e}");
+                }
+            }
+        });
+    }
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        // Blocks while every worker is busy.
+        if tx.send(stream).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn handle(
+    mut stream: TcpStream,
+    config: &Config,
+    rules: &[Box<dyn Rule>],
+    allow_origin: Option<&str>,
+) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let response = match read_request(&mut stream)? {
+        Ok(request) => respond(&request.method, &request.path, &request.body, config, rules),
+        Err(response) => response,
+    };
+    write_response(&mut stream, &response, allow_origin)
+}
+
+/// The request on `stream`, or the error response for one that can't be
+/// read.
+fn read_request(stream: impl Read) -> std::io::Result<Result<Request, Response>> {
+    let mut reader = BufReader::new(stream);
+    let Some(line) = read_line(&mut reader, MAX_LINE_BYTES)? else {
+        return Ok(Err(Response::error(400, "request line too long")));
+    };
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Ok(Err(Response::error(400, "malformed request line")));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0;
+    let mut header_bytes = 0;
+    loop {
+        let Some(header) = read_line(&mut reader, MAX_LINE_BYTES)? else {
+            return Ok(Err(Response::error(431, "header line too long")));
+        };
+        header_bytes += header.len() as u64;
+        if header_bytes > MAX_HEADER_BYTES {
+            return Ok(Err(Response::error(431, "headers too large")));
+        }
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            match value.trim().parse::<usize>() {
+                Ok(length) => content_length = length,
+                Err(_) => return Ok(Err(Response::error(400, "invalid Content-Length"))),
+            }
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Ok(Err(Response::error(413, "request body too large")));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Ok(Request { method, path, body }))
+}
+
+/// The next line of `reader`, `None` if it's longer than `limit` bytes. Empty
+/// at the end of the stream.
+fn read_line(reader: &mut impl BufRead, limit: u64) -> std::io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.take(limit + 1).read_line(&mut line)?;
+    if line.len() as u64 > limit {
+        return Ok(None);
+    }
+    Ok(Some(line))
+}
+
+/// The response to one request.
+fn respond(
+    method: &str,
+    path: &str,
+    body: &[u8],
+    config: &Config,
+    rules: &[Box<dyn Rule>],
+) -> Response {
+    let path = path.split('?').next().unwrap_or(path);
+    match (method, path) {
+        // CORS preflight, for browser extensions and preview pages; answered
+        // with the allowed origin, if there is one.
+        ("OPTIONS", _) => Response {
+            status: 204,
+            body: None,
+        },
+        ("POST", "/lint") => lint(body, config, rules),
+        (_, "/lint") => Response::error(405, "use POST"),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn lint(body: &[u8], config: &Config, rules: &[Box<dyn Rule>]) -> Response {
+    let request: LintRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return Response::error(400, &format!("invalid request: {e}")),
+    };
+    let path = request.path.as_deref().unwrap_or("");
+    let file_type = match request.filetype.as_deref() {
+        Some(name) => FileType::from_name(name),
+        None => config.file_type_for(path, None, &request.source),
+    };
+    if file_type == FileType::Unknown {
+        return Response::error(400, "unknown filetype");
+    }
+
+    let (mut diagnostics, _) = scan::lint_source(path, &request.source, file_type, rules, config);
+    config.present(&mut diagnostics, HTTP_CLIENT);
+    let elements = scan::count_elements(file_type, &request.source);
+    let score = Tally::of(&diagnostics, elements, rules).score();
+    let name = if path.is_empty() { "<input>" } else { path };
+    let results = BTreeMap::from([(name.to_string(), diagnostics)]);
+    Response {
+        status: 200,
+        body: Some(report::json(&results, rules, &score)),
+    }
+}
+
+fn write_response(
+    stream: &mut impl Write,
+    response: &Response,
+    allow_origin: Option<&str>,
+) -> std::io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Error",
+    };
+    let body = response
+        .body
+        .as_ref()
+        .map(|body| body.to_string())
+        .unwrap_or_default();
+    let cors = allow_origin
+        .map(|origin| {
+            format!(
+                "Access-Control-Allow-Origin: {origin}\r\n\
+                 Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+                 Access-Control-Allow-Headers: Content-Type\r\n"
+            )
+        })
+        .unwrap_or_default();
+    write!(
+        stream,
+        "HTTP/1.1 {} {reason}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         {cors}\
+         Connection: close\r\n\r\n{body}",
+        response.status,
+        body.len()
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(body: &str) -> Response {
+        let rules = rules::all_rules();
+        respond("POST", "/lint", body.as_bytes(), &Config::default(), &rules)
+    }
+
+    #[test]
+    fn test_lint_fragment() {
+        let response = post(r#"{"source": "<img src=\"a.png\">", "filetype": "html"}"#);
+        assert_eq!(response.status, 200);
+        let body = response.body.unwrap();
+        let result = &body["results"][0];
+        assert_eq!(result["ruleId"], "img-alt");
+        assert_eq!(result["file"], "<input>");
+        assert_eq!(result["wcagCriterion"], "1.1.1");
+    }
+
+    #[test]
+    fn test_file_type_from_path() {
+        let response = post(r#"{"source": "const A = () => <img />;", "path": "a.tsx"}"#);
+        assert_eq!(response.body.unwrap()["results"][0]["file"], "a.tsx");
+    }
+
+    #[test]
+    fn test_bad_requests() {
+        assert_eq!(post("not json").status, 400);
+        assert_eq!(post(r#"{"source": "x", "filetype": "cobol"}"#).status, 400);
+        let rules = rules::all_rules();
+        let config = Config::default();
+        assert_eq!(respond("GET", "/lint", b"", &config, &rules).status, 405);
+        assert_eq!(respond("GET", "/", b"", &config, &rules).status, 404);
+        assert_eq!(
+            respond("OPTIONS", "/lint", b"", &config, &rules).status,
+            204
+        );
+    }
+
+    #[test]
+    fn test_serves_over_tcp() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            handle(stream, &Config::default(), &rules::all_rules(), None).unwrap();
+        });
+
+        let body = r#"{"source": "<html><body></body></html>", "filetype": "html"}"#;
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(
+            stream,
+            "POST /lint HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"ruleId\":\"html-lang\""));
+        assert!(!response.contains("Access-Control-Allow-Origin"));
+    }
+
+    #[test]
+    fn test_oversized_headers() {
+        let status = |request: String| match read_request(request.as_bytes()).unwrap() {
+            Ok(_) => 200,
+            Err(response) => response.status,
+        };
+        let long = "x".repeat(MAX_LINE_BYTES as usize);
+        assert_eq!(status(format!("GET /{long} HTTP/1.1\r\n\r\n")), 400);
+        assert_eq!(
+            status(format!("GET / HTTP/1.1\r\nX-A: {long}\r\n\r\n")),
+            431
+        );
+        let many = "X-A: b\r\n".repeat(MAX_HEADER_BYTES as usize / 8);
+        assert_eq!(status(format!("GET / HTTP/1.1\r\n{many}\r\n")), 431);
+        assert_eq!(status("GET / HTTP/1.1\r\nX-A: b\r\n\r\n".to_string()), 200);
+    }
+
+    #[test]
+    fn test_allowed_origin() {
+        let response = Response {
+            status: 204,
+            body: None,
+        };
+        let mut out = Vec::new();
+        write_response(&mut out, &response, None).unwrap();
+        assert!(!String::from_utf8(out).unwrap().contains("Access-Control"));
+        let mut out = Vec::new();
+        write_response(&mut out, &response, Some("https://cms.example")).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Access-Control-Allow-Origin: https://cms.example\r\n"));
+    }
+}
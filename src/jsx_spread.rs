@@ -0,0 +1,321 @@
+//! Props forwarded with a JSX spread (`<img {...imgProps} />`).
+//!
+//! Rules only see the attributes written on an element, so an `alt` or
+//! `aria-label` that comes in through a spread looks missing. After the rules
+//! ran, the engine hands the diagnostics of rules a prop can satisfy
+//! ([`PROP_RULES`]) on elements with a spread to [`apply`], which follows the
+//! configured [`SpreadPolicy`]. Resolving looks for the spread object in the
+//! file: an object literal spread in place (`{...{ alt: "" }}`), or a variable
+//! declared once with one (`const imgProps = { alt: "Logo", src }`).
+//!
+//! The args spreads of Storybook stories are left to
+//! [`storybook::drop_resolved_by_args`], which knows their args.
+
+use crate::config::SpreadPolicy;
+use crate::storybook;
+use tower_lsp_server::ls_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position};
+use tree_sitter::Node;
+
+/// Rules whose requirement a prop can meet, and the props that meet it.
+pub const PROP_RULES: &[(&str, &[&str])] = &[
+    (
+        "anchor-content",
+        &["children", "aria-label", "aria-labelledby", "title"],
+    ),
+    ("area-alt", &["alt", "aria-label", "aria-labelledby"]),
+    ("autoplay-controls", &["controls"]),
+    (
+        "button-name",
+        &["children", "aria-label", "aria-labelledby", "title"],
+    ),
+    ("form-label", &["aria-label", "aria-labelledby", "title"]),
+    (
+        "heading-content",
+        &["children", "aria-label", "aria-labelledby"],
+    ),
+    ("iframe-title", &["title", "aria-label", "aria-labelledby"]),
+    ("img-alt", &["alt", "aria-label", "aria-labelledby"]),
+    ("input-image-alt", &["alt", "aria-label", "aria-labelledby"]),
+    (
+        "object-alt",
+        &["children", "aria-label", "aria-labelledby", "title"],
+    ),
+];
+
+/// An element with one or more spreads.
+struct SpreadElement {
+    /// From the start of the element to the end of its opening tag.
+    start: Position,
+    end: Position,
+    /// The props the spreads provide, or `None` if one of them can't be
+    /// resolved.
+    props: Option<Vec<String>>,
+}
+
+/// Applies `policy` to the diagnostics of [`PROP_RULES`] on elements with a
+/// spread. `story` is whether the file is a Storybook story.
+pub fn apply(
+    root: &Node,
+    source: &str,
+    policy: SpreadPolicy,
+    story: bool,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if policy == SpreadPolicy::Report {
+        return;
+    }
+    let mut elements = Vec::new();
+    collect_elements(root, root, source, &mut elements);
+    if story {
+        let args = storybook::args_spread_starts(root, source);
+        elements.retain(|element| !args.contains(&element.start));
+    }
+    if elements.is_empty() {
+        return;
+    }
+
+    diagnostics.retain_mut(|diagnostic| {
+        let Some(NumberOrString::String(code)) = &diagnostic.code else {
+            return true;
+        };
+        let Some((_, providing)) = PROP_RULES.iter().find(|(id, _)| id == code) else {
+            return true;
+        };
+        let at = position_key(diagnostic.range.start);
+        let Some(element) = elements
+            .iter()
+            .find(|e| position_key(e.start) <= at && at <= position_key(e.end))
+        else {
+            return true;
+        };
+        match (&element.props, policy) {
+            (_, SpreadPolicy::Skip) => return false,
+            (Some(props), SpreadPolicy::Resolve) => {
+                return !providing.iter().any(|p| props.iter().any(|a| a == p));
+            }
+            // Hinting, or the spread couldn't be resolved.
+            _ => {}
+        }
+        diagnostic.severity = Some(DiagnosticSeverity::HINT);
+        diagnostic.message = format!("Unless the spread props provide it: {}", diagnostic.message);
+        true
+    });
+}
+
+fn position_key(position: Position) -> (u32, u32) {
+    (position.line, position.character)
+}
+
+fn collect_elements(node: &Node, root: &Node, source: &str, elements: &mut Vec<SpreadElement>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let mut spreads = Vec::new();
+        let mut cursor = tag.walk();
+        for attr in tag.named_children(&mut cursor) {
+            let mut attr_cursor = attr.walk();
+            if attr.kind() == "jsx_expression" {
+                spreads.extend(
+                    attr.named_children(&mut attr_cursor)
+                        .filter(|s| s.kind() == "spread_element")
+                        .filter_map(|s| s.named_child(0)),
+                );
+            }
+        }
+        if !spreads.is_empty() {
+            let props = spreads
+                .iter()
+                .map(|spread| resolve(spread, root, source))
+                .collect::<Option<Vec<_>>>()
+                .map(|props| props.concat());
+            let start = node.start_position();
+            let end = tag.end_position();
+            elements.push(SpreadElement {
+                start: Position::new(start.row as u32, start.column as u32),
+                end: Position::new(end.row as u32, end.column as u32),
+                props,
+            });
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_elements(&child, root, source, elements);
+    }
+}
+
+/// The props a spread argument provides, if it's an object literal or a
+/// variable the file declares once, with an object literal.
+fn resolve(spread: &Node, root: &Node, source: &str) -> Option<Vec<String>> {
+    let spread = unwrap_expression(*spread);
+    match spread.kind() {
+        "object" => object_keys(&spread, source),
+        "identifier" => {
+            let mut values = Vec::new();
+            declared_values(root, &source[spread.byte_range()], source, &mut values);
+            match values.as_slice() {
+                [value] if value.kind() == "object" => object_keys(value, source),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The values of the variables named `name` anywhere in the file.
+fn declared_values<'a>(node: &Node<'a>, name: &str, source: &str, values: &mut Vec<Node<'a>>) {
+    if node.kind() == "variable_declarator"
+        && node
+            .child_by_field_name("name")
+            .is_some_and(|n| &source[n.byte_range()] == name)
+    {
+        // A declaration without a value (`let props;`) is filled in later.
+        values.push(
+            node.child_by_field_name("value")
+                .map_or(*node, unwrap_expression),
+        );
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        declared_values(&child, name, source, values);
+    }
+}
+
+/// The keys of an object literal, leaving out those set to `undefined`,
+/// `null` or an empty string (except `alt: ""`, which marks an image
+/// decorative). `None` if the object spreads another one or computes a key.
+fn object_keys(object: &Node, source: &str) -> Option<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut cursor = object.walk();
+    for entry in object.named_children(&mut cursor) {
+        match entry.kind() {
+            "shorthand_property_identifier" => keys.push(source[entry.byte_range()].to_string()),
+            "pair" => {
+                let key = entry.child_by_field_name("key")?;
+                if key.kind() == "computed_property_name" {
+                    return None;
+                }
+                let key = source[key.byte_range()].trim_matches(['"', '\'']);
+                let value = entry
+                    .child_by_field_name("value")
+                    .map_or("", |v| &source[v.byte_range()]);
+                let empty = matches!(value, "undefined" | "null")
+                    || (key != "alt" && matches!(value, "\"\"" | "''" | "``"));
+                if !empty {
+                    keys.push(key.to_string());
+                }
+            }
+            "method_definition" => {
+                keys.extend(
+                    entry
+                        .child_by_field_name("name")
+                        .map(|n| source[n.byte_range()].to_string()),
+                );
+            }
+            "comment" => {}
+            _ => return None,
+        }
+    }
+    Some(keys)
+}
+
+/// `(x)`, `x as T` and `x satisfies T` → `x`.
+fn unwrap_expression(node: Node) -> Node {
+    match node.kind() {
+        "parenthesized_expression" | "satisfies_expression" | "as_expression" => {
+            node.named_child(0).map_or(node, unwrap_expression)
+        }
+        _ => node,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{self, FileType};
+    use crate::rules;
+
+    fn lint(source: &str, policy: SpreadPolicy) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let root = tree.root_node();
+        let mut diagnostics: Vec<Diagnostic> = rules::all_rules()
+            .iter()
+            .flat_map(|rule| rule.check(&root, source, FileType::Tsx))
+            .collect();
+        apply(&root, source, policy, false, &mut diagnostics);
+        diagnostics
+    }
+
+    const SOURCE: &str = r#"const logo = { src: "logo.png", alt: "Acme" };
+const hero = { src: "hero.png", alt: undefined };
+export const A = (props) => (
+  <>
+    <img {...logo} />
+    <img {...hero} />
+    <img {...props} />
+    <button {...{ "aria-label": "Close" }} />
+  </>
+);
+"#;
+
+    fn found(diagnostics: &[Diagnostic]) -> Vec<(u32, Option<DiagnosticSeverity>)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.range.start.line, d.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_looks_up_object_literals() {
+        let diags = lint(SOURCE, SpreadPolicy::Resolve);
+        let error = Some(DiagnosticSeverity::ERROR);
+        assert_eq!(
+            found(&diags),
+            [(5, error), (6, Some(DiagnosticSeverity::HINT))]
+        );
+        assert!(
+            diags[1]
+                .message
+                .starts_with("Unless the spread props provide it: ")
+        );
+    }
+
+    #[test]
+    fn test_skip_hint_and_report() {
+        assert!(lint(SOURCE, SpreadPolicy::Skip).is_empty());
+        let hints = lint(SOURCE, SpreadPolicy::Hint);
+        assert_eq!(hints.len(), 4);
+        assert!(
+            hints
+                .iter()
+                .all(|d| d.severity == Some(DiagnosticSeverity::HINT))
+        );
+        let reported = lint(SOURCE, SpreadPolicy::Report);
+        assert_eq!(reported.len(), 4);
+        assert!(reported.iter().all(|d| !d.message.starts_with("Unless")));
+    }
+
+    #[test]
+    fn test_unresolvable_objects() {
+        let diags = lint(
+            r#"const a = { ...base, alt: "x" };
+let b;
+const c = { alt: "c" };
+function f() { const c = { src: "x" }; }
+export const X = () => (<><img {...a} /><img {...b} /><img {...c} /><img {...getProps()} /></>);
+"#,
+            SpreadPolicy::Resolve,
+        );
+        assert_eq!(diags.len(), 4);
+        assert!(
+            diags
+                .iter()
+                .all(|d| d.severity == Some(DiagnosticSeverity::HINT))
+        );
+    }
+}
@@ -1,9 +1,31 @@
+pub mod analysis;
+pub mod annotate;
+pub mod autofix;
+pub mod cache;
+pub mod changelog;
 pub mod cli;
+pub mod component_map;
 pub mod config;
+pub mod config_import;
+pub mod crash;
+pub mod diagnostic_data;
 pub mod document;
 pub mod engine;
+#[cfg(feature = "serve-http")]
+pub mod http;
 pub mod inline_directives;
+pub mod jsx_spread;
+pub mod nav_index;
 pub mod parser;
+pub mod quickfix;
+pub mod report;
+pub mod rule_actions;
 pub mod rules;
+pub mod scan;
+pub mod score;
 pub mod server;
+pub mod state;
+pub mod storybook;
+pub mod tagged_templates;
 pub mod updater;
+pub mod workspace;
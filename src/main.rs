@@ -14,14 +14,42 @@ async fn main() {
         return;
     }
 
+    wcag_lsp::crash::install_panic_hook();
+
     if args.get(1).map(|s| s.as_str()) == Some("check") {
         let rest = &args[2..];
-        let mut config_path: Option<&str> = None;
+        let mut options = wcag_lsp::cli::CheckOptions::default();
         let mut patterns: Vec<String> = Vec::new();
         let mut i = 0;
         while i < rest.len() {
             if (rest[i] == "--config" || rest[i] == "-c") && i + 1 < rest.len() {
-                config_path = Some(&rest[i + 1]);
+                options.config_path = Some(&rest[i + 1]);
+                i += 2;
+            } else if (rest[i] == "--jobs" || rest[i] == "-j") && i + 1 < rest.len() {
+                match rest[i + 1].parse::<usize>() {
+                    Ok(n) if n > 0 => options.workers = Some(n),
+                    _ => {
+                        eprintln!("--jobs expects a positive number");
+                        std::process::exit(1);
+                    }
+                }
+                i += 2;
+            } else if rest[i] == "--no-cache" {
+                options.no_cache = true;
+                i += 1;
+            } else if parse_gate_flag(rest, i, &mut options.gate) {
+                i += 2;
+            } else if rest[i] == "--format" && i + 1 < rest.len() {
+                match wcag_lsp::report::OutputFormat::parse(&rest[i + 1]) {
+                    Some(format) => options.format = format,
+                    None => {
+                        eprintln!("--format expects text, json, sarif or html");
+                        std::process::exit(1);
+                    }
+                }
+                i += 2;
+            } else if rest[i] == "--package" && i + 1 < rest.len() {
+                options.package = Some(std::path::Path::new(&rest[i + 1]));
                 i += 2;
             } else {
                 patterns.push(rest[i].clone());
@@ -29,14 +57,184 @@ async fn main() {
             }
         }
         if patterns.is_empty() {
-            eprintln!("Usage: wcag-lsp check [--config <path>] <patterns...>");
+            eprintln!(
+                "Usage: wcag-lsp check [--config <path>] [--package <dir>] [--no-cache] [--jobs <n>] [--format <text|json|sarif|html>] [--max-errors <n>] [--max-warnings <n>] [--fail-on-level <A|AA|AAA>] [--min-score <n>] <patterns...>"
+            );
             std::process::exit(1);
         }
-        std::process::exit(wcag_lsp::cli::run_check_with_config(&patterns, config_path));
+        std::process::exit(wcag_lsp::cli::run_check_with_options(&patterns, &options));
+    }
+
+    if args.get(1).map(|s| s.as_str()) == Some("fix") {
+        let rest = &args[2..];
+        let mut options = wcag_lsp::cli::FixCommandOptions::default();
+        let mut patterns: Vec<String> = Vec::new();
+        let mut i = 0;
+        while i < rest.len() {
+            if (rest[i] == "--config" || rest[i] == "-c") && i + 1 < rest.len() {
+                options.config_path = Some(&rest[i + 1]);
+                i += 2;
+            } else if rest[i] == "--diff" {
+                options.diff = true;
+                i += 1;
+            } else if rest[i] == "--package" && i + 1 < rest.len() {
+                options.package = Some(std::path::Path::new(&rest[i + 1]));
+                i += 2;
+            } else {
+                patterns.push(rest[i].clone());
+                i += 1;
+            }
+        }
+        if patterns.is_empty() {
+            eprintln!(
+                "Usage: wcag-lsp fix [--config <path>] [--package <dir>] [--diff] <patterns...>"
+            );
+            std::process::exit(1);
+        }
+        std::process::exit(wcag_lsp::cli::run_fix(&patterns, &options));
+    }
+
+    if args.get(1).map(|s| s.as_str()) == Some("lint") {
+        let rest = &args[2..];
+        let mut options = wcag_lsp::cli::LintDomOptions::default();
+        let mut snapshot: Option<&str> = None;
+        let mut i = 0;
+        while i < rest.len() {
+            if rest[i] == "--dom" && i + 1 < rest.len() {
+                snapshot = Some(&rest[i + 1]);
+                i += 2;
+            } else if rest[i] == "--dynamic" {
+                options.dynamic = true;
+                i += 1;
+            } else if (rest[i] == "--config" || rest[i] == "-c") && i + 1 < rest.len() {
+                options.config_path = Some(&rest[i + 1]);
+                i += 2;
+            } else if rest[i] == "--format" && i + 1 < rest.len() {
+                match wcag_lsp::report::OutputFormat::parse(&rest[i + 1]) {
+                    Some(format) => options.format = format,
+                    None => {
+                        eprintln!("--format expects text, json, sarif or html");
+                        std::process::exit(1);
+                    }
+                }
+                i += 2;
+            } else if parse_gate_flag(rest, i, &mut options.gate) {
+                i += 2;
+            } else {
+                snapshot = None;
+                break;
+            }
+        }
+        let Some(snapshot) = snapshot else {
+            eprintln!(
+                "Usage: wcag-lsp lint --dom <snapshot.html|-> [--dynamic] [--config <path>] [--format <text|json|sarif|html>] [--max-errors <n>] [--max-warnings <n>] [--fail-on-level <A|AA|AAA>] [--min-score <n>]"
+            );
+            std::process::exit(1);
+        };
+        std::process::exit(wcag_lsp::cli::run_lint_dom(snapshot, &options));
+    }
+
+    if args.get(1).map(|s| s.as_str()) == Some("annotate") {
+        let rest = &args[2..];
+        let mut options = wcag_lsp::cli::AnnotateOptions::default();
+        let mut file: Option<&str> = None;
+        let mut i = 0;
+        while i < rest.len() {
+            if (rest[i] == "--out" || rest[i] == "-o") && i + 1 < rest.len() {
+                options.out = Some(&rest[i + 1]);
+                i += 2;
+            } else if (rest[i] == "--config" || rest[i] == "-c") && i + 1 < rest.len() {
+                options.config_path = Some(&rest[i + 1]);
+                i += 2;
+            } else if file.is_none() && !rest[i].starts_with('-') {
+                file = Some(&rest[i]);
+                i += 1;
+            } else {
+                file = None;
+                break;
+            }
+        }
+        let Some(file) = file else {
+            eprintln!("Usage: wcag-lsp annotate <file> [--out <path>] [--config <path>]");
+            std::process::exit(1);
+        };
+        std::process::exit(wcag_lsp::cli::run_annotate(file, &options));
+    }
+
+    if args.get(1).map(|s| s.as_str()) == Some("serve-http") {
+        serve_http(&args[2..]);
+        return;
+    }
+
+    if args.get(1).map(|s| s.as_str()) == Some("config")
+        && args.get(2).map(|s| s.as_str()) == Some("import")
+    {
+        let rest = &args[3..];
+        let mut from: Option<&str> = None;
+        let mut output: Option<&str> = None;
+        let mut i = 0;
+        while i < rest.len() {
+            if rest[i] == "--from" && i + 1 < rest.len() {
+                from = Some(&rest[i + 1]);
+                i += 2;
+            } else if (rest[i] == "--output" || rest[i] == "-o") && i + 1 < rest.len() {
+                output = Some(&rest[i + 1]);
+                i += 2;
+            } else {
+                i += 1;
+            }
+        }
+        let Some(from) = from else {
+            eprintln!(
+                "Usage: wcag-lsp config import --from <.eslintrc.json|axe.json> [--output <path>]"
+            );
+            std::process::exit(1);
+        };
+        let rules = wcag_lsp::rules::all_rules();
+        let toml = match wcag_lsp::config_import::import(std::path::Path::new(from), &rules) {
+            Ok(toml) => toml,
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        };
+        match output {
+            Some(path) => {
+                if let Err(e) = std::fs::write(path, toml) {
+                    eprintln!("Failed to write {path}: {e}");
+                    std::process::exit(1);
+                }
+                eprintln!("Wrote {path}");
+            }
+            None => print!("{toml}"),
+        }
+        return;
     }
 
     if args.iter().any(|a| a == "--self-update") {
-        if let Err(e) = wcag_lsp::updater::self_update().await {
+        if args.iter().any(|a| a == "--rollback") {
+            let force = args.iter().any(|a| a == "--force");
+            if let Err(e) = wcag_lsp::updater::rollback(force) {
+                eprintln!("Rollback failed: {e}");
+                std::process::exit(1);
+            }
+            return;
+        }
+        let mut options = wcag_lsp::updater::UpdateOptions {
+            check_only: args.iter().any(|a| a == "--check"),
+            force: args.iter().any(|a| a == "--force"),
+            ..Default::default()
+        };
+        if let Some(pos) = args.iter().position(|a| a == "--timeout") {
+            match args.get(pos + 1).and_then(|s| s.parse::<u64>().ok()) {
+                Some(secs) => options.timeout = std::time::Duration::from_secs(secs),
+                None => {
+                    eprintln!("--timeout expects a number of seconds");
+                    std::process::exit(1);
+                }
+            }
+        }
+        if let Err(e) = wcag_lsp::updater::self_update(&options).await {
             eprintln!("Update failed: {e}");
             std::process::exit(1);
         }
@@ -46,10 +244,102 @@ async fn main() {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, socket) = LspService::new(wcag_lsp::server::WcagLspServer::new);
+    let (service, socket) = LspService::build(wcag_lsp::server::WcagLspServer::new)
+        .custom_method("wcag/status", wcag_lsp::server::WcagLspServer::status)
+        .custom_method("wcag/score", wcag_lsp::server::WcagLspServer::score)
+        .custom_method(
+            "wcag/scanWorkspace",
+            wcag_lsp::server::WcagLspServer::scan_workspace,
+        )
+        .finish();
     Server::new(stdin, stdout, socket).serve(service).await;
 }
 
+#[cfg(feature = "serve-http")]
+fn serve_http(rest: &[String]) {
+    let mut port = wcag_lsp::http::DEFAULT_PORT;
+    let mut config_path: Option<&str> = None;
+    let mut allow_origin = None;
+    let mut i = 0;
+    while i < rest.len() {
+        if rest[i] == "--port" && i + 1 < rest.len() {
+            match rest[i + 1].parse::<u16>() {
+                Ok(n) => port = n,
+                Err(_) => {
+                    eprintln!("--port expects a port number");
+                    std::process::exit(1);
+                }
+            }
+            i += 2;
+        } else if (rest[i] == "--config" || rest[i] == "-c") && i + 1 < rest.len() {
+            config_path = Some(&rest[i + 1]);
+            i += 2;
+        } else if rest[i] == "--allow-origin" && i + 1 < rest.len() {
+            allow_origin = Some(rest[i + 1].clone());
+            i += 2;
+        } else {
+            eprintln!(
+                "Usage: wcag-lsp serve-http [--port <n>] [--config <path>] [--allow-origin <origin>]"
+            );
+            std::process::exit(1);
+        }
+    }
+    let config = match config_path {
+        Some(path) => wcag_lsp::config::Config::from_file(std::path::Path::new(path)),
+        None => std::env::current_dir()
+            .map(|dir| wcag_lsp::config::Config::from_dir(&dir))
+            .unwrap_or_default(),
+    };
+    if let Err(e) = wcag_lsp::http::serve(port, config, allow_origin) {
+        eprintln!("Could not serve on port {port}: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// Parses the gate flag (`--max-errors <n>` and the like) at `rest[i]`
+/// into `gate`. Returns whether there was one; exits on a bad value.
+fn parse_gate_flag(rest: &[String], i: usize, gate: &mut wcag_lsp::cli::Gate) -> bool {
+    use wcag_lsp::rules::WcagLevel;
+
+    let Some(value) = rest.get(i + 1) else {
+        return false;
+    };
+    let fail = |message: &str| -> ! {
+        eprintln!("{} expects {message}", rest[i]);
+        std::process::exit(1);
+    };
+    match rest[i].as_str() {
+        "--max-errors" => {
+            gate.max_errors = Some(value.parse().unwrap_or_else(|_| fail("a number")));
+        }
+        "--max-warnings" => {
+            gate.max_warnings = Some(value.parse().unwrap_or_else(|_| fail("a number")));
+        }
+        "--fail-on-level" => {
+            gate.fail_on_level = Some(match value.to_ascii_uppercase().as_str() {
+                "A" => WcagLevel::A,
+                "AA" => WcagLevel::AA,
+                "AAA" => WcagLevel::AAA,
+                _ => fail("A, AA or AAA"),
+            });
+        }
+        "--min-score" => match value.parse::<f64>() {
+            Ok(n) if (0.0..=100.0).contains(&n) => gate.min_score = Some(n),
+            _ => fail("a number from 0 to 100"),
+        },
+        _ => return false,
+    }
+    true
+}
+
+#[cfg(not(feature = "serve-http"))]
+fn serve_http(_rest: &[String]) {
+    eprintln!(
+        "This build of wcag-lsp has no HTTP server; install it with `cargo install wcag-lsp --features serve-http`"
+    );
+    std::process::exit(1);
+}
+
 fn print_help() {
     println!(
         "wcag-lsp v{}
@@ -59,16 +349,75 @@ USAGE:
     wcag-lsp [OPTIONS] [COMMAND]
 
 COMMANDS:
-    check [--config <path>] <patterns...>
+    check [--config <path>] [--package <dir>] [--no-cache] [--jobs <n>]
+          [--format <text|json|sarif|html>] [--max-errors <n>]
+          [--max-warnings <n>] [--fail-on-level <A|AA|AAA>]
+          [--min-score <n>] <patterns...>
                            Lint files matching glob patterns
+                           --package limits the run to one monorepo package
+                           --no-cache re-lints files whose cached result is
+                           still valid
+                           --jobs sets the number of worker threads
+                           (default: all cores but one)
+                           --format json or sarif prints the diagnostics,
+                           with their WCAG criteria and axe-core rule ids,
+                           to stdout; html prints a report page with the
+                           score
+                           --max-errors and --max-warnings fail the run
+                           only beyond that many errors or warnings (by
+                           default any error fails it, warnings don't)
+                           --fail-on-level counts only problems of rules up
+                           to that level against those limits
+                           --min-score fails the run if the accessibility
+                           score (0-100, problems weighted by WCAG level and
+                           severity against the number of elements) is lower
                            Example: wcag-lsp check \"src/**/*.tsx\" \"**/*.html\"
                            Example: wcag-lsp check --config .wcag.toml \"src/**/*.html\"
+                           Example: wcag-lsp check --package packages/web \"**/*.tsx\"
+    fix [--config <path>] [--package <dir>] [--diff] <patterns...>
+                           Apply safe quick fixes to files matching glob
+                           patterns and report what changed per rule
+                           --diff prints a unified diff instead of writing
+                           Example: wcag-lsp fix --diff \"src/**/*.html\"
+    lint --dom <snapshot.html|-> [--dynamic] [--config <path>]
+         [--format <text|json|sarif|html>] [--max-errors <n>] [--max-warnings <n>]
+         [--fail-on-level <A|AA|AAA>] [--min-score <n>]
+                           Lint a DOM snapshot saved from a browser
+                           (outerHTML, or scripts/dom-snapshot.mjs); - reads
+                           it from stdin
+                           --dynamic adds the rules for rendered pages, such
+                           as live regions for toasts and alerts
+                           Example: wcag-lsp lint --dom snapshot.html --dynamic
+    annotate <file> [--out <path>] [--config <path>]
+                           Export the role, accessible name and states of
+                           each element of a template as JSON (printed
+                           unless --out is given), for design and QA tools
+                           Example: wcag-lsp annotate index.html --out annotations.json
+    config import --from <path> [--output <path>]
+                           Translate the eslint-plugin-jsx-a11y rules of an
+                           .eslintrc.json, or an axe-core rule configuration,
+                           into a .wcag.toml (printed unless --output is given)
+                           Example: wcag-lsp config import --from .eslintrc.json -o .wcag.toml
+    serve-http [--port <n>] [--config <path>] [--allow-origin <origin>]
+                           Serve POST /lint on 127.0.0.1 (default port 8787)
+                           for CMS previews and browser extensions; needs a
+                           build with the serve-http feature. Browsers may
+                           only call it from the --allow-origin origin
 
 OPTIONS:
     -h, --help             Show this help message
     -v, --version          Print version
     -c, --config <path>    Path to .wcag.toml or .wcag.json config file
-        --self-update      Update to latest release",
+        --self-update      Update to latest release
+        --self-update --check
+                           Only report whether a newer release exists
+        --self-update --timeout <secs>
+                           Network timeout for the update (default: 30)
+        --self-update --rollback
+                           Restore the binary replaced by the last update
+        --self-update --force
+                           Replace the binary even if a package manager
+                           (Homebrew, Scoop, cargo) installed it",
         env!("CARGO_PKG_VERSION")
     );
 }
@@ -0,0 +1,312 @@
+//! The navigation landmarks of every file in the workspace, for
+//! `consistent-nav-label`.
+//!
+//! That rule compares files with each other, so it doesn't run in the
+//! engine. The CLI and the server's workspace scan index every file before
+//! linting, open documents are re-indexed whenever they're checked, and each
+//! file's result gets [`NavIndex::diagnostics_for`] appended.
+
+use crate::config::Config;
+use crate::document::Document;
+use crate::engine;
+use crate::parser::FileType;
+use crate::rules::Severity;
+use crate::rules::consistent_nav_label::{self, ConsistentNavLabel, NavLandmark};
+use crate::scan;
+use crate::storybook;
+use std::collections::HashMap;
+use std::path::Path;
+use tower_lsp_server::ls_types::*;
+
+/// File (URI, or path from the CLI) → its navigation landmarks.
+#[derive(Debug, Default, Clone)]
+pub struct NavIndex {
+    files: HashMap<String, Vec<NavLandmark>>,
+}
+
+impl NavIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the landmarks of `uri`, replacing what was known about it.
+    pub fn insert(&mut self, uri: &str, landmarks: Vec<NavLandmark>) {
+        if landmarks.is_empty() {
+            self.files.remove(uri);
+        } else {
+            self.files.insert(uri.to_string(), landmarks);
+        }
+    }
+
+    /// Forgets every file `keep` returns `false` for.
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.files.retain(|uri, _| keep(uri));
+    }
+
+    /// Re-indexes an open document.
+    pub fn update(&mut self, doc: &Document) {
+        let landmarks = if storybook::is_story_file(&doc.uri) {
+            Vec::new()
+        } else {
+            consistent_nav_label::landmarks(&doc.tree.root_node(), &doc.source, doc.file_type)
+        };
+        self.insert(&doc.uri, landmarks);
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// A warning on each landmark of `uri` that's labelled differently from
+    /// the same navigation (same links) elsewhere: the label most of its
+    /// occurrences have wins. Empty unless `config` enables the rule.
+    pub fn diagnostics_for(&self, uri: &str, config: &Config) -> Vec<Diagnostic> {
+        let Some(severity) = config.rule_severity(&ConsistentNavLabel) else {
+            return Vec::new();
+        };
+        let Some(own) = self.files.get(uri) else {
+            return Vec::new();
+        };
+
+        let mut others: Vec<(&String, &Vec<NavLandmark>)> = self
+            .files
+            .iter()
+            .filter(|(other, _)| *other != uri)
+            .collect();
+        others.sort_by_key(|(other, _)| *other);
+
+        let mut diagnostics = Vec::new();
+        for landmark in own {
+            let same: Vec<(&str, &NavLandmark)> = others
+                .iter()
+                .flat_map(|(other, landmarks)| {
+                    landmarks
+                        .iter()
+                        .filter(|l| l.links == landmark.links)
+                        .map(|l| (other.as_str(), l))
+                })
+                .collect();
+            if same.is_empty() {
+                continue;
+            }
+
+            let expected = majority_label(landmark, &same);
+            if label_key(&landmark.label) == label_key(&expected) {
+                continue;
+            }
+            let matching: Vec<&(&str, &NavLandmark)> = same
+                .iter()
+                .filter(|(_, l)| label_key(&l.label) == label_key(&expected))
+                .collect();
+            let mut files: Vec<&str> = matching.iter().map(|(file, _)| *file).collect();
+            files.dedup();
+            let related = matching
+                .iter()
+                .filter_map(|(file, l)| {
+                    Some(DiagnosticRelatedInformation {
+                        location: Location {
+                            uri: engine::document_uri(file)?,
+                            range: l.range,
+                        },
+                        message: "The same navigation elsewhere".to_string(),
+                    })
+                })
+                .collect();
+            let mut diag = consistent_nav_label::make_diagnostic(
+                landmark,
+                expected.as_deref(),
+                files.len(),
+                related,
+            );
+            diag.severity = Some(match severity {
+                Severity::Error => DiagnosticSeverity::ERROR,
+                Severity::Warning => DiagnosticSeverity::WARNING,
+            });
+            diagnostics.push(diag);
+        }
+        diagnostics
+    }
+}
+
+/// Whether any of the configs enables `consistent-nav-label`, so indexing
+/// is worth it.
+pub fn is_enabled<'a>(configs: impl IntoIterator<Item = &'a Config>) -> bool {
+    configs
+        .into_iter()
+        .any(|config| config.rule_severity(&ConsistentNavLabel).is_some())
+}
+
+/// Reads and parses `path` for its landmarks. Unreadable and unsupported
+/// files, and Storybook stories, have none.
+pub fn landmarks_in_file(path: &Path, config: &Config) -> Vec<NavLandmark> {
+    let uri = path.to_string_lossy();
+    if storybook::is_story_file(&uri) {
+        return Vec::new();
+    }
+    let Ok(source) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let file_type = config.file_type_for(&uri, None, &source);
+    if file_type == FileType::Unknown {
+        return Vec::new();
+    }
+    match scan::parse(file_type, &source) {
+        Some(tree) => consistent_nav_label::landmarks(&tree.root_node(), &source, file_type),
+        None => Vec::new(),
+    }
+}
+
+/// The label most occurrences of a navigation have, `landmark` included.
+/// Ties go to a label over none, then to the alphabetically first.
+fn majority_label(landmark: &NavLandmark, same: &[(&str, &NavLandmark)]) -> Option<String> {
+    let mut counts: HashMap<Option<String>, (usize, Option<String>)> = HashMap::new();
+    for label in std::iter::once(&landmark.label).chain(same.iter().map(|(_, l)| &l.label)) {
+        let entry = counts.entry(label_key(label)).or_insert((0, label.clone()));
+        entry.0 += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|(a_key, (a_count, _)), (b_key, (b_count, _))| {
+            a_count
+                .cmp(b_count)
+                .then(a_key.is_some().cmp(&b_key.is_some()))
+                .then(b_key.cmp(a_key))
+        })
+        .and_then(|(_, (_, label))| label)
+}
+
+/// Labels compare case-insensitively.
+fn label_key(label: &Option<String>) -> Option<String> {
+    label.as_ref().map(|l| l.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::DocumentManager;
+
+    fn enabled() -> Config {
+        Config::parse("[rules]\nconsistent-nav-label = \"warning\"\n")
+    }
+
+    fn index(files: &[(&str, &str)]) -> NavIndex {
+        let mut docs = DocumentManager::new();
+        let mut index = NavIndex::new();
+        for (uri, source) in files {
+            docs.open(uri.to_string(), source.to_string(), 1);
+            index.update(docs.get(uri).unwrap());
+        }
+        index
+    }
+
+    const MAIN: &str = r#"<a href="/">Home</a><a href="/blog">Blog</a>"#;
+
+    #[test]
+    fn test_different_label_warns_against_majority() {
+        let index = index(&[
+            (
+                "file:///a.html",
+                &format!(r#"<nav aria-label="Main">{MAIN}</nav>"#),
+            ),
+            (
+                "file:///b.html",
+                &format!(r#"<nav aria-label="main">{MAIN}</nav>"#),
+            ),
+            (
+                "file:///c.html",
+                &format!(r#"<nav aria-label="Primary">{MAIN}</nav>"#),
+            ),
+        ]);
+        assert!(
+            index
+                .diagnostics_for("file:///a.html", &enabled())
+                .is_empty()
+        );
+        assert!(
+            index
+                .diagnostics_for("file:///b.html", &enabled())
+                .is_empty()
+        );
+
+        let diags = index.diagnostics_for("file:///c.html", &enabled());
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.starts_with(
+            "This navigation is labelled \"Primary\" here but labelled \"Main\" in 2 other files"
+        ));
+        let related = diags[0].related_information.as_ref().unwrap();
+        assert_eq!(related.len(), 2);
+        assert_eq!(related[0].location.uri.as_str(), "file:///a.html");
+    }
+
+    #[test]
+    fn test_missing_label_warns() {
+        let index = index(&[
+            (
+                "file:///a.html",
+                &format!(r#"<nav aria-label="Main">{MAIN}</nav>"#),
+            ),
+            ("file:///b.html", &format!("<nav>{MAIN}</nav>")),
+        ]);
+        // One each: the labelled one wins.
+        let diags = index.diagnostics_for("file:///b.html", &enabled());
+        assert_eq!(diags.len(), 1);
+        assert!(
+            diags[0]
+                .message
+                .contains("is unlabelled here but labelled \"Main\" in 1 other file.")
+        );
+        assert!(
+            index
+                .diagnostics_for("file:///a.html", &enabled())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_different_links_are_different_navigations() {
+        let index = index(&[
+            (
+                "file:///a.html",
+                &format!(r#"<nav aria-label="Main">{MAIN}</nav>"#),
+            ),
+            (
+                "file:///b.html",
+                r#"<nav aria-label="Footer"><a href="/">Home</a><a href="/imprint">Imprint</a></nav>"#,
+            ),
+        ]);
+        assert!(
+            index
+                .diagnostics_for("file:///b.html", &enabled())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_off_by_default_and_stories_skipped() {
+        let index = index(&[
+            (
+                "file:///a.html",
+                &format!(r#"<nav aria-label="Main">{MAIN}</nav>"#),
+            ),
+            (
+                "file:///b.html",
+                &format!(r#"<nav aria-label="Menu">{MAIN}</nav>"#),
+            ),
+            (
+                "file:///Nav.stories.tsx",
+                r#"export const Default = () => <nav aria-label="X"><a href="/">Home</a><a href="/blog">Blog</a></nav>;"#,
+            ),
+        ]);
+        assert_eq!(index.len(), 2);
+        assert!(
+            index
+                .diagnostics_for("file:///b.html", &Config::default())
+                .is_empty()
+        );
+        assert_eq!(index.diagnostics_for("file:///b.html", &enabled()).len(), 1);
+    }
+}
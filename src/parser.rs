@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+use std::ops::Range;
 use tree_sitter::{Language, Parser};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -7,6 +9,29 @@ pub enum FileType {
     Tsx,
     Vue,
     Svelte,
+    /// Angular component template (`*.component.html`).
+    Angular,
+    /// HTML with embedded `<?php … ?>` code.
+    Php,
+    /// Laravel Blade template (`*.blade.php`).
+    Blade,
+    /// MJML email template (`*.mjml`).
+    Mjml,
+    /// Embedded Ruby template, such as a Rails view (`*.html.erb`).
+    Erb,
+    /// Liquid template, as used by Shopify themes (`*.liquid`).
+    Liquid,
+    /// Markdown with raw HTML in it (`*.md`).
+    Markdown,
+    /// The markup of the `` html`…` `` and `` svg`…` `` tagged templates of a
+    /// JS/TS file (Lit, htm), linted next to the file's own tree. See
+    /// [`crate::tagged_templates`].
+    HtmlTemplate,
+    /// Plain JavaScript (`*.js`, `*.mjs`, `*.cjs`). Only the markup of its
+    /// tagged templates is linted.
+    JavaScript,
+    /// Plain TypeScript (`*.ts`, `*.mts`, `*.cts`), like [`FileType::JavaScript`].
+    TypeScript,
     Unknown,
 }
 
@@ -16,20 +41,128 @@ impl FileType {
             "html" | "htm" => FileType::Html,
             "jsx" => FileType::Jsx,
             "tsx" => FileType::Tsx,
+            "js" | "mjs" | "cjs" => FileType::JavaScript,
+            "ts" | "mts" | "cts" => FileType::TypeScript,
             "vue" => FileType::Vue,
             "svelte" => FileType::Svelte,
-            "astro" | "php" | "erb" | "hbs" | "twig" => FileType::Html,
+            "php" | "phtml" => FileType::Php,
+            "mjml" => FileType::Mjml,
+            "erb" => FileType::Erb,
+            "liquid" => FileType::Liquid,
+            "md" | "markdown" => FileType::Markdown,
+            "astro" | "hbs" | "twig" => FileType::Html,
             _ => FileType::Unknown,
         }
     }
 
+    /// File types named by a double extension (`nav.component.html`,
+    /// `welcome.blade.php`) rather than the last one alone.
+    pub fn from_suffix(uri: &str) -> Option<Self> {
+        let lower = uri.to_ascii_lowercase();
+        if lower.ends_with(".component.html") {
+            Some(FileType::Angular)
+        } else if lower.ends_with(".blade.php") {
+            Some(FileType::Blade)
+        } else {
+            None
+        }
+    }
+
     pub fn from_uri(uri: &str) -> Self {
+        if let Some(file_type) = Self::from_suffix(uri) {
+            return file_type;
+        }
         uri.rsplit('.')
             .next()
             .map(Self::from_extension)
             .unwrap_or(FileType::Unknown)
     }
 
+    /// Map an LSP `languageId` to a file type. Returns `Unknown` for language
+    /// ids we don't lint, so callers can fall back to the extension.
+    pub fn from_language_id(language_id: &str) -> Self {
+        match language_id {
+            "html" => FileType::Html,
+            "javascriptreact" => FileType::Jsx,
+            "typescriptreact" => FileType::Tsx,
+            "javascript" => FileType::JavaScript,
+            "typescript" => FileType::TypeScript,
+            "vue" => FileType::Vue,
+            "svelte" => FileType::Svelte,
+            "angular" | "htmlangular" => FileType::Angular,
+            "php" => FileType::Php,
+            "blade" => FileType::Blade,
+            "mjml" => FileType::Mjml,
+            "erb" => FileType::Erb,
+            "liquid" => FileType::Liquid,
+            "markdown" => FileType::Markdown,
+            "astro" | "handlebars" | "twig" => FileType::Html,
+            _ => FileType::Unknown,
+        }
+    }
+
+    /// Parse a file type name as used in the `[filetypes]` config section.
+    pub fn from_name(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "html" => FileType::Html,
+            "jsx" => FileType::Jsx,
+            "tsx" => FileType::Tsx,
+            "vue" => FileType::Vue,
+            "svelte" => FileType::Svelte,
+            "angular" => FileType::Angular,
+            "php" => FileType::Php,
+            "blade" => FileType::Blade,
+            "mjml" => FileType::Mjml,
+            "erb" => FileType::Erb,
+            "liquid" => FileType::Liquid,
+            "markdown" => FileType::Markdown,
+            "javascript" => FileType::JavaScript,
+            "typescript" => FileType::TypeScript,
+            _ => FileType::Unknown,
+        }
+    }
+
+    /// Guess the file type from the content alone. Used for files whose
+    /// extension doesn't tell us anything (`.inc`, `.tpl`, `.vto`, …).
+    pub fn sniff(source: &str) -> Self {
+        // Only a leading doctype or `<html>` tag counts: XML, SVG and prose
+        // mention or start with tags too.
+        let start = leading_markup(source);
+        let head = start
+            .get(..start.len().min(16))
+            .unwrap_or(start)
+            .to_ascii_lowercase();
+        let html_tag = head.strip_prefix("<html").is_some_and(|rest| {
+            rest.is_empty() || rest.starts_with(|c: char| c == '>' || c.is_ascii_whitespace())
+        });
+        if head.starts_with("<!doctype html") || html_tag {
+            return FileType::Html;
+        }
+
+        // Vue SFC: a `<template>` block next to `<script setup>`.
+        let lower = source.to_ascii_lowercase();
+        if lower.contains("<template") && lower.contains("<script setup") {
+            return FileType::Vue;
+        }
+
+        // JSX pragmas (`/** @jsx h */`, `/** @jsxImportSource preact */`). The
+        // TSX grammar is a superset of JSX, so it handles both dialects.
+        if source.contains("@jsx ") || source.contains("@jsxImportSource") {
+            return FileType::Tsx;
+        }
+
+        FileType::Unknown
+    }
+
+    /// Detect the file type from the URI's extension, falling back to content
+    /// sniffing when the extension is unknown.
+    pub fn detect(uri: &str, source: &str) -> Self {
+        match Self::from_uri(uri) {
+            FileType::Unknown => Self::sniff(source),
+            file_type => file_type,
+        }
+    }
+
     pub fn tree_sitter_language(&self) -> Option<Language> {
         match self {
             FileType::Html => Some(tree_sitter_html::LANGUAGE.into()),
@@ -37,6 +170,16 @@ impl FileType {
             FileType::Tsx => Some(tree_sitter_typescript::LANGUAGE_TSX.into()),
             FileType::Vue => Some(tree_sitter_html::LANGUAGE.into()),
             FileType::Svelte => Some(tree_sitter_html::LANGUAGE.into()),
+            FileType::Angular => Some(tree_sitter_html::LANGUAGE.into()),
+            FileType::Php => Some(tree_sitter_html::LANGUAGE.into()),
+            FileType::Blade => Some(tree_sitter_html::LANGUAGE.into()),
+            FileType::Mjml => Some(tree_sitter_html::LANGUAGE.into()),
+            FileType::Erb => Some(tree_sitter_html::LANGUAGE.into()),
+            FileType::Liquid => Some(tree_sitter_html::LANGUAGE.into()),
+            FileType::Markdown => Some(tree_sitter_html::LANGUAGE.into()),
+            FileType::HtmlTemplate => Some(tree_sitter_html::LANGUAGE.into()),
+            FileType::JavaScript => Some(tree_sitter_javascript::LANGUAGE.into()),
+            FileType::TypeScript => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
             FileType::Unknown => None,
         }
     }
@@ -45,17 +188,48 @@ impl FileType {
         matches!(self, FileType::Jsx | FileType::Tsx)
     }
 
+    /// Plain JavaScript or TypeScript, whose own tree has no markup to lint.
+    pub fn is_script(&self) -> bool {
+        matches!(self, FileType::JavaScript | FileType::TypeScript)
+    }
+
     /// Component/template file types that represent a fragment of a page rather
-    /// than a full HTML document. Document-level rules (e.g. page-title, which
+    /// than a full HTML document. Most Liquid files are Shopify sections and
+    /// snippets, so Liquid counts as one too, as does Markdown, which a site
+    /// generator renders into a layout, and the templates of a Lit or htm
+    /// component. Document-level rules (e.g. page-title, which
     /// requires a `<title>` to exist) don't apply to these.
     pub fn is_fragment(&self) -> bool {
         matches!(
             self,
-            FileType::Jsx | FileType::Tsx | FileType::Vue | FileType::Svelte
+            FileType::Jsx
+                | FileType::Tsx
+                | FileType::Vue
+                | FileType::Svelte
+                | FileType::Angular
+                | FileType::Liquid
+                | FileType::Markdown
+                | FileType::HtmlTemplate
         )
     }
 }
 
+/// `source` from its first tag on, past leading whitespace, a byte order
+/// mark and HTML comments.
+fn leading_markup(source: &str) -> &str {
+    let mut rest = source.trim_start_matches('\u{feff}');
+    loop {
+        rest = rest.trim_start();
+        let Some(comment) = rest.strip_prefix("<!--") else {
+            return rest;
+        };
+        match comment.find("-->") {
+            Some(end) => rest = &comment[end + 3..],
+            None => return "",
+        }
+    }
+}
+
 pub fn create_parser(file_type: FileType) -> Option<Parser> {
     let language = file_type.tree_sitter_language()?;
     let mut parser = Parser::new();
@@ -63,6 +237,654 @@ pub fn create_parser(file_type: FileType) -> Option<Parser> {
     Some(parser)
 }
 
+/// The text to hand tree-sitter for `source`.
+///
+/// For a Vue single-file component that's only its top-level `<template>`
+/// block: `<script>`, `<style>` and custom blocks (`<docs>`, `<i18n>`) are
+/// replaced by spaces, keeping line breaks, so every byte offset and
+/// line/column in the tree is still that of the original file. A template in
+/// another language (`<template lang="pug">`) is blanked as well.
+///
+/// For PHP it's everything outside `<?php … ?>`/`<?= … ?>` blocks, blanked
+/// the same way. Rules still get the original source, so an attribute value
+/// that a block prints (`alt="<?= $alt ?>"`) reads as set. The `?` of a
+/// block that prints (`<?=`, `echo`, or a WordPress template tag such as
+/// `the_title()`) is kept as a stand-in for its output, so
+/// `<a><?php the_title(); ?></a>` still has text.
+///
+/// For Blade it's the markup around Blade and PHP code: comments, `@php`
+/// blocks and directive arguments (`@if ($a < $b)`) are blanked, and so is
+/// the expression inside `{{ … }}`/`{!! … !!}`, keeping the braces. A
+/// `<button>{{ $label }}</button>` still has text, and `->` or `<` in an
+/// expression can't end a tag early.
+///
+/// For ERB it's the markup around `<% … %>` tags. Only the `%` of an output
+/// tag (`<%= … %>`) is kept, as a stand-in for what it prints, so a
+/// `<button><%= t(".save") %></button>` still has text.
+///
+/// For Liquid it's the markup around `{% … %}` tags, with the expression
+/// inside `{{ … }}` blanked like Blade's. `{% comment %}`, `{% schema %}`,
+/// `{% javascript %}`, `{% stylesheet %}` and `{% highlight %}` blocks are
+/// blanked whole, as is the YAML front matter of a Jekyll page. The content
+/// of `{% raw %}` is kept as written.
+///
+/// For Markdown it's the raw HTML: HTML blocks, and the lines around inline
+/// tags, without their code spans and autolinks (`<https://…>`). Fenced and
+/// indented code blocks and YAML front matter are blanked.
+///
+/// Other file types are parsed as they are.
+pub fn parse_input(file_type: FileType, source: &str) -> Cow<'_, str> {
+    let keep = match file_type {
+        FileType::Vue => sfc_templates(source),
+        FileType::Php => php_markup(source),
+        FileType::Blade => blade_markup(source),
+        FileType::Erb => erb_markup(source),
+        FileType::Liquid => liquid_markup(source),
+        FileType::Markdown => markdown_markup(source),
+        _ => return Cow::Borrowed(source),
+    };
+    if keep.len() == 1 && keep[0] == (0..source.len()) {
+        return Cow::Borrowed(source);
+    }
+
+    let blank = |text: &str| -> String {
+        text.chars()
+            .flat_map(|c| {
+                let blank = if c == '\n' || c == '\r' { c } else { ' ' };
+                std::iter::repeat_n(blank, c.len_utf8())
+            })
+            .collect()
+    };
+    let mut out = String::with_capacity(source.len());
+    let mut pos = 0;
+    for range in keep {
+        out.push_str(&blank(&source[pos..range.start]));
+        out.push_str(&source[range.clone()]);
+        pos = range.end;
+    }
+    out.push_str(&blank(&source[pos..]));
+    Cow::Owned(out)
+}
+
+/// Byte ranges of the markup between the PHP blocks of `source`.
+fn php_markup(source: &str) -> Vec<Range<usize>> {
+    let mut markup = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = source[pos..].find("<?") {
+        let start = pos + offset;
+        // `<?xml …?>` declarations are markup, not short open tags.
+        if source[start + 2..].starts_with("xml") {
+            let end = source[start..]
+                .find("?>")
+                .map_or(source.len(), |i| start + i + 2);
+            markup.push(pos..end);
+            pos = end;
+            continue;
+        }
+        markup.push(pos..start);
+        pos = php_block_end(source, start + 2);
+        if php_prints(&source[start + 2..pos]) {
+            markup.push(start + 1..start + 2);
+        }
+    }
+    markup.push(pos..source.len());
+    markup.retain(|r| !r.is_empty());
+    markup
+}
+
+/// WordPress functions that echo their result, besides the `the_*()`
+/// template tags and the `*_e()` translation functions.
+const WP_ECHOING_FUNCTIONS: &[&str] = &[
+    "bloginfo",
+    "comments_number",
+    "printf",
+    "single_cat_title",
+    "single_post_title",
+    "single_tag_title",
+    "single_term_title",
+    "wp_title",
+];
+
+/// Whether the PHP block `code` (what follows `<?`) prints something.
+fn php_prints(code: &str) -> bool {
+    if code.starts_with('=') {
+        return true;
+    }
+    let code = code.strip_prefix("php").unwrap_or(code).trim_start();
+    let word_end = code
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(code.len());
+    let word = &code[..word_end];
+    if matches!(word, "echo" | "print") {
+        return true;
+    }
+    code[word_end..].trim_start().starts_with('(')
+        && (word.starts_with("the_")
+            || word.ends_with("_e")
+            || word == "_ex"
+            || WP_ECHOING_FUNCTIONS.contains(&word))
+}
+
+/// End of the PHP block whose code starts at `from`: just past its `?>`, or
+/// the end of the file. A `?>` in a string or block comment doesn't close it;
+/// one in a line comment does, as in PHP itself. Quotes in comments (`// it's
+/// done`) don't open a string.
+fn php_block_end(source: &str, from: usize) -> usize {
+    let bytes = source.as_bytes();
+    let mut i = from;
+    while i < bytes.len() {
+        // `#[` opens a PHP 8 attribute rather than a comment.
+        let line_comment = match bytes[i] {
+            b'/' => bytes.get(i + 1) == Some(&b'/'),
+            b'#' => bytes.get(i + 1) != Some(&b'['),
+            _ => false,
+        };
+        match bytes[i] {
+            b'?' if bytes.get(i + 1) == Some(&b'>') => return i + 2,
+            _ if line_comment => {
+                let rest = &source[i..];
+                let line = rest.find('\n').map_or(rest, |j| &rest[..j]);
+                if let Some(close) = line.find("?>") {
+                    return i + close + 2;
+                }
+                i += line.len();
+            }
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = source[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |j| i + 2 + j + 1);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// Byte ranges of the markup between the Blade and PHP code of `source`.
+fn blade_markup(source: &str) -> Vec<Range<usize>> {
+    let bytes = source.as_bytes();
+    let mut markup = Vec::new();
+    let mut keep_from = 0;
+    let mut blank = |range: Range<usize>, keep_from: &mut usize| {
+        markup.push(*keep_from..range.start);
+        *keep_from = range.end;
+    };
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &source[i..];
+        if rest.starts_with("{{--") {
+            let end = rest.find("--}}").map_or(bytes.len(), |j| i + j + 4);
+            blank(i..end, &mut keep_from);
+            i = end;
+        } else if rest.starts_with("{{") || rest.starts_with("{!!") {
+            let (open, close) = if rest.starts_with("{{") {
+                (2, "}}")
+            } else {
+                (3, "!!}")
+            };
+            let end = source[i + open..]
+                .find(close)
+                .map_or(bytes.len(), |j| i + open + j);
+            blank(i + open..end, &mut keep_from);
+            i = end + close.len();
+        } else if rest.starts_with("<?") && !rest[2..].starts_with("xml") {
+            let end = php_block_end(source, i + 2);
+            blank(i..end, &mut keep_from);
+            i = end;
+        } else if bytes[i] == b'@' && is_directive_start(source, i) {
+            let name_end = rest[1..]
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .map_or(bytes.len(), |j| i + 1 + j);
+            let name = &source[i + 1..name_end];
+            let args =
+                name_end + (source[name_end..].len() - source[name_end..].trim_start().len());
+            if name == "verbatim" {
+                // Kept as written, `{{ }}` and all (usually a JS template).
+                i = source[i..]
+                    .find("@endverbatim")
+                    .map_or(bytes.len(), |j| i + j + 12);
+            } else if name == "php" && !source[args..].starts_with('(') {
+                let end = source[i..]
+                    .find("@endphp")
+                    .map_or(bytes.len(), |j| i + j + 7);
+                blank(i..end, &mut keep_from);
+                i = end;
+            } else if source[args..].starts_with('(') {
+                let end = closing_paren(source, args);
+                blank(args..end, &mut keep_from);
+                i = end;
+            } else {
+                i = name_end;
+            }
+        } else {
+            i += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    markup.push(keep_from..bytes.len());
+    markup.retain(|r| !r.is_empty());
+    markup
+}
+
+/// Byte ranges of the markup around the ERB tags of `source`, plus the `%`
+/// of each output tag.
+fn erb_markup(source: &str) -> Vec<Range<usize>> {
+    let mut markup = Vec::new();
+    let mut keep_from = 0;
+    let mut pos = 0;
+    while let Some(offset) = source[pos..].find("<%") {
+        let start = pos + offset;
+        // `<%%` is an escaped, literal `<%`.
+        if source[start + 2..].starts_with('%') {
+            pos = start + 3;
+            continue;
+        }
+        markup.push(keep_from..start);
+        if source[start + 2..].starts_with('=') {
+            markup.push(start + 1..start + 2);
+        }
+        pos = source[start + 2..]
+            .find("%>")
+            .map_or(source.len(), |i| start + 2 + i + 2);
+        keep_from = pos;
+    }
+    markup.push(keep_from..source.len());
+    markup.retain(|r| !r.is_empty());
+    markup
+}
+
+/// Liquid blocks whose body isn't markup: Shopify's section settings,
+/// scripts and styles, and Jekyll's code samples.
+const LIQUID_OPAQUE_BLOCKS: &[&str] =
+    &["comment", "highlight", "javascript", "schema", "stylesheet"];
+
+/// Byte ranges of the markup around the Liquid tags and output expressions
+/// of `source`.
+fn liquid_markup(source: &str) -> Vec<Range<usize>> {
+    let mut markup = Vec::new();
+    let mut keep_from = front_matter_end(source);
+    let mut i = keep_from;
+    while let Some(offset) = source[i..].find('{') {
+        let start = i + offset;
+        let rest = &source[start..];
+        if rest.starts_with("{{") {
+            let end = source[start + 2..]
+                .find("}}")
+                .map_or(source.len(), |j| start + 2 + j);
+            markup.push(keep_from..start + 2);
+            keep_from = end;
+            i = end;
+        } else if rest.starts_with("{%") {
+            let tag_end = source[start + 2..]
+                .find("%}")
+                .map_or(source.len(), |j| start + 2 + j + 2);
+            let name = source[start + 2..tag_end]
+                .trim_start_matches('-')
+                .trim_start()
+                .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .next()
+                .unwrap_or("");
+            markup.push(keep_from..start);
+            keep_from = tag_end;
+            i = tag_end;
+            if LIQUID_OPAQUE_BLOCKS.contains(&name) {
+                keep_from = liquid_block_end(source, tag_end, name).1;
+                i = keep_from;
+            } else if name == "raw" {
+                // The body is kept; scanning resumes at `{% endraw %}`.
+                i = liquid_block_end(source, tag_end, name).0;
+            }
+        } else {
+            i = start + 1;
+        }
+    }
+    markup.push(keep_from..source.len());
+    markup.retain(|r| !r.is_empty());
+    markup
+}
+
+/// Raw HTML block elements whose block runs to their closing tag rather
+/// than to the next blank line.
+const MARKDOWN_RAW_BLOCKS: &[&str] = &["pre", "script", "style", "textarea"];
+
+/// Byte ranges of the HTML in Markdown `source`, following CommonMark's
+/// block structure line by line: HTML blocks are kept whole, other lines
+/// without their code spans and autolinks, and code blocks not at all.
+fn markdown_markup(source: &str) -> Vec<Range<usize>> {
+    let mut markup = Vec::new();
+    // The open code fence's character and length.
+    let mut fence: Option<(char, usize)> = None;
+    // What ends the open HTML block: `None` for a blank line.
+    let mut html_block: Option<Option<String>> = None;
+    let mut indented_code = false;
+    let mut prev_blank = true;
+    let mut line_start = front_matter_end(source);
+    for line in source[line_start..].split_inclusive('\n') {
+        let start = line_start;
+        line_start += line.len();
+        let content = line.trim_end_matches(['\n', '\r']);
+        let text = content.trim_start_matches([' ', '\t']);
+        let indent: usize = content[..content.len() - text.len()]
+            .chars()
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum();
+        let blank = text.is_empty();
+
+        if let Some((ch, len)) = fence {
+            let run = text.len() - text.trim_start_matches(ch).len();
+            if indent < 4 && run >= len && text[run..].trim().is_empty() {
+                fence = None;
+                prev_blank = true;
+            }
+            continue;
+        }
+        if let Some(end) = &html_block {
+            markup.push(start..start + content.len());
+            let closed = match end {
+                Some(marker) => content.to_ascii_lowercase().contains(marker.as_str()),
+                None => blank,
+            };
+            if closed {
+                html_block = None;
+            }
+            prev_blank = blank;
+            continue;
+        }
+        if blank {
+            prev_blank = true;
+            continue;
+        }
+        if indent >= 4 && (prev_blank || indented_code) {
+            indented_code = true;
+            prev_blank = false;
+            continue;
+        }
+        indented_code = false;
+        prev_blank = false;
+
+        if let Some(ch) = text.chars().next().filter(|c| matches!(c, '`' | '~')) {
+            let run = text.len() - text.trim_start_matches(ch).len();
+            // A backtick fence's info string can't contain backticks.
+            if run >= 3 && !(ch == '`' && text[run..].contains('`')) {
+                fence = Some((ch, run));
+                continue;
+            }
+        }
+        if let Some(end) = markdown_html_block_start(text) {
+            markup.push(start..start + content.len());
+            let closed = end
+                .as_ref()
+                .is_some_and(|marker| text.to_ascii_lowercase().contains(marker.as_str()));
+            if !closed {
+                html_block = Some(end);
+            }
+            continue;
+        }
+
+        // Any other line, without its code spans and autolinks.
+        let line_end = start + content.len();
+        let mut keep_from = start;
+        let mut i = start;
+        while i < line_end {
+            let rest = &source[i..line_end];
+            let skip = if rest.starts_with('`') {
+                let ticks = rest.len() - rest.trim_start_matches('`').len();
+                match code_span_len(rest, ticks) {
+                    Some(len) => Some(len),
+                    None => {
+                        i += ticks;
+                        continue;
+                    }
+                }
+            } else if rest.starts_with('<') && is_autolink(rest) {
+                rest.find('>').map(|j| j + 1)
+            } else {
+                None
+            };
+            match skip {
+                Some(len) => {
+                    markup.push(keep_from..i);
+                    i += len;
+                    keep_from = i;
+                }
+                None => i += rest.chars().next().map_or(1, char::len_utf8),
+            }
+        }
+        markup.push(keep_from..line_end);
+    }
+    markup.retain(|r| !r.is_empty());
+    markup
+}
+
+/// Length of the code span at the start of `text`, which opens with `ticks`
+/// backticks: up to the next run of exactly as many, if there is one.
+fn code_span_len(text: &str, ticks: usize) -> Option<usize> {
+    let mut i = ticks;
+    while let Some(j) = text[i..].find('`') {
+        let at = i + j;
+        let run = text[at..].len() - text[at..].trim_start_matches('`').len();
+        if run == ticks {
+            return Some(at + run);
+        }
+        i = at + run;
+    }
+    None
+}
+
+/// If `text`, a line without its indentation, starts a CommonMark HTML
+/// block: what ends it, `None` meaning the next blank line.
+fn markdown_html_block_start(text: &str) -> Option<Option<String>> {
+    let lower = text.to_ascii_lowercase();
+    if lower.starts_with("<!--") {
+        return Some(Some("-->".to_string()));
+    }
+    if lower.starts_with("<?") {
+        return Some(Some("?>".to_string()));
+    }
+    if lower.starts_with("<![cdata[") {
+        return Some(Some("]]>".to_string()));
+    }
+    if lower.starts_with("<!") {
+        return Some(Some(">".to_string()));
+    }
+    let name_start = if lower.starts_with("</") { 2 } else { 1 };
+    if !lower.starts_with('<') || is_autolink(text) {
+        return None;
+    }
+    let name: String = lower[name_start..]
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    if name_start == 1 && MARKDOWN_RAW_BLOCKS.contains(&name.as_str()) {
+        return Some(Some(format!("</{name}>")));
+    }
+    Some(None)
+}
+
+/// Whether `text` starts with an autolink, `<scheme:…>` or `<user@host>`.
+fn is_autolink(text: &str) -> bool {
+    let Some(end) = text.find('>') else {
+        return false;
+    };
+    let inner = &text[1..end];
+    if inner.is_empty() || inner.contains([' ', '<']) {
+        return false;
+    }
+    let scheme = inner.split(':').next().unwrap_or_default();
+    let is_uri = inner.contains(':')
+        && (2..=32).contains(&scheme.len())
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '.' | '-'));
+    let is_email = !inner.contains(['/', ':']) && inner.contains('@');
+    is_uri || is_email
+}
+
+/// End of the `---` fenced YAML front matter at the very start of `source`,
+/// or 0 if there is none.
+fn front_matter_end(source: &str) -> usize {
+    let Some(body) = source
+        .strip_prefix("---\n")
+        .or_else(|| source.strip_prefix("---\r\n"))
+    else {
+        return 0;
+    };
+    let from = source.len() - body.len();
+    let mut line_start = from;
+    for line in body.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return line_start + line.len();
+        }
+        line_start += line.len();
+    }
+    0
+}
+
+/// Start and end of the `{% end<name> %}` tag closing the block whose body
+/// starts at `from`, or the end of the file.
+fn liquid_block_end(source: &str, from: usize, name: &str) -> (usize, usize) {
+    let Some(at) = source[from..].find(&format!("end{name}")).map(|j| from + j) else {
+        return (source.len(), source.len());
+    };
+    let start = source[..at].rfind("{%").unwrap_or(at);
+    let end = source[at..].find("%}").map_or(source.len(), |j| at + j + 2);
+    (start, end)
+}
+
+/// Whether the `@` at `at` starts a Blade directive: followed by a letter,
+/// and not an escaped `@@if` or part of an email address.
+fn is_directive_start(source: &str, at: usize) -> bool {
+    let before = source[..at].chars().next_back();
+    let after = source[at + 1..].chars().next();
+    after.is_some_and(|c| c.is_ascii_alphabetic())
+        && !before.is_some_and(|c| c.is_alphanumeric() || c == '@' || c == '.' || c == '_')
+}
+
+/// End of the parenthesized arguments opening at `open`: just past the
+/// matching `)`, skipping parentheses in strings.
+fn closing_paren(source: &str, open: usize) -> usize {
+    let bytes = source.as_bytes();
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            quote @ (b'\'' | b'"') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// Byte ranges of the top-level HTML `<template>` blocks of a Vue SFC, each
+/// from `<template` through `</template>`.
+fn sfc_templates(source: &str) -> Vec<Range<usize>> {
+    let lower = source.to_ascii_lowercase();
+    let mut templates = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = lower[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &lower[start..];
+        if rest.starts_with("<!--") {
+            pos = rest.find("-->").map_or(lower.len(), |end| start + end + 3);
+            continue;
+        }
+        let name: String = rest[1..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .collect();
+        if name.is_empty() {
+            pos = start + 1;
+            continue;
+        }
+        let open_end = rest.find('>').map_or(lower.len(), |end| start + end + 1);
+        if lower[..open_end].ends_with("/>") {
+            pos = open_end;
+            continue;
+        }
+        let end = if name == "template" {
+            matching_template_end(&lower, open_end)
+        } else {
+            let close = format!("</{name}");
+            lower[open_end..]
+                .find(&close)
+                .map(|i| open_end + i)
+                .and_then(|i| lower[i..].find('>').map(|j| i + j + 1))
+                .unwrap_or(lower.len())
+        };
+        if name == "template" && is_html_template(&lower[start..open_end]) {
+            templates.push(start..end);
+        }
+        pos = end;
+    }
+    templates
+}
+
+/// End of the `</template>` closing the template whose content starts at
+/// `from`, skipping nested `<template v-if>`/`<template #slot>` elements.
+fn matching_template_end(lower: &str, from: usize) -> usize {
+    let mut depth = 1;
+    let mut pos = from;
+    while let Some(offset) = lower[pos..].find("template") {
+        let at = pos + offset;
+        pos = at + "template".len();
+        let boundary = lower[pos..]
+            .chars()
+            .next()
+            .is_none_or(|c| c.is_ascii_whitespace() || c == '>' || c == '/');
+        if !boundary {
+            continue;
+        }
+        if lower[..at].ends_with("</") {
+            depth -= 1;
+            if depth == 0 {
+                return lower[pos..].find('>').map_or(lower.len(), |i| pos + i + 1);
+            }
+        } else if lower[..at].ends_with('<') {
+            let open_end = lower[pos..].find('>').map_or(lower.len(), |i| pos + i + 1);
+            if !lower[..open_end].ends_with("/>") {
+                depth += 1;
+            }
+        }
+    }
+    lower.len()
+}
+
+/// Whether a `<template ...>` opening tag is HTML rather than e.g. Pug.
+fn is_html_template(open_tag: &str) -> bool {
+    let Some(i) = open_tag.find("lang") else {
+        return true;
+    };
+    let value = open_tag[i + "lang".len()..]
+        .trim_start()
+        .strip_prefix('=')
+        .map(|v| v.trim_start().trim_start_matches(['"', '\'']))
+        .unwrap_or("");
+    value.is_empty() || value.starts_with("html")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,8 +895,22 @@ mod tests {
         assert_eq!(FileType::from_extension("htm"), FileType::Html);
         assert_eq!(FileType::from_extension("jsx"), FileType::Jsx);
         assert_eq!(FileType::from_extension("tsx"), FileType::Tsx);
+        assert_eq!(FileType::from_extension("js"), FileType::JavaScript);
+        assert_eq!(FileType::from_extension("mts"), FileType::TypeScript);
         assert_eq!(FileType::from_extension("vue"), FileType::Vue);
         assert_eq!(FileType::from_extension("svelte"), FileType::Svelte);
+        assert_eq!(FileType::from_extension("php"), FileType::Php);
+        assert_eq!(FileType::from_extension("mjml"), FileType::Mjml);
+        assert_eq!(FileType::from_extension("md"), FileType::Markdown);
+        assert_eq!(
+            FileType::from_uri("file:///app/views/show.html.erb"),
+            FileType::Erb
+        );
+        assert_eq!(FileType::from_extension("liquid"), FileType::Liquid);
+        assert_eq!(
+            FileType::from_uri("file:///views/welcome.blade.php"),
+            FileType::Blade
+        );
         assert_eq!(FileType::from_extension("rs"), FileType::Unknown);
     }
 
@@ -82,12 +918,112 @@ mod tests {
     fn test_file_type_from_uri() {
         assert_eq!(FileType::from_uri("file:///app/index.html"), FileType::Html);
         assert_eq!(FileType::from_uri("file:///app/App.tsx"), FileType::Tsx);
+        assert_eq!(
+            FileType::from_uri("file:///app/src/app/nav.component.html"),
+            FileType::Angular
+        );
         assert_eq!(
             FileType::from_uri("file:///app/style.css"),
             FileType::Unknown
         );
     }
 
+    #[test]
+    fn test_file_type_from_language_id() {
+        assert_eq!(FileType::from_language_id("html"), FileType::Html);
+        assert_eq!(FileType::from_language_id("javascriptreact"), FileType::Jsx);
+        assert_eq!(FileType::from_language_id("typescriptreact"), FileType::Tsx);
+        assert_eq!(
+            FileType::from_language_id("typescript"),
+            FileType::TypeScript
+        );
+        assert_eq!(
+            FileType::from_language_id("javascript"),
+            FileType::JavaScript
+        );
+        assert_eq!(FileType::from_language_id("vue"), FileType::Vue);
+        assert_eq!(FileType::from_language_id("svelte"), FileType::Svelte);
+        assert_eq!(FileType::from_language_id("htmlangular"), FileType::Angular);
+        assert_eq!(FileType::from_language_id("rust"), FileType::Unknown);
+    }
+
+    #[test]
+    fn test_file_type_from_name() {
+        assert_eq!(FileType::from_name("html"), FileType::Html);
+        assert_eq!(FileType::from_name("Vue"), FileType::Vue);
+        assert_eq!(FileType::from_name("markdown"), FileType::Markdown);
+        assert_eq!(FileType::from_name("typescript"), FileType::TypeScript);
+        assert_eq!(FileType::from_name("pug"), FileType::Unknown);
+    }
+
+    #[test]
+    fn test_sniff_doctype() {
+        assert_eq!(
+            FileType::sniff("\n<!DOCTYPE html>\n<html></html>"),
+            FileType::Html
+        );
+    }
+
+    #[test]
+    fn test_sniff_vue_sfc() {
+        let src = "<script setup>\nconst a = 1\n</script>\n<template><img></template>";
+        assert_eq!(FileType::sniff(src), FileType::Vue);
+    }
+
+    #[test]
+    fn test_sniff_jsx_pragma() {
+        let src = "/** @jsxImportSource preact */\nexport default () => <img />;";
+        assert_eq!(FileType::sniff(src), FileType::Tsx);
+    }
+
+    #[test]
+    fn test_sniff_html_tag_after_comments() {
+        assert_eq!(
+            FileType::sniff("<!-- layout -->\n<!-- v2 -->\n<html lang=\"en\"></html>"),
+            FileType::Html
+        );
+        assert_eq!(FileType::sniff("\u{feff}<HTML>"), FileType::Html);
+        // Fragments are only linted through a `[filetypes]` mapping.
+        assert_eq!(
+            FileType::sniff("<div><img src=\"x\"></div>"),
+            FileType::Unknown
+        );
+    }
+
+    #[test]
+    fn test_sniff_non_markup() {
+        assert_eq!(FileType::sniff("fn main() {}"), FileType::Unknown);
+        assert_eq!(
+            FileType::sniff("<?xml version=\"1.0\"?>"),
+            FileType::Unknown
+        );
+        assert_eq!(
+            FileType::sniff("<svg xmlns=\"http://www.w3.org/2000/svg\"><text>x</text></svg>"),
+            FileType::Unknown
+        );
+        assert_eq!(
+            FileType::sniff("<feed><title>Notes on the <html> element</title></feed>"),
+            FileType::Unknown
+        );
+        assert_eq!(
+            FileType::sniff("Wrap the page in <html> and add a lang."),
+            FileType::Unknown
+        );
+        assert_eq!(FileType::sniff("<htmlx>"), FileType::Unknown);
+    }
+
+    #[test]
+    fn test_detect_prefers_extension() {
+        assert_eq!(
+            FileType::detect("file:///a/App.tsx", "<!DOCTYPE html>"),
+            FileType::Tsx
+        );
+        assert_eq!(
+            FileType::detect("file:///a/header.inc", "<!doctype html><header></header>"),
+            FileType::Html
+        );
+    }
+
     #[test]
     fn test_create_parser_html() {
         let parser = create_parser(FileType::Html);
@@ -124,6 +1060,175 @@ mod tests {
         assert_eq!(root.kind(), "program");
     }
 
+    #[test]
+    fn test_parse_input_keeps_only_vue_template() {
+        let src = "<script setup>\nconst a = '<img>'\n</script>\n\n<template>\n  <template v-if=\"x\"><img></template>\n  <p>é</p>\n</template>\n<docs>\n<img>\n</docs>\n";
+        let input = parse_input(FileType::Vue, src);
+        assert_eq!(input.len(), src.len());
+        assert_eq!(input.lines().count(), src.lines().count());
+        let start = src.find("<template>").unwrap();
+        let end = src.rfind("</template>").unwrap() + "</template>".len();
+        assert_eq!(&input[start..end], &src[start..end]);
+        assert!(input[..start].trim().is_empty());
+        assert!(input[end..].trim().is_empty());
+    }
+
+    #[test]
+    fn test_parse_input_blanks_pug_template() {
+        let src = "<template lang=\"pug\">\ndiv\n  img\n</template>\n";
+        assert!(parse_input(FileType::Vue, src).trim().is_empty());
+    }
+
+    #[test]
+    fn test_parse_input_blanks_php_blocks() {
+        let src = "<?php $a = '?>'; /* ?> */ ?>\n<img alt=\"<?= $alt ?>\">\n<?php if ($x): // done ?>\n<p>é</p><?php endif;";
+        let input = parse_input(FileType::Php, src);
+        assert_eq!(input.len(), src.len());
+        let lines: Vec<&str> = input.lines().map(str::trim).collect();
+        assert_eq!(lines, ["", "<img alt=\" ?         \">", "", "<p>é</p>"]);
+    }
+
+    #[test]
+    fn test_php_comments_hold_no_strings() {
+        let src = "<?php // it's done ?>\n<img>\n<?php # don't \"x\n/* it's */ ?><p>a</p>\n<?php #[Attr('?>')] ?><br>";
+        let input = parse_input(FileType::Php, src);
+        assert_eq!(input.len(), src.len());
+        let lines: Vec<&str> = input.lines().map(str::trim).collect();
+        assert_eq!(lines, ["", "<img>", "", "<p>a</p>", "<br>"]);
+    }
+
+    #[test]
+    fn test_parse_input_keeps_xml_declaration_in_php() {
+        let src = "<?xml version=\"1.0\"?>\n<svg><?= $icon ?></svg>";
+        let input = parse_input(FileType::Php, src);
+        assert!(input.starts_with("<?xml version=\"1.0\"?>\n<svg>"));
+        assert!(input.ends_with("<svg> ?          </svg>"));
+    }
+
+    #[test]
+    fn test_php_prints_wordpress_output() {
+        assert!(php_prints("= $alt "));
+        assert!(php_prints("php echo esc_attr( $alt ); "));
+        assert!(php_prints("php the_title(); "));
+        assert!(php_prints("php esc_html_e( 'Menu', 'theme' ); "));
+        assert!(php_prints("php bloginfo( 'name' ); "));
+        assert!(!php_prints("php if ( have_posts() ) : "));
+        assert!(!php_prints("php get_header(); "));
+        assert!(!php_prints("php $the_title = 1; "));
+    }
+
+    #[test]
+    fn test_parse_input_blanks_erb_tags() {
+        let src = "<%# <img> %>\n<% if @user.admin? && x < 2 %>\n<img alt=\"<%= @alt %>\"><button><%== t('.save') %></button>\n<%% é <% end -%>";
+        let input = parse_input(FileType::Erb, src);
+        assert_eq!(input.len(), src.len());
+        let lines: Vec<&str> = input.lines().map(str::trim).collect();
+        assert_eq!(
+            lines,
+            [
+                "",
+                "",
+                "<img alt=\" %         \"><button> %                </button>",
+                "<%% é",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_input_blanks_blade_code() {
+        let src = "{{-- <img> --}}\n@if (count($a) < 2)\n<x-card :title=\"$t\" {{ $attributes->merge(['a' => 'b']) }}>\n<button>{!! $label !!}</button> é mail@example.com @@if\n@endif @php $x = '<b>'; @endphp\n@verbatim {{ vue }} @endverbatim";
+        let input = parse_input(FileType::Blade, src);
+        assert_eq!(input.len(), src.len());
+        let lines: Vec<&str> = input.lines().map(str::trim_end).collect();
+        assert_eq!(
+            lines,
+            [
+                "",
+                "@if",
+                "<x-card :title=\"$t\" {{                                  }}>",
+                "<button>{!!        !!}</button> é mail@example.com @@if",
+                "@endif",
+                "@verbatim {{ vue }} @endverbatim",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_input_blanks_liquid_tags() {
+        let src = "{% comment %}<img>{% endcomment %}\n{%- if product.price < 10 -%}\n<img src=\"{{ product | img_url }}\" alt=\"{{- product.title -}}\">\n{% raw %}{{ vue }}{% endraw %} é\n{% schema %}{\"name\": \"<b>\"}{% endschema %}";
+        let input = parse_input(FileType::Liquid, src);
+        assert_eq!(input.len(), src.len());
+        let lines: Vec<&str> = input.lines().map(str::trim).collect();
+        assert_eq!(
+            lines,
+            [
+                "",
+                "",
+                "<img src=\"{{                   }}\" alt=\"{{                 }}\">",
+                "{{ vue }}             é",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_input_blanks_jekyll_front_matter() {
+        let src = "---\nlayout: post\ntitle: <Draft>\n---\n<h1>{{ page.title }}</h1>\n{% highlight html %}<img src=\"x\">{% endhighlight %}";
+        let input = parse_input(FileType::Liquid, src);
+        assert_eq!(input.len(), src.len());
+        let lines: Vec<&str> = input.lines().map(str::trim).collect();
+        assert_eq!(lines, ["", "", "", "", "<h1>{{            }}</h1>", ""]);
+        assert_eq!(front_matter_end("---\nunclosed"), 0);
+        assert_eq!(front_matter_end("<p>---</p>"), 0);
+    }
+
+    #[test]
+    fn test_parse_input_keeps_markdown_html() {
+        let src = "---\ntitle: <Draft>\n---\n# Logo `<img>`\n\n<div align=\"center\">\n  <img src=\"logo.png\">\n</div>\n\n```html\n<img src=\"x\">\n```\n\n    <img src=\"indented\">\n\nSee <https://x.dev> or <b>this</b> ``a ` <i>``.";
+        let input = parse_input(FileType::Markdown, src);
+        assert_eq!(input.len(), src.len());
+        let lines: Vec<&str> = input.lines().map(str::trim).collect();
+        assert_eq!(
+            lines,
+            [
+                "",
+                "",
+                "",
+                "# Logo",
+                "",
+                "<div align=\"center\">",
+                "<img src=\"logo.png\">",
+                "</div>",
+                "",
+                "",
+                "",
+                "",
+                "",
+                "",
+                "",
+                "See                 or <b>this</b>            .",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markdown_raw_blocks_run_to_closing_tag() {
+        let src = "<pre>\n\n<img src=\"a\">\n</pre>\n<!-- wcag-disable -->\n<script>\n\n</script>";
+        assert_eq!(parse_input(FileType::Markdown, src), src);
+        assert!(is_autolink("<me@example.com>"));
+        assert!(!is_autolink("<a href=\"x\">"));
+    }
+
+    #[test]
+    fn test_parse_input_other_file_types_untouched() {
+        let src = "<script>a</script><p>b</p>";
+        assert!(matches!(parse_input(FileType::Html, src), Cow::Borrowed(_)));
+        assert!(matches!(
+            parse_input(FileType::Vue, "<template><p>b</p></template>"),
+            Cow::Borrowed(_)
+        ));
+    }
+
     #[test]
     fn test_is_jsx_like() {
         assert!(FileType::Jsx.is_jsx_like());
@@ -0,0 +1,474 @@
+//! Quick fixes attached to diagnostics.
+//!
+//! Rules that know how to repair what they report store one or more [`Fix`]es
+//! in the diagnostic's `data` field. The server hands them back to the editor
+//! as `quickfix` code actions; nothing is recomputed when the user picks one,
+//! so a fix is only valid for the document version it was produced for.
+//!
+//! A fix may use snippet syntax (`${1:Label text}`, `$0`) so the editor can
+//! put the cursor on text the user has to fill in. Clients that don't
+//! advertise the `snippetTextEdit` experimental capability get the same edit
+//! with the placeholders replaced by their default text.
+//!
+//! A fix marked `per_locale` has `{locale}` in its title and edits and is
+//! offered once per candidate locale (see [`candidate_locales`]).
+//!
+//! Fixes marked `safe` only remove what the diagnostic reports and don't
+//! change how the page looks or behaves otherwise; `wcag-lsp fix` applies
+//! those without review.
+
+use crate::engine::node_to_range;
+use serde::{Deserialize, Serialize};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Fix {
+    pub title: String,
+    pub edits: Vec<TextEdit>,
+    /// Whether `edits` use snippet syntax.
+    #[serde(default)]
+    pub snippet: bool,
+    /// Whether `{locale}` in the title and edits is filled in per locale.
+    #[serde(default)]
+    pub per_locale: bool,
+    /// Whether the fix can be applied without review.
+    #[serde(default)]
+    pub safe: bool,
+}
+
+/// What the client can do and wants, for turning fixes into code actions.
+#[derive(Debug, Default)]
+pub struct FixOptions {
+    /// The client applies snippet syntax.
+    pub snippets: bool,
+    /// Locales for `per_locale` fixes, preferred first.
+    pub locales: Vec<String>,
+}
+
+/// Locale used when neither the config nor the editor names one.
+pub const FALLBACK_LOCALE: &str = "en";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FixData {
+    #[serde(default)]
+    fixes: Vec<Fix>,
+}
+
+/// Attach `fix` to `diagnostic`, keeping any fixes already attached.
+pub fn attach(diagnostic: &mut Diagnostic, fix: Fix) {
+    let mut fixes = fixes(diagnostic);
+    fixes.push(fix);
+    diagnostic.data = serde_json::to_value(FixData { fixes }).ok();
+}
+
+/// The fixes attached to `diagnostic`, if any.
+pub fn fixes(diagnostic: &Diagnostic) -> Vec<Fix> {
+    diagnostic
+        .data
+        .clone()
+        .and_then(|data| serde_json::from_value::<FixData>(data).ok())
+        .map(|data| data.fixes)
+        .unwrap_or_default()
+}
+
+/// An edit deleting `node` together with the whitespace separating it from
+/// its previous sibling, e.g. an attribute and the space before it.
+pub fn remove_node(node: &Node) -> TextEdit {
+    let mut range = node_to_range(node);
+    if let Some(prev) = node.prev_sibling() {
+        range.start = node_to_range(&prev).end;
+    }
+    TextEdit {
+        range,
+        new_text: String::new(),
+    }
+}
+
+/// An edit inserting ` name="value"` right after `tag_name`, the node
+/// holding an element's name.
+pub fn insert_attr(tag_name: &Node, name: &str, value: &str) -> TextEdit {
+    let end = node_to_range(tag_name).end;
+    TextEdit {
+        range: Range { start: end, end },
+        new_text: format!(" {name}=\"{value}\""),
+    }
+}
+
+/// Escapes `text` for use inside a snippet, so it is inserted literally.
+pub fn snippet_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '$' | '}' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Turns snippet text into the plain text it stands for: placeholders become
+/// their default text, tab stops disappear and escapes are resolved.
+pub fn snippet_to_plain(snippet: &str) -> String {
+    let mut plain = String::with_capacity(snippet.len());
+    let mut chars = snippet.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => plain.extend(chars.next()),
+            '$' if chars.peek() == Some(&'{') => {
+                chars.next();
+                while chars.next_if(|c| c.is_ascii_digit()).is_some() {}
+                chars.next_if_eq(&':');
+            }
+            '$' if chars.peek().is_some_and(char::is_ascii_digit) => {
+                while chars.next_if(|c| c.is_ascii_digit()).is_some() {}
+            }
+            // Closes a placeholder; literal braces are escaped.
+            '}' => {}
+            c => plain.push(c),
+        }
+    }
+    plain
+}
+
+/// The locales to offer: the configured one, the editor's, then
+/// [`FALLBACK_LOCALE`], normalized to BCP 47 casing and without duplicates.
+pub fn candidate_locales(configured: Option<&str>, editor: Option<&str>) -> Vec<String> {
+    let mut locales: Vec<String> = Vec::new();
+    for locale in [configured, editor, Some(FALLBACK_LOCALE)]
+        .into_iter()
+        .flatten()
+        .filter_map(normalize_locale)
+    {
+        if !locales.contains(&locale) {
+            locales.push(locale);
+        }
+    }
+    locales
+}
+
+/// `de_ch` → `de-CH`, `EN` → `en`, `zh-hant-tw` → `zh-Hant-TW`.
+fn normalize_locale(locale: &str) -> Option<String> {
+    let mut parts = locale.trim().split(['-', '_']).filter(|p| !p.is_empty());
+    let language = parts.next()?.to_ascii_lowercase();
+    if !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut tag = language;
+    for part in parts {
+        tag.push('-');
+        match part.len() {
+            2 => tag.push_str(&part.to_ascii_uppercase()),
+            4 => {
+                let (first, rest) = part.split_at(1);
+                tag.push_str(&first.to_ascii_uppercase());
+                tag.push_str(&rest.to_ascii_lowercase());
+            }
+            _ => tag.push_str(&part.to_ascii_lowercase()),
+        }
+    }
+    Some(tag)
+}
+
+/// Fills in `{locale}` in a `per_locale` fix.
+pub fn localize(fix: &Fix, locale: &str) -> Fix {
+    Fix {
+        title: fix.title.replace("{locale}", locale),
+        edits: fix
+            .edits
+            .iter()
+            .map(|edit| TextEdit {
+                range: edit.range,
+                new_text: edit.new_text.replace("{locale}", locale),
+            })
+            .collect(),
+        snippet: fix.snippet,
+        per_locale: false,
+        safe: fix.safe,
+    }
+}
+
+/// The byte offset of `position` in `source`. Columns are byte offsets into
+/// the line, as produced by [`node_to_range`].
+pub fn offset(source: &str, position: Position) -> usize {
+    let line_start: usize = source
+        .split_inclusive('\n')
+        .take(position.line as usize)
+        .map(str::len)
+        .sum();
+    (line_start + position.character as usize).min(source.len())
+}
+
+/// `source` with `edits` applied, or `None` if two edits overlap.
+pub fn apply(source: &str, edits: &[TextEdit]) -> Option<String> {
+    let mut spans: Vec<(usize, usize, &str)> = edits
+        .iter()
+        .map(|e| {
+            (
+                offset(source, e.range.start),
+                offset(source, e.range.end),
+                e.new_text.as_str(),
+            )
+        })
+        .collect();
+    spans.sort_by_key(|&(start, end, _)| (start, end));
+    if spans.windows(2).any(|w| w[0].1 > w[1].0) {
+        return None;
+    }
+    let mut result = source.to_string();
+    for (start, end, text) in spans.into_iter().rev() {
+        result.replace_range(start..end, text);
+    }
+    Some(result)
+}
+
+/// Code actions for the wcag-lsp diagnostics in `diagnostics`. Snippet fixes
+/// keep their placeholders only if the client supports snippets.
+pub fn code_actions(
+    uri: &Uri,
+    diagnostics: &[Diagnostic],
+    options: &FixOptions,
+) -> Vec<CodeActionOrCommand> {
+    let mut actions = Vec::new();
+    for diagnostic in diagnostics {
+        if diagnostic.source.as_deref() != Some("wcag-lsp") {
+            continue;
+        }
+        let fixes = fixes(diagnostic).into_iter().flat_map(|fix| {
+            if !fix.per_locale {
+                return vec![fix];
+            }
+            let locales: Vec<&str> = if options.locales.is_empty() {
+                vec![FALLBACK_LOCALE]
+            } else {
+                options.locales.iter().map(String::as_str).collect()
+            };
+            locales.into_iter().map(|l| localize(&fix, l)).collect()
+        });
+        for (i, mut fix) in fixes.enumerate() {
+            if fix.snippet && !options.snippets {
+                for edit in &mut fix.edits {
+                    edit.new_text = snippet_to_plain(&edit.new_text);
+                }
+            }
+            let mut changes = std::collections::HashMap::new();
+            changes.insert(uri.clone(), fix.edits);
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: fix.title,
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }),
+                is_preferred: Some(i == 0),
+                ..Default::default()
+            }));
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(line: u32, start: u32, end: u32) -> TextEdit {
+        TextEdit {
+            range: Range {
+                start: Position {
+                    line,
+                    character: start,
+                },
+                end: Position {
+                    line,
+                    character: end,
+                },
+            },
+            new_text: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_attach_round_trips() {
+        let mut diag = Diagnostic::default();
+        assert!(fixes(&diag).is_empty());
+
+        attach(
+            &mut diag,
+            Fix {
+                title: "Remove it".to_string(),
+                edits: vec![edit(0, 1, 2)],
+                ..Default::default()
+            },
+        );
+        attach(
+            &mut diag,
+            Fix {
+                title: "Remove the other".to_string(),
+                edits: vec![edit(0, 3, 4)],
+                ..Default::default()
+            },
+        );
+
+        let titles: Vec<String> = fixes(&diag).into_iter().map(|f| f.title).collect();
+        assert_eq!(titles, ["Remove it", "Remove the other"]);
+    }
+
+    #[test]
+    fn test_code_actions_only_for_own_diagnostics() {
+        let uri: Uri = "file:///a.html".parse().unwrap();
+        let mut ours = Diagnostic {
+            source: Some("wcag-lsp".to_string()),
+            ..Default::default()
+        };
+        attach(
+            &mut ours,
+            Fix {
+                title: "Remove it".to_string(),
+                edits: vec![edit(0, 1, 2)],
+                ..Default::default()
+            },
+        );
+        let mut theirs = ours.clone();
+        theirs.source = Some("eslint".to_string());
+
+        let actions = code_actions(&uri, &[ours, theirs], &FixOptions::default());
+        assert_eq!(actions.len(), 1);
+        let CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+            panic!("expected a code action");
+        };
+        assert_eq!(action.kind, Some(CodeActionKind::QUICKFIX));
+        let changes = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+        assert_eq!(changes[&uri], vec![edit(0, 1, 2)]);
+    }
+
+    #[test]
+    fn test_snippet_to_plain() {
+        assert_eq!(
+            snippet_to_plain("<label>${1:Label text} <input></label>$0"),
+            "<label>Label text <input></label>"
+        );
+        let literal = "{`${a}`} \\ $5";
+        assert_eq!(snippet_to_plain(&snippet_escape(literal)), literal);
+    }
+
+    #[test]
+    fn test_snippet_fix_falls_back_to_plain_text() {
+        let uri: Uri = "file:///a.html".parse().unwrap();
+        let mut diag = Diagnostic {
+            source: Some("wcag-lsp".to_string()),
+            ..Default::default()
+        };
+        let mut snippet_edit = edit(0, 0, 0);
+        snippet_edit.new_text = "<label>${1:Label text}</label>".to_string();
+        attach(
+            &mut diag,
+            Fix {
+                title: "Add label".to_string(),
+                edits: vec![snippet_edit],
+                snippet: true,
+                ..Default::default()
+            },
+        );
+
+        let text = |snippets| {
+            let options = FixOptions {
+                snippets,
+                ..Default::default()
+            };
+            let actions = code_actions(&uri, std::slice::from_ref(&diag), &options);
+            let CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+                panic!("expected a code action");
+            };
+            action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri][0]
+                .new_text
+                .clone()
+        };
+        assert_eq!(text(true), "<label>${1:Label text}</label>");
+        assert_eq!(text(false), "<label>Label text</label>");
+    }
+
+    #[test]
+    fn test_candidate_locales() {
+        assert_eq!(candidate_locales(None, None), ["en"]);
+        assert_eq!(
+            candidate_locales(Some("de_ch"), Some("EN")),
+            ["de-CH", "en"]
+        );
+        assert_eq!(
+            candidate_locales(Some("fr"), Some("zh-hant-tw")),
+            ["fr", "zh-Hant-TW", "en"]
+        );
+        assert_eq!(candidate_locales(Some(""), Some("1x")), ["en"]);
+    }
+
+    #[test]
+    fn test_per_locale_fix_expands() {
+        let uri: Uri = "file:///a.html".parse().unwrap();
+        let mut diag = Diagnostic {
+            source: Some("wcag-lsp".to_string()),
+            ..Default::default()
+        };
+        let mut insert = edit(0, 5, 5);
+        insert.new_text = " lang=\"{locale}\"".to_string();
+        attach(
+            &mut diag,
+            Fix {
+                title: "Add lang=\"{locale}\"".to_string(),
+                edits: vec![insert],
+                per_locale: true,
+                ..Default::default()
+            },
+        );
+
+        let options = FixOptions {
+            locales: candidate_locales(Some("de"), Some("en-US")),
+            ..Default::default()
+        };
+        let actions = code_actions(&uri, &[diag], &options);
+        let titles: Vec<&str> = actions
+            .iter()
+            .map(|a| match a {
+                CodeActionOrCommand::CodeAction(a) => a.title.as_str(),
+                _ => panic!("expected a code action"),
+            })
+            .collect();
+        assert_eq!(
+            titles,
+            ["Add lang=\"de\"", "Add lang=\"en-US\"", "Add lang=\"en\""]
+        );
+        let CodeActionOrCommand::CodeAction(first) = &actions[0] else {
+            unreachable!()
+        };
+        assert_eq!(first.is_preferred, Some(true));
+        let changes = first.edit.as_ref().unwrap().changes.as_ref().unwrap();
+        assert_eq!(changes[&uri][0].new_text, " lang=\"de\"");
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let source = "<a>\n<b c d>";
+        let mut first = edit(1, 2, 4);
+        first.new_text = "x".to_string();
+        let mut second = edit(0, 0, 3);
+        second.new_text = "<p>".to_string();
+        assert_eq!(
+            apply(source, &[first.clone(), second]).as_deref(),
+            Some("<p>\n<bx d>")
+        );
+        assert_eq!(apply(source, &[first.clone(), edit(1, 3, 5)]), None);
+    }
+
+    #[test]
+    fn test_remove_node_includes_leading_space() {
+        let source = r#"<input required aria-required="true">"#;
+        let mut parser = crate::parser::create_parser(crate::parser::FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let tag = tree.root_node().child(0).unwrap().child(0).unwrap();
+        let attr = tag.child(tag.child_count() - 2).unwrap();
+        assert_eq!(&source[attr.byte_range()], r#"aria-required="true""#);
+
+        assert_eq!(remove_node(&attr), edit(0, 15, 36));
+    }
+}
@@ -0,0 +1,420 @@
+//! Machine-readable and shareable `wcag-lsp check` output.
+//!
+//! `--format json` prints every diagnostic with its rule's WCAG criterion and
+//! axe-core equivalents; `--format sarif` prints a SARIF 2.1.0 log for code
+//! scanning tools; `--format html` prints a standalone page for people to
+//! read. Either way the output goes to stdout, alone, and lines and
+//! columns count from 1 as in the text output. JSON and SARIF carry each
+//! diagnostic's fingerprint (see [`crate::diagnostic_data`]) for trackers to
+//! dedupe by, and all three the accessibility score of the run (see
+//! [`crate::score`]).
+//!
+//! Findings about a whole file rather than an element (see
+//! [`engine::DOCUMENT_RANGE`]) have the `"document"` scope in JSON, and no
+//! region in SARIF, which makes them file-level results there.
+
+use crate::diagnostic_data;
+use crate::engine;
+use crate::rules::{Rule, RuleMetadata};
+use crate::score::Score;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use tower_lsp_server::ls_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+const INFORMATION_URI: &str = "https://github.com/maxischmaxi/wcag-lsp";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+/// `partialFingerprints` key of the SARIF output; bump the version if the
+/// fingerprints ever change how they're computed.
+const SARIF_FINGERPRINT: &str = "wcagLsp/v1";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Sarif,
+    Html,
+}
+
+impl OutputFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            "sarif" => Some(OutputFormat::Sarif),
+            "html" => Some(OutputFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+/// The diagnostics of each file, by path, and the `score` of the run as
+/// JSON.
+pub fn json(
+    results: &BTreeMap<String, Vec<Diagnostic>>,
+    rules: &[Box<dyn Rule>],
+    score: &Score,
+) -> Value {
+    let metadata = by_id(rules);
+    let mut errors = 0;
+    let mut warnings = 0;
+    let mut found = Vec::new();
+    for (path, diagnostics) in results {
+        for diagnostic in diagnostics {
+            if diagnostic.severity == Some(DiagnosticSeverity::ERROR) {
+                errors += 1;
+            } else {
+                warnings += 1;
+            }
+            let rule_id = rule_id(diagnostic);
+            let meta = metadata.get(rule_id);
+            let range = diagnostic.range;
+            found.push(json!({
+                "file": path,
+                "line": range.start.line + 1,
+                "column": range.start.character + 1,
+                "endLine": range.end.line + 1,
+                "endColumn": range.end.character + 1,
+                "severity": severity_name(diagnostic.severity),
+                "ruleId": rule_id,
+                "scope": if engine::is_document_level(diagnostic) { "document" } else { "element" },
+                "message": diagnostic.message,
+                "wcagCriterion": meta.map(|m| m.wcag_criterion),
+                "wcagLevel": meta.map(|m| format!("{:?}", m.wcag_level)),
+                "axeRules": meta.map_or(&[][..], |m| m.axe_rules),
+                "fingerprint": fingerprint(diagnostic),
+            }));
+        }
+    }
+    json!({
+        "results": found,
+        "errors": errors,
+        "warnings": warnings,
+        "score": score,
+    })
+}
+
+/// The diagnostics of each file, by path, as a SARIF log with one run, which
+/// has the `score` in its properties.
+pub fn sarif(
+    results: &BTreeMap<String, Vec<Diagnostic>>,
+    rules: &[Box<dyn Rule>],
+    score: &Score,
+) -> Value {
+    let driver_rules: Vec<Value> = rules
+        .iter()
+        .map(|rule| {
+            let meta = rule.metadata();
+            json!({
+                "id": meta.id,
+                "shortDescription": { "text": meta.description },
+                "helpUri": meta.wcag_url,
+                "properties": {
+                    "wcagCriterion": meta.wcag_criterion,
+                    "wcagLevel": format!("{:?}", meta.wcag_level),
+                    "axeRuleIds": meta.axe_rules,
+                    "tags": ["accessibility"],
+                },
+            })
+        })
+        .collect();
+
+    let mut found = Vec::new();
+    for (path, diagnostics) in results {
+        for diagnostic in diagnostics {
+            let rule_id = rule_id(diagnostic);
+            let range = diagnostic.range;
+            let mut result = json!({
+                "ruleId": rule_id,
+                "level": match diagnostic.severity {
+                    Some(DiagnosticSeverity::ERROR) => "error",
+                    Some(DiagnosticSeverity::WARNING) | None => "warning",
+                    _ => "note",
+                },
+                "message": { "text": diagnostic.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": path.replace('\\', "/") },
+                        "region": {
+                            "startLine": range.start.line + 1,
+                            "startColumn": range.start.character + 1,
+                            "endLine": range.end.line + 1,
+                            "endColumn": range.end.character + 1,
+                        },
+                    },
+                }],
+            });
+            if engine::is_document_level(diagnostic) {
+                result["locations"][0]["physicalLocation"]
+                    .as_object_mut()
+                    .map(|location| location.remove("region"));
+            }
+            if let Some(index) = rules.iter().position(|r| r.metadata().id == rule_id) {
+                result["ruleIndex"] = json!(index);
+            }
+            if let Some(fingerprint) = fingerprint(diagnostic) {
+                result["partialFingerprints"] = json!({ SARIF_FINGERPRINT: fingerprint });
+            }
+            found.push(result);
+        }
+    }
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "wcag-lsp",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": INFORMATION_URI,
+                    "rules": driver_rules,
+                },
+            },
+            "results": found,
+            "properties": { "score": score },
+        }],
+    })
+}
+
+/// The diagnostics of each file, by path, as an HTML page that opens with
+/// the `score` of the run and has a table of findings per file.
+pub fn html(
+    results: &BTreeMap<String, Vec<Diagnostic>>,
+    rules: &[Box<dyn Rule>],
+    score: &Score,
+) -> String {
+    let metadata = by_id(rules);
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>wcag-lsp report</title>\n\
+         <style>body{font-family:system-ui,sans-serif;margin:2rem}\
+         table{border-collapse:collapse;margin-bottom:2rem}\
+         th,td{border:1px solid #767676;padding:.25rem .5rem;text-align:left}\
+         caption{font-weight:bold;text-align:left;padding:.5rem 0}</style>\n\
+         </head>\n<body>\n<main>\n<h1>wcag-lsp report</h1>\n",
+    );
+    let plural = |n: f64, word: &str| match n == 1.0 {
+        true => format!("{n} {word}"),
+        false => format!("{n} {word}s"),
+    };
+    out.push_str(&format!(
+        "<p>Accessibility score: <strong>{:.1}/100</strong> ({} in {}, {})</p>\n",
+        score.score,
+        plural(score.weighted_problems, "weighted problem"),
+        plural(score.elements as f64, "element"),
+        plural(score.files as f64, "file")
+    ));
+    if results.is_empty() {
+        out.push_str("<p>No problems found.</p>\n");
+    }
+    for (path, diagnostics) in results {
+        out.push_str(&format!(
+            "<table>\n<caption>{}</caption>\n<thead><tr><th scope=\"col\">Location</th>\
+             <th scope=\"col\">Severity</th><th scope=\"col\">Rule</th>\
+             <th scope=\"col\">WCAG</th><th scope=\"col\">Message</th></tr></thead>\n<tbody>\n",
+            escape(path)
+        ));
+        for diagnostic in diagnostics {
+            let rule_id = rule_id(diagnostic);
+            let location = match engine::is_document_level(diagnostic) {
+                true => "document".to_string(),
+                false => format!(
+                    "{}:{}",
+                    diagnostic.range.start.line + 1,
+                    diagnostic.range.start.character + 1
+                ),
+            };
+            let wcag = metadata.get(rule_id).map_or(String::new(), |m| {
+                format!(
+                    "<a href=\"{}\">{} {:?}</a>",
+                    escape(m.wcag_url),
+                    m.wcag_criterion,
+                    m.wcag_level
+                )
+            });
+            out.push_str(&format!(
+                "<tr><td>{location}</td><td>{}</td><td>{}</td><td>{wcag}</td><td>{}</td></tr>\n",
+                severity_name(diagnostic.severity),
+                escape(rule_id),
+                escape(&diagnostic.message)
+            ));
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+    out.push_str("</main>\n</body>\n</html>\n");
+    out
+}
+
+/// `text` with the characters that are markup in HTML escaped.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn by_id(rules: &[Box<dyn Rule>]) -> BTreeMap<&str, &RuleMetadata> {
+    rules
+        .iter()
+        .map(|rule| (rule.metadata().id, rule.metadata()))
+        .collect()
+}
+
+fn rule_id(diagnostic: &Diagnostic) -> &str {
+    match &diagnostic.code {
+        Some(NumberOrString::String(id)) => id,
+        _ => "",
+    }
+}
+
+fn fingerprint(diagnostic: &Diagnostic) -> Option<String> {
+    diagnostic_data::data(diagnostic)
+        .map(|data| data.fingerprint)
+        .filter(|fingerprint| !fingerprint.is_empty())
+}
+
+/// The severity as the text output names it.
+pub fn severity_name(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::INFORMATION) => "info",
+        Some(DiagnosticSeverity::HINT) => "hint",
+        _ => "warning",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules;
+    use crate::score::Tally;
+    use tower_lsp_server::ls_types::{Position, Range};
+
+    fn score() -> Score {
+        Tally::default().score()
+    }
+
+    fn results() -> BTreeMap<String, Vec<Diagnostic>> {
+        let diagnostic = Diagnostic {
+            range: Range::new(Position::new(2, 4), Position::new(2, 24)),
+            severity: Some(DiagnosticSeverity::ERROR),
+            code: Some(NumberOrString::String("img-alt".to_string())),
+            message: "Missing alt".to_string(),
+            ..Default::default()
+        };
+        BTreeMap::from([("src/index.html".to_string(), vec![diagnostic])])
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(OutputFormat::parse("SARIF"), Some(OutputFormat::Sarif));
+        assert_eq!(OutputFormat::parse("json"), Some(OutputFormat::Json));
+        assert_eq!(OutputFormat::parse("html"), Some(OutputFormat::Html));
+        assert_eq!(OutputFormat::parse("xml"), None);
+    }
+
+    #[test]
+    fn test_json_has_wcag_and_axe_ids() {
+        let out = json(&results(), &rules::all_rules(), &score());
+        let result = &out["results"][0];
+        assert_eq!(result["file"], "src/index.html");
+        assert_eq!(result["line"], 3);
+        assert_eq!(result["column"], 5);
+        assert_eq!(result["ruleId"], "img-alt");
+        assert_eq!(result["wcagCriterion"], "1.1.1");
+        assert_eq!(result["axeRules"], json!(["image-alt"]));
+        assert_eq!(out["errors"], 1);
+        assert_eq!(result["fingerprint"], Value::Null);
+        assert_eq!(out["score"]["score"], 100.0);
+    }
+
+    #[test]
+    fn test_fingerprints_from_data() {
+        let mut results = results();
+        let diagnostic = &mut results.get_mut("src/index.html").unwrap()[0];
+        diagnostic.data = Some(json!({
+            "ruleId": "img-alt",
+            "wcagCriterion": "1.1.1",
+            "wcagLevel": "A",
+            "fixable": false,
+            "fingerprint": "0123456789abcdef",
+        }));
+        let rules = rules::all_rules();
+        assert_eq!(
+            json(&results, &rules, &score())["results"][0]["fingerprint"],
+            "0123456789abcdef"
+        );
+        let result = &sarif(&results, &rules, &score())["runs"][0]["results"][0];
+        assert_eq!(
+            result["partialFingerprints"]["wcagLsp/v1"],
+            "0123456789abcdef"
+        );
+    }
+
+    #[test]
+    fn test_sarif_log() {
+        let rules = rules::all_rules();
+        let tally = Tally::of(&results()["src/index.html"], 100, &rules);
+        let out = sarif(&results(), &rules, &tally.score());
+        assert_eq!(out["version"], "2.1.0");
+        let run = &out["runs"][0];
+        let driver_rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(driver_rules.len(), rules.len());
+
+        let result = &run["results"][0];
+        assert_eq!(result["level"], "error");
+        let index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(driver_rules[index]["id"], "img-alt");
+        assert_eq!(
+            driver_rules[index]["properties"]["axeRuleIds"],
+            json!(["image-alt"])
+        );
+        let region = &result["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 3);
+        assert_eq!(region["endColumn"], 25);
+        assert_eq!(run["properties"]["score"]["score"], 76.9);
+    }
+
+    #[test]
+    fn test_html_report() {
+        let rules = rules::all_rules();
+        let mut results = results();
+        results.get_mut("src/index.html").unwrap()[0].message = "Missing <img> alt".to_string();
+        let tally = Tally::of(&results["src/index.html"], 100, &rules);
+        let out = html(&results, &rules, &tally.score());
+        assert!(out.starts_with("<!DOCTYPE html>\n<html lang=\"en\">"));
+        assert!(out.contains("Accessibility score: <strong>76.9/100</strong>"));
+        assert!(out.contains("<caption>src/index.html</caption>"));
+        assert!(out.contains("<td>3:5</td><td>error</td><td>img-alt</td>"));
+        assert!(out.contains(">1.1.1 A</a>"));
+        assert!(out.contains("<td>Missing &lt;img&gt; alt</td>"));
+
+        let out = html(&BTreeMap::new(), &rules, &score());
+        assert!(out.contains("<strong>100.0/100</strong>"));
+        assert!(out.contains("No problems found."));
+        assert!(!out.contains("<table>"));
+    }
+
+    #[test]
+    fn test_document_level_results() {
+        let diagnostic = Diagnostic {
+            range: engine::DOCUMENT_RANGE,
+            code: Some(NumberOrString::String("page-title".to_string())),
+            message: "Missing title".to_string(),
+            ..Default::default()
+        };
+        let results = BTreeMap::from([("index.html".to_string(), vec![diagnostic])]);
+        let rules = rules::all_rules();
+        assert_eq!(
+            json(&results, &rules, &score())["results"][0]["scope"],
+            "document"
+        );
+        let location = &sarif(&results, &rules, &score())["runs"][0]["results"][0]["locations"][0];
+        assert_eq!(
+            location["physicalLocation"]["artifactLocation"]["uri"],
+            "index.html"
+        );
+        assert_eq!(location["physicalLocation"]["region"], Value::Null);
+    }
+}
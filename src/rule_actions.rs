@@ -0,0 +1,594 @@
+//! Code actions offered on every wcag-lsp diagnostic, after its quick fixes:
+//! turning its rule off for the line with a comment directive, or in the
+//! config file, and opening the rule's documentation.
+
+use crate::config::Config;
+use crate::document::Document;
+use crate::engine;
+use crate::quickfix;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tower_lsp_server::ls_types::*;
+
+/// The command that opens a rule's documentation, with its URL as argument.
+pub const SHOW_DOCUMENTATION: &str = "wcag-lsp.showDocumentation";
+
+/// What the actions need besides the diagnostics.
+pub struct RuleActionContext<'a> {
+    /// The document the diagnostics are in, for the comment directives.
+    pub document: Option<&'a Document>,
+    /// The config the document is linted with.
+    pub config: &'a Config,
+    /// Where `.wcag.toml` is created when there's no config file yet.
+    pub workspace_root: Option<&'a Path>,
+    /// The client can create files in a workspace edit.
+    pub create_files: bool,
+    /// The client can open a URL with `window/showDocument`.
+    pub show_document: bool,
+}
+
+/// How a comment directive is written where it goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommentStyle {
+    /// `<!-- … -->`, in markup.
+    Html,
+    /// `// …`, in JS.
+    Line,
+    /// `{/* … */}`, among the children of a JSX element.
+    JsxChild,
+    /// `/* … */`, at the top of a JS file.
+    Block,
+}
+
+impl CommentStyle {
+    fn comment(self, text: &str) -> String {
+        match self {
+            CommentStyle::Html => format!("<!-- {text} -->"),
+            CommentStyle::Line => format!("// {text}"),
+            CommentStyle::JsxChild => format!("{{/* {text} */}}"),
+            CommentStyle::Block => format!("/* {text} */"),
+        }
+    }
+}
+
+/// Actions disabling the rules of the wcag-lsp diagnostics in `diagnostics`
+/// and showing their documentation, once per line and rule.
+pub fn code_actions(
+    uri: &Uri,
+    diagnostics: &[Diagnostic],
+    cx: &RuleActionContext,
+) -> Vec<CodeActionOrCommand> {
+    let mut actions = Vec::new();
+    let mut lines = HashSet::new();
+    let mut rules = HashSet::new();
+    for diagnostic in diagnostics {
+        if diagnostic.source.as_deref() != Some("wcag-lsp") {
+            continue;
+        }
+        let Some(NumberOrString::String(rule_id)) = &diagnostic.code else {
+            continue;
+        };
+        if let Some(document) = cx.document
+            && lines.insert((rule_id.clone(), diagnostic.range.start.line))
+        {
+            actions.push(disable_with_comment(uri, document, diagnostic, rule_id));
+        }
+        if !rules.insert(rule_id.clone()) {
+            continue;
+        }
+        actions.extend(disable_in_config(diagnostic, rule_id, cx));
+        if cx.show_document {
+            actions.extend(show_documentation(diagnostic, rule_id));
+        }
+    }
+    actions
+        .into_iter()
+        .map(CodeActionOrCommand::CodeAction)
+        .collect()
+}
+
+/// `wcag-disable-next-line` above the diagnostic, indented like its line, or
+/// `wcag-disable` at the top for a finding about the whole document.
+fn disable_with_comment(
+    uri: &Uri,
+    document: &Document,
+    diagnostic: &Diagnostic,
+    rule_id: &str,
+) -> CodeAction {
+    let (title, new_text, line) = if engine::is_document_level(diagnostic) {
+        let style = match document.file_type.is_jsx_like() {
+            true => CommentStyle::Block,
+            false => CommentStyle::Html,
+        };
+        (
+            format!("Disable {rule_id} for this file"),
+            format!("{}\n", style.comment(&format!("wcag-disable {rule_id}"))),
+            0,
+        )
+    } else {
+        let line = diagnostic.range.start.line;
+        let indent: String = document
+            .source
+            .lines()
+            .nth(line as usize)
+            .unwrap_or("")
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+        let style = comment_style(document, diagnostic.range.start);
+        (
+            format!("Disable {rule_id} for this line"),
+            format!(
+                "{indent}{}\n",
+                style.comment(&format!("wcag-disable-next-line {rule_id}"))
+            ),
+            line,
+        )
+    };
+    let position = Position { line, character: 0 };
+    let edit = TextEdit {
+        range: Range {
+            start: position,
+            end: position,
+        },
+        new_text,
+    };
+    action(
+        title,
+        diagnostic,
+        HashMap::from([(uri.clone(), vec![edit])]),
+    )
+}
+
+/// The comment syntax for a line above `position`. In JSX it depends on
+/// what the line break before it is in: JSX children take an expression,
+/// anything else a line comment.
+fn comment_style(document: &Document, position: Position) -> CommentStyle {
+    if !document.file_type.is_jsx_like() {
+        return CommentStyle::Html;
+    }
+    let offset = quickfix::offset(&document.source, position);
+    let mut node = document
+        .tree
+        .root_node()
+        .descendant_for_byte_range(offset, offset);
+    while let Some(n) = node {
+        // The markup of an `` html`…` `` template.
+        if n.kind() == "template_string" {
+            return CommentStyle::Html;
+        }
+        if n.start_position().row < position.line as usize {
+            return match n.kind() {
+                "jsx_element" => CommentStyle::JsxChild,
+                _ => CommentStyle::Line,
+            };
+        }
+        node = n.parent();
+    }
+    CommentStyle::Line
+}
+
+/// Sets the rule to `"off"` in the config file, or creates a `.wcag.toml`
+/// doing so in the workspace root if there's none.
+fn disable_in_config(
+    diagnostic: &Diagnostic,
+    rule_id: &str,
+    cx: &RuleActionContext,
+) -> Option<CodeAction> {
+    let Some(path) = &cx.config.path else {
+        if !cx.create_files {
+            return None;
+        }
+        let uri = Uri::from_file_path(cx.workspace_root?.join(".wcag.toml"))?;
+        let edit = TextEdit {
+            range: Range::default(),
+            new_text: format!("[rules]\n{rule_id} = \"off\"\n"),
+        };
+        let mut action = action(
+            format!("Disable {rule_id} in a new .wcag.toml"),
+            diagnostic,
+            HashMap::new(),
+        );
+        action.edit = Some(WorkspaceEdit {
+            document_changes: Some(DocumentChanges::Operations(vec![
+                DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                    uri: uri.clone(),
+                    options: Some(CreateFileOptions {
+                        overwrite: Some(false),
+                        ignore_if_exists: Some(true),
+                    }),
+                    annotation_id: None,
+                })),
+                DocumentChangeOperation::Edit(TextDocumentEdit {
+                    text_document: OptionalVersionedTextDocumentIdentifier { uri, version: None },
+                    edits: vec![OneOf::Left(edit)],
+                }),
+            ])),
+            ..Default::default()
+        });
+        return Some(action);
+    };
+    let text = std::fs::read_to_string(path).ok()?;
+    let edit = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => json_edit(&text, rule_id)?,
+        _ => toml_edit(&text, rule_id),
+    };
+    let name = path.file_name()?.to_string_lossy();
+    Some(action(
+        format!("Disable {rule_id} in {name}"),
+        diagnostic,
+        HashMap::from([(Uri::from_file_path(path)?, vec![edit])]),
+    ))
+}
+
+/// Sets `rule_id = "off"` in the `[rules]` table, replacing an entry for it.
+fn toml_edit(text: &str, rule_id: &str) -> TextEdit {
+    let entry = format!("{rule_id} = \"off\"");
+    let mut rules_header = None;
+    let mut in_rules = false;
+    for (i, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.starts_with('[') {
+            in_rules = line == "[rules]";
+            if in_rules {
+                rules_header = Some(i);
+            }
+            continue;
+        }
+        let key = line
+            .split('=')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .trim_matches('"');
+        if in_rules && key == rule_id {
+            let start = text.split_inclusive('\n').take(i).map(str::len).sum();
+            let end = start + text[start..].find('\n').unwrap_or(text.len() - start);
+            return replace(text, start, end, entry);
+        }
+    }
+    match rules_header {
+        Some(i) => {
+            let start = text.split_inclusive('\n').take(i + 1).map(str::len).sum();
+            // The header may be the last line, without a newline.
+            let separator = if text[..start].ends_with('\n') {
+                ""
+            } else {
+                "\n"
+            };
+            replace(text, start, start, format!("{separator}{entry}\n"))
+        }
+        None => {
+            let separator = match text {
+                "" => "",
+                _ if text.ends_with("\n\n") => "",
+                _ if text.ends_with('\n') => "\n",
+                _ => "\n\n",
+            };
+            replace(
+                text,
+                text.len(),
+                text.len(),
+                format!("{separator}[rules]\n{entry}\n"),
+            )
+        }
+    }
+}
+
+/// Sets `"rule_id": "off"` in the `"rules"` object, replacing the value of
+/// an entry for it. `None` if the file isn't a JSON object.
+fn json_edit(text: &str, rule_id: &str) -> Option<TextEdit> {
+    let key = format!("\"{rule_id}\"");
+    if let Some(rules) = text.find("\"rules\"") {
+        let open = rules + text[rules..].find('{')?;
+        let close = open + text[open..].find('}')?;
+        if let Some(found) = text[open..close].find(&key) {
+            // The string after the key's colon.
+            let after = open + found + key.len();
+            let value = after + text[after..close].find('"')?;
+            let end = value + 1 + text[value + 1..close].find('"')? + 1;
+            return Some(replace(text, value, end, "\"off\"".to_string()));
+        }
+        return Some(insert_member(text, open, &format!("{key}: \"off\"")));
+    }
+    let open = text.find('{')?;
+    Some(insert_member(
+        text,
+        open,
+        &format!("\"rules\": {{ {key}: \"off\" }}"),
+    ))
+}
+
+/// Inserts `member` first in the object opening at `open`, spaced like the
+/// member after it.
+fn insert_member(text: &str, open: usize, member: &str) -> TextEdit {
+    let rest = &text[open + 1..];
+    let space = &rest[..rest.len() - rest.trim_start().len()];
+    let new_text = match rest.trim_start().starts_with('}') {
+        true => format!(" {member} "),
+        false if space.is_empty() => format!(" {member},"),
+        false => format!("{space}{member},"),
+    };
+    replace(text, open + 1, open + 1, new_text)
+}
+
+/// An edit replacing the bytes `start..end` of `text`.
+fn replace(text: &str, start: usize, end: usize, new_text: String) -> TextEdit {
+    TextEdit {
+        range: Range {
+            start: position_at(text, start),
+            end: position_at(text, end),
+        },
+        new_text,
+    }
+}
+
+/// The position of byte `offset`; columns are bytes, as in
+/// [`quickfix::offset`].
+fn position_at(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: (offset - line_start) as u32,
+    }
+}
+
+/// Opens the page the diagnostic's `codeDescription` links to.
+fn show_documentation(diagnostic: &Diagnostic, rule_id: &str) -> Option<CodeAction> {
+    let href = &diagnostic.code_description.as_ref()?.href;
+    let mut action = action(
+        format!("Show documentation for {rule_id}"),
+        diagnostic,
+        HashMap::new(),
+    );
+    action.edit = None;
+    action.command = Some(Command {
+        title: action.title.clone(),
+        command: SHOW_DOCUMENTATION.to_string(),
+        arguments: Some(vec![serde_json::Value::String(href.to_string())]),
+    });
+    Some(action)
+}
+
+fn action(
+    title: String,
+    diagnostic: &Diagnostic,
+    changes: HashMap<Uri, Vec<TextEdit>>,
+) -> CodeAction {
+    CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        is_preferred: Some(false),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::DocumentManager;
+    use crate::rules;
+
+    fn diagnostics(docs: &mut DocumentManager, uri: &str, source: &str) -> Vec<Diagnostic> {
+        let doc = docs.open(uri.to_string(), source.to_string(), 1).unwrap();
+        engine::run_diagnostics(doc, &rules::all_rules(), &Config::default())
+    }
+
+    fn titles(actions: &[CodeActionOrCommand]) -> Vec<&str> {
+        actions
+            .iter()
+            .map(|a| match a {
+                CodeActionOrCommand::CodeAction(a) => a.title.as_str(),
+                _ => panic!("expected a code action"),
+            })
+            .collect()
+    }
+
+    fn inserted(action: &CodeActionOrCommand, uri: &Uri) -> String {
+        let CodeActionOrCommand::CodeAction(action) = action else {
+            panic!("expected a code action");
+        };
+        let changes = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+        changes[uri][0].new_text.clone()
+    }
+
+    fn context<'a>(document: &'a Document, config: &'a Config) -> RuleActionContext<'a> {
+        RuleActionContext {
+            document: Some(document),
+            config,
+            workspace_root: None,
+            create_files: false,
+            show_document: true,
+        }
+    }
+
+    #[test]
+    fn test_html_directive_and_documentation() {
+        let mut docs = DocumentManager::new();
+        let source = "<main>\n  <img src=\"a.png\"><img src=\"b.png\">\n</main>\n";
+        let mut diags = diagnostics(&mut docs, "file:///a.html", source);
+        // A fragment has no title either.
+        diags.retain(|d| d.code == Some(NumberOrString::String("img-alt".to_string())));
+        let uri: Uri = "file:///a.html".parse().unwrap();
+        let config = Config::default();
+        let doc = docs.get("file:///a.html").unwrap();
+        let actions = code_actions(&uri, &diags, &context(doc, &config));
+
+        // One directive for the two images on the line.
+        assert_eq!(
+            titles(&actions),
+            [
+                "Disable img-alt for this line",
+                "Show documentation for img-alt"
+            ]
+        );
+        assert_eq!(
+            inserted(&actions[0], &uri),
+            "  <!-- wcag-disable-next-line img-alt -->\n"
+        );
+        let CodeActionOrCommand::CodeAction(docs_action) = &actions[1] else {
+            unreachable!()
+        };
+        let command = docs_action.command.as_ref().unwrap();
+        assert_eq!(command.command, SHOW_DOCUMENTATION);
+        assert!(docs_action.edit.is_none());
+
+        // The directive silences the rule.
+        let fixed = format!(
+            "<main>\n{}  <img src=\"a.png\"><img src=\"b.png\">\n</main>\n",
+            inserted(&actions[0], &uri)
+        );
+        let left = diagnostics(&mut docs, "file:///b.html", &fixed);
+        assert!(left.iter().all(|d| d.code != diags[0].code));
+    }
+
+    #[test]
+    fn test_jsx_comment_styles() {
+        let mut docs = DocumentManager::new();
+        let source = r#"const A = () => (
+  <div>
+    <img src="a.png" />
+    {show && (
+      <img src="b.png" />
+    )}
+  </div>
+);
+const B = () => <img src="c.png" />;
+"#;
+        let diags = diagnostics(&mut docs, "file:///a.tsx", source);
+        let uri: Uri = "file:///a.tsx".parse().unwrap();
+        let config = Config::default();
+        let doc = docs.get("file:///a.tsx").unwrap();
+        let mut cx = context(doc, &config);
+        cx.show_document = false;
+        let actions = code_actions(&uri, &diags, &cx);
+        let texts: Vec<String> = actions.iter().map(|a| inserted(a, &uri)).collect();
+        assert_eq!(
+            texts,
+            [
+                "    {/* wcag-disable-next-line img-alt */}\n",
+                "      // wcag-disable-next-line img-alt\n",
+                "// wcag-disable-next-line img-alt\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_document_level_directive() {
+        let mut docs = DocumentManager::new();
+        let source = "<html lang=\"en\"><body><main><p>Hi</p></main></body></html>\n";
+        let diags = diagnostics(&mut docs, "file:///a.html", source);
+        let uri: Uri = "file:///a.html".parse().unwrap();
+        let config = Config::default();
+        let doc = docs.get("file:///a.html").unwrap();
+        let actions = code_actions(&uri, &diags, &context(doc, &config));
+        assert_eq!(titles(&actions)[0], "Disable page-title for this file");
+        assert_eq!(
+            inserted(&actions[0], &uri),
+            "<!-- wcag-disable page-title -->\n"
+        );
+    }
+
+    #[test]
+    fn test_disable_in_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml = dir.path().join(".wcag.toml");
+        std::fs::write(
+            &toml,
+            "[severity]\nAA = \"error\"\n\n[rules]\nimg-alt = \"warning\"\n",
+        )
+        .unwrap();
+        let config = Config::from_dir(dir.path());
+        let diagnostic = Diagnostic {
+            source: Some("wcag-lsp".to_string()),
+            code: Some(NumberOrString::String("img-alt".to_string())),
+            ..Default::default()
+        };
+        let cx = RuleActionContext {
+            document: None,
+            config: &config,
+            workspace_root: Some(dir.path()),
+            create_files: true,
+            show_document: false,
+        };
+        let uri: Uri = "file:///a.html".parse().unwrap();
+        let actions = code_actions(&uri, std::slice::from_ref(&diagnostic), &cx);
+        assert_eq!(titles(&actions), ["Disable img-alt in .wcag.toml"]);
+        let CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+            unreachable!()
+        };
+        let changes = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+        let text = std::fs::read_to_string(&toml).unwrap();
+        let edited = quickfix::apply(&text, &changes[&Uri::from_file_path(&toml).unwrap()]);
+        assert_eq!(
+            edited.as_deref(),
+            Some("[severity]\nAA = \"error\"\n\n[rules]\nimg-alt = \"off\"\n")
+        );
+
+        // Without a config file, one is created.
+        let config = Config::default();
+        let cx = RuleActionContext {
+            config: &config,
+            ..cx
+        };
+        let actions = code_actions(&uri, &[diagnostic], &cx);
+        assert_eq!(titles(&actions), ["Disable img-alt in a new .wcag.toml"]);
+        let CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+            unreachable!()
+        };
+        let Some(DocumentChanges::Operations(operations)) =
+            &action.edit.as_ref().unwrap().document_changes
+        else {
+            panic!("expected document changes");
+        };
+        assert!(matches!(
+            operations[0],
+            DocumentChangeOperation::Op(ResourceOp::Create(_))
+        ));
+    }
+
+    #[test]
+    fn test_toml_edit() {
+        let edit = |text: &str| quickfix::apply(text, &[toml_edit(text, "img-alt")]).unwrap();
+        assert_eq!(edit(""), "[rules]\nimg-alt = \"off\"\n");
+        assert_eq!(
+            edit("[severity]\nA = \"error\""),
+            "[severity]\nA = \"error\"\n\n[rules]\nimg-alt = \"off\"\n"
+        );
+        assert_eq!(
+            edit("[rules] # overrides\nheading-order = \"off\"\n"),
+            "[rules] # overrides\nimg-alt = \"off\"\nheading-order = \"off\"\n"
+        );
+        assert_eq!(edit("[rules]"), "[rules]\nimg-alt = \"off\"\n");
+        assert_eq!(
+            edit("[rules]\n\"img-alt\" = \"error\"\n[jsx]\nstyled = true\n"),
+            "[rules]\nimg-alt = \"off\"\n[jsx]\nstyled = true\n"
+        );
+    }
+
+    #[test]
+    fn test_json_edit() {
+        let edit =
+            |text: &str| quickfix::apply(text, &[json_edit(text, "img-alt").unwrap()]).unwrap();
+        assert_eq!(edit("{}"), "{ \"rules\": { \"img-alt\": \"off\" } }");
+        assert_eq!(
+            edit("{\n  \"severity\": { \"A\": \"error\" }\n}"),
+            "{\n  \"rules\": { \"img-alt\": \"off\" },\n  \"severity\": { \"A\": \"error\" }\n}"
+        );
+        assert_eq!(
+            edit("{\n  \"rules\": {\n    \"heading-order\": \"off\"\n  }\n}"),
+            "{\n  \"rules\": {\n    \"img-alt\": \"off\",\n    \"heading-order\": \"off\"\n  }\n}"
+        );
+        assert_eq!(
+            edit("{ \"rules\": { \"img-alt\": \"warning\" } }"),
+            "{ \"rules\": { \"img-alt\": \"off\" } }"
+        );
+        assert!(json_edit("[]", "img-alt").is_none());
+    }
+}
@@ -14,6 +14,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "2.4.4",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/link-purpose-in-context.html",
     default_severity: Severity::Error,
+    axe_rules: &["link-name"],
 };
 
 impl Rule for AnchorContent {
@@ -60,10 +61,11 @@ fn check_html_element(element: &Node, source: &str, diagnostics: &mut Vec<Diagno
 
     // A static or bound `aria-label`/`aria-labelledby` provides an accessible
     // name; a bound `:aria-label="x"` still counts as present.
-    let has_aria_label = html_attrs::attrs(&tag, source)
+    let attrs = html_attrs::attrs(&tag, source);
+    let has_aria_label = attrs
         .iter()
         .any(|a| a.name_eq("aria-label") || a.name_eq("aria-labelledby"));
-    if has_aria_label {
+    if has_aria_label || html_attrs::has_dynamic_content(&attrs) {
         return;
     }
 
@@ -269,6 +271,12 @@ mod tests {
         rule.check(&tree.root_node(), source, FileType::Vue)
     }
 
+    #[test]
+    fn test_vue_v_text_passes() {
+        let diags = check_vue(r#"<template><a href="/" v-text="label"></a></template>"#);
+        assert_eq!(diags.len(), 0, "v-text fills the anchor, got: {diags:?}");
+    }
+
     #[test]
     fn test_vue_bound_aria_label_passes() {
         let diags = check_vue(r#"<template><a href="/" :aria-label="label"></a></template>"#);
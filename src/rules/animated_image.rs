@@ -0,0 +1,323 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AnimatedImage;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "animated-image",
+    description: "Animated images must not flash more than three times a second",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.3.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/three-flashes-or-below-threshold.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Extensions of image formats that are usually animated.
+const ANIMATED_EXTENSIONS: &[&str] = &[".gif", ".apng"];
+
+/// Landmark elements and roles whose images are prominent.
+const PROMINENT_TAGS: &[&str] = &["header"];
+const PROMINENT_ROLES: &[&str] = &["banner"];
+
+/// Class or id fragments of page sections whose images are prominent.
+const PROMINENT_NAMES: &[&str] = &["hero", "banner", "masthead", "splash", "jumbotron"];
+
+/// Width or height, in pixels, from which an image is prominent wherever it
+/// is.
+const PROMINENT_SIZE: u32 = 300;
+
+/// What the ancestors of an element say about it.
+#[derive(Clone, Copy, Default)]
+struct Context {
+    /// In a header, banner or hero section.
+    prominent: bool,
+    /// In a `<picture>` with a `prefers-reduced-motion` source.
+    reduced_motion_source: bool,
+}
+
+impl Rule for AnimatedImage {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Whether an image flashes can't be seen in the markup; this only points
+    /// out the prominent ones to review.
+    fn informational(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, Context::default(), &mut diagnostics);
+        } else {
+            visit_html(root, source, Context::default(), &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, mut context: Context, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source).unwrap_or("");
+        let attrs: Vec<(String, Option<String>)> = html_attrs::attrs(&tag, source)
+            .into_iter()
+            .filter(|a| !a.bound)
+            .map(|a| (a.name_lower(), a.value))
+            .collect();
+        if name.eq_ignore_ascii_case("picture") {
+            context.reduced_motion_source = html_has_reduced_motion_source(node, source);
+        }
+        check_element(
+            node,
+            &name.to_ascii_lowercase(),
+            &attrs,
+            &mut context,
+            diagnostics,
+        );
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, context, diagnostics);
+    }
+}
+
+fn html_has_reduced_motion_source(picture: &Node, source: &str) -> bool {
+    let mut cursor = picture.walk();
+    picture.named_children(&mut cursor).any(|child| {
+        html_attrs::element_tag_name(&child, source)
+            .is_some_and(|n| n.eq_ignore_ascii_case("source"))
+            && html_attrs::element_attr_value(&child, source, "media")
+                .is_some_and(|media| is_reduced_motion_query(&media))
+    })
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, mut context: Context, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let attrs = jsx_string_attrs(&tag, source);
+        if name == "picture" {
+            context.reduced_motion_source = jsx_has_reduced_motion_source(node, source);
+        }
+        check_element(node, name, &attrs, &mut context, diagnostics);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, context, diagnostics);
+    }
+}
+
+/// The attributes of a JSX tag with their value if it's a string; `className`
+/// is called `class`.
+fn jsx_string_attrs(tag: &Node, source: &str) -> Vec<(String, Option<String>)> {
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .filter_map(|attr| jsx_attrs::jsx_attribute(&attr, source))
+        .map(|(name, value)| {
+            let name = if name == "className" { "class" } else { name };
+            let value = value
+                .filter(|v| v.kind() == "string")
+                .map(|v| source[v.byte_range()].trim_matches(['"', '\'']).to_string());
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+fn jsx_has_reduced_motion_source(picture: &Node, source: &str) -> bool {
+    let mut cursor = picture.walk();
+    picture.named_children(&mut cursor).any(|child| {
+        if child.kind() != "jsx_self_closing_element"
+            || child
+                .child_by_field_name("name")
+                .is_none_or(|n| &source[n.byte_range()] != "source")
+        {
+            return false;
+        }
+        jsx_string_attrs(&child, source)
+            .iter()
+            .any(|(name, value)| {
+                name == "media" && value.as_deref().is_some_and(is_reduced_motion_query)
+            })
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+/// Updates `context` with an element named `name`, and reports it if it's an
+/// animated image in a prominent spot with no reduced motion alternative.
+fn check_element(
+    node: &Node,
+    name: &str,
+    attrs: &[(String, Option<String>)],
+    context: &mut Context,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let value = |attr: &str| {
+        attrs
+            .iter()
+            .find(|(n, _)| n == attr)
+            .and_then(|(_, v)| v.as_deref())
+    };
+    let named_prominent = ["class", "id"].iter().any(|attr| {
+        value(attr).is_some_and(|v| {
+            let v = v.to_ascii_lowercase();
+            PROMINENT_NAMES.iter().any(|p| v.contains(p))
+        })
+    });
+    if PROMINENT_TAGS.contains(&name)
+        || value("role").is_some_and(|r| PROMINENT_ROLES.contains(&r))
+        || named_prominent
+    {
+        context.prominent = true;
+    }
+
+    if name != "img" || context.reduced_motion_source {
+        return;
+    }
+    let Some(src) = value("src").filter(|src| is_animated_format(src)) else {
+        return;
+    };
+    let large = ["width", "height"].iter().any(|attr| {
+        value(attr)
+            .and_then(|v| v.trim_end_matches("px").parse::<u32>().ok())
+            .is_some_and(|size| size >= PROMINENT_SIZE)
+    });
+    if context.prominent || large {
+        diagnostics.push(make_diagnostic(node, src));
+    }
+}
+
+/// Whether `src` names a GIF or APNG file, ignoring any query or fragment.
+fn is_animated_format(src: &str) -> bool {
+    let path = src
+        .split(['?', '#'])
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    ANIMATED_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+fn is_reduced_motion_query(media: &str) -> bool {
+    media
+        .to_ascii_lowercase()
+        .contains("prefers-reduced-motion")
+}
+
+fn make_diagnostic(node: &Node, src: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::INFORMATION),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "`{}` may be animated and is prominent on the page: check that it doesn't flash, and offer a still image in a `<picture>` with `<source media=\"(prefers-reduced-motion: reduce)\">`. {} [WCAG {} Level {:?}]",
+            src, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AnimatedImage;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_informational() {
+        assert!(AnimatedImage.informational());
+        assert!(!AnimatedImage.opt_in());
+    }
+
+    #[test]
+    fn test_prominent_animated_images_reported() {
+        let diags = check_html(
+            r#"<header><img src="/logo.gif" alt="Acme"></header>
+<section class="page-hero"><img src="intro.APNG?v=2" alt=""></section>
+<main><img src="dance.gif" alt="Dance" width="480"></main>"#,
+        );
+        let lines: Vec<u32> = diags.iter().map(|d| d.range.start.line).collect();
+        assert_eq!(lines, [0, 1, 2]);
+        assert!(diags[0].message.starts_with("`/logo.gif` may be animated"));
+        assert_eq!(diags[0].severity, Some(DiagnosticSeverity::INFORMATION));
+    }
+
+    #[test]
+    fn test_small_or_still_images_pass() {
+        let diags = check_html(
+            r#"<main><p>Loading <img src="spinner.gif" alt="" width="16"></p>
+<header><img src="logo.png" alt="Acme"></header></main>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_reduced_motion_source_passes() {
+        let diags = check_html(
+            r#"<header><picture>
+  <source srcset="hero-still.png" media="(prefers-reduced-motion: reduce)">
+  <img src="hero.gif" alt="Our team at work">
+</picture></header>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx() {
+        let diags = check(
+            r#"const Hero = () => (
+  <div className="Hero">
+    <img src="/celebrate.gif" alt="" />
+    <picture>
+      <source srcSet="/still.png" media="(prefers-reduced-motion: reduce)" />
+      <img src="/celebrate.gif" alt="" />
+    </picture>
+  </div>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+    }
+}
@@ -14,6 +14,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.1.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/non-text-content.html",
     default_severity: Severity::Error,
+    axe_rules: &["area-alt"],
 };
 
 impl Rule for AreaAlt {
@@ -0,0 +1,211 @@
+use crate::analysis::{Analysis, Element};
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::quickfix::{self, Fix};
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaActivedescendant;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-activedescendant",
+    description: "aria-activedescendant must point at an element that exists, on an element that can take focus",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Error,
+    axe_rules: &["aria-valid-attr-value"],
+};
+
+impl Rule for AriaActivedescendant {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        let jsx = analysis.file_type().is_jsx_like();
+        let mut diagnostics = Vec::new();
+        for element in analysis.elements() {
+            let Some(attr) = element.attr("aria-activedescendant") else {
+                continue;
+            };
+            if !can_take_focus(element) {
+                let mut diagnostic = make_diagnostic(
+                    &attr.node,
+                    "aria-activedescendant on an element that can't take focus: the active item is only announced while this element has focus, so give it a tabindex",
+                );
+                if let Some(name) = element.name_node() {
+                    let end = node_to_range(&name).end;
+                    let (title, new_text) = if jsx {
+                        ("Add tabIndex={0}", " tabIndex={0}")
+                    } else {
+                        ("Add tabindex=\"0\"", " tabindex=\"0\"")
+                    };
+                    quickfix::attach(
+                        &mut diagnostic,
+                        Fix {
+                            title: title.to_string(),
+                            edits: vec![TextEdit {
+                                range: Range { start: end, end },
+                                new_text: new_text.to_string(),
+                            }],
+                            ..Default::default()
+                        },
+                    );
+                }
+                diagnostics.push(diagnostic);
+            }
+            // The active item may be rendered by a component or get its id
+            // at runtime.
+            if attr.bound || analysis.runtime_ids() {
+                continue;
+            }
+            // An empty value means no item is active.
+            let id = attr.value.as_deref().unwrap_or("").trim();
+            if !id.is_empty() && !analysis.ids().contains_key(id) {
+                diagnostics.push(make_diagnostic(
+                    &attr.node,
+                    &format!(
+                        "aria-activedescendant points at id \"{id}\", which no element has: give the active item that id"
+                    ),
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Natively focusable or given a `tabindex`, even `-1`, which still lets
+/// scripts focus it. A component or spread props may add one.
+fn can_take_focus(element: &Element) -> bool {
+    element.focusable || element.attr("tabindex").is_some() || element.component || element.spread
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaActivedescendant;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_valid_activedescendant_passes() {
+        let source = r#"<input role="combobox" aria-controls="opts" aria-activedescendant="opt-2">
+<ul role="listbox" id="opts">
+  <li role="option" id="opt-1">Apple</li>
+  <li role="option" id="opt-2">Banana</li>
+</ul>
+<ul role="listbox" tabindex="0" aria-activedescendant="">
+  <li role="option" id="x">X</li>
+</ul>
+<div role="tree" tabindex="-1" aria-activedescendant="x"></div>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_missing_target_fails() {
+        let source = r#"<ul role="listbox" tabindex="0" aria-activedescendant="opt-3">
+  <li role="option" id="opt-1">Apple</li>
+</ul>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("aria-activedescendant points at id \"opt-3\", which no element has")
+        );
+        assert!(quickfix::fixes(&diags[0]).is_empty());
+    }
+
+    #[test]
+    fn test_unfocusable_host_fails() {
+        let source = r#"<ul role="listbox" aria-activedescendant="opt-1">
+  <li role="option" id="opt-1">Apple</li>
+</ul>
+<button disabled aria-activedescendant="opt-1">Pick</button>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 2);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("aria-activedescendant on an element that can't take focus")
+        );
+        assert_eq!(diags[1].range.start.line, 3);
+
+        let fixes = quickfix::fixes(&diags[0]);
+        assert_eq!(fixes.len(), 1);
+        assert!(!fixes[0].safe);
+        let fixed = quickfix::apply(source, &fixes[0].edits).unwrap();
+        assert!(fixed.starts_with(r#"<ul tabindex="0" role="listbox""#));
+    }
+
+    #[test]
+    fn test_vue_bound_values() {
+        let source = r#"<template>
+  <div role="listbox" :tabindex="0" :aria-activedescendant="activeId"></div>
+  <div role="listbox" tabindex="0" aria-activedescendant="none"></div>
+</template>"#;
+        let diags = check(source, FileType::Vue);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert!(diags[0].message.contains("id \"none\""));
+    }
+
+    #[test]
+    fn test_jsx() {
+        let source = r#"const Combo = ({ active }) => (
+  <>
+    <div role="listbox" aria-activedescendant={active}>
+      <div role="option" id="a">A</div>
+    </div>
+    <Listbox aria-activedescendant="b" />
+  </>
+);"#;
+        let diags = check(source, FileType::Tsx);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+        let fixes = quickfix::fixes(&diags[0]);
+        let fixed = quickfix::apply(source, &fixes[0].edits).unwrap();
+        assert!(fixed.contains(r#"<div tabIndex={0} role="listbox""#));
+
+        let diags = check(
+            r#"const List = () => <ul role="listbox" tabIndex={0} aria-activedescendant="b" />;"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("id \"b\""));
+    }
+}
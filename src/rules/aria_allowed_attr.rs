@@ -16,6 +16,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "4.1.2",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
     default_severity: Severity::Error,
+    axe_rules: &["aria-allowed-attr"],
 };
 
 static GLOBAL_ARIA_ATTRS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
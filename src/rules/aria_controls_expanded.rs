@@ -0,0 +1,334 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaControlsExpanded;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-controls-expanded",
+    description: "Buttons and menu items with aria-controls should expose their state with aria-expanded",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Roles for which `aria-controls` almost always means "shows or hides that
+/// element", i.e. a disclosure or popup trigger.
+const DISCLOSURE_ROLES: &[&str] = &["button", "menuitem"];
+
+/// Attributes that tell assistive technology what activating the control
+/// does to the controlled element.
+const STATE_ATTRS: &[&str] = &["aria-expanded", "aria-haspopup"];
+
+impl Rule for AriaControlsExpanded {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Some buttons legitimately control an element without expanding it
+    /// (carousel "next slide", a player's play button), so this only runs
+    /// when enabled explicitly.
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, &mut diagnostics);
+        } else {
+            visit_html(root, source, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let attrs = html_attrs::attrs(&tag, source);
+        let explicit_role = attrs
+            .iter()
+            .find(|a| a.name_eq("role"))
+            .map(|a| if a.bound { None } else { a.value.clone() });
+        let tag_name = html_attrs::tag_name(&tag, source).unwrap_or_default();
+        let input_type = attrs
+            .iter()
+            .find(|a| a.name_eq("type"))
+            .and_then(|a| a.value.as_deref());
+        // A bound `:role` could be anything.
+        let role = match explicit_role {
+            Some(role) => role,
+            None => implicit_role(tag_name, input_type).map(str::to_string),
+        };
+        if let Some(role) = role.as_deref().and_then(disclosure_role)
+            && let Some(controls) = attrs.iter().find(|a| a.name_eq("aria-controls"))
+            && !attrs
+                .iter()
+                .any(|a| STATE_ATTRS.iter().any(|s| a.name_eq(s)))
+        {
+            diagnostics.push(make_diagnostic(&controls.node, role));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, diagnostics);
+    }
+}
+
+fn implicit_role(tag_name: &str, input_type: Option<&str>) -> Option<&'static str> {
+    match tag_name.to_ascii_lowercase().as_str() {
+        "button" => Some("button"),
+        "input"
+            if input_type.is_some_and(|t| {
+                ["button", "submit", "reset"].contains(&t.to_ascii_lowercase().as_str())
+            }) =>
+        {
+            Some("button")
+        }
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        check_jsx_tag(&tag, source, diagnostics);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, diagnostics);
+    }
+}
+
+fn check_jsx_tag(tag: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut tag_name = "";
+    let mut role: Option<Option<String>> = None;
+    let mut input_type = None;
+    let mut controls = None;
+    let mut has_state = false;
+    let mut cursor = tag.walk();
+    for child in tag.children(&mut cursor) {
+        match child.kind() {
+            "identifier" | "member_expression" if tag_name.is_empty() => {
+                tag_name = &source[child.byte_range()];
+            }
+            // `{...props}` may well carry aria-expanded.
+            "jsx_expression" => return,
+            "jsx_attribute" => {
+                let (name, value) = extract_jsx_attribute(&child, source);
+                match name.as_deref() {
+                    Some("role") => role = Some(value),
+                    Some("type") => input_type = value,
+                    Some("aria-controls") => controls = Some(child),
+                    Some(name) if STATE_ATTRS.contains(&name) => has_state = true,
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // `role={expr}` could be anything, and capitalized tags are components.
+    let role = match role {
+        Some(role) => role,
+        None if tag_name.starts_with(|c: char| c.is_ascii_lowercase()) => {
+            implicit_role(tag_name, input_type.as_deref()).map(str::to_string)
+        }
+        None => None,
+    };
+    if let Some(role) = role.as_deref().and_then(disclosure_role)
+        && let Some(controls) = controls
+        && !has_state
+    {
+        diagnostics.push(make_diagnostic(&controls, role));
+    }
+}
+
+/// `(name, string value)` of a JSX attribute; the value is `None` for
+/// expressions.
+fn extract_jsx_attribute(attr_node: &Node, source: &str) -> (Option<String>, Option<String>) {
+    let mut name = None;
+    let mut value = None;
+    let mut cursor = attr_node.walk();
+    for child in attr_node.children(&mut cursor) {
+        match child.kind() {
+            "property_identifier" => name = Some(source[child.byte_range()].to_string()),
+            "string" => {
+                let raw = &source[child.byte_range()];
+                value = Some(raw.trim_matches('"').trim_matches('\'').to_string());
+            }
+            _ => {}
+        }
+    }
+    (name, value)
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+/// The first role in a (possibly fallback) role list, if it's a disclosure
+/// role.
+fn disclosure_role(roles: &str) -> Option<&'static str> {
+    let first = roles.split_whitespace().next()?.to_ascii_lowercase();
+    DISCLOSURE_ROLES.iter().copied().find(|r| *r == first)
+}
+
+fn make_diagnostic(node: &Node, role: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{} has aria-controls but no aria-expanded or aria-haspopup; add the state, or drop aria-controls if it doesn't show or hide anything. {} [WCAG {} Level {:?}]",
+            role, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaControlsExpanded;
+        rule.check(&tree.root_node(), source, FileType::Html)
+    }
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaControlsExpanded;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    fn check_vue(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Vue).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaControlsExpanded;
+        rule.check(&tree.root_node(), source, FileType::Vue)
+    }
+
+    #[test]
+    fn test_is_opt_in() {
+        assert!(AriaControlsExpanded.opt_in());
+    }
+
+    #[test]
+    fn test_button_without_expanded_fails() {
+        let diags = check_html(r#"<button aria-controls="menu">Menu</button>"#);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 8);
+        assert!(diags[0].message.contains("button has aria-controls"));
+    }
+
+    #[test]
+    fn test_button_with_expanded_passes() {
+        let diags =
+            check_html(r#"<button aria-controls="menu" aria-expanded="false">Menu</button>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_haspopup_passes() {
+        let diags =
+            check_html(r#"<button aria-controls="menu" aria-haspopup="menu">Menu</button>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_role_button_and_menuitem_fail() {
+        let diags = check_html(
+            r#"<div role="button" aria-controls="a">A</div><li role="menuitem" aria-controls="b">B</li>"#,
+        );
+        assert_eq!(diags.len(), 2);
+    }
+
+    #[test]
+    fn test_input_button_fails() {
+        let diags = check_html(r#"<input type="button" value="More" aria-controls="more">"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_other_roles_ignored() {
+        let diags = check_html(
+            r#"<div role="tab" aria-controls="panel">Tab</div><input type="search" aria-controls="results">"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_bound_expanded_passes() {
+        let diags = check_vue(
+            r#"<template><button aria-controls="menu" :aria-expanded="open">Menu</button></template>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_bound_role_ignored() {
+        let diags =
+            check_vue(r#"<template><div :role="r" aria-controls="menu">Menu</div></template>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_button_without_expanded_fails() {
+        let diags = check_tsx(
+            r#"const B = () => <button aria-controls="menu" onClick={toggle}>Menu</button>;"#,
+        );
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_tsx_expression_expanded_passes() {
+        let diags = check_tsx(
+            r#"const B = () => <button aria-controls="menu" aria-expanded={open}>Menu</button>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_spread_passes() {
+        let diags =
+            check_tsx(r#"const B = () => <button aria-controls="menu" {...toggleProps} />;"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_component_ignored() {
+        let diags = check_tsx(r#"const B = () => <Button aria-controls="menu">Menu</Button>;"#);
+        assert_eq!(diags.len(), 0);
+    }
+}
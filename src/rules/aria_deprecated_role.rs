@@ -16,6 +16,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "4.1.2",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
     default_severity: Severity::Warning,
+    axe_rules: &["aria-deprecated-role"],
 };
 
 static DEPRECATED_ROLES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
@@ -0,0 +1,160 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaErrormessage;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-errormessage",
+    description: "aria-errormessage must come with aria-invalid and point at an element that exists",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "3.3.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/error-identification.html",
+    default_severity: Severity::Error,
+    axe_rules: &["aria-valid-attr-value"],
+};
+
+impl Rule for AriaErrormessage {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for element in analysis.elements() {
+            let Some(attr) = element.attr("aria-errormessage") else {
+                continue;
+            };
+            // Spread props may set `aria-invalid`.
+            if element.attr("aria-invalid").is_none() && !element.spread {
+                diagnostics.push(make_diagnostic(
+                    &attr.node,
+                    "aria-errormessage without aria-invalid: the message is only exposed while aria-invalid is \"true\", so set it from the field's validity",
+                ));
+            }
+            // The message may be rendered by a component or get its id at
+            // runtime.
+            if attr.bound || analysis.runtime_ids() {
+                continue;
+            }
+            let ids = attr.value.as_deref().unwrap_or("").split_whitespace();
+            for id in ids.filter(|id| !analysis.ids().contains_key(*id)) {
+                diagnostics.push(make_diagnostic(
+                    &attr.node,
+                    &format!(
+                        "aria-errormessage points at id \"{id}\", which no element has: give the error message that id"
+                    ),
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaErrormessage;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_valid_errormessage_passes() {
+        let source = r#"<label for="email">Email</label>
+<input id="email" aria-invalid="true" aria-errormessage="email-error">
+<p id="email-error">Enter an email address like name@example.com</p>
+<input aria-invalid="false" aria-errormessage="zip-error">
+<span id="zip-error" hidden>Enter a 5-digit ZIP code</span>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_missing_aria_invalid_fails() {
+        let source = r#"<input aria-errormessage="err">
+<p id="err">Required</p>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("aria-errormessage without aria-invalid")
+        );
+        assert_eq!(diags[0].range.start.line, 0);
+    }
+
+    #[test]
+    fn test_missing_message_element_fails() {
+        let source = r#"<input aria-invalid="true" aria-errormessage="name-error">
+<p id="name-eror">Enter your name</p>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("aria-errormessage points at id \"name-error\", which no element has")
+        );
+    }
+
+    #[test]
+    fn test_vue_bound_values() {
+        let source = r#"<template>
+  <input :aria-invalid="!valid" aria-errormessage="pw-error">
+  <p id="pw-error">Too short</p>
+  <input :aria-errormessage="errorId">
+  <input v-bind="field" aria-errormessage="pw-error">
+</template>"#;
+        let diags = check(source, FileType::Vue);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 3);
+    }
+
+    #[test]
+    fn test_jsx_runtime_ids_skip_lookup() {
+        let diags = check(
+            r#"const Field = ({ id, error }) => (
+  <>
+    <input aria-invalid={!!error} aria-errormessage={`${id}-error`} />
+    <input aria-invalid={!!error} aria-errormessage="missing" />
+    <p id={`${id}-error`}>{error}</p>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert!(diags.is_empty());
+    }
+}
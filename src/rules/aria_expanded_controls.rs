@@ -0,0 +1,258 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaExpandedControls;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-expanded-controls",
+    description: "Elements with aria-expanded should name what they expand with aria-controls",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Elements whose expanded state the browser ties to their content itself.
+const NATIVE_DISCLOSURES: &[&str] = &["summary", "details"];
+
+/// Roles whose `aria-expanded` is about the element itself (a tree item or
+/// treegrid row showing its children), or that follow the combobox pattern,
+/// where the popup is found through the input.
+const EXEMPT_ROLES: &[&str] = &["combobox", "treeitem", "row"];
+
+/// Attributes that point at the element being expanded.
+const LINK_ATTRS: &[&str] = &["aria-controls", "aria-owns"];
+
+impl Rule for AriaExpandedControls {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Many disclosures work fine with the panel right after the button, and
+    /// screen readers do little with `aria-controls`, so this only runs when
+    /// enabled explicitly.
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, &mut diagnostics);
+        } else {
+            visit_html(root, source, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source)
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let attrs = html_attrs::attrs(&tag, source);
+        let attr_values: Vec<(String, Option<String>)> = attrs
+            .iter()
+            .map(|a| {
+                // A bound value could be anything, including a real one.
+                let value = if a.bound {
+                    Some("{}".to_string())
+                } else {
+                    a.value.clone()
+                };
+                (a.name_lower(), value)
+            })
+            .collect();
+        if let Some(expanded) = attrs.iter().find(|a| a.name_eq("aria-expanded"))
+            && lacks_controls(&name, &attr_values)
+        {
+            diagnostics.push(make_diagnostic(&expanded.node));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        check_jsx_tag(&tag, source, diagnostics);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, diagnostics);
+    }
+}
+
+fn check_jsx_tag(tag: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let name = tag
+        .child_by_field_name("name")
+        .map_or("", |n| &source[n.byte_range()]);
+    // Components may wire up aria-controls themselves.
+    if !name.starts_with(|c: char| c.is_ascii_lowercase()) || name.contains('.') {
+        return;
+    }
+    let mut expanded = None;
+    let mut attrs = Vec::new();
+    let mut cursor = tag.walk();
+    for child in tag.named_children(&mut cursor) {
+        // `{...props}` may well carry aria-controls.
+        if child.kind() == "jsx_expression" {
+            return;
+        }
+        let Some((attr, value)) = jsx_attrs::jsx_attribute(&child, source) else {
+            continue;
+        };
+        if attr == "aria-expanded" {
+            expanded = Some(child);
+        }
+        let value = value.map(|v| match v.kind() {
+            "string" => source[v.byte_range()].trim_matches(['"', '\'']).to_string(),
+            _ => "{}".to_string(),
+        });
+        attrs.push((attr.to_ascii_lowercase(), value));
+    }
+    if let Some(expanded) = expanded
+        && lacks_controls(name, &attrs)
+    {
+        diagnostics.push(make_diagnostic(&expanded));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+/// Whether an element `name` with `aria-expanded` among `attrs` should have
+/// `aria-controls` but has no (non-empty) one, nor `aria-owns`.
+fn lacks_controls(name: &str, attrs: &[(String, Option<String>)]) -> bool {
+    let value = |attr: &str| {
+        attrs
+            .iter()
+            .find(|(n, _)| n == attr)
+            .and_then(|(_, v)| v.as_deref())
+            .map(str::trim)
+    };
+    if NATIVE_DISCLOSURES.contains(&name) {
+        return false;
+    }
+    let role = value("role")
+        .and_then(|roles| roles.split_whitespace().next())
+        .map(str::to_ascii_lowercase);
+    if role.is_some_and(|role| EXEMPT_ROLES.contains(&role.as_str())) {
+        return false;
+    }
+    // An `<input list>` or an autocompleting input is a combobox too.
+    let combobox_input = name == "input" && value("list").is_some();
+    if combobox_input || value("aria-autocomplete").is_some() {
+        return false;
+    }
+    !LINK_ATTRS
+        .iter()
+        .any(|attr| value(attr).is_some_and(|v| !v.is_empty()))
+}
+
+fn make_diagnostic(node: &Node) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "aria-expanded without aria-controls: add aria-controls with the id of the element that is shown and hidden. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaExpandedControls;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_is_opt_in() {
+        assert!(AriaExpandedControls.opt_in());
+    }
+
+    #[test]
+    fn test_expanded_without_controls_fails() {
+        let diags = check_html(
+            r#"<button aria-expanded="false">Menu</button>
+<div role="button" aria-expanded="true" aria-controls="">Filters</div>"#,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.character, 8);
+        assert!(diags[0].message.contains("aria-controls"));
+    }
+
+    #[test]
+    fn test_linked_and_exempt_pass() {
+        let source = r#"<button aria-expanded="false" aria-controls="menu">Menu</button>
+<button aria-expanded="false" :aria-controls="panelId">More</button>
+<details><summary aria-expanded="false">More</summary></details>
+<input role="combobox" aria-expanded="false">
+<input list="cities" aria-expanded="false">
+<li role="treeitem" aria-expanded="true">Folder</li>
+<div role="combobox" aria-expanded="true" aria-owns="listbox">x</div>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const D = () => (
+  <>
+    <button aria-expanded={open} onClick={toggle}>More</button>
+    <button aria-expanded={open} aria-controls={panelId}>More</button>
+    <button aria-expanded={open} {...toggleProps}>More</button>
+    <Disclosure.Button aria-expanded={open}>More</Disclosure.Button>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+    }
+}
@@ -14,6 +14,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "4.1.2",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
     default_severity: Severity::Error,
+    axe_rules: &["aria-hidden-focus"],
 };
 
 /// Natively focusable HTML tags (some require additional conditions).
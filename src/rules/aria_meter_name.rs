@@ -0,0 +1,121 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::aria_name;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaMeterName;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-meter-name",
+    description: "Elements with role=\"meter\" must have an accessible name",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/non-text-content.html",
+    default_severity: Severity::Error,
+    axe_rules: &["aria-meter-name"],
+};
+
+/// `progress-meter-name` checks the native elements.
+const NATIVE: &[&str] = &["meter", "progress"];
+
+impl Rule for AriaMeterName {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        // A meter isn't named by its content, which is usually its value.
+        aria_name::unnamed(analysis, &["meter"], false, NATIVE)
+            .into_iter()
+            .map(|(element, _)| {
+                make_diagnostic(
+                    &element.tag,
+                    "role=\"meter\" without a name: add aria-label, or aria-labelledby pointing at its visible label, so the value has something to be the value of",
+                )
+            })
+            .collect()
+    }
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaMeterName;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_named_meters_pass() {
+        let source = r#"<div role="meter" aria-valuenow="3" aria-label="Disk usage"></div>
+<span id="pw">Password strength</span><div role="meter" aria-labelledby="pw"></div>
+<div role="meter" title="Battery"></div>
+<meter value="3" max="5"></meter>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_unnamed_meters_fail() {
+        let source = r#"<div role="meter" aria-valuenow="60">60%</div>
+<div role="meter" aria-label=""></div>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 2);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("role=\"meter\" without a name")
+        );
+        assert_eq!(diags[1].range.start.line, 1);
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Usage = () => (
+  <>
+    <div role="meter" aria-label={label} />
+    <div role="meter" {...props} />
+    <Meter role="meter" />
+    <div role="meter" aria-valuenow={used}>{used}%</div>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 5);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::analysis::{Analysis, Element};
+use crate::config::Config;
+use crate::engine::{node_to_range, related_location};
+use crate::parser::FileType;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaModalSiblings;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-modal-siblings",
+    description: "Content beside a modal dialog should be inert or aria-hidden while it's open",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.4.3",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/focus-order.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+const DIALOG_ROLES: &[&str] = &["alertdialog", "dialog"];
+
+impl Rule for AriaModalSiblings {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Whether the siblings are made inert when the dialog opens depends on
+    /// the script; this points out the ones to check.
+    fn informational(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        let elements = analysis.elements();
+        let mut diagnostics = Vec::new();
+        for (d, dialog) in elements.iter().enumerate() {
+            if !is_modal(dialog) {
+                continue;
+            }
+            let related: Vec<DiagnosticRelatedInformation> = elements
+                .iter()
+                .enumerate()
+                .filter(|&(s, sibling)| {
+                    s != d && sibling.parent == dialog.parent && reachable(analysis, s)
+                })
+                .map(|(_, sibling)| {
+                    related_location(&sibling.tag, "Focusable and not inert beside the dialog")
+                })
+                .collect();
+            if related.is_empty() {
+                continue;
+            }
+            let count = match related.len() {
+                1 => "An element".to_string(),
+                n => format!("{n} elements"),
+            };
+            diagnostics.push(make_diagnostic(
+                &dialog.tag,
+                &format!(
+                    "{count} beside this modal dialog with focusable content stay reachable: make them inert while the dialog is open, or move the dialog out of their container"
+                ),
+                related,
+            ));
+        }
+        diagnostics
+    }
+}
+
+/// A `role="dialog"` or `"alertdialog"` with `aria-modal="true"`. A native
+/// `<dialog>` opened with `showModal()` makes the rest inert by itself.
+fn is_modal(element: &Element) -> bool {
+    let Some(Some(role)) = &element.role else {
+        return false;
+    };
+    DIALOG_ROLES.contains(&role.as_str())
+        && element
+            .static_value("aria-modal")
+            .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+/// Whether the element, or something in it, is in the tab order without
+/// anything on the way taking it out. A bound `inert` or `aria-hidden`, or
+/// spread props that may set them, are taken to do so while the dialog is
+/// open.
+fn reachable(analysis: &Analysis, s: usize) -> bool {
+    let elements = analysis.elements();
+    analysis.focusable().any(|f| {
+        let mut current = Some(f);
+        while let Some(i) = current {
+            if hides(&elements[i]) {
+                return false;
+            }
+            if i == s {
+                return true;
+            }
+            current = elements[i].parent;
+        }
+        false
+    })
+}
+
+fn hides(element: &Element) -> bool {
+    element.spread
+        || element.attr("inert").is_some()
+        || element.attr("aria-hidden").is_some_and(|a| {
+            a.bound
+                || a.value
+                    .as_deref()
+                    .is_some_and(|v| v.trim().eq_ignore_ascii_case("true"))
+        })
+}
+
+fn make_diagnostic(
+    node: &Node,
+    problem: &str,
+    related: Vec<DiagnosticRelatedInformation>,
+) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::INFORMATION),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        related_information: Some(related),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaModalSiblings;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_inert_siblings_pass() {
+        let source = r#"<body>
+  <div inert>
+    <header><a href="/">Home</a></header>
+    <main><button>Buy</button></main>
+  </div>
+  <footer aria-hidden="true"><a href="/about" tabindex="-1">About</a></footer>
+  <p>No controls here</p>
+  <div role="dialog" aria-modal="true" aria-label="Confirm">
+    <button>OK</button>
+  </div>
+</body>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_reachable_siblings_fail() {
+        let source = r#"<body>
+  <header><nav><a href="/">Home</a></nav></header>
+  <main><p>Text</p><button>Buy</button></main>
+  <p>No controls here</p>
+  <div role="alertdialog" aria-modal="true" aria-label="Confirm">
+    <button>OK</button>
+  </div>
+</body>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].severity, Some(DiagnosticSeverity::INFORMATION));
+        assert!(diags[0].message.starts_with(
+            "2 elements beside this modal dialog with focusable content stay reachable"
+        ));
+        assert_eq!(diags[0].range.start.line, 4);
+        let related = diags[0].related_information.as_ref().unwrap();
+        assert_eq!(related.len(), 2);
+        assert_eq!(related[0].location.range.start.line, 1);
+        assert_eq!(related[1].location.range.start.line, 2);
+    }
+
+    #[test]
+    fn test_only_direct_siblings_checked() {
+        // The dialog's own container has nothing else in it; the rest of
+        // the page is beyond what this heuristic looks at.
+        let source = r#"<body>
+  <header><nav><a href="/">Home</a></nav></header>
+  <main><button>Buy</button></main>
+  <div class="backdrop">
+    <div role="dialog" aria-modal="true" aria-label="Confirm">
+      <button>OK</button>
+    </div>
+  </div>
+</body>"#;
+        assert!(check_html(source).is_empty());
+
+        let source = r#"<div class="backdrop">
+  <a href="/cart">Cart</a>
+  <div role="dialog" aria-modal="true" aria-label="Confirm"><button>OK</button></div>
+</div>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("An element beside this modal dialog")
+        );
+    }
+
+    #[test]
+    fn test_non_modal_dialogs_skipped() {
+        let source = r#"<button>Open</button>
+<div role="dialog" aria-label="Chat"><input></div>
+<div role="dialog" aria-modal="false" aria-label="Help"><input></div>
+<dialog open><button>Close</button></dialog>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Page = ({ open }) => (
+  <>
+    <main inert={open}><button>Buy</button></main>
+    <aside {...asideProps}><a href="/help">Help</a></aside>
+    <nav aria-hidden={open}><a href="/">Home</a></nav>
+    <footer><a href="/about">About</a></footer>
+    {open && (
+      <div role="dialog" aria-modal="true" aria-label="Confirm">
+        <button>OK</button>
+      </div>
+    )}
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        let related = diags[0].related_information.as_ref().unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].location.range.start.line, 5);
+    }
+}
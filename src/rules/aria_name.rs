@@ -0,0 +1,122 @@
+//! Accessible names of elements with an ARIA role, for the rules that require
+//! one.
+
+use crate::analysis::{Analysis, Element};
+use crate::rules::html_attrs;
+use tree_sitter::Node;
+
+/// Attributes that name an element on their own.
+const NAME_ATTRS: &[&str] = &["aria-label", "aria-labelledby", "title"];
+
+/// The elements with a static role among `roles` that have no name, with
+/// that role. Unless `from_content`, text inside doesn't name them.
+///
+/// Components and elements with spread props may get a name from elsewhere,
+/// and the elements in `native` are named the way their element is, which
+/// other rules check; all of these are skipped.
+pub fn unnamed<'b, 'a>(
+    analysis: &'b Analysis<'a>,
+    roles: &[&str],
+    from_content: bool,
+    native: &[&str],
+) -> Vec<(&'b Element<'a>, &'b str)> {
+    analysis
+        .elements()
+        .iter()
+        .filter_map(|element| {
+            let Some(Some(role)) = &element.role else {
+                return None;
+            };
+            let skipped = !roles.contains(&role.as_str())
+                || element.component
+                || element.spread
+                || native.contains(&element.name.as_str());
+            let named =
+                has_name_attr(element) || (from_content && has_content(element, analysis.source()));
+            (!skipped && !named).then_some((element, role.as_str()))
+        })
+        .collect()
+}
+
+/// A bound name attribute names it at runtime.
+fn has_name_attr(element: &Element) -> bool {
+    element.attrs.iter().any(|a| {
+        NAME_ATTRS.iter().any(|n| a.name_eq(n))
+            && (a.bound || a.value.as_deref().is_some_and(|v| !v.trim().is_empty()))
+    })
+}
+
+/// Whether the element has text, child elements or expressions to take its
+/// name from.
+fn has_content(element: &Element, source: &str) -> bool {
+    if html_attrs::has_dynamic_content(&element.attrs) {
+        return true;
+    }
+    let node = element.node();
+    let mut cursor = node.walk();
+    node.children(&mut cursor).any(|child| match child.kind() {
+        "text" | "jsx_text" => !source[child.byte_range()].trim().is_empty(),
+        "element" | "jsx_element" | "jsx_self_closing_element" | "jsx_expression" => true,
+        _ => false,
+    })
+}
+
+/// The text content of `node`, whitespace collapsed and character
+/// references as written, with the `alt` of its images and without hidden
+/// elements. Content only known at runtime, a JSX expression or bound `alt`,
+/// shows as `{expression}`.
+pub fn text(analysis: &Analysis, node: Node) -> String {
+    let mut parts = Vec::new();
+    collect_text(analysis, node, &mut parts);
+    parts
+        .iter()
+        .flat_map(|part| part.split_whitespace())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn collect_text(analysis: &Analysis, node: Node, parts: &mut Vec<String>) {
+    let source = analysis.source();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "text" | "jsx_text" | "entity" => parts.push(source[child.byte_range()].to_string()),
+            "jsx_expression" => parts.push(source[child.byte_range()].to_string()),
+            "start_tag"
+            | "end_tag"
+            | "self_closing_tag"
+            | "jsx_opening_element"
+            | "jsx_closing_element"
+            | "script_element"
+            | "style_element"
+            | "comment" => {}
+            "element" | "jsx_element" | "jsx_self_closing_element" => {
+                if let Some(j) = analysis.element_at(&child) {
+                    let element = &analysis.elements()[j];
+                    if hidden(element) {
+                        continue;
+                    }
+                    if element.name == "img" {
+                        parts.extend(element.attr("alt").and_then(|alt| {
+                            let value = alt.value.as_deref()?.trim();
+                            Some(match alt.bound {
+                                true => format!("{{{value}}}"),
+                                false => value.to_string(),
+                            })
+                        }));
+                        continue;
+                    }
+                }
+                collect_text(analysis, child, parts);
+            }
+            _ => collect_text(analysis, child, parts),
+        }
+    }
+}
+
+fn hidden(element: &Element) -> bool {
+    element
+        .static_value("aria-hidden")
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+        || element.attr("hidden").is_some_and(|a| !a.bound)
+}
@@ -0,0 +1,354 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaOwnsValid;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-owns-valid",
+    description: "aria-owns must not create ownership cycles or give an element more than one owner",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.3.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
+    default_severity: Severity::Error,
+    axe_rules: &["aria-valid-attr-value"],
+};
+
+/// An element in document order, as far as ownership is concerned.
+struct Element<'a> {
+    /// Index of the nearest enclosing element.
+    parent: Option<usize>,
+    /// Static `id`.
+    id: Option<String>,
+    /// The static `aria-owns` attribute and the ids it lists.
+    owns: Option<(Node<'a>, Vec<String>)>,
+}
+
+impl Rule for AriaOwnsValid {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut elements = Vec::new();
+        if file_type.is_jsx_like() {
+            collect_jsx(root, source, None, &mut elements);
+        } else {
+            collect_html(root, source, None, &mut elements);
+        }
+        check_ownership(&elements)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn collect_html<'a>(
+    node: &Node<'a>,
+    source: &str,
+    parent: Option<usize>,
+    elements: &mut Vec<Element<'a>>,
+) {
+    let mut parent = parent;
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        // Bound `:id`/`:aria-owns` are runtime values and can't be resolved.
+        let attrs = html_attrs::attrs(&tag, source);
+        let static_value = |name: &str| {
+            attrs
+                .iter()
+                .find(|a| a.name_eq(name) && !a.bound)
+                .and_then(|a| a.value.clone().map(|v| (a.node, v)))
+        };
+        elements.push(Element {
+            parent,
+            id: static_value("id").map(|(_, v)| v.trim().to_string()),
+            owns: static_value("aria-owns").map(|(node, v)| (node, split_ids(&v))),
+        });
+        parent = Some(elements.len() - 1);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_html(&child, source, parent, elements);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn collect_jsx<'a>(
+    node: &Node<'a>,
+    source: &str,
+    parent: Option<usize>,
+    elements: &mut Vec<Element<'a>>,
+) {
+    let mut parent = parent;
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let mut id = None;
+        let mut owns = None;
+        let mut cursor = tag.walk();
+        for attr in tag.children(&mut cursor) {
+            if attr.kind() != "jsx_attribute" {
+                continue;
+            }
+            match jsx_attr_string(&attr, source) {
+                Some((name, value)) if name == "id" => id = Some(value.trim().to_string()),
+                Some((name, value)) if name == "aria-owns" => {
+                    owns = Some((attr, split_ids(&value)));
+                }
+                _ => {}
+            }
+        }
+        elements.push(Element { parent, id, owns });
+        parent = Some(elements.len() - 1);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_jsx(&child, source, parent, elements);
+    }
+}
+
+/// `(name, value)` of a JSX attribute with a string literal value.
+fn jsx_attr_string(attr: &Node, source: &str) -> Option<(String, String)> {
+    let mut name = None;
+    let mut value = None;
+    let mut cursor = attr.walk();
+    for child in attr.children(&mut cursor) {
+        match child.kind() {
+            "property_identifier" => name = Some(source[child.byte_range()].to_string()),
+            "string" => {
+                let raw = &source[child.byte_range()];
+                value = Some(raw.trim_matches('"').trim_matches('\'').to_string());
+            }
+            _ => {}
+        }
+    }
+    Some((name?, value?))
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn split_ids(value: &str) -> Vec<String> {
+    value.split_whitespace().map(str::to_string).collect()
+}
+
+/// Builds the ownership graph and reports self-ownership, elements claimed
+/// by a second owner, and owners that end up owning one of their own
+/// ancestors in the accessibility tree.
+fn check_ownership(elements: &[Element]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    // The first element with an id wins, as in the browser.
+    let mut by_id: HashMap<&str, usize> = HashMap::new();
+    for (i, element) in elements.iter().enumerate() {
+        if let Some(id) = element.id.as_deref().filter(|id| !id.is_empty()) {
+            by_id.entry(id).or_insert(i);
+        }
+    }
+
+    // Owned element → (owner, the id it was referenced by).
+    let mut owner_of: HashMap<usize, usize> = HashMap::new();
+    let mut edges: Vec<(usize, usize, &str)> = Vec::new();
+    for (owner, element) in elements.iter().enumerate() {
+        let Some((attr, ids)) = &element.owns else {
+            continue;
+        };
+        for id in ids {
+            // Dangling references are left to other rules.
+            let Some(&owned) = by_id.get(id.as_str()) else {
+                continue;
+            };
+            if owned == owner {
+                diagnostics.push(make_diagnostic(
+                    attr,
+                    &format!("aria-owns references the element's own id \"{id}\""),
+                ));
+            } else if let Entry::Vacant(entry) = owner_of.entry(owned) {
+                entry.insert(owner);
+                edges.push((owner, owned, id));
+            } else {
+                diagnostics.push(make_diagnostic(
+                    attr,
+                    &format!(
+                        "\"{id}\" is already owned by an earlier aria-owns; an element can only have one owner"
+                    ),
+                ));
+            }
+        }
+    }
+
+    // In the accessibility tree an owned element's parent is its owner. A
+    // cycle is reported once, on the first owner found in it.
+    let parent = |i: usize| owner_of.get(&i).copied().or(elements[i].parent);
+    let mut in_cycle: HashSet<usize> = HashSet::new();
+    for (owner, owned, id) in edges {
+        if in_cycle.contains(&owner) {
+            continue;
+        }
+        let mut path = Vec::new();
+        let mut current = Some(owner);
+        while let Some(i) = current
+            && path.len() <= elements.len()
+        {
+            path.push(i);
+            if i == owned {
+                in_cycle.extend(&path);
+                let (attr, _) = elements[owner].owns.as_ref().expect("owner has aria-owns");
+                diagnostics.push(make_diagnostic(
+                    attr,
+                    &format!(
+                        "aria-owns=\"{id}\" creates a cycle: \"{id}\" already contains this element"
+                    ),
+                ));
+                break;
+            }
+            current = parent(i);
+        }
+    }
+
+    diagnostics
+}
+
+fn make_diagnostic(node: &Node, detail: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{}. {} [WCAG {} Level {:?}]",
+            detail, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaOwnsValid;
+        rule.check(&tree.root_node(), source, FileType::Html)
+    }
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaOwnsValid;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    fn check_vue(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Vue).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaOwnsValid;
+        rule.check(&tree.root_node(), source, FileType::Vue)
+    }
+
+    #[test]
+    fn test_valid_ownership_passes() {
+        let diags = check_html(
+            r#"<div role="listbox" aria-owns="a b"></div><div id="a" role="option">A</div><div id="b" role="option">B</div>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_self_ownership_fails() {
+        let diags = check_html(r#"<div id="x" aria-owns="x"></div>"#);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("own id \"x\""));
+    }
+
+    #[test]
+    fn test_duplicate_owner_fails() {
+        let diags = check_html(
+            r#"<ul aria-owns="item"></ul><ul aria-owns="item"></ul><li id="item">A</li>"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("already owned"));
+        assert_eq!(diags[0].range.start.character, 30);
+    }
+
+    #[test]
+    fn test_owning_an_ancestor_fails() {
+        let diags = check_html(r#"<div id="outer"><span aria-owns="outer"></span></div>"#);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("creates a cycle"));
+    }
+
+    #[test]
+    fn test_mutual_ownership_fails() {
+        let diags =
+            check_html(r#"<div id="a" aria-owns="b"></div><div id="b" aria-owns="a"></div>"#);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("aria-owns=\"b\""));
+    }
+
+    #[test]
+    fn test_cycle_through_descendant_fails() {
+        // a owns b; b contains c; c owns a.
+        let diags = check_html(
+            r#"<div id="a" aria-owns="b"></div><div id="b"><p id="c" aria-owns="a"></p></div>"#,
+        );
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_owning_own_descendant_passes() {
+        let diags = check_html(r#"<div aria-owns="inner"><p><span id="inner"></span></p></div>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_missing_id_ignored() {
+        let diags = check_html(r#"<div aria-owns="nowhere"></div>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_cycle_fails() {
+        let diags = check_tsx(
+            r#"const A = () => <div id="menu"><div role="menuitem" aria-owns="menu" /></div>;"#,
+        );
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_tsx_dynamic_value_ignored() {
+        let diags = check_tsx(r#"const A = () => <div id="x" aria-owns={ownedId} />;"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_bound_owns_ignored() {
+        let diags = check_vue(r#"<template><div id="x" :aria-owns="'x'"></div></template>"#);
+        assert_eq!(diags.len(), 0);
+    }
+}
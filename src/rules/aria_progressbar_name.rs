@@ -0,0 +1,121 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::aria_name;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaProgressbarName;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-progressbar-name",
+    description: "Elements with role=\"progressbar\" must have an accessible name",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/non-text-content.html",
+    default_severity: Severity::Error,
+    axe_rules: &["aria-progressbar-name"],
+};
+
+/// `progress-meter-name` checks the native elements.
+const NATIVE: &[&str] = &["meter", "progress"];
+
+impl Rule for AriaProgressbarName {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        // A progress bar isn't named by its content, which is usually its value.
+        aria_name::unnamed(analysis, &["progressbar"], false, NATIVE)
+            .into_iter()
+            .map(|(element, _)| {
+                make_diagnostic(
+                    &element.tag,
+                    "role=\"progressbar\" without a name: add aria-label, or aria-labelledby pointing at its visible label, so users know what is progressing",
+                )
+            })
+            .collect()
+    }
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaProgressbarName;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_named_progressbars_pass() {
+        let source = r#"<div role="progressbar" aria-valuenow="3" aria-label="Upload"></div>
+<span id="up">Uploading photo.jpg</span><div role="progressbar" aria-labelledby="up"></div>
+<div role="progressbar" title="Installation"></div>
+<progress value="3" max="5"></progress>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_unnamed_progressbars_fail() {
+        let source = r#"<div role="progressbar" aria-valuenow="60">60%</div>
+<div role="progressbar" aria-label=""></div>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 2);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("role=\"progressbar\" without a name")
+        );
+        assert_eq!(diags[1].range.start.line, 1);
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Upload = () => (
+  <>
+    <div role="progressbar" aria-label={label} />
+    <div role="progressbar" {...props} />
+    <ProgressBar role="progressbar" />
+    <div role="progressbar" aria-valuenow={pct}>{pct}%</div>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 5);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::engine::node_to_range;
 use crate::parser::FileType;
+use crate::quickfix::{self, Fix};
 use crate::rules::html_attrs;
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
 use std::collections::HashSet;
@@ -16,6 +17,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "4.1.2",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
     default_severity: Severity::Error,
+    axe_rules: &["aria-valid-attr"],
 };
 
 static VALID_ARIA_ATTRS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
@@ -121,7 +123,7 @@ fn check_html_attribute(node: &Node, source: &str, diagnostics: &mut Vec<Diagnos
     if name.starts_with("aria-") && !VALID_ARIA_ATTRS.contains(name.as_str()) {
         // Report against the attribute_name node when available for a tight range.
         let name_node = attribute_name_node(node).unwrap_or(*node);
-        diagnostics.push(make_diagnostic(&name_node, &name));
+        diagnostics.push(make_diagnostic(&name_node, &name, source));
     }
 }
 
@@ -154,7 +156,7 @@ fn check_jsx_attribute(node: &Node, source: &str, diagnostics: &mut Vec<Diagnost
         if child.kind() == "property_identifier" {
             let name = &source[child.byte_range()];
             if name.starts_with("aria-") && !VALID_ARIA_ATTRS.contains(name) {
-                diagnostics.push(make_diagnostic(&child, name));
+                diagnostics.push(make_diagnostic(&child, name, source));
             }
         }
     }
@@ -164,9 +166,55 @@ fn check_jsx_attribute(node: &Node, source: &str, diagnostics: &mut Vec<Diagnost
 // Shared
 // ---------------------------------------------------------------------------
 
-fn make_diagnostic(node: &Node, invalid_attr: &str) -> Diagnostic {
+/// Attributes at most this many edits away are suggested for a typo.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// The valid attribute closest to `invalid_attr`, if it's close enough to be
+/// what was meant.
+fn suggestion(invalid_attr: &str) -> Option<&'static str> {
+    let invalid_attr = invalid_attr.to_ascii_lowercase();
+    VALID_ARIA_ATTRS
+        .iter()
+        .map(|&valid| (edit_distance(&invalid_attr, valid), valid))
+        .filter(|&(distance, _)| distance <= MAX_SUGGESTION_DISTANCE)
+        .min()
+        .map(|(_, valid)| valid)
+}
+
+/// The number of insertions, deletions, substitutions and swaps of adjacent
+/// characters that turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    // Rows i - 2, i - 1 and i of the distance table.
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// `node` is the attribute's name as written, which for a bound Vue or
+/// Angular attribute also holds its prefix; the fix only renames the
+/// `aria-*` part.
+fn make_diagnostic(node: &Node, invalid_attr: &str, source: &str) -> Diagnostic {
     let meta = &METADATA;
-    Diagnostic {
+    let suggested = suggestion(invalid_attr);
+    let hint = suggested.map_or(String::new(), |s| format!(" (did you mean '{s}'?)"));
+    let mut diagnostic = Diagnostic {
         range: node_to_range(node),
         severity: Some(DiagnosticSeverity::ERROR),
         code: Some(NumberOrString::String(meta.id.to_string())),
@@ -175,11 +223,31 @@ fn make_diagnostic(node: &Node, invalid_attr: &str) -> Diagnostic {
         }),
         source: Some("wcag-lsp".to_string()),
         message: format!(
-            "Invalid ARIA attribute '{}'. {} [WCAG {} Level {:?}]",
-            invalid_attr, meta.description, meta.wcag_criterion, meta.wcag_level
+            "Invalid ARIA attribute '{}'{}. {} [WCAG {} Level {:?}]",
+            invalid_attr, hint, meta.description, meta.wcag_criterion, meta.wcag_level
         ),
         ..Default::default()
+    };
+    let written = &source[node.byte_range()];
+    if let Some(suggested) = suggested
+        && let Some(offset) = written.to_ascii_lowercase().find(invalid_attr)
+    {
+        let start = node.start_position();
+        let column = (start.column + offset) as u32;
+        let range = Range::new(
+            Position::new(start.row as u32, column),
+            Position::new(start.row as u32, column + invalid_attr.len() as u32),
+        );
+        quickfix::attach(
+            &mut diagnostic,
+            Fix {
+                title: format!("Change to '{suggested}'"),
+                edits: vec![TextEdit::new(range, suggested.to_string())],
+                ..Default::default()
+            },
+        );
     }
+    diagnostic
 }
 
 #[cfg(test)]
@@ -255,6 +323,62 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    #[test]
+    fn test_typos_suggest_the_valid_attribute() {
+        let source = r#"<div aria-lable="Close" aria-discribedby="hint" aria-foo="bar"></div>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 3);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("Invalid ARIA attribute 'aria-lable' (did you mean 'aria-label'?).")
+        );
+        assert!(
+            diags[1]
+                .message
+                .contains("(did you mean 'aria-describedby'?)")
+        );
+        assert!(!diags[2].message.contains("did you mean"));
+
+        let fixes = quickfix::fixes(&diags[0]);
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].title, "Change to 'aria-label'");
+        assert!(!fixes[0].safe);
+        let fixed = quickfix::apply(source, &fixes[0].edits).unwrap();
+        assert!(fixed.starts_with(r#"<div aria-label="Close""#));
+        assert!(quickfix::fixes(&diags[2]).is_empty());
+    }
+
+    #[test]
+    fn test_typo_fix_keeps_vue_binding() {
+        let source = r#"<template><div :aria-expaned="open"></div></template>"#;
+        let diags = check_vue(source);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("(did you mean 'aria-expanded'?)"));
+        let fixes = quickfix::fixes(&diags[0]);
+        let fixed = quickfix::apply(source, &fixes[0].edits).unwrap();
+        assert!(fixed.contains(r#":aria-expanded="open""#));
+    }
+
+    #[test]
+    fn test_tsx_typo_suggestion() {
+        let source = r#"const App = () => <div aria-hiden="true" />;"#;
+        let diags = check_tsx(source);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("(did you mean 'aria-hidden'?)"));
+        let fixes = quickfix::fixes(&diags[0]);
+        let fixed = quickfix::apply(source, &fixes[0].edits).unwrap();
+        assert!(fixed.contains(r#"<div aria-hidden="true" />"#));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("aria-label", "aria-label"), 0);
+        assert_eq!(edit_distance("aria-lable", "aria-label"), 1);
+        assert_eq!(edit_distance("aria-labe", "aria-label"), 1);
+        assert_eq!(edit_distance("aria-foo", "aria-flowto"), 3);
+    }
+
     #[test]
     fn test_tsx_invalid_aria_attr_fails() {
         let diags = check_tsx(r#"const App = () => <div aria-foo="bar" />;"#);
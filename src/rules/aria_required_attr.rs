@@ -1,6 +1,8 @@
 use crate::engine::node_to_range;
 use crate::parser::FileType;
 use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::react_native;
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
 use std::collections::HashMap;
 use std::sync::LazyLock;
@@ -16,6 +18,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "4.1.2",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
     default_severity: Severity::Error,
+    axe_rules: &["aria-required-attr"],
 };
 
 static REQUIRED_ATTRS_BY_ROLE: LazyLock<HashMap<&'static str, Vec<&'static str>>> =
@@ -24,6 +27,8 @@ static REQUIRED_ATTRS_BY_ROLE: LazyLock<HashMap<&'static str, Vec<&'static str>>
         map.insert("checkbox", vec!["aria-checked"]);
         map.insert("combobox", vec!["aria-expanded"]);
         map.insert("heading", vec!["aria-level"]);
+        map.insert("menuitemcheckbox", vec!["aria-checked"]);
+        map.insert("menuitemradio", vec!["aria-checked"]);
         map.insert("meter", vec!["aria-valuenow"]);
         map.insert("option", vec!["aria-selected"]);
         map.insert("radio", vec!["aria-checked"]);
@@ -35,6 +40,30 @@ static REQUIRED_ATTRS_BY_ROLE: LazyLock<HashMap<&'static str, Vec<&'static str>>
         map
     });
 
+/// ARIA attributes whose value a native element already exposes, so an
+/// explicit role on it doesn't need them (e.g. `<input type="checkbox"
+/// role="switch">` gets its checked state from the `checked` property).
+fn native_provided_attrs(tag: &str, input_type: Option<&str>) -> &'static [&'static str] {
+    match tag.to_ascii_lowercase().as_str() {
+        "input" => match input_type.map(|t| t.to_ascii_lowercase()).as_deref() {
+            Some("checkbox") | Some("radio") => &["aria-checked"],
+            Some("range") | Some("number") => &["aria-valuenow"],
+            _ => &[],
+        },
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => &["aria-level"],
+        "option" => &["aria-selected"],
+        "meter" | "progress" => &["aria-valuenow"],
+        "select" => &["aria-expanded"],
+        _ => &[],
+    }
+}
+
+/// The element an ARIA role is declared on, as far as this rule cares.
+struct Host<'a> {
+    tag: Option<&'a str>,
+    input_type: Option<String>,
+}
+
 impl Rule for AriaRequiredAttr {
     fn metadata(&self) -> &RuleMetadata {
         &METADATA
@@ -87,7 +116,15 @@ fn check_html_element(element: &Node, source: &str, diagnostics: &mut Vec<Diagno
     // A bound `:aria-x` still counts as the attribute being present.
     let present_attrs: Vec<String> = attrs.iter().map(|a| a.name_lower()).collect();
 
-    check_required_attrs(&role_value, &present_attrs, element, diagnostics);
+    let host = Host {
+        tag: html_attrs::element_tag_name(element, source),
+        input_type: attrs
+            .iter()
+            .find(|a| a.name_eq("type") && !a.bound)
+            .and_then(|a| a.value.clone()),
+    };
+
+    check_required_attrs(&role_value, &host, &present_attrs, element, diagnostics);
 }
 
 // ---------------------------------------------------------------------------
@@ -112,56 +149,67 @@ fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
 }
 
 fn check_jsx_self_closing(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
-    let mut role_value: Option<String> = None;
-    let mut present_attrs: Vec<String> = Vec::new();
+    check_jsx_tag(node, node, source, diagnostics);
+}
 
+fn check_jsx_element(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        if child.kind() == "jsx_attribute" {
-            let (attr_name, attr_value) = extract_jsx_attribute(&child, source);
-            if let Some(ref name) = attr_name {
-                present_attrs.push(name.clone());
-                if name == "role"
-                    && let Some(val) = attr_value
-                {
-                    role_value = Some(val);
-                }
-            }
+        if child.kind() == "jsx_opening_element" {
+            check_jsx_tag(&child, node, source, diagnostics);
         }
     }
-
-    if let Some(role) = role_value {
-        check_required_attrs(&role, &present_attrs, node, diagnostics);
-    }
 }
 
-fn check_jsx_element(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
-    let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        if child.kind() == "jsx_opening_element" {
-            let mut role_value: Option<String> = None;
-            let mut present_attrs: Vec<String> = Vec::new();
-
-            let mut inner_cursor = child.walk();
-            for inner_child in child.children(&mut inner_cursor) {
-                if inner_child.kind() == "jsx_attribute" {
-                    let (attr_name, attr_value) = extract_jsx_attribute(&inner_child, source);
-                    if let Some(ref name) = attr_name {
-                        present_attrs.push(name.clone());
-                        if name == "role"
-                            && let Some(val) = attr_value
-                        {
-                            role_value = Some(val);
-                        }
+/// Check the attributes on a JSX opening/self-closing `tag`, reporting on
+/// `report_node`.
+fn check_jsx_tag(tag: &Node, report_node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut role_value: Option<String> = None;
+    let mut native_role: Option<String> = None;
+    let mut present_attrs: Vec<String> = Vec::new();
+    let mut host = Host {
+        tag: None,
+        input_type: None,
+    };
+
+    let mut cursor = tag.walk();
+    for child in tag.children(&mut cursor) {
+        match child.kind() {
+            "identifier" => host.tag = Some(&source[child.byte_range()]),
+            "jsx_attribute" => {
+                let (attr_name, attr_value) = extract_jsx_attribute(&child, source);
+                if let Some(ref name) = attr_name {
+                    present_attrs.push(name.to_ascii_lowercase());
+                    if name == "role" {
+                        role_value = attr_value;
+                    } else if name == "accessibilityRole" {
+                        native_role = attr_value;
+                    } else if name == "type" {
+                        host.input_type = attr_value;
+                    }
+                    // React Native's `accessibilityState={{ checked }}` and
+                    // `accessibilityValue={{ now }}`.
+                    if let Some((_, Some(value))) = jsx_attrs::jsx_attribute(&child, source) {
+                        present_attrs.extend(
+                            react_native::state_attrs(name, &value, source)
+                                .into_iter()
+                                .map(str::to_string),
+                        );
                     }
                 }
             }
-
-            if let Some(role) = role_value {
-                check_required_attrs(&role, &present_attrs, node, diagnostics);
-            }
+            _ => {}
         }
     }
+
+    // React Native calls a slider "adjustable".
+    let native_role = native_role.map(|role| match role.as_str() {
+        "adjustable" => "slider".to_string(),
+        _ => role,
+    });
+    if let Some(role) = role_value.or(native_role) {
+        check_required_attrs(&role, &host, &present_attrs, report_node, diagnostics);
+    }
 }
 
 /// Extract (attribute_name, Option<string_value>) from a JSX attribute node.
@@ -191,14 +239,25 @@ fn extract_jsx_attribute(attr_node: &Node, source: &str) -> (Option<String>, Opt
 
 fn check_required_attrs(
     role: &str,
+    host: &Host,
     present_attrs: &[String],
     node: &Node,
     diagnostics: &mut Vec<Diagnostic>,
 ) {
+    // Only a focusable separator (a splitter) is a widget with a value.
+    if role == "separator" && !present_attrs.iter().any(|a| a == "tabindex") {
+        return;
+    }
+
     if let Some(required) = REQUIRED_ATTRS_BY_ROLE.get(role) {
+        let native = host
+            .tag
+            .map(|tag| native_provided_attrs(tag, host.input_type.as_deref()))
+            .unwrap_or(&[]);
         let missing: Vec<&str> = required
             .iter()
             .filter(|attr| !present_attrs.iter().any(|a| a.eq_ignore_ascii_case(attr)))
+            .filter(|attr| !native.contains(attr))
             .copied()
             .collect();
 
@@ -343,6 +402,73 @@ mod tests {
         assert_eq!(diags.len(), 1);
     }
 
+    #[test]
+    fn test_menuitemcheckbox_without_aria_checked_fails() {
+        let diags = check_html(r#"<li role="menuitemcheckbox">Bold</li>"#);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("aria-checked"));
+    }
+
+    #[test]
+    fn test_menuitemradio_without_aria_checked_fails() {
+        let diags = check_html(r#"<li role="menuitemradio">Small</li>"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_native_checkbox_as_switch_passes() {
+        let diags = check_html(r#"<input type="checkbox" role="switch">"#);
+        assert_eq!(diags.len(), 0, "checked state comes from the native input");
+    }
+
+    #[test]
+    fn test_native_range_as_slider_passes() {
+        let diags = check_html(r#"<input type="range" role="slider">"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_native_text_input_as_switch_fails() {
+        let diags = check_html(r#"<input type="text" role="switch">"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_native_heading_with_heading_role_passes() {
+        let diags = check_html(r#"<h2 role="heading">Title</h2>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_native_option_passes() {
+        let diags = check_html(r#"<select><option role="option">A</option></select>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_static_separator_passes() {
+        let diags = check_html(r#"<div role="separator"></div>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_focusable_separator_without_valuenow_fails() {
+        let diags = check_html(r#"<div role="separator" tabindex="0"></div>"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_tsx_native_checkbox_as_switch_passes() {
+        let diags = check_tsx(r#"const App = () => <input type="checkbox" role="switch" />;"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_focusable_separator_fails() {
+        let diags = check_tsx(r#"const App = () => <div role="separator" tabIndex={0} />;"#);
+        assert_eq!(diags.len(), 1);
+    }
+
     #[test]
     fn test_tsx_checkbox_with_aria_checked_passes() {
         let diags = check_tsx(r#"const App = () => <div role="checkbox" aria-checked="true" />;"#);
@@ -371,4 +497,21 @@ mod tests {
             check_tsx(r#"const App = () => <div role="slider" aria-valuenow="50">content</div>;"#);
         assert_eq!(diags.len(), 0);
     }
+
+    #[test]
+    fn test_react_native_accessibility_state() {
+        let diags = check_tsx(
+            r#"const A = () => (
+  <>
+    <Pressable accessibilityRole="checkbox" accessibilityState={{ checked }} />
+    <Pressable accessibilityRole="switch" accessibilityState={{ disabled: true }} />
+    <View accessibilityRole="adjustable" accessibilityValue={{ min: 0, max: 10, now: 3 }} />
+    <View accessibilityRole="adjustable" />
+  </>
+);"#,
+        );
+        let lines: Vec<u32> = diags.iter().map(|d| d.range.start.line).collect();
+        assert_eq!(lines, [3, 5]);
+        assert!(diags[1].message.contains("aria-valuenow"));
+    }
 }
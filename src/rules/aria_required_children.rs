@@ -16,6 +16,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.3.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
     default_severity: Severity::Error,
+    axe_rules: &["aria-required-children"],
 };
 
 static REQUIRED_CHILDREN_BY_ROLE: LazyLock<HashMap<&'static str, Vec<&'static str>>> =
@@ -0,0 +1,389 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaRoledescription;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-roledescription",
+    description: "aria-roledescription must be on an element with a role and must describe more than the role name",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Warning,
+    axe_rules: &["aria-roledescription"],
+};
+
+/// Elements with no implicit role (generic or none). `aria-roledescription`
+/// on these is ignored by assistive technology.
+const ROLELESS_TAGS: &[&str] = &[
+    "abbr", "b", "bdi", "bdo", "cite", "data", "div", "i", "kbd", "q", "samp", "small", "span",
+    "u", "var",
+];
+
+/// Explicit roles that remove the element's semantics.
+const ROLELESS_ROLES: &[&str] = &["none", "presentation"];
+
+/// One element's attributes as far as this rule is concerned.
+struct Element<'a> {
+    /// Lowercase tag name; `None` for components and custom elements, whose
+    /// role we can't know.
+    tag: Option<String>,
+    /// The explicit role: `None` without one, `Some(None)` when it's dynamic.
+    role: Option<Option<String>>,
+    has_href: bool,
+    input_type: Option<String>,
+    /// The `aria-roledescription` attribute and its static value.
+    roledescription: Option<(Node<'a>, Option<String>)>,
+}
+
+impl Rule for AriaRoledescription {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, &mut diagnostics);
+        } else {
+            visit_html(root, source, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let attrs = html_attrs::attrs(&tag, source);
+        let static_value = |a: &html_attrs::Attr| if a.bound { None } else { a.value.clone() };
+        let tag_name = html_attrs::tag_name(&tag, source)
+            .map(str::to_ascii_lowercase)
+            .filter(|name| !name.contains('-'));
+        let element = Element {
+            tag: tag_name,
+            role: attrs.iter().find(|a| a.name_eq("role")).map(static_value),
+            has_href: attrs.iter().any(|a| a.name_eq("href")),
+            input_type: attrs
+                .iter()
+                .find(|a| a.name_eq("type"))
+                .and_then(static_value),
+            roledescription: attrs
+                .iter()
+                .find(|a| a.name_eq("aria-roledescription"))
+                .map(|a| (a.node, static_value(a))),
+        };
+        check_element(&element, diagnostics);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        check_jsx_tag(&tag, source, diagnostics);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, diagnostics);
+    }
+}
+
+fn check_jsx_tag(tag: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut element = Element {
+        tag: None,
+        role: None,
+        has_href: false,
+        input_type: None,
+        roledescription: None,
+    };
+    let mut cursor = tag.walk();
+    for child in tag.children(&mut cursor) {
+        match child.kind() {
+            // Only lowercase identifiers are DOM elements; `<Card>` and
+            // `<ui.Card>` are components.
+            "identifier" => {
+                let name = &source[child.byte_range()];
+                if name.starts_with(|c: char| c.is_ascii_lowercase()) && !name.contains('-') {
+                    element.tag = Some(name.to_string());
+                }
+            }
+            // `{...props}` may set the role.
+            "jsx_expression" if element.role.is_none() => element.role = Some(None),
+            "jsx_attribute" => {
+                let (name, value) = extract_jsx_attribute(&child, source);
+                match name.as_deref() {
+                    Some("role") => element.role = Some(value),
+                    Some("href") => element.has_href = true,
+                    Some("type") => element.input_type = value,
+                    Some("aria-roledescription") => element.roledescription = Some((child, value)),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    check_element(&element, diagnostics);
+}
+
+fn extract_jsx_attribute(attr_node: &Node, source: &str) -> (Option<String>, Option<String>) {
+    let mut name = None;
+    let mut value = None;
+    let mut cursor = attr_node.walk();
+    for child in attr_node.children(&mut cursor) {
+        match child.kind() {
+            "property_identifier" => name = Some(source[child.byte_range()].to_string()),
+            "string" => {
+                let raw = &source[child.byte_range()];
+                value = Some(raw.trim_matches('"').trim_matches('\'').to_string());
+            }
+            _ => {}
+        }
+    }
+    (name, value)
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn check_element(element: &Element, diagnostics: &mut Vec<Diagnostic>) {
+    let Some((attr, value)) = &element.roledescription else {
+        return;
+    };
+
+    let role = match &element.role {
+        // A dynamic role could be anything.
+        Some(None) => return,
+        Some(Some(roles)) => roles.split_whitespace().next().map(str::to_ascii_lowercase),
+        None => match element.tag.as_deref() {
+            Some(tag) => implicit_role(tag, element.has_href, element.input_type.as_deref())
+                .map(str::to_string),
+            None => return,
+        },
+    };
+
+    let Some(role) = role.filter(|r| !ROLELESS_ROLES.contains(&r.as_str())) else {
+        // An empty or presentational role, or a known role-less tag.
+        let known_roleless = element.role.is_some()
+            || element
+                .tag
+                .as_deref()
+                .is_some_and(|t| ROLELESS_TAGS.contains(&t) || t == "a");
+        if known_roleless {
+            diagnostics.push(make_diagnostic(
+                attr,
+                "aria-roledescription is ignored on an element without a role; give it a role or remove the attribute",
+            ));
+        }
+        return;
+    };
+
+    if let Some(value) = value
+        && repeats_role(value, &role)
+    {
+        diagnostics.push(make_diagnostic(
+            attr,
+            &format!(
+                "aria-roledescription=\"{}\" only repeats the role \"{role}\"; describe what the element is, or remove the attribute",
+                value.trim()
+            ),
+        ));
+    }
+}
+
+/// The implicit role of a DOM element, for the elements that have one and
+/// commonly carry `aria-roledescription`. `None` for role-less elements and
+/// elements this rule doesn't know.
+fn implicit_role(tag: &str, has_href: bool, input_type: Option<&str>) -> Option<&'static str> {
+    Some(match tag {
+        "a" | "area" if has_href => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "button" => "button",
+        "dialog" => "dialog",
+        "fieldset" => "group",
+        "figure" => "figure",
+        "footer" => "contentinfo",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "header" => "banner",
+        "img" => "img",
+        "input" => match input_type.map(str::to_ascii_lowercase).as_deref() {
+            Some("button" | "submit" | "reset" | "image") => "button",
+            Some("checkbox") => "checkbox",
+            Some("radio") => "radio",
+            Some("range") => "slider",
+            Some("search") => "searchbox",
+            _ => "textbox",
+        },
+        "li" => "listitem",
+        "main" => "main",
+        "nav" => "navigation",
+        "ol" | "ul" => "list",
+        "p" => "paragraph",
+        "section" => "region",
+        "select" => "combobox",
+        "table" => "table",
+        "textarea" => "textbox",
+        _ => return None,
+    })
+}
+
+/// Whether `value` says nothing but the role name, e.g. "button" or
+/// "Button button" on a button.
+fn repeats_role(value: &str, role: &str) -> bool {
+    let mut words = value.split_whitespace().peekable();
+    words.peek().is_some() && words.all(|w| w.eq_ignore_ascii_case(role))
+}
+
+fn make_diagnostic(node: &Node, detail: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{}. {} [WCAG {} Level {:?}]",
+            detail, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaRoledescription;
+        rule.check(&tree.root_node(), source, FileType::Html)
+    }
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaRoledescription;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    fn check_vue(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Vue).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaRoledescription;
+        rule.check(&tree.root_node(), source, FileType::Vue)
+    }
+
+    #[test]
+    fn test_meaningful_description_passes() {
+        let diags = check_html(
+            r#"<section aria-roledescription="slide" aria-label="1 of 3"></section><div role="button" aria-roledescription="toggle">A</div>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_div_without_role_fails() {
+        let diags = check_html(r#"<div aria-roledescription="slide"></div>"#);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 5);
+        assert!(diags[0].message.contains("without a role"));
+    }
+
+    #[test]
+    fn test_presentational_role_fails() {
+        let diags = check_html(r#"<li role="none" aria-roledescription="item"></li>"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_anchor_without_href_fails() {
+        let diags = check_html(r#"<a aria-roledescription="card">A</a>"#);
+        assert_eq!(diags.len(), 1);
+        let diags = check_html(r##"<a href="#" aria-roledescription="card">A</a>"##);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_repeated_role_fails() {
+        let diags = check_html(r#"<button aria-roledescription="button button">Go</button>"#);
+        assert_eq!(diags.len(), 1);
+        assert!(
+            diags[0]
+                .message
+                .contains("only repeats the role \"button\"")
+        );
+    }
+
+    #[test]
+    fn test_repeated_explicit_role_fails() {
+        let diags = check_html(r#"<div role="tab" aria-roledescription="Tab">A</div>"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_elements_ignored() {
+        let diags = check_html(
+            r#"<my-card aria-roledescription="card"></my-card><dl aria-roledescription="glossary"></dl>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_bound_role_ignored() {
+        let diags = check_vue(
+            r#"<template><div :role="role" aria-roledescription="slide"></div></template>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_span_without_role_fails() {
+        let diags = check_tsx(r#"const A = () => <span aria-roledescription="badge">3</span>;"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_tsx_repeated_role_fails() {
+        let diags =
+            check_tsx(r#"const A = () => <div role="slider" aria-roledescription="slider" />;"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_tsx_component_and_spread_ignored() {
+        let diags = check_tsx(
+            r#"const A = () => <><Card aria-roledescription="card" /><div {...props} aria-roledescription="slide" /></>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+}
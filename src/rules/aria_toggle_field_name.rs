@@ -0,0 +1,153 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::aria_name;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaToggleFieldName;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-toggle-field-name",
+    description: "Elements with a checkbox, radio, switch, menuitemcheckbox or menuitemradio role must have an accessible name",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Error,
+    axe_rules: &["aria-toggle-field-name"],
+};
+
+const TOGGLE_ROLES: &[&str] = &[
+    "checkbox",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "radio",
+    "switch",
+];
+
+/// Elements named by a `<label>` or their own attributes, which
+/// `form-label` and `button-name` check.
+const NATIVE_FIELDS: &[&str] = &["button", "input", "select", "textarea"];
+
+impl Rule for AriaToggleFieldName {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        aria_name::unnamed(analysis, TOGGLE_ROLES, true, NATIVE_FIELDS)
+            .into_iter()
+            .map(|(element, role)| {
+                make_diagnostic(
+                    &element.tag,
+                    &format!(
+                        "role=\"{role}\" without a name: add a text label inside it, aria-label, or aria-labelledby pointing at its visible label"
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaToggleFieldName;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_named_toggles_pass() {
+        let source = r#"<div role="checkbox" aria-checked="false">Subscribe</div>
+<span role="switch" aria-checked="true" aria-label="Dark mode"></span>
+<li role="menuitemradio" aria-checked="true"><span>Small</span></li>
+<div role="radio" aria-labelledby="opt-a"></div>
+<input type="checkbox" role="switch">
+<div role="button"></div>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_unnamed_toggles_fail() {
+        let source = r#"<div role="checkbox" aria-checked="false"></div>
+<span role="Switch" aria-label=" ">  </span>
+<li role="menuitemcheckbox" title=""></li>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 3);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("role=\"checkbox\" without a name")
+        );
+        assert!(
+            diags[1]
+                .message
+                .starts_with("role=\"switch\" without a name")
+        );
+        assert_eq!(diags[2].range.start.line, 2);
+    }
+
+    #[test]
+    fn test_vue_bound_attributes() {
+        let source = r#"<template>
+  <div role="switch" :aria-label="label"></div>
+  <div role="radio" v-text="option.name"></div>
+  <div role="checkbox" v-bind="attrs"></div>
+  <div role="checkbox" :aria-checked="on"></div>
+</template>"#;
+        let diags = check(source, FileType::Vue);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 4);
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Options = () => (
+  <>
+    <div role="checkbox" aria-checked={on}>{label}</div>
+    <div role="switch" aria-label={t("dark")} />
+    <div role="radio" {...props} />
+    <Toggle role="switch" />
+    <div role="menuitemradio" aria-checked={on} />
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 6);
+    }
+}
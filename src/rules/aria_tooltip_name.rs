@@ -0,0 +1,128 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::aria_name;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaTooltipName;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-tooltip-name",
+    description: "Elements with role=\"tooltip\" must have an accessible name",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Error,
+    axe_rules: &["aria-tooltip-name"],
+};
+
+impl Rule for AriaTooltipName {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        // A tooltip is named by its text, which is what it's there to show.
+        aria_name::unnamed(analysis, &["tooltip"], true, &[])
+            .into_iter()
+            .map(|(element, _)| make_diagnostic(&element.tag, "role=\"tooltip\" without a name: put the text it shows inside it, or add aria-label or aria-labelledby"))
+            .collect()
+    }
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaTooltipName;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_named_tooltips_pass() {
+        let source = r#"<div role="tooltip" id="tip">Copies the link</div>
+<div role="tooltip" aria-label="Keyboard shortcut"><kbd>Ctrl</kbd></div>
+<span role="tooltip" aria-labelledby="tip"></span>
+<div role="tooltip" title="Help"></div>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_unnamed_tooltips_fail() {
+        let source = r#"<div role="tooltip" id="tip"></div>
+<div role="Tooltip">   </div>
+<div role="tooltip" aria-label=""></div>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 3);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("role=\"tooltip\" without a name")
+        );
+        assert_eq!(diags[2].range.start.line, 2);
+    }
+
+    #[test]
+    fn test_vue_bound_content() {
+        let source = r#"<template>
+  <div role="tooltip" v-text="hint"></div>
+  <div role="tooltip">{{ hint }}</div>
+  <div role="tooltip" :aria-label="hint"></div>
+  <div role="tooltip" v-bind="attrs"></div>
+  <div role="tooltip" :id="tipId"></div>
+</template>"#;
+        let diags = check(source, FileType::Vue);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 5);
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Hint = ({ text }) => (
+  <>
+    <div role="tooltip">{text}</div>
+    <div role="tooltip" {...props} />
+    <Tooltip role="tooltip" />
+    <div role="tooltip" id={id} />
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 5);
+    }
+}
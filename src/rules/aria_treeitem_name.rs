@@ -0,0 +1,121 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::aria_name;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AriaTreeitemName;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "aria-treeitem-name",
+    description: "Elements with role=\"treeitem\" must have an accessible name",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Error,
+    axe_rules: &["aria-treeitem-name"],
+};
+
+impl Rule for AriaTreeitemName {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        aria_name::unnamed(analysis, &["treeitem"], true, &[])
+            .into_iter()
+            .map(|(element, _)| make_diagnostic(&element.tag, "role=\"treeitem\" without a name: add a text label inside it, aria-label, or aria-labelledby pointing at its visible label"))
+            .collect()
+    }
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AriaTreeitemName;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_named_treeitems_pass() {
+        let source = r#"<ul role="tree" aria-label="Files">
+  <li role="treeitem" aria-expanded="true">src
+    <ul role="group">
+      <li role="treeitem"><span class="icon"></span>main.rs</li>
+      <li role="treeitem" aria-label="lib.rs"></li>
+      <li role="treeitem" aria-labelledby="cfg"></li>
+    </ul>
+  </li>
+</ul>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_unnamed_treeitems_fail() {
+        let source = r#"<ul role="tree" aria-label="Files">
+  <li role="treeitem"></li>
+  <li role="TreeItem" title=" "> </li>
+</ul>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 2);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("role=\"treeitem\" without a name")
+        );
+        assert_eq!(diags[0].range.start.line, 1);
+        assert_eq!(diags[1].range.start.line, 2);
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Node = ({ node }) => (
+  <ul role="tree">
+    <li role="treeitem">{node.name}</li>
+    <li role="treeitem" aria-label={node.name} />
+    <li role="treeitem" {...props} />
+    <TreeItem role="treeitem" />
+    <li role="treeitem" aria-selected={selected} />
+  </ul>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 6);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AudioTranscript;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "audio-transcript",
+    description: "Prerecorded audio needs a transcript",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.2.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/audio-only-and-video-only-prerecorded.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+impl Rule for AudioTranscript {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_config(root, source, file_type, &Config::default())
+    }
+
+    fn check_with_config(
+        &self,
+        root: &Node,
+        source: &str,
+        file_type: FileType,
+        config: &Config,
+    ) -> Vec<Diagnostic> {
+        let keywords = &config.audio_transcript.keywords;
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, keywords, &mut diagnostics);
+        } else {
+            visit_html(root, source, keywords, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, keywords: &[String], diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+        && html_attrs::tag_name(&tag, source).is_some_and(|n| n.eq_ignore_ascii_case("audio"))
+    {
+        if !has_transcript_nearby(node, source, keywords) {
+            diagnostics.push(make_diagnostic(&tag));
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, keywords, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, keywords: &[String], diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag
+        && tag
+            .child_by_field_name("name")
+            .is_some_and(|n| &source[n.byte_range()] == "audio")
+    {
+        if !has_transcript_nearby(node, source, keywords) {
+            diagnostics.push(make_diagnostic(&tag));
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, keywords, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+/// Whether one of `keywords` appears in the player's fallback content, in
+/// the elements beside it, or in those beside its container: the text, a
+/// link, an id or class name or a component name of a transcript.
+fn has_transcript_nearby(audio: &Node, source: &str, keywords: &[String]) -> bool {
+    let mentions = |node: &Node| {
+        let text = source[node.byte_range()].to_lowercase();
+        keywords
+            .iter()
+            .any(|keyword| text.contains(keyword.as_str()))
+    };
+    if mentions(audio) {
+        return true;
+    }
+    let mut current = *audio;
+    // The player's own siblings, then its container's.
+    for _ in 0..2 {
+        let Some(parent) = current.parent() else {
+            return false;
+        };
+        let mut cursor = parent.walk();
+        let found = parent
+            .named_children(&mut cursor)
+            .filter(|sibling| sibling.id() != current.id())
+            .any(|sibling| mentions(&sibling));
+        if found {
+            return true;
+        }
+        current = parent;
+    }
+    false
+}
+
+fn make_diagnostic(tag: &Node) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(tag),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "No transcript found near this `<audio>`: put the transcript, or a link to it, next to the player. Captions don't replace it for audio-only content. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType, config: &Config) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AudioTranscript;
+        rule.check_with_config(&tree.root_node(), source, file_type, config)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html, &Config::default())
+    }
+
+    #[test]
+    fn test_audio_without_transcript_fails() {
+        let diags = check_html(
+            r#"<article><h2>Episode 12</h2><audio controls src="ep12.mp3"></audio><p>Show notes</p></article>"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 28);
+        assert!(diags[0].message.contains("1.2.1"));
+    }
+
+    #[test]
+    fn test_transcript_nearby_passes() {
+        let source = r#"<div><audio controls src="a.mp3"></audio><a href="/a/transcript">Read along</a></div>
+<figure><div class="player"><audio controls src="b.mp3"></audio></div><figcaption><a href="b.html">Transcript</a></figcaption></figure>
+<audio controls src="c.mp3"><a href="c-transcript.txt">Download</a></audio>
+<section><audio src="d.mp3"></audio><details id="d-transkript"><summary>Text</summary></details></section>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_transcript_too_far_away_fails() {
+        let source = r#"<main><section><div><audio src="a.mp3"></audio></div></section><aside><a href="/transcripts">All transcripts</a></aside></main>"#;
+        assert_eq!(check_html(source).len(), 1);
+    }
+
+    #[test]
+    fn test_configured_keywords() {
+        let config = Config::parse("[audio-transcript]\nkeywords = [\"mitschrift\"]\n");
+        let source = r#"<section><div><audio src="a.mp3"></audio><a href="a.html">Mitschrift lesen</a></div></section>
+<section><div><audio src="b.mp3"></audio><a href="b.html">Transcript</a></div></section>"#;
+        let diags = check(source, FileType::Html, &config);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 1);
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Episode = ({ src }) => (
+  <>
+    <section><div><audio controls src={src} /></div></section>
+    <section><div><audio controls src={src} /><Transcript episode={id} /></div></section>
+  </>
+);"#,
+            FileType::Tsx,
+            &Config::default(),
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+    }
+}
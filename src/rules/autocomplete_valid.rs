@@ -16,6 +16,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.3.5",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/identify-input-purpose.html",
     default_severity: Severity::Warning,
+    axe_rules: &["autocomplete-valid"],
 };
 
 static VALID_AUTOCOMPLETE_TOKENS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
@@ -0,0 +1,261 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct AutoplayControls;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "autoplay-controls",
+    description: "Autoplaying video must offer a way to pause it",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.2.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/pause-stop-hide.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// An autoplaying `<video>` and what it has besides.
+struct Video<'a> {
+    node: Node<'a>,
+    id: Option<String>,
+    looping: bool,
+}
+
+impl Rule for AutoplayControls {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut videos = Vec::new();
+        // Ids named by an element's `aria-controls`, such as a custom pause
+        // button's.
+        let mut controlled = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, &mut videos, &mut controlled);
+        } else {
+            visit_html(root, source, &mut videos, &mut controlled);
+        }
+
+        videos
+            .iter()
+            .filter(|video| {
+                !video
+                    .id
+                    .as_ref()
+                    .is_some_and(|id| controlled.iter().any(|c| c == id))
+            })
+            .map(make_diagnostic)
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html<'a>(
+    node: &Node<'a>,
+    source: &str,
+    videos: &mut Vec<Video<'a>>,
+    controlled: &mut Vec<String>,
+) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let attrs = html_attrs::attrs(&tag, source);
+        let value = |name: &str| {
+            attrs
+                .iter()
+                .find(|a| a.name_eq(name) && !a.bound)
+                .and_then(|a| a.value.clone())
+        };
+        if let Some(ids) = value("aria-controls") {
+            controlled.extend(ids.split_whitespace().map(str::to_string));
+        }
+
+        // A bound `:autoplay` or `:controls` counts as present.
+        let has = |name: &str| attrs.iter().any(|a| a.name_eq(name));
+        let is_video =
+            html_attrs::tag_name(&tag, source).is_some_and(|n| n.eq_ignore_ascii_case("video"));
+        if is_video && has("autoplay") && !has("controls") {
+            videos.push(Video {
+                node: *node,
+                id: value("id"),
+                looping: has("loop"),
+            });
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, videos, controlled);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx<'a>(
+    node: &Node<'a>,
+    source: &str,
+    videos: &mut Vec<Video<'a>>,
+    controlled: &mut Vec<String>,
+) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let mut cursor = tag.walk();
+        let attrs: Vec<_> = tag
+            .named_children(&mut cursor)
+            .filter_map(|attr| jsx_attrs::jsx_attribute(&attr, source))
+            .collect();
+        let string = |name: &str| {
+            attrs
+                .iter()
+                .find(|(n, _)| *n == name)
+                .and_then(|(_, v)| v.filter(|v| v.kind() == "string"))
+                .map(|v| source[v.byte_range()].trim_matches(['"', '\'']).to_string())
+        };
+        // `autoPlay={false}` and `loop={false}` are off.
+        let on = |names: &[&str]| {
+            attrs.iter().any(|(n, v)| {
+                names.contains(n) && !v.is_some_and(|v| &source[v.byte_range()] == "{false}")
+            })
+        };
+        if let Some(ids) = string("aria-controls") {
+            controlled.extend(ids.split_whitespace().map(str::to_string));
+        }
+
+        let is_video = tag
+            .child_by_field_name("name")
+            .is_some_and(|n| &source[n.byte_range()] == "video");
+        if is_video && on(&["autoPlay", "autoplay"]) && !on(&["controls"]) {
+            videos.push(Video {
+                node: *node,
+                id: string("id"),
+                looping: on(&["loop"]),
+            });
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, videos, controlled);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn make_diagnostic(video: &Video) -> Diagnostic {
+    let meta = &METADATA;
+    let detail = if video.looping {
+        "This video plays on its own and loops"
+    } else {
+        "This video plays on its own"
+    };
+    Diagnostic {
+        range: node_to_range(&video.node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{}: unless it stops within 3 seconds, add `controls` or a pause button with `aria-controls` naming its id. {} [WCAG {} Level {:?}]",
+            detail, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = AutoplayControls;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_muted_autoplay_without_controls_fails() {
+        let diags = check_html(r#"<video src="hero.mp4" autoplay muted loop playsinline></video>"#);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(
+            diags[0].code,
+            Some(NumberOrString::String("autoplay-controls".to_string()))
+        );
+        assert!(
+            diags[0]
+                .message
+                .starts_with("This video plays on its own and loops:")
+        );
+    }
+
+    #[test]
+    fn test_controls_or_pause_button_passes() {
+        let diags = check_html(
+            r#"<video src="a.mp4" autoplay muted controls></video>
+<video id="hero" src="b.mp4" autoplay muted loop></video>
+<button aria-controls="hero" aria-pressed="false">Pause</button>
+<video src="c.mp4" muted loop></video>
+<audio src="d.mp3" autoplay></audio>"#,
+        );
+        assert_eq!(diags.len(), 0, "got: {diags:?}");
+    }
+
+    #[test]
+    fn test_pause_button_for_another_video_fails() {
+        let diags = check_html(
+            r#"<video id="a" src="a.mp4" autoplay muted></video><button aria-controls="b">Pause</button>"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.starts_with("This video plays on its own:"));
+    }
+
+    #[test]
+    fn test_vue_bound_controls_passes() {
+        let diags = check(
+            r#"<template><video src="a.mp4" autoplay muted :controls="showControls"></video></template>"#,
+            FileType::Vue,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx() {
+        let diags = check(
+            r#"const A = () => (
+  <>
+    <video src="a.mp4" autoPlay muted loop />
+    <video src="b.mp4" autoPlay={false} muted />
+    <video src="c.mp4" autoPlay muted controls />
+    <video id="intro" src="d.mp4" autoPlay muted />
+    <PauseButton aria-controls="intro" />
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+    }
+}
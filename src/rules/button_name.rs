@@ -1,6 +1,8 @@
 use crate::engine::node_to_range;
 use crate::parser::FileType;
 use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::react_native::{self, Components};
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
 use tower_lsp_server::ls_types::*;
 use tree_sitter::Node;
@@ -14,6 +16,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "4.1.2",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
     default_severity: Severity::Error,
+    axe_rules: &["button-name"],
 };
 
 impl Rule for ButtonName {
@@ -24,7 +27,8 @@ impl Rule for ButtonName {
     fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
         if file_type.is_jsx_like() {
-            visit_jsx(root, source, &mut diagnostics);
+            let native = react_native::components(root, source);
+            visit_jsx(root, source, native.as_ref(), &mut diagnostics);
         } else {
             visit_html(root, source, &mut diagnostics);
         }
@@ -66,7 +70,7 @@ fn check_html_element(element: &Node, source: &str, diagnostics: &mut Vec<Diagno
     let has_accessible_name = attrs
         .iter()
         .any(|a| a.name_eq("aria-label") || a.name_eq("aria-labelledby") || a.name_eq("title"));
-    if has_accessible_name {
+    if has_accessible_name || html_attrs::has_dynamic_content(&attrs) {
         return;
     }
 
@@ -102,7 +106,12 @@ fn has_content(element: &Node, source: &str) -> bool {
 // JSX / TSX
 // ---------------------------------------------------------------------------
 
-fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+fn visit_jsx(
+    node: &Node,
+    source: &str,
+    native: Option<&Components>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
     match node.kind() {
         "jsx_self_closing_element" => {
             check_jsx_self_closing(node, source, diagnostics);
@@ -112,10 +121,62 @@ fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
         }
         _ => {}
     }
+    if let Some(native) = native {
+        check_native_button(node, source, native, diagnostics);
+    }
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        visit_jsx(&child, source, diagnostics);
+        visit_jsx(&child, source, native, diagnostics);
+    }
+}
+
+/// React Native roles of a button.
+const NATIVE_BUTTON_ROLES: &[&str] = &["button", "imagebutton", "togglebutton"];
+
+/// A React Native `<Pressable>` or `Touchable*`, or an element with a button
+/// `accessibilityRole`, is named by a label prop or its content; a `<Button>`
+/// by its `title`.
+fn check_native_button(
+    node: &Node,
+    source: &str,
+    native: &Components,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => *node,
+        "jsx_element" => match node.child(0).filter(|c| c.kind() == "jsx_opening_element") {
+            Some(tag) => tag,
+            None => return,
+        },
+        _ => return,
+    };
+    let name = tag
+        .child_by_field_name("name")
+        .map_or("", |n| &source[n.byte_range()]);
+    let attrs = react_native::tag_attrs(&tag, source);
+    if react_native::is_hidden(&attrs, source) || react_native::has_label(&attrs, source) {
+        return;
+    }
+
+    let named = if native.button == Some(name) {
+        attrs.iter().any(|(n, value)| {
+            *n == "title" && !jsx_attrs::is_empty_jsx_value(value.as_ref(), source)
+        })
+    } else {
+        let is_button = match react_native::role(&attrs, source) {
+            Some(role) => NATIVE_BUTTON_ROLES.contains(&role.as_str()),
+            None => native.pressables.contains(&name),
+        };
+        !is_button || has_jsx_content(node, source)
+    };
+    if !named {
+        let mut diagnostic = make_diagnostic(node);
+        diagnostic.message = format!(
+            "This control has no `accessibilityLabel` or text. {}",
+            diagnostic.message
+        );
+        diagnostics.push(diagnostic);
     }
 }
 
@@ -271,6 +332,14 @@ mod tests {
         rule.check(&tree.root_node(), source, FileType::Vue)
     }
 
+    #[test]
+    fn test_vue_v_text_passes() {
+        let diags = check_vue(
+            r#"<template><button v-text="label"></button><button :textContent="label"></button></template>"#,
+        );
+        assert_eq!(diags.len(), 0, "v-text/textContent fill the button, got: {diags:?}");
+    }
+
     #[test]
     fn test_vue_bound_aria_label_passes() {
         let diags = check_vue(r#"<template><button :aria-label="label"></button></template>"#);
@@ -358,4 +427,26 @@ mod tests {
         let diags = check_tsx(r#"const App = () => <button><img alt="icon" /></button>;"#);
         assert_eq!(diags.len(), 0);
     }
+
+    #[test]
+    fn test_react_native_pressables() {
+        let diags = check_tsx(
+            r#"import { Button, Pressable, Text, TouchableOpacity, View } from "react-native";
+const App = () => (
+  <View>
+    <Pressable onPress={close} />
+    <Pressable onPress={close} accessibilityLabel="Close" />
+    <Pressable onPress={save}><Text>Save</Text></Pressable>
+    <TouchableOpacity onPress={back} accessible={false} />
+    <View accessibilityRole="button" onTouchEnd={next} />
+    <Button title="" onPress={send} />
+    <Button title="Send" onPress={send} />
+    <Pressable accessibilityRole="none" onPress={noop} />
+  </View>
+);"#,
+        );
+        let lines: Vec<u32> = diags.iter().map(|d| d.range.start.line).collect();
+        assert_eq!(lines, [3, 7, 8]);
+        assert!(diags[0].message.starts_with("This control has no `accessibilityLabel`"));
+    }
 }
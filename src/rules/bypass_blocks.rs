@@ -0,0 +1,178 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
+use crate::engine::{DOCUMENT_RANGE, node_to_range};
+use crate::parser::FileType;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct BypassBlocks;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "bypass-blocks",
+    description: "A page must have a skip link, landmarks or headings to bypass repeated content",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.4.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/bypass-blocks.html",
+    default_severity: Severity::Warning,
+    axe_rules: &["bypass"],
+};
+
+impl Rule for BypassBlocks {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Skipping repeated content is a matter of the whole page.
+    fn page_level(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        // Components and fragments aren't pages, and the layouts of server
+        // templates (PHP, Blade, ERB) include their content from elsewhere.
+        if analysis.file_type() != FileType::Html {
+            return Vec::new();
+        }
+        let elements = analysis.elements();
+        if !elements.iter().any(|e| e.name == "body")
+            || analysis.landmarks().next().is_some()
+            || analysis.headings().next().is_some()
+        {
+            return Vec::new();
+        }
+
+        let first = analysis.focusable().next().map(|i| &elements[i]);
+        let fragment = first
+            .and_then(|link| link.static_value("href"))
+            .and_then(|href| href.strip_prefix('#'))
+            .filter(|f| !f.is_empty());
+        match (first, fragment) {
+            (Some(_), Some(fragment))
+                if analysis.runtime_ids() || has_anchor(analysis, fragment) =>
+            {
+                Vec::new()
+            }
+            (Some(link), Some(fragment)) => vec![make_diagnostic(
+                node_to_range(&link.tag),
+                &format!(
+                    "The first link points to \"#{fragment}\", but no element has that id, and the page has no landmarks or headings"
+                ),
+            )],
+            _ => vec![make_diagnostic(
+                DOCUMENT_RANGE,
+                "Nothing lets keyboard users skip to the content: add a \"Skip to main content\" link as the first link, a `<main>` landmark, or headings",
+            )],
+        }
+    }
+}
+
+/// Whether an element has the id `fragment`, or is an `<a name>` anchor of
+/// that name.
+fn has_anchor(analysis: &Analysis, fragment: &str) -> bool {
+    analysis.ids().contains_key(fragment)
+        || analysis
+            .elements()
+            .iter()
+            .any(|e| e.name == "a" && e.static_value("name") == Some(fragment))
+}
+
+fn make_diagnostic(range: Range, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = BypassBlocks;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_page_without_structure_fails() {
+        let source = r#"<html><body>
+<div class="nav"><a href="/">Home</a><a href="/about">About</a></div>
+<div class="content"><p>Text</p></div>
+</body></html>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range, DOCUMENT_RANGE);
+        assert!(diags[0].message.contains("Skip to main content"));
+    }
+
+    #[test]
+    fn test_landmarks_headings_and_skip_links_pass() {
+        let pages = [
+            r#"<body><div class="nav"><a href="/">Home</a></div><main>Text</main></body>"#,
+            r#"<body><a href="/">Home</a><div role="main">Text</div></body>"#,
+            r#"<body><a href="/">Home</a><h2>Intro</h2></body>"#,
+            r##"<body><a href="#content">Skip</a><a href="/">Home</a><div id="content">Text</div></body>"##,
+            r##"<body><input type="hidden" name="t"><a href="#top">Skip</a><a name="top"></a></body>"##,
+        ];
+        for page in pages {
+            assert!(check_html(page).is_empty(), "{page}");
+        }
+    }
+
+    #[test]
+    fn test_skip_link_without_target_fails() {
+        let source = r##"<body><a href="#main">Skip</a><div>Text</div></body>"##;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 6);
+        assert!(
+            diags[0]
+                .message
+                .contains("\"#main\", but no element has that id")
+        );
+
+        // A skip link that isn't the first focusable element doesn't count.
+        let source =
+            r##"<body><button>Menu</button><a href="#c">Skip</a><div id="c"></div></body>"##;
+        assert!(
+            check_html(source)[0]
+                .message
+                .contains("Skip to main content")
+        );
+    }
+
+    #[test]
+    fn test_fragments_and_templates_pass() {
+        assert!(check_html("<div><a href=\"/\">Home</a></div>").is_empty());
+        assert!(check("<body><a href=\"/\">Home</a></body>", FileType::Php).is_empty());
+        assert!(
+            check(
+                "<template><body><a href=\"/\">x</a></body></template>",
+                FileType::Vue
+            )
+            .is_empty()
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct CaptchaAlternative;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "captcha-alternative",
+    description: "CAPTCHAs must offer an alternative to people who can't solve them",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/non-text-content.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+const AUTHENTICATION_URL: &str =
+    "https://www.w3.org/WAI/WCAG22/Understanding/accessible-authentication-minimum.html";
+
+/// Markers of CAPTCHA providers in a class, id or URL, most specific first,
+/// and the name the diagnostic uses.
+const PROVIDERS: &[(&str, &str)] = &[
+    ("recaptcha", "reCAPTCHA"),
+    ("hcaptcha", "hCaptcha"),
+    ("h-captcha", "hCaptcha"),
+    ("cf-turnstile", "Cloudflare Turnstile"),
+    ("challenges.cloudflare.com", "Cloudflare Turnstile"),
+    ("frc-captcha", "Friendly Captcha"),
+    ("friendlycaptcha", "Friendly Captcha"),
+    ("captcha", "CAPTCHA"),
+];
+
+/// React components of the common CAPTCHA packages.
+const CAPTCHA_COMPONENTS: &[(&str, &str)] = &[
+    ("FriendlyCaptcha", "Friendly Captcha"),
+    ("GoogleReCaptcha", "reCAPTCHA"),
+    ("HCaptcha", "hCaptcha"),
+    ("ReCAPTCHA", "reCAPTCHA"),
+    ("ReCaptcha", "reCAPTCHA"),
+    ("Turnstile", "Cloudflare Turnstile"),
+];
+
+impl Rule for CaptchaAlternative {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Whether a CAPTCHA has a usable alternative can't be seen in the
+    /// markup; this only lists them for an audit.
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    fn informational(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, &mut diagnostics);
+        } else {
+            visit_html(root, source, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source).unwrap_or("");
+        let attrs: Vec<(String, Option<String>)> = html_attrs::attrs(&tag, source)
+            .into_iter()
+            .filter(|a| !a.bound)
+            .map(|a| (a.name_lower(), a.value))
+            .collect();
+        if let Some(provider) = widget_provider(name, &attrs) {
+            diagnostics.push(make_diagnostic(&tag, provider));
+            // The widget's own markup is the same CAPTCHA.
+            return;
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let provider = match CAPTCHA_COMPONENTS.iter().find(|(c, _)| *c == name) {
+            Some((_, provider)) => Some(*provider),
+            None if name.starts_with(char::is_uppercase) => None,
+            None => widget_provider(name, &jsx_string_attrs(&tag, source)),
+        };
+        if let Some(provider) = provider {
+            diagnostics.push(make_diagnostic(&tag, provider));
+            return;
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, diagnostics);
+    }
+}
+
+/// The attributes of a JSX tag, lowercased, with their value if it's a
+/// string.
+fn jsx_string_attrs(tag: &Node, source: &str) -> Vec<(String, Option<String>)> {
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .filter_map(|attr| jsx_attrs::jsx_attribute(&attr, source))
+        .map(|(name, value)| {
+            let value = value
+                .filter(|v| v.kind() == "string")
+                .map(|v| source[v.byte_range()].trim_matches(['"', '\'']).to_string());
+            (name.to_ascii_lowercase(), value)
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+/// The provider of the CAPTCHA widget an element with `attrs` is: it has a
+/// `data-sitekey`, a CAPTCHA class or id, or is an `<iframe>` or `<img>`
+/// loading one. Script tags alone aren't reported, since score-based
+/// CAPTCHAs such as reCAPTCHA v3 never challenge anyone.
+fn widget_provider(name: &str, attrs: &[(String, Option<String>)]) -> Option<&'static str> {
+    let value = |attr: &str| {
+        attrs
+            .iter()
+            .find(|(n, _)| n == attr)
+            .and_then(|(_, v)| v.as_deref())
+    };
+    let mut markers = Vec::new();
+    markers.extend(value("class").or_else(|| value("classname")));
+    markers.extend(value("id"));
+    if name.eq_ignore_ascii_case("iframe") || name.eq_ignore_ascii_case("img") {
+        markers.extend(value("src"));
+    }
+    let found = markers.iter().find_map(|marker| provider(marker));
+    if found.is_some() {
+        return found;
+    }
+    attrs
+        .iter()
+        .any(|(n, _)| n == "data-sitekey")
+        .then_some("CAPTCHA")
+}
+
+fn provider(text: &str) -> Option<&'static str> {
+    let text = text.to_ascii_lowercase();
+    PROVIDERS
+        .iter()
+        .find(|(marker, _)| text.contains(marker))
+        .map(|(_, provider)| *provider)
+}
+
+fn make_diagnostic(node: &Node, provider: &str) -> Diagnostic {
+    let meta = &METADATA;
+    let what = if provider == "CAPTCHA" {
+        "This looks like a CAPTCHA".to_string()
+    } else {
+        format!("This looks like a CAPTCHA ({provider})")
+    };
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::INFORMATION),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{}: check that it offers a challenge in another modality (such as audio) or another way through, like contacting support, and that signing in doesn't depend on solving it ({}). {} [WCAG {} Level {:?}]",
+            what, AUTHENTICATION_URL, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = CaptchaAlternative;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_opt_in_and_informational() {
+        assert!(CaptchaAlternative.opt_in());
+        assert!(CaptchaAlternative.informational());
+    }
+
+    #[test]
+    fn test_widgets_reported_by_provider() {
+        let diags = check_html(
+            r#"<form>
+<div class="g-recaptcha" data-sitekey="abc"></div>
+<div class="h-captcha" data-sitekey="abc"></div>
+<div class="cf-turnstile" data-sitekey="abc"></div>
+<iframe src="https://www.google.com/recaptcha/api2/anchor?k=abc" title="reCAPTCHA"></iframe>
+</form>"#,
+        );
+        let messages: Vec<&str> = diags.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages.len(), 4);
+        assert!(messages[0].starts_with("This looks like a CAPTCHA (reCAPTCHA):"));
+        assert!(messages[1].starts_with("This looks like a CAPTCHA (hCaptcha):"));
+        assert!(messages[2].starts_with("This looks like a CAPTCHA (Cloudflare Turnstile):"));
+        assert!(messages[3].starts_with("This looks like a CAPTCHA (reCAPTCHA):"));
+        assert!(messages[0].contains("accessible-authentication-minimum"));
+    }
+
+    #[test]
+    fn test_image_captcha_and_sitekey_reported_once() {
+        let diags = check_html(
+            r#"<img src="/captcha.php?id=7" alt="Code"><div data-sitekey="abc"><iframe src="https://hcaptcha.com/x"></iframe></div>"#,
+        );
+        assert_eq!(diags.len(), 2);
+        assert!(diags[0].message.starts_with("This looks like a CAPTCHA:"));
+    }
+
+    #[test]
+    fn test_scripts_and_unrelated_markup_pass() {
+        let diags = check_html(
+            r#"<script src="https://www.google.com/recaptcha/api.js?render=abc"></script><a href="/captcha-help">Help</a><div class="card"></div>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_jsx_components_and_classes_reported() {
+        let diags = check(
+            r#"const F = () => <form><ReCAPTCHA sitekey={key} /><div className="frc-captcha" /><Button>Send</Button></form>;"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 2);
+        assert!(
+            diags[1]
+                .message
+                .starts_with("This looks like a CAPTCHA (Friendly Captcha):")
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use crate::analysis::{Analysis, Element};
+use crate::config::Config;
+use crate::engine::{node_to_range, related_location};
+use crate::parser::FileType;
+use crate::rules::html_attrs::Attr;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel, aria_name, patterns};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct Carousel;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "carousel",
+    description: "Carousels should let users pause them and label each slide",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.2.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/pause-stop-hide.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Words in a class, id or label that mark a carousel.
+const KEYWORDS: &[&str] = &[
+    "carousel",
+    "slideshow",
+    "slider",
+    "swiper",
+    "splide",
+    "glide",
+];
+
+/// Words in a component name that mark a carousel. `Slider` is left out:
+/// component libraries use it for range inputs.
+const COMPONENT_KEYWORDS: &[&str] = &["carousel", "slideshow", "swiper", "splide"];
+
+/// Classes of the slides of common carousel libraries, which also match
+/// with a prefix, like `swiper-slide`.
+const SLIDE_CLASSES: &[&str] = &["slide", "carousel-item"];
+
+/// Attributes that make a carousel advance on its own, unless `false`.
+const AUTOPLAY_ATTRS: &[&str] = &[
+    "autoplay",
+    "data-autoplay",
+    "data-ride",
+    "data-bs-ride",
+    "data-interval",
+    "data-bs-interval",
+    "data-swiper-autoplay",
+];
+
+/// Words in a control's name, class or label that mark it as the one
+/// pausing the rotation.
+const PAUSE_WORDS: &[&str] = &["pause", "stop", "play"];
+
+/// Attributes whose words, even in a bound expression like
+/// `:aria-label="playing ? 'Pause' : 'Play'"`, name what a control does.
+const CONTROL_NAMING_ATTRS: &[&str] = &["class", "className", "id", "aria-label", "title"];
+
+impl Rule for Carousel {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Carousels are recognized by their class and whether one rotates
+    /// depends on its script; this lists what to review in each.
+    fn informational(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        let elements = analysis.elements();
+        let mut diagnostics = Vec::new();
+        for carousel in patterns::outermost(analysis, is_carousel) {
+            let inside: Vec<usize> = std::iter::once(carousel)
+                .chain(patterns::descendants(analysis, carousel))
+                .collect();
+            let mut findings = Vec::new();
+            let mut related = Vec::new();
+
+            let autoplay = inside
+                .iter()
+                .find_map(|&i| elements[i].attrs.iter().find(|a| advances(a)));
+            if let Some(attr) = autoplay
+                && !has_pause_control(analysis, carousel, &inside)
+            {
+                findings.push(
+                    "it advances on its own and has no pause or stop button: add one, and stop the rotation while the carousel has hover or focus".to_string(),
+                );
+                related.push(related_location(
+                    &attr.node,
+                    &format!("Advances automatically ({})", attr.name),
+                ));
+            }
+
+            let slides = patterns::outermost_in(
+                analysis,
+                patterns::descendants(analysis, carousel),
+                is_slide,
+            );
+            let total = slides.len();
+            let (mut unmarked, mut unlabelled) = (0, 0);
+            for (n, &s) in slides.iter().enumerate() {
+                let slide = &elements[s];
+                if slide.spread {
+                    continue;
+                }
+                let mut missing = Vec::new();
+                if !marked_as_slide(slide) {
+                    unmarked += 1;
+                    missing.push("aria-roledescription=\"slide\"".to_string());
+                }
+                if !slide.named {
+                    unlabelled += 1;
+                    missing.push(format!("aria-label=\"{} of {total}\"", n + 1));
+                }
+                if !missing.is_empty() {
+                    related.push(related_location(
+                        &slide.tag,
+                        &format!("Slide {} of {total}: add {}", n + 1, missing.join(" and ")),
+                    ));
+                }
+            }
+            if unmarked > 0 {
+                findings.push(format!(
+                    "{unmarked} of {total} slides aren't marked with aria-roledescription=\"slide\""
+                ));
+            }
+            if unlabelled > 0 {
+                findings.push(format!(
+                    "{unlabelled} of {total} slides have no label saying which they are, such as aria-label=\"1 of {total}\""
+                ));
+            }
+
+            if !findings.is_empty() {
+                diagnostics.push(make_diagnostic(
+                    &elements[carousel].tag,
+                    &findings.join("; "),
+                    related,
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+fn is_carousel(element: &Element) -> bool {
+    // A range input, which some markup calls a slider.
+    if element.name == "input" || element.role == Some(Some("slider".to_string())) {
+        return false;
+    }
+    let component = element.component && {
+        let name = element.name.to_lowercase();
+        COMPONENT_KEYWORDS.iter().any(|k| name.contains(k))
+    };
+    component
+        || patterns::named_like(element, KEYWORDS)
+        || element
+            .static_value("aria-roledescription")
+            .is_some_and(|d| d.eq_ignore_ascii_case("carousel"))
+}
+
+fn is_slide(element: &Element) -> bool {
+    (element.component && element.name.ends_with("Slide"))
+        || patterns::has_class(element, SLIDE_CLASSES)
+        || element.attr("aria-roledescription").is_some()
+}
+
+/// With `aria-roledescription="slide"`, or a tab panel of a carousel picked
+/// by tabs, which is announced as such.
+fn marked_as_slide(slide: &Element) -> bool {
+    slide.role == Some(Some("tabpanel".to_string()))
+        || slide.attr("aria-roledescription").is_some_and(|a| {
+            a.bound
+                || a.value
+                    .as_deref()
+                    .is_some_and(|v| v.trim().eq_ignore_ascii_case("slide"))
+        })
+}
+
+/// An autoplay attribute that isn't `false`; a boolean attribute, or a
+/// bound value like Swiper's `autoplay={{ delay: 3000 }}`, turns it on.
+fn advances(attr: &Attr) -> bool {
+    AUTOPLAY_ATTRS.iter().any(|name| attr.name_eq(name))
+        && !attr
+            .value
+            .as_deref()
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("false"))
+}
+
+/// A button or link inside the carousel, or pointing at it with
+/// `aria-controls`, whose text, class or label says pause, stop or play.
+fn has_pause_control(analysis: &Analysis, carousel: usize, inside: &[usize]) -> bool {
+    let elements = analysis.elements();
+    let id = elements[carousel].id.as_deref();
+    let says_pause = |text: &str| {
+        let text = text.to_lowercase();
+        PAUSE_WORDS.iter().any(|w| text.contains(w))
+    };
+    elements.iter().enumerate().any(|(i, element)| {
+        let controls = inside.contains(&i)
+            || id.is_some_and(|id| {
+                element
+                    .static_value("aria-controls")
+                    .is_some_and(|c| c.split_whitespace().any(|c| c == id))
+            });
+        let control = matches!(element.name.as_str(), "button" | "a")
+            || matches!(&element.role, Some(Some(role)) if role == "button")
+            || element.component;
+        controls
+            && control
+            && (element.attrs.iter().any(|a| {
+                CONTROL_NAMING_ATTRS.iter().any(|n| a.name_eq(n))
+                    && a.value.as_deref().is_some_and(says_pause)
+            }) || says_pause(&aria_name::text(analysis, element.node())))
+    })
+}
+
+fn make_diagnostic(
+    node: &Node,
+    findings: &str,
+    related: Vec<DiagnosticRelatedInformation>,
+) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::INFORMATION),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "Carousel to review: {findings}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        related_information: (!related.is_empty()).then_some(related),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = Carousel;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_accessible_carousel_passes() {
+        let source = r#"<section class="carousel" aria-roledescription="carousel" aria-label="Offers" data-bs-ride="carousel">
+  <button class="carousel-pause" aria-label="Stop rotation"></button>
+  <div class="carousel-item" role="group" aria-roledescription="slide" aria-label="1 of 2">A</div>
+  <div class="carousel-item" role="group" aria-roledescription="slide" aria-label="2 of 2">B</div>
+</section>
+<input type="range" class="volume-slider">
+<div class="carousel" data-interval="false">
+  <div class="slide" aria-roledescription="slide" aria-labelledby="t1"><h3 id="t1">One</h3></div>
+</div>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_findings_grouped_per_carousel() {
+        let source = r#"<div id="hero" class="carousel slide" data-bs-ride="carousel">
+  <div class="carousel-inner">
+    <div class="carousel-item active"><img src="a.jpg" alt="Sale"></div>
+    <div class="carousel-item" aria-roledescription="slide"><img src="b.jpg" alt="New"></div>
+    <div class="carousel-item" aria-label="3 of 3"><img src="c.jpg" alt="Gifts"></div>
+  </div>
+  <button class="carousel-control-next">Next</button>
+</div>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].severity, Some(DiagnosticSeverity::INFORMATION));
+        assert!(diags[0].message.starts_with(
+            "Carousel to review: it advances on its own and has no pause or stop button: add one, and stop the rotation while the carousel has hover or focus; 2 of 3 slides aren't marked with aria-roledescription=\"slide\"; 2 of 3 slides have no label saying which they are, such as aria-label=\"1 of 3\""
+        ));
+        let related = diags[0].related_information.as_ref().unwrap();
+        assert_eq!(related.len(), 4);
+        assert_eq!(related[0].message, "Advances automatically (data-bs-ride)");
+        assert_eq!(
+            related[1].message,
+            "Slide 1 of 3: add aria-roledescription=\"slide\" and aria-label=\"1 of 3\""
+        );
+        assert_eq!(related[1].location.range.start.line, 2);
+        assert_eq!(
+            related[3].message,
+            "Slide 3 of 3: add aria-roledescription=\"slide\""
+        );
+    }
+
+    #[test]
+    fn test_pause_control_outside_carousel() {
+        let source = r#"<button aria-controls="promo">Pause</button>
+<ul id="promo" class="slideshow" data-autoplay>
+  <li class="slideshow-slide" aria-roledescription="slide" aria-label="1 of 1">A</li>
+</ul>
+<ul class="slideshow" autoplay>
+  <li class="slideshow-slide" aria-roledescription="slide" aria-label="1 of 1">B</li>
+</ul>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 4);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("Carousel to review: it advances on its own")
+        );
+    }
+
+    #[test]
+    fn test_vue_bound_attributes() {
+        let source = r#"<template>
+  <div class="splide" :data-autoplay="autoplay">
+    <button @click="toggle" :aria-label="playing ? 'Pause' : 'Play'">{{ icon }}</button>
+    <div v-for="(s, i) in slides" class="splide__slide" :aria-roledescription="'slide'" :aria-label="`${i + 1} of ${slides.length}`">{{ s }}</div>
+  </div>
+  <div class="glide" data-autoplay="4000">
+    <div class="glide__slide">{{ first }}</div>
+  </div>
+</template>"#;
+        let diags = check(source, FileType::Vue);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 5);
+        let related = diags[0].related_information.as_ref().unwrap();
+        assert_eq!(related.len(), 2);
+        assert_eq!(
+            related[1].message,
+            "Slide 1 of 1: add aria-roledescription=\"slide\" and aria-label=\"1 of 1\""
+        );
+    }
+
+    #[test]
+    fn test_jsx_components() {
+        let diags = check(
+            r#"const Hero = () => (
+  <Swiper autoplay={{ delay: 3000 }} modules={[Autoplay]}>
+    <SwiperSlide>One</SwiperSlide>
+    <SwiperSlide aria-label="2 of 2" aria-roledescription="slide">Two</SwiperSlide>
+  </Swiper>
+);
+const Volume = () => <Slider min={0} max={10} />;
+const Still = () => (
+  <Carousel autoPlay={false}>
+    <PauseButton />
+  </Carousel>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 1);
+        let related = diags[0].related_information.as_ref().unwrap();
+        assert_eq!(related.len(), 2);
+        assert_eq!(related[0].message, "Advances automatically (autoplay)");
+    }
+}
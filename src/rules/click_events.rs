@@ -1,6 +1,7 @@
 use crate::engine::node_to_range;
 use crate::parser::FileType;
 use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
 use tower_lsp_server::ls_types::*;
 use tree_sitter::Node;
@@ -14,6 +15,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "2.1.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/keyboard.html",
     default_severity: Severity::Error,
+    axe_rules: &[],
 };
 
 /// Elements that natively handle keyboard events and don't need explicit key handlers.
@@ -235,12 +237,12 @@ fn check_jsx_self_closing(
             tag_name = Some(source[child.byte_range()].to_string());
         }
         if child.kind() == "jsx_attribute" {
-            let attr_name = extract_jsx_attr_name(&child, source);
+            let attr_name = jsx_event_name(&child, source);
             if let Some(name) = attr_name {
-                if name == "onClick" {
+                if name == "click" {
                     has_onclick = true;
                 }
-                if name == "onKeyDown" || name == "onKeyUp" {
+                if name == "keydown" || name == "keyup" {
                     has_key_event = true;
                 }
             }
@@ -283,12 +285,12 @@ fn check_jsx_opening(
                     tag_name = Some(source[inner_child.byte_range()].to_string());
                 }
                 if inner_child.kind() == "jsx_attribute" {
-                    let attr_name = extract_jsx_attr_name(&inner_child, source);
+                    let attr_name = jsx_event_name(&inner_child, source);
                     if let Some(name) = attr_name {
-                        if name == "onClick" {
+                        if name == "click" {
                             has_onclick = true;
                         }
-                        if name == "onKeyDown" || name == "onKeyUp" {
+                        if name == "keydown" || name == "keyup" {
                             has_key_event = true;
                         }
                     }
@@ -314,14 +316,10 @@ fn check_jsx_opening(
     }
 }
 
-fn extract_jsx_attr_name(attr_node: &Node, source: &str) -> Option<String> {
-    let mut cursor = attr_node.walk();
-    for child in attr_node.children(&mut cursor) {
-        if child.kind() == "property_identifier" {
-            return Some(source[child.byte_range()].to_string());
-        }
-    }
-    None
+/// The event a JSX attribute handles, in any of the dialects
+/// [`jsx_attrs::jsx_event`] knows.
+fn jsx_event_name(attr_node: &Node, source: &str) -> Option<String> {
+    jsx_attrs::jsx_attribute(attr_node, source).and_then(|(name, _)| jsx_attrs::jsx_event(name))
 }
 
 /// The lowercased `role` attribute value declared directly on a JSX
@@ -533,6 +531,21 @@ mod tests {
         assert_eq!(diags.len(), 1);
     }
 
+    #[test]
+    fn test_tsx_solid_and_qwik_handlers() {
+        let diags = check_tsx(
+            r#"const App = () => <><div on:click={a} /><span onClick$={b} /><p oncapture:click={c} /></>;"#,
+        );
+        assert_eq!(diags.len(), 3);
+        let diags = check_tsx(
+            r#"const App = () => <><div on:click={a} on:keydown={k} /><span onClick$={b} onKeyUp$={k} /></>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+        // Qwik's page-level listeners aren't the element's.
+        let diags = check_tsx(r#"const App = () => <div document:onClick$={close} />;"#);
+        assert_eq!(diags.len(), 0);
+    }
+
     #[test]
     fn test_tsx_listbox_option_with_onclick_passes() {
         // role="option" inside role="listbox": keyboard handled by the container.
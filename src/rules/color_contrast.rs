@@ -0,0 +1,951 @@
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::css::{self, StyleRule};
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct ColorContrast;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "color-contrast",
+    description: "Text must have a contrast ratio of at least 4.5:1 (3:1 for large text)",
+    wcag_level: WcagLevel::AA,
+    wcag_criterion: "1.4.3",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/contrast-minimum.html",
+    default_severity: Severity::Warning,
+    axe_rules: &["color-contrast"],
+};
+
+const NORMAL_TEXT_RATIO: f64 = 4.5;
+const LARGE_TEXT_RATIO: f64 = 3.0;
+/// 18pt, and 14pt for bold text, in CSS pixels.
+const LARGE_TEXT_PX: f64 = 24.0;
+const LARGE_BOLD_TEXT_PX: f64 = 18.66;
+const ROOT_FONT_PX: f64 = 16.0;
+
+/// Elements browsers show bold by default.
+const BOLD_TAGS: &[&str] = &["b", "strong", "th", "h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// Color keywords that are read; any other name leaves the color unknown.
+const NAMED_COLORS: &[(&str, Rgb)] = &[
+    ("aqua", (0, 255, 255)),
+    ("black", (0, 0, 0)),
+    ("blue", (0, 0, 255)),
+    ("cyan", (0, 255, 255)),
+    ("darkgray", (169, 169, 169)),
+    ("darkgrey", (169, 169, 169)),
+    ("dimgray", (105, 105, 105)),
+    ("dimgrey", (105, 105, 105)),
+    ("fuchsia", (255, 0, 255)),
+    ("gainsboro", (220, 220, 220)),
+    ("gray", (128, 128, 128)),
+    ("green", (0, 128, 0)),
+    ("grey", (128, 128, 128)),
+    ("lightgray", (211, 211, 211)),
+    ("lightgrey", (211, 211, 211)),
+    ("lime", (0, 255, 0)),
+    ("magenta", (255, 0, 255)),
+    ("maroon", (128, 0, 0)),
+    ("navy", (0, 0, 128)),
+    ("olive", (128, 128, 0)),
+    ("orange", (255, 165, 0)),
+    ("purple", (128, 0, 128)),
+    ("red", (255, 0, 0)),
+    ("silver", (192, 192, 192)),
+    ("teal", (0, 128, 128)),
+    ("white", (255, 255, 255)),
+    ("whitesmoke", (245, 245, 245)),
+    ("yellow", (255, 255, 0)),
+];
+
+type Rgb = (u8, u8, u8);
+
+/// Where a declaration stands in the cascade: `!important`, specificity
+/// (inline, ids, classes, types) and source order.
+type Precedence = (bool, (u8, usize, usize, usize), usize);
+
+impl Rule for ColorContrast {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_config(root, source, file_type, &Config::default())
+    }
+
+    fn check_with_config(
+        &self,
+        root: &Node,
+        source: &str,
+        file_type: FileType,
+        config: &Config,
+    ) -> Vec<Diagnostic> {
+        // Configured workspace style sheets come first, as `<link>`s in the
+        // head would.
+        let mut sheets: Vec<StyleRule> = config
+            .color_contrast
+            .css
+            .iter()
+            .flat_map(|css| css::rules(css))
+            .collect();
+        for sheet in css::style_sheets(root, source, file_type) {
+            sheets.extend(css::rules(&source[sheet.byte_range()]));
+        }
+        let selectors: Vec<Vec<Selector>> = sheets
+            .iter()
+            .map(|rule| {
+                if rule.conditional {
+                    Vec::new()
+                } else {
+                    rule.selector
+                        .split(',')
+                        .filter_map(Selector::parse)
+                        .collect()
+                }
+            })
+            .collect();
+
+        let mut elements = Vec::new();
+        if file_type.is_jsx_like() {
+            collect_jsx(root, source, None, &mut elements);
+        } else {
+            collect_html(root, source, None, &mut elements);
+        }
+        for element in &mut elements {
+            element.cascade(&sheets, &selectors);
+        }
+
+        let mut diagnostics = Vec::new();
+        for (i, element) in elements.iter().enumerate() {
+            if !element.has_text {
+                continue;
+            }
+            let (Some(background), Some(foreground)) =
+                (background(&elements, i), foreground(&elements, i))
+            else {
+                continue;
+            };
+            let foreground = foreground.over(background);
+            let ratio = contrast_ratio(foreground, background);
+            let required = if is_large_text(&elements, i) {
+                LARGE_TEXT_RATIO
+            } else {
+                NORMAL_TEXT_RATIO
+            };
+            if ratio < required {
+                diagnostics.push(make_diagnostic(
+                    node_to_range(&element.tag),
+                    ratio,
+                    required,
+                    foreground,
+                    background,
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// An element with what the rule needs to know about its styles.
+struct Element<'a> {
+    /// The start tag (or JSX opening element) diagnostics point at.
+    tag: Node<'a>,
+    parent: Option<usize>,
+    name: String,
+    id: Option<String>,
+    classes: Vec<String>,
+    inline: Vec<(String, String)>,
+    /// Whether its styles can't be known from the source: a bound `style` or
+    /// `class`, a spread, or a component.
+    dynamic: bool,
+    /// Whether it has text of its own.
+    has_text: bool,
+    /// The declarations that apply to it, least important first.
+    declarations: Vec<(String, String)>,
+}
+
+impl Element<'_> {
+    /// Orders the matching style sheet declarations and its inline style by
+    /// importance, specificity and source order.
+    fn cascade(&mut self, sheets: &[StyleRule], selectors: &[Vec<Selector>]) {
+        let mut found: Vec<(Precedence, &str, &str)> = Vec::new();
+        let mut order = 0;
+        for (rule, selectors) in sheets.iter().zip(selectors) {
+            let specificity = selectors
+                .iter()
+                .filter(|s| s.matches(self))
+                .map(Selector::specificity)
+                .max();
+            for declaration in &rule.declarations {
+                order += 1;
+                if let Some((ids, classes, tags)) = specificity {
+                    let (value, important) = split_important(&declaration.value);
+                    let precedence = (important, (0, ids, classes, tags), order);
+                    found.push((precedence, &declaration.property, value));
+                }
+            }
+        }
+        for (property, value) in &self.inline {
+            order += 1;
+            let (value, important) = split_important(value);
+            found.push(((important, (1, 0, 0, 0), order), property, value));
+        }
+        found.sort_by_key(|(precedence, _, _)| *precedence);
+        self.declarations = found
+            .into_iter()
+            .map(|(_, property, value)| (property.to_string(), value.to_string()))
+            .collect();
+    }
+
+    /// The value of the last declaration that wins of any of `properties`.
+    fn value(&self, properties: &[&str]) -> Option<(&str, &str)> {
+        self.declarations
+            .iter()
+            .rev()
+            .find(|(property, _)| properties.contains(&property.as_str()))
+            .map(|(property, value)| (property.as_str(), value.as_str()))
+    }
+}
+
+/// A compound selector of type, class and id selectors, the only kind
+/// matched. Combinators, pseudo-classes and attribute selectors make the
+/// styles depend on more than the element.
+struct Selector {
+    name: Option<String>,
+    ids: Vec<String>,
+    classes: Vec<String>,
+}
+
+impl Selector {
+    fn parse(selector: &str) -> Option<Self> {
+        let selector = selector.trim();
+        let selector = if selector == ":root" {
+            "html"
+        } else {
+            selector
+        };
+        if selector.is_empty()
+            || !selector
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '#' | '*'))
+        {
+            return None;
+        }
+        let mut parsed = Selector {
+            name: None,
+            ids: Vec::new(),
+            classes: Vec::new(),
+        };
+        let mut rest = selector;
+        let name_end = rest.find(['.', '#']).unwrap_or(rest.len());
+        match &rest[..name_end] {
+            "" | "*" => {}
+            name => parsed.name = Some(name.to_string()),
+        }
+        rest = &rest[name_end..];
+        while !rest.is_empty() {
+            let end = rest[1..].find(['.', '#']).map_or(rest.len(), |i| i + 1);
+            let part = rest[1..end].to_string();
+            if part.is_empty() || part.contains('*') {
+                return None;
+            }
+            if rest.starts_with('#') {
+                parsed.ids.push(part);
+            } else {
+                parsed.classes.push(part);
+            }
+            rest = &rest[end..];
+        }
+        Some(parsed)
+    }
+
+    fn matches(&self, element: &Element) -> bool {
+        self.name.as_ref().is_none_or(|name| *name == element.name)
+            && self
+                .ids
+                .iter()
+                .all(|id| element.id.as_deref() == Some(id.as_str()))
+            && self
+                .classes
+                .iter()
+                .all(|class| element.classes.contains(class))
+    }
+
+    fn specificity(&self) -> (usize, usize, usize) {
+        (
+            self.ids.len(),
+            self.classes.len(),
+            usize::from(self.name.is_some()),
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn collect_html<'a>(
+    node: &Node<'a>,
+    source: &str,
+    parent: Option<usize>,
+    elements: &mut Vec<Element<'a>>,
+) {
+    let mut parent = parent;
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source)
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let mut element = Element {
+            tag,
+            parent,
+            name,
+            id: None,
+            classes: Vec::new(),
+            inline: Vec::new(),
+            dynamic: false,
+            has_text: false,
+            declarations: Vec::new(),
+        };
+        for attr in html_attrs::attrs(&tag, source) {
+            let styles = ["style", "class", "id"]
+                .iter()
+                .any(|name| attr.name_eq(name));
+            if !styles {
+                continue;
+            }
+            let value = attr.value.clone().unwrap_or_default();
+            if attr.bound {
+                element.dynamic = true;
+            } else if attr.name_eq("style") {
+                element.inline = css::inline_declarations(&value);
+            } else if attr.name_eq("class") {
+                element.classes = value
+                    .split_whitespace()
+                    .map(str::to_ascii_lowercase)
+                    .collect();
+            } else {
+                element.id = Some(value.trim().to_ascii_lowercase());
+            }
+        }
+        let mut cursor = node.walk();
+        element.has_text = node
+            .children(&mut cursor)
+            .any(|child| child.kind() == "text" && !source[child.byte_range()].trim().is_empty());
+        elements.push(element);
+        parent = Some(elements.len() - 1);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_html(&child, source, parent, elements);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn collect_jsx<'a>(
+    node: &Node<'a>,
+    source: &str,
+    parent: Option<usize>,
+    elements: &mut Vec<Element<'a>>,
+) {
+    let mut parent = parent;
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let mut element = Element {
+            tag,
+            parent,
+            name: name.to_string(),
+            id: None,
+            classes: Vec::new(),
+            inline: Vec::new(),
+            // A component styles what it renders its own way.
+            dynamic: !name.starts_with(|c: char| c.is_ascii_lowercase()),
+            has_text: false,
+            declarations: Vec::new(),
+        };
+        let mut cursor = tag.walk();
+        for attr in tag.named_children(&mut cursor) {
+            if attr.kind() == "jsx_expression" {
+                element.dynamic = true;
+                continue;
+            }
+            let Some((name, value)) = jsx_attrs::jsx_attribute(&attr, source) else {
+                continue;
+            };
+            let literal = value
+                .filter(|v| v.kind() == "string")
+                .map(|v| source[v.byte_range()].trim_matches(['"', '\'']));
+            match (name, literal) {
+                ("className" | "class", Some(classes)) => {
+                    element.classes = classes
+                        .split_whitespace()
+                        .map(str::to_ascii_lowercase)
+                        .collect();
+                }
+                ("id", Some(id)) => element.id = Some(id.trim().to_ascii_lowercase()),
+                ("style", _) => match value.and_then(|v| jsx_attrs::jsx_object(&v)) {
+                    Some(object) => match object_declarations(&object, source) {
+                        Some(declarations) => element.inline = declarations,
+                        None => element.dynamic = true,
+                    },
+                    None => element.dynamic = true,
+                },
+                ("className" | "class" | "id", None) => element.dynamic = true,
+                _ => {}
+            }
+        }
+        if node.kind() == "jsx_element" {
+            let mut cursor = node.walk();
+            element.has_text = node.named_children(&mut cursor).any(|child| {
+                child.kind() == "jsx_text" && !source[child.byte_range()].trim().is_empty()
+            });
+        }
+        elements.push(element);
+        parent = Some(elements.len() - 1);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_jsx(&child, source, parent, elements);
+    }
+}
+
+/// The declarations of a `style={{ … }}` object, or `None` if any of its
+/// entries isn't a literal.
+fn object_declarations(object: &Node, source: &str) -> Option<Vec<(String, String)>> {
+    let mut declarations = Vec::new();
+    let mut cursor = object.walk();
+    for entry in object.named_children(&mut cursor) {
+        if entry.kind() == "comment" {
+            continue;
+        }
+        let key = entry.child_by_field_name("key")?;
+        let value = entry
+            .child_by_field_name("value")
+            .filter(|v| matches!(v.kind(), "string" | "number"))?;
+        let key = source[key.byte_range()].trim_matches(['"', '\'']);
+        let mut property = String::new();
+        for c in key.chars() {
+            if c.is_ascii_uppercase() {
+                property.push('-');
+            }
+            property.push(c.to_ascii_lowercase());
+        }
+        let value = source[value.byte_range()].trim_matches(['"', '\'']);
+        // React adds `px` to unitless numbers.
+        let value = if value.parse::<f64>().is_ok() && property == "font-size" {
+            format!("{value}px")
+        } else {
+            value.to_ascii_lowercase()
+        };
+        declarations.push((property, value));
+    }
+    Some(declarations)
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+/// A color with its alpha.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Color {
+    rgb: Rgb,
+    alpha: f64,
+}
+
+impl Color {
+    /// This color drawn over `background`.
+    fn over(self, background: Rgb) -> Rgb {
+        let mix = |fg: u8, bg: u8| {
+            (f64::from(fg) * self.alpha + f64::from(bg) * (1.0 - self.alpha)).round() as u8
+        };
+        (
+            mix(self.rgb.0, background.0),
+            mix(self.rgb.1, background.1),
+            mix(self.rgb.2, background.2),
+        )
+    }
+}
+
+/// The text color of element `i`, if the source sets one that can be read.
+fn foreground(elements: &[Element], i: usize) -> Option<Color> {
+    let mut current = Some(i);
+    while let Some(i) = current {
+        let element = &elements[i];
+        if element.dynamic || element_opacity(element) {
+            return None;
+        }
+        if let Some((_, value)) = element.value(&["color"])
+            && !matches!(value, "inherit" | "currentcolor" | "unset")
+        {
+            // Transparent text is hidden on purpose.
+            return parse_color(value).filter(|color| color.alpha > 0.0);
+        }
+        current = element.parent;
+    }
+    None
+}
+
+/// The background color behind element `i`'s text, if the source paints one
+/// that can be read: the element's own or, where that's transparent, its
+/// ancestors'.
+fn background(elements: &[Element], i: usize) -> Option<Rgb> {
+    let element = &elements[i];
+    if element.dynamic
+        || element
+            .value(&["background-image"])
+            .is_some_and(|(_, v)| v != "none")
+    {
+        return None;
+    }
+    let color = match element.value(&["background", "background-color"]) {
+        Some(("background", value)) => {
+            if value.contains("url(") || value.contains("gradient(") || value.contains("var(") {
+                return None;
+            }
+            match shorthand_color(value) {
+                Some(color) => Some(color?),
+                None => None,
+            }
+        }
+        Some((_, value)) if !matches!(value, "inherit" | "unset" | "initial") => {
+            Some(parse_color(value)?)
+        }
+        _ => None,
+    };
+    match color {
+        Some(color) if color.alpha >= 1.0 => Some(color.rgb),
+        Some(color) if color.alpha > 0.0 => {
+            Some(color.over(background(elements, element.parent?)?))
+        }
+        _ => background(elements, element.parent?),
+    }
+}
+
+/// The color in a `background` shorthand: `None` if it has none, `Some(None)`
+/// if it has one that can't be read.
+fn shorthand_color(value: &str) -> Option<Option<Color>> {
+    if value == "none" {
+        return None;
+    }
+    // Function arguments contain spaces, so split on spaces outside them.
+    let mut tokens = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ' ' if depth == 0 => {
+                tokens.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    tokens.push(&value[start..]);
+    let token = tokens.into_iter().find(|token| {
+        token.starts_with('#')
+            || token.starts_with("rgb")
+            || token.starts_with("hsl")
+            || token == &"transparent"
+            || NAMED_COLORS.iter().any(|(name, _)| name == token)
+    });
+    match token {
+        Some(token) => Some(parse_color(token)),
+        // Positions, sizes and keywords like `no-repeat`; anything else may
+        // be a color that isn't read.
+        None if value.split_whitespace().all(|word| {
+            word.starts_with(|c: char| c.is_ascii_digit())
+                || matches!(word, "no-repeat" | "repeat" | "center" | "top" | "left")
+        }) =>
+        {
+            None
+        }
+        None => Some(None),
+    }
+}
+
+/// Whether an element is drawn see-through, which the colors can't show.
+fn element_opacity(element: &Element) -> bool {
+    element
+        .value(&["opacity"])
+        .is_some_and(|(_, value)| value.parse::<f64>().is_ok_and(|o| o < 1.0))
+}
+
+/// Whether element `i`'s text is large: 18pt, or 14pt and bold. A size
+/// that can't be read counts as large, so only the lower ratio is required.
+fn is_large_text(elements: &[Element], i: usize) -> bool {
+    match font_size(elements, i) {
+        Some(px) => px >= LARGE_TEXT_PX || (px >= LARGE_BOLD_TEXT_PX && is_bold(elements, i)),
+        None => true,
+    }
+}
+
+/// Element `i`'s font size in pixels.
+fn font_size(elements: &[Element], i: usize) -> Option<f64> {
+    let element = &elements[i];
+    let parent = || {
+        element
+            .parent
+            .map_or(Some(ROOT_FONT_PX), |p| font_size(elements, p))
+    };
+    let Some((_, value)) = element.value(&["font-size"]) else {
+        let scale = match element.name.as_str() {
+            "h1" => 2.0,
+            "h2" => 1.5,
+            "h3" => 1.17,
+            "h5" => 0.83,
+            "h6" => 0.67,
+            "small" => 0.83,
+            _ => 1.0,
+        };
+        return Some(parent()? * scale);
+    };
+    let number = |unit: &str| value.strip_suffix(unit)?.trim().parse::<f64>().ok();
+    if let Some(px) = number("px") {
+        Some(px)
+    } else if let Some(pt) = number("pt") {
+        Some(pt * 4.0 / 3.0)
+    } else if let Some(rem) = number("rem") {
+        Some(rem * ROOT_FONT_PX)
+    } else if let Some(em) = number("em") {
+        Some(em * parent()?)
+    } else if let Some(percent) = number("%") {
+        Some(percent / 100.0 * parent()?)
+    } else {
+        match value {
+            "small" => Some(13.0),
+            "medium" => Some(16.0),
+            "large" => Some(18.0),
+            "x-large" => Some(24.0),
+            "xx-large" => Some(32.0),
+            "inherit" => parent(),
+            _ => None,
+        }
+    }
+}
+
+/// Whether element `i`'s text is bold.
+fn is_bold(elements: &[Element], i: usize) -> bool {
+    let element = &elements[i];
+    let inherited = || {
+        BOLD_TAGS.contains(&element.name.as_str())
+            || element.parent.is_some_and(|p| is_bold(elements, p))
+    };
+    match element.value(&["font-weight"]).map(|(_, value)| value) {
+        Some("bold" | "bolder") => true,
+        Some("normal" | "lighter") => false,
+        Some(value) => match value.parse::<u32>() {
+            Ok(weight) => weight >= 700,
+            Err(_) => inherited(),
+        },
+        None => inherited(),
+    }
+}
+
+fn split_important(value: &str) -> (&str, bool) {
+    match value.strip_suffix("!important") {
+        Some(value) => (value.trim_end(), true),
+        None => (value, false),
+    }
+}
+
+/// A `#hex`, `rgb()`, `hsl()` or named color.
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    if value == "transparent" {
+        return Some(Color {
+            rgb: (0, 0, 0),
+            alpha: 0.0,
+        });
+    }
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if let Some((_, rgb)) = NAMED_COLORS.iter().find(|(name, _)| *name == value) {
+        return Some(Color {
+            rgb: *rgb,
+            alpha: 1.0,
+        });
+    }
+    let (function, arguments) = value.strip_suffix(')')?.split_once('(')?;
+    let arguments: Vec<&str> = arguments
+        .split([',', ' ', '/'])
+        .filter(|a| !a.is_empty())
+        .collect();
+    if arguments.len() < 3 {
+        return None;
+    }
+    let alpha = match arguments.get(3) {
+        Some(alpha) => match alpha.strip_suffix('%') {
+            Some(percent) => percent.parse::<f64>().ok()? / 100.0,
+            None => alpha.parse::<f64>().ok()?,
+        },
+        None => 1.0,
+    };
+    let rgb = match function {
+        "rgb" | "rgba" => {
+            let channel = |a: &str| match a.strip_suffix('%') {
+                Some(percent) => percent.parse::<f64>().ok().map(|p| p * 2.55),
+                None => a.parse::<f64>().ok(),
+            };
+            (
+                channel(arguments[0])?,
+                channel(arguments[1])?,
+                channel(arguments[2])?,
+            )
+        }
+        "hsl" | "hsla" => hsl_to_rgb(
+            arguments[0].trim_end_matches("deg").parse().ok()?,
+            arguments[1].strip_suffix('%')?.parse::<f64>().ok()? / 100.0,
+            arguments[2].strip_suffix('%')?.parse::<f64>().ok()? / 100.0,
+        ),
+        _ => return None,
+    };
+    let byte = |c: f64| c.round().clamp(0.0, 255.0) as u8;
+    Some(Color {
+        rgb: (byte(rgb.0), byte(rgb.1), byte(rgb.2)),
+        alpha: alpha.clamp(0.0, 1.0),
+    })
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits: Vec<u8> = match hex.len() {
+        3 | 4 => hex
+            .chars()
+            .map(|c| u8::from_str_radix(&format!("{c}{c}"), 16).ok())
+            .collect::<Option<_>>()?,
+        6 | 8 => (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect::<Option<_>>()?,
+        _ => return None,
+    };
+    Some(Color {
+        rgb: (digits[0], digits[1], digits[2]),
+        alpha: digits.get(3).map_or(1.0, |a| f64::from(*a) / 255.0),
+    })
+}
+
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (f64, f64, f64) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    ((r + m) * 255.0, (g + m) * 255.0, (b + m) * 255.0)
+}
+
+/// The WCAG contrast ratio of two colors, from 1 to 21.
+fn contrast_ratio(a: Rgb, b: Rgb) -> f64 {
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// The relative luminance of a color.
+fn luminance((r, g, b): Rgb) -> f64 {
+    let linear = |c: u8| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+fn hex((r, g, b): Rgb) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn make_diagnostic(
+    range: Range,
+    ratio: f64,
+    required: f64,
+    foreground: Rgb,
+    background: Rgb,
+) -> Diagnostic {
+    let meta = &METADATA;
+    // Rounded down, so a failing ratio never reads as the required one.
+    let ratio = (ratio * 100.0).floor() / 100.0;
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "Text contrast is {ratio}:1 ({} on {}), below the required {required}:1. {} [WCAG {} Level {:?}]",
+            hex(foreground),
+            hex(background),
+            meta.description,
+            meta.wcag_criterion,
+            meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check_with(source: &str, file_type: FileType, config: &Config) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = ColorContrast;
+        rule.check_with_config(&tree.root_node(), source, file_type, config)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check_with(source, FileType::Html, &Config::default())
+    }
+
+    #[test]
+    fn test_ratio() {
+        assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21.0).abs() < 0.01);
+        let grey = parse_color("#777").unwrap().rgb;
+        assert!((contrast_ratio(grey, (255, 255, 255)) - 4.48).abs() < 0.01);
+        assert_eq!(parse_color("rgb(255 0 0 / 50%)").unwrap().alpha, 0.5);
+        assert_eq!(
+            parse_color("hsl(0, 0%, 100%)").unwrap().rgb,
+            (255, 255, 255)
+        );
+        assert_eq!(parse_color("var(--fg)"), None);
+    }
+
+    #[test]
+    fn test_inline_styles() {
+        let diags = check_html(
+            r#"<div style="background-color: #fff"><p style="color: #999">Low</p><p style="color: #595959">Fine</p></div>"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("2.84:1 (#999999 on #ffffff)"));
+        assert!(diags[0].message.contains("required 4.5:1"));
+        assert_eq!(diags[0].range.start.character, 36);
+    }
+
+    #[test]
+    fn test_style_block() {
+        let source = r#"<style>
+  body { background: #222 }
+  .muted { color: #555 }
+  .muted.ok { color: #ddd !important }
+  @media (prefers-color-scheme: light) { body { background: white } }
+</style>
+<body><span class="muted">Low</span><span class="muted ok">Fine</span></body>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 6);
+        assert!(diags[0].message.contains("#555555 on #222222"));
+    }
+
+    #[test]
+    fn test_large_text_needs_less() {
+        // #949494 on white is about 3.03:1.
+        let source = r#"<div style="background:#fff;color:#949494"><h1>Title</h1><p style="font-size: 14pt; font-weight: bold">Bold</p><p>Body</p></div>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("required 4.5:1"));
+        assert_eq!(diags[0].range.start.character, 111);
+    }
+
+    #[test]
+    fn test_unknown_colors_pass() {
+        assert!(check_html(r#"<p style="color: #999">No background</p>"#).is_empty());
+        assert!(
+            check_html(
+                r#"<div style="background: url(a.png) #fff"><p style="color:#999">x</p></div>"#
+            )
+            .is_empty()
+        );
+        assert!(
+            check_html(
+                r#"<div style="background:#fff"><p style="color: var(--muted)">x</p></div>"#
+            )
+            .is_empty()
+        );
+        assert!(
+            check_with(
+                r#"<div :style="s" style="background:#fff;color:#999">x</div>"#,
+                FileType::Vue,
+                &Config::default()
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_selectors_with_combinators_are_skipped() {
+        let source = r#"<style>div > .x { background: #000 } .X { color: #aaa }</style>
+<div style="background:#fff"><div class="x y">Text<p style="color:#999">x</p></div></div>"#;
+        assert_eq!(check_html(source).len(), 2);
+    }
+
+    #[test]
+    fn test_workspace_stylesheets() {
+        let config = Config {
+            color_contrast: crate::config::ColorContrastConfig {
+                stylesheets: vec!["theme.css".to_string()],
+                css: vec!["html { background: #000 } p { color: #333 }".to_string()],
+            },
+            ..Config::default()
+        };
+        let diags = check_with(
+            "<html><body><p>Hi</p></body></html>",
+            FileType::Html,
+            &config,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(check_html("<html><body><p>Hi</p></body></html>").is_empty());
+    }
+
+    #[test]
+    fn test_tsx() {
+        let source = r##"const A = () => (
+  <div style={{ backgroundColor: "#fff" }}>
+    <span style={{ color: "#aaa" }}>Low</span>
+    <span style={{ color: "#949494", fontSize: 32 }}>Large</span>
+    <span style={{ color: muted }}>Unknown</span>
+    <Card style={{ color: "#aaa" }}>Component</Card>
+    <span className="x" style={{ color: "rgba(0, 0, 0, 0.3)" }}>Faint</span>
+  </div>
+);"##;
+        let diags = check_with(source, FileType::Tsx, &Config::default());
+        let lines: Vec<u32> = diags.iter().map(|d| d.range.start.line).collect();
+        assert_eq!(lines, vec![2, 6]);
+        assert!(diags[1].message.contains("#b3b3b3 on #ffffff"));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct CompositeFocus;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "composite-focus",
+    description: "Composite widgets must manage focus with aria-activedescendant or a roving tabIndex",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/keyboard.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Container roles whose items are reached with arrow keys rather than Tab.
+const COMPOSITE_ROLES: &[&str] = &["listbox", "menu", "menubar", "tree", "treegrid"];
+
+impl Rule for CompositeFocus {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Focus management usually lives in hooks or child components this rule
+    /// can't see, so it only runs when enabled explicitly.
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        // The HTML-grammar file types have no code to manage focus in.
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag
+        && let Some(role) = jsx_attr_value(&tag, source, "role")
+        && COMPOSITE_ROLES.contains(&role.as_str())
+        && !manages_focus(node, &tag, source)
+    {
+        diagnostics.push(make_diagnostic(node, &role));
+    }
+
+    // Recurse into children
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, diagnostics);
+    }
+}
+
+/// Whether the widget shows any sign of focus management: an
+/// `aria-activedescendant` on the container, a `tabIndex` on one of its items,
+/// or a spread whose props we can't see (e.g. `{...getItemProps()}`).
+fn manages_focus(element: &Node, tag: &Node, source: &str) -> bool {
+    if has_jsx_attr(tag, source, "aria-activedescendant") || has_spread(tag) {
+        return true;
+    }
+    if element.kind() == "jsx_self_closing_element" {
+        return false;
+    }
+    let mut cursor = element.walk();
+    element
+        .children(&mut cursor)
+        .filter(|c| c.kind() != "jsx_opening_element")
+        .any(|c| items_manage_focus(&c, source))
+}
+
+fn items_manage_focus(node: &Node, source: &str) -> bool {
+    if (node.kind() == "jsx_opening_element" || node.kind() == "jsx_self_closing_element")
+        && (has_jsx_attr(node, source, "tabIndex") || has_spread(node))
+    {
+        return true;
+    }
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .any(|c| items_manage_focus(&c, source))
+}
+
+fn has_spread(tag: &Node) -> bool {
+    let mut cursor = tag.walk();
+    tag.children(&mut cursor)
+        .any(|c| c.kind() == "jsx_expression")
+}
+
+fn has_jsx_attr(tag: &Node, source: &str, name: &str) -> bool {
+    let mut cursor = tag.walk();
+    tag.children(&mut cursor)
+        .filter(|c| c.kind() == "jsx_attribute")
+        .any(|attr| {
+            attr.child(0)
+                .is_some_and(|n| &source[n.byte_range()] == name)
+        })
+}
+
+fn jsx_attr_value(tag: &Node, source: &str, name: &str) -> Option<String> {
+    let mut cursor = tag.walk();
+    let attr = tag
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "jsx_attribute")
+        .find(|attr| {
+            attr.child(0)
+                .is_some_and(|n| &source[n.byte_range()] == name)
+        })?;
+    let mut attr_cursor = attr.walk();
+    let value = attr
+        .children(&mut attr_cursor)
+        .find(|c| c.kind() == "string")?;
+    let raw = &source[value.byte_range()];
+    Some(raw.trim_matches('"').trim_matches('\'').to_string())
+}
+
+fn make_diagnostic(node: &Node, role: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "role=\"{}\" has no visible focus management: set aria-activedescendant on the container, or give the active item tabIndex={{0}} and the rest tabIndex={{-1}}. {} [WCAG {} Level {:?}]",
+            role, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = CompositeFocus;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = CompositeFocus;
+        rule.check(&tree.root_node(), source, FileType::Html)
+    }
+
+    #[test]
+    fn test_is_opt_in() {
+        assert!(CompositeFocus.opt_in());
+    }
+
+    #[test]
+    fn test_listbox_without_focus_management_fails() {
+        let diags = check_tsx(
+            r#"const L = () => <ul role="listbox">{items.map(i => <li role="option">{i}</li>)}</ul>;"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("role=\"listbox\""));
+    }
+
+    #[test]
+    fn test_activedescendant_passes() {
+        let diags = check_tsx(
+            r#"const L = () => <ul role="listbox" tabIndex={0} aria-activedescendant={activeId}><li role="option" id="a">A</li></ul>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_roving_tabindex_passes() {
+        let diags = check_tsx(
+            r#"const M = () => <div role="menu">{items.map((i, n) => <div role="menuitem" tabIndex={n === active ? 0 : -1}>{i}</div>)}</div>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_spread_props_pass() {
+        let diags = check_tsx(
+            r#"const T = () => <ul role="tree" {...getTreeProps()}><li role="treeitem">A</li></ul>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_empty_self_closing_menu_fails() {
+        let diags = check_tsx(r#"const M = () => <div role="menu" />;"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_non_composite_role_ignored() {
+        let diags = check_tsx(r#"const L = () => <ul role="list"><li>A</li></ul>;"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_html_ignored() {
+        let diags = check_html(r#"<ul role="listbox"><li role="option">A</li></ul>"#);
+        assert_eq!(diags.len(), 0);
+    }
+}
@@ -0,0 +1,372 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct ConsistentNavLabel;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "consistent-nav-label",
+    description: "The same navigation should have the same label on every page",
+    wcag_level: WcagLevel::AA,
+    wcag_criterion: "3.2.3",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/consistent-navigation.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Router link components, whose target is in `to` or `href`.
+const LINK_COMPONENTS: &[&str] = &[
+    "Link",
+    "NavLink",
+    "NuxtLink",
+    "RouterLink",
+    "nuxt-link",
+    "router-link",
+];
+
+impl Rule for ConsistentNavLabel {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Comparing files takes a second pass over the workspace, and the
+    /// heuristic can pair up navigations that merely share their links.
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    /// A single file has nothing to compare with; the workspace index
+    /// ([`crate::nav_index`]) collects [`landmarks`] and reports the
+    /// differences.
+    fn check(&self, _root: &Node, _source: &str, _file_type: FileType) -> Vec<Diagnostic> {
+        Vec::new()
+    }
+}
+
+/// A navigation landmark with a statically known label and links.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavLandmark {
+    /// The `aria-label`, whitespace collapsed; `None` when there is none.
+    pub label: Option<String>,
+    /// The distinct link targets in it, sorted. Two landmarks with the same
+    /// targets are taken to be the same navigation.
+    pub links: Vec<String>,
+    /// The landmark's opening tag.
+    pub range: Range,
+}
+
+/// The `<nav>` and `role="navigation"` landmarks of a file with at least two
+/// static links. Landmarks named with `aria-labelledby` or a bound label are
+/// left out, since their label isn't known.
+pub fn landmarks(root: &Node, source: &str, file_type: FileType) -> Vec<NavLandmark> {
+    let mut landmarks = Vec::new();
+    if file_type.is_jsx_like() {
+        visit_jsx(root, source, &mut landmarks);
+    } else {
+        visit_html(root, source, &mut landmarks);
+    }
+    landmarks
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, landmarks: &mut Vec<NavLandmark>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source).unwrap_or_default();
+        let attrs = html_attrs::attrs(&tag, source);
+        let role = attrs.iter().find(|a| a.name_eq("role") && !a.bound);
+        let is_nav = name.eq_ignore_ascii_case("nav")
+            || role
+                .and_then(|a| a.value.as_deref())
+                .is_some_and(is_navigation_role);
+        if is_nav {
+            if attrs.iter().any(|a| a.name_eq("aria-labelledby")) {
+                return;
+            }
+            let label = match attrs.iter().find(|a| a.name_eq("aria-label")) {
+                Some(attr) if attr.bound => return,
+                Some(attr) => attr.value.as_deref().and_then(normalize_label),
+                None => None,
+            };
+            let mut links = Vec::new();
+            collect_html_links(node, source, &mut links);
+            push_landmark(landmarks, label, links, &tag);
+            return;
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, landmarks);
+    }
+}
+
+fn collect_html_links(node: &Node, source: &str, links: &mut Vec<String>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source).unwrap_or_default();
+        if name.eq_ignore_ascii_case("a") || LINK_COMPONENTS.contains(&name) {
+            let target = html_attrs::attrs(&tag, source)
+                .into_iter()
+                .find(|a| a.name_eq("href") || a.name_eq("to"))
+                .filter(|a| !a.bound)
+                .and_then(|a| a.value);
+            links.extend(target.filter(|t| is_static_target(t)));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_html_links(&child, source, links);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, landmarks: &mut Vec<NavLandmark>) {
+    if let Some(tag) = jsx_tag(node) {
+        let name = jsx_name(&tag, source);
+        let role = jsx_attr(&tag, source, "role");
+        let is_nav = name == "nav"
+            || role
+                .and_then(|r| r)
+                .as_deref()
+                .is_some_and(is_navigation_role);
+        if is_nav {
+            if jsx_attr(&tag, source, "aria-labelledby").is_some() {
+                return;
+            }
+            let label = match jsx_attr(&tag, source, "aria-label") {
+                Some(None) => return,
+                Some(Some(value)) => normalize_label(&value),
+                None => None,
+            };
+            let mut links = Vec::new();
+            collect_jsx_links(node, source, &mut links);
+            push_landmark(landmarks, label, links, &tag);
+            return;
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, landmarks);
+    }
+}
+
+fn collect_jsx_links(node: &Node, source: &str, links: &mut Vec<String>) {
+    if let Some(tag) = jsx_tag(node) {
+        let name = jsx_name(&tag, source);
+        if name == "a" || LINK_COMPONENTS.contains(&name) {
+            let target = jsx_attr(&tag, source, "href")
+                .or_else(|| jsx_attr(&tag, source, "to"))
+                .flatten();
+            links.extend(target.filter(|t| is_static_target(t)));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_jsx_links(&child, source, links);
+    }
+}
+
+/// The opening tag of a `jsx_element`, or a `jsx_self_closing_element`.
+fn jsx_tag<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    }
+}
+
+fn jsx_name<'s>(tag: &Node, source: &'s str) -> &'s str {
+    tag.child_by_field_name("name")
+        .map_or("", |n| &source[n.byte_range()])
+}
+
+/// Attribute `name` on a JSX tag: `Some(Some(value))` for a string (or
+/// `{"string"}`), `Some(None)` for any other value, `None` if it's missing.
+fn jsx_attr(tag: &Node, source: &str, name: &str) -> Option<Option<String>> {
+    let mut cursor = tag.walk();
+    let attr = tag.named_children(&mut cursor).find(|attr| {
+        attr.kind() == "jsx_attribute"
+            && attr
+                .named_child(0)
+                .is_some_and(|n| &source[n.byte_range()] == name)
+    })?;
+    let mut value = attr.named_child(1);
+    if let Some(expr) = value.filter(|v| v.kind() == "jsx_expression") {
+        value = expr.named_child(0);
+    }
+    Some(
+        value
+            .filter(|v| v.kind() == "string")
+            .map(|v| source[v.byte_range()].trim_matches(['"', '\'']).to_string()),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn is_navigation_role(role: &str) -> bool {
+    role.split_whitespace()
+        .next()
+        .is_some_and(|r| r.eq_ignore_ascii_case("navigation"))
+}
+
+fn normalize_label(label: &str) -> Option<String> {
+    let label = label.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!label.is_empty()).then_some(label)
+}
+
+/// Targets that are the same on every page: not empty, not a fragment of
+/// the current page, and not filled in by a template.
+fn is_static_target(target: &str) -> bool {
+    let target = target.trim();
+    !target.is_empty()
+        && !target.starts_with('#')
+        && !["{{", "{%", "{!!", "<%", "<?", "${"]
+            .iter()
+            .any(|marker| target.contains(marker))
+}
+
+fn push_landmark(
+    landmarks: &mut Vec<NavLandmark>,
+    label: Option<String>,
+    mut links: Vec<String>,
+    tag: &Node,
+) {
+    links.sort();
+    links.dedup();
+    if links.len() >= 2 {
+        landmarks.push(NavLandmark {
+            label,
+            links,
+            range: node_to_range(tag),
+        });
+    }
+}
+
+/// A diagnostic on `landmark`, whose label differs from `expected`, the
+/// label the same navigation has in `others` other files.
+pub fn make_diagnostic(
+    landmark: &NavLandmark,
+    expected: Option<&str>,
+    others: usize,
+    related: Vec<DiagnosticRelatedInformation>,
+) -> Diagnostic {
+    let meta = &METADATA;
+    let quoted = |label: Option<&str>| match label {
+        Some(label) => format!("labelled \"{label}\""),
+        None => "unlabelled".to_string(),
+    };
+    let files = if others == 1 { "file" } else { "files" };
+    Diagnostic {
+        range: landmark.range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "This navigation is {} here but {} in {} other {}. {} [WCAG {} Level {:?}]",
+            quoted(landmark.label.as_deref()),
+            quoted(expected),
+            others,
+            files,
+            meta.description,
+            meta.wcag_criterion,
+            meta.wcag_level
+        ),
+        related_information: Some(related),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn landmarks_of(source: &str, file_type: FileType) -> Vec<NavLandmark> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        landmarks(&tree.root_node(), source, file_type)
+    }
+
+    #[test]
+    fn test_is_opt_in_and_checks_nothing_alone() {
+        assert!(ConsistentNavLabel.opt_in());
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let source = r#"<nav aria-label="Main"><a href="/">Home</a><a href="/blog">Blog</a></nav>"#;
+        let tree = parser.parse(source, None).unwrap();
+        let diags = ConsistentNavLabel.check(&tree.root_node(), source, FileType::Html);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_html_landmarks() {
+        let found = landmarks_of(
+            r##"<header>
+<nav aria-label="  Main   menu ">
+  <a href="/blog">Blog</a><a href="/">Home</a><a href="/blog">Posts</a><a href="#top">Top</a>
+</nav>
+<div role="navigation"><a href="/a">A</a><a href="/b">B</a></div>
+</header>"##,
+            FileType::Html,
+        );
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].label.as_deref(), Some("Main menu"));
+        assert_eq!(found[0].links, ["/", "/blog"]);
+        assert_eq!(found[0].range.start.line, 1);
+        assert_eq!(found[1].label, None);
+    }
+
+    #[test]
+    fn test_unknown_labels_and_few_links_skipped() {
+        let found = landmarks_of(
+            r#"<template>
+<nav aria-labelledby="h"><a href="/">Home</a><a href="/x">X</a></nav>
+<nav :aria-label="label"><a href="/">Home</a><a href="/x">X</a></nav>
+<nav aria-label="Pages"><a href="/">Home</a><a :href="next">Next</a></nav>
+<nav aria-label="Main"><router-link to="/">Home</router-link><NuxtLink to="/about">About</NuxtLink></nav>
+</template>"#,
+            FileType::Vue,
+        );
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].label.as_deref(), Some("Main"));
+        assert_eq!(found[0].links, ["/", "/about"]);
+    }
+
+    #[test]
+    fn test_jsx_landmarks() {
+        let found = landmarks_of(
+            r#"const Header = () => (
+  <>
+    <nav aria-label={"Primary"}><Link href="/">Home</Link><NavLink to="/docs">Docs</NavLink></nav>
+    <nav aria-label={label}><a href="/">Home</a><a href="/docs">Docs</a></nav>
+    <ul role="navigation"><li><a href="/a">A</a></li><li><a href={`/b`}>B</a></li><li><a href="/c">C</a></li></ul>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].label.as_deref(), Some("Primary"));
+        assert_eq!(found[0].links, ["/", "/docs"]);
+        assert_eq!(found[1].label, None);
+        assert_eq!(found[1].links, ["/a", "/c"]);
+    }
+}
@@ -0,0 +1,237 @@
+//! CSS written into the page: `<style>` blocks, and styled-jsx
+//! `<style>{`…`}</style>` template strings in JSX.
+//!
+//! There's no CSS grammar here, only a scanner good enough for the rules
+//! that read style sheets: it splits the text on braces and semicolons into
+//! rules and their declarations, keeping where each declaration starts so a
+//! diagnostic can point at it.
+
+use crate::parser::FileType;
+use tower_lsp_server::ls_types::{Position, Range};
+use tree_sitter::Node;
+
+/// A rule with declarations, under the innermost selector (or at-rule
+/// prelude) it's written in.
+#[derive(Debug)]
+pub struct StyleRule {
+    /// Lowercased, with whitespace collapsed.
+    pub selector: String,
+    /// Whether it's inside an at-rule (`@media`, `@supports`), so it only
+    /// applies some of the time.
+    pub conditional: bool,
+    pub declarations: Vec<Declaration>,
+}
+
+#[derive(Debug)]
+pub struct Declaration {
+    /// Lowercased and trimmed.
+    pub property: String,
+    pub value: String,
+    /// Byte offset and length of the whole declaration in the style sheet.
+    pub offset: usize,
+    pub len: usize,
+}
+
+/// The style sheets of the file, as the nodes holding their text.
+pub fn style_sheets<'a>(root: &Node<'a>, source: &str, file_type: FileType) -> Vec<Node<'a>> {
+    let mut sheets = Vec::new();
+    if file_type.is_jsx_like() {
+        collect_jsx(root, source, &mut sheets);
+    } else {
+        collect_html(root, &mut sheets);
+    }
+    sheets
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+/// The `raw_text` of every `<style>` element.
+fn collect_html<'a>(node: &Node<'a>, sheets: &mut Vec<Node<'a>>) {
+    if node.kind() == "style_element" {
+        let mut cursor = node.walk();
+        sheets.extend(
+            node.children(&mut cursor)
+                .filter(|child| child.kind() == "raw_text"),
+        );
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_html(&child, sheets);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+/// The template literal of every `<style>{`…`}</style>`, as written for
+/// styled-jsx.
+fn collect_jsx<'a>(node: &Node<'a>, source: &str, sheets: &mut Vec<Node<'a>>) {
+    if node.kind() == "jsx_element"
+        && node
+            .child(0)
+            .and_then(|open| open.child_by_field_name("name"))
+            .is_some_and(|name| &source[name.byte_range()] == "style")
+    {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            let mut expr_cursor = child.walk();
+            if child.kind() == "jsx_expression" {
+                sheets.extend(
+                    child
+                        .named_children(&mut expr_cursor)
+                        .filter(|expr| expr.kind() == "template_string"),
+                );
+            }
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_jsx(&child, source, sheets);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Scanning
+// ---------------------------------------------------------------------------
+
+/// `css` with its comments blanked, keeping every other byte in place.
+pub fn strip_comments(css: &str) -> String {
+    let mut out = css.as_bytes().to_vec();
+    let mut pos = 0;
+    while let Some(start) = css[pos..].find("/*").map(|i| pos + i) {
+        let end = css[start + 2..]
+            .find("*/")
+            .map_or(css.len(), |i| start + 2 + i + 2);
+        for b in &mut out[start..end] {
+            if *b != b'\n' {
+                *b = b' ';
+            }
+        }
+        pos = end;
+    }
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// The declarations of an inline `style`, property and value lowercased.
+pub fn inline_declarations(style: &str) -> Vec<(String, String)> {
+    style
+        .split(';')
+        .filter_map(|decl| {
+            let (property, value) = decl.split_once(':')?;
+            Some((
+                property.trim().to_ascii_lowercase(),
+                value.trim().to_ascii_lowercase(),
+            ))
+        })
+        .collect()
+}
+
+/// The rules of `css` that have declarations, in order. A rule nested in
+/// another (`@media`, CSS nesting) comes before the rest of its parent.
+pub fn rules(css: &str) -> Vec<StyleRule> {
+    let css = strip_comments(css);
+    let mut finished = Vec::new();
+    // The rules whose blocks are open, innermost last.
+    let mut open: Vec<StyleRule> = Vec::new();
+    let mut start = 0;
+    for (i, c) in css.char_indices() {
+        if !matches!(c, '{' | '}' | ';') {
+            continue;
+        }
+        let segment = &css[start..i];
+        if c == '{' {
+            let conditional = open.iter().any(|rule| rule.selector.starts_with('@'));
+            open.push(StyleRule {
+                selector: segment
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .to_ascii_lowercase(),
+                conditional,
+                declarations: Vec::new(),
+            });
+        } else if let Some(rule) = open.last_mut() {
+            let declaration = segment.trim_start();
+            if let Some((property, value)) = declaration.split_once(':') {
+                rule.declarations.push(Declaration {
+                    property: property.trim().to_ascii_lowercase(),
+                    value: value.trim().to_ascii_lowercase(),
+                    offset: start + (segment.len() - declaration.len()),
+                    len: declaration.trim_end().len(),
+                });
+            }
+        }
+        if c == '}'
+            && let Some(rule) = open.pop()
+            && !rule.declarations.is_empty()
+        {
+            finished.push(rule);
+        }
+        start = i + 1;
+    }
+    finished
+}
+
+/// The range in `source` of a declaration of the style sheet held by
+/// `sheet`.
+pub fn declaration_range(source: &str, sheet: &Node, declaration: &Declaration) -> Range {
+    let start = sheet.start_byte() + declaration.offset;
+    Range {
+        start: position_at(source, start),
+        end: position_at(source, start + declaration.len),
+    }
+}
+
+/// The position of byte `offset` of `source`.
+fn position_at(source: &str, offset: usize) -> Position {
+    let before = &source[..offset];
+    let line = before.matches('\n').count();
+    let column = before.rfind('\n').map_or(offset, |i| offset - i - 1);
+    Position::new(line as u32, column as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_and_declarations() {
+        let css = "a:focus,\n  button { outline: NONE; color: red }\n/* b { x: y } */\n@media print { .x { display: none; } }";
+        let rules = rules(css);
+        let summary: Vec<(&str, Vec<(&str, &str)>)> = rules
+            .iter()
+            .map(|r| {
+                let decls = r
+                    .declarations
+                    .iter()
+                    .map(|d| (d.property.as_str(), d.value.as_str()))
+                    .collect();
+                (r.selector.as_str(), decls)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "a:focus, button",
+                    vec![("outline", "none"), ("color", "red")]
+                ),
+                (".x", vec![("display", "none")]),
+            ]
+        );
+        let first = &rules[0].declarations[0];
+        assert_eq!(
+            &css[first.offset..first.offset + first.len],
+            "outline: NONE"
+        );
+        assert!(!rules[0].conditional);
+        assert!(rules[1].conditional);
+    }
+}
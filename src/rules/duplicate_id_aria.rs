@@ -0,0 +1,177 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct DuplicateIdAria;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "duplicate-id-aria",
+    description: "ids referenced by ARIA, labels or table headers must be unique",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Error,
+    axe_rules: &["duplicate-id-aria"],
+};
+
+/// Attributes that point at other elements by id. `no-duplicate-id` reports
+/// the duplicates themselves; this reports the references they break.
+const REFERENCE_ATTRS: &[&str] = &[
+    "aria-controls",
+    "aria-describedby",
+    "aria-labelledby",
+    "for",
+    "headers",
+    "htmlFor",
+];
+
+impl Rule for DuplicateIdAria {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Each story renders on its own, so ids may repeat across stories.
+    fn page_level(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        let elements = analysis.elements();
+        let ids = analysis.ids();
+        let mut diagnostics = Vec::new();
+        for element in elements {
+            for attr in &element.attrs {
+                if attr.bound || !REFERENCE_ATTRS.iter().any(|n| attr.name_eq(n)) {
+                    continue;
+                }
+                let Some(value) = attr.value.as_deref() else {
+                    continue;
+                };
+                for id in value.split_whitespace() {
+                    let Some(owners) = ids.get(id).filter(|owners| owners.len() > 1) else {
+                        continue;
+                    };
+                    let lines: Vec<String> = owners
+                        .iter()
+                        .map(|&o| (elements[o].tag.start_position().row + 1).to_string())
+                        .collect();
+                    diagnostics.push(make_diagnostic(
+                        &attr.node,
+                        &format!(
+                            "{} points at id \"{id}\", which {} elements have (lines {}): only the first is used",
+                            attr.name,
+                            owners.len(),
+                            lines.join(", ")
+                        ),
+                    ));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = DuplicateIdAria;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_unique_referenced_ids_pass() {
+        let source = r#"<h2 id="title">Billing</h2>
+<section aria-labelledby="title" aria-describedby="note"><p id="note">All fields required</p></section>
+<label for="email">Email</label><input id="email">
+<div id="dup"></div><div id="dup"></div>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_referenced_duplicates_fail() {
+        let source = r#"<span id="name">Billing</span>
+<span id="name">Shipping</span>
+<div role="group" aria-labelledby="name"></div>
+<label for="email">Email</label><input id="email"><input id="email">
+<table>
+  <tr><th id="h">Price</th><th id="h">Tax</th></tr>
+  <tr><td headers="h">3</td></tr>
+</table>
+<button aria-controls="menu other">Menu</button><ul id="menu"></ul><ul id="menu"></ul>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 4);
+        assert!(diags[0].message.starts_with(
+            "aria-labelledby points at id \"name\", which 2 elements have (lines 1, 2): only the first is used"
+        ));
+        assert_eq!(diags[0].range.start.line, 2);
+        assert!(diags[1].message.starts_with("for points at id \"email\""));
+        assert!(diags[2].message.starts_with("headers points at id \"h\""));
+        assert!(
+            diags[3]
+                .message
+                .starts_with("aria-controls points at id \"menu\"")
+        );
+    }
+
+    #[test]
+    fn test_vue_bound_references_skipped() {
+        let source = r#"<template>
+  <p id="hint">One</p>
+  <p id="hint">Two</p>
+  <input :aria-describedby="hintId">
+</template>"#;
+        assert!(check(source, FileType::Vue).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Form = () => (
+  <form>
+    <label htmlFor="q">Search</label>
+    <input id="q" />
+    <input id="q" aria-describedby={hintId} />
+  </form>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert!(diags[0].message.starts_with("htmlFor points at id \"q\""));
+    }
+}
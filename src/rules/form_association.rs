@@ -0,0 +1,298 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct FormAssociation;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "form-association",
+    description: "Submit buttons and text inputs should belong to a form",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/keyboard.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Input types that submit their form when Enter is pressed in them.
+const IMPLICIT_SUBMIT_TYPES: &[&str] = &[
+    "date",
+    "datetime-local",
+    "email",
+    "month",
+    "number",
+    "password",
+    "search",
+    "tel",
+    "text",
+    "time",
+    "url",
+    "week",
+];
+
+impl Rule for FormAssociation {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// A component is often rendered inside a form that another file
+    /// provides, and script may handle Enter itself, so this only runs when
+    /// enabled explicitly.
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, false, &mut diagnostics);
+        } else {
+            visit_html(root, source, false, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, mut in_form: bool, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source).unwrap_or_default();
+        if is_form_tag(name) {
+            in_form = true;
+        } else if !in_form {
+            let attrs = html_attrs::attrs(&tag, source);
+            let input_type = attrs.iter().find(|a| a.name_eq("type"));
+            // A bound `:type` could be anything, and a bound `:form` still
+            // associates the control.
+            if !input_type.is_some_and(|a| a.bound)
+                && !attrs.iter().any(|a| a.name_eq("form"))
+                && let Some(kind) = control_kind(name, input_type.and_then(|a| a.value.as_deref()))
+            {
+                diagnostics.push(make_diagnostic(&tag, kind));
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, in_form, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, mut in_form: bool, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        if is_form_tag(name) {
+            in_form = true;
+        } else if !in_form {
+            check_jsx_tag(&tag, name, source, diagnostics);
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, in_form, diagnostics);
+    }
+}
+
+fn check_jsx_tag(tag: &Node, name: &str, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    // Components decide for themselves what they render.
+    if !name.starts_with(|c: char| c.is_ascii_lowercase()) {
+        return;
+    }
+    let mut input_type = None;
+    let mut cursor = tag.walk();
+    for child in tag.children(&mut cursor) {
+        match child.kind() {
+            // `{...props}` may well carry `form` or `type`.
+            "jsx_expression" => return,
+            "jsx_attribute" => {
+                let Some(attr_name) = child.named_child(0) else {
+                    continue;
+                };
+                match &source[attr_name.byte_range()] {
+                    "form" => return,
+                    "type" => match child.named_child(1) {
+                        Some(value) if value.kind() == "string" => {
+                            input_type = Some(source[value.byte_range()].trim_matches(['"', '\'']));
+                        }
+                        // `type={kind}` could be anything.
+                        Some(_) => return,
+                        None => {}
+                    },
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(kind) = control_kind(name, input_type) {
+        diagnostics.push(make_diagnostic(tag, kind));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+/// `<form>`, a component named `Form` (`<Form>`, `<Formik.Form>`), or a UI
+/// library's form element (`<el-form>`, `<v-form>`).
+fn is_form_tag(name: &str) -> bool {
+    name.eq_ignore_ascii_case("form")
+        || name.ends_with(".Form")
+        || name.to_ascii_lowercase().ends_with("-form")
+}
+
+/// What kind of control `name` with `type` is, if it's one that needs a
+/// form: a submit button or a text input.
+fn control_kind(name: &str, input_type: Option<&str>) -> Option<&'static str> {
+    let input_type = input_type.map(str::to_ascii_lowercase);
+    match name.to_ascii_lowercase().as_str() {
+        "button" if input_type.as_deref() == Some("submit") => Some("submit button"),
+        "input" => match input_type.as_deref().unwrap_or("text") {
+            "submit" | "image" => Some("submit button"),
+            t if IMPLICIT_SUBMIT_TYPES.contains(&t) => Some("text input"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn make_diagnostic(node: &Node, kind: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "This {} is outside any <form> and has no form attribute, so pressing Enter won't submit it. {} [WCAG {} Level {:?}]",
+            kind, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = FormAssociation;
+        rule.check(&tree.root_node(), source, FileType::Html)
+    }
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = FormAssociation;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    fn check_vue(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Vue).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = FormAssociation;
+        rule.check(&tree.root_node(), source, FileType::Vue)
+    }
+
+    #[test]
+    fn test_is_opt_in() {
+        assert!(FormAssociation.opt_in());
+    }
+
+    #[test]
+    fn test_orphaned_input_and_submit_fail() {
+        let diags =
+            check_html(r#"<input type="search" name="q"><button type="submit">Search</button>"#);
+        assert_eq!(diags.len(), 2);
+        assert!(diags[0].message.contains("This text input is outside"));
+        assert!(diags[1].message.contains("This submit button is outside"));
+        assert_eq!(diags[1].range.start.character, 30);
+    }
+
+    #[test]
+    fn test_input_without_type_is_text() {
+        let diags = check_html(r#"<input name="q">"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_controls_inside_form_pass() {
+        let diags = check_html(
+            r#"<form action="/search"><div><input name="q"><input type="submit" value="Go"></div></form>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_form_attribute_passes() {
+        let diags = check_html(
+            r#"<form id="f"></form><input name="q" form="f"><button type="submit" form="f">Go</button>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_other_controls_ignored() {
+        let diags = check_html(
+            r#"<button>Menu</button><button type="button">Close</button><input type="checkbox"><input type="hidden" name="t"><select></select><textarea></textarea>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_library_form_and_bound_type() {
+        let diags = check_vue(
+            r#"<template><el-form><input v-model="q"></el-form><input :type="t"><input :form="id"><input type="email"></template>"#,
+        );
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_tsx_orphaned_input_fails() {
+        let diags = check_tsx(r#"const S = () => <div><input type="text" onChange={f} /></div>;"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_tsx_form_components_pass() {
+        let diags = check_tsx(
+            r#"const S = () => (<><form><input /></form><Form><input /></Form><Formik.Form><button type="submit">Go</button></Formik.Form></>);"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_dynamic_props_and_components_ignored() {
+        let diags = check_tsx(
+            r#"const S = () => (<><input {...field} /><input type={kind} /><input form="f" /><Input /></>);"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+}
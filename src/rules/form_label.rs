@@ -1,5 +1,6 @@
 use crate::engine::node_to_range;
 use crate::parser::FileType;
+use crate::quickfix::{self, Fix};
 use crate::rules::html_attrs;
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
 use std::collections::HashSet;
@@ -15,6 +16,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.3.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
     default_severity: Severity::Error,
+    axe_rules: &["label"],
 };
 
 /// Tag names that require a label.
@@ -46,6 +48,25 @@ struct LabelForValues {
     has_bound_for: bool,
 }
 
+/// An unlabelled control, as far as the quick fixes need to know it.
+struct Control<'a> {
+    /// The whole element, wrapped when moving it into a `<label>`.
+    element: Node<'a>,
+    /// The node holding the tag name; a generated `id` goes right after it.
+    tag_name: Node<'a>,
+    id: ControlId,
+    /// The `name` attribute, used to derive a readable generated id.
+    name: Option<String>,
+    jsx: bool,
+}
+
+enum ControlId {
+    None,
+    Literal(String),
+    /// A JSX expression such as `{inputId}`, referenced as is.
+    Expression(String),
+}
+
 impl LabelForValues {
     fn new() -> Self {
         Self {
@@ -142,6 +163,7 @@ fn check_html_element(
     let mut has_label = false;
     let mut static_id: Option<String> = None;
     let mut id_is_bound = false;
+    let mut name_attr: Option<String> = None;
 
     for attr in &attrs {
         // A static or bound `aria-label`/`aria-labelledby`/`title` provides a
@@ -165,6 +187,9 @@ fn check_html_element(
                 static_id = attr.value.clone();
             }
         }
+        if attr.name_eq("name") && !attr.bound {
+            name_attr = attr.value.clone();
+        }
     }
 
     // Check if the element is wrapped in a <label>
@@ -186,7 +211,21 @@ fn check_html_element(
     }
 
     if !is_hidden && !has_label {
-        diags.push(make_diagnostic(element));
+        let mut cursor = tag.walk();
+        let tag_name = tag.children(&mut cursor).find(|c| c.kind() == "tag_name");
+        let fixes = tag_name
+            .map(|tag_name| {
+                let control = Control {
+                    element: *element,
+                    tag_name,
+                    id: static_id.map_or(ControlId::None, ControlId::Literal),
+                    name: name_attr,
+                    jsx: false,
+                };
+                label_fixes(&control, source)
+            })
+            .unwrap_or_default();
+        diags.push(make_diagnostic(element, fixes));
     }
 }
 
@@ -227,10 +266,10 @@ fn collect_jsx_labels(node: &Node, source: &str, values: &mut LabelForValues) {
                 }
             }
         }
-        "jsx_self_closing_element" => {
-            if jsx_tag_name(node, source).as_deref() == Some("label") {
-                collect_htmlfor_attrs(node, source, values);
-            }
+        "jsx_self_closing_element"
+            if jsx_tag_name(node, source).as_deref() == Some("label") =>
+        {
+            collect_htmlfor_attrs(node, source, values);
         }
         _ => {}
     }
@@ -319,6 +358,7 @@ fn check_jsx_self_closing(
     let mut has_label = false;
     let mut id_literal: Option<String> = None;
     let mut id_expression: Option<String> = None;
+    let mut name_attr: Option<String> = None;
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
@@ -338,6 +378,9 @@ fn check_jsx_self_closing(
                     id_literal = lit;
                     id_expression = expr;
                 }
+                if name == "name" {
+                    name_attr = jsx_attr_string_value(&child, source);
+                }
             }
         }
     }
@@ -351,7 +394,10 @@ fn check_jsx_self_closing(
     }
 
     if !is_hidden && !has_label {
-        diags.push(make_diagnostic(node));
+        let fixes = jsx_control(node, node, id_literal, id_expression, name_attr)
+            .map(|control| label_fixes(&control, source))
+            .unwrap_or_default();
+        diags.push(make_diagnostic(node, fixes));
     }
 }
 
@@ -373,6 +419,7 @@ fn check_jsx_element(
             let mut has_label = false;
             let mut id_literal: Option<String> = None;
             let mut id_expression: Option<String> = None;
+            let mut name_attr: Option<String> = None;
 
             let mut inner_cursor = child.walk();
             for inner_child in child.children(&mut inner_cursor) {
@@ -393,6 +440,9 @@ fn check_jsx_element(
                             id_literal = lit;
                             id_expression = expr;
                         }
+                        if name == "name" {
+                            name_attr = jsx_attr_string_value(&inner_child, source);
+                        }
                     }
                 }
             }
@@ -407,7 +457,10 @@ fn check_jsx_element(
             }
 
             if !is_hidden && !has_label {
-                diags.push(make_diagnostic(node));
+                let fixes = jsx_control(node, &child, id_literal, id_expression, name_attr)
+                    .map(|control| label_fixes(&control, source))
+                    .unwrap_or_default();
+                diags.push(make_diagnostic(node, fixes));
             }
         }
     }
@@ -506,6 +559,31 @@ fn jsx_attr_id_value(attr_node: &Node, source: &str) -> (Option<String>, Option<
     (literal, expression)
 }
 
+fn jsx_control<'a>(
+    element: &Node<'a>,
+    tag: &Node<'a>,
+    id_literal: Option<String>,
+    id_expression: Option<String>,
+    name: Option<String>,
+) -> Option<Control<'a>> {
+    let mut cursor = tag.walk();
+    let tag_name = tag
+        .children(&mut cursor)
+        .find(|c| c.kind() == "identifier")?;
+    let id = match (id_literal, id_expression) {
+        (Some(id), _) => ControlId::Literal(id),
+        (None, Some(expr)) => ControlId::Expression(expr),
+        (None, None) => ControlId::None,
+    };
+    Some(Control {
+        element: *element,
+        tag_name,
+        id,
+        name,
+        jsx: true,
+    })
+}
+
 /// Walk up ancestors to see if this JSX element is inside a <label>.
 fn is_inside_jsx_label(node: &Node, source: &str) -> bool {
     let mut current = node.parent();
@@ -529,9 +607,102 @@ fn is_inside_jsx_label(node: &Node, source: &str) -> bool {
 // Shared
 // ---------------------------------------------------------------------------
 
-fn make_diagnostic(node: &Node) -> Diagnostic {
+/// Two ways to label `control`: wrap it in a `<label>`, or put a
+/// `<label for>` in front of it, adding an `id` if it has none. The label text
+/// is a snippet placeholder.
+fn label_fixes(control: &Control, source: &str) -> Vec<Fix> {
+    let element = &source[control.element.byte_range()];
+    let range = node_to_range(&control.element);
+    let wrap = Fix {
+        title: "Wrap in a <label>".to_string(),
+        edits: vec![TextEdit {
+            range,
+            new_text: format!(
+                "<label>${{1:Label text}} {}</label>",
+                quickfix::snippet_escape(element)
+            ),
+        }],
+        snippet: true,
+        ..Default::default()
+    };
+
+    let for_attr = if control.jsx { "htmlFor" } else { "for" };
+    let mut edits = Vec::new();
+    let reference = match &control.id {
+        ControlId::Literal(id) => format!("{for_attr}=\"{id}\""),
+        ControlId::Expression(expr) => format!("{for_attr}={{{expr}}}"),
+        ControlId::None => {
+            let id = generated_id(control, source);
+            edits.push(quickfix::insert_attr(&control.tag_name, "id", &id));
+            format!("{for_attr}=\"{id}\"")
+        }
+    };
+    edits.insert(
+        0,
+        TextEdit {
+            range: Range {
+                start: range.start,
+                end: range.start,
+            },
+            new_text: format!(
+                "<label {}>${{1:Label text}}</label>{}",
+                quickfix::snippet_escape(&reference),
+                separator(&control.element, source)
+            ),
+        },
+    );
+    let label_for = Fix {
+        title: format!("Add a <label {for_attr}>"),
+        edits,
+        snippet: true,
+        ..Default::default()
+    };
+
+    vec![wrap, label_for]
+}
+
+/// An id for a control that has none, based on its `name` or tag and not
+/// used anywhere in the file yet.
+fn generated_id(control: &Control, source: &str) -> String {
+    let base: String = control
+        .name
+        .as_deref()
+        .unwrap_or(&source[control.tag_name.byte_range()])
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let base = format!("{}-field", base.trim_matches('-'));
+    let mut id = base.clone();
+    let mut n = 2;
+    while source.contains(&format!("\"{id}\"")) {
+        id = format!("{base}-{n}");
+        n += 1;
+    }
+    id
+}
+
+/// What goes between an inserted label and `element`: a line break with the
+/// same indentation if the element starts its line, a space otherwise.
+fn separator(element: &Node, source: &str) -> String {
+    let start = element.start_byte();
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let indent = &source[line_start..start];
+    if indent.chars().all(char::is_whitespace) {
+        format!("\n{indent}")
+    } else {
+        " ".to_string()
+    }
+}
+
+fn make_diagnostic(node: &Node, fixes: Vec<Fix>) -> Diagnostic {
     let meta = &METADATA;
-    Diagnostic {
+    let mut diagnostic = Diagnostic {
         range: node_to_range(node),
         severity: Some(DiagnosticSeverity::ERROR),
         code: Some(NumberOrString::String(meta.id.to_string())),
@@ -544,7 +715,11 @@ fn make_diagnostic(node: &Node) -> Diagnostic {
             meta.description, meta.wcag_criterion, meta.wcag_level
         ),
         ..Default::default()
+    };
+    for fix in fixes {
+        quickfix::attach(&mut diagnostic, fix);
     }
+    diagnostic
 }
 
 #[cfg(test)]
@@ -772,4 +947,83 @@ mod tests {
         );
         assert_eq!(diags.len(), 0);
     }
+
+    /// Applies fix `index` of the first diagnostic to `source`, as a client
+    /// without snippet support would.
+    fn apply_fix(source: &str, diags: &[Diagnostic], index: usize) -> String {
+        let fix = &quickfix::fixes(&diags[0])[index];
+        let offset = |pos: Position| {
+            let line_start: usize = source
+                .split_inclusive('\n')
+                .take(pos.line as usize)
+                .map(str::len)
+                .sum();
+            line_start + pos.character as usize
+        };
+        let mut result = source.to_string();
+        for edit in fix.edits.iter().rev() {
+            let range = offset(edit.range.start)..offset(edit.range.end);
+            result.replace_range(range, &quickfix::snippet_to_plain(&edit.new_text));
+        }
+        result
+    }
+
+    #[test]
+    fn test_fix_wraps_input_in_label() {
+        let source = r#"<form><input type="email"></form>"#;
+        let diags = check_html(source);
+        let fixes = quickfix::fixes(&diags[0]);
+        assert_eq!(fixes[0].title, "Wrap in a <label>");
+        assert!(fixes[0].edits[0].new_text.starts_with("<label>${1:Label text} "));
+        assert_eq!(
+            apply_fix(source, &diags, 0),
+            r#"<form><label>Label text <input type="email"></label></form>"#
+        );
+    }
+
+    #[test]
+    fn test_fix_adds_label_for_with_generated_id() {
+        let source = "<form>\n  <input type=\"email\" name=\"user[email]\">\n</form>";
+        let diags = check_html(source);
+        assert_eq!(
+            apply_fix(source, &diags, 1),
+            "<form>\n  <label for=\"user-email-field\">Label text</label>\n  <input id=\"user-email-field\" type=\"email\" name=\"user[email]\">\n</form>"
+        );
+        // The fixed document is clean.
+        assert_eq!(check_html(&apply_fix(source, &diags, 1)).len(), 0);
+    }
+
+    #[test]
+    fn test_fix_reuses_existing_id() {
+        let source = r#"<p>Mail: <textarea id="msg"></textarea></p>"#;
+        let diags = check_html(source);
+        assert_eq!(
+            apply_fix(source, &diags, 1),
+            r#"<p>Mail: <label for="msg">Label text</label> <textarea id="msg"></textarea></p>"#
+        );
+    }
+
+    #[test]
+    fn test_fix_tsx_uses_html_for_expression() {
+        let source = r#"const App = () => <div><input id={inputId} /></div>;"#;
+        let diags = check_tsx(source);
+        let fixed = apply_fix(source, &diags, 1);
+        assert_eq!(
+            fixed,
+            r#"const App = () => <div><label htmlFor={inputId}>Label text</label> <input id={inputId} /></div>;"#
+        );
+        assert_eq!(check_tsx(&fixed).len(), 0);
+    }
+
+    #[test]
+    fn test_fix_tsx_wrap_escapes_snippet_syntax() {
+        let source = r#"const App = () => <div><input value={`${a}`} /></div>;"#;
+        let diags = check_tsx(source);
+        let fixed = apply_fix(source, &diags, 0);
+        assert_eq!(
+            fixed,
+            r#"const App = () => <div><label>Label text <input value={`${a}`} /></label></div>;"#
+        );
+        assert_eq!(check_tsx(&fixed).len(), 0);
+    }
 }
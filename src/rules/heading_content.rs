@@ -14,6 +14,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "2.4.6",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/headings-and-labels.html",
     default_severity: Severity::Warning,
+    axe_rules: &["empty-heading"],
 };
 
 impl Rule for HeadingContent {
@@ -68,10 +69,11 @@ fn check_html_element(element: &Node, source: &str, diagnostics: &mut Vec<Diagno
 
     // A static or bound `aria-label`/`aria-labelledby` provides an accessible
     // name; a bound `:aria-label="x"` still counts as present.
-    let has_aria_label = html_attrs::attrs(&tag, source)
+    let attrs = html_attrs::attrs(&tag, source);
+    let has_aria_label = attrs
         .iter()
         .any(|a| a.name_eq("aria-label") || a.name_eq("aria-labelledby"));
-    if has_aria_label {
+    if has_aria_label || html_attrs::has_dynamic_content(&attrs) {
         return;
     }
 
@@ -274,6 +276,12 @@ mod tests {
         rule.check(&tree.root_node(), source, FileType::Vue)
     }
 
+    #[test]
+    fn test_vue_v_html_passes() {
+        let diags = check_vue(r#"<template><h2 v-html="title"></h2></template>"#);
+        assert_eq!(diags.len(), 0, "v-html fills the heading, got: {diags:?}");
+    }
+
     #[test]
     fn test_vue_bound_aria_label_passes() {
         let diags = check_vue(r#"<template><h2 :aria-label="label"></h2></template>"#);
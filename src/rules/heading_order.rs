@@ -1,11 +1,25 @@
+use crate::analysis::{Analysis, Element};
+use crate::config::Config;
 use crate::engine::node_to_range;
 use crate::parser::FileType;
+use crate::quickfix::{self, Fix};
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
 use tower_lsp_server::ls_types::*;
 use tree_sitter::Node;
 
 pub struct HeadingOrder;
 
+/// A heading in document order.
+struct Heading {
+    level: u32,
+    range: Range,
+    /// The tag name in the opening tag and, if there is one, the closing tag.
+    /// Empty for a `role="heading"`, whose tag isn't what makes it one.
+    names: Vec<Range>,
+    /// Written `<H4>` rather than `<h4>`.
+    uppercase: bool,
+}
+
 static METADATA: RuleMetadata = RuleMetadata {
     id: "heading-order",
     description: "Heading levels should not be skipped",
@@ -13,6 +27,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.3.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
     default_severity: Severity::Warning,
+    axe_rules: &["heading-order"],
 };
 
 impl Rule for HeadingOrder {
@@ -20,94 +35,86 @@ impl Rule for HeadingOrder {
         &METADATA
     }
 
+    /// Heading levels are relative to the rest of the page.
+    fn page_level(&self) -> bool {
+        true
+    }
+
     fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
-        let mut headings = Vec::new();
-        if file_type.is_jsx_like() {
-            collect_headings_jsx(root, source, &mut headings);
-        } else {
-            collect_headings_html(root, source, &mut headings);
-        }
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
 
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        // Markdown's own `#` headings aren't in the tree, so the levels of
+        // its raw HTML headings can't be followed.
+        if analysis.file_type() == FileType::Markdown {
+            return Vec::new();
+        }
+        let elements = analysis.elements();
         let mut diagnostics = Vec::new();
-        let mut prev_level: u8 = 0;
+        let mut prev_level = 0;
 
-        for (level, node_range) in &headings {
-            if *level > prev_level + 1 {
-                diagnostics.push(make_diagnostic(*node_range, prev_level, *level));
+        for (i, level) in analysis.headings() {
+            if level > prev_level + 1 {
+                let heading = heading(&elements[i], level, analysis);
+                diagnostics.push(make_diagnostic(&heading, prev_level));
             }
-            prev_level = *level;
+            prev_level = level;
         }
 
         diagnostics
     }
 }
 
-/// Extract heading level from a tag name like "h1" .. "h6". Returns None if not a heading.
-fn heading_level(tag_name: &str) -> Option<u8> {
-    let lower = tag_name.to_ascii_lowercase();
-    match lower.as_str() {
-        "h1" => Some(1),
-        "h2" => Some(2),
-        "h3" => Some(3),
-        "h4" => Some(4),
-        "h5" => Some(5),
-        "h6" => Some(6),
-        _ => None,
-    }
-}
-
-/// Collect all headings from an HTML AST in document order as (level, range) pairs.
-fn collect_headings_html(node: &Node, source: &str, headings: &mut Vec<(u8, Range)>) {
-    if node.kind() == "element" {
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            if child.kind() == "start_tag" {
-                let mut tag_cursor = child.walk();
-                for tag_child in child.children(&mut tag_cursor) {
-                    if tag_child.kind() == "tag_name" {
-                        let name = &source[tag_child.byte_range()];
-                        if let Some(level) = heading_level(name) {
-                            headings.push((level, node_to_range(node)));
-                        }
-                    }
-                }
-            }
+/// The heading `element` as reported, at `level`.
+fn heading(element: &Element, level: u32, analysis: &Analysis) -> Heading {
+    let source = analysis.source();
+    let jsx = analysis.file_type().is_jsx_like();
+    // Without a role, it's an `<h1>`…`<h6>`, which can be renamed.
+    let name = element
+        .name_node()
+        .filter(|_| element.role.is_none())
+        .map(|name| (name, &source[name.byte_range()]));
+    let mut names = Vec::new();
+    if let Some((name, _)) = name {
+        names.push(node_to_range(&name));
+        if let Some(closing) = closing_name(element, jsx) {
+            names.push(node_to_range(&closing));
         }
     }
-
-    // Recurse into children
-    let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        collect_headings_html(&child, source, headings);
+    let range = if jsx { element.tag } else { element.node() };
+    Heading {
+        level,
+        range: node_to_range(&range),
+        names,
+        uppercase: name.is_some_and(|(_, name)| name.starts_with('H')),
     }
 }
 
-/// Collect all headings from a JSX/TSX AST in document order as (level, range) pairs.
-fn collect_headings_jsx(node: &Node, source: &str, headings: &mut Vec<(u8, Range)>) {
-    if node.kind() == "jsx_opening_element" || node.kind() == "jsx_self_closing_element" {
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            if child.kind() == "identifier" {
-                let name = &source[child.byte_range()];
-                if let Some(level) = heading_level(name) {
-                    headings.push((level, node_to_range(node)));
-                }
-            }
-        }
-    }
-
-    // Recurse into children
+/// The tag name in the end tag of `element`, if it has one.
+fn closing_name<'a>(element: &Element<'a>, jsx: bool) -> Option<Node<'a>> {
+    let (closing_kind, name_kind) = if jsx {
+        ("jsx_closing_element", "identifier")
+    } else {
+        ("end_tag", "tag_name")
+    };
+    let node = element.node();
     let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        collect_headings_jsx(&child, source, headings);
-    }
+    let closing = node
+        .children(&mut cursor)
+        .find(|c| c.kind() == closing_kind)?;
+    let mut closing_cursor = closing.walk();
+    closing
+        .children(&mut closing_cursor)
+        .find(|c| c.kind() == name_kind)
 }
 
-fn make_diagnostic(range: Range, prev_level: u8, current_level: u8) -> Diagnostic {
+fn make_diagnostic(heading: &Heading, prev_level: u32) -> Diagnostic {
     let meta = &METADATA;
+    let current_level = heading.level;
     let expected = prev_level + 1;
-    Diagnostic {
-        range,
+    let mut diagnostic = Diagnostic {
+        range: heading.range,
         severity: Some(DiagnosticSeverity::WARNING),
         code: Some(NumberOrString::String("heading-order".to_string())),
         code_description: Some(CodeDescription {
@@ -119,7 +126,28 @@ fn make_diagnostic(range: Range, prev_level: u8, current_level: u8) -> Diagnosti
             current_level, expected, meta.wcag_criterion, meta.wcag_level
         ),
         ..Default::default()
+    };
+    // Keep the author's casing: `<H4>` becomes `<H3>`.
+    let h = if heading.uppercase { 'H' } else { 'h' };
+    if heading.names.is_empty() {
+        return diagnostic;
     }
+    quickfix::attach(
+        &mut diagnostic,
+        Fix {
+            title: format!("Change <h{current_level}> to <h{expected}>"),
+            edits: heading
+                .names
+                .iter()
+                .map(|range| TextEdit {
+                    range: *range,
+                    new_text: format!("{h}{expected}"),
+                })
+                .collect(),
+            ..Default::default()
+        },
+    );
+    diagnostic
 }
 
 #[cfg(test)]
@@ -168,9 +196,69 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    #[test]
+    fn test_role_heading_counts_without_fix() {
+        let diags = check_html(r#"<h1>A</h1><div role="heading" aria-level="4">B</div>"#);
+        assert_eq!(diags.len(), 1);
+        assert!(quickfix::fixes(&diags[0]).is_empty());
+    }
+
+    #[test]
+    fn test_hidden_headings_skipped() {
+        let diags = check_html("<h1>A</h1><div hidden><h4>B</h4></div><h2>C</h2>");
+        assert_eq!(diags.len(), 0);
+    }
+
     #[test]
     fn test_decreasing_levels_ok() {
         let diags = check_html("<h1>A</h1><h2>B</h2><h3>C</h3><h2>D</h2>");
         assert_eq!(diags.len(), 0);
     }
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = HeadingOrder;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    fn fix_texts(diag: &Diagnostic) -> (String, Vec<(u32, String)>) {
+        let fix = &quickfix::fixes(diag)[0];
+        let edits = fix
+            .edits
+            .iter()
+            .map(|e| (e.range.start.character, e.new_text.clone()))
+            .collect();
+        (fix.title.clone(), edits)
+    }
+
+    #[test]
+    fn test_fix_changes_open_and_close_tag() {
+        let diags = check_html("<h1>A</h1><h4>B</h4>");
+        let (title, edits) = fix_texts(&diags[0]);
+        assert_eq!(title, "Change <h4> to <h2>");
+        assert_eq!(edits, [(11, "h2".to_string()), (17, "h2".to_string())]);
+    }
+
+    #[test]
+    fn test_fix_keeps_uppercase() {
+        let diags = check_html("<H1>A</H1><H3>B</H3>");
+        let (_, edits) = fix_texts(&diags[0]);
+        assert_eq!(edits[0].1, "H2");
+    }
+
+    #[test]
+    fn test_fix_tsx_element() {
+        let diags = check_tsx("const A = () => <><h1>A</h1><h3 className=\"x\">B</h3></>;");
+        let (title, edits) = fix_texts(&diags[0]);
+        assert_eq!(title, "Change <h3> to <h2>");
+        assert_eq!(edits, [(29, "h2".to_string()), (49, "h2".to_string())]);
+    }
+
+    #[test]
+    fn test_fix_tsx_self_closing() {
+        let diags = check_tsx("const A = () => <><h1>A</h1><h3 /></>;");
+        let (_, edits) = fix_texts(&diags[0]);
+        assert_eq!(edits, [(29, "h2".to_string())]);
+    }
 }
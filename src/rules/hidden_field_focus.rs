@@ -0,0 +1,561 @@
+use crate::engine::{node_to_range, related_location};
+use crate::parser::FileType;
+use crate::rules::css;
+use crate::rules::html_attrs::{self, Attr};
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct HiddenFieldFocus;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "hidden-field-focus",
+    description: "Visually hidden form fields must not be focusable",
+    wcag_level: WcagLevel::AA,
+    wcag_criterion: "2.4.7",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/focus-visible.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Input types that aren't text fields. Checkboxes, radios and file inputs
+/// are routinely hidden behind a styled label that shows their focus, so
+/// they're left alone.
+const NON_TEXT_TYPES: &[&str] = &[
+    "button", "checkbox", "color", "file", "hidden", "image", "radio", "range", "reset", "submit",
+];
+
+/// Classes that hide an element visually but keep it in the focus order:
+/// the screen-reader-only utilities of the common CSS frameworks, and the
+/// names anti-spam honeypots usually go by. Any class containing `honeypot`
+/// counts as well.
+const VISUALLY_HIDDEN_CLASSES: &[&str] = &[
+    "hidden-visually",
+    "hp-field",
+    "hpot",
+    "ohnohoney",
+    "screen-reader-text",
+    "sr-only",
+    "visually-hidden",
+    "visuallyhidden",
+];
+
+/// Classes that take an element out of rendering (`display: none`), which
+/// also takes it out of the focus order.
+const NOT_RENDERED_CLASSES: &[&str] = &["d-none", "hidden", "is-hidden"];
+
+/// React components that render their children visually hidden.
+const VISUALLY_HIDDEN_COMPONENTS: &[&str] = &["ScreenReaderOnly", "SrOnly", "VisuallyHidden"];
+
+/// What an element's attributes do to its visibility.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Hiding {
+    /// Nothing we can tell.
+    None,
+    /// Invisible, but still rendered and focusable.
+    Visual,
+    /// Not rendered at all (`display: none`, `hidden`, `inert`): not
+    /// focusable either, which is how a honeypot should be hidden.
+    Removed,
+}
+
+impl Rule for HiddenFieldFocus {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, None, &mut diagnostics);
+        } else {
+            visit_html(root, source, None, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+/// `hidden_by` is the outermost element hiding `node` visually, if any.
+fn visit_html<'a>(
+    node: &Node<'a>,
+    source: &str,
+    mut hidden_by: Option<Node<'a>>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let attrs = html_attrs::attrs(&tag, source);
+        match html_hiding(&attrs) {
+            Hiding::Removed => return,
+            Hiding::Visual => {
+                hidden_by.get_or_insert(*node);
+            }
+            Hiding::None => {}
+        }
+        if let Some(hider) = hidden_by
+            && is_html_text_field(&tag, source, &attrs)
+            && html_stays_focusable(&attrs)
+        {
+            diagnostics.push(make_diagnostic(&tag, (hider != *node).then_some(&hider)));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, hidden_by, diagnostics);
+    }
+}
+
+/// Bound attributes are only known at runtime and hide nothing as far as
+/// we can tell.
+fn html_hiding(attrs: &[Attr]) -> Hiding {
+    let mut hiding = Hiding::None;
+    for attr in attrs.iter().filter(|a| !a.bound) {
+        if attr.name_eq("hidden") || attr.name_eq("inert") {
+            return Hiding::Removed;
+        }
+        let found = match attr.value.as_deref() {
+            Some(style) if attr.name_eq("style") => style_hiding(&css::inline_declarations(style)),
+            Some(classes) if attr.name_eq("class") => class_hiding(classes),
+            _ => Hiding::None,
+        };
+        match found {
+            Hiding::Removed => return Hiding::Removed,
+            Hiding::Visual => hiding = Hiding::Visual,
+            Hiding::None => {}
+        }
+    }
+    hiding
+}
+
+fn is_html_text_field(tag: &Node, source: &str, attrs: &[Attr]) -> bool {
+    match html_attrs::tag_name(tag, source).map(|n| n.to_ascii_lowercase()) {
+        Some(name) if name == "textarea" => true,
+        Some(name) if name == "input" => match attrs.iter().find(|a| a.name_eq("type")) {
+            None => true,
+            Some(attr) if attr.bound => false,
+            Some(attr) => is_text_type(attr.value.as_deref().unwrap_or("")),
+        },
+        _ => false,
+    }
+}
+
+/// A field stays in the focus order unless it's disabled or has a negative
+/// `tabindex`. A bound `disabled` or `tabindex` may well do either, so it
+/// counts as taken out.
+fn html_stays_focusable(attrs: &[Attr]) -> bool {
+    if attrs.iter().any(|a| a.name_eq("disabled")) {
+        return false;
+    }
+    match attrs.iter().find(|a| a.name_eq("tabindex")) {
+        None => true,
+        Some(attr) if attr.bound => false,
+        Some(attr) => !is_negative_tabindex(attr.value.as_deref().unwrap_or("")),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx<'a>(
+    node: &Node<'a>,
+    source: &str,
+    mut hidden_by: Option<Node<'a>>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if let Some(tag) = jsx_tag(node) {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let attrs = jsx_attrs(&tag, source);
+        let hiding = if VISUALLY_HIDDEN_COMPONENTS.contains(&name) {
+            Hiding::Visual
+        } else if name.starts_with(char::is_uppercase) || name.contains('.') {
+            Hiding::None
+        } else {
+            jsx_hiding(&attrs, source)
+        };
+        match hiding {
+            Hiding::Removed => return,
+            Hiding::Visual => {
+                hidden_by.get_or_insert(*node);
+            }
+            Hiding::None => {}
+        }
+        if let Some(hider) = hidden_by
+            && is_jsx_text_field(name, &attrs, source)
+            && jsx_stays_focusable(&attrs, source)
+        {
+            diagnostics.push(make_diagnostic(&tag, (hider != *node).then_some(&hider)));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, hidden_by, diagnostics);
+    }
+}
+
+/// The opening tag of a `jsx_element`, or a `jsx_self_closing_element`.
+fn jsx_tag<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    }
+}
+
+/// The attributes of a JSX tag by name; `None` stands for a spread
+/// (`{...props}`), which may set anything.
+fn jsx_attrs<'a, 's>(tag: &Node<'a>, source: &'s str) -> Vec<Option<(&'s str, Option<Node<'a>>)>> {
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .filter_map(|child| match child.kind() {
+            "jsx_attribute" => jsx_attrs::jsx_attribute(&child, source).map(Some),
+            "jsx_expression" => Some(None),
+            _ => None,
+        })
+        .collect()
+}
+
+fn jsx_attr<'a>(
+    attrs: &[Option<(&str, Option<Node<'a>>)>],
+    names: &[&str],
+) -> Option<Option<Node<'a>>> {
+    attrs
+        .iter()
+        .flatten()
+        .find(|(name, _)| names.contains(name))
+        .map(|(_, value)| *value)
+}
+
+fn jsx_hiding(attrs: &[Option<(&str, Option<Node>)>], source: &str) -> Hiding {
+    let mut hiding = Hiding::None;
+    for (name, value) in attrs.iter().flatten() {
+        let found = match *name {
+            // `hidden` and `hidden={true}` hide; `hidden={false}` and
+            // expressions don't, as far as we know.
+            "hidden" | "inert" => match value {
+                None => Hiding::Removed,
+                Some(value) => match jsx_literal(value, source) {
+                    Some(v) if v != "false" => Hiding::Removed,
+                    _ => Hiding::None,
+                },
+            },
+            "className" | "class" => value
+                .and_then(|v| jsx_literal(&v, source))
+                .map_or(Hiding::None, |classes| class_hiding(&classes)),
+            "style" => value
+                .and_then(|v| jsx_attrs::jsx_object(&v))
+                .map_or(Hiding::None, |object| {
+                    style_hiding(&object_declarations(&object, source))
+                }),
+            _ => Hiding::None,
+        };
+        match found {
+            Hiding::Removed => return Hiding::Removed,
+            Hiding::Visual => hiding = Hiding::Visual,
+            Hiding::None => {}
+        }
+    }
+    hiding
+}
+
+/// A field with a spread is skipped: the props may disable it or set
+/// `tabIndex`.
+fn is_jsx_text_field(name: &str, attrs: &[Option<(&str, Option<Node>)>], source: &str) -> bool {
+    if attrs.iter().any(Option::is_none) {
+        return false;
+    }
+    match name {
+        "textarea" => true,
+        "input" => match jsx_attr(attrs, &["type"]) {
+            None => true,
+            Some(value) => value
+                .and_then(|v| jsx_literal(&v, source))
+                .is_some_and(|t| is_text_type(&t)),
+        },
+        _ => false,
+    }
+}
+
+fn jsx_stays_focusable(attrs: &[Option<(&str, Option<Node>)>], source: &str) -> bool {
+    if let Some(value) = jsx_attr(attrs, &["disabled"]) {
+        let literal = value.and_then(|v| jsx_literal(&v, source));
+        if literal.as_deref() != Some("false") {
+            return false;
+        }
+    }
+    match jsx_attr(attrs, &["tabIndex", "tabindex"]) {
+        None => true,
+        Some(value) => value
+            .and_then(|v| jsx_literal(&v, source))
+            .is_some_and(|v| !is_negative_tabindex(&v)),
+    }
+}
+
+/// The text of a literal attribute or property value: a string, a number,
+/// `true`/`false`, or a negated number, directly or in `{…}`.
+fn jsx_literal(value: &Node, source: &str) -> Option<String> {
+    let value = if value.kind() == "jsx_expression" {
+        value.named_child(0)?
+    } else {
+        *value
+    };
+    match value.kind() {
+        "string" => Some(
+            source[value.byte_range()]
+                .trim_matches(['"', '\''])
+                .to_string(),
+        ),
+        "number" | "true" | "false" => Some(source[value.byte_range()].to_string()),
+        "unary_expression"
+            if value
+                .named_child(0)
+                .is_some_and(|operand| operand.kind() == "number") =>
+        {
+            Some(source[value.byte_range()].replace(char::is_whitespace, ""))
+        }
+        _ => None,
+    }
+}
+
+/// The literal properties of a `style={{ … }}` object as CSS declarations,
+/// `marginLeft` becoming `margin-left`.
+fn object_declarations(object: &Node, source: &str) -> Vec<(String, String)> {
+    let mut cursor = object.walk();
+    object
+        .named_children(&mut cursor)
+        .filter(|pair| pair.kind() == "pair")
+        .filter_map(|pair| {
+            let key = pair.child_by_field_name("key")?;
+            let key = source[key.byte_range()].trim_matches(['"', '\'']);
+            let value = jsx_literal(&pair.child_by_field_name("value")?, source)?;
+            let mut property = String::new();
+            for c in key.chars() {
+                if c.is_ascii_uppercase() {
+                    property.push('-');
+                }
+                property.push(c.to_ascii_lowercase());
+            }
+            Some((property, value.to_ascii_lowercase()))
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn is_text_type(input_type: &str) -> bool {
+    let input_type = input_type.trim().to_ascii_lowercase();
+    !NON_TEXT_TYPES.contains(&input_type.as_str())
+}
+
+fn is_negative_tabindex(value: &str) -> bool {
+    value.trim().parse::<i64>().is_ok_and(|n| n < 0)
+}
+
+fn style_hiding(declarations: &[(String, String)]) -> Hiding {
+    let mut hiding = Hiding::None;
+    for (property, value) in declarations {
+        let value = value.trim_end_matches("!important").trim();
+        let visual = match property.as_str() {
+            "display" if value == "none" => return Hiding::Removed,
+            "visibility" if value == "hidden" || value == "collapse" => return Hiding::Removed,
+            "opacity" => length(value).is_some_and(|(n, _)| n == 0.0),
+            "left" | "top" | "right" | "bottom" | "margin-left" | "margin-top" => {
+                is_off_screen(value)
+            }
+            "width" | "height" | "max-width" | "max-height" => {
+                length(value).is_some_and(|(n, _)| n == 0.0)
+            }
+            "clip" => value.starts_with("rect("),
+            "clip-path" => value.starts_with("inset(50%") || value.starts_with("inset(100%"),
+            "transform" => value == "scale(0)",
+            _ => false,
+        };
+        if visual {
+            hiding = Hiding::Visual;
+        }
+    }
+    hiding
+}
+
+fn class_hiding(classes: &str) -> Hiding {
+    let mut hiding = Hiding::None;
+    for class in classes.split_whitespace() {
+        let class = class.to_ascii_lowercase();
+        if NOT_RENDERED_CLASSES.contains(&class.as_str()) {
+            return Hiding::Removed;
+        }
+        if VISUALLY_HIDDEN_CLASSES.contains(&class.as_str()) || class.contains("honeypot") {
+            hiding = Hiding::Visual;
+        }
+    }
+    hiding
+}
+
+/// A CSS length (or plain number) split into its number and unit.
+fn length(value: &str) -> Option<(f64, &str)> {
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.')))
+        .unwrap_or(value.len());
+    let number = value[..end].parse().ok()?;
+    Some((number, value[end..].trim()))
+}
+
+/// Offsets like `left: -9999px` that push an element out of the viewport.
+/// A unitless number is React's pixels.
+fn is_off_screen(value: &str) -> bool {
+    match length(value) {
+        Some((n, "" | "px")) => n <= -500.0,
+        Some((n, "em" | "rem")) => n <= -30.0,
+        Some((n, "%" | "vw" | "vh")) => n <= -100.0,
+        _ => false,
+    }
+}
+
+/// A diagnostic on a field's tag; `hidden_by` is the ancestor hiding it,
+/// unless the field hides itself.
+fn make_diagnostic(tag: &Node, hidden_by: Option<&Node>) -> Diagnostic {
+    let meta = &METADATA;
+    let (how, related) = match hidden_by {
+        Some(ancestor) => (
+            "is inside a visually hidden element",
+            Some(vec![related_location(
+                ancestor,
+                "The element hiding the field",
+            )]),
+        ),
+        None => ("is visually hidden", None),
+    };
+    Diagnostic {
+        range: node_to_range(tag),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "This field {} but can still be focused, so keyboard and screen reader users land in it unseen; add tabindex=\"-1\" and autocomplete=\"off\", or hide it with display: none. {} [WCAG {} Level {:?}]",
+            how, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        related_information: related,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = HiddenFieldFocus;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Tsx)
+    }
+
+    #[test]
+    fn test_honeypot_wrapper_fails_with_related_wrapper() {
+        let diags = check_html(
+            "<form>\n<div class=\"form-honeypot\">\n<label>Leave empty <input name=\"website\"></label>\n</div>\n</form>",
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("This field is inside a visually hidden element")
+        );
+        let related = diags[0].related_information.as_ref().unwrap();
+        assert_eq!(related[0].location.range.start.line, 1);
+    }
+
+    #[test]
+    fn test_off_screen_and_transparent_fields_fail() {
+        let diags = check_html(
+            r#"<input name="url" style="position: absolute; left: -9999px">
+<textarea name="comment2" style="opacity:0"></textarea>
+<input type="email" class="sr-only">"#,
+        );
+        assert_eq!(diags.len(), 3);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("This field is visually hidden")
+        );
+        assert!(diags[0].related_information.is_none());
+    }
+
+    #[test]
+    fn test_removed_from_focus_order_passes() {
+        let diags = check_html(
+            r#"<div class="honeypot"><input name="a" tabindex="-1"><input name="b" disabled></div>
+<div style="display: none"><input name="c"></div>
+<div class="honeypot" hidden><input name="d"></div>
+<div inert style="left:-9999px"><input name="e"></div>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_visible_fields_and_hidden_checkboxes_pass() {
+        let diags = check_html(
+            r#"<input name="q" style="left: -10px"><input type="checkbox" class="sr-only"><input type="hidden" class="honeypot">"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_bound_attributes_ignored() {
+        let diags = check(
+            r#"<template><div :class="hp"><input name="a"></div><div class="honeypot"><input :tabindex="t"></div></template>"#,
+            FileType::Vue,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_style_object_fails() {
+        let diags = check_tsx(
+            r#"const F = () => <form><div style={{ position: "absolute", left: -5000 }}><input name="hp" /></div><input className="visually-hidden" /></form>;"#,
+        );
+        assert_eq!(diags.len(), 2);
+    }
+
+    #[test]
+    fn test_tsx_negative_tab_index_and_spread_pass() {
+        let diags = check_tsx(
+            r#"const F = (p) => <div className="honeypot"><input tabIndex={-1} /><input {...p} /><input hidden={false} disabled /></div>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_visually_hidden_component_fails() {
+        let diags =
+            check_tsx(r#"const F = () => <VisuallyHidden><textarea name="x" /></VisuallyHidden>;"#);
+        assert_eq!(diags.len(), 1);
+        let diags = check_tsx(r#"const F = () => <div className="hidden"><input /></div>;"#);
+        assert_eq!(diags.len(), 0);
+    }
+}
@@ -12,11 +12,42 @@
 //!   - `v-html`, `v-if`, …     → name kept as-is (`v-html`), `directive = true`
 //!   - modifiers are stripped: `@click.prevent` → `click`, `:foo.sync` → `foo`
 //!
+//! Angular template syntax is normalized the same way:
+//!   - `[alt]` / `[attr.alt]` / `[(ngModel)]` → name `alt` / `ngModel`, `bound = true`
+//!   - `(click)` / `(keydown.enter)` → name `click` / `keydown`, `bound = true`, `event = true`
+//!   - `*ngIf`, `*ngFor`, `#ref` → name kept as-is, like Vue directives
+//!
+//! So are Lit's bindings in an `` html`…` `` template: `.value` and
+//! `?disabled` → name `value` / `disabled`, `bound = true`, and `@click` as
+//! in Vue.
+//!
+//! A plain attribute whose value is interpolated by a server-side or client
+//! template engine (`alt="{{ $alt }}"` in Blade, Liquid, Twig, Handlebars or
+//! Angular, `alt="{% if a %}…{% endif %}"` in Liquid or Twig,
+//! `alt="{!! $alt !!}"` in Blade, `alt="<%= @alt %>"` in ERB,
+//! `alt="<?php echo esc_attr( $alt ); ?>"` in PHP) is `bound` as well, since
+//! its value is only known once rendered.
+//!
 //! Plain HTML attributes pass through unchanged, so this is safe to use for
 //! every HTML-grammar file type.
+//!
+//! Tag names go through an alias table as well: MJML components are reported
+//! under the HTML element they compile to, so `<mj-image>` is checked like an
+//! `<img>` by every rule that reads names through [`tag_name`].
 
 use tree_sitter::Node;
 
+/// Components of template languages and the HTML element each compiles to.
+const TAG_ALIASES: &[(&str, &str)] = &[
+    ("mj-body", "body"),
+    ("mj-button", "button"),
+    ("mj-head", "head"),
+    ("mj-image", "img"),
+    ("mj-table", "table"),
+    ("mj-title", "title"),
+    ("mjml", "html"),
+];
+
 /// A normalized attribute on an element parsed with the HTML grammar.
 #[derive(Debug, Clone)]
 pub struct Attr<'a> {
@@ -29,9 +60,10 @@ pub struct Attr<'a> {
     /// that should be validated literally.
     pub value: Option<String>,
     /// `true` when the value is a dynamic expression (`:x`, `v-bind:x`, `@x`,
-    /// `v-on:x`). The literal `value` text is then a JS expression.
+    /// `v-on:x`, Angular's `[x]` and `(x)`). The literal `value` text is then
+    /// a JS expression.
     pub bound: bool,
-    /// `true` for event bindings (`@x` / `v-on:x`).
+    /// `true` for event bindings (`@x` / `v-on:x` / `(x)`).
     pub event: bool,
 }
 
@@ -57,12 +89,25 @@ pub fn normalize_attr_name(raw: &str) -> (String, bool, bool) {
         (rest, true, false)
     } else if let Some(rest) = raw.strip_prefix("v-bind:") {
         (rest, true, false)
+    } else if let Some(rest) = raw.strip_prefix("[(").and_then(|r| r.strip_suffix(")]")) {
+        // Angular two-way binding, `[(ngModel)]`.
+        (rest, true, false)
+    } else if let Some(rest) = raw.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+        // Angular property (`[alt]`) or attribute (`[attr.aria-label]`) binding.
+        (rest.strip_prefix("attr.").unwrap_or(rest), true, false)
+    } else if let Some(rest) = raw.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+        // Angular event binding, `(click)`.
+        (rest, true, true)
+    } else if let Some(rest) = raw.strip_prefix(['.', '?']) {
+        // Lit property (`.value`) and boolean attribute (`?disabled`) binding.
+        (rest, true, false)
     } else {
         // Plain attribute or a `v-*` directive (kept as-is).
         (raw, false, false)
     };
 
-    // Strip Vue modifiers (`.prevent`, `.enter`, `.camel`, …). HTML/ARIA
+    // Strip Vue modifiers (`.prevent`, `.enter`, `.camel`, …) and Angular
+    // key/unit suffixes (`keydown.enter`, `style.width.px`). HTML/ARIA
     // attribute names never contain a dot, so this is safe.
     let name = base.split('.').next().unwrap_or(base).to_string();
     (name, bound, event)
@@ -97,6 +142,12 @@ pub fn attr_from_node<'a>(node: &Node<'a>, source: &str) -> Option<Attr<'a>> {
 
     let raw_name = raw_name?;
     let (name, bound, event) = normalize_attr_name(raw_name);
+    let bound = bound
+        || value.as_deref().is_some_and(|v| {
+            ["{{", "{!!", "{%", "<%", "<?"]
+                .iter()
+                .any(|open| v.contains(open))
+        });
     Some(Attr {
         node: *node,
         name,
@@ -118,17 +169,26 @@ pub fn element_tag<'a>(element: &Node<'a>) -> Option<Node<'a>> {
         .find(|c| c.kind() == "start_tag" || c.kind() == "self_closing_tag")
 }
 
-/// The tag name from a `start_tag`/`self_closing_tag` node.
+/// The tag name from a `start_tag`/`self_closing_tag` node, with aliases
+/// resolved to their HTML element.
 pub fn tag_name<'a>(tag: &Node, source: &'a str) -> Option<&'a str> {
     let mut cursor = tag.walk();
     for child in tag.children(&mut cursor) {
         if child.kind() == "tag_name" {
-            return Some(&source[child.byte_range()]);
+            return Some(resolve_alias(&source[child.byte_range()]));
         }
     }
     None
 }
 
+/// The HTML element `name` compiles to, or `name` itself.
+pub fn resolve_alias(name: &str) -> &str {
+    TAG_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+        .map_or(name, |(_, html)| html)
+}
+
 /// The tag name of an `element` node (resolves the inner tag first).
 pub fn element_tag_name<'a>(element: &Node, source: &'a str) -> Option<&'a str> {
     let tag = element_tag(element)?;
@@ -175,6 +235,17 @@ pub fn element_attr_value(element: &Node, source: &str, name: &str) -> Option<St
         .and_then(|a| a.value)
 }
 
+/// Whether the attributes replace the element's content at runtime: Vue's
+/// `v-text`/`v-html`, or a bound `innerHTML`/`textContent`. Rules that look
+/// for text content should treat such an element as having some.
+pub fn has_dynamic_content(attrs: &[Attr]) -> bool {
+    attrs.iter().any(|a| {
+        a.name_eq("v-text")
+            || a.name_eq("v-html")
+            || (a.bound && (a.name_eq("innerHTML") || a.name_eq("textContent")))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,6 +271,14 @@ mod tests {
             .find_map(|c| find_element_by_tag(c, source, tag))
     }
 
+    #[test]
+    fn test_resolve_alias() {
+        assert_eq!(resolve_alias("mj-image"), "img");
+        assert_eq!(resolve_alias("mjml"), "html");
+        assert_eq!(resolve_alias("mj-column"), "mj-column");
+        assert_eq!(resolve_alias("img"), "img");
+    }
+
     #[test]
     fn test_normalize_bind_shorthand() {
         assert_eq!(normalize_attr_name(":alt"), ("alt".to_string(), true, false));
@@ -250,6 +329,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normalize_angular_bindings() {
+        assert_eq!(
+            normalize_attr_name("[alt]"),
+            ("alt".to_string(), true, false)
+        );
+        assert_eq!(
+            normalize_attr_name("[attr.aria-label]"),
+            ("aria-label".to_string(), true, false)
+        );
+        assert_eq!(
+            normalize_attr_name("[(ngModel)]"),
+            ("ngModel".to_string(), true, false)
+        );
+        assert_eq!(
+            normalize_attr_name("(keydown.enter)"),
+            ("keydown".to_string(), true, true)
+        );
+        assert_eq!(
+            normalize_attr_name("*ngIf"),
+            ("*ngIf".to_string(), false, false)
+        );
+    }
+
+    #[test]
+    fn test_normalize_lit_bindings() {
+        assert_eq!(
+            normalize_attr_name(".value"),
+            ("value".to_string(), true, false)
+        );
+        assert_eq!(
+            normalize_attr_name("?disabled"),
+            ("disabled".to_string(), true, false)
+        );
+    }
+
+    #[test]
+    fn test_attrs_on_angular_img() {
+        let mut p = parser::create_parser(FileType::Html).unwrap();
+        let src =
+            r#"<img *ngIf="user" [src]="user.avatar" [attr.alt]="user.name" (click)="open()">"#;
+        let tree = p.parse(src, None).unwrap();
+        let img = find_element_by_tag(tree.root_node(), src, "img").unwrap();
+        let attrs = element_attrs(&img, src);
+        let names: Vec<_> = attrs.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["*ngIf", "src", "alt", "click"]);
+        assert!(attrs[2].bound && !attrs[2].event);
+        assert!(attrs[3].event);
+    }
+
+    #[test]
+    fn test_interpolated_value_is_bound() {
+        let mut p = parser::create_parser(FileType::Html).unwrap();
+        let src = r#"<img alt="{{ $alt }}" title="Photo of {{ name }}" class="{% if a %}b{% endif %}" src="a.png">"#;
+        let tree = p.parse(src, None).unwrap();
+        let img = find_element_by_tag(tree.root_node(), src, "img").unwrap();
+        let bound: Vec<_> = element_attrs(&img, src).iter().map(|a| a.bound).collect();
+        assert_eq!(bound, [true, true, true, false]);
+    }
+
     #[test]
     fn test_attrs_on_vue_img() {
         let (tree, src) = parse_vue(r#"<template><img :alt="alt" src="x"></template>"#);
@@ -270,4 +409,18 @@ mod tests {
         assert_eq!(element_tag_name(&input, &src), Some("input"));
         assert!(element_has_attr(&input, &src, "type"));
     }
+
+    #[test]
+    fn test_dynamic_content() {
+        let (tree, src) = parse_vue(
+            r#"<template><p v-html="a"></p><b :innerHTML="b"></b><i innerHTML="c"></i></template>"#,
+        );
+        let content = |tag| {
+            let element = find_element_by_tag(tree.root_node(), &src, tag).unwrap();
+            has_dynamic_content(&element_attrs(&element, &src))
+        };
+        assert!(content("p"));
+        assert!(content("b"));
+        assert!(!content("i"), "a static innerHTML attribute does nothing");
+    }
 }
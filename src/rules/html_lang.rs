@@ -1,7 +1,8 @@
 use crate::engine::node_to_range;
 use crate::parser::FileType;
-use crate::rules::html_attrs;
+use crate::quickfix::{self, Fix};
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use crate::rules::{html_attrs, jsx_attrs, nuxt, react_head};
 use tower_lsp_server::ls_types::*;
 use tree_sitter::Node;
 
@@ -14,6 +15,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "3.1.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/language-of-page.html",
     default_severity: Severity::Error,
+    axe_rules: &["html-has-lang"],
 };
 
 impl Rule for HtmlLang {
@@ -21,9 +23,26 @@ impl Rule for HtmlLang {
         &METADATA
     }
 
+    /// Only a page has an `<html>` element.
+    fn page_level(&self) -> bool {
+        true
+    }
+
     fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
         if file_type.is_jsx_like() {
+            // The app router's root layout renders `<html>`; the pages router
+            // renders `next/document`'s `<Html>` in `_document.tsx`. Helmet
+            // sets it with `htmlAttributes`.
+            let names = JsxNames {
+                html: react_head::local_name(root, source, "next/document", "Html"),
+                html_attributes: react_head::html_attributes_components(root, source),
+            };
+            visit_jsx(root, source, &names, &mut diagnostics);
+            return diagnostics;
+        }
+        // Nuxt's `<Html>` can leave `lang` to `useHead({ htmlAttrs: { lang } })`.
+        if file_type == FileType::Vue && nuxt::head_sets(source, &["htmlAttrs", "lang"]) {
             return diagnostics;
         }
         visit_html(root, source, &mut diagnostics);
@@ -55,20 +74,130 @@ fn check_html_element(element: &Node, source: &str, diagnostics: &mut Vec<Diagno
 
     // A bound `:lang`/`v-bind:lang` provides a (dynamic) language → treat as set.
     // A static `lang` must have a non-empty value.
-    let lang_ok = match html_attrs::attrs(&tag, source).into_iter().find(|a| a.name_eq("lang")) {
+    let lang = html_attrs::attrs(&tag, source)
+        .into_iter()
+        .find(|a| a.name_eq("lang"));
+    let lang_ok = match &lang {
         Some(a) if a.bound => true,
         Some(a) => a.value.as_deref().is_some_and(|v| !v.trim().is_empty()),
         None => false,
     };
 
     if !lang_ok {
-        diagnostics.push(make_diagnostic(element));
+        // Fill in an empty `lang=""`, or add the attribute after the tag name.
+        let edit = match &lang {
+            Some(attr) => Some(TextEdit {
+                range: node_to_range(&attr.node),
+                new_text: "lang=\"{locale}\"".to_string(),
+            }),
+            None => {
+                let mut cursor = tag.walk();
+                let name = tag.children(&mut cursor).find(|c| c.kind() == "tag_name");
+                name.map(|name| quickfix::insert_attr(&name, "lang", "{locale}"))
+            }
+        };
+        diagnostics.push(make_diagnostic(element, edit));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+/// Local names of the components that render or configure `<html>`.
+struct JsxNames<'s> {
+    /// `next/document`'s `<Html>`.
+    html: Option<&'s str>,
+    /// Components with an `htmlAttributes` prop, such as Helmet.
+    html_attributes: Vec<&'s str>,
+}
+
+fn visit_jsx(node: &Node, source: &str, names: &JsxNames, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag
+        && let Some(name) = tag.child_by_field_name("name")
+    {
+        let name_text = &source[name.byte_range()];
+        if name_text == "html" || Some(name_text) == names.html {
+            check_jsx_html(node, &tag, &name, source, diagnostics);
+        } else if names.html_attributes.contains(&name_text) {
+            check_html_attributes(&tag, source, diagnostics);
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, names, diagnostics);
+    }
+}
+
+fn check_jsx_html(
+    element: &Node,
+    tag: &Node,
+    name: &Node,
+    source: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut lang = None;
+    let mut cursor = tag.walk();
+    for child in tag.children(&mut cursor) {
+        match child.kind() {
+            // `{...props}` may well carry `lang`.
+            "jsx_expression" => return,
+            "jsx_attribute" => {
+                if let Some(("lang", value)) = jsx_attrs::jsx_attribute(&child, source) {
+                    lang = Some((child, value));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // `lang={locale}` is fine; `lang`, `lang=""` and `lang={""}` are not.
+    let lang_ok = lang
+        .as_ref()
+        .is_some_and(|(_, value)| !jsx_attrs::is_empty_jsx_value(value.as_ref(), source));
+
+    if !lang_ok {
+        let edit = match &lang {
+            Some((attr, _)) => TextEdit {
+                range: node_to_range(attr),
+                new_text: "lang=\"{locale}\"".to_string(),
+            },
+            None => quickfix::insert_attr(name, "lang", "{locale}"),
+        };
+        diagnostics.push(make_diagnostic(element, Some(edit)));
     }
 }
 
-fn make_diagnostic(node: &Node) -> Diagnostic {
+/// `htmlAttributes={{ lang: "" }}` on a Helmet-style component. Leaving `lang`
+/// out is fine; the `<html>` element keeps its own.
+fn check_html_attributes(tag: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut cursor = tag.walk();
+    let lang = tag
+        .named_children(&mut cursor)
+        .filter_map(|attr| jsx_attrs::jsx_attribute(&attr, source))
+        .find(|(name, _)| *name == "htmlAttributes")
+        .and_then(|(_, value)| jsx_attrs::jsx_object(&value?))
+        .and_then(|object| jsx_attrs::object_property(&object, source, "lang"));
+    if let Some(value) = lang
+        && jsx_attrs::is_empty_string(&value, source)
+    {
+        let edit = TextEdit {
+            range: node_to_range(&value),
+            new_text: "\"{locale}\"".to_string(),
+        };
+        diagnostics.push(make_diagnostic(&value, Some(edit)));
+    }
+}
+
+fn make_diagnostic(node: &Node, edit: Option<TextEdit>) -> Diagnostic {
     let meta = &METADATA;
-    Diagnostic {
+    let mut diagnostic = Diagnostic {
         range: node_to_range(node),
         severity: Some(DiagnosticSeverity::ERROR),
         code: Some(NumberOrString::String("html-lang".to_string())),
@@ -81,7 +210,21 @@ fn make_diagnostic(node: &Node) -> Diagnostic {
             meta.description, meta.wcag_criterion, meta.wcag_level
         ),
         ..Default::default()
+    };
+    if let Some(edit) = edit {
+        // One action per locale: configured, editor, then "en".
+        quickfix::attach(
+            &mut diagnostic,
+            Fix {
+                title: "Add lang=\"{locale}\"".to_string(),
+                edits: vec![edit],
+                per_locale: true,
+                safe: true,
+                ..Default::default()
+            },
+        );
     }
+    diagnostic
 }
 
 #[cfg(test)]
@@ -124,6 +267,72 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = HtmlLang;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    #[test]
+    fn test_next_root_layout() {
+        let ok = check_tsx(
+            r#"export default function RootLayout({ children }) { return <html lang="en"><body>{children}</body></html>; }"#,
+        );
+        assert_eq!(ok.len(), 0);
+
+        let diags = check_tsx(
+            r#"export default function RootLayout({ children }) { return <html><body>{children}</body></html>; }"#,
+        );
+        assert_eq!(diags.len(), 1);
+        let fix = quickfix::fixes(&diags[0]);
+        assert_eq!(fix[0].edits[0].new_text, r#" lang="{locale}""#);
+        assert_eq!(fix[0].edits[0].range.start.character, 63);
+    }
+
+    #[test]
+    fn test_next_document_html() {
+        let diags = check_tsx(
+            r#"import { Html as DocHtml, Head, Main } from "next/document";
+const Doc = () => <DocHtml lang={""}><Head /><body><Main /></body></DocHtml>;"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 1);
+
+        let diags = check_tsx(
+            r#"import { Html } from "next/document";
+const Doc = () => <Html lang={locale}><body /></Html>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_helmet_html_attributes_lang() {
+        let diags = check_tsx(
+            r#"import { Helmet } from "react-helmet";
+const Page = () => <Helmet htmlAttributes={{ lang: "" }}><title>Home</title></Helmet>;"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 51);
+        let fix = quickfix::fixes(&diags[0]);
+        assert_eq!(fix[0].edits[0].new_text, r#""{locale}""#);
+
+        let diags = check_tsx(
+            r#"import { Helmet } from "react-helmet-async";
+const Page = () => <><Helmet htmlAttributes={{ lang: locale }} /><Helmet title="x" /></>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_jsx_other_html_components_ignored() {
+        let diags = check_tsx(
+            r#"import { Html } from "@react-email/components";
+const Email = () => <><Html><body /></Html><html {...props} /></>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
     fn check_vue(source: &str) -> Vec<Diagnostic> {
         let mut parser = parser::create_parser(FileType::Vue).unwrap();
         let tree = parser.parse(source, None).unwrap();
@@ -142,4 +351,37 @@ mod tests {
         let diags = check_vue("<html><body></body></html>");
         assert_eq!(diags.len(), 1);
     }
+
+    #[test]
+    fn test_nuxt_html_component_without_lang_fails() {
+        let diags = check_vue(r#"<template><Html><Body /></Html></template>"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_nuxt_use_head_lang_passes() {
+        let source = r#"<script setup>
+useHead({ htmlAttrs: { lang: locale.value } })
+</script>
+<template><Html><Body /></Html></template>"#;
+        assert_eq!(check_vue(source).len(), 0);
+    }
+
+    #[test]
+    fn test_fix_inserts_lang_per_locale() {
+        let diags = check_html("<html><body></body></html>");
+        let fix = &quickfix::fixes(&diags[0])[0];
+        assert!(fix.per_locale);
+        assert_eq!(fix.edits[0].range.start.character, 5);
+        assert_eq!(fix.edits[0].new_text, r#" lang="{locale}""#);
+    }
+
+    #[test]
+    fn test_fix_fills_empty_lang() {
+        let diags = check_html(r#"<html lang=""><body></body></html>"#);
+        let fix = &quickfix::fixes(&diags[0])[0];
+        assert_eq!(fix.edits[0].range.start.character, 6);
+        assert_eq!(fix.edits[0].range.end.character, 13);
+        assert_eq!(fix.edits[0].new_text, r#"lang="{locale}""#);
+    }
 }
@@ -14,6 +14,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "2.4.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/bypass-blocks.html",
     default_severity: Severity::Error,
+    axe_rules: &["frame-title"],
 };
 
 impl Rule for IframeTitle {
@@ -1,6 +1,7 @@
 use crate::engine::node_to_range;
 use crate::parser::FileType;
 use crate::rules::html_attrs;
+use crate::rules::react_native::{self, Components};
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
 use tower_lsp_server::ls_types::*;
 use tree_sitter::Node;
@@ -14,6 +15,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.1.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/non-text-content.html",
     default_severity: Severity::Error,
+    axe_rules: &["image-alt"],
 };
 
 impl Rule for ImgAlt {
@@ -24,7 +26,8 @@ impl Rule for ImgAlt {
     fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
         if file_type.is_jsx_like() {
-            visit_jsx(root, source, &mut diagnostics);
+            let native = react_native::components(root, source);
+            visit_jsx(root, source, native.as_ref(), &mut diagnostics);
         } else {
             visit_html(root, source, &mut diagnostics);
         }
@@ -62,15 +65,23 @@ fn check_html_element(element: &Node, source: &str, diagnostics: &mut Vec<Diagno
     }
 }
 
-fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+fn visit_jsx(
+    node: &Node,
+    source: &str,
+    native: Option<&Components>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
     if node.kind() == "jsx_self_closing_element" {
         check_jsx_element(node, source, diagnostics);
     }
+    if let Some(native) = native {
+        check_native_image(node, source, native, diagnostics);
+    }
 
     // Recurse into children
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        visit_jsx(&child, source, diagnostics);
+        visit_jsx(&child, source, native, diagnostics);
     }
 }
 
@@ -105,6 +116,48 @@ fn check_jsx_element(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic
     }
 }
 
+/// A React Native `<Image>`, or an element with `accessibilityRole="image"`,
+/// needs an `accessibilityLabel` or `alt` unless it's hidden from assistive
+/// technology.
+fn check_native_image(
+    node: &Node,
+    source: &str,
+    native: &Components,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => *node,
+        "jsx_element" => match node.child(0).filter(|c| c.kind() == "jsx_opening_element") {
+            Some(tag) => tag,
+            None => return,
+        },
+        _ => return,
+    };
+    let name = tag
+        .child_by_field_name("name")
+        .map_or("", |n| &source[n.byte_range()]);
+    let attrs = react_native::tag_attrs(&tag, source);
+    let role = react_native::role(&attrs, source);
+    let is_image = match role.as_deref() {
+        Some(role) => role == "image",
+        None => native.images.contains(&name),
+    };
+    if !is_image
+        || react_native::is_hidden(&attrs, source)
+        || react_native::has_label(&attrs, source)
+        || attrs.iter().any(|(n, _)| *n == "alt")
+    {
+        return;
+    }
+
+    let mut diagnostic = make_diagnostic(node);
+    diagnostic.message = format!(
+        "This image has no `accessibilityLabel` or `alt`; give a decorative one `accessible={{false}}`. {}",
+        diagnostic.message
+    );
+    diagnostics.push(diagnostic);
+}
+
 fn make_diagnostic(node: &Node) -> Diagnostic {
     let meta = &METADATA;
     Diagnostic {
@@ -218,4 +271,34 @@ mod tests {
         let diags = check_tsx(r#"const App = () => <img src="photo.jpg" alt="A photo" />;"#);
         assert_eq!(diags.len(), 0);
     }
+
+    #[test]
+    fn test_react_native_image() {
+        let diags = check_tsx(
+            r#"import { Image, View } from "react-native";
+const App = () => (
+  <View>
+    <Image source={logo} />
+    <Image source={logo} accessibilityLabel="Acme" />
+    <Image source={logo} alt="Acme" />
+    <Image source={divider} accessible={false} />
+    <Image source={divider} importantForAccessibility="no" />
+    <View accessibilityRole="image" accessibilityLabel="Chart" />
+    <View accessibilityRole="image"><Bar /></View>
+  </View>
+);"#,
+        );
+        let lines: Vec<u32> = diags.iter().map(|d| d.range.start.line).collect();
+        assert_eq!(lines, [3, 9]);
+        assert!(diags[0].message.starts_with("This image has no `accessibilityLabel`"));
+    }
+
+    #[test]
+    fn test_image_not_from_react_native_ignored() {
+        let diags = check_tsx(
+            r#"import Image from "next/image";
+const App = () => <Image src={logo} accessibilityRole="image" />;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
 }
@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct InputListValid;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "input-list-valid",
+    description: "list attributes must refer to a <datalist>, and aria-autocomplete must not deny its suggestions",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// `aria-autocomplete` values that say there is no list of suggestions.
+const NO_LIST_VALUES: &[&str] = &["none", "inline"];
+
+/// What an id in the file belongs to.
+#[derive(PartialEq)]
+enum Target {
+    Datalist,
+    /// Some other element, by tag name.
+    Element(String),
+    /// A component, which may render a `<datalist>`.
+    Component,
+}
+
+/// An `<input>` with a static, non-empty `list`.
+struct Input<'a> {
+    list: Node<'a>,
+    id: String,
+    /// The static `aria-autocomplete` attribute and its value.
+    autocomplete: Option<(Node<'a>, String)>,
+}
+
+#[derive(Default)]
+struct Collected<'a> {
+    ids: HashMap<String, Target>,
+    /// Some element gets its id at runtime, so a missing id may exist.
+    runtime_ids: bool,
+    inputs: Vec<Input<'a>>,
+}
+
+impl Collected<'_> {
+    fn add_id(&mut self, id: &str, target: Target) {
+        let id = id.trim();
+        if id.is_empty() {
+            return;
+        }
+        // With duplicate ids (a problem of their own), a datalist wins.
+        if target == Target::Datalist {
+            self.ids.insert(id.to_string(), target);
+        } else {
+            self.ids.entry(id.to_string()).or_insert(target);
+        }
+    }
+}
+
+impl Rule for InputListValid {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut collected = Collected::default();
+        if file_type.is_jsx_like() {
+            collect_jsx(root, source, &mut collected);
+        } else {
+            collect_html(root, source, &mut collected);
+        }
+        check_inputs(&collected)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn collect_html<'a>(node: &Node<'a>, source: &str, collected: &mut Collected<'a>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source)
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let attrs = html_attrs::attrs(&tag, source);
+        let static_attr = |attr: &str| {
+            attrs
+                .iter()
+                .find(|a| a.name_eq(attr) && !a.bound)
+                .and_then(|a| a.value.clone().map(|v| (a.node, v)))
+        };
+        if attrs.iter().any(|a| a.bound && a.name_eq("id")) {
+            collected.runtime_ids = true;
+        }
+        if let Some((_, id)) = static_attr("id") {
+            collected.add_id(&id, target_of(&name));
+        }
+        if name == "input"
+            && let Some((list, id)) = static_attr("list")
+            && !id.trim().is_empty()
+        {
+            collected.inputs.push(Input {
+                list,
+                id: id.trim().to_string(),
+                autocomplete: static_attr("aria-autocomplete"),
+            });
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_html(&child, source, collected);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn collect_jsx<'a>(node: &Node<'a>, source: &str, collected: &mut Collected<'a>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let component = !name.starts_with(|c: char| c.is_ascii_lowercase()) || name.contains('.');
+        let mut list = None;
+        let mut autocomplete = None;
+        let mut cursor = tag.walk();
+        for attr in tag.named_children(&mut cursor) {
+            let Some((attr_name, value)) = jsx_attrs::jsx_attribute(&attr, source) else {
+                continue;
+            };
+            let text = value
+                .filter(|v| v.kind() == "string")
+                .map(|v| source[v.byte_range()].trim_matches(['"', '\'']).to_string());
+            match (attr_name, text) {
+                ("id", Some(id)) if component => collected.add_id(&id, Target::Component),
+                ("id", Some(id)) => collected.add_id(&id, target_of(name)),
+                ("id", None) => collected.runtime_ids = true,
+                ("list", Some(id)) if !id.trim().is_empty() => list = Some((attr, id)),
+                ("aria-autocomplete", Some(value)) => autocomplete = Some((attr, value)),
+                _ => {}
+            }
+        }
+        if name == "input"
+            && let Some((list, id)) = list
+        {
+            collected.inputs.push(Input {
+                list,
+                id: id.trim().to_string(),
+                autocomplete,
+            });
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_jsx(&child, source, collected);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn target_of(name: &str) -> Target {
+    match name {
+        "datalist" => Target::Datalist,
+        _ => Target::Element(name.to_string()),
+    }
+}
+
+fn check_inputs(collected: &Collected) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for input in &collected.inputs {
+        let id = &input.id;
+        let problem = match collected.ids.get(id) {
+            Some(Target::Element(tag)) => {
+                format!("`list` refers to \"{id}\", a `<{tag}>`, not a `<datalist>`")
+            }
+            None if !collected.runtime_ids => {
+                format!("`list` refers to \"{id}\", but no element has that id")
+            }
+            _ => {
+                let Some((node, value)) = &input.autocomplete else {
+                    continue;
+                };
+                let value = value.trim().to_ascii_lowercase();
+                if !NO_LIST_VALUES.contains(&value.as_str()) {
+                    continue;
+                }
+                diagnostics.push(make_diagnostic(
+                    node,
+                    &format!(
+                        "aria-autocomplete=\"{value}\" on an input with a `<datalist>`: use \"list\", or \"both\" if it also completes inline"
+                    ),
+                ));
+                continue;
+            }
+        };
+        diagnostics.push(make_diagnostic(&input.list, &problem));
+    }
+    diagnostics
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = InputListValid;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_valid_lists_pass() {
+        let source = r#"<input list="cities" aria-autocomplete="list">
+<input list=" cities ">
+<input list="">
+<datalist id="cities"><option value="Berlin"></datalist>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_broken_lists_fail() {
+        let source = r#"<input list="towns">
+<input list="menu">
+<ul id="menu"></ul>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 2);
+        assert!(
+            diags[0]
+                .message
+                .contains("\"towns\", but no element has that id")
+        );
+        assert_eq!(diags[0].range.start.character, 7);
+        assert!(diags[1].message.contains("a `<ul>`, not a `<datalist>`"));
+    }
+
+    #[test]
+    fn test_autocomplete_contradicting_datalist_fails() {
+        let source = r#"<input list="c" aria-autocomplete="none">
+<input list="c" aria-autocomplete="Inline">
+<input list="c" aria-autocomplete="both">
+<datalist id="c"></datalist>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.character, 16);
+        assert!(diags[0].message.contains("aria-autocomplete=\"none\""));
+        assert!(diags[1].message.contains("aria-autocomplete=\"inline\""));
+    }
+
+    #[test]
+    fn test_runtime_ids_skip_missing() {
+        let source =
+            r#"<template><input list="towns"><datalist :id="listId"></datalist></template>"#;
+        assert!(check(source, FileType::Vue).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const F = () => (
+  <>
+    <input list="cities" />
+    <input list="sizes" aria-autocomplete="none" />
+    <input list="missing" />
+    <input list={listId} />
+    <datalist id="cities"></datalist>
+    <Suggestions id="sizes" />
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 2);
+        assert!(diags[0].message.contains("aria-autocomplete=\"none\""));
+        assert!(diags[1].message.contains("\"missing\""));
+    }
+}
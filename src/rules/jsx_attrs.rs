@@ -0,0 +1,151 @@
+//! Attribute helpers for the JSX tree-sitter grammar, the counterpart of
+//! [`crate::rules::html_attrs`] for JSX and TSX files.
+//!
+//! They read an attribute's name and raw value node, tell which attributes
+//! are event handlers across React, Solid and Qwik, and say whether a value
+//! is statically empty, so a rule needn't walk `jsx_attribute` nodes itself.
+
+use tree_sitter::Node;
+
+/// Whether `node` is a string literal (or a template string without
+/// substitutions) holding nothing but whitespace.
+pub fn is_empty_string(node: &Node, source: &str) -> bool {
+    match node.kind() {
+        "string" => source[node.byte_range()]
+            .trim_matches(['"', '\''])
+            .trim()
+            .is_empty(),
+        "template_string" => {
+            let mut cursor = node.walk();
+            let substitution = node
+                .children(&mut cursor)
+                .any(|c| c.kind() == "template_substitution");
+            !substitution
+                && source[node.byte_range()]
+                    .trim_matches('`')
+                    .trim()
+                    .is_empty()
+        }
+        _ => false,
+    }
+}
+
+/// The name and value of a `jsx_attribute`; the value is `None` for a bare
+/// attribute such as `<html lang>`.
+pub fn jsx_attribute<'a, 's>(
+    attr: &Node<'a>,
+    source: &'s str,
+) -> Option<(&'s str, Option<Node<'a>>)> {
+    if attr.kind() != "jsx_attribute" {
+        return None;
+    }
+    let mut cursor = attr.walk();
+    let mut parts = attr.named_children(&mut cursor);
+    let name = parts.next()?;
+    Some((&source[name.byte_range()], parts.next()))
+}
+
+/// The DOM event a JSX attribute listens to, lowercased, if it's a handler:
+/// React's `onClick` (or `onclick`), Solid's `on:click` and
+/// `oncapture:click`, and Qwik's `onClick$`. Qwik's `document:onClick$` and
+/// `window:onClick$` listen on the page rather than the element, so they
+/// aren't the element's handlers.
+pub fn jsx_event(name: &str) -> Option<String> {
+    let event = match name.split_once(':') {
+        Some(("on" | "oncapture", event)) => event,
+        Some(_) => return None,
+        None => name.strip_suffix('$').unwrap_or(name).strip_prefix("on")?,
+    };
+    (!event.is_empty() && event.chars().all(|c| c.is_ascii_alphabetic()))
+        .then(|| event.to_ascii_lowercase())
+}
+
+/// Whether a JSX attribute value is missing or statically empty: `attr`,
+/// `attr=""`, `attr={""}` or `attr={/* comment */}`.
+pub fn is_empty_jsx_value(value: Option<&Node>, source: &str) -> bool {
+    match value {
+        None => true,
+        Some(value) if value.kind() == "jsx_expression" => {
+            let mut cursor = value.walk();
+            value
+                .named_children(&mut cursor)
+                .all(|expr| expr.kind() == "comment" || is_empty_string(&expr, source))
+        }
+        Some(value) => is_empty_string(value, source),
+    }
+}
+
+/// The object literal in a `{{ … }}` JSX attribute value.
+pub fn jsx_object<'a>(value: &Node<'a>) -> Option<Node<'a>> {
+    if value.kind() != "jsx_expression" {
+        return None;
+    }
+    let mut cursor = value.walk();
+    value
+        .named_children(&mut cursor)
+        .find(|expr| expr.kind() == "object")
+}
+
+/// The value of property `key` in an object literal.
+pub fn object_property<'a>(object: &Node<'a>, source: &str, key: &str) -> Option<Node<'a>> {
+    if object.kind() != "object" {
+        return None;
+    }
+    let mut cursor = object.walk();
+    object.children(&mut cursor).find_map(|pair| {
+        let name = pair.child_by_field_name("key")?;
+        let name = source[name.byte_range()].trim_matches(['"', '\'']);
+        (pair.kind() == "pair" && name == key)
+            .then(|| pair.child_by_field_name("value"))
+            .flatten()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{self, FileType};
+
+    fn parse(source: &str) -> tree_sitter::Tree {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_jsx_event() {
+        assert_eq!(jsx_event("onClick").as_deref(), Some("click"));
+        assert_eq!(jsx_event("onkeydown").as_deref(), Some("keydown"));
+        assert_eq!(jsx_event("on:click").as_deref(), Some("click"));
+        assert_eq!(jsx_event("oncapture:keyUp").as_deref(), Some("keyup"));
+        assert_eq!(jsx_event("onKeyDown$").as_deref(), Some("keydown"));
+        assert_eq!(jsx_event("document:onClick$"), None);
+        assert_eq!(jsx_event("xlink:href"), None);
+        assert_eq!(jsx_event("className"), None);
+        assert_eq!(jsx_event("on"), None);
+    }
+
+    #[test]
+    fn test_jsx_values() {
+        let src = r#"const a = <Helmet title="" lang={''} dir={/* x */} htmlAttributes={{ lang: "en" }} defer />;"#;
+        let tree = parse(src);
+        let element = tree
+            .root_node()
+            .named_descendant_for_byte_range(10, 11)
+            .unwrap();
+        let mut cursor = element.walk();
+        let attrs: Vec<_> = element
+            .named_children(&mut cursor)
+            .filter_map(|a| jsx_attribute(&a, src))
+            .collect();
+        let names: Vec<_> = attrs.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["title", "lang", "dir", "htmlAttributes", "defer"]);
+        let empty: Vec<_> = attrs
+            .iter()
+            .map(|(_, value)| is_empty_jsx_value(value.as_ref(), src))
+            .collect();
+        assert_eq!(empty, [true, true, true, false, true]);
+        let object = jsx_object(attrs[3].1.as_ref().unwrap()).unwrap();
+        let lang = object_property(&object, src, "lang").unwrap();
+        assert!(!is_empty_string(&lang, src));
+    }
+}
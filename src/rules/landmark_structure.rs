@@ -0,0 +1,265 @@
+use crate::analysis::{Analysis, Element};
+use crate::config::Config;
+use crate::engine::{node_to_range, related_location};
+use crate::parser::FileType;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct LandmarkStructure;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "landmark-structure",
+    description: "Banner, main and contentinfo landmarks must be unique, and top-level landmarks must not be nested in others",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.3.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[
+        "landmark-banner-is-top-level",
+        "landmark-complementary-is-top-level",
+        "landmark-contentinfo-is-top-level",
+        "landmark-main-is-top-level",
+        "landmark-no-duplicate-banner",
+        "landmark-no-duplicate-contentinfo",
+        "landmark-no-duplicate-main",
+    ],
+};
+
+/// Landmarks a page has at most one of.
+const UNIQUE_ROLES: &[&str] = &["banner", "main", "contentinfo"];
+
+/// Landmarks that belong at the top level of the page.
+const TOP_LEVEL_ROLES: &[&str] = &["banner", "main", "contentinfo", "complementary"];
+
+impl Rule for LandmarkStructure {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// How many banners a page has is a matter of the whole page.
+    fn page_level(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        let elements = analysis.elements();
+        let mut diagnostics = Vec::new();
+        for (i, role) in analysis.landmarks() {
+            if !TOP_LEVEL_ROLES.contains(&role) {
+                continue;
+            }
+            let mut current = elements[i].parent;
+            while let Some(j) = current {
+                if let Some(outer) = analysis.landmark(j) {
+                    diagnostics.push(make_diagnostic(
+                        &elements[i].tag,
+                        &format!(
+                            "The {role} landmark {} is inside the {outer} landmark {}: move it to the top level of the page",
+                            describe(&elements[i], role),
+                            describe(&elements[j], outer)
+                        ),
+                        Vec::new(),
+                    ));
+                    break;
+                }
+                current = elements[j].parent;
+            }
+        }
+
+        // Only in a full document are its landmarks all of the page's.
+        if analysis.is_page() {
+            for unique in UNIQUE_ROLES {
+                let all: Vec<_> = analysis
+                    .landmarks()
+                    .filter(|(_, role)| role == unique)
+                    .map(|(i, _)| i)
+                    .collect();
+                for &i in all.iter().skip(1) {
+                    let others = all
+                        .iter()
+                        .filter(|&&j| j != i)
+                        .map(|&j| {
+                            related_location(
+                                &elements[j].tag,
+                                &format!("Another {unique} landmark"),
+                            )
+                        })
+                        .collect();
+                    diagnostics.push(make_diagnostic(
+                        &elements[i].tag,
+                        &format!(
+                            "The page has {} {unique} landmarks: {}",
+                            all.len(),
+                            duplicate_advice(unique)
+                        ),
+                        others,
+                    ));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn describe(element: &Element, role: &str) -> String {
+    match element.role {
+        Some(_) => format!("<{} role=\"{role}\">", element.name),
+        None => format!("<{}>", element.name),
+    }
+}
+
+fn duplicate_advice(role: &str) -> &'static str {
+    match role {
+        "banner" => {
+            "keep one `<header>` for the site header, and put the others inside the `<article>` or `<section>` they head"
+        }
+        "contentinfo" => {
+            "keep one `<footer>` for the site footer, and put the others inside the `<article>` or `<section>` they belong to"
+        }
+        _ => "merge them into one `<main>`, or hide all but the current one with `hidden`",
+    }
+}
+
+fn make_diagnostic(
+    node: &Node,
+    problem: &str,
+    related: Vec<DiagnosticRelatedInformation>,
+) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        related_information: (!related.is_empty()).then_some(related),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = LandmarkStructure;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_well_formed_page_passes() {
+        let source = r#"<html><body>
+<header><nav>Menu</nav></header>
+<main>
+  <article><header><h1>Post</h1></header><footer>By me</footer></article>
+  <section aria-label="Related"><aside>Links</aside></section>
+</main>
+<aside>Ads</aside>
+<footer>Contact</footer>
+<dialog><main>Modal</main></dialog>
+<main hidden>Other view</main>
+</body></html>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_landmarks_fail() {
+        let source = r#"<html><body>
+<header>Site</header>
+<div role="banner">Promo</div>
+<main>A</main>
+<main>B</main>
+<footer>One</footer>
+</body></html>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("The page has 2 banner landmarks")
+        );
+        assert_eq!(diags[0].related_information.as_ref().unwrap().len(), 1);
+        assert!(
+            diags[1]
+                .message
+                .starts_with("The page has 2 main landmarks")
+        );
+    }
+
+    #[test]
+    fn test_nested_landmarks_fail() {
+        let source = r#"<html><body>
+<header><main>Content</main></header>
+<main><aside>Sidebar</aside><div role="contentinfo">x</div></main>
+</body></html>"#;
+        let diags = check_html(source);
+        // The second `<main>` is also a duplicate.
+        assert_eq!(diags.len(), 4);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("The main landmark <main> is inside the banner landmark <header>")
+        );
+        assert!(diags[1].message.contains("complementary landmark <aside>"));
+        assert!(diags[2].message.contains("<div role=\"contentinfo\">"));
+        assert!(
+            diags[3]
+                .message
+                .starts_with("The page has 2 main landmarks")
+        );
+    }
+
+    #[test]
+    fn test_fragments_only_check_nesting() {
+        // Without the page, a `<header>` may be inside an `<article>`.
+        let source = r#"<header>A</header><header>B</header><nav><main>x</main></nav>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert!(
+            diags[0]
+                .message
+                .contains("inside the navigation landmark <nav>")
+        );
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Layout = () => (
+  <>
+    <header><Logo /></header>
+    <nav role="navigation"><aside role="complementary">x</aside></nav>
+    <main {...props}><div role="main" /></main>
+    <Main><aside>y</aside></Main>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 3);
+        assert!(diags[0].message.contains("<nav role=\"navigation\">"));
+    }
+}
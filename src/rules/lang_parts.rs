@@ -0,0 +1,204 @@
+use crate::analysis::{Analysis, Element};
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::quickfix::{self, Fix};
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct LangParts;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "lang-parts",
+    description: "lang attributes on parts of a page must name a language that differs from the surrounding one",
+    wcag_level: WcagLevel::AA,
+    wcag_criterion: "3.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/language-of-parts.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+impl Rule for LangParts {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        let elements = analysis.elements();
+        let mut diagnostics = Vec::new();
+        for element in elements {
+            // `html-lang` checks the page's own language.
+            if element.name == "html" {
+                continue;
+            }
+            let Some(attr) = element.attr("lang").filter(|a| !a.bound) else {
+                continue;
+            };
+            let lang = attr.value.as_deref().map_or("", str::trim);
+            if lang.is_empty() {
+                diagnostics.push(make_diagnostic(
+                    &attr.node,
+                    "Empty lang marks the language of this part as unknown: set it to the language of its text, or remove it to inherit the surrounding one",
+                ));
+                continue;
+            }
+            let Some(outer) = enclosing_lang(elements, element) else {
+                continue;
+            };
+            if outer.eq_ignore_ascii_case(lang) {
+                let mut diagnostic = make_diagnostic(
+                    &attr.node,
+                    &format!(
+                        "lang=\"{lang}\" repeats the language of the enclosing element, so it marks no change of language"
+                    ),
+                );
+                quickfix::attach(
+                    &mut diagnostic,
+                    Fix {
+                        title: "Remove redundant 'lang'".to_string(),
+                        edits: vec![quickfix::remove_node(&attr.node)],
+                        safe: true,
+                        ..Default::default()
+                    },
+                );
+                diagnostics.push(diagnostic);
+            }
+        }
+        diagnostics
+    }
+}
+
+/// The static `lang` of the nearest ancestor that has one, or `None` if
+/// there's none or it's set at runtime.
+fn enclosing_lang<'b>(elements: &'b [Element], element: &Element) -> Option<&'b str> {
+    let mut parent = element.parent;
+    while let Some(p) = parent {
+        if let Some(attr) = elements[p].attr("lang") {
+            if attr.bound {
+                return None;
+            }
+            return attr.value.as_deref().map(str::trim);
+        }
+        parent = elements[p].parent;
+    }
+    None
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = LangParts;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_changes_of_language_pass() {
+        let source = r#"<html lang="en">
+<body>
+  <p>The French say <span lang="fr">bonjour</span>.</p>
+  <blockquote lang="de"><p>Guten Tag, <q lang="en-GB">good day</q></p></blockquote>
+  <p lang="en-US">Color</p>
+</body>
+</html>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_redundant_lang_fails() {
+        let source = r#"<section lang="fr">
+  <p>Bonjour, <span lang="FR">monde</span></p>
+  <div><p lang="fr">Au revoir</p></div>
+</section>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 2);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("lang=\"FR\" repeats the language")
+        );
+        assert_eq!(diags[0].range.start.line, 1);
+        assert_eq!(diags[1].range.start.line, 2);
+
+        let fixes = quickfix::fixes(&diags[0]);
+        assert_eq!(fixes.len(), 1);
+        assert!(fixes[0].safe);
+        let fixed = quickfix::apply(source, &fixes[0].edits).unwrap();
+        assert!(fixed.contains("<span>monde</span>"));
+    }
+
+    #[test]
+    fn test_empty_lang_fails() {
+        let source = r#"<html lang="">
+<body>
+  <p lang="">Text</p>
+  <span lang>More</span>
+</body>
+</html>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 2);
+        assert!(diags[0].message.starts_with("Empty lang"));
+        assert_eq!(diags[0].range.start.line, 2);
+        assert_eq!(diags[1].range.start.line, 3);
+        assert!(quickfix::fixes(&diags[0]).is_empty());
+    }
+
+    #[test]
+    fn test_vue_bound_lang_skipped() {
+        let source = r#"<template>
+  <div :lang="locale">
+    <p lang="en">Hello</p>
+    <p :lang="other">Hi</p>
+  </div>
+</template>"#;
+        assert!(check(source, FileType::Vue).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Quote = () => (
+  <article lang="es">
+    <p lang="es">Hola</p>
+    <p lang={locale}>Hi</p>
+    <p lang="">?</p>
+  </article>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert_eq!(diags[1].range.start.line, 4);
+    }
+}
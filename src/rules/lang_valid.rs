@@ -16,6 +16,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "3.1.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/language-of-page.html",
     default_severity: Severity::Error,
+    axe_rules: &["html-lang-valid", "valid-lang"],
 };
 
 static VALID_LANG_SUBTAGS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
@@ -0,0 +1,225 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct LayoutTable;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "layout-table",
+    description: "Layout tables must not use data table markup",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.3.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Roles that turn a `<table>` into a layout table.
+const PRESENTATIONAL_ROLES: &[&str] = &["presentation", "none"];
+
+/// Whether a `role` value makes a table a layout table.
+pub(crate) fn is_presentational(role: Option<&str>) -> bool {
+    role.and_then(|r| r.split_whitespace().next())
+        .is_some_and(|r| {
+            PRESENTATIONAL_ROLES
+                .iter()
+                .any(|p| r.eq_ignore_ascii_case(p))
+        })
+}
+
+impl Rule for LayoutTable {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, None, &mut diagnostics);
+        } else {
+            visit_html(root, source, None, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+/// `layout` is the role of the enclosing layout table, while inside one.
+fn visit_html(node: &Node, source: &str, layout: Option<&str>, diagnostics: &mut Vec<Diagnostic>) {
+    let mut layout = layout.map(str::to_string);
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source)
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let attrs = html_attrs::attrs(&tag, source);
+        if name == "table" {
+            let role = attrs
+                .iter()
+                .find(|a| a.name_eq("role") && !a.bound)
+                .and_then(|a| a.value.clone());
+            // A nested table is a table of its own.
+            layout = role.filter(|r| is_presentational(Some(r)));
+        }
+        if let Some(role) = &layout {
+            if matches!(name.as_str(), "th" | "caption") {
+                diagnostics.push(make_diagnostic(&tag, &format!("`<{name}>`"), role));
+            }
+            for attr in &attrs {
+                let table_attr = name == "table" && attr.name_eq("summary");
+                if table_attr || attr.name_eq("scope") {
+                    let what = format!("`{}`", attr.name_lower());
+                    diagnostics.push(make_diagnostic(&attr.node, &what, role));
+                }
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, layout.as_deref(), diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, layout: Option<&str>, diagnostics: &mut Vec<Diagnostic>) {
+    let mut layout = layout.map(str::to_string);
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let mut attrs = Vec::new();
+        let mut cursor = tag.walk();
+        for child in tag.named_children(&mut cursor) {
+            if let Some((attr, value)) = jsx_attrs::jsx_attribute(&child, source) {
+                let text = value
+                    .filter(|v| v.kind() == "string")
+                    .map(|v| source[v.byte_range()].trim_matches(['"', '\'']).to_string());
+                attrs.push((attr, text, child));
+            }
+        }
+        if name == "table" {
+            let role = attrs
+                .iter()
+                .find(|(attr, _, _)| *attr == "role")
+                .and_then(|(_, text, _)| text.clone());
+            layout = role.filter(|r| is_presentational(Some(r)));
+        }
+        if let Some(role) = &layout {
+            if matches!(name, "th" | "caption") {
+                diagnostics.push(make_diagnostic(&tag, &format!("`<{name}>`"), role));
+            }
+            for (attr, _, node) in &attrs {
+                if (name == "table" && *attr == "summary") || *attr == "scope" {
+                    diagnostics.push(make_diagnostic(node, &format!("`{attr}`"), role));
+                }
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, layout.as_deref(), diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn make_diagnostic(node: &Node, what: &str, role: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{what} in a table with role=\"{}\": remove it, or drop the role if this is a data table. {} [WCAG {} Level {:?}]",
+            role.trim(),
+            meta.description,
+            meta.wcag_criterion,
+            meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = LayoutTable;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_data_markup_in_layout_table_fails() {
+        let source = r#"<table role="presentation" summary="Layout">
+  <caption>Page</caption>
+  <tr><th>Nav</th><td scope="col">Main</td></tr>
+</table>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 4);
+        assert_eq!(diags[0].range.start.character, 27);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("`summary` in a table with role=\"presentation\"")
+        );
+        assert!(diags[1].message.starts_with("`<caption>`"));
+        assert!(diags[2].message.starts_with("`<th>`"));
+        assert!(diags[3].message.starts_with("`scope`"));
+    }
+
+    #[test]
+    fn test_plain_layout_and_data_tables_pass() {
+        let source = r#"<table role="none"><tr><td>Nav</td><td>
+  <table><caption>Prices</caption><tr><th scope="col">Item</th></tr></table>
+</td></tr></table>
+<table summary="Prices"><tr><th scope="row">A</th></tr></table>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const L = () => (
+  <table role="presentation">
+    <tbody><tr><th>Nav</th><td>{children}</td></tr></tbody>
+  </table>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert!(diags[0].message.contains("role=\"presentation\""));
+    }
+}
@@ -0,0 +1,381 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct ListLayout;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "list-layout",
+    description: "Lists should group related items, not lay out unrelated content",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.3.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Single-item lists are fine on their own (a filtered result, a one-entry
+/// nav); this many in one document suggests lists used as layout boxes.
+const SINGLE_ITEM_LISTS: usize = 3;
+
+/// What a list's direct children tell us.
+struct List<'a> {
+    node: Node<'a>,
+    /// Number of `<li>` children.
+    items: usize,
+    /// Children rendered at runtime (`v-for`, `*ngFor`, `{items.map(…)}`,
+    /// `{#each}`, `@for`), so the real item count is unknown.
+    dynamic: bool,
+    /// Every item holds nothing but empty `<div>`s.
+    layout_items: bool,
+}
+
+impl Rule for ListLayout {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Whether a list is "layout" is a judgement call, so this only runs when
+    /// enabled explicitly.
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    /// Counts single-item lists across the whole file.
+    fn page_level(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut lists = Vec::new();
+        if file_type.is_jsx_like() {
+            collect_jsx(root, source, &mut lists);
+        } else {
+            collect_html(root, source, &mut lists);
+        }
+        check_lists(&lists)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn collect_html<'a>(node: &Node<'a>, source: &str, lists: &mut Vec<List<'a>>) {
+    if node.kind() == "element"
+        && html_attrs::element_tag_name(node, source)
+            .is_some_and(|n| n.eq_ignore_ascii_case("ul") || n.eq_ignore_ascii_case("ol"))
+    {
+        let mut list = List {
+            node: *node,
+            items: 0,
+            dynamic: false,
+            layout_items: true,
+        };
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "element" => {
+                    let is_item = html_attrs::element_tag_name(&child, source)
+                        .is_some_and(|n| n.eq_ignore_ascii_case("li"));
+                    if !is_item {
+                        continue;
+                    }
+                    list.items += 1;
+                    list.dynamic |= html_attrs::element_has_attr(&child, source, "v-for")
+                        || html_attrs::element_has_attr(&child, source, "*ngFor");
+                    list.layout_items &= html_only_empty_divs(&child, source);
+                }
+                // Svelte's `{#each}`, Angular's `@for` and other template syntax.
+                "text" if !source[child.byte_range()].trim().is_empty() => list.dynamic = true,
+                _ => {}
+            }
+        }
+        lists.push(list);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_html(&child, source, lists);
+    }
+}
+
+/// Whether an element contains at least one empty `<div>` and nothing else.
+fn html_only_empty_divs(element: &Node, source: &str) -> bool {
+    let mut found_div = false;
+    let mut cursor = element.walk();
+    for child in element.children(&mut cursor) {
+        match child.kind() {
+            "text" if !source[child.byte_range()].trim().is_empty() => return false,
+            "element" if !html_is_empty_div(&child, source) => return false,
+            "element" => found_div = true,
+            _ => {}
+        }
+    }
+    found_div
+}
+
+/// A `<div>` with no text and no other elements, at any depth.
+fn html_is_empty_div(element: &Node, source: &str) -> bool {
+    let is_div = html_attrs::element_tag_name(element, source)
+        .is_some_and(|n| n.eq_ignore_ascii_case("div"));
+    if !is_div || html_attrs::has_dynamic_content(&html_attrs::element_attrs(element, source)) {
+        return false;
+    }
+    let mut cursor = element.walk();
+    element
+        .children(&mut cursor)
+        .all(|child| match child.kind() {
+            "text" => source[child.byte_range()].trim().is_empty(),
+            "element" => html_is_empty_div(&child, source),
+            _ => true,
+        })
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn collect_jsx<'a>(node: &Node<'a>, source: &str, lists: &mut Vec<List<'a>>) {
+    if node.kind() == "jsx_element" && matches!(jsx_name(node, source), Some("ul" | "ol")) {
+        let mut list = List {
+            node: *node,
+            items: 0,
+            dynamic: false,
+            layout_items: true,
+        };
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "jsx_element" | "jsx_self_closing_element"
+                    if jsx_name(&child, source) == Some("li") =>
+                {
+                    list.items += 1;
+                    list.layout_items &= jsx_only_empty_divs(&child, source);
+                }
+                "jsx_expression" => list.dynamic = true,
+                _ => {}
+            }
+        }
+        lists.push(list);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_jsx(&child, source, lists);
+    }
+}
+
+/// The tag name of a `jsx_element`/`jsx_self_closing_element`.
+fn jsx_name<'s>(node: &Node, source: &'s str) -> Option<&'s str> {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => *node,
+        "jsx_element" => node.child(0)?,
+        _ => return None,
+    };
+    let mut cursor = tag.walk();
+    tag.children(&mut cursor)
+        .find(|c| c.kind() == "identifier")
+        .map(|c| &source[c.byte_range()])
+}
+
+fn jsx_only_empty_divs(element: &Node, source: &str) -> bool {
+    let mut found_div = false;
+    let mut cursor = element.walk();
+    for child in element.children(&mut cursor) {
+        match child.kind() {
+            "jsx_text" if !source[child.byte_range()].trim().is_empty() => return false,
+            "jsx_expression" => return false,
+            "jsx_element" | "jsx_self_closing_element" if !jsx_is_empty_div(&child, source) => {
+                return false;
+            }
+            "jsx_element" | "jsx_self_closing_element" => found_div = true,
+            _ => {}
+        }
+    }
+    found_div
+}
+
+fn jsx_is_empty_div(element: &Node, source: &str) -> bool {
+    if jsx_name(element, source) != Some("div") {
+        return false;
+    }
+    let mut cursor = element.walk();
+    element
+        .children(&mut cursor)
+        .all(|child| match child.kind() {
+            "jsx_text" => source[child.byte_range()].trim().is_empty(),
+            "jsx_expression" => false,
+            "jsx_element" | "jsx_self_closing_element" => jsx_is_empty_div(&child, source),
+            _ => true,
+        })
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn check_lists(lists: &[List]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let single: Vec<&List> = lists
+        .iter()
+        .filter(|l| l.items == 1 && !l.dynamic)
+        .collect();
+    if single.len() >= SINGLE_ITEM_LISTS {
+        for list in &single {
+            diagnostics.push(make_diagnostic(
+                &list.node,
+                &format!(
+                    "This is one of {} lists with a single item in this file; if they only position content, use other elements",
+                    single.len()
+                ),
+            ));
+        }
+    }
+
+    for list in lists.iter().filter(|l| l.items > 0 && l.layout_items) {
+        diagnostics.push(make_diagnostic(
+            &list.node,
+            "Every item of this list holds only empty <div>s, which looks like decoration or layout rather than a list",
+        ));
+    }
+
+    diagnostics.sort_by_key(|d| (d.range.start.line, d.range.start.character));
+    diagnostics
+}
+
+fn make_diagnostic(node: &Node, detail: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{}. {} [WCAG {} Level {:?}]",
+            detail, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = ListLayout;
+        rule.check(&tree.root_node(), source, FileType::Html)
+    }
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = ListLayout;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    fn check_vue(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Vue).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = ListLayout;
+        rule.check(&tree.root_node(), source, FileType::Vue)
+    }
+
+    #[test]
+    fn test_is_opt_in() {
+        assert!(ListLayout.opt_in());
+    }
+
+    #[test]
+    fn test_repeated_single_item_lists_fail() {
+        let diags = check_html(
+            "<ul><li>A</li></ul>\n<ul><li>B</li></ul>\n<ol><li>C</li></ol>\n<ul><li>D</li><li>E</li></ul>",
+        );
+        assert_eq!(diags.len(), 3);
+        assert!(
+            diags[0]
+                .message
+                .contains("one of 3 lists with a single item")
+        );
+        assert_eq!(diags[2].range.start.line, 2);
+    }
+
+    #[test]
+    fn test_few_single_item_lists_pass() {
+        let diags = check_html("<ul><li>A</li></ul><ul><li>B</li></ul>");
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_empty_div_items_fail() {
+        let diags = check_html(
+            r#"<ul class="dots"><li><div class="dot"></div></li><li><div><div></div></div></li></ul>"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("only empty <div>s"));
+    }
+
+    #[test]
+    fn test_items_with_content_pass() {
+        let diags = check_html(
+            r#"<ul><li><div>Text</div></li><li><div><img src="a.png" alt="A"></div></li></ul>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_v_for_item_not_single() {
+        let diags = check_vue(
+            r#"<template><ul><li v-for="a in as">{{ a }}</li></ul><ul><li v-for="b in bs">{{ b }}</li></ul><ul><li v-for="c in cs">{{ c }}</li></ul></template>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_angular_ng_for_item_not_single() {
+        let item = r#"<ul><li *ngFor="let a of as">{{ a }}</li></ul>"#;
+        let diags = check_html(&item.repeat(3));
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_v_text_div_is_content() {
+        let diags = check_vue(r#"<template><ul><li><div v-text="a"></div></li></ul></template>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_single_item_lists_fail() {
+        let diags = check_tsx(
+            r#"const A = () => <><ul><li>A</li></ul><ul><li>B</li></ul><ul><li>C</li></ul></>;"#,
+        );
+        assert_eq!(diags.len(), 3);
+    }
+
+    #[test]
+    fn test_tsx_mapped_items_pass() {
+        let diags = check_tsx(
+            r#"const A = () => <><ul>{a.map(x => <li>{x}</li>)}</ul><ul><li>A</li>{more}</ul><ul><li>B</li>{more}</ul></>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_empty_div_items_fail() {
+        let diags = check_tsx(
+            r#"const A = () => <ul><li><div className="bar" /></li><li><div className="bar" /></li></ul>;"#,
+        );
+        assert_eq!(diags.len(), 1);
+    }
+}
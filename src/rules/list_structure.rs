@@ -13,6 +13,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.3.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
     default_severity: Severity::Error,
+    axe_rules: &["listitem"],
 };
 
 /// Valid parent tag names for <li> elements.
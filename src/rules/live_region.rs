@@ -0,0 +1,159 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct LiveRegion;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "live-region",
+    description: "Status messages must be in a live region so screen readers announce them",
+    wcag_level: WcagLevel::AA,
+    wcag_criterion: "4.1.3",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/status-messages.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Words in a class name or id that mark an element as a status message.
+const STATUS_WORDS: &[&str] = &["alert", "flash", "notification", "snackbar", "toast"];
+
+/// Roles that make an element a live region.
+const LIVE_ROLES: &[&str] = &["alert", "log", "marquee", "status", "timer"];
+
+impl Rule for LiveRegion {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Toasts and alerts are usually added by scripts, so only a rendered
+    /// DOM shows them.
+    fn dynamic(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if !file_type.is_jsx_like() {
+            visit_html(root, source, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source).unwrap_or("");
+        let attrs = html_attrs::attrs(&tag, source);
+        let value = |attr: &str| {
+            attrs
+                .iter()
+                .find(|a| a.name_eq(attr) && !a.bound)
+                .and_then(|a| a.value.as_deref())
+                .map(str::to_ascii_lowercase)
+        };
+        // Whatever is inside a live region is announced with it.
+        let live = name.eq_ignore_ascii_case("output")
+            || value("aria-live").is_some_and(|live| live != "off")
+            || value("role").is_some_and(|roles| {
+                roles
+                    .split_whitespace()
+                    .next()
+                    .is_some_and(|role| LIVE_ROLES.contains(&role))
+            });
+        if live {
+            return;
+        }
+        let hidden = attrs.iter().any(|a| a.name_eq("hidden"))
+            || value("aria-hidden").as_deref() == Some("true");
+        let status = value("class")
+            .into_iter()
+            .chain(value("id"))
+            .any(|names| names.split_whitespace().any(is_status_name));
+        if status && !hidden {
+            diagnostics.push(make_diagnostic(node_to_range(&tag)));
+            // One report for a toast container and the toasts in it.
+            return;
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, diagnostics);
+    }
+}
+
+/// Whether a class name or id has a status word as one of its parts:
+/// `toast`, `alert-danger`, `Toastify__toast-container`, `flash_notice`.
+fn is_status_name(name: &str) -> bool {
+    name.split(['-', '_'])
+        .any(|word| STATUS_WORDS.contains(&word))
+}
+
+fn make_diagnostic(range: Range) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "This looks like a status message but isn't in a live region: add `role=\"status\"`, `role=\"alert\"` or `aria-live` to it or its container, present before the message is. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = LiveRegion;
+        rule.check(&tree.root_node(), source, FileType::Html)
+    }
+
+    #[test]
+    fn test_status_message_without_live_region_fails() {
+        let diags = check_html(
+            r#"<div class="Toastify__toast-container"><div class="toast">Saved</div></div>
+<p class="alert alert-danger">Payment failed</p>"#,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.line, 0);
+        assert_eq!(diags[1].range.start.line, 1);
+        assert!(diags[0].message.contains("4.1.3"));
+    }
+
+    #[test]
+    fn test_live_regions_pass() {
+        let source = r#"<div aria-live="polite"><div class="toast">Saved</div></div>
+<p class="alert" role="alert">Payment failed</p>
+<output class="notification">3 results</output>
+<div id="flash" role="status"></div>
+<div class="toast" hidden></div>
+<div class="alerting-system">Not a status</div>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_live_off_fails() {
+        let diags = check_html(r#"<div class="snackbar" aria-live="off">Undo</div>"#);
+        assert_eq!(diags.len(), 1);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct MathAlt;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "math-alt",
+    description: "MathML should have a text alternative for assistive technology without MathML support",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/non-text-content.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Roles of a wrapper whose label stands in for the formula in it.
+const WRAPPER_ROLES: &[&str] = &["img", "math"];
+
+impl Rule for MathAlt {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Current screen readers mostly read MathML well; the alternative is
+    /// for those that don't, so a missing one is worth a look rather than a
+    /// failure.
+    fn informational(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, false, &mut diagnostics);
+        } else {
+            visit_html(root, source, false, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, labelled: bool, diagnostics: &mut Vec<Diagnostic>) {
+    let mut labelled = labelled;
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source).unwrap_or("");
+        let attrs: Vec<(String, Option<String>)> = html_attrs::attrs(&tag, source)
+            .into_iter()
+            .map(|a| {
+                let name = a.name_lower();
+                // A bound value is taken to be a real one.
+                let value = if a.bound {
+                    Some("{}".to_string())
+                } else {
+                    a.value
+                };
+                (name, value)
+            })
+            .collect();
+        if name.eq_ignore_ascii_case("math") {
+            if !labelled && !has_alternative(&attrs) {
+                diagnostics.push(make_diagnostic(&tag));
+            }
+            // The MathML inside is the formula itself.
+            return;
+        }
+        labelled = labelled || is_labelled_wrapper(&attrs);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, labelled, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, labelled: bool, diagnostics: &mut Vec<Diagnostic>) {
+    let mut labelled = labelled;
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let attrs = jsx_attrs(&tag, source);
+        if name == "math" {
+            // Spread props may carry the alternative.
+            if !labelled && !has_alternative(&attrs) && !has_spread(&tag) {
+                diagnostics.push(make_diagnostic(&tag));
+            }
+            return;
+        }
+        labelled = labelled || is_labelled_wrapper(&attrs);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, labelled, diagnostics);
+    }
+}
+
+/// The attributes of a JSX tag, lowercased, with their value: the text of a
+/// string, or `{}` for an expression.
+fn jsx_attrs(tag: &Node, source: &str) -> Vec<(String, Option<String>)> {
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .filter_map(|attr| jsx_attrs::jsx_attribute(&attr, source))
+        .map(|(name, value)| {
+            let value = value.map(|v| match v.kind() {
+                "string" => source[v.byte_range()].trim_matches(['"', '\'']).to_string(),
+                _ => "{}".to_string(),
+            });
+            (name.to_ascii_lowercase(), value)
+        })
+        .collect()
+}
+
+fn has_spread(tag: &Node) -> bool {
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .any(|c| c.kind() == "jsx_expression")
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn value<'a>(attrs: &'a [(String, Option<String>)], name: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(n, _)| n == name)
+        .and_then(|(_, v)| v.as_deref())
+}
+
+fn is_set(attrs: &[(String, Option<String>)], name: &str) -> bool {
+    value(attrs, name).is_some_and(|v| !v.trim().is_empty())
+}
+
+/// Whether a `<math>` with `attrs` has a text alternative of its own, or is
+/// hidden because the text around it says the same.
+fn has_alternative(attrs: &[(String, Option<String>)]) -> bool {
+    is_set(attrs, "alttext")
+        || is_set(attrs, "aria-label")
+        || is_set(attrs, "aria-labelledby")
+        || value(attrs, "aria-hidden").is_some_and(|v| v.trim() != "false")
+}
+
+/// Whether an element with `attrs` is a labelled image or math wrapper, so
+/// the formula inside has its label as its alternative.
+fn is_labelled_wrapper(attrs: &[(String, Option<String>)]) -> bool {
+    let role = value(attrs, "role").and_then(|r| r.split_whitespace().next());
+    role.is_some_and(|r| WRAPPER_ROLES.contains(&r.to_ascii_lowercase().as_str()))
+        && (is_set(attrs, "aria-label") || is_set(attrs, "aria-labelledby"))
+}
+
+fn make_diagnostic(tag: &Node) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(tag),
+        severity: Some(DiagnosticSeverity::INFORMATION),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "`<math>` has no `alttext`, `aria-label` or labelled wrapper: screen readers and braille displays without MathML support get the bare symbols. Add `alttext` with the formula in words or TeX. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = MathAlt;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    #[test]
+    fn test_math_without_alternative_is_listed() {
+        let diags = check(
+            r#"<p>Energy: <math><mi>E</mi><mo>=</mo><mi>m</mi><msup><mi>c</mi><mn>2</mn></msup></math></p>"#,
+            FileType::Html,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].severity, Some(DiagnosticSeverity::INFORMATION));
+        assert_eq!(diags[0].range.start.character, 11);
+        assert!(diags[0].message.contains("1.1.1"));
+    }
+
+    #[test]
+    fn test_alternatives_pass() {
+        let source = r#"<math alttext="E equals m c squared"><mi>E</mi></math>
+<math aria-label="x squared"><msup><mi>x</mi><mn>2</mn></msup></math>
+<math :alttext="formula.tex"><mi>y</mi></math>
+<span>x squared</span><math aria-hidden="true"><msup><mi>x</mi><mn>2</mn></msup></math>
+<span role="img" aria-label="x squared"><math><msup><mi>x</mi><mn>2</mn></msup></math></span>"#;
+        assert!(check(source, FileType::Html).is_empty());
+    }
+
+    #[test]
+    fn test_empty_alttext_and_unlabelled_wrapper_listed() {
+        let diags = check(
+            r#"<math alttext=" "><mi>x</mi></math><div role="img"><math><mi>y</mi></math></div>"#,
+            FileType::Html,
+        );
+        assert_eq!(diags.len(), 2);
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const F = () => (
+  <>
+    <math><mi>x</mi></math>
+    <math alttext={tex}><mi>y</mi></math>
+    <math {...mathProps}><mi>z</mi></math>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+    }
+}
@@ -14,6 +14,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.2.2",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/captions-prerecorded.html",
     default_severity: Severity::Warning,
+    axe_rules: &["video-caption"],
 };
 
 impl Rule for MediaCaptions {
@@ -94,12 +95,13 @@ fn is_caption_track_element(element: &Node, source: &str) -> bool {
     }
 
     html_attrs::attrs(&tag, source).iter().any(|attr| {
-        // A bound `:kind` is a runtime expression — skip the value check.
+        // A bound `:kind` is a runtime expression that may well be
+        // "captions", so it counts.
         attr.name_eq("kind")
-            && !attr.bound
-            && attr.value.as_deref().is_some_and(|val| {
-                val.eq_ignore_ascii_case("captions") || val.eq_ignore_ascii_case("subtitles")
-            })
+            && (attr.bound
+                || attr.value.as_deref().is_some_and(|val| {
+                    val.eq_ignore_ascii_case("captions") || val.eq_ignore_ascii_case("subtitles")
+                }))
     })
 }
 
@@ -140,6 +142,14 @@ mod tests {
         rule.check(&tree.root_node(), source, FileType::Vue)
     }
 
+    #[test]
+    fn test_vue_bound_track_kind_passes() {
+        let diags = check_vue(
+            r#"<template><video src="movie.mp4"><track :kind="kind" src="caps.vtt" /></video></template>"#,
+        );
+        assert_eq!(diags.len(), 0, "bound :kind may be captions, got: {diags:?}");
+    }
+
     #[test]
     fn test_vue_static_captions_track_passes() {
         let diags = check_vue(
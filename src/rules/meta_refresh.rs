@@ -14,6 +14,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "2.2.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/timing-adjustable.html",
     default_severity: Severity::Error,
+    axe_rules: &["meta-refresh"],
 };
 
 impl Rule for MetaRefresh {
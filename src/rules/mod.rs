@@ -1,50 +1,106 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
 use crate::parser::FileType;
 use tower_lsp_server::ls_types::Diagnostic;
 use tree_sitter::Node;
 
 pub mod anchor_content;
+pub mod animated_image;
 pub mod area_alt;
+pub mod aria_activedescendant;
 pub mod aria_allowed_attr;
+pub mod aria_controls_expanded;
 pub mod aria_deprecated_role;
+pub mod aria_errormessage;
+pub mod aria_expanded_controls;
 pub mod aria_hidden_body;
 pub mod aria_hidden_focus;
+pub mod aria_meter_name;
+pub mod aria_modal_siblings;
+pub mod aria_name;
+pub mod aria_owns_valid;
+pub mod aria_progressbar_name;
 pub mod aria_prohibited_attr;
 pub mod aria_props;
 pub mod aria_required_attr;
 pub mod aria_required_children;
 pub mod aria_required_parent;
 pub mod aria_role;
+pub mod aria_roledescription;
+pub mod aria_toggle_field_name;
+pub mod aria_tooltip_name;
+pub mod aria_treeitem_name;
 pub mod aria_valid_attr_value;
+pub mod audio_transcript;
 pub mod autocomplete_valid;
+pub mod autoplay_controls;
 pub mod button_name;
+pub mod bypass_blocks;
+pub mod captcha_alternative;
+pub mod carousel;
 pub mod click_events;
+pub mod color_contrast;
+pub mod composite_focus;
+pub mod consistent_nav_label;
+pub mod css;
+pub mod duplicate_id_aria;
+pub mod form_association;
 pub mod form_label;
 pub mod heading_content;
 pub mod heading_order;
+pub mod hidden_field_focus;
 pub mod html_attrs;
 pub mod html_lang;
 pub mod iframe_title;
 pub mod img_alt;
 pub mod input_image_alt;
+pub mod input_list_valid;
+pub mod jsx_attrs;
+pub mod landmark_structure;
+pub mod lang_parts;
 pub mod lang_valid;
+pub mod layout_table;
+pub mod list_layout;
 pub mod list_structure;
+pub mod live_region;
+pub mod math_alt;
 pub mod media_captions;
 pub mod meta_refresh;
 pub mod mouse_events;
 pub mod nested_interactive;
 pub mod no_access_key;
 pub mod no_autoplay;
+pub mod no_contradictory_state;
 pub mod no_distracting_elements;
 pub mod no_duplicate_id;
+pub mod no_multiple_h1;
+pub mod no_outline_removal;
 pub mod no_redundant_alt;
+pub mod no_redundant_aria_state;
 pub mod no_redundant_roles;
+pub mod nuxt;
 pub mod object_alt;
+pub mod output_association;
 pub mod page_title;
+pub mod pagination;
+pub mod patterns;
+pub mod pdf_alternative;
+pub mod progress_meter_name;
+pub mod radio_group_name;
+pub mod react_head;
+pub mod react_native;
+pub mod reduced_motion;
+pub mod region;
+pub mod role_img_alt;
 pub mod scope_attr;
+pub mod svg_img_alt;
+pub mod svg_interactive;
 pub mod tabindex;
 pub mod table_header;
+pub mod td_headers_attr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Ordered from the basic Level A to AAA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum WcagLevel {
     A,
     AA,
@@ -64,54 +120,141 @@ pub struct RuleMetadata {
     pub wcag_criterion: &'static str,
     pub wcag_url: &'static str,
     pub default_severity: Severity,
+    /// The axe-core rules that check the same thing, for correlating
+    /// findings with axe scans.
+    pub axe_rules: &'static [&'static str],
 }
 
 pub trait Rule: Send + Sync {
     fn metadata(&self) -> &RuleMetadata;
+    /// Opt-in rules are off unless given a severity under `[rules]`.
+    fn opt_in(&self) -> bool {
+        false
+    }
+    /// Page-level rules check the document as a whole (its title, language,
+    /// heading outline, unique ids) and are skipped for files that render
+    /// components in isolation, such as Storybook stories.
+    fn page_level(&self) -> bool {
+        false
+    }
+    /// Informational rules point out something to review by hand rather
+    /// than a failure: their diagnostics are information, whichever severity
+    /// turns them on.
+    fn informational(&self) -> bool {
+        false
+    }
+    /// Dynamic rules check a DOM snapshot a browser rendered (`wcag-lsp lint
+    /// --dom … --dynamic`), where what scripts add at runtime is part of the
+    /// markup, and don't run on source files.
+    fn dynamic(&self) -> bool {
+        false
+    }
     fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic>;
+    /// [`Rule::check`], unless the rule has settings of its own in the
+    /// config file.
+    fn check_with_config(
+        &self,
+        root: &Node,
+        source: &str,
+        file_type: FileType,
+        _config: &Config,
+    ) -> Vec<Diagnostic> {
+        self.check(root, source, file_type)
+    }
+    /// What the engine runs: [`Rule::check_with_config`], unless the rule
+    /// uses the facts about the document shared between rules.
+    fn check_with_analysis(&self, analysis: &Analysis, config: &Config) -> Vec<Diagnostic> {
+        self.check_with_config(analysis.root(), analysis.source(), analysis.file_type(), config)
+    }
 }
 
 pub fn all_rules() -> Vec<Box<dyn Rule>> {
     vec![
         Box::new(anchor_content::AnchorContent),
+        Box::new(animated_image::AnimatedImage),
         Box::new(area_alt::AreaAlt),
+        Box::new(aria_activedescendant::AriaActivedescendant),
         Box::new(aria_allowed_attr::AriaAllowedAttr),
+        Box::new(aria_controls_expanded::AriaControlsExpanded),
         Box::new(aria_deprecated_role::AriaDeprecatedRole),
+        Box::new(aria_errormessage::AriaErrormessage),
+        Box::new(aria_expanded_controls::AriaExpandedControls),
         Box::new(aria_hidden_body::AriaHiddenBody),
         Box::new(aria_hidden_focus::AriaHiddenFocus),
+        Box::new(aria_meter_name::AriaMeterName),
+        Box::new(aria_modal_siblings::AriaModalSiblings),
+        Box::new(aria_owns_valid::AriaOwnsValid),
+        Box::new(aria_progressbar_name::AriaProgressbarName),
         Box::new(aria_prohibited_attr::AriaProhibitedAttr),
         Box::new(aria_props::AriaProps),
         Box::new(aria_required_attr::AriaRequiredAttr),
         Box::new(aria_required_children::AriaRequiredChildren),
         Box::new(aria_required_parent::AriaRequiredParent),
         Box::new(aria_role::AriaRole),
+        Box::new(aria_roledescription::AriaRoledescription),
+        Box::new(aria_toggle_field_name::AriaToggleFieldName),
+        Box::new(aria_tooltip_name::AriaTooltipName),
+        Box::new(aria_treeitem_name::AriaTreeitemName),
         Box::new(aria_valid_attr_value::AriaValidAttrValue),
+        Box::new(audio_transcript::AudioTranscript),
         Box::new(autocomplete_valid::AutocompleteValid),
+        Box::new(autoplay_controls::AutoplayControls),
         Box::new(button_name::ButtonName),
+        Box::new(bypass_blocks::BypassBlocks),
+        Box::new(captcha_alternative::CaptchaAlternative),
+        Box::new(carousel::Carousel),
         Box::new(click_events::ClickEvents),
+        Box::new(color_contrast::ColorContrast),
+        Box::new(composite_focus::CompositeFocus),
+        Box::new(consistent_nav_label::ConsistentNavLabel),
+        Box::new(duplicate_id_aria::DuplicateIdAria),
+        Box::new(form_association::FormAssociation),
         Box::new(form_label::FormLabel),
         Box::new(heading_content::HeadingContent),
         Box::new(heading_order::HeadingOrder),
+        Box::new(hidden_field_focus::HiddenFieldFocus),
         Box::new(html_lang::HtmlLang),
         Box::new(iframe_title::IframeTitle),
         Box::new(img_alt::ImgAlt),
         Box::new(input_image_alt::InputImageAlt),
+        Box::new(input_list_valid::InputListValid),
+        Box::new(landmark_structure::LandmarkStructure),
+        Box::new(lang_parts::LangParts),
         Box::new(lang_valid::LangValid),
+        Box::new(layout_table::LayoutTable),
+        Box::new(list_layout::ListLayout),
         Box::new(list_structure::ListStructure),
+        Box::new(live_region::LiveRegion),
+        Box::new(math_alt::MathAlt),
         Box::new(media_captions::MediaCaptions),
         Box::new(meta_refresh::MetaRefresh),
         Box::new(mouse_events::MouseEvents),
         Box::new(nested_interactive::NestedInteractive),
         Box::new(no_access_key::NoAccessKey),
         Box::new(no_autoplay::NoAutoplay),
+        Box::new(no_contradictory_state::NoContradictoryState),
         Box::new(no_distracting_elements::NoDistractingElements),
         Box::new(no_duplicate_id::NoDuplicateId),
+        Box::new(no_multiple_h1::NoMultipleH1),
+        Box::new(no_outline_removal::NoOutlineRemoval),
         Box::new(no_redundant_alt::NoRedundantAlt),
+        Box::new(no_redundant_aria_state::NoRedundantAriaState),
         Box::new(no_redundant_roles::NoRedundantRoles),
         Box::new(object_alt::ObjectAlt),
+        Box::new(output_association::OutputAssociation),
         Box::new(page_title::PageTitle),
+        Box::new(pagination::Pagination),
+        Box::new(pdf_alternative::PdfAlternative),
+        Box::new(progress_meter_name::ProgressMeterName),
+        Box::new(radio_group_name::RadioGroupName),
+        Box::new(reduced_motion::ReducedMotion),
+        Box::new(region::Region),
+        Box::new(role_img_alt::RoleImgAlt),
         Box::new(scope_attr::ScopeAttr),
+        Box::new(svg_img_alt::SvgImgAlt),
+        Box::new(svg_interactive::SvgInteractive),
         Box::new(tabindex::Tabindex),
         Box::new(table_header::TableHeader),
+        Box::new(td_headers_attr::TdHeadersAttr),
     ]
 }
@@ -1,6 +1,7 @@
 use crate::engine::node_to_range;
 use crate::parser::FileType;
 use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
 use tower_lsp_server::ls_types::*;
 use tree_sitter::Node;
@@ -14,6 +15,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "2.1.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/keyboard.html",
     default_severity: Severity::Error,
+    axe_rules: &[],
 };
 
 /// In JSX, components starting with an uppercase letter are custom React components.
@@ -129,18 +131,18 @@ fn check_jsx_self_closing(node: &Node, source: &str, diagnostics: &mut Vec<Diagn
             tag_name = Some(source[child.byte_range()].to_string());
         }
         if child.kind() == "jsx_attribute" {
-            let attr_name = extract_jsx_attr_name(&child, source);
+            let attr_name = jsx_event_name(&child, source);
             if let Some(name) = attr_name {
-                if name == "onMouseOver" {
+                if name == "mouseover" {
                     has_mouseover = true;
                 }
-                if name == "onMouseOut" {
+                if name == "mouseout" {
                     has_mouseout = true;
                 }
-                if name == "onFocus" {
+                if name == "focus" {
                     has_focus = true;
                 }
-                if name == "onBlur" {
+                if name == "blur" {
                     has_blur = true;
                 }
             }
@@ -178,18 +180,18 @@ fn check_jsx_opening(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic
                     tag_name = Some(source[inner_child.byte_range()].to_string());
                 }
                 if inner_child.kind() == "jsx_attribute" {
-                    let attr_name = extract_jsx_attr_name(&inner_child, source);
+                    let attr_name = jsx_event_name(&inner_child, source);
                     if let Some(name) = attr_name {
-                        if name == "onMouseOver" {
+                        if name == "mouseover" {
                             has_mouseover = true;
                         }
-                        if name == "onMouseOut" {
+                        if name == "mouseout" {
                             has_mouseout = true;
                         }
-                        if name == "onFocus" {
+                        if name == "focus" {
                             has_focus = true;
                         }
-                        if name == "onBlur" {
+                        if name == "blur" {
                             has_blur = true;
                         }
                     }
@@ -213,14 +215,10 @@ fn check_jsx_opening(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic
     }
 }
 
-fn extract_jsx_attr_name(attr_node: &Node, source: &str) -> Option<String> {
-    let mut cursor = attr_node.walk();
-    for child in attr_node.children(&mut cursor) {
-        if child.kind() == "property_identifier" {
-            return Some(source[child.byte_range()].to_string());
-        }
-    }
-    None
+/// The event a JSX attribute handles, in any of the dialects
+/// [`jsx_attrs::jsx_event`] knows.
+fn jsx_event_name(attr_node: &Node, source: &str) -> Option<String> {
+    jsx_attrs::jsx_attribute(attr_node, source).and_then(|(name, _)| jsx_attrs::jsx_event(name))
 }
 
 fn make_diagnostic(node: &Node, mouse_event: &str, keyboard_event: &str) -> Diagnostic {
@@ -343,6 +341,17 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    #[test]
+    fn test_tsx_solid_and_qwik_handlers() {
+        let diags =
+            check_tsx(r#"const App = () => <><div on:mouseover={a} /><div onMouseOut$={b} /></>;"#);
+        assert_eq!(diags.len(), 2);
+        let diags = check_tsx(
+            r#"const App = () => <div on:mouseover={a} on:focus={a} onMouseOut$={b} onBlur$={b} />;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
     #[test]
     fn test_tsx_mouseout_without_blur_fails() {
         let diags = check_tsx(r#"const App = () => <div onMouseOut={handler} />;"#);
@@ -14,6 +14,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.4.2",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/audio-control.html",
     default_severity: Severity::Warning,
+    axe_rules: &["no-autoplay-audio"],
 };
 
 impl Rule for NoAutoplay {
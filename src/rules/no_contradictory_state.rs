@@ -0,0 +1,368 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::quickfix::{self, Fix};
+use crate::rules::html_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct NoContradictoryState;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "no-contradictory-state",
+    description: "Native state attributes and their ARIA counterparts must not contradict each other",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Error,
+    axe_rules: &[],
+};
+
+/// A native boolean attribute, the ARIA attribute describing the same state,
+/// the elements the native attribute has an effect on (`None` = all), and
+/// what users actually get when both are present.
+struct StatePair {
+    native: &'static str,
+    aria: &'static str,
+    elements: Option<&'static [&'static str]>,
+    outcome: &'static str,
+}
+
+static STATE_PAIRS: &[StatePair] = &[
+    StatePair {
+        native: "disabled",
+        aria: "aria-disabled",
+        elements: Some(&[
+            "button", "fieldset", "input", "optgroup", "option", "select", "textarea",
+        ]),
+        outcome: "the control is disabled and exposed as disabled",
+    },
+    StatePair {
+        native: "hidden",
+        aria: "aria-hidden",
+        elements: None,
+        outcome: "the element is not rendered and stays hidden from assistive technology",
+    },
+    StatePair {
+        native: "readonly",
+        aria: "aria-readonly",
+        elements: Some(&["input", "textarea"]),
+        outcome: "the field cannot be edited and is exposed as read-only",
+    },
+];
+
+/// One attribute as far as this rule cares.
+struct StateAttr<'a> {
+    node: Node<'a>,
+    name: String,
+    /// Static value; `None` when absent or dynamic.
+    value: Option<String>,
+    /// `false` only for an explicit JSX `{false}`.
+    enabled: bool,
+    /// A JSX expression other than `true`/`false`, only known at runtime.
+    dynamic: bool,
+}
+
+impl Rule for NoContradictoryState {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, &mut diagnostics);
+        } else {
+            visit_html(root, source, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+        && let Some(tag_name) = html_attrs::tag_name(&tag, source)
+    {
+        // Bound values are runtime expressions and can't be compared.
+        let attrs: Vec<StateAttr> = html_attrs::attrs(&tag, source)
+            .into_iter()
+            .filter(|a| !a.bound)
+            .map(|a| StateAttr {
+                node: a.node,
+                name: a.name_lower(),
+                value: a.value,
+                enabled: true,
+                dynamic: false,
+            })
+            .collect();
+        check_attrs(tag_name, &attrs, diagnostics);
+    }
+
+    // Recurse into children
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "jsx_self_closing_element" {
+        check_jsx_tag(node, source, diagnostics);
+    } else if node.kind() == "jsx_element" {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "jsx_opening_element" {
+                check_jsx_tag(&child, source, diagnostics);
+            }
+        }
+    }
+
+    // Recurse into children
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, diagnostics);
+    }
+}
+
+fn check_jsx_tag(tag: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut tag_name: Option<&str> = None;
+    let mut attrs = Vec::new();
+
+    let mut cursor = tag.walk();
+    for child in tag.children(&mut cursor) {
+        match child.kind() {
+            "identifier" => tag_name = Some(&source[child.byte_range()]),
+            "jsx_attribute" => {
+                if let Some(attr) = extract_jsx_attribute(&child, source) {
+                    attrs.push(attr);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Only lowercase intrinsic elements; components decide what props mean.
+    if let Some(name) = tag_name
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+    {
+        check_attrs(name, &attrs, diagnostics);
+    }
+}
+
+fn extract_jsx_attribute<'a>(attr_node: &Node<'a>, source: &str) -> Option<StateAttr<'a>> {
+    let mut name = None;
+    let mut value = None;
+    let mut enabled = true;
+    let mut dynamic = false;
+
+    let mut cursor = attr_node.walk();
+    for child in attr_node.children(&mut cursor) {
+        match child.kind() {
+            "property_identifier" => name = Some(source[child.byte_range()].to_ascii_lowercase()),
+            "string" => {
+                let raw = &source[child.byte_range()];
+                value = Some(raw.trim_matches('"').trim_matches('\'').to_string());
+            }
+            "jsx_expression" => {
+                let expr = source[child.byte_range()]
+                    .trim_start_matches('{')
+                    .trim_end_matches('}')
+                    .trim();
+                match expr {
+                    "true" => value = Some("true".to_string()),
+                    "false" => {
+                        value = Some("false".to_string());
+                        enabled = false;
+                    }
+                    _ => dynamic = true,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(StateAttr {
+        node: *attr_node,
+        name: name?,
+        value,
+        enabled,
+        dynamic,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn check_attrs(tag_name: &str, attrs: &[StateAttr], diagnostics: &mut Vec<Diagnostic>) {
+    let tag = tag_name.to_ascii_lowercase();
+    let find = |name: &str| attrs.iter().find(|a| a.name == name);
+
+    for pair in STATE_PAIRS {
+        if pair
+            .elements
+            .is_some_and(|elements| !elements.contains(&tag.as_str()))
+        {
+            continue;
+        }
+        // Either side only known at runtime may not contradict the other.
+        if [pair.native, pair.aria]
+            .iter()
+            .any(|name| find(name).is_some_and(|a| a.dynamic))
+        {
+            continue;
+        }
+        let native_on = find(pair.native).is_some_and(|a| a.enabled);
+        let aria_off = find(pair.aria).filter(|a| {
+            a.value
+                .as_deref()
+                .is_some_and(|v| v.trim().eq_ignore_ascii_case("false"))
+        });
+        if native_on && let Some(aria) = aria_off {
+            diagnostics.push(make_diagnostic(&aria.node, &tag, pair));
+        }
+    }
+}
+
+fn make_diagnostic(attr: &Node, tag_name: &str, pair: &StatePair) -> Diagnostic {
+    let meta = &METADATA;
+    let mut diagnostic = Diagnostic {
+        range: node_to_range(attr),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "<{}> has '{}' but {}=\"false\"; '{}' wins, so {}. {} [WCAG {} Level {:?}]",
+            tag_name,
+            pair.native,
+            pair.aria,
+            pair.native,
+            pair.outcome,
+            meta.description,
+            meta.wcag_criterion,
+            meta.wcag_level
+        ),
+        ..Default::default()
+    };
+    quickfix::attach(
+        &mut diagnostic,
+        Fix {
+            title: format!("Remove '{}'", pair.aria),
+            edits: vec![quickfix::remove_node(attr)],
+            safe: true,
+            ..Default::default()
+        },
+    );
+    diagnostic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = NoContradictoryState;
+        rule.check(&tree.root_node(), source, FileType::Html)
+    }
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = NoContradictoryState;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    fn check_vue(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Vue).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = NoContradictoryState;
+        rule.check(&tree.root_node(), source, FileType::Vue)
+    }
+
+    #[test]
+    fn test_disabled_with_aria_disabled_false_fails() {
+        let diags = check_html(r#"<button disabled aria-disabled="false">Go</button>"#);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].severity, Some(DiagnosticSeverity::ERROR));
+        assert!(diags[0].message.contains("'disabled' wins"));
+        assert_eq!(
+            quickfix::fixes(&diags[0])[0].title,
+            "Remove 'aria-disabled'"
+        );
+    }
+
+    #[test]
+    fn test_hidden_with_aria_hidden_false_fails() {
+        let diags = check_html(r#"<div hidden aria-hidden="false">Secret</div>"#);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("'hidden' wins"));
+    }
+
+    #[test]
+    fn test_readonly_with_aria_readonly_false_fails() {
+        let diags = check_html(r#"<input readonly aria-readonly="false">"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_matching_states_pass() {
+        let diags = check_html(r#"<button disabled aria-disabled="true">Go</button>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_aria_false_without_native_passes() {
+        let diags = check_html(r#"<button aria-disabled="false">Go</button>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_disabled_on_div_ignored() {
+        // `disabled` has no effect on a <div>, so there's nothing to contradict.
+        let diags = check_html(r#"<div disabled aria-disabled="false"></div>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_bound_value_ignored() {
+        let diags = check_vue(r#"<template><div hidden :aria-hidden="flag"></div></template>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_readonly_fails() {
+        let diags = check_tsx(r#"const App = () => <input readOnly aria-readonly="false" />;"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_tsx_dynamic_native_state_passes() {
+        let diags = check_tsx(
+            r#"const App = () => <button disabled={busy} aria-disabled="false">Save</button>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+        let diags = check_tsx(r#"const App = () => <div hidden={!open} aria-hidden="false" />;"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_hidden_false_passes() {
+        let diags = check_tsx(r#"const App = () => <div hidden={false} aria-hidden="false" />;"#);
+        assert_eq!(diags.len(), 0);
+    }
+}
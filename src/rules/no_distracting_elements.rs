@@ -13,6 +13,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "2.2.2",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/pause-stop-hide.html",
     default_severity: Severity::Error,
+    axe_rules: &["blink", "marquee"],
 };
 
 impl Rule for NoDistractingElements {
@@ -1,8 +1,7 @@
-use std::collections::HashMap;
-
+use crate::analysis::Analysis;
+use crate::config::Config;
 use crate::engine::node_to_range;
 use crate::parser::FileType;
-use crate::rules::html_attrs;
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
 use tower_lsp_server::ls_types::*;
 use tree_sitter::Node;
@@ -16,6 +15,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "4.1.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/parsing.html",
     default_severity: Severity::Error,
+    axe_rules: &["duplicate-id"],
 };
 
 impl Rule for NoDuplicateId {
@@ -23,129 +23,35 @@ impl Rule for NoDuplicateId {
         &METADATA
     }
 
-    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
-        let mut id_entries: Vec<(String, Node)> = Vec::new();
-        if file_type.is_jsx_like() {
-            collect_ids_jsx(root, source, &mut id_entries);
-        } else {
-            collect_ids_html(root, source, &mut id_entries);
-        }
-
-        let mut diagnostics = Vec::new();
-        let mut seen: HashMap<String, bool> = HashMap::new();
-
-        for (id_value, node) in &id_entries {
-            if let Some(_first_seen) = seen.get(id_value) {
-                // This is a duplicate; report on this (second or subsequent) occurrence
-                diagnostics.push(make_diagnostic(node, id_value));
-            } else {
-                seen.insert(id_value.clone(), true);
-            }
-        }
-
-        diagnostics
-    }
-}
-
-// ---------------------------------------------------------------------------
-// HTML
-// ---------------------------------------------------------------------------
-
-fn collect_ids_html<'a>(node: &Node<'a>, source: &str, entries: &mut Vec<(String, Node<'a>)>) {
-    if node.kind() == "element"
-        && let Some(tag) = html_attrs::element_tag(node)
-        && let Some(id_value) = extract_html_id(&tag, source)
-    {
-        entries.push((id_value, *node));
-    }
-
-    let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        collect_ids_html(&child, source, entries);
-    }
-}
-
-/// The value of a static `id` attribute on a tag, if present and non-empty.
-///
-/// A bound `:id="expr"` is a runtime value that can't be compared literally, so
-/// it is excluded — only static `id="…"` values participate in duplicate
-/// detection.
-fn extract_html_id(tag: &Node, source: &str) -> Option<String> {
-    html_attrs::attrs(tag, source).into_iter().find_map(|attr| {
-        if attr.name_eq("id") && !attr.bound {
-            attr.value.filter(|v| !v.trim().is_empty())
-        } else {
-            None
-        }
-    })
-}
-
-// ---------------------------------------------------------------------------
-// JSX / TSX
-// ---------------------------------------------------------------------------
-
-fn collect_ids_jsx<'a>(node: &Node<'a>, source: &str, entries: &mut Vec<(String, Node<'a>)>) {
-    match node.kind() {
-        "jsx_self_closing_element" => {
-            if let Some(id_value) = extract_jsx_id(node, source) {
-                entries.push((id_value, *node));
-            }
-        }
-        "jsx_element" => {
-            let mut cursor = node.walk();
-            for child in node.children(&mut cursor) {
-                if child.kind() == "jsx_opening_element"
-                    && let Some(id_value) = extract_jsx_id(&child, source)
-                {
-                    entries.push((id_value, *node));
-                }
-            }
-        }
-        _ => {}
+    /// Each story renders on its own, so ids may repeat across stories.
+    fn page_level(&self) -> bool {
+        true
     }
 
-    let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        collect_ids_jsx(&child, source, entries);
-    }
-}
-
-/// Extract the value of an `id` attribute from a JSX element or opening element node.
-fn extract_jsx_id(node: &Node, source: &str) -> Option<String> {
-    let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        if child.kind() == "jsx_attribute" {
-            let (attr_name, attr_value) = extract_jsx_attribute(&child, source);
-            if let Some(name) = attr_name
-                && name == "id"
-                && let Some(val) = attr_value
-                && !val.trim().is_empty()
-            {
-                return Some(val);
-            }
-        }
-    }
-    None
-}
-
-/// Extract (attribute_name, Option<string_value>) from a JSX attribute node.
-fn extract_jsx_attribute(attr_node: &Node, source: &str) -> (Option<String>, Option<String>) {
-    let mut name = None;
-    let mut value = None;
-
-    let mut cursor = attr_node.walk();
-    for child in attr_node.children(&mut cursor) {
-        if child.kind() == "property_identifier" {
-            name = Some(source[child.byte_range()].to_string());
-        }
-        if child.kind() == "string" {
-            let raw = &source[child.byte_range()];
-            let trimmed = raw.trim_matches('"').trim_matches('\'');
-            value = Some(trimmed.to_string());
-        }
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    /// A bound `:id="expr"` is a runtime value that can't be compared
+    /// literally, so only static `id="…"` values take part.
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        // Every occurrence after the first, in document order.
+        let mut duplicates: Vec<usize> = analysis
+            .ids()
+            .values()
+            .flat_map(|elements| elements.iter().skip(1).copied())
+            .collect();
+        duplicates.sort_unstable();
+
+        let elements = analysis.elements();
+        duplicates
+            .into_iter()
+            .map(|i| {
+                let element = &elements[i];
+                make_diagnostic(&element.node(), element.id.as_deref().unwrap_or(""))
+            })
+            .collect()
     }
-
-    (name, value)
 }
 
 fn make_diagnostic(node: &Node, id_value: &str) -> Diagnostic {
@@ -0,0 +1,192 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
+use crate::engine::{node_to_range, related_location};
+use crate::parser::FileType;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct NoMultipleH1;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "no-multiple-h1",
+    description: "A page should have a single top-level <h1> heading",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.3.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Elements whose `<h1>` heads that element rather than the page: sectioning
+/// content and the sectioning roots other than `<body>`.
+const SECTIONING_ELEMENTS: &[&str] = &[
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "fieldset",
+    "figure",
+    "nav",
+    "section",
+    "td",
+];
+
+impl Rule for NoMultipleH1 {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// The outline belongs to the page, not to a component.
+    fn page_level(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        // Only a full document (one with `<html>` or `<body>`) is the whole
+        // page; the headings of components and partials add up with others.
+        if !analysis.is_page() {
+            return Vec::new();
+        }
+        let elements = analysis.elements();
+        let h1s: Vec<Node> = analysis
+            .headings()
+            .filter(|&(i, level)| {
+                level == 1
+                    && !analysis.within(i, |e| SECTIONING_ELEMENTS.contains(&e.name.as_str()))
+            })
+            .map(|(i, _)| elements[i].node())
+            .collect();
+        if h1s.len() < 2 {
+            return Vec::new();
+        }
+
+        h1s.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, h1)| {
+                let others = h1s
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(j, other)| {
+                        let which = if j == 0 { "First" } else { "Another" };
+                        related_location(other, &format!("{which} <h1> of the page"))
+                    })
+                    .collect();
+                make_diagnostic(h1, h1s.len(), others)
+            })
+            .collect()
+    }
+}
+
+fn make_diagnostic(
+    node: &Node,
+    count: usize,
+    related: Vec<DiagnosticRelatedInformation>,
+) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "The page has {} <h1> headings outside of sections; use <h2> for this one or move it into a <section> or <article>. {} [WCAG {} Level {:?}]",
+            count, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        related_information: Some(related),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = NoMultipleH1;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_single_h1_passes() {
+        let diags = check_html("<html><body><h1>Shop</h1><h2>Sale</h2></body></html>");
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_two_h1s_fail_on_second_with_related_first() {
+        let diags = check_html("<body>\n<h1>Shop</h1>\n<h1>Sale</h1>\n</body>");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert!(diags[0].message.contains("The page has 2 <h1> headings"));
+        let related = diags[0].related_information.as_ref().unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].location.range.start.line, 1);
+        assert_eq!(related[0].message, "First <h1> of the page");
+    }
+
+    #[test]
+    fn test_each_extra_h1_lists_the_others() {
+        let diags = check_html("<body><h1>A</h1><h1>B</h1><div><h1>C</h1></div></body>");
+        assert_eq!(diags.len(), 2);
+        assert!(
+            diags
+                .iter()
+                .all(|d| d.related_information.as_ref().unwrap().len() == 2)
+        );
+    }
+
+    #[test]
+    fn test_h1s_in_sections_ignored() {
+        let diags = check_html(
+            "<body><h1>Blog</h1><article><h1>Post</h1></article><section><h1>About</h1></section><blockquote><h1>Quote</h1></blockquote></body>",
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_hidden_and_aria_headings() {
+        let diags = check_html(
+            r#"<body><h1>A</h1><div hidden><h1>B</h1></div><p role="heading" aria-level="1">C</p></body>"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("The page has 2 <h1> headings"));
+    }
+
+    #[test]
+    fn test_fragment_without_body_ignored() {
+        let diags = check_html("<h1>A</h1><h1>B</h1>");
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_component_files_ignored() {
+        let diags = check(
+            "<template><body><h1>A</h1><h1>B</h1></body></template>",
+            FileType::Vue,
+        );
+        assert_eq!(diags.len(), 0);
+        let diags = check(
+            "const P = () => <body><h1>A</h1><h1>B</h1></body>;",
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+}
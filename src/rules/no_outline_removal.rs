@@ -0,0 +1,365 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::css::{self, StyleRule};
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct NoOutlineRemoval;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "no-outline-removal",
+    description: "Focus outlines must not be removed without another focus style",
+    wcag_level: WcagLevel::AA,
+    wcag_criterion: "2.4.7",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/focus-visible.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Elements that take focus by default.
+const INTERACTIVE_TAGS: &[&str] = &[
+    "a", "area", "button", "details", "input", "select", "summary", "textarea",
+];
+
+/// Attribute selectors that match focusable elements.
+const INTERACTIVE_ATTR_SELECTORS: &[&str] = &["[contenteditable", "[href", "[tabindex"];
+
+/// Properties that can show focus in place of an outline. `outline` itself
+/// counts when it's set to something visible.
+const INDICATOR_PROPERTIES: &[&str] = &["background", "border", "box-shadow", "text-decoration"];
+
+impl Rule for NoOutlineRemoval {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let sheets = css::style_sheets(root, source, file_type);
+        let rules: Vec<(Node, Vec<StyleRule>)> = sheets
+            .into_iter()
+            .map(|sheet| (sheet, css::rules(&source[sheet.byte_range()])))
+            .collect();
+        // A focus style anywhere in the file is taken to replace the outlines
+        // it removes; which elements it styles isn't followed. An inline
+        // `outline` wins over the style sheet's, so only the other
+        // properties make up for one.
+        let has_focus_style = |outline_counts: bool| {
+            rules
+                .iter()
+                .flat_map(|(_, rules)| rules)
+                .any(|rule| is_focus_style(rule, outline_counts))
+        };
+
+        let mut diagnostics = Vec::new();
+        if !has_focus_style(true) {
+            for (sheet, rules) in &rules {
+                for rule in rules.iter().filter(|r| targets_interactive(&r.selector)) {
+                    for declaration in &rule.declarations {
+                        if removes_outline(&declaration.property, &declaration.value) {
+                            let range = css::declaration_range(source, sheet, declaration);
+                            diagnostics.push(make_diagnostic(range));
+                        }
+                    }
+                }
+            }
+        }
+        if !has_focus_style(false) {
+            if file_type.is_jsx_like() {
+                visit_jsx(root, source, &mut diagnostics);
+            } else {
+                visit_html(root, source, &mut diagnostics);
+            }
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source).unwrap_or("");
+        let attrs = html_attrs::attrs(&tag, source);
+        let focusable = INTERACTIVE_TAGS.contains(&name.to_ascii_lowercase().as_str())
+            || attrs
+                .iter()
+                .any(|a| a.name_eq("tabindex") || a.name_eq("contenteditable"));
+        if focusable
+            && let Some(style) = attrs.iter().find(|a| a.name_eq("style") && !a.bound)
+            && css::inline_declarations(style.value.as_deref().unwrap_or(""))
+                .iter()
+                .any(|(property, value)| removes_outline(property, value))
+        {
+            diagnostics.push(make_diagnostic(node_to_range(&style.node)));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let mut cursor = tag.walk();
+        let attrs: Vec<(Node, &str, Option<Node>)> = tag
+            .named_children(&mut cursor)
+            .filter_map(|attr| {
+                jsx_attrs::jsx_attribute(&attr, source).map(|(name, value)| (attr, name, value))
+            })
+            .collect();
+        let focusable = INTERACTIVE_TAGS.contains(&name)
+            || attrs
+                .iter()
+                .any(|(_, name, _)| matches!(*name, "tabIndex" | "contentEditable"));
+        if focusable
+            && let Some((style, _, Some(value))) =
+                attrs.iter().find(|(_, name, _)| *name == "style")
+            && let Some(object) = jsx_attrs::jsx_object(value)
+            && object_removes_outline(&object, source)
+        {
+            diagnostics.push(make_diagnostic(node_to_range(style)));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, diagnostics);
+    }
+}
+
+/// Whether a `style={{ … }}` object sets `outline: "none"`, `outline: 0` or
+/// the like.
+fn object_removes_outline(object: &Node, source: &str) -> bool {
+    let mut cursor = object.walk();
+    object
+        .named_children(&mut cursor)
+        .filter(|pair| pair.kind() == "pair")
+        .any(|pair| {
+            let (Some(key), Some(value)) = (
+                pair.child_by_field_name("key"),
+                pair.child_by_field_name("value"),
+            ) else {
+                return false;
+            };
+            let property = match source[key.byte_range()].trim_matches(['"', '\'']) {
+                "outline" => "outline",
+                "outlineStyle" => "outline-style",
+                "outlineWidth" => "outline-width",
+                _ => return false,
+            };
+            matches!(value.kind(), "string" | "number")
+                && removes_outline(
+                    property,
+                    &source[value.byte_range()]
+                        .trim_matches(['"', '\''])
+                        .to_ascii_lowercase(),
+                )
+        })
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+/// Whether a declaration hides the outline: `outline: none`, `outline: 0`,
+/// `outline-style: none` or `outline-width: 0`.
+fn removes_outline(property: &str, value: &str) -> bool {
+    let value = value.trim_end_matches("!important").trim();
+    match property {
+        "outline" => !value.is_empty() && value.split_whitespace().all(is_none_or_zero),
+        "outline-style" | "outline-width" => is_none_or_zero(value),
+        _ => false,
+    }
+}
+
+fn is_none_or_zero(value: &str) -> bool {
+    value == "none"
+        || value
+            .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+            .parse::<f64>()
+            .is_ok_and(|n| n == 0.0)
+}
+
+/// Whether any selector of the list matches an element that takes focus:
+/// one that's focused, an interactive element, an attribute selector for a
+/// focusable one, or every element. `:focus:not(:focus-visible)` hides the
+/// outline only from mouse users and doesn't count.
+fn targets_interactive(selector_list: &str) -> bool {
+    selector_list.split(',').any(|selector| {
+        if selector.contains(":not(:focus-visible)") {
+            return false;
+        }
+        // Only the last compound selector is the styled element.
+        let compound = selector
+            .rsplit(|c: char| c.is_whitespace() || matches!(c, '>' | '+' | '~'))
+            .find(|s| !s.is_empty())
+            .unwrap_or("");
+        let element = compound.split(['.', '#', '[', ':']).next().unwrap_or("");
+        is_focus_selector(compound)
+            || element == "*"
+            || INTERACTIVE_TAGS.contains(&element)
+            || INTERACTIVE_ATTR_SELECTORS
+                .iter()
+                .any(|attr| compound.contains(attr))
+    })
+}
+
+/// Whether a selector styles focused elements (`:focus`, `:focus-visible`);
+/// `:focus-within` styles their containers.
+fn is_focus_selector(selector: &str) -> bool {
+    selector
+        .match_indices(":focus")
+        .any(|(i, _)| !selector[i..].starts_with(":focus-within"))
+}
+
+/// Whether a rule shows focus some way other than removing the outline.
+/// `outline_counts` is whether a visible `outline` does.
+fn is_focus_style(rule: &StyleRule, outline_counts: bool) -> bool {
+    is_focus_selector(&rule.selector)
+        && !rule.selector.contains(":not(:focus-visible)")
+        && rule.declarations.iter().any(|d| {
+            let value = d.value.trim_end_matches("!important").trim();
+            if d.property.starts_with("outline") {
+                outline_counts && !removes_outline(&d.property, &d.value)
+            } else {
+                INDICATOR_PROPERTIES
+                    .iter()
+                    .any(|p| d.property.starts_with(p))
+                    && !matches!(value, "none" | "0" | "transparent")
+            }
+        })
+}
+
+fn make_diagnostic(range: Range) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "This removes the focus outline, and no `:focus` or `:focus-visible` style replaces it, so keyboard users can't see where they are; keep the outline or style focus with a border, box-shadow or background. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = NoOutlineRemoval;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    fn lines(diags: &[Diagnostic]) -> Vec<u32> {
+        diags.iter().map(|d| d.range.start.line).collect()
+    }
+
+    #[test]
+    fn test_outline_removed_in_style_block_fails() {
+        let diags = check_html(
+            r#"<style>
+a:focus { outline: none; }
+.nav > button { color: red; outline: 0 !important }
+*:focus-visible { outline-style: none }
+.card { outline: none }
+.menu:focus-within { outline: none }
+</style>"#,
+        );
+        assert_eq!(lines(&diags), [1, 2, 3]);
+        assert_eq!(diags[0].range.start.character, 10);
+        assert_eq!(diags[0].range.end.character, 23);
+        assert_eq!(
+            diags[0].code,
+            Some(NumberOrString::String("no-outline-removal".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_replacement_focus_style_passes() {
+        let diags = check_html(
+            r#"<style>
+button:focus { outline: none; box-shadow: 0 0 0 3px #36c; }
+a { outline: 0 }
+</style>"#,
+        );
+        assert_eq!(diags.len(), 0);
+
+        let diags = check_html(
+            r#"<style>
+:focus:not(:focus-visible) { outline: none }
+input:focus { outline: 2px solid #36c }
+</style>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_inline_styles() {
+        let diags = check_html(
+            r#"<button style="outline: none">Save</button>
+<div tabindex="0" style="color: red; outline:0">Card</div>
+<p style="outline: none">Text</p>"#,
+        );
+        assert_eq!(lines(&diags), [0, 1]);
+        assert_eq!(diags[0].range.start.character, 8);
+
+        // An inline outline wins over the style sheet's `:focus` outline.
+        let diags = check_html(
+            r#"<style>a:focus { outline: 2px solid }</style><a href="/" style="outline: none">Home</a>"#,
+        );
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_tsx() {
+        let diags = check(
+            r#"const A = () => (
+  <>
+    <button style={{ outline: "none" }}>Go</button>
+    <input style={{ outlineWidth: 0 }} />
+    <div style={{ outline: "none" }} />
+    <style jsx>{`
+      a:focus { outline: none; }
+    `}</style>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(lines(&diags), [6, 2, 3]);
+    }
+}
@@ -0,0 +1,412 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::quickfix::{self, Fix};
+use crate::rules::html_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct NoRedundantAriaState;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "no-redundant-aria-state",
+    description: "Native form controls should not repeat their state in ARIA attributes",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// `(aria attribute, native attribute, elements that support the native one)`.
+const STATE_PAIRS: &[(&str, &str, &[&str])] = &[
+    (
+        "aria-required",
+        "required",
+        &["input", "select", "textarea"],
+    ),
+    (
+        "aria-disabled",
+        "disabled",
+        &[
+            "button", "fieldset", "input", "optgroup", "option", "select", "textarea",
+        ],
+    ),
+    ("aria-checked", "checked", &["input"]),
+];
+
+/// One attribute as far as this rule cares.
+struct StateAttr<'a> {
+    node: Node<'a>,
+    name: String,
+    /// Static value; `None` when absent or dynamic.
+    value: Option<String>,
+    /// `false` only for an explicit JSX `{false}`.
+    enabled: bool,
+    /// The value is a runtime expression (`:checked="c"`, `checked={c}`).
+    dynamic: bool,
+}
+
+impl Rule for NoRedundantAriaState {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, &mut diagnostics);
+        } else {
+            visit_html(root, source, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+        && let Some(tag_name) = html_attrs::tag_name(&tag, source)
+    {
+        // A bound ARIA value can't be compared, so it's dropped; a bound native
+        // state is kept as dynamic so the pair is skipped rather than the
+        // native state taken as absent.
+        let attrs: Vec<StateAttr> = html_attrs::attrs(&tag, source)
+            .into_iter()
+            .filter(|a| !(a.bound && a.name_lower().starts_with("aria-")))
+            .map(|a| StateAttr {
+                node: a.node,
+                name: a.name_lower(),
+                value: if a.bound { None } else { a.value },
+                enabled: true,
+                dynamic: a.bound,
+            })
+            .collect();
+        check_attrs(tag_name, &attrs, diagnostics);
+    }
+
+    // Recurse into children
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "jsx_self_closing_element" {
+        check_jsx_tag(node, source, diagnostics);
+    } else if node.kind() == "jsx_element" {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "jsx_opening_element" {
+                check_jsx_tag(&child, source, diagnostics);
+            }
+        }
+    }
+
+    // Recurse into children
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, diagnostics);
+    }
+}
+
+fn check_jsx_tag(tag: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut tag_name: Option<&str> = None;
+    let mut attrs = Vec::new();
+
+    let mut cursor = tag.walk();
+    for child in tag.children(&mut cursor) {
+        match child.kind() {
+            "identifier" => tag_name = Some(&source[child.byte_range()]),
+            "jsx_attribute" => {
+                if let Some(attr) = extract_jsx_attribute(&child, source) {
+                    attrs.push(attr);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Only lowercase intrinsic elements; `<Input required>` is a component.
+    if let Some(name) = tag_name
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+    {
+        check_attrs(name, &attrs, diagnostics);
+    }
+}
+
+fn extract_jsx_attribute<'a>(attr_node: &Node<'a>, source: &str) -> Option<StateAttr<'a>> {
+    let mut name = None;
+    let mut value = None;
+    let mut enabled = true;
+    let mut dynamic = false;
+
+    let mut cursor = attr_node.walk();
+    for child in attr_node.children(&mut cursor) {
+        match child.kind() {
+            "property_identifier" => name = Some(source[child.byte_range()].to_ascii_lowercase()),
+            "string" => {
+                let raw = &source[child.byte_range()];
+                value = Some(raw.trim_matches('"').trim_matches('\'').to_string());
+            }
+            "jsx_expression" => {
+                let expr = source[child.byte_range()]
+                    .trim_start_matches('{')
+                    .trim_end_matches('}')
+                    .trim();
+                match expr {
+                    "true" => value = Some("true".to_string()),
+                    "false" => {
+                        value = Some("false".to_string());
+                        enabled = false;
+                    }
+                    _ => dynamic = true,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(StateAttr {
+        node: *attr_node,
+        name: name?,
+        value,
+        enabled,
+        dynamic,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn check_attrs(tag_name: &str, attrs: &[StateAttr], diagnostics: &mut Vec<Diagnostic>) {
+    let tag = tag_name.to_ascii_lowercase();
+    let find = |name: &str| attrs.iter().find(|a| a.name == name);
+
+    for (aria_name, native_name, elements) in STATE_PAIRS {
+        if !elements.contains(&tag.as_str()) {
+            continue;
+        }
+        let Some(aria) = find(aria_name) else {
+            continue;
+        };
+
+        // A checkbox or radio always exposes its checked state natively, so
+        // `aria-checked` is redundant even without a `checked` attribute.
+        let native_state = match find(native_name) {
+            // Only known at runtime.
+            Some(native) if native.dynamic => continue,
+            Some(native) if native.enabled => true,
+            None if *native_name == "checked"
+                && find("type")
+                    .and_then(|a| a.value.as_deref())
+                    .is_some_and(is_checkable) =>
+            {
+                false
+            }
+            _ => continue,
+        };
+
+        let aria_state = !aria
+            .value
+            .as_deref()
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("false"));
+        let contradicts = aria_state != native_state;
+        // `disabled` vs. `aria-disabled="false"` is an error under
+        // no-contradictory-state; don't report it twice.
+        if contradicts && *aria_name == "aria-disabled" {
+            continue;
+        }
+        diagnostics.push(make_diagnostic(
+            &aria.node,
+            &tag,
+            aria_name,
+            native_name,
+            contradicts,
+        ));
+    }
+}
+
+fn is_checkable(input_type: &str) -> bool {
+    input_type.eq_ignore_ascii_case("checkbox") || input_type.eq_ignore_ascii_case("radio")
+}
+
+fn make_diagnostic(
+    attr: &Node,
+    tag_name: &str,
+    aria_name: &str,
+    native_name: &str,
+    contradicts: bool,
+) -> Diagnostic {
+    let meta = &METADATA;
+    let detail = if contradicts {
+        format!(
+            "'{aria_name}' on <{tag_name}> contradicts its native '{native_name}' state, which browsers expose instead"
+        )
+    } else {
+        format!("'{aria_name}' on <{tag_name}> duplicates its native '{native_name}' state")
+    };
+    let mut diagnostic = Diagnostic {
+        range: node_to_range(attr),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{}. {} [WCAG {} Level {:?}]",
+            detail, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    };
+    quickfix::attach(
+        &mut diagnostic,
+        Fix {
+            title: format!("Remove '{aria_name}'"),
+            edits: vec![quickfix::remove_node(attr)],
+            safe: true,
+            ..Default::default()
+        },
+    );
+    diagnostic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = NoRedundantAriaState;
+        rule.check(&tree.root_node(), source, FileType::Html)
+    }
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = NoRedundantAriaState;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    fn check_vue(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Vue).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = NoRedundantAriaState;
+        rule.check(&tree.root_node(), source, FileType::Vue)
+    }
+
+    #[test]
+    fn test_required_with_aria_required_fails() {
+        let diags = check_html(r#"<input required aria-required="true">"#);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("duplicates"));
+        assert_eq!(
+            diags[0].code,
+            Some(NumberOrString::String(
+                "no-redundant-aria-state".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_quick_fix_removes_aria_attribute() {
+        let source = r#"<input required aria-required="true">"#;
+        let diags = check_html(source);
+        let fixes = quickfix::fixes(&diags[0]);
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].title, "Remove 'aria-required'");
+        let range = fixes[0].edits[0].range;
+        assert_eq!((range.start.character, range.end.character), (15, 36));
+    }
+
+    #[test]
+    fn test_required_with_aria_required_false_contradicts() {
+        let diags = check_html(r#"<textarea required aria-required="false"></textarea>"#);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("contradicts"));
+    }
+
+    #[test]
+    fn test_disabled_with_aria_disabled_false_left_to_contradiction_rule() {
+        let diags = check_html(r#"<button disabled aria-disabled="false">Go</button>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_aria_disabled_alone_passes() {
+        // Keeping a control focusable while marking it disabled is legitimate.
+        let diags = check_html(r#"<button aria-disabled="true">Go</button>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_aria_checked_on_native_checkbox_fails() {
+        let diags = check_html(r#"<input type="checkbox" aria-checked="true">"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_aria_checked_true_on_unchecked_checkbox_contradicts() {
+        let diags = check_html(r#"<input type="radio" aria-checked="true">"#);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("contradicts"));
+    }
+
+    #[test]
+    fn test_aria_checked_on_text_input_ignored() {
+        let diags = check_html(r#"<input type="text" aria-checked="true">"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_non_form_element_ignored() {
+        let diags = check_html(r#"<div disabled aria-disabled="true"></div>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_bound_aria_ignored() {
+        let diags = check_vue(r#"<template><input required :aria-required="needed"></template>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_vue_bound_native_state_ignored() {
+        let diags = check_vue(
+            r#"<template><input type="checkbox" :checked="on" aria-checked="true"></template>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_duplicate_fails() {
+        let diags = check_tsx(r#"const App = () => <select required aria-required="true" />;"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_tsx_disabled_false_passes() {
+        let diags =
+            check_tsx(r#"const App = () => <button disabled={false} aria-disabled="true" />;"#);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_tsx_component_ignored() {
+        let diags = check_tsx(r#"const App = () => <Input required aria-required="true" />;"#);
+        assert_eq!(diags.len(), 0);
+    }
+}
@@ -0,0 +1,150 @@
+//! Nuxt head management, for the page-level rules.
+//!
+//! Nuxt components never write `<html>`, `<head>` or `<title>` themselves.
+//! `app.vue`, layouts and pages set them with the `<Html>`, `<Head>` and
+//! `<Title>` components in the template, or with `useHead()`/`useSeoMeta()`
+//! in `<script setup>`. The template is in the tree; these helpers read the
+//! composable calls from the raw SFC source, which is good enough for the
+//! object literals they are almost always called with.
+
+/// Composables that set document head state.
+const HEAD_COMPOSABLES: &[&str] = &["useHead", "useServerHead", "useSeoMeta", "useServerSeoMeta"];
+
+/// The argument text of every head composable call in `source`.
+pub fn head_call_args(source: &str) -> Vec<&str> {
+    let mut calls = Vec::new();
+    for name in HEAD_COMPOSABLES {
+        let mut pos = 0;
+        while let Some(offset) = source[pos..].find(name) {
+            let start = pos + offset;
+            pos = start + name.len();
+            let preceded_by_ident = source[..start]
+                .chars()
+                .next_back()
+                .is_some_and(is_ident_char);
+            let rest = source[pos..].trim_start();
+            if preceded_by_ident || !rest.starts_with('(') {
+                continue;
+            }
+            let open = source.len() - rest.len();
+            calls.push(balanced(source, open));
+        }
+    }
+    calls
+}
+
+/// Whether any head composable call sets `path`, e.g. `["title"]` or
+/// `["htmlAttrs", "lang"]`, to something other than an empty string.
+pub fn head_sets(source: &str, path: &[&str]) -> bool {
+    head_call_args(source).into_iter().any(|args| {
+        let mut scope = Some(args);
+        for key in path {
+            scope = scope.and_then(|s| key_value(s, key));
+        }
+        scope.is_some_and(|value| !is_empty_string(value))
+    })
+}
+
+/// The value of `key` in the object literal text `object`, at any depth.
+/// Shorthand properties (`{ lang }`) yield the key itself.
+fn key_value<'a>(object: &'a str, key: &str) -> Option<&'a str> {
+    let mut pos = 0;
+    while let Some(offset) = object[pos..].find(key) {
+        let start = pos + offset;
+        let end = start + key.len();
+        pos = end;
+        let before = object[..start].trim_end_matches(['"', '\'']);
+        let after = object[end..].trim_start_matches(['"', '\'']);
+        if before.chars().next_back().is_some_and(is_ident_char)
+            || after.chars().next().is_some_and(is_ident_char)
+        {
+            continue;
+        }
+        let after = after.trim_start();
+        if let Some(value) = after.strip_prefix(':') {
+            let value = value.trim_start();
+            let value_start = object.len() - value.len();
+            return Some(value_until_separator(object, value_start));
+        }
+        if after.starts_with(',') || after.starts_with('}') {
+            return Some(&object[start..end]);
+        }
+    }
+    None
+}
+
+/// From `start`, the text up to the next `,` or closing bracket outside of
+/// nested brackets and strings.
+fn value_until_separator(text: &str, start: usize) -> &str {
+    text[start..separator(text, start)].trim_end()
+}
+
+/// The bracketed text starting at the opening bracket at `open`.
+fn balanced(text: &str, open: usize) -> &str {
+    let close = separator(text, open + 1);
+    &text[open..(close + 1).min(text.len())]
+}
+
+/// Index of the first `,` or unmatched closing bracket at or after `start`.
+fn separator(text: &str, start: usize) -> usize {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    for (i, c) in text[start..].char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`') => quote = Some(c),
+            (None, '{' | '[' | '(') => depth += 1,
+            (None, '}' | ']' | ')' | ',') if depth == 0 => return start + i,
+            (None, '}' | ']' | ')') => depth -= 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+fn is_empty_string(value: &str) -> bool {
+    let value = value.trim();
+    ["\"", "'", "`"].iter().any(|q| {
+        value
+            .strip_prefix(q)
+            .and_then(|v| v.strip_suffix(q))
+            .is_some_and(|v| v.trim().is_empty())
+    })
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_head_call_args() {
+        let src =
+            "useHead({ title: 'A' })\nconst x = myuseHead(1)\nuseSeoMeta ({ description: d })";
+        assert_eq!(
+            head_call_args(src),
+            ["({ title: 'A' })", "({ description: d })"]
+        );
+    }
+
+    #[test]
+    fn test_head_sets_nested_key() {
+        let src = "useHead({ htmlAttrs: { lang: locale.value, dir: 'ltr' }, title })";
+        assert!(head_sets(src, &["htmlAttrs", "lang"]));
+        assert!(head_sets(src, &["title"]));
+        assert!(!head_sets(src, &["htmlAttrs", "class"]));
+        assert!(!head_sets(src, &["titleTemplate"]));
+    }
+
+    #[test]
+    fn test_head_sets_ignores_empty_and_other_keys() {
+        assert!(!head_sets("useHead({ title: '' })", &["title"]));
+        assert!(!head_sets("useHead({ subtitle: 'A' })", &["title"]));
+        assert!(head_sets(r#"useSeoMeta({ "title": "A" })"#, &["title"]));
+        assert!(!head_sets("const title = 'A'", &["title"]));
+    }
+}
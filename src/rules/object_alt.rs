@@ -14,6 +14,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.1.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/non-text-content.html",
     default_severity: Severity::Error,
+    axe_rules: &["object-alt"],
 };
 
 impl Rule for ObjectAlt {
@@ -60,10 +61,11 @@ fn check_html_element(element: &Node, source: &str, diagnostics: &mut Vec<Diagno
     }
 
     // A bound `:title`/`:aria-label` still provides an accessible name.
-    let has_accessible_name = html_attrs::attrs(&tag, source).iter().any(|a| {
+    let attrs = html_attrs::attrs(&tag, source);
+    let has_accessible_name = attrs.iter().any(|a| {
         a.name_eq("aria-label") || a.name_eq("aria-labelledby") || a.name_eq("title")
     });
-    if has_accessible_name {
+    if has_accessible_name || html_attrs::has_dynamic_content(&attrs) {
         return;
     }
 
@@ -268,6 +270,12 @@ mod tests {
         rule.check(&tree.root_node(), source, FileType::Vue)
     }
 
+    #[test]
+    fn test_vue_v_text_passes() {
+        let diags = check_vue(r#"<template><object data="a.svg" v-text="fallback"></object></template>"#);
+        assert_eq!(diags.len(), 0, "v-text fills the fallback, got: {diags:?}");
+    }
+
     #[test]
     fn test_vue_bound_aria_label_passes() {
         let diags =
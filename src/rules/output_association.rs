@@ -0,0 +1,206 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct OutputAssociation;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "output-association",
+    description: "<output> elements should be tied to the form controls they show the result of",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.3.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Attributes that tie an `<output>` to controls or a form (`htmlFor` in
+/// React).
+const LINK_ATTRS: &[&str] = &["for", "htmlFor", "form"];
+
+impl Rule for OutputAssociation {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, false, &mut diagnostics);
+        } else {
+            visit_html(root, source, false, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+/// `in_form` is whether `node` is inside a `<form>`.
+fn visit_html(node: &Node, source: &str, in_form: bool, diagnostics: &mut Vec<Diagnostic>) {
+    let mut in_form = in_form;
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source)
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        if name == "form" {
+            in_form = true;
+        } else if name == "output" && !in_form {
+            let attrs = html_attrs::attrs(&tag, source);
+            // A bound value counts: it's some id at runtime.
+            let linked = attrs.iter().any(|a| {
+                LINK_ATTRS.iter().any(|l| a.name_eq(l))
+                    && (a.bound || a.value.as_deref().is_some_and(|v| !v.trim().is_empty()))
+            });
+            // `v-bind="attrs"` may carry `for`.
+            if !linked && !attrs.iter().any(|a| a.name_eq("v-bind")) {
+                diagnostics.push(make_diagnostic(&tag));
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, in_form, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, in_form: bool, diagnostics: &mut Vec<Diagnostic>) {
+    let mut in_form = in_form;
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        if name == "form" {
+            in_form = true;
+        } else if name == "output" && !in_form && !jsx_linked(&tag, source) {
+            diagnostics.push(make_diagnostic(&tag));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, in_form, diagnostics);
+    }
+}
+
+/// Whether a JSX `<output>` has a non-empty `for`/`htmlFor`/`form`, or
+/// spread props that may carry one.
+fn jsx_linked(tag: &Node, source: &str) -> bool {
+    let mut cursor = tag.walk();
+    for child in tag.named_children(&mut cursor) {
+        if child.kind() == "jsx_expression" {
+            return true;
+        }
+        let Some((attr, value)) = jsx_attrs::jsx_attribute(&child, source) else {
+            continue;
+        };
+        let non_empty = value.is_some_and(|v| {
+            v.kind() != "string"
+                || !source[v.byte_range()]
+                    .trim_matches(['"', '\'', ' '])
+                    .is_empty()
+        });
+        if LINK_ATTRS.contains(&attr) && non_empty {
+            return true;
+        }
+    }
+    false
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn make_diagnostic(node: &Node) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "<output> outside a form and without `for`: list the ids of the controls it is computed from in `for`, or move it into their `<form>`. For a status message rather than a result, use an element with role=\"status\" instead. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = OutputAssociation;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_unassociated_output_fails() {
+        let diags = check_html(
+            r#"<input id="a" type="number"> + <input id="b" type="number">
+<output>0</output>
+<output for=" ">0</output>"#,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.line, 1);
+        assert!(diags[0].message.contains("role=\"status\""));
+    }
+
+    #[test]
+    fn test_associated_output_passes() {
+        let source = r#"<form><fieldset><input name="a"><output name="sum">0</output></fieldset></form>
+<output for="a b">0</output>
+<output form="calc">0</output>"#;
+        assert!(check_html(source).is_empty());
+        let vue =
+            r#"<template><output :for="ids">0</output><output v-bind="attrs"></output></template>"#;
+        assert!(check(vue, FileType::Vue).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const C = () => (
+  <>
+    <output htmlFor="a b">{sum}</output>
+    <output htmlFor={ids}>{sum}</output>
+    <output {...props}>{sum}</output>
+    <form><output>{sum}</output></form>
+    <output>{sum}</output>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 6);
+    }
+}
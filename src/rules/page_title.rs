@@ -1,6 +1,8 @@
-use crate::engine::node_to_range;
+use crate::config::{Config, PageTitleConfig};
+use crate::engine::{DOCUMENT_RANGE, node_to_range};
 use crate::parser::FileType;
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use crate::rules::{html_attrs, jsx_attrs, nuxt, react_head};
 use tower_lsp_server::ls_types::*;
 use tree_sitter::Node;
 
@@ -13,70 +15,195 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "2.4.2",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/page-titled.html",
     default_severity: Severity::Error,
+    axe_rules: &["document-title"],
 };
 
+/// Titles that starter templates and editors fill in, by language.
+/// `[page-title.placeholders]` replaces a language's list.
+const DEFAULT_PLACEHOLDERS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "Create Next App",
+            "Document",
+            "Home Page",
+            "My App",
+            "New Page",
+            "Page Title",
+            "React App",
+            "Title",
+            "Untitled",
+            "Untitled Document",
+            "Vite + React",
+            "Vite + React + TS",
+            "Vite + Vue",
+            "Vite + Vue + TS",
+            "Vite App",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "Dokument",
+            "Neue Seite",
+            "Seitentitel",
+            "Titel",
+            "Unbenannt",
+            "Unbenanntes Dokument",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "Documento",
+            "Documento sin título",
+            "Nueva página",
+            "Sin título",
+            "Título",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "Document",
+            "Document sans titre",
+            "Nouvelle page",
+            "Sans titre",
+            "Titre",
+        ],
+    ),
+];
+
 impl Rule for PageTitle {
     fn metadata(&self) -> &RuleMetadata {
         &METADATA
     }
 
+    /// A page title belongs to the page, not to a component.
+    fn page_level(&self) -> bool {
+        true
+    }
+
     fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_config(root, source, file_type, &Config::default())
+    }
+
+    fn check_with_config(
+        &self,
+        root: &Node,
+        source: &str,
+        file_type: FileType,
+        config: &Config,
+    ) -> Vec<Diagnostic> {
+        let settings = &config.page_title;
+        // A Vue SFC only owns the title if it manages the head, Nuxt style.
+        if file_type == FileType::Vue {
+            let mut diagnostics = Vec::new();
+            check_nuxt_head(root, source, settings, &mut diagnostics);
+            return diagnostics;
+        }
+        // Likewise a React component, with `next/head`, `metadata` or Helmet.
+        if file_type.is_jsx_like() {
+            let mut diagnostics = Vec::new();
+            check_react_head(root, source, config, &mut diagnostics);
+            return diagnostics;
+        }
+
         // Document-level rule: a page title only makes sense for full documents,
-        // not for component/template fragments (JSX, Vue SFC, Svelte).
+        // not for component/template fragments (JSX, Vue SFC, Svelte, Angular).
         if file_type.is_fragment() {
             return Vec::new();
         }
 
         let mut diagnostics = Vec::new();
-        check_document(root, source, &mut diagnostics);
+        check_document(root, source, file_type, settings, &mut diagnostics);
         diagnostics
     }
 }
 
 /// Walk the entire document looking for a <title> element with non-empty text.
-/// If no such element is found, report a diagnostic on the root node.
-fn check_document(root: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
-    if find_title_with_content(root, source) {
+/// If no such element is found, report a diagnostic on the whole document.
+/// Otherwise check that the title says something, unless a server template
+/// (PHP, Blade, ERB, Liquid) fills it in.
+fn check_document(
+    root: &Node,
+    source: &str,
+    file_type: FileType,
+    settings: &PageTitleConfig,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some(title) = find_title_with_content(root, source) else {
+        diagnostics.push(Diagnostic {
+            range: DOCUMENT_RANGE,
+            ..make_diagnostic(root)
+        });
         return;
-    }
+    };
 
-    diagnostics.push(make_diagnostic(root));
+    if matches!(file_type, FileType::Html | FileType::Mjml)
+        && let Some(text) = static_text(&title, source)
+    {
+        let language = document_language(root, source);
+        diagnostics.extend(check_title_text(
+            &title,
+            &text,
+            language.as_deref(),
+            settings,
+        ));
+    }
 }
 
 /// Recursively search for a <title> element that has non-empty text content.
-fn find_title_with_content(node: &Node, source: &str) -> bool {
+fn find_title_with_content<'a>(node: &Node<'a>, source: &str) -> Option<Node<'a>> {
     if node.kind() == "element" && is_title_element_with_content(node, source) {
-        return true;
+        return Some(*node);
     }
 
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        if find_title_with_content(&child, source) {
-            return true;
+        if let Some(title) = find_title_with_content(&child, source) {
+            return Some(title);
         }
     }
 
-    false
+    None
 }
 
-/// Check whether a given element node is a <title> with non-empty text content.
-fn is_title_element_with_content(element: &Node, source: &str) -> bool {
-    let mut is_title = false;
+/// The primary language subtag of `<html lang>`, lowercase.
+fn document_language(node: &Node, source: &str) -> Option<String> {
+    if node.kind() == "element"
+        && html_attrs::element_tag_name(node, source)
+            .is_some_and(|name| name.eq_ignore_ascii_case("html"))
+    {
+        let lang = html_attrs::element_attr_value(node, source, "lang")?;
+        let language = lang.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        return (!language.is_empty()).then_some(language);
+    }
 
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .find_map(|child| document_language(&child, source))
+}
+
+/// The text of an element holding nothing but text, unless it has
+/// `{{ interpolation }}`.
+fn static_text(element: &Node, source: &str) -> Option<String> {
+    let mut text = String::new();
     let mut cursor = element.walk();
     for child in element.children(&mut cursor) {
-        if child.kind() == "start_tag" {
-            let mut tag_cursor = child.walk();
-            for tag_child in child.children(&mut tag_cursor) {
-                if tag_child.kind() == "tag_name" {
-                    let name = &source[tag_child.byte_range()];
-                    if name.eq_ignore_ascii_case("title") {
-                        is_title = true;
-                    }
-                }
-            }
+        match child.kind() {
+            "text" => text.push_str(&source[child.byte_range()]),
+            "start_tag" | "end_tag" => {}
+            _ => return None,
         }
     }
+    (!text.contains("{{")).then_some(text)
+}
+
+/// Check whether a given element node is a <title> with non-empty text content.
+fn is_title_element_with_content(element: &Node, source: &str) -> bool {
+    let is_title = html_attrs::element_tag_name(element, source)
+        .is_some_and(|name| name.eq_ignore_ascii_case("title"));
 
     if !is_title {
         return false;
@@ -96,6 +223,294 @@ fn is_title_element_with_content(element: &Node, source: &str) -> bool {
     false
 }
 
+// ---------------------------------------------------------------------------
+// Nuxt
+// ---------------------------------------------------------------------------
+
+/// Checks Nuxt's `<Head>`/`<Title>` components: a `<Title>` must have
+/// content, and a `<Head>` block must set a title, unless `useHead()` or
+/// `useSeoMeta()` does. Components that don't touch the head aren't checked;
+/// their title comes from `app.vue` or a layout.
+fn check_nuxt_head(
+    root: &Node,
+    source: &str,
+    settings: &PageTitleConfig,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut heads = Vec::new();
+    let mut titles = Vec::new();
+    find_components(root, source, &mut heads, &mut titles);
+
+    for title in &titles {
+        if !nuxt_title_has_content(title, source) {
+            diagnostics.push(make_diagnostic(title));
+        } else if let Some(text) = static_text(title, source) {
+            diagnostics.extend(check_title_text(title, &text, None, settings));
+        }
+    }
+
+    let script_title =
+        nuxt::head_sets(source, &["title"]) || nuxt::head_sets(source, &["titleTemplate"]);
+    if titles.is_empty()
+        && !script_title
+        && let Some(head) = heads.first()
+    {
+        diagnostics.push(make_diagnostic(head));
+    }
+}
+
+/// `<Head>` and `<Title>` elements. Component names are case-sensitive, so
+/// a plain `<head>`/`<title>` in a template isn't one.
+fn find_components<'a>(
+    node: &Node<'a>,
+    source: &str,
+    heads: &mut Vec<Node<'a>>,
+    titles: &mut Vec<Node<'a>>,
+) {
+    if node.kind() == "element" {
+        match html_attrs::element_tag_name(node, source) {
+            Some("Head") => heads.push(*node),
+            Some("Title") => titles.push(*node),
+            _ => {}
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        find_components(&child, source, heads, titles);
+    }
+}
+
+/// Text (including `{{ interpolation }}`), `v-text`/`v-html`, or a child
+/// element that may render some.
+fn nuxt_title_has_content(title: &Node, source: &str) -> bool {
+    if html_attrs::has_dynamic_content(&html_attrs::element_attrs(title, source)) {
+        return true;
+    }
+    let mut cursor = title.walk();
+    title.children(&mut cursor).any(|child| match child.kind() {
+        "text" => !source[child.byte_range()].trim().is_empty(),
+        "element" => true,
+        _ => false,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// React (JSX / TSX)
+// ---------------------------------------------------------------------------
+
+/// Checks the ways a React page sets its title: every `<title>` and Helmet
+/// `title`/`defaultTitle` prop must have content, an exported Next.js
+/// `metadata.title` must not be empty, and a head component (`next/head`,
+/// Helmet, …) must set a title unless `metadata` does.
+fn check_react_head(root: &Node, source: &str, config: &Config, diagnostics: &mut Vec<Diagnostic>) {
+    let settings = &config.page_title;
+    let head_names = react_head::head_components(root, source, &config.components);
+    let mut heads = Vec::new();
+    let mut titles = Vec::new();
+    find_jsx_head(root, source, &head_names, &mut heads, &mut titles);
+
+    for title in &titles {
+        if !jsx_title_has_content(title, source) {
+            diagnostics.push(make_diagnostic(title));
+        } else if let Some(text) = jsx_static_text(title, source) {
+            diagnostics.extend(check_title_text(title, &text, None, settings));
+        }
+    }
+
+    let mut title_props = 0;
+    for head in &heads {
+        for (attr, value) in title_props_of(head, source) {
+            title_props += 1;
+            if jsx_attrs::is_empty_jsx_value(value.as_ref(), source) {
+                diagnostics.push(make_diagnostic(&attr));
+            } else if let Some(text) = value.and_then(|v| static_string(&v, source)) {
+                diagnostics.extend(check_title_text(&attr, &text, None, settings));
+            }
+        }
+    }
+
+    let metadata_title = react_head::metadata_title(root, source);
+    if let Some(value) = metadata_title {
+        if react_head::is_empty_title(&value, source) {
+            diagnostics.push(make_diagnostic(&value));
+        } else if let Some(text) = static_string(&value, source) {
+            diagnostics.extend(check_title_text(&value, &text, None, settings));
+        }
+    }
+
+    if titles.is_empty()
+        && title_props == 0
+        && metadata_title.is_none()
+        && let Some(head) = heads.first()
+    {
+        diagnostics.push(make_diagnostic(head));
+    }
+}
+
+/// Head component elements (under whatever name they were imported as) and
+/// `<title>` elements.
+fn find_jsx_head<'a>(
+    node: &Node<'a>,
+    source: &str,
+    head_names: &[&str],
+    heads: &mut Vec<Node<'a>>,
+    titles: &mut Vec<Node<'a>>,
+) {
+    if let Some(name) = jsx_name(node, source) {
+        if name == "title" {
+            titles.push(*node);
+        } else if head_names.contains(&name) {
+            heads.push(*node);
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        find_jsx_head(&child, source, head_names, heads, titles);
+    }
+}
+
+/// Helmet's `title` and `defaultTitle` props on a head component, with
+/// their values.
+fn title_props_of<'a>(element: &Node<'a>, source: &str) -> Vec<(Node<'a>, Option<Node<'a>>)> {
+    let tag = match element.kind() {
+        "jsx_element" => element.child(0),
+        _ => Some(*element),
+    };
+    let Some(tag) = tag else {
+        return Vec::new();
+    };
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .filter_map(|attr| {
+            let (name, value) = jsx_attrs::jsx_attribute(&attr, source)?;
+            matches!(name, "title" | "defaultTitle").then_some((attr, value))
+        })
+        .collect()
+}
+
+/// The tag name of a `jsx_element`/`jsx_self_closing_element`.
+fn jsx_name<'s>(node: &Node, source: &'s str) -> Option<&'s str> {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => *node,
+        "jsx_element" => node.child(0)?,
+        _ => return None,
+    };
+    let name = tag.child_by_field_name("name")?;
+    Some(&source[name.byte_range()])
+}
+
+/// Text, a child element, or an expression other than `{""}` or a comment.
+fn jsx_title_has_content(title: &Node, source: &str) -> bool {
+    let mut cursor = title.walk();
+    title.children(&mut cursor).any(|child| match child.kind() {
+        "jsx_text" => !source[child.byte_range()].trim().is_empty(),
+        "jsx_element" | "jsx_self_closing_element" => true,
+        "jsx_expression" => !jsx_attrs::is_empty_jsx_value(Some(&child), source),
+        _ => false,
+    })
+}
+
+/// The text of a JSX element holding nothing but text.
+fn jsx_static_text(element: &Node, source: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut cursor = element.walk();
+    for child in element.children(&mut cursor) {
+        match child.kind() {
+            "jsx_text" => text.push_str(&source[child.byte_range()]),
+            "jsx_opening_element" | "jsx_closing_element" => {}
+            _ => return None,
+        }
+    }
+    Some(text)
+}
+
+/// The value of a string literal, a template string without substitutions,
+/// or a `{"…"}` JSX expression holding one.
+fn static_string(node: &Node, source: &str) -> Option<String> {
+    match node.kind() {
+        "string" => Some(
+            source[node.byte_range()]
+                .trim_matches(['"', '\''])
+                .to_string(),
+        ),
+        "template_string" => {
+            let mut cursor = node.walk();
+            let substitution = node
+                .children(&mut cursor)
+                .any(|c| c.kind() == "template_substitution");
+            (!substitution).then(|| source[node.byte_range()].trim_matches('`').to_string())
+        }
+        "jsx_expression" => static_string(&node.named_child(0)?, source),
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Title text
+// ---------------------------------------------------------------------------
+
+/// Flags a title that's a placeholder or longer than `max-length`.
+/// `language` picks the placeholder list; without one (or without a list
+/// for it), every list applies.
+fn check_title_text(
+    node: &Node,
+    text: &str,
+    language: Option<&str>,
+    settings: &PageTitleConfig,
+) -> Option<Diagnostic> {
+    let title = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if is_placeholder(&title, language, settings) {
+        return Some(make_text_diagnostic(
+            node,
+            &format!(
+                "The title \"{title}\" is a placeholder; describe the page's topic or purpose"
+            ),
+        ));
+    }
+    let length = title.chars().count();
+    if settings.max_length > 0 && length > settings.max_length {
+        return Some(make_text_diagnostic(
+            node,
+            &format!(
+                "The title is {length} characters long; keep it to {} or fewer, leading with what's unique to the page",
+                settings.max_length
+            ),
+        ));
+    }
+    None
+}
+
+fn is_placeholder(title: &str, language: Option<&str>, settings: &PageTitleConfig) -> bool {
+    let mut lists: Vec<(&str, Vec<&str>)> = DEFAULT_PLACEHOLDERS
+        .iter()
+        .filter(|(lang, _)| !settings.placeholders.contains_key(*lang))
+        .map(|(lang, titles)| (*lang, titles.to_vec()))
+        .collect();
+    lists.extend(
+        settings
+            .placeholders
+            .iter()
+            .map(|(lang, titles)| (lang.as_str(), titles.iter().map(String::as_str).collect())),
+    );
+
+    let language = language.filter(|l| lists.iter().any(|(lang, _)| lang == l));
+    let title = title.to_lowercase();
+    lists
+        .iter()
+        .filter(|(lang, _)| language.is_none_or(|l| l == *lang))
+        .flat_map(|(_, titles)| titles)
+        .any(|placeholder| {
+            placeholder
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase()
+                == title
+        })
+}
+
 fn make_diagnostic(node: &Node) -> Diagnostic {
     let meta = &METADATA;
     Diagnostic {
@@ -114,6 +529,24 @@ fn make_diagnostic(node: &Node) -> Diagnostic {
     }
 }
 
+fn make_text_diagnostic(node: &Node, detail: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{}. {} [WCAG {} Level {:?}]",
+            detail, meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +587,89 @@ mod tests {
         assert_eq!(diags.len(), 1);
     }
 
+    #[test]
+    fn test_placeholder_title_fails() {
+        let diags = check_html("<html><head><title>React  App</title></head></html>");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 12);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("The title \"React App\" is a placeholder")
+        );
+        assert_eq!(check_html("<title>untitled document</title>").len(), 1);
+    }
+
+    #[test]
+    fn test_placeholder_list_follows_document_language() {
+        assert_eq!(
+            check_html(r#"<html lang="de-AT"><title>Unbenannt</title></html>"#).len(),
+            1
+        );
+        // Only the German list applies to a German page.
+        assert_eq!(
+            check_html(r#"<html lang="de"><title>Untitled</title></html>"#).len(),
+            0
+        );
+        // Languages without a list fall back to all of them.
+        assert_eq!(
+            check_html(r#"<html lang="ja"><title>Untitled</title></html>"#).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_configured_placeholders_replace_language_list() {
+        let config = Config::parse("[page-title.placeholders]\nen = [\"Welcome\"]\n");
+        let check = |source: &str| {
+            let mut parser = parser::create_parser(FileType::Html).unwrap();
+            let tree = parser.parse(source, None).unwrap();
+            PageTitle.check_with_config(&tree.root_node(), source, FileType::Html, &config)
+        };
+        assert_eq!(
+            check(r#"<html lang="en"><title>Welcome</title></html>"#).len(),
+            1
+        );
+        assert_eq!(
+            check(r#"<html lang="en"><title>React App</title></html>"#).len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_overlong_title_fails() {
+        let title = "Quarterly results, outlook and guidance for investors and analysts, 2026";
+        let diags = check_html(&format!("<title>{title}</title>"));
+        assert_eq!(diags.len(), 1);
+        assert!(
+            diags[0]
+                .message
+                .contains("is 72 characters long; keep it to 70")
+        );
+
+        let config = Config::parse("[page-title]\nmax-length = 0\n");
+        let source = format!("<title>{title}</title>");
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(&source, None).unwrap();
+        let diags =
+            PageTitle.check_with_config(&tree.root_node(), &source, FileType::Html, &config);
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_template_titles_not_judged() {
+        let mut parser = parser::create_parser(FileType::Php).unwrap();
+        let source = "<title>Document</title>";
+        let tree = parser.parse(source, None).unwrap();
+        assert_eq!(
+            PageTitle
+                .check(&tree.root_node(), source, FileType::Php)
+                .len(),
+            0
+        );
+        assert_eq!(check_html("<title>{{ title }}</title>").len(), 0);
+    }
+
     #[test]
     fn test_non_html_returns_empty() {
         let mut parser = parser::create_parser(FileType::Tsx).unwrap();
@@ -164,6 +680,186 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = PageTitle;
+        rule.check(&tree.root_node(), source, FileType::Tsx)
+    }
+
+    #[test]
+    fn test_next_head_title_passes() {
+        let diags = check_tsx(
+            r#"import Head from "next/head";
+const Page = () => <><Head><title>{`${post.title} | Blog`}</title></Head><main /></>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_next_head_without_title_fails() {
+        let diags = check_tsx(
+            r#"import NextHead from "next/head";
+const Page = () => <NextHead><meta name="description" content="x" /></NextHead>;"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 1);
+        assert_eq!(diags[0].range.start.character, 19);
+    }
+
+    #[test]
+    fn test_jsx_empty_title_fails() {
+        let diags = check_tsx(
+            r#"import Head from "next/head";
+const Page = () => <Head><title>{""}</title><title>{/* todo */}</title></Head>;"#,
+        );
+        assert_eq!(diags.len(), 2);
+    }
+
+    #[test]
+    fn test_helmet_title() {
+        let diags = check_tsx(
+            r#"import { Helmet } from "react-helmet-async";
+const Page = () => <><Helmet><title>Cart</title></Helmet><Helmet title="Cart" /></>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+
+        let diags = check_tsx(
+            r#"import { Helmet } from "react-helmet";
+const Page = () => <Helmet title="" htmlAttributes={{ lang: "en" }} />;"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 27);
+    }
+
+    #[test]
+    fn test_helmet_without_title_fails() {
+        let diags = check_tsx(
+            r#"import MetaTags from "react-meta-tags";
+const Page = () => <MetaTags><meta name="description" content="x" /></MetaTags>;"#,
+        );
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_configured_head_wrapper() {
+        let config = Config::parse("[components]\nSeoHead = \"head\"\n");
+        let source = r#"import SeoHead from "../components/seo-head";
+const Page = () => <SeoHead><meta name="description" content="x" /></SeoHead>;"#;
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let diags = PageTitle.check_with_config(&tree.root_node(), source, FileType::Tsx, &config);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 19);
+        assert!(check_tsx(source).is_empty());
+
+        // The engine sees it renamed to `head`.
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(PageTitle)];
+        let (diags, _) =
+            crate::scan::lint_source("file:///page.tsx", source, FileType::Tsx, &rules, &config);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 19);
+        let titled = source.replace("<meta", "<title>Cart</title><meta");
+        let (diags, _) =
+            crate::scan::lint_source("file:///page.tsx", &titled, FileType::Tsx, &rules, &config);
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_next_document_head_not_checked() {
+        // `next/document`'s `<Head>` holds document-wide tags, not the title.
+        let diags = check_tsx(
+            r#"import { Html, Head, Main } from "next/document";
+const Doc = () => <Html lang="en"><Head /><body><Main /></body></Html>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_next_metadata_title() {
+        let diags = check_tsx(r#"export const metadata: Metadata = { title: "Dashboard" };"#);
+        assert_eq!(diags.len(), 0);
+
+        let diags = check_tsx(
+            r#"export const metadata = { title: { default: "", template: "%s | Site" } };"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 33);
+    }
+
+    #[test]
+    fn test_jsx_placeholder_titles_fail() {
+        let diags = check_tsx(
+            r#"import { Helmet } from "react-helmet";
+export const metadata = { title: "Create Next App" };
+const Page = () => <><Helmet title={"Vite App"} /><Helmet><title>Document</title></Helmet></>;"#,
+        );
+        assert_eq!(diags.len(), 3);
+        assert!(diags.iter().all(|d| d.message.contains("is a placeholder")));
+    }
+
+    #[test]
+    fn test_next_head_with_metadata_title_passes() {
+        let diags = check_tsx(
+            r#"import Head from "next/head";
+export const metadata = { title: "Home" };
+const Page = () => <Head><meta name="robots" content="noindex" /></Head>;"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    fn check_vue(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Vue).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = PageTitle;
+        rule.check(&tree.root_node(), source, FileType::Vue)
+    }
+
+    #[test]
+    fn test_nuxt_title_component_passes() {
+        let diags = check_vue(
+            r#"<template><Html lang="en"><Head><Title>{{ page.title }}</Title></Head></Html></template>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_nuxt_placeholder_title_fails() {
+        let diags = check_vue(r#"<template><Head><Title>Vite + Vue</Title></Head></template>"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_nuxt_empty_title_fails() {
+        let diags = check_vue(r#"<template><Head><Title> </Title></Head></template>"#);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 16);
+    }
+
+    #[test]
+    fn test_nuxt_head_without_title_fails() {
+        let diags = check_vue(
+            r#"<template><Head><Meta name="description" content="x" /></Head></template>"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 10);
+    }
+
+    #[test]
+    fn test_nuxt_head_with_use_head_title_passes() {
+        let source = r#"<script setup>
+useHead({ titleTemplate: (t) => `${t} - Site` })
+</script>
+<template><Head><Meta name="description" content="x" /></Head></template>"#;
+        assert_eq!(check_vue(source).len(), 0);
+    }
+
+    #[test]
+    fn test_nuxt_page_without_head_passes() {
+        let source = "<script setup>\nuseSeoMeta({ description: 'x' })\n</script>\n<template><div>Page</div></template>";
+        assert_eq!(check_vue(source).len(), 0);
+    }
+
     #[test]
     fn test_vue_sfc_fragment_returns_empty() {
         // A Vue SFC template is a fragment, not a document — no title expected.
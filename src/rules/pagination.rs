@@ -0,0 +1,393 @@
+use crate::analysis::{Analysis, Element};
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::quickfix::{self, Fix};
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel, aria_name, patterns};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct Pagination;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "pagination",
+    description: "Pagination links must say where they lead and mark the current page",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.4.4",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/link-purpose-in-context.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// Words in a class, id or label that mark a pagination nav.
+const KEYWORDS: &[&str] = &["pagination", "paginator", "pager", "page navigation"];
+
+/// Classes that mark the current page.
+const CURRENT_CLASSES: &[&str] = &["active", "current", "selected"];
+
+/// Character references pagination arrows are often written with.
+const ENTITIES: &[(&str, &str)] = &[
+    ("&laquo;", "«"),
+    ("&lsaquo;", "‹"),
+    ("&larr;", "←"),
+    ("&lt;", "<"),
+    ("&raquo;", "»"),
+    ("&rsaquo;", "›"),
+    ("&rarr;", "→"),
+    ("&gt;", ">"),
+    ("&hellip;", "…"),
+    ("&nbsp;", " "),
+];
+
+impl Rule for Pagination {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, config: &Config) -> Vec<Diagnostic> {
+        let elements = analysis.elements();
+        let mut diagnostics = Vec::new();
+        for nav in patterns::outermost(analysis, |e| patterns::named_like(e, KEYWORDS)) {
+            let inside: Vec<usize> = std::iter::once(nav)
+                .chain(patterns::descendants(analysis, nav))
+                .collect();
+            let mut pages = 0;
+            for &i in &inside {
+                let control = &elements[i];
+                if !is_control(control) || control.spread || labelled(control) {
+                    continue;
+                }
+                let text = aria_name::text(analysis, control.node());
+                // Content only known at runtime may well be readable.
+                if text.contains('{') {
+                    continue;
+                }
+                let symbols = ENTITIES
+                    .iter()
+                    .fold(text.clone(), |text, (entity, c)| text.replace(entity, c));
+                if !symbols.chars().any(char::is_alphanumeric) {
+                    diagnostics.push(unreadable(control, &text, &symbols));
+                } else if text.chars().all(|c| c.is_ascii_digit()) {
+                    pages += 1;
+                    if config.pagination.bare_numbers {
+                        diagnostics.push(bare_number(control, &text));
+                    }
+                }
+            }
+            // A single page link or a list rendered at runtime has no
+            // current page to mark.
+            if pages < 2
+                || inside
+                    .iter()
+                    .any(|&i| elements[i].attr("aria-current").is_some())
+            {
+                continue;
+            }
+            let marked = inside
+                .iter()
+                .copied()
+                .find(|&i| patterns::has_class(&elements[i], CURRENT_CLASSES));
+            if let Some(marked) = marked {
+                diagnostics.push(unmarked_current(analysis, marked, &inside));
+            } else if !inside.iter().any(|&i| elements[i].spread) {
+                diagnostics.push(make_diagnostic(
+                    &elements[nav].tag,
+                    "No link in this pagination has aria-current=\"page\": add it to the link to the current page",
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// A link or button, native or by role.
+fn is_control(element: &Element) -> bool {
+    match &element.role {
+        Some(Some(role)) => role == "link" || role == "button",
+        Some(None) => false,
+        None => element.name == "a" || element.name == "button",
+    }
+}
+
+/// Named by `aria-label`, `aria-labelledby` or `title` rather than its text.
+fn labelled(element: &Element) -> bool {
+    element.named || element.attr("title").is_some()
+}
+
+fn unreadable(control: &Element, text: &str, symbols: &str) -> Diagnostic {
+    let shown = if text.is_empty() {
+        "with no text".to_string()
+    } else {
+        format!("\"{text}\"")
+    };
+    let Some(label) = direction(control, symbols) else {
+        return make_diagnostic(
+            &control.tag,
+            &format!(
+                "Pagination control {shown} has no readable name: give it an aria-label such as \"Next page\""
+            ),
+        );
+    };
+    let mut diagnostic = make_diagnostic(
+        &control.tag,
+        &format!("Pagination control {shown} has no readable name: give it aria-label=\"{label}\""),
+    );
+    add_attr_fix(&mut diagnostic, control, "aria-label", label, false);
+    diagnostic
+}
+
+/// The label a control without text most likely needs, from its `rel`, its
+/// class or the arrow it shows.
+fn direction(control: &Element, symbols: &str) -> Option<&'static str> {
+    let rel = control.static_value("rel").unwrap_or("").to_lowercase();
+    let class = ["class", "className"]
+        .iter()
+        .filter_map(|name| control.static_value(name))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let has = |word: &str| rel.split_whitespace().any(|r| r == word) || class.contains(word);
+    if has("first") {
+        Some("First page")
+    } else if has("last") {
+        Some("Last page")
+    } else if has("prev") || symbols.contains(['‹', '«', '←', '<']) {
+        Some("Previous page")
+    } else if has("next") || symbols.contains(['›', '»', '→', '>']) {
+        Some("Next page")
+    } else {
+        None
+    }
+}
+
+fn bare_number(control: &Element, number: &str) -> Diagnostic {
+    let label = format!("Page {number}");
+    let mut diagnostic = make_diagnostic(
+        &control.tag,
+        &format!(
+            "Page link \"{number}\" is named only by its number, which says little out of context: give it aria-label=\"{label}\""
+        ),
+    );
+    add_attr_fix(&mut diagnostic, control, "aria-label", &label, false);
+    diagnostic
+}
+
+/// The current page shown only by a class. The fix goes on the link when
+/// the class is on its list item.
+fn unmarked_current(analysis: &Analysis, marked: usize, inside: &[usize]) -> Diagnostic {
+    let elements = analysis.elements();
+    let target = std::iter::once(marked)
+        .chain(patterns::descendants(analysis, marked))
+        .find(|&i| is_control(&elements[i]))
+        .filter(|i| inside.contains(i))
+        .unwrap_or(marked);
+    let mut diagnostic = make_diagnostic(
+        &elements[marked].tag,
+        "The current page is marked only by its class, which assistive technology doesn't announce: add aria-current=\"page\"",
+    );
+    add_attr_fix(
+        &mut diagnostic,
+        &elements[target],
+        "aria-current",
+        "page",
+        true,
+    );
+    diagnostic
+}
+
+fn add_attr_fix(
+    diagnostic: &mut Diagnostic,
+    element: &Element,
+    name: &str,
+    value: &str,
+    safe: bool,
+) {
+    let Some(tag_name) = element.name_node() else {
+        return;
+    };
+    quickfix::attach(
+        diagnostic,
+        Fix {
+            title: format!("Add {name}=\"{value}\""),
+            edits: vec![quickfix::insert_attr(&tag_name, name, value)],
+            safe,
+            ..Default::default()
+        },
+    );
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = Pagination;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_labelled_pagination_passes() {
+        let source = r#"<nav aria-label="Pagination">
+  <ul>
+    <li><a href="?page=1" aria-label="Previous page">‹</a></li>
+    <li><a href="?page=1" aria-label="Page 1">1</a></li>
+    <li><a href="?page=2" aria-label="Page 2" aria-current="page">2</a></li>
+    <li><a href="?page=3">Page 3</a></li>
+    <li><a href="?page=3" rel="next">Next</a></li>
+  </ul>
+</nav>
+<ul class="menu"><li><a href="/">1</a></li><li><a href="/b">»</a></li></ul>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_unreadable_controls_fail() {
+        let source = r#"<div class="pager">
+  <a href="?p=1" class="pager-prev">&laquo;</a>
+  <a href="?p=3">›</a>
+  <button class="pager-first"><svg></svg></button>
+  <a href="?p=9">*</a>
+</div>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 4);
+        assert!(diags[0].message.starts_with(
+            "Pagination control \"&laquo;\" has no readable name: give it aria-label=\"Previous page\""
+        ));
+        assert!(diags[1].message.contains("aria-label=\"Next page\""));
+        assert!(
+            diags[2]
+                .message
+                .starts_with("Pagination control with no text has no readable name: give it aria-label=\"First page\"")
+        );
+        assert!(diags[3].message.contains("such as \"Next page\""));
+        assert!(quickfix::fixes(&diags[3]).is_empty());
+
+        let fixes = quickfix::fixes(&diags[1]);
+        assert_eq!(fixes.len(), 1);
+        assert!(!fixes[0].safe);
+        let fixed = quickfix::apply(source, &fixes[0].edits).unwrap();
+        assert!(fixed.contains(r#"<a aria-label="Next page" href="?p=3">›</a>"#));
+    }
+
+    #[test]
+    fn test_bare_numbers_and_current_page() {
+        let source = r#"<nav class="pagination">
+  <a href="?p=1">1</a>
+  <span class="is-active"><a href="?p=2">2</a></span>
+  <a href="?p=3">3</a>
+</nav>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 4);
+        assert!(diags[0].message.starts_with(
+            "Page link \"1\" is named only by its number, which says little out of context: give it aria-label=\"Page 1\""
+        ));
+        assert_eq!(diags[1].range.start.line, 2);
+        assert!(
+            diags[3]
+                .message
+                .starts_with("The current page is marked only by its class")
+        );
+        assert_eq!(diags[3].range.start.line, 2);
+
+        let fixes = quickfix::fixes(&diags[3]);
+        assert!(fixes[0].safe);
+        let fixed = quickfix::apply(source, &fixes[0].edits).unwrap();
+        assert!(fixed.contains(r#"<span class="is-active"><a aria-current="page" href="?p=2">"#));
+    }
+
+    #[test]
+    fn test_bare_numbers_configurable() {
+        let source = r#"<nav aria-label="Page navigation">
+  <a href="?p=1" aria-current="page">1</a>
+  <a href="?p=2">2</a>
+</nav>"#;
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let analysis = Analysis::new(tree.root_node(), source, FileType::Html);
+        assert_eq!(
+            Pagination
+                .check_with_analysis(&analysis, &Config::default())
+                .len(),
+            2
+        );
+        let config = Config::parse("[pagination]\nbare-numbers = false\n");
+        assert!(
+            Pagination
+                .check_with_analysis(&analysis, &config)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_missing_current_page_fails() {
+        let config = Config::parse("[pagination]\nbare-numbers = false\n");
+        let source = r#"<template>
+  <ul class="paginator">
+    <li><a href="?p=1">1</a></li>
+    <li><a href="?p=2" :class="{ active: page === 2 }">2</a></li>
+    <li><a href="?p=3">3</a></li>
+    <li v-for="p in pages"><a :href="p">{{ p }}</a></li>
+  </ul>
+</template>"#;
+        let mut parser = parser::create_parser(FileType::Vue).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let analysis = Analysis::new(tree.root_node(), source, FileType::Vue);
+        let diags = Pagination.check_with_analysis(&analysis, &config);
+        assert_eq!(diags.len(), 1);
+        assert!(
+            diags[0]
+                .message
+                .starts_with("No link in this pagination has aria-current=\"page\"")
+        );
+        assert_eq!(diags[0].range.start.line, 1);
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Pager = ({ page }) => (
+  <nav className="Pagination">
+    <a href="?p=1" aria-current={page === 1 ? "page" : undefined}>1</a>
+    <a href="?p=2" aria-label="Page 2">2</a>
+    <Link to="?p=3">3</Link>
+    <button className="pagination__next" onClick={next}>→</button>
+    <a {...prevProps}>←</a>
+    <a href={url}>{label}</a>
+  </nav>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert!(diags[1].message.contains("aria-label=\"Next page\""));
+    }
+}
@@ -0,0 +1,114 @@
+//! Widgets built from generic markup, such as pagination, recognized by the
+//! words in their class, id and label, for the rules that check how such a
+//! pattern is put together.
+
+use crate::analysis::{Analysis, Element};
+
+/// Attributes whose words name the pattern an element implements.
+const NAMING_ATTRS: &[&str] = &["class", "className", "id", "aria-label"];
+
+/// Whether a static class, id or `aria-label` of the element contains one of
+/// `keywords`, which are lowercase.
+pub fn named_like(element: &Element, keywords: &[&str]) -> bool {
+    NAMING_ATTRS.iter().any(|name| {
+        element.static_value(name).is_some_and(|value| {
+            let value = value.to_lowercase();
+            keywords.iter().any(|keyword| value.contains(keyword))
+        })
+    })
+}
+
+/// Whether a static class of the element is one of `classes`, which are
+/// lowercase, or ends in one after a `-` or `_`, like `is-active`.
+pub fn has_class(element: &Element, classes: &[&str]) -> bool {
+    ["class", "className"].iter().any(|name| {
+        element.static_value(name).is_some_and(|value| {
+            value.split_whitespace().any(|token| {
+                let token = token.to_lowercase();
+                classes.iter().any(|class| {
+                    token == *class
+                        || token
+                            .strip_suffix(class)
+                            .is_some_and(|rest| rest.ends_with(['-', '_']))
+                })
+            })
+        })
+    })
+}
+
+/// The elements that match `detect`, leaving out those inside another one:
+/// a pattern is checked once, from its outermost element.
+pub fn outermost(analysis: &Analysis, detect: impl Fn(&Element) -> bool) -> Vec<usize> {
+    outermost_in(analysis, 0..analysis.elements().len(), detect)
+}
+
+/// Like [`outermost`], among `candidates` in document order, such as the
+/// [`descendants`] of a pattern's element.
+pub fn outermost_in(
+    analysis: &Analysis,
+    candidates: impl IntoIterator<Item = usize>,
+    detect: impl Fn(&Element) -> bool,
+) -> Vec<usize> {
+    let elements = analysis.elements();
+    let mut found: Vec<usize> = Vec::new();
+    for i in candidates {
+        if detect(&elements[i]) && !found.last().is_some_and(|&last| inside(analysis, i, last)) {
+            found.push(i);
+        }
+    }
+    found
+}
+
+/// The elements inside element `i`, in document order.
+pub fn descendants<'b>(analysis: &'b Analysis, i: usize) -> impl Iterator<Item = usize> + 'b {
+    // Document order puts an element's descendants right after it.
+    (i + 1..analysis.elements().len()).take_while(move |&j| inside(analysis, j, i))
+}
+
+/// Whether element `j` is inside element `i`.
+fn inside(analysis: &Analysis, j: usize, i: usize) -> bool {
+    let elements = analysis.elements();
+    let mut current = elements[j].parent;
+    while let Some(p) = current {
+        if p == i {
+            return true;
+        }
+        current = elements[p].parent;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{self, FileType};
+
+    #[test]
+    fn test_detection_and_descendants() {
+        let source = r#"<nav aria-label="Pagination">
+  <ul class="site-pagination"><li class="is-active"><a href="?p=1">1</a></li></ul>
+</nav>
+<div class="pagination-wrapper"></div>
+<p class="active-users"></p>"#;
+        let mut parser = parser::create_parser(FileType::Html).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let analysis = Analysis::new(tree.root_node(), source, FileType::Html);
+        let elements = analysis.elements();
+
+        let found = outermost(&analysis, |e| named_like(e, &["pagination"]));
+        assert_eq!(found, vec![0, 4]);
+        let inside: Vec<&str> = descendants(&analysis, 0)
+            .map(|j| elements[j].name.as_str())
+            .collect();
+        assert_eq!(inside, vec!["ul", "li", "a"]);
+        assert_eq!(descendants(&analysis, 4).count(), 0);
+
+        let items = outermost_in(&analysis, descendants(&analysis, 0), |e| {
+            e.name == "ul" || e.name == "li"
+        });
+        assert_eq!(items, vec![1]);
+
+        assert!(has_class(&elements[2], &["active"]));
+        assert!(!has_class(&elements[5], &["active"]));
+    }
+}
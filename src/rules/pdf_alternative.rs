@@ -0,0 +1,227 @@
+use crate::analysis::{Analysis, Element};
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct PdfAlternative;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "pdf-alternative",
+    description: "Embedded documents need a link to the document or an accessible alternative",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/non-text-content.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+impl Rule for PdfAlternative {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, config: &Config) -> Vec<Diagnostic> {
+        let extensions = &config.pdf_alternative.extensions;
+        let mut diagnostics = Vec::new();
+        if extensions.is_empty() {
+            return diagnostics;
+        }
+        for (i, element) in analysis.elements().iter().enumerate() {
+            if element.component || (element.name != "object" && element.name != "embed") {
+                continue;
+            }
+            if let Some(document) = embedded_document(element, extensions)
+                && !has_link_nearby(analysis, i)
+            {
+                diagnostics.push(make_diagnostic(&element.tag, &element.name, &document));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Whether `element` has an `href`, such as `<a>` or a router's `<Link>`.
+/// A bound one counts unless it's a literal empty string.
+fn is_link(element: &Element) -> bool {
+    element.attr("href").is_some_and(|a| {
+        let value = a.value.as_deref().map_or("", str::trim);
+        let empty_string = a.bound && matches!(value, "\"\"" | "''" | "``");
+        !value.is_empty() && !empty_string
+    })
+}
+
+/// The URL (or, failing that, the type) of the document an `<object>` or
+/// `<embed>` shows, if it's one of `extensions`.
+fn embedded_document(element: &Element, extensions: &[String]) -> Option<String> {
+    let url = if element.name == "object" {
+        element.static_value("data")
+    } else {
+        element.static_value("src")
+    };
+    if let Some(url) = url
+        && is_document_url(url, extensions)
+    {
+        return Some(url.to_string());
+    }
+    let mime = element.static_value("type")?.to_ascii_lowercase();
+    let subtype = mime.split(';').next()?.split('/').nth(1)?.trim();
+    extensions
+        .iter()
+        .any(|ext| subtype == ext || subtype.strip_prefix("x-") == Some(ext))
+        .then(|| mime.clone())
+}
+
+/// Whether the path of `url` ends in one of `extensions`.
+fn is_document_url(url: &str, extensions: &[String]) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit_once('.').is_some_and(|(_, ext)| {
+        !ext.contains('/') && extensions.iter().any(|e| ext.eq_ignore_ascii_case(e))
+    })
+}
+
+/// Whether a link is in the fallback content of element `i` or next to it:
+/// the element just before or after it (text and comments aside) is a link,
+/// or holds one and isn't a landmark, as a navigation beside it is about
+/// something else.
+fn has_link_nearby(analysis: &Analysis, i: usize) -> bool {
+    let elements = analysis.elements();
+    let parent = elements[i].parent;
+    let previous = (0..i).rev().find(|&j| elements[j].parent == parent);
+    let next = (i + 1..elements.len()).find(|&j| elements[j].parent == parent);
+    let holds_link = |j: usize| subtree(elements, j).any(|k| is_link(&elements[k]));
+    holds_link(i)
+        || [previous, next]
+            .into_iter()
+            .flatten()
+            .any(|j| is_link(&elements[j]) || (analysis.landmark(j).is_none() && holds_link(j)))
+}
+
+/// The elements inside element `i`, which follow it in document order.
+fn subtree(elements: &[Element], i: usize) -> impl Iterator<Item = usize> {
+    (i + 1..elements.len()).take_while(move |&j| is_inside(elements, j, i))
+}
+
+/// Whether element `j` is inside element `i`.
+fn is_inside(elements: &[Element], j: usize, i: usize) -> bool {
+    let mut current = elements[j].parent;
+    while let Some(k) = current {
+        if k == i {
+            return true;
+        }
+        current = elements[k].parent;
+    }
+    false
+}
+
+fn make_diagnostic(tag: &Node, name: &str, document: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(tag),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "`<{name}>` shows `{document}` without a link beside it: browser viewers often aren't usable with assistive technology, so link to the document or an accessible version (HTML, tagged PDF). {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType, config: &Config) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = PdfAlternative;
+        let analysis = Analysis::new(tree.root_node(), source, file_type);
+        rule.check_with_analysis(&analysis, config)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html, &Config::default())
+    }
+
+    #[test]
+    fn test_embedded_pdf_without_link_fails() {
+        let diags = check_html(
+            r#"<div><embed src="/files/Report.PDF?v=2" width="600"></div>
+<section><object type="application/pdf" data="/api/doc/7"><p>Your browser can't show this.</p></object></section>"#,
+        );
+        assert_eq!(diags.len(), 2);
+        assert!(
+            diags[0]
+                .message
+                .contains("`<embed>` shows `/files/Report.PDF?v=2`")
+        );
+        assert!(diags[1].message.contains("`application/pdf`"));
+    }
+
+    #[test]
+    fn test_link_beside_or_inside_passes() {
+        let source = r#"<div><embed src="a.pdf"><p>Or <a href="a.html">read it as a web page</a>.</p></div>
+<object data="b.pdf" type="application/pdf"><a href="b.pdf">Download the PDF</a></object>
+<div><a :href="url">Download</a><object data="c.pdf"></object></div>
+<embed src="movie.swf">
+<object data="chart.svg" type="image/svg+xml"></object>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_unrelated_links_do_not_count() {
+        let diags = check_html(
+            r#"<main><embed src="a.pdf"><nav><a href="/">Home</a></nav></main>
+<div><a href="/">Home</a><p>Intro</p><embed src="b.pdf"><p>Outro</p></div>"#,
+        );
+        assert_eq!(diags.len(), 2);
+    }
+
+    #[test]
+    fn test_empty_href_does_not_count() {
+        let diags = check_html(r#"<div><embed src="a.pdf"><a href="">PDF</a></div>"#);
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn test_configured_extensions() {
+        let config = Config::parse("[pdf-alternative]\nextensions = [\"docx\"]\n");
+        let source = r#"<div><embed src="a.pdf"><object data="b.docx"></object></div>"#;
+        let diags = check(source, FileType::Html, &config);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("b.docx"));
+
+        let off = Config::parse("[pdf-alternative]\nextensions = []\n");
+        assert!(check(source, FileType::Html, &off).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Doc = () => (
+  <>
+    <div><embed src="/a.pdf" /></div>
+    <div><object data="/b.pdf" /><Link href="/b">Accessible version</Link></div>
+    <div><embed src={url} type="application/pdf" /></div>
+  </>
+);"#,
+            FileType::Tsx,
+            &Config::default(),
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert_eq!(diags[1].range.start.line, 4);
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::HashSet;
+
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct ProgressMeterName;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "progress-meter-name",
+    description: "<progress> and <meter> elements must have an accessible name",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "4.1.2",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/name-role-value.html",
+    default_severity: Severity::Error,
+    axe_rules: &[],
+};
+
+const ELEMENTS: &[&str] = &["progress", "meter"];
+
+/// Attributes that name an element on their own.
+const NAME_ATTRS: &[&str] = &["aria-label", "aria-labelledby", "title"];
+
+/// What `<label for>` points at in a file.
+#[derive(Default)]
+struct Labels {
+    ids: HashSet<String>,
+    /// A `for` is only known at runtime, so any id may be labelled.
+    runtime: bool,
+}
+
+/// A `<progress>` or `<meter>`, as far as this rule is concerned.
+struct Element<'a> {
+    tag: Node<'a>,
+    name: &'static str,
+    /// Named by an attribute or a wrapping `<label>`.
+    named: bool,
+    /// The static id, for `<label for>`.
+    id: Option<String>,
+    /// Whether the id, or all of its attributes, are only known at runtime.
+    dynamic: bool,
+    /// `value` without `max`, and the static value if there is one.
+    value_without_max: Option<(Node<'a>, Option<String>)>,
+}
+
+impl Rule for ProgressMeterName {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut labels = Labels::default();
+        let mut elements = Vec::new();
+        if file_type.is_jsx_like() {
+            collect_jsx(root, source, false, &mut labels, &mut elements);
+        } else {
+            collect_html(root, source, false, &mut labels, &mut elements);
+        }
+
+        let mut diagnostics = Vec::new();
+        for element in &elements {
+            let labelled = element.named
+                || element.dynamic
+                || labels.runtime
+                || element
+                    .id
+                    .as_ref()
+                    .is_some_and(|id| labels.ids.contains(id));
+            if !labelled {
+                diagnostics.push(make_diagnostic(
+                    &element.tag,
+                    &format!(
+                        "<{}> without a name: add aria-label, or a <label> with `for` pointing at its id",
+                        element.name
+                    ),
+                ));
+            }
+            if let Some((value, text)) = &element.value_without_max
+                && !text.as_deref().is_some_and(is_fraction)
+            {
+                diagnostics.push(make_diagnostic(
+                    value,
+                    "<progress value> without `max`: max defaults to 1, so larger values show as complete; set `max`",
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn collect_html<'a>(
+    node: &Node<'a>,
+    source: &str,
+    in_label: bool,
+    labels: &mut Labels,
+    elements: &mut Vec<Element<'a>>,
+) {
+    let mut in_label = in_label;
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source)
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let attrs = html_attrs::attrs(&tag, source);
+        let find = |attr: &str| attrs.iter().find(|a| a.name_eq(attr));
+        if name == "label" {
+            in_label = true;
+            match find("for") {
+                Some(a) if a.bound => labels.runtime = true,
+                Some(a) => labels
+                    .ids
+                    .extend(a.value.as_deref().map(|v| v.trim().to_string())),
+                None => {}
+            }
+        } else if let Some(&name) = ELEMENTS.iter().find(|e| **e == name) {
+            // A bound name attribute names it at runtime.
+            let named = in_label
+                || attrs.iter().any(|a| {
+                    NAME_ATTRS.iter().any(|n| a.name_eq(n))
+                        && (a.bound || a.value.as_deref().is_some_and(|v| !v.trim().is_empty()))
+                });
+            let value = find("value").filter(|_| name == "progress" && find("max").is_none());
+            elements.push(Element {
+                tag,
+                name,
+                named,
+                id: find("id")
+                    .filter(|a| !a.bound)
+                    .and_then(|a| a.value.as_deref())
+                    .map(|v| v.trim().to_string()),
+                dynamic: find("id").is_some_and(|a| a.bound) || find("v-bind").is_some(),
+                value_without_max: value.map(|a| (a.node, a.value.clone().filter(|_| !a.bound))),
+            });
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_html(&child, source, in_label, labels, elements);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn collect_jsx<'a>(
+    node: &Node<'a>,
+    source: &str,
+    in_label: bool,
+    labels: &mut Labels,
+    elements: &mut Vec<Element<'a>>,
+) {
+    let mut in_label = in_label;
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let mut attrs = Vec::new();
+        let mut spread = false;
+        let mut cursor = tag.walk();
+        for child in tag.named_children(&mut cursor) {
+            if child.kind() == "jsx_expression" {
+                spread = true;
+            }
+            if let Some((attr, value)) = jsx_attrs::jsx_attribute(&child, source) {
+                // `None` for an expression, whose value is only known at runtime.
+                let text = value.map(|v| match v.kind() {
+                    "string" => Some(source[v.byte_range()].trim_matches(['"', '\'']).to_string()),
+                    _ => None,
+                });
+                attrs.push((attr, text, child));
+            }
+        }
+        let find = |attr: &str| attrs.iter().find(|(a, _, _)| *a == attr);
+
+        if name == "label" {
+            in_label = true;
+            match find("htmlFor").or_else(|| find("for")) {
+                Some((_, Some(Some(id)), _)) => {
+                    labels.ids.insert(id.trim().to_string());
+                }
+                Some(_) => labels.runtime = true,
+                None => {}
+            }
+        } else if let Some(&name) = ELEMENTS.iter().find(|e| **e == name) {
+            let named = in_label
+                || attrs.iter().any(|(attr, text, _)| {
+                    NAME_ATTRS.contains(attr)
+                        && match text {
+                            Some(Some(text)) => !text.trim().is_empty(),
+                            Some(None) => true,
+                            None => false,
+                        }
+                });
+            let value = find("value").filter(|_| name == "progress" && find("max").is_none());
+            elements.push(Element {
+                tag,
+                name,
+                named,
+                id: find("id")
+                    .and_then(|(_, text, _)| text.clone().flatten())
+                    .map(|v| v.trim().to_string()),
+                dynamic: spread
+                    || find("id").is_some_and(|(_, text, _)| matches!(text, Some(None))),
+                value_without_max: value
+                    .filter(|_| !spread)
+                    .map(|(_, text, node)| (*node, text.clone().flatten())),
+            });
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_jsx(&child, source, in_label, labels, elements);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+/// Whether a `value` is within the default `max` of 1.
+fn is_fraction(value: &str) -> bool {
+    value
+        .trim()
+        .parse::<f64>()
+        .is_ok_and(|v| (0.0..=1.0).contains(&v))
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = ProgressMeterName;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_unnamed_fails() {
+        let diags = check_html(
+            r#"<progress max="100" value="40"></progress>
+<meter value="0.6" aria-label=" "></meter>
+<label for="other">Disk</label><meter id="disk" value="3" max="5"></meter>"#,
+        );
+        assert_eq!(diags.len(), 3);
+        assert!(diags[0].message.starts_with("<progress> without a name"));
+        assert!(diags[1].message.starts_with("<meter> without a name"));
+        assert_eq!(diags[2].range.start.line, 2);
+    }
+
+    #[test]
+    fn test_named_passes() {
+        let source = r#"<label>Upload <progress max="100" value="40"></progress></label>
+<label for="disk">Disk</label><meter id="disk" value="3" max="5"></meter>
+<progress aria-labelledby="h" value="0.5"></progress>
+<meter title="Score" value="7" max="10"></meter>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_progress_value_without_max_fails() {
+        let source = r#"<progress aria-label="Upload" value="40"></progress>
+<progress aria-label="Upload" value="0.4"></progress>
+<progress aria-label="Upload"></progress>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.character, 30);
+        assert!(diags[0].message.contains("without `max`"));
+    }
+
+    #[test]
+    fn test_vue_bound_attributes() {
+        let source = r#"<template><progress :aria-label="label" :value="pct"></progress><meter :id="id"></meter></template>"#;
+        let diags = check(source, FileType::Vue);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("without `max`"));
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const P = () => (
+  <>
+    <label htmlFor="up">Upload</label>
+    <progress id="up" value={pct} max={100} />
+    <progress aria-label={label} value={pct} />
+    <meter {...props} />
+    <meter value={3} max={5} />
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 2);
+        assert!(diags[0].message.contains("without `max`"));
+        assert!(diags[1].message.starts_with("<meter> without a name"));
+    }
+}
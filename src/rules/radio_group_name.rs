@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use crate::analysis::{Analysis, Element};
+use crate::config::Config;
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct RadioGroupName;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "radio-group-name",
+    description: "Radio buttons in the same group must share one name",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/keyboard.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// The `name` of a radio button.
+enum Name {
+    Missing,
+    Static(String),
+    /// Bound, or possibly set through spread props.
+    Unknown,
+}
+
+struct Radio<'a> {
+    /// The node id of the element grouping the radio.
+    group: usize,
+    /// Whether that element is a `<fieldset>` or `role="radiogroup"`, which
+    /// says outright that its radios belong together.
+    explicit: bool,
+    name: Name,
+    /// The `name` attribute, or the whole tag without one.
+    node: Node<'a>,
+}
+
+/// The element grouping the radios below: an explicit group if there is one,
+/// else the nearest enclosing element that isn't a `<label>`.
+#[derive(Clone, Copy)]
+struct Group {
+    id: usize,
+    explicit: bool,
+}
+
+impl Group {
+    /// The group for the children of `element`.
+    fn enter(self, element: &Element) -> Group {
+        let radiogroup = matches!(&element.role, Some(Some(role)) if role == "radiogroup");
+        if element.name == "fieldset" || radiogroup {
+            Group {
+                id: element.node().id(),
+                explicit: true,
+            }
+        } else if self.explicit || element.name == "label" {
+            self
+        } else {
+            Group {
+                id: element.node().id(),
+                explicit: false,
+            }
+        }
+    }
+}
+
+impl Rule for RadioGroupName {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        let elements = analysis.elements();
+        // Radios at the top level of a fragment are grouped by the file.
+        let top = Group {
+            id: analysis.root().id(),
+            explicit: false,
+        };
+        // The group of each element's children; parents come first.
+        let mut inner: Vec<Group> = Vec::with_capacity(elements.len());
+        let mut radios = Vec::new();
+        for element in elements {
+            let group = element.parent.map_or(top, |parent| inner[parent]);
+            if let Some(radio) = radio(element, group) {
+                radios.push(radio);
+            }
+            inner.push(group.enter(element));
+        }
+        check_groups(&radios)
+    }
+}
+
+/// `element` as a member of `group`, if it's a radio button.
+fn radio<'a>(element: &Element<'a>, group: Group) -> Option<Radio<'a>> {
+    let is_radio = element
+        .static_value("type")
+        .is_some_and(|t| t.eq_ignore_ascii_case("radio"));
+    if element.component || element.name != "input" || !is_radio {
+        return None;
+    }
+    // Spread props or `v-bind="attrs"` may carry the name.
+    let (name, node) = match element.attr("name") {
+        Some(a) if a.bound => (Name::Unknown, a.node),
+        Some(a) => (static_name(a.value.as_deref()), a.node),
+        None if element.spread => (Name::Unknown, element.tag),
+        None => (Name::Missing, element.tag),
+    };
+    Some(Radio {
+        group: group.id,
+        explicit: group.explicit,
+        name,
+        node,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn static_name(value: Option<&str>) -> Name {
+    match value.map(str::trim) {
+        Some(value) if !value.is_empty() => Name::Static(value.to_string()),
+        _ => Name::Missing,
+    }
+}
+
+/// Reports, in each group of two or more radios, those without a name and
+/// those whose name differs from the group's most common one. Outside a
+/// `<fieldset>` or radiogroup, one parent often holds several groups side by
+/// side, so only a name used by a single radio next to a repeated one is
+/// taken for a mistake there.
+fn check_groups(radios: &[Radio]) -> Vec<Diagnostic> {
+    let mut groups: Vec<(usize, Vec<&Radio>)> = Vec::new();
+    for radio in radios {
+        match groups.iter_mut().find(|(id, _)| *id == radio.group) {
+            Some((_, members)) => members.push(radio),
+            None => groups.push((radio.group, vec![radio])),
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    for (_, members) in &groups {
+        if members.len() < 2 {
+            continue;
+        }
+        // All members share their group, and so whether it is explicit.
+        let explicit = members[0].explicit;
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for radio in members {
+            if let Name::Static(name) = &radio.name {
+                *counts.entry(name).or_default() += 1;
+            }
+        }
+        // The most common name, the first one on a tie.
+        let common = members
+            .iter()
+            .filter_map(|r| match &r.name {
+                Name::Static(name) => Some((name.as_str(), counts[name.as_str()])),
+                _ => None,
+            })
+            .reduce(|best, next| if next.1 > best.1 { next } else { best });
+
+        for radio in members {
+            match (&radio.name, common) {
+                (Name::Missing, _) => diagnostics.push(make_diagnostic(
+                    &radio.node,
+                    "Radio button without a name: arrow keys can't reach the other options",
+                )),
+                (Name::Static(name), Some((common, most)))
+                    if name != common && (explicit || counts[name.as_str()] == 1 && most > 1) =>
+                {
+                    diagnostics.push(make_diagnostic(
+                        &radio.node,
+                        &format!(
+                            "Radio button named \"{name}\" while the others in its group are named \"{common}\": arrow keys only move between radios of one name"
+                        ),
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+    diagnostics
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = RadioGroupName;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_consistent_groups_pass() {
+        let source = r#"<fieldset>
+  <legend>Size</legend>
+  <div><label><input type="radio" name="size" value="s"> S</label></div>
+  <div><label><input type="radio" name="size" value="m"> M</label></div>
+</fieldset>
+<div>
+  <input type="radio" name="color" value="red"><input type="radio" name="color" value="blue">
+  <input type="radio" name="shape" value="round"><input type="radio" name="shape" value="square">
+</div>
+<p><input type="radio"></p>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_missing_and_differing_names_fail() {
+        let source = r#"<fieldset>
+  <label><input type="radio" name="size"> S</label>
+  <label><input type="radio" name="sizes"> M</label>
+  <label><input type="radio" name="size"> L</label>
+  <label><input type="radio"> XL</label>
+</fieldset>
+<div role="radiogroup"><input type="radio" name="a"><input type="radio" name="b"></div>
+<div><input type="radio" name="color"><input type="radio" name="color"><input type="radio" name="colour"></div>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 4);
+        assert!(diags[0].message.contains("named \"sizes\""));
+        assert_eq!(diags[0].range.start.line, 2);
+        assert_eq!(diags[0].range.start.character, 29);
+        assert!(diags[1].message.contains("without a name"));
+        assert!(
+            diags[2]
+                .message
+                .contains("named \"b\" while the others in its group are named \"a\"")
+        );
+        assert!(diags[3].message.contains("\"colour\""));
+    }
+
+    #[test]
+    fn test_bound_names_pass() {
+        let source = r#"<template><fieldset>
+  <input type="radio" :name="field" value="a">
+  <input type="radio" v-bind="radioAttrs" value="b">
+  <input type="radio" name="plan" value="c">
+</fieldset></template>"#;
+        assert!(check(source, FileType::Vue).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const F = () => (
+  <fieldset>
+    <input type="radio" name="plan" />
+    <input type="radio" name={field} />
+    <input type="radio" {...register("plan")} />
+    <input type="radio" />
+    <Radio name="other" />
+  </fieldset>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 5);
+        assert!(diags[0].message.contains("without a name"));
+    }
+}
@@ -0,0 +1,219 @@
+//! React head management, for the page-level rules.
+//!
+//! React components don't write `<head>` themselves. With the Next.js pages
+//! router a page sets its title in `next/head`'s `<Head>`, and
+//! `pages/_document.tsx` puts `lang` on `next/document`'s `<Html>`. With the
+//! app router the root layout renders `<html lang>` and layouts/pages export
+//! a `metadata` object whose `title` Next.js turns into the `<title>`. Other
+//! SPAs use a library component such as React Helmet's `<Helmet>`, which also
+//! takes `title` and `htmlAttributes={{ lang }}` props. A project's own
+//! wrapper around one of them counts once `[components]` maps it to `head`.
+
+use crate::config::ComponentMapping;
+use crate::rules::jsx_attrs::{is_empty_string, object_property};
+use std::collections::HashMap;
+use tree_sitter::Node;
+
+/// Components that render their children into `<head>`, as
+/// `(module, export)`, besides those `[components]` maps to `head`.
+const HEAD_COMPONENTS: &[(&str, &str)] = &[
+    ("next/head", "default"),
+    ("react-helmet", "Helmet"),
+    ("react-helmet-async", "Helmet"),
+    ("react-meta-tags", "default"),
+];
+
+/// Components that take `htmlAttributes` for the `<html>` element.
+const HTML_ATTRIBUTES_COMPONENTS: &[(&str, &str)] =
+    &[("react-helmet", "Helmet"), ("react-helmet-async", "Helmet")];
+
+/// Local names of the head components this file imports, and the names
+/// `components` maps to `head`. The engine renames those to `head` before
+/// the rules run, so `head` is one too if there are any.
+pub fn head_components<'s>(
+    root: &Node,
+    source: &'s str,
+    components: &'s HashMap<String, ComponentMapping>,
+) -> Vec<&'s str> {
+    let mut names = imported(root, source, HEAD_COMPONENTS);
+    let mut mapped: Vec<&str> = components
+        .iter()
+        .filter(|(_, mapping)| mapping.element == "head")
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !mapped.is_empty() {
+        mapped.sort_unstable();
+        names.extend(mapped);
+        names.push("head");
+    }
+    names
+}
+
+/// Local names of the components this file imports that set `<html>`
+/// attributes through an `htmlAttributes` prop.
+pub fn html_attributes_components<'s>(root: &Node, source: &'s str) -> Vec<&'s str> {
+    imported(root, source, HTML_ATTRIBUTES_COMPONENTS)
+}
+
+fn imported<'s>(root: &Node, source: &'s str, components: &[(&str, &str)]) -> Vec<&'s str> {
+    components
+        .iter()
+        .filter_map(|(module, export)| local_name(root, source, module, export))
+        .collect()
+}
+
+/// The local name `export` of `module` is imported under, e.g. `Head` for
+/// `import Head from "next/head"` (pass `"default"`) or `H` for
+/// `import { Html as H } from "next/document"`.
+pub fn local_name<'s>(root: &Node, source: &'s str, module: &str, export: &str) -> Option<&'s str> {
+    let mut cursor = root.walk();
+    for import in root.children(&mut cursor) {
+        if import.kind() != "import_statement" {
+            continue;
+        }
+        let from = import
+            .child_by_field_name("source")
+            .map(|s| source[s.byte_range()].trim_matches(['"', '\'']));
+        if from != Some(module) {
+            continue;
+        }
+        let mut import_cursor = import.walk();
+        for clause in import.children(&mut import_cursor) {
+            if clause.kind() != "import_clause" {
+                continue;
+            }
+            let mut clause_cursor = clause.walk();
+            for child in clause.children(&mut clause_cursor) {
+                match child.kind() {
+                    "identifier" if export == "default" => {
+                        return Some(&source[child.byte_range()]);
+                    }
+                    "named_imports" => {
+                        let mut named_cursor = child.walk();
+                        for spec in child.children(&mut named_cursor) {
+                            let name = spec.child_by_field_name("name");
+                            if name.is_some_and(|n| &source[n.byte_range()] == export) {
+                                let local = spec.child_by_field_name("alias").or(name)?;
+                                return Some(&source[local.byte_range()]);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The `title` property of `export const metadata = { … }`, if there is one.
+/// `generateMetadata()` is only known at runtime and isn't looked at.
+pub fn metadata_title<'a>(root: &Node<'a>, source: &str) -> Option<Node<'a>> {
+    let mut cursor = root.walk();
+    for export in root.children(&mut cursor) {
+        if export.kind() != "export_statement" {
+            continue;
+        }
+        let Some(declaration) = export.child_by_field_name("declaration") else {
+            continue;
+        };
+        let mut decl_cursor = declaration.walk();
+        for declarator in declaration.children(&mut decl_cursor) {
+            let is_metadata = declarator
+                .child_by_field_name("name")
+                .is_some_and(|n| &source[n.byte_range()] == "metadata");
+            if declarator.kind() == "variable_declarator"
+                && is_metadata
+                && let Some(value) = declarator.child_by_field_name("value")
+            {
+                return object_property(&unwrap_type_expression(value), source, "title");
+            }
+        }
+    }
+    None
+}
+
+/// Whether a metadata `title` value is statically known to be empty: an
+/// empty string, or a `{ default, absolute }` object with an empty one.
+pub fn is_empty_title(value: &Node, source: &str) -> bool {
+    let value = unwrap_type_expression(*value);
+    match value.kind() {
+        "object" => ["default", "absolute"].iter().any(|key| {
+            object_property(&value, source, key).is_some_and(|v| is_empty_string(&v, source))
+        }),
+        _ => is_empty_string(&value, source),
+    }
+}
+
+/// `{ … } satisfies Metadata` and `{ … } as Metadata` → `{ … }`.
+fn unwrap_type_expression(node: Node) -> Node {
+    match node.kind() {
+        "satisfies_expression" | "as_expression" => node.child(0).unwrap_or(node),
+        _ => node,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::parser::{self, FileType};
+
+    fn parse(source: &str) -> tree_sitter::Tree {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_local_name() {
+        let src = "import Document, { Html, Head as H } from 'next/document'\nimport NextHead from \"next/head\"";
+        let tree = parse(src);
+        let root = tree.root_node();
+        assert_eq!(
+            local_name(&root, src, "next/head", "default"),
+            Some("NextHead")
+        );
+        assert_eq!(
+            local_name(&root, src, "next/document", "Html"),
+            Some("Html")
+        );
+        assert_eq!(local_name(&root, src, "next/document", "Head"), Some("H"));
+        assert_eq!(local_name(&root, src, "next/document", "Main"), None);
+        assert_eq!(local_name(&root, src, "react-helmet", "Helmet"), None);
+    }
+
+    #[test]
+    fn test_head_components() {
+        let src = "import { Helmet } from 'react-helmet-async'\nimport MetaTags from 'react-meta-tags'\nimport { Head } from 'somewhere'";
+        let tree = parse(src);
+        let root = tree.root_node();
+        assert_eq!(
+            head_components(&root, src, &HashMap::new()),
+            ["Helmet", "MetaTags"]
+        );
+        assert_eq!(html_attributes_components(&root, src), ["Helmet"]);
+
+        let config = Config::parse("[components]\nSeoHead = \"head\"\nImg = \"img\"\n");
+        assert_eq!(
+            head_components(&root, src, &config.components),
+            ["Helmet", "MetaTags", "SeoHead", "head"]
+        );
+    }
+
+    #[test]
+    fn test_metadata_title() {
+        let src = "export const metadata: Metadata = { description: 'd', title: { default: '', template: '%s | Site' } }";
+        let tree = parse(src);
+        let title = metadata_title(&tree.root_node(), src).unwrap();
+        assert!(is_empty_title(&title, src));
+
+        let src = "export const metadata = { title: `Home` } satisfies Metadata";
+        let tree = parse(src);
+        let title = metadata_title(&tree.root_node(), src).unwrap();
+        assert!(!is_empty_title(&title, src));
+
+        let src = "const metadata = { title: '' }\nexport const other = { title: '' }";
+        let tree = parse(src);
+        assert!(metadata_title(&tree.root_node(), src).is_none());
+    }
+}
@@ -0,0 +1,202 @@
+//! React Native components and accessibility props.
+//!
+//! React Native renders no DOM: images are `<Image>`, controls are
+//! `<Pressable>`, the `Touchable*` components and `<Button>`, and their
+//! accessibility comes from props. `accessibilityLabel` (or `alt` on an
+//! `<Image>`) names an element, `accessibilityRole` gives it a role,
+//! `accessibilityState` and `accessibilityValue` hold what `aria-checked`,
+//! `aria-valuenow` and the like would, and `accessible={false}` takes it out
+//! of the accessibility tree. React Native 0.71 also takes the `aria-*` and
+//! `role` props, which the rules already read.
+//!
+//! Components only count as React Native's when the file imports them from
+//! `react-native`, so a web `<Image>` component isn't mistaken for one.
+
+use crate::rules::{jsx_attrs, react_head};
+use tree_sitter::Node;
+
+const MODULE: &str = "react-native";
+
+/// Components that render an image.
+const IMAGES: &[&str] = &["Image"];
+
+/// Components that are pressable controls, named by a label prop or their
+/// content.
+const PRESSABLES: &[&str] = &[
+    "Pressable",
+    "TouchableHighlight",
+    "TouchableNativeFeedback",
+    "TouchableOpacity",
+    "TouchableWithoutFeedback",
+];
+
+/// Props that name an element.
+pub const LABEL_PROPS: &[&str] = &[
+    "accessibilityLabel",
+    "accessibilityLabelledBy",
+    "aria-label",
+    "aria-labelledby",
+];
+
+/// The ARIA attribute each `accessibilityState` and `accessibilityValue` key
+/// stands for.
+const STATE_ATTRS: &[(&str, &str)] = &[
+    ("busy", "aria-busy"),
+    ("checked", "aria-checked"),
+    ("disabled", "aria-disabled"),
+    ("expanded", "aria-expanded"),
+    ("selected", "aria-selected"),
+];
+const VALUE_ATTRS: &[(&str, &str)] = &[
+    ("max", "aria-valuemax"),
+    ("min", "aria-valuemin"),
+    ("now", "aria-valuenow"),
+    ("text", "aria-valuetext"),
+];
+
+/// Local names of the React Native components a file imports.
+pub struct Components<'s> {
+    pub images: Vec<&'s str>,
+    pub pressables: Vec<&'s str>,
+    pub button: Option<&'s str>,
+}
+
+/// The React Native components the file imports, or `None` if it imports
+/// nothing from `react-native`.
+pub fn components<'s>(root: &Node, source: &'s str) -> Option<Components<'s>> {
+    if !imports_react_native(root, source) {
+        return None;
+    }
+    let imported = |names: &[&str]| -> Vec<&'s str> {
+        names
+            .iter()
+            .filter_map(|name| react_head::local_name(root, source, MODULE, name))
+            .collect()
+    };
+    Some(Components {
+        images: imported(IMAGES),
+        pressables: imported(PRESSABLES),
+        button: react_head::local_name(root, source, MODULE, "Button"),
+    })
+}
+
+fn imports_react_native(root: &Node, source: &str) -> bool {
+    let mut cursor = root.walk();
+    root.children(&mut cursor).any(|import| {
+        import.kind() == "import_statement"
+            && import
+                .child_by_field_name("source")
+                .is_some_and(|s| source[s.byte_range()].trim_matches(['"', '\'']) == MODULE)
+    })
+}
+
+/// The `accessibilityRole` of a JSX tag, if it's a string.
+pub fn role(attrs: &[(&str, Option<Node>)], source: &str) -> Option<String> {
+    attrs
+        .iter()
+        .find(|(name, _)| *name == "accessibilityRole")
+        .and_then(|(_, value)| string_value(value.as_ref(), source))
+}
+
+/// The name and value of each attribute of a JSX opening or self-closing
+/// tag.
+pub fn tag_attrs<'a, 's>(tag: &Node<'a>, source: &'s str) -> Vec<(&'s str, Option<Node<'a>>)> {
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .filter_map(|attr| jsx_attrs::jsx_attribute(&attr, source))
+        .collect()
+}
+
+/// The string value of a JSX attribute (`"x"` or `{"x"}`), if it is one.
+pub fn string_value(value: Option<&Node>, source: &str) -> Option<String> {
+    let value = value?;
+    let value = if value.kind() == "jsx_expression" {
+        value.named_child(0)?
+    } else {
+        *value
+    };
+    matches!(value.kind(), "string" | "template_string").then(|| {
+        source[value.byte_range()]
+            .trim_matches(['"', '\'', '`'])
+            .to_string()
+    })
+}
+
+/// Whether the props take the element out of the accessibility tree:
+/// `accessible={false}`, `accessibilityElementsHidden`,
+/// `importantForAccessibility="no"` or `"no-hide-descendants"`, or
+/// `aria-hidden`.
+pub fn is_hidden(attrs: &[(&str, Option<Node>)], source: &str) -> bool {
+    attrs.iter().any(|(name, value)| {
+        let text = value.map(|v| &source[v.byte_range()]);
+        match *name {
+            "accessible" => text == Some("{false}"),
+            "accessibilityElementsHidden" | "aria-hidden" => text != Some("{false}"),
+            "importantForAccessibility" => {
+                matches!(
+                    string_value(value.as_ref(), source).as_deref(),
+                    Some("no" | "no-hide-descendants")
+                )
+            }
+            _ => false,
+        }
+    })
+}
+
+/// Whether one of the props names the element. An empty string doesn't.
+pub fn has_label(attrs: &[(&str, Option<Node>)], source: &str) -> bool {
+    attrs.iter().any(|(name, value)| {
+        LABEL_PROPS.contains(name) && !jsx_attrs::is_empty_jsx_value(value.as_ref(), source)
+    })
+}
+
+/// The ARIA attributes an `accessibilityState` or `accessibilityValue`
+/// object literal sets, e.g. `aria-checked` for `{{ checked: true }}`.
+pub fn state_attrs(name: &str, value: &Node, source: &str) -> Vec<&'static str> {
+    let keys = match name {
+        "accessibilityState" => STATE_ATTRS,
+        "accessibilityValue" => VALUE_ATTRS,
+        _ => return Vec::new(),
+    };
+    let Some(object) = jsx_attrs::jsx_object(value) else {
+        return Vec::new();
+    };
+    let has_key = |key: &str| {
+        let mut cursor = object.walk();
+        jsx_attrs::object_property(&object, source, key).is_some()
+            || object.named_children(&mut cursor).any(|entry| {
+                entry.kind() == "shorthand_property_identifier"
+                    && &source[entry.byte_range()] == key
+            })
+    };
+    keys.iter()
+        .filter(|(key, _)| has_key(key))
+        .map(|(_, attr)| *attr)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{self, FileType};
+
+    fn parse(source: &str) -> tree_sitter::Tree {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_components_imported_from_react_native() {
+        let source = r#"import { Image as RNImage, Pressable, Button } from "react-native";
+import { TouchableOpacity } from "react-native-gesture-handler";"#;
+        let tree = parse(source);
+        let native = components(&tree.root_node(), source).unwrap();
+        assert_eq!(native.images, ["RNImage"]);
+        assert_eq!(native.pressables, ["Pressable"]);
+        assert_eq!(native.button, Some("Button"));
+
+        let source = r#"import Image from "next/image";"#;
+        let tree = parse(source);
+        assert!(components(&tree.root_node(), source).is_none());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::parser::FileType;
+use crate::rules::css;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct ReducedMotion;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "reduced-motion",
+    description: "Endless animations must stop for users who prefer reduced motion",
+    wcag_level: WcagLevel::AAA,
+    wcag_criterion: "2.3.3",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/animation-from-interactions.html",
+    default_severity: Severity::Warning,
+    axe_rules: &[],
+};
+
+/// The media feature whose queries let an animation respect the setting.
+const REDUCED_MOTION_FEATURE: &str = "prefers-reduced-motion";
+
+impl Rule for ReducedMotion {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let sheets = css::style_sheets(root, source, file_type);
+        let text = |sheet: &Node| &source[sheet.byte_range()];
+
+        // One `prefers-reduced-motion` query anywhere in the file is taken to
+        // handle its animations; which rules it overrides isn't followed.
+        if sheets.iter().any(|sheet| {
+            css::strip_comments(text(sheet))
+                .to_ascii_lowercase()
+                .contains(REDUCED_MOTION_FEATURE)
+        }) {
+            return Vec::new();
+        }
+
+        let mut diagnostics = Vec::new();
+        for sheet in &sheets {
+            for rule in css::rules(text(sheet)) {
+                for declaration in rule.declarations.iter().filter(|d| is_endless(d)) {
+                    diagnostics.push(make_diagnostic(css::declaration_range(
+                        source,
+                        sheet,
+                        declaration,
+                    )));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Whether an `animation` or `animation-iteration-count` declaration repeats
+/// forever.
+fn is_endless(declaration: &css::Declaration) -> bool {
+    matches!(
+        declaration.property.as_str(),
+        "animation" | "animation-iteration-count"
+    ) && declaration
+        .value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .any(|word| word == "infinite")
+}
+
+fn make_diagnostic(range: Range) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "This animation never ends: stop it in `@media (prefers-reduced-motion: reduce)`, or only start it in `@media (prefers-reduced-motion: no-preference)`. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = ReducedMotion;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_endless_animation_without_query_fails() {
+        let source = r#"<style>
+  .spinner { animation: spin 1s linear infinite; }
+  .pulse {
+    animation-name: pulse;
+    animation-iteration-count: INFINITE
+  }
+  .fade { animation: fade 2s 3; }
+  /* .old { animation: spin 1s infinite; } */
+</style>"#;
+        let diags = check_html(source);
+        let ranges: Vec<(u32, u32, u32)> = diags
+            .iter()
+            .map(|d| {
+                (
+                    d.range.start.line,
+                    d.range.start.character,
+                    d.range.end.character,
+                )
+            })
+            .collect();
+        assert_eq!(ranges, [(1, 13, 47), (4, 4, 39)]);
+        assert_eq!(
+            diags[0].code,
+            Some(NumberOrString::String("reduced-motion".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_reduced_motion_query_passes() {
+        let diags = check_html(
+            r#"<style>
+.spinner { animation: spin 1s linear infinite; }
+@media (prefers-reduced-motion: reduce) { .spinner { animation: none; } }
+</style>"#,
+        );
+        assert_eq!(diags.len(), 0);
+
+        let diags = check_html(
+            r#"<style>@media (prefers-reduced-motion: no-preference) { .a { animation: spin 1s infinite; } }</style>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_keyframes_and_other_properties_pass() {
+        let diags = check_html(
+            r#"<style>@keyframes spin { from { transform: rotate(0) } to { transform: rotate(1turn) } }
+.a { transition: opacity 1s; content: "infinite"; }</style>"#,
+        );
+        assert_eq!(diags.len(), 0);
+    }
+
+    #[test]
+    fn test_styled_jsx() {
+        let diags = check(
+            r#"const Loader = () => (
+  <div className="loader">
+    <style jsx>{`
+      .loader { animation: spin 1s infinite; }
+    `}</style>
+  </div>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range.start.line, 3);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::analysis::Analysis;
+use crate::config::Config;
+use crate::engine::{DOCUMENT_RANGE, node_to_range};
+use crate::parser::FileType;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct Region;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "region",
+    description: "All page content should be inside landmarks",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.4.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/bypass-blocks.html",
+    default_severity: Severity::Warning,
+    axe_rules: &["region"],
+};
+
+/// Elements that are content even without text.
+const CONTENT_ELEMENTS: &[&str] = &[
+    "audio", "button", "canvas", "embed", "iframe", "img", "input", "object", "select", "svg",
+    "textarea", "video",
+];
+
+/// Elements whose content isn't shown on the page.
+const SKIPPED_ELEMENTS: &[&str] = &["script", "style", "template", "noscript", "head"];
+
+const PROBLEM: &str = "Content outside any landmark: move it into `<main>`, `<header>`, `<nav>`, `<footer>` or another landmark, so screen reader users who move by landmarks don't miss it";
+
+impl Rule for Region {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    /// Landmarks are a matter of the whole page.
+    fn page_level(&self) -> bool {
+        true
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        self.check_with_analysis(&Analysis::new(*root, source, file_type), &Config::default())
+    }
+
+    fn check_with_analysis(&self, analysis: &Analysis, _config: &Config) -> Vec<Diagnostic> {
+        // Components and partials end up inside the landmarks of a layout
+        // elsewhere.
+        if analysis.file_type() != FileType::Html {
+            return Vec::new();
+        }
+        let elements = analysis.elements();
+        let Some(body) = elements.iter().position(|e| e.name == "body") else {
+            return Vec::new();
+        };
+
+        let walk = Walk::new(analysis);
+        if walk.landmarks.iter().all(|l| !l) {
+            let mut diagnostics = Vec::new();
+            if walk.has_content(body) {
+                diagnostics.push(make_diagnostic(
+                    DOCUMENT_RANGE,
+                    "The page has no landmarks: put its content in `<header>`, `<nav>`, `<main>` and `<footer>`",
+                ));
+            }
+            return diagnostics;
+        }
+        let mut diagnostics = Vec::new();
+        walk.check_children(body, &mut diagnostics);
+        diagnostics
+    }
+}
+
+/// The walk over the elements of a page.
+struct Walk<'a, 'b> {
+    analysis: &'b Analysis<'a>,
+    /// Whether each element is a landmark or contains one.
+    landmarks: Vec<bool>,
+}
+
+impl<'a, 'b> Walk<'a, 'b> {
+    fn new(analysis: &'b Analysis<'a>) -> Self {
+        let elements = analysis.elements();
+        let mut landmarks = vec![false; elements.len()];
+        for (i, _) in analysis.landmarks() {
+            let mut current = Some(i);
+            while let Some(j) = current
+                && !landmarks[j]
+            {
+                landmarks[j] = true;
+                current = elements[j].parent;
+            }
+        }
+        Walk {
+            analysis,
+            landmarks,
+        }
+    }
+
+    /// Reports the content among the children of element `i`, which contains
+    /// a landmark but isn't one.
+    fn check_children(&self, i: usize, diagnostics: &mut Vec<Diagnostic>) {
+        let node = self.analysis.elements()[i].node();
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if is_text(&child, self.analysis.source()) {
+                diagnostics.push(make_diagnostic(node_to_range(&child), PROBLEM));
+                continue;
+            }
+            // Its own tag is among its children.
+            let Some(j) = self.analysis.element_at(&child).filter(|&j| j != i) else {
+                continue;
+            };
+            if self.analysis.landmark(j).is_some() || self.skipped(j) {
+                continue;
+            }
+            if self.landmarks[j] {
+                self.check_children(j, diagnostics);
+            } else if self.has_content(j) {
+                diagnostics.push(make_diagnostic(
+                    node_to_range(&self.analysis.elements()[j].tag),
+                    PROBLEM,
+                ));
+            }
+        }
+    }
+
+    /// Whether element `i` shows anything other than skip links.
+    fn has_content(&self, i: usize) -> bool {
+        let element = &self.analysis.elements()[i];
+        if self.skipped(i) {
+            return false;
+        }
+        if CONTENT_ELEMENTS.contains(&element.name.as_str()) {
+            return !element
+                .static_value("type")
+                .is_some_and(|t| t.eq_ignore_ascii_case("hidden"));
+        }
+        let node = element.node();
+        let mut cursor = node.walk();
+        node.children(&mut cursor).any(|child| {
+            is_text(&child, self.analysis.source())
+                || self
+                    .analysis
+                    .element_at(&child)
+                    .is_some_and(|j| j != i && self.has_content(j))
+        })
+    }
+
+    /// Hidden and invisible elements, and skip links, which come before the
+    /// landmarks they skip to.
+    fn skipped(&self, i: usize) -> bool {
+        let element = &self.analysis.elements()[i];
+        element.inert
+            || SKIPPED_ELEMENTS.contains(&element.name.as_str())
+            || element
+                .static_value("aria-hidden")
+                .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+            || (element.name == "a"
+                && element
+                    .static_value("href")
+                    .is_some_and(|h| h.starts_with('#')))
+    }
+}
+
+fn is_text(node: &Node, source: &str) -> bool {
+    node.kind() == "text" && !source[node.byte_range()].trim().is_empty()
+}
+
+fn make_diagnostic(range: Range, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = Region;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_content_in_landmarks_passes() {
+        let source = r##"<html><head><title>Shop</title></head><body>
+<div class="skip"><a href="#main">Skip to content</a></div>
+<div class="wrapper">
+  <header>Shop</header>
+  <main id="main"><h1>Sale</h1></main>
+</div>
+<div aria-hidden="true"><img src="deco.png" alt=""></div>
+<input type="hidden" name="csrf">
+<script>track()</script>
+<dialog>Cookie settings</dialog>
+</body></html>"##;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_content_outside_landmarks_fails() {
+        let source = r#"<html><body>
+<div class="banner">Free shipping!</div>
+<div class="wrapper">
+  Welcome
+  <main><h1>Sale</h1></main>
+  <div class="ad"><img src="ad.png" alt="Ad"></div>
+</div>
+</body></html>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 3);
+        assert_eq!(diags[0].range.start.line, 1);
+        assert!(diags[0].message.starts_with("Content outside any landmark"));
+        assert_eq!(diags[1].range.start.line, 3);
+        assert_eq!(diags[2].range.start.line, 5);
+    }
+
+    #[test]
+    fn test_page_without_landmarks_reports_once() {
+        let diags = check_html("<html><body><h1>Hi</h1><p>Text</p></body></html>");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].range, DOCUMENT_RANGE);
+        assert!(check_html("<html><body></body></html>").is_empty());
+    }
+
+    #[test]
+    fn test_fragments_pass() {
+        assert!(check_html("<div>Text</div>").is_empty());
+        assert!(check("<template><body><p>x</p></body></template>", FileType::Vue).is_empty());
+    }
+}
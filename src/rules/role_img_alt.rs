@@ -0,0 +1,244 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct RoleImgAlt;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "role-img-alt",
+    description: "Elements with role=\"img\" must have an accessible name",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/non-text-content.html",
+    default_severity: Severity::Error,
+    axe_rules: &["role-img-alt"],
+};
+
+/// Elements other rules check the names of: `img-alt` and `svg-img-alt`.
+const CHECKED_ELSEWHERE: &[&str] = &["img", "svg"];
+
+impl Rule for RoleImgAlt {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, &mut diagnostics);
+        } else {
+            visit_html(root, source, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source)
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let attrs: Vec<(String, Option<String>)> = html_attrs::attrs(&tag, source)
+            .into_iter()
+            .map(|a| {
+                let name = a.name_lower();
+                // A bound value is taken to be a real one.
+                let value = if a.bound {
+                    Some("{}".to_string())
+                } else {
+                    a.value
+                };
+                (name, value)
+            })
+            .collect();
+        if is_hidden(&attrs) {
+            return;
+        }
+        if needs_name(&name, &attrs) {
+            diagnostics.push(make_diagnostic(&tag));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let attrs = jsx_attrs(&tag, source);
+        if is_hidden(&attrs) {
+            return;
+        }
+        // Components and spread props may supply the name themselves.
+        let intrinsic = name.starts_with(|c: char| c.is_ascii_lowercase()) && !name.contains('.');
+        if intrinsic && !has_spread(&tag) && needs_name(name, &attrs) {
+            diagnostics.push(make_diagnostic(&tag));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, diagnostics);
+    }
+}
+
+/// The attributes of a JSX tag, lowercased, with their value: the text of a
+/// string, or `{}` for an expression.
+fn jsx_attrs(tag: &Node, source: &str) -> Vec<(String, Option<String>)> {
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .filter_map(|attr| jsx_attrs::jsx_attribute(&attr, source))
+        .map(|(name, value)| {
+            let value = value.map(|v| match v.kind() {
+                "string" => source[v.byte_range()].trim_matches(['"', '\'']).to_string(),
+                _ => "{}".to_string(),
+            });
+            (name.to_ascii_lowercase(), value)
+        })
+        .collect()
+}
+
+fn has_spread(tag: &Node) -> bool {
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .any(|c| c.kind() == "jsx_expression")
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn value<'a>(attrs: &'a [(String, Option<String>)], name: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(n, _)| n == name)
+        .and_then(|(_, v)| v.as_deref())
+}
+
+/// Whether an element with `attrs` is hidden, along with everything in it.
+/// A bare `aria-hidden` (JSX's `true`) counts.
+fn is_hidden(attrs: &[(String, Option<String>)]) -> bool {
+    attrs
+        .iter()
+        .any(|(n, v)| n == "aria-hidden" && v.as_deref().is_none_or(|v| v.trim() != "false"))
+}
+
+/// Whether an element `name` with `attrs` has `role="img"` and nothing to
+/// name it: a non-empty `aria-label`, `aria-labelledby` or, as axe allows,
+/// `title`.
+fn needs_name(name: &str, attrs: &[(String, Option<String>)]) -> bool {
+    if CHECKED_ELSEWHERE.contains(&name) {
+        return false;
+    }
+    let is_img = value(attrs, "role")
+        .and_then(|roles| roles.split_whitespace().next())
+        .is_some_and(|role| role.eq_ignore_ascii_case("img"));
+    let named = ["aria-label", "aria-labelledby", "title"]
+        .iter()
+        .any(|attr| value(attrs, attr).is_some_and(|v| !v.trim().is_empty()));
+    is_img && !named
+}
+
+fn make_diagnostic(tag: &Node) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(tag),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "This `role=\"img\"` has no accessible name, and its content isn't read: add `aria-label` or `aria-labelledby`, or `aria-hidden=\"true\"` if it's decorative. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = RoleImgAlt;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_unnamed_role_img_fails() {
+        let diags = check_html(
+            r#"<p>Great job <span role="img">🎉</span></p>
+<div class="hero" role="img" aria-label=" "></div>"#,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.character, 13);
+        assert_eq!(diags[1].range.start.line, 1);
+        assert!(diags[0].message.contains("1.1.1"));
+    }
+
+    #[test]
+    fn test_named_or_hidden_passes() {
+        let source = r#"<span role="img" aria-label="party popper">🎉</span>
+<div class="hero" role="img" aria-labelledby="hero-caption"></div>
+<i role="img" title="Warning"></i>
+<span role="img" :aria-label="label"></span>
+<span role="img" aria-hidden="true">✨</span>
+<span :role="role"></span>
+<svg role="img"></svg>
+<img role="img" src="a.png" alt="A">"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const E = () => (
+  <>
+    <span role="img">🎉</span>
+    <span role="img" aria-label={label}>🎉</span>
+    <span role="img" aria-label="">🎉</span>
+    <span role="img" aria-hidden>✨</span>
+    <span role="img" {...emojiProps}>✨</span>
+    <Emoji role="img" symbol="✨" />
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert_eq!(diags[1].range.start.line, 4);
+    }
+}
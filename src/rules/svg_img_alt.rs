@@ -0,0 +1,347 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct SvgImgAlt;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "svg-img-alt",
+    description: "SVG images must have an accessible name",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/non-text-content.html",
+    default_severity: Severity::Error,
+    axe_rules: &["svg-img-alt"],
+};
+
+/// Roles that make an `<svg>` an image that needs a name.
+const IMAGE_ROLES: &[&str] = &["img", "graphics-document", "graphics-symbol"];
+
+impl Rule for SvgImgAlt {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, &mut diagnostics);
+        } else {
+            visit_html(root, source, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+/// What an `<svg>` that isn't hidden says about itself.
+struct Svg {
+    /// First token of a literal `role`; `Some("")` for a bound one.
+    role: Option<String>,
+    /// `aria-label`, `aria-labelledby` or a `title` attribute.
+    labelled: bool,
+    /// A `<title>` or `<desc>` child with text.
+    described: bool,
+}
+
+impl Svg {
+    fn check(&self, tag: &Node, diagnostics: &mut Vec<Diagnostic>) {
+        if self.labelled || self.described {
+            return;
+        }
+        match self.role.as_deref() {
+            Some(role) if IMAGE_ROLES.contains(&role) => {
+                diagnostics.push(make_diagnostic(tag, role));
+            }
+            None => diagnostics.push(make_hint(tag)),
+            // `none`/`presentation` (decorative), bound, or a widget role
+            // other rules check.
+            Some(_) => {}
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let attrs = html_attrs::attrs(&tag, source);
+        let value = |name: &str| {
+            attrs
+                .iter()
+                .find(|a| a.name_eq(name))
+                .map(|a| {
+                    if a.bound {
+                        "{}"
+                    } else {
+                        a.value.as_deref().unwrap_or("")
+                    }
+                })
+                .map(str::trim)
+        };
+        // Nothing inside a hidden element is announced.
+        if value("aria-hidden").is_some_and(|v| v != "false") {
+            return;
+        }
+        if html_attrs::tag_name(&tag, source).is_some_and(|n| n.eq_ignore_ascii_case("svg")) {
+            let is_set = |name: &str| value(name).is_some_and(|v| !v.is_empty());
+            let svg = Svg {
+                role: value("role").map(first_role),
+                labelled: is_set("aria-label") || is_set("aria-labelledby") || is_set("title"),
+                described: html_has_text_child(node, source),
+            };
+            svg.check(&tag, diagnostics);
+            // Nested graphics are part of this one.
+            return;
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, diagnostics);
+    }
+}
+
+/// Whether `svg` has a `<title>` or `<desc>` child with text in it.
+fn html_has_text_child(svg: &Node, source: &str) -> bool {
+    let mut cursor = svg.walk();
+    svg.named_children(&mut cursor).any(|child| {
+        child.kind() == "element"
+            && html_attrs::element_tag_name(&child, source)
+                .is_some_and(|n| n.eq_ignore_ascii_case("title") || n.eq_ignore_ascii_case("desc"))
+            && {
+                let mut inner = child.walk();
+                child
+                    .named_children(&mut inner)
+                    .any(|c| c.kind() == "text" && !source[c.byte_range()].trim().is_empty())
+            }
+    })
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let attrs = jsx_attrs(&tag, source);
+        let value = |name: &str| {
+            attrs
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| v.as_deref().unwrap_or(""))
+        };
+        if value("aria-hidden").is_some_and(|v| v != "false") {
+            return;
+        }
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        if name == "svg" {
+            // Spread props may carry the name or `aria-hidden`.
+            if !has_spread(&tag) {
+                let is_set = |name: &str| value(name).is_some_and(|v| !v.is_empty());
+                let svg = Svg {
+                    role: value("role").map(first_role),
+                    labelled: is_set("aria-label") || is_set("aria-labelledby") || is_set("title"),
+                    described: jsx_has_text_child(node, source),
+                };
+                svg.check(&tag, diagnostics);
+            }
+            return;
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, diagnostics);
+    }
+}
+
+/// The attributes of a JSX tag with their value: the trimmed text of a
+/// string, `true`/`false` for those literals in braces, `{}` for any other
+/// expression and `None` for a bare attribute.
+fn jsx_attrs<'s>(tag: &Node, source: &'s str) -> Vec<(&'s str, Option<String>)> {
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .filter_map(|attr| jsx_attrs::jsx_attribute(&attr, source))
+        .map(|(name, value)| {
+            let value = value.map(|v| match v.kind() {
+                "string" => source[v.byte_range()]
+                    .trim_matches(['"', '\''])
+                    .trim()
+                    .to_string(),
+                _ => match source[v.byte_range()].trim_matches(['{', '}']).trim() {
+                    literal @ ("true" | "false") => literal.to_string(),
+                    _ => "{}".to_string(),
+                },
+            });
+            (name, value)
+        })
+        .collect()
+}
+
+fn has_spread(tag: &Node) -> bool {
+    let mut cursor = tag.walk();
+    tag.named_children(&mut cursor)
+        .any(|c| c.kind() == "jsx_expression")
+}
+
+/// Whether `svg` has a `<title>` or `<desc>` child with text or an
+/// expression in it.
+fn jsx_has_text_child(svg: &Node, source: &str) -> bool {
+    let mut cursor = svg.walk();
+    svg.named_children(&mut cursor).any(|child| {
+        child.kind() == "jsx_element"
+            && child
+                .child(0)
+                .and_then(|open| open.child_by_field_name("name"))
+                .is_some_and(|n| matches!(&source[n.byte_range()], "title" | "desc"))
+            && {
+                let mut inner = child.walk();
+                child.named_children(&mut inner).any(|c| match c.kind() {
+                    "jsx_text" => !source[c.byte_range()].trim().is_empty(),
+                    "jsx_expression" => true,
+                    _ => false,
+                })
+            }
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+/// The role that applies of a `role` value, lowercased, or `""` for a bound
+/// one (`{}`).
+fn first_role(value: &str) -> String {
+    match value.split_whitespace().next().unwrap_or("") {
+        "{}" => String::new(),
+        role => role.to_ascii_lowercase(),
+    }
+}
+
+fn make_diagnostic(tag: &Node, role: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(tag),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "`<svg role=\"{role}\">` has no accessible name: add a `<title>` as its first child, or `aria-label` or `aria-labelledby`. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+/// The secondary finding: an `<svg>` that's neither named nor hidden.
+fn make_hint(tag: &Node) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(tag),
+        severity: Some(DiagnosticSeverity::HINT),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "`<svg>` has neither a name nor `aria-hidden`: if it's decorative, add `aria-hidden=\"true\"`; if it conveys something, add `role=\"img\"` and a `<title>` or `aria-label`. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = SvgImgAlt;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_svg_img_without_name_fails() {
+        let diags = check_html(
+            r#"<svg role="img" viewBox="0 0 10 10"><circle r="4"/></svg>
+<svg role="graphics-document"><title> </title></svg>"#,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].severity, Some(DiagnosticSeverity::ERROR));
+        assert!(diags[0].message.contains("`<svg role=\"img\">`"));
+        assert_eq!(diags[1].range.start.line, 1);
+    }
+
+    #[test]
+    fn test_named_svgs_pass() {
+        let source = r#"<svg role="img"><title>Sales by month</title><rect/></svg>
+<svg role="img"><desc>A bar chart of sales</desc></svg>
+<svg role="img" aria-label="Logo"></svg>
+<svg role="img" aria-labelledby="chart-title"></svg>
+<svg role="img" :aria-label="label"></svg>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_decorative_svgs() {
+        let source = r#"<svg aria-hidden="true"><path d="M0"/></svg>
+<svg role="presentation"></svg>
+<button aria-hidden="true"><svg></svg></button>
+<svg role="button" tabindex="0"></svg>"#;
+        assert!(check_html(source).is_empty());
+
+        let diags =
+            check_html(r#"<a href="/"><svg viewBox="0 0 4 4"><path d="M0"/></svg> Home</a>"#);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].severity, Some(DiagnosticSeverity::HINT));
+        assert!(diags[0].message.contains("aria-hidden=\"true\""));
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const Icons = () => (
+  <>
+    <svg role="img"><path d="M0" /></svg>
+    <svg role="img"><title>{t("logo")}</title></svg>
+    <svg aria-hidden={true}><path d="M0" /></svg>
+    <svg aria-hidden><path d="M0" /></svg>
+    <svg {...iconProps} />
+    <svg viewBox="0 0 4 4"><path d="M0" /></svg>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert_eq!(diags[0].severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(diags[1].range.start.line, 7);
+        assert_eq!(diags[1].severity, Some(DiagnosticSeverity::HINT));
+    }
+}
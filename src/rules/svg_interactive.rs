@@ -0,0 +1,347 @@
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct SvgInteractive;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "svg-interactive",
+    description: "Interactive SVG graphics must be keyboard focusable and have a role",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "2.1.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/keyboard.html",
+    default_severity: Severity::Error,
+    axe_rules: &[],
+};
+
+impl Rule for SvgInteractive {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if file_type.is_jsx_like() {
+            visit_jsx(root, source, false, &mut diagnostics);
+        } else {
+            visit_html(root, source, false, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+/// What an element in an SVG has of what makes it usable as a control.
+struct Graphic<'a> {
+    name: &'a str,
+    clickable: bool,
+    /// `href` or `xlink:href`, which make an SVG `<a>` a focusable link.
+    href: bool,
+    tabindex: Option<TabIndex>,
+    /// The legacy SVG Tiny `focusable="true"`.
+    focusable: bool,
+    role: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TabIndex {
+    /// In the tab order, or bound to an expression we can't evaluate.
+    Reachable,
+    /// Negative: focusable from script only.
+    Negative,
+}
+
+impl TabIndex {
+    fn parse(value: &str) -> Self {
+        match value.trim().parse::<i64>() {
+            Ok(n) if n < 0 => TabIndex::Negative,
+            _ => TabIndex::Reachable,
+        }
+    }
+}
+
+impl Graphic<'_> {
+    /// What's missing, as the start of the diagnostic message, if the
+    /// element is a control that keyboard and screen reader users can't use.
+    fn problem(&self) -> Option<String> {
+        let link = self.name == "a";
+        // An `<a>` without `href` looks like a link but can't be focused.
+        let unlinked = link && !self.href;
+        if !self.clickable && !unlinked {
+            return None;
+        }
+        let reachable = self.tabindex == Some(TabIndex::Reachable)
+            || (self.tabindex.is_none() && (self.focusable || (link && self.href)));
+        // A linked `<a>` is announced as a link.
+        let role = self.role || (link && self.href);
+        let subject = if self.clickable {
+            format!("Clickable `<{}>` in an SVG", self.name)
+        } else {
+            "SVG `<a>` without `href`".to_string()
+        };
+        match (reachable, role) {
+            (true, true) => None,
+            (false, true) => Some(format!(
+                "{subject} can't be reached with the keyboard: add `tabindex=\"0\"`"
+            )),
+            (true, false) => Some(format!(
+                "{subject} has no role, so screen readers don't present it as a control: add one such as `role=\"button\"`"
+            )),
+            (false, false) => Some(format!(
+                "{subject} can't be reached with the keyboard and has no role: add `tabindex=\"0\"` and a role such as `role=\"button\"`"
+            )),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn visit_html(node: &Node, source: &str, in_svg: bool, diagnostics: &mut Vec<Diagnostic>) {
+    let mut in_svg = in_svg;
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source)
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        in_svg = in_svg || name == "svg";
+        if in_svg {
+            let mut graphic = Graphic {
+                name: &name,
+                clickable: false,
+                href: false,
+                tabindex: None,
+                focusable: false,
+                role: false,
+            };
+            for attr in html_attrs::attrs(&tag, source) {
+                let lower = attr.name_lower();
+                match lower.as_str() {
+                    // DOM (`onclick`) and Vue/Angular (`@click`, `(click)`)
+                    // click handlers.
+                    "onclick" => graphic.clickable = true,
+                    "click" if attr.event => graphic.clickable = true,
+                    "href" | "xlink:href" => graphic.href = true,
+                    "tabindex" if attr.bound => graphic.tabindex = Some(TabIndex::Reachable),
+                    "tabindex" => {
+                        graphic.tabindex = attr.value.as_deref().map(TabIndex::parse);
+                    }
+                    "focusable" => {
+                        graphic.focusable = attr.bound || attr.value.as_deref() == Some("true");
+                    }
+                    "role" => graphic.role = true,
+                    _ => {}
+                }
+            }
+            if let Some(problem) = graphic.problem() {
+                diagnostics.push(make_diagnostic(node_to_range(&tag), &problem));
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_html(&child, source, in_svg, diagnostics);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn visit_jsx(node: &Node, source: &str, in_svg: bool, diagnostics: &mut Vec<Diagnostic>) {
+    let mut in_svg = in_svg;
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag
+        && let Some(name) = tag.child_by_field_name("name")
+    {
+        let name = &source[name.byte_range()];
+        in_svg = in_svg || name == "svg";
+        if in_svg
+            && let Some(problem) = jsx_graphic(&tag, name, source).and_then(|g| g.problem())
+        {
+            diagnostics.push(make_diagnostic(node_to_range(&tag), &problem));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, in_svg, diagnostics);
+    }
+}
+
+/// The SVG element `tag` names, or `None` for a component or an element
+/// whose spread props we can't see.
+fn jsx_graphic<'a>(tag: &Node, name: &'a str, source: &str) -> Option<Graphic<'a>> {
+    if name.starts_with(char::is_uppercase) || name.contains('.') {
+        return None;
+    }
+    let mut graphic = Graphic {
+        name,
+        clickable: false,
+        href: false,
+        tabindex: None,
+        focusable: false,
+        role: false,
+    };
+    let mut cursor = tag.walk();
+    for attr in tag.named_children(&mut cursor) {
+        if attr.kind() == "jsx_expression" {
+            return None;
+        }
+        let Some((attr_name, value)) = jsx_attrs::jsx_attribute(&attr, source) else {
+            continue;
+        };
+        let text = value.map(|value| {
+            source[value.byte_range()]
+                .trim_matches(['"', '\'', '{', '}'])
+                .trim()
+        });
+        match attr_name {
+            "href" | "xlinkHref" | "xlink:href" => graphic.href = true,
+            "tabIndex" | "tabindex" => graphic.tabindex = text.map(TabIndex::parse),
+            "focusable" => graphic.focusable = text != Some("false"),
+            "role" => graphic.role = true,
+            _ if jsx_attrs::jsx_event(attr_name).as_deref() == Some("click") => {
+                graphic.clickable = true;
+            }
+            _ => {}
+        }
+    }
+    Some(graphic)
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn make_diagnostic(range: Range, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = SvgInteractive;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    fn check_tsx(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Tsx)
+    }
+
+    #[test]
+    fn test_clickable_shape_fails() {
+        let diags = check_html(
+            r#"<svg viewBox="0 0 10 10"><circle cx="5" cy="5" r="4" onclick="toggle()"/></svg>"#,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("Clickable `<circle>`"));
+        assert!(diags[0].message.contains("keyboard and has no role"));
+        assert_eq!(diags[0].range.start.character, 25);
+    }
+
+    #[test]
+    fn test_missing_only_role_or_focus() {
+        let diags = check_html(
+            r#"<svg><rect tabindex="0" onclick="a()"/><g role="button" @click="b"><path d=""/></g><rect role="button" tabindex="-1" onclick="c()"/></svg>"#,
+        );
+        assert_eq!(diags.len(), 3);
+        assert!(diags[0].message.contains("has no role"));
+        assert!(
+            diags[1]
+                .message
+                .contains("can't be reached with the keyboard:")
+        );
+        assert!(
+            diags[2]
+                .message
+                .contains("can't be reached with the keyboard:")
+        );
+    }
+
+    #[test]
+    fn test_accessible_graphics_pass() {
+        let source = r#"<svg role="img" aria-label="Chart">
+  <rect role="button" tabindex="0" onclick="a()" onkeydown="a()"/>
+  <g role="link" :tabindex="index" focusable="true" @click="go"></g>
+  <a href="/details"><text>Details</text></a>
+  <a xlink:href="/more"><text>More</text></a>
+</svg>
+<div onclick="x()">Not SVG</div>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_svg_link_without_href_fails() {
+        let diags = check_html(
+            r#"<svg><a onclick="go()"><text>Go</text></a><a><text>Static</text></a></svg>"#,
+        );
+        assert_eq!(diags.len(), 2);
+        assert!(diags[0].message.contains("Clickable `<a>`"));
+        assert!(diags[1].message.contains("SVG `<a>` without `href`"));
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check_tsx(
+            r#"const Map = () => (
+  <svg>
+    <path d="M0" onClick={select} />
+    <path d="M1" onClick={select} role="button" tabIndex={0} />
+    <circle onClick={select} role="button" tabIndex={-1} />
+    <g {...handlers}><Marker onClick={select} /></g>
+    <a xlinkHref="/a"><text>A</text></a>
+  </svg>
+);"#,
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].range.start.line, 2);
+        assert!(diags[0].message.contains("Clickable `<path>`"));
+        assert_eq!(diags[1].range.start.line, 4);
+        assert!(
+            diags[1]
+                .message
+                .contains("can't be reached with the keyboard:")
+        );
+    }
+
+    #[test]
+    fn test_clickable_svg_element_itself() {
+        let diags = check_tsx(r#"const I = () => <svg onClick={open}><path d="M0" /></svg>;"#);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("Clickable `<svg>`"));
+    }
+}
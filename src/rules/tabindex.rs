@@ -14,6 +14,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "2.4.3",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/focus-order.html",
     default_severity: Severity::Warning,
+    axe_rules: &["tabindex"],
 };
 
 impl Rule for Tabindex {
@@ -1,6 +1,8 @@
 use crate::engine::node_to_range;
 use crate::parser::FileType;
+use crate::quickfix::{self, Fix};
 use crate::rules::html_attrs;
+use crate::rules::layout_table;
 use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
 use tower_lsp_server::ls_types::*;
 use tree_sitter::Node;
@@ -14,6 +16,7 @@ static METADATA: RuleMetadata = RuleMetadata {
     wcag_criterion: "1.3.1",
     wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
     default_severity: Severity::Warning,
+    axe_rules: &["td-has-header"],
 };
 
 impl Rule for TableHeader {
@@ -53,12 +56,85 @@ fn check_html_element(element: &Node, source: &str, diagnostics: &mut Vec<Diagno
         return;
     }
 
+    // Layout tables have no headers; `layout-table` checks they don't.
+    let role = html_attrs::element_attr_value(element, source, "role");
+    if layout_table::is_presentational(role.as_deref()) {
+        return;
+    }
+
     // Check if any descendant element is a <th>
     if has_th_descendant(element, source) {
         return;
     }
 
-    diagnostics.push(make_diagnostic(element));
+    diagnostics.push(make_diagnostic(element, header_row_fix(element, source)));
+}
+
+/// Turns the `<td>`s of the table's first row into `<th scope="col">`,
+/// keeping their content and other attributes.
+fn header_row_fix(table: &Node, source: &str) -> Option<Fix> {
+    let row = first_row(table, source)?;
+    let mut edits = Vec::new();
+    let mut cursor = row.walk();
+    for cell in row.children(&mut cursor) {
+        if cell.kind() != "element"
+            || !html_attrs::element_tag_name(&cell, source)
+                .is_some_and(|n| n.eq_ignore_ascii_case("td"))
+        {
+            continue;
+        }
+        let has_scope = html_attrs::element_has_attr(&cell, source, "scope");
+        let mut cell_cursor = cell.walk();
+        for tag in cell.children(&mut cell_cursor) {
+            if !matches!(tag.kind(), "start_tag" | "self_closing_tag" | "end_tag") {
+                continue;
+            }
+            let mut tag_cursor = tag.walk();
+            let Some(name) = tag
+                .children(&mut tag_cursor)
+                .find(|c| c.kind() == "tag_name")
+            else {
+                continue;
+            };
+            let new_text = if tag.kind() != "end_tag" && !has_scope {
+                "th scope=\"col\""
+            } else {
+                "th"
+            };
+            edits.push(TextEdit {
+                range: node_to_range(&name),
+                new_text: new_text.to_string(),
+            });
+        }
+    }
+    (!edits.is_empty()).then(|| Fix {
+        title: "Make the first row a header row".to_string(),
+        edits,
+        ..Default::default()
+    })
+}
+
+/// The first `<tr>` of `table`, looking through `<thead>`/`<tbody>` but not
+/// into nested tables.
+fn first_row<'a>(node: &Node<'a>, source: &str) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() != "element" {
+            continue;
+        }
+        let name = html_attrs::element_tag_name(&child, source).unwrap_or("");
+        if name.eq_ignore_ascii_case("tr") {
+            return Some(child);
+        }
+        if ["thead", "tbody", "tfoot"]
+            .iter()
+            .any(|t| name.eq_ignore_ascii_case(t))
+            && let Some(row) = first_row(&child, source)
+        {
+            return Some(row);
+        }
+    }
+    None
 }
 
 /// Recursively check whether the element contains a <th> descendant.
@@ -80,9 +156,9 @@ fn has_th_descendant(node: &Node, source: &str) -> bool {
     false
 }
 
-fn make_diagnostic(node: &Node) -> Diagnostic {
+fn make_diagnostic(node: &Node, fix: Option<Fix>) -> Diagnostic {
     let meta = &METADATA;
-    Diagnostic {
+    let mut diagnostic = Diagnostic {
         range: node_to_range(node),
         severity: Some(DiagnosticSeverity::WARNING),
         code: Some(NumberOrString::String(meta.id.to_string())),
@@ -95,7 +171,11 @@ fn make_diagnostic(node: &Node) -> Diagnostic {
             meta.description, meta.wcag_criterion, meta.wcag_level
         ),
         ..Default::default()
+    };
+    if let Some(fix) = fix {
+        quickfix::attach(&mut diagnostic, fix);
     }
+    diagnostic
 }
 
 #[cfg(test)]
@@ -151,6 +231,12 @@ mod tests {
         assert_eq!(diags.len(), 0);
     }
 
+    #[test]
+    fn test_layout_table_passes() {
+        let diags = check_html(r#"<table role="presentation"><tr><td>Nav</td></tr></table>"#);
+        assert_eq!(diags.len(), 0);
+    }
+
     #[test]
     fn test_no_table_passes() {
         let diags = check_html(r#"<div><p>Hello</p></div>"#);
@@ -171,4 +257,44 @@ mod tests {
             check_html(r#"<table><tr><th>H</th></tr></table><table><tr><td>D</td></tr></table>"#);
         assert_eq!(diags.len(), 1);
     }
+
+    /// Applies the first fix of the first diagnostic to a one-line `source`.
+    fn apply_fix(source: &str, diags: &[Diagnostic]) -> String {
+        let fix = &quickfix::fixes(&diags[0])[0];
+        let mut result = source.to_string();
+        for edit in fix.edits.iter().rev() {
+            let range = edit.range.start.character as usize..edit.range.end.character as usize;
+            result.replace_range(range, &edit.new_text);
+        }
+        result
+    }
+
+    #[test]
+    fn test_fix_converts_first_row() {
+        let source = r#"<table><tr><td>Name</td><td class="n">Age</td></tr><tr><td>Ann</td><td>5</td></tr></table>"#;
+        let diags = check_html(source);
+        let fixed = apply_fix(source, &diags);
+        assert_eq!(
+            fixed,
+            r#"<table><tr><th scope="col">Name</th><th scope="col" class="n">Age</th></tr><tr><td>Ann</td><td>5</td></tr></table>"#
+        );
+        assert_eq!(check_html(&fixed).len(), 0);
+    }
+
+    #[test]
+    fn test_fix_looks_into_tbody_and_keeps_scope() {
+        let source = r#"<table><tbody><tr><td scope="row">A</td><td>B</td></tr></tbody></table>"#;
+        let diags = check_html(source);
+        assert_eq!(
+            apply_fix(source, &diags),
+            r#"<table><tbody><tr><th scope="row">A</th><th scope="col">B</th></tr></tbody></table>"#
+        );
+    }
+
+    #[test]
+    fn test_no_fix_for_empty_table() {
+        let diags = check_html("<table></table>");
+        assert_eq!(diags.len(), 1);
+        assert!(quickfix::fixes(&diags[0]).is_empty());
+    }
 }
@@ -0,0 +1,343 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::engine::node_to_range;
+use crate::parser::FileType;
+use crate::rules::html_attrs;
+use crate::rules::jsx_attrs;
+use crate::rules::{Rule, RuleMetadata, Severity, WcagLevel};
+use tower_lsp_server::ls_types::*;
+use tree_sitter::Node;
+
+pub struct TdHeadersAttr;
+
+static METADATA: RuleMetadata = RuleMetadata {
+    id: "td-headers-attr",
+    description: "headers attributes must refer to header cells in the same table",
+    wcag_level: WcagLevel::A,
+    wcag_criterion: "1.3.1",
+    wcag_url: "https://www.w3.org/WAI/WCAG21/Understanding/info-and-relationships.html",
+    default_severity: Severity::Error,
+    axe_rules: &["td-headers-attr"],
+};
+
+/// Roles that make any element a header cell.
+const HEADER_ROLES: &[&str] = &["columnheader", "rowheader"];
+
+/// An element in document order, as far as table headers are concerned.
+struct Element<'a> {
+    /// Index of the nearest enclosing element.
+    parent: Option<usize>,
+    /// Lowercased for HTML, as written for JSX.
+    name: String,
+    /// Static `id`.
+    id: Option<String>,
+    /// Has a runtime `id`, or is a component or has spread props, which may
+    /// render any id.
+    unknown_ids: bool,
+    /// A `<th>`, or an element with a header role.
+    header: bool,
+    /// The static `headers` attribute of a `<td>` or `<th>` and the ids it
+    /// lists.
+    headers: Option<(Node<'a>, Vec<String>)>,
+}
+
+impl Rule for TdHeadersAttr {
+    fn metadata(&self) -> &RuleMetadata {
+        &METADATA
+    }
+
+    fn check(&self, root: &Node, source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut elements = Vec::new();
+        if file_type.is_jsx_like() {
+            collect_jsx(root, source, None, &mut elements);
+        } else {
+            collect_html(root, source, None, &mut elements);
+        }
+        check_headers(&elements)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn collect_html<'a>(
+    node: &Node<'a>,
+    source: &str,
+    parent: Option<usize>,
+    elements: &mut Vec<Element<'a>>,
+) {
+    let mut parent = parent;
+    if node.kind() == "element"
+        && let Some(tag) = html_attrs::element_tag(node)
+    {
+        let name = html_attrs::tag_name(&tag, source)
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let attrs = html_attrs::attrs(&tag, source);
+        let static_value = |attr: &str| {
+            attrs
+                .iter()
+                .find(|a| a.name_eq(attr) && !a.bound)
+                .and_then(|a| a.value.clone().map(|v| (a.node, v)))
+        };
+        let role = static_value("role").map(|(_, v)| v);
+        let headers = static_value("headers").filter(|_| is_cell(&name));
+        elements.push(Element {
+            parent,
+            header: is_header(&name, role.as_deref()),
+            id: static_value("id").map(|(_, v)| v.trim().to_string()),
+            unknown_ids: attrs.iter().any(|a| a.bound && a.name_eq("id")),
+            headers: headers.map(|(node, v)| (node, split_ids(&v))),
+            name,
+        });
+        parent = Some(elements.len() - 1);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_html(&child, source, parent, elements);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// JSX / TSX
+// ---------------------------------------------------------------------------
+
+fn collect_jsx<'a>(
+    node: &Node<'a>,
+    source: &str,
+    parent: Option<usize>,
+    elements: &mut Vec<Element<'a>>,
+) {
+    let mut parent = parent;
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let name = tag
+            .child_by_field_name("name")
+            .map_or("", |n| &source[n.byte_range()]);
+        let mut unknown_ids =
+            !name.starts_with(|c: char| c.is_ascii_lowercase()) || name.contains('.');
+        let mut id = None;
+        let mut role = None;
+        let mut headers = None;
+        let mut cursor = tag.walk();
+        for attr in tag.named_children(&mut cursor) {
+            if attr.kind() == "jsx_expression" {
+                unknown_ids = true;
+            }
+            let Some((attr_name, value)) = jsx_attrs::jsx_attribute(&attr, source) else {
+                continue;
+            };
+            let text = value
+                .filter(|v| v.kind() == "string")
+                .map(|v| source[v.byte_range()].trim_matches(['"', '\'']).to_string());
+            match (attr_name, text) {
+                ("id", Some(text)) => id = Some(text.trim().to_string()),
+                ("id", None) => unknown_ids = true,
+                ("role", text) => role = text,
+                ("headers", Some(text)) if is_cell(name) => {
+                    headers = Some((attr, split_ids(&text)));
+                }
+                _ => {}
+            }
+        }
+        elements.push(Element {
+            parent,
+            name: name.to_string(),
+            id,
+            unknown_ids,
+            header: is_header(name, role.as_deref()),
+            headers,
+        });
+        parent = Some(elements.len() - 1);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_jsx(&child, source, parent, elements);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared
+// ---------------------------------------------------------------------------
+
+fn is_cell(name: &str) -> bool {
+    name == "td" || name == "th"
+}
+
+fn is_header(name: &str, role: Option<&str>) -> bool {
+    let role = role
+        .and_then(|r| r.split_whitespace().next())
+        .map(str::to_ascii_lowercase);
+    match role {
+        Some(role) => HEADER_ROLES.contains(&role.as_str()),
+        None => name == "th",
+    }
+}
+
+fn split_ids(value: &str) -> Vec<String> {
+    value.split_whitespace().map(str::to_string).collect()
+}
+
+/// The index of the nearest `<table>` around element `i`.
+fn table_of(elements: &[Element], i: usize) -> Option<usize> {
+    let mut current = elements[i].parent;
+    while let Some(j) = current {
+        if elements[j].name == "table" {
+            return Some(j);
+        }
+        current = elements[j].parent;
+    }
+    None
+}
+
+/// Checks each id in a cell's `headers` against the elements of its table:
+/// it must be there, and be a header cell other than the cell itself.
+fn check_headers(elements: &[Element]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let tables: Vec<Option<usize>> = (0..elements.len()).map(|i| table_of(elements, i)).collect();
+    let mut by_id: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, element) in elements.iter().enumerate() {
+        if let Some(id) = element.id.as_deref().filter(|id| !id.is_empty()) {
+            by_id.entry(id).or_default().push(i);
+        }
+    }
+    // Tables where a missing id might still be rendered at runtime.
+    let uncertain: HashSet<usize> = elements
+        .iter()
+        .enumerate()
+        .filter(|(_, element)| element.unknown_ids)
+        .filter_map(|(i, _)| tables[i])
+        .collect();
+
+    for (cell, element) in elements.iter().enumerate() {
+        let Some((attr, ids)) = &element.headers else {
+            continue;
+        };
+        let Some(table) = tables[cell] else {
+            continue;
+        };
+        for id in ids {
+            let found = by_id.get(id.as_str()).map_or(&[][..], Vec::as_slice);
+            let in_table = found.iter().find(|&&i| tables[i] == Some(table));
+            let problem = match in_table {
+                Some(&i) if i == cell => format!("`headers` refers to the cell itself (\"{id}\")"),
+                Some(&i) if !elements[i].header => format!(
+                    "`headers` refers to \"{id}\", which isn't a header cell: point it at a `<th>`"
+                ),
+                Some(_) => continue,
+                None if uncertain.contains(&table) => continue,
+                None if !found.is_empty() => {
+                    format!("`headers` refers to \"{id}\", which is outside this table")
+                }
+                None => format!("`headers` refers to \"{id}\", but no element has that id"),
+            };
+            diagnostics.push(make_diagnostic(attr, &problem));
+        }
+    }
+    diagnostics
+}
+
+fn make_diagnostic(node: &Node, problem: &str) -> Diagnostic {
+    let meta = &METADATA;
+    Diagnostic {
+        range: node_to_range(node),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(meta.id.to_string())),
+        code_description: Some(CodeDescription {
+            href: meta.wcag_url.parse().expect("valid URL"),
+        }),
+        source: Some("wcag-lsp".to_string()),
+        message: format!(
+            "{problem}. {} [WCAG {} Level {:?}]",
+            meta.description, meta.wcag_criterion, meta.wcag_level
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn check(source: &str, file_type: FileType) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(file_type).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let rule = TdHeadersAttr;
+        rule.check(&tree.root_node(), source, file_type)
+    }
+
+    fn check_html(source: &str) -> Vec<Diagnostic> {
+        check(source, FileType::Html)
+    }
+
+    #[test]
+    fn test_valid_headers_pass() {
+        let source = r#"<table>
+  <tr><td></td><th id="q1">Q1</th><th id="q2">Q2</th></tr>
+  <tr><td id="sales" role="rowheader">Sales</td><td headers="q1 sales">10</td><td headers="q2  sales">12</td></tr>
+</table>"#;
+        assert!(check_html(source).is_empty());
+    }
+
+    #[test]
+    fn test_broken_references_fail() {
+        let source = r#"<table>
+  <tr><th id="name">Name</th><td id="note">Note</td></tr>
+  <tr><td id="self" headers="missing name note self">x</td></tr>
+</table>"#;
+        let diags = check_html(source);
+        let messages: Vec<_> = diags.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(diags.len(), 3);
+        assert!(messages[0].contains("\"missing\", but no element has that id"));
+        assert!(messages[1].contains("\"note\", which isn't a header cell"));
+        assert!(messages[2].contains("the cell itself"));
+        assert_eq!(diags[0].range.start.line, 2);
+        assert_eq!(diags[0].range.start.character, 20);
+    }
+
+    #[test]
+    fn test_cross_table_reference_fails() {
+        let source = r#"<table><tr><th id="a">A</th></tr></table>
+<table><tr><td headers="a">1</td></tr>
+  <tr><td><table><tr><td headers="outer">2</td></tr></table></td><th id="outer">Outer</th></tr>
+</table>"#;
+        let diags = check_html(source);
+        assert_eq!(diags.len(), 2);
+        assert!(
+            diags
+                .iter()
+                .all(|d| d.message.contains("outside this table"))
+        );
+    }
+
+    #[test]
+    fn test_runtime_ids_skip_missing() {
+        let source = r#"<template><table><tr><th :id="col.id">A</th></tr><tr><td headers="a-1" :headers="x">1</td><td headers="b-2">2</td></tr></table></template>"#;
+        assert!(check(source, FileType::Vue).is_empty());
+    }
+
+    #[test]
+    fn test_jsx() {
+        let diags = check(
+            r#"const T = () => (
+  <>
+    <table><tr><th id="h">H</th><td headers="h">1</td><td headers="x">2</td></tr></table>
+    <table><tr><HeaderCell /><td headers="y">3</td></tr></table>
+    <table><tr><th id={colId}>H</th><td headers="z">4</td></tr></table>
+  </>
+);"#,
+            FileType::Tsx,
+        );
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("\"x\""));
+    }
+}
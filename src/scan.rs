@@ -0,0 +1,380 @@
+//! Linting many files at once: the CLI `check` command and the server's
+//! workspace scan.
+//!
+//! Files are spread over a fixed number of worker threads. Each thread keeps
+//! one tree-sitter parser per file type for its whole life instead of creating
+//! one per file. Finished results go through a bounded channel, so workers
+//! pause when the consumer (printing, publishing to the editor) falls behind
+//! rather than piling up results in memory.
+
+use crate::cache::{self, Cache};
+use crate::config::Config;
+use crate::crash;
+use crate::document::Document;
+use crate::engine;
+use crate::parser::{self, FileType};
+use crate::rules::Rule;
+use crate::score;
+use crate::tagged_templates;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use tower_lsp_server::ls_types::Diagnostic;
+use tree_sitter::{Parser, Tree};
+
+/// Directories never worth descending into during a workspace scan.
+const SKIP_DIRS: &[&str] = &["node_modules", "target", "dist", "build", "vendor"];
+
+thread_local! {
+    static PARSERS: RefCell<HashMap<FileType, Parser>> = RefCell::new(HashMap::new());
+}
+
+/// Parses `source` with this thread's parser for `file_type`.
+pub fn parse(file_type: FileType, source: &str) -> Option<Tree> {
+    PARSERS.with(|parsers| {
+        let mut parsers = parsers.borrow_mut();
+        if let std::collections::hash_map::Entry::Vacant(e) = parsers.entry(file_type) {
+            e.insert(parser::create_parser(file_type)?);
+        }
+        parsers
+            .get_mut(&file_type)?
+            .parse(parser::parse_input(file_type, source).as_ref(), None)
+    })
+}
+
+/// Worker threads for a scan: all cores but one, which stays free for the
+/// editor (or whatever else the machine is doing).
+pub fn default_workers() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get().saturating_sub(1).max(1))
+        .unwrap_or(1)
+}
+
+/// Runs `job` for every item on up to `workers` threads and hands each result
+/// to `sink` on the calling thread, in completion order. At most two results
+/// per worker are buffered; beyond that, workers wait for `sink`.
+pub fn for_each_parallel<T, R>(
+    items: &[T],
+    workers: usize,
+    job: impl Fn(&T) -> R + Sync,
+    mut sink: impl FnMut(&T, R),
+) where
+    T: Sync,
+    R: Send,
+{
+    let workers = workers.clamp(1, items.len().max(1));
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::sync_channel::<(usize, R)>(workers * 2);
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (next, job) = (&next, &job);
+            scope.spawn(move || {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else {
+                        break;
+                    };
+                    if tx.send((i, job(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+        for (i, result) in rx {
+            sink(&items[i], result);
+        }
+    });
+}
+
+/// Lints `source`, read from `uri` (a path or URI, which selects profiles
+/// such as the one for Storybook stories). The flag is `false` if a rule
+/// crashed, so the result is incomplete and must not be cached.
+pub fn lint_source(
+    uri: &str,
+    source: &str,
+    file_type: FileType,
+    rules: &[Box<dyn Rule>],
+    config: &Config,
+) -> (Vec<Diagnostic>, bool) {
+    let (diagnostics, complete, _) = lint(uri, source, file_type, false, rules, config);
+    (diagnostics, complete)
+}
+
+/// Lints a DOM snapshot saved from a browser, with the dynamic rules too if
+/// `dynamic` is set. The flag is as for [`lint_source`].
+pub fn lint_snapshot(
+    uri: &str,
+    source: &str,
+    dynamic: bool,
+    rules: &[Box<dyn Rule>],
+    config: &Config,
+) -> (Vec<Diagnostic>, bool) {
+    let (diagnostics, complete, _) = lint(uri, source, FileType::Html, dynamic, rules, config);
+    (diagnostics, complete)
+}
+
+/// The diagnostics of `source`, whether they're complete, and its number
+/// of elements.
+fn lint(
+    uri: &str,
+    source: &str,
+    file_type: FileType,
+    rendered: bool,
+    rules: &[Box<dyn Rule>],
+    config: &Config,
+) -> (Vec<Diagnostic>, bool, usize) {
+    if file_type.is_script() && !tagged_templates::may_have_templates(source) {
+        return (vec![], true, 0);
+    }
+    let Some(doc) = document(uri, source, file_type, rendered) else {
+        return (vec![], true, 0);
+    };
+    let elements = score::document_elements(&doc);
+
+    let (diagnostics, crashes) = engine::run_diagnostics_checked(&doc, rules, config);
+    for crash in &crashes {
+        crash::write_report(crash);
+    }
+    (diagnostics, crashes.is_empty(), elements)
+}
+
+/// `source` parsed as a document of `file_type`, outside of an editor.
+pub fn document(uri: &str, source: &str, file_type: FileType, rendered: bool) -> Option<Document> {
+    let tree = parse(file_type, source)?;
+    Some(Document {
+        uri: uri.to_string(),
+        file_type,
+        source: source.to_string(),
+        templates: tagged_templates::parse(file_type, &tree, source),
+        tree,
+        version: 0,
+        rendered,
+    })
+}
+
+/// The number of elements in `source`, for linting that doesn't go through
+/// [`lint_file`].
+pub fn count_elements(file_type: FileType, source: &str) -> usize {
+    parse(file_type, source).map_or(0, |tree| score::count_elements(&tree.root_node()))
+}
+
+/// Reads and lints one file, going through `cache` when there is one.
+/// Returns its diagnostics and number of elements, or `Ok(None)` for files
+/// that aren't a supported file type.
+pub fn lint_file(
+    path: &Path,
+    config: &Config,
+    rules: &[Box<dyn Rule>],
+    cache: Option<&Cache>,
+    package_key: &str,
+) -> std::io::Result<Option<(Vec<Diagnostic>, usize)>> {
+    let uri = path.to_string_lossy();
+    let (source, file_type) = match config.file_type_from_name(&uri) {
+        FileType::Unknown => {
+            // Only files whose name says nothing are sniffed, so images and
+            // other binaries a glob matches are skipped quietly.
+            if !path.is_file() {
+                return Ok(None);
+            }
+            let source = match std::fs::read_to_string(path) {
+                Ok(source) => source,
+                Err(e) if e.kind() == std::io::ErrorKind::InvalidData => return Ok(None),
+                Err(e) => return Err(e),
+            };
+            let file_type = FileType::sniff(&source);
+            if file_type == FileType::Unknown {
+                return Ok(None);
+            }
+            (source, file_type)
+        }
+        file_type => (std::fs::read_to_string(path)?, file_type),
+    };
+
+    let key = cache::cache_key(&source, file_type, config, rules);
+    if let Some(cached) = cache.and_then(|c| c.get(package_key, path, &key)) {
+        return Ok(Some(cached));
+    }
+    let (diagnostics, complete, elements) = lint(&uri, &source, file_type, false, rules, config);
+    if complete && let Some(cache) = cache {
+        cache.put(package_key, path, &key, &diagnostics, elements);
+    }
+    Ok(Some((diagnostics, elements)))
+}
+
+/// Every file under `root` with an extension wcag-lsp lints (built in or
+/// mapped in `config`), skipping hidden and dependency/build directories.
+pub fn workspace_files(root: &Path, config: &Config) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let Ok(kind) = entry.file_type() else {
+                continue;
+            };
+            if kind.is_dir() {
+                if !name.starts_with('.') && !SKIP_DIRS.contains(&name.as_ref()) {
+                    dirs.push(entry.path());
+                }
+            } else if kind.is_file() {
+                let ext = name.rsplit_once('.').map(|(_, e)| e.to_ascii_lowercase());
+                let supported = ext.is_some_and(|ext| {
+                    config.file_types.contains_key(&ext)
+                        || FileType::from_extension(&ext) != FileType::Unknown
+                });
+                if supported {
+                    files.push(entry.path());
+                }
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules;
+
+    #[test]
+    fn test_for_each_parallel_visits_every_item_once() {
+        let items: Vec<usize> = (0..200).collect();
+        let mut seen = Vec::new();
+        for_each_parallel(
+            &items,
+            4,
+            |n| n * 2,
+            |n, doubled| {
+                assert_eq!(*n * 2, doubled);
+                seen.push(*n);
+            },
+        );
+        seen.sort();
+        assert_eq!(seen, items);
+    }
+
+    #[test]
+    fn test_for_each_parallel_empty() {
+        let items: Vec<u8> = Vec::new();
+        let mut calls = 0;
+        for_each_parallel(&items, 8, |_| (), |_, _| calls += 1);
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn test_parser_is_reused_per_thread() {
+        let first = parse(FileType::Html, "<p>a</p>").unwrap();
+        let second = parse(FileType::Html, "<img src=x>").unwrap();
+        assert_eq!(first.root_node().kind(), "document");
+        assert_eq!(second.root_node().kind(), "document");
+        PARSERS.with(|p| assert_eq!(p.borrow().len(), 1));
+    }
+
+    #[test]
+    fn test_lint_file_uses_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.html");
+        std::fs::write(&file, r#"<img src="x.png">"#).unwrap();
+        let cache = Cache::new(dir.path().join("cache"));
+        let rules = rules::all_rules();
+        let config = Config::default();
+
+        let (first, elements) = lint_file(&file, &config, &rules, Some(&cache), "pkg")
+            .unwrap()
+            .unwrap();
+        assert!(first.iter().any(|d| d.message.contains("alt")));
+        assert_eq!(elements, 1);
+
+        // A cache hit doesn't re-lint: an entry we planted comes back as is.
+        let source = std::fs::read_to_string(&file).unwrap();
+        let key = cache::cache_key(&source, FileType::Html, &config, &rules);
+        cache.put("pkg", &file, &key, &[], 1);
+        let (second, _) = lint_file(&file, &config, &rules, Some(&cache), "pkg")
+            .unwrap()
+            .unwrap();
+        assert!(second.is_empty());
+    }
+
+    #[test]
+    fn test_lint_file_skips_binaries_and_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let rules = rules::all_rules();
+        let config = Config::default();
+        let logo = dir.path().join("logo.png");
+        std::fs::write(&logo, [0x89, b'P', b'N', b'G', 0xff, 0xfe]).unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir(&sub).unwrap();
+        let notes = dir.path().join("notes.txt");
+        std::fs::write(&notes, "Wrap the page in <html>.").unwrap();
+
+        for path in [&logo, &sub, &notes] {
+            assert!(
+                lint_file(path, &config, &rules, None, "pkg")
+                    .unwrap()
+                    .is_none()
+            );
+        }
+    }
+
+    #[test]
+    fn test_plain_scripts_lint_only_their_templates() {
+        let rules = rules::all_rules();
+        let config = Config::default();
+        let lint = |source: &str| {
+            lint(
+                "file:///a.ts",
+                source,
+                FileType::TypeScript,
+                false,
+                &rules,
+                &config,
+            )
+        };
+
+        let (diagnostics, complete, elements) =
+            lint("const n = <number>value;\nconst s = `<img src=\"a.png\">`;\n");
+        assert!(diagnostics.is_empty());
+        assert!(complete);
+        assert_eq!(elements, 0);
+
+        let (diagnostics, _, elements) =
+            lint("const n = <number>value;\nconst t = html`<img src=\"a.png\">`;\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start.line, 1);
+        assert_eq!(elements, 1);
+    }
+
+    #[test]
+    fn test_workspace_files_skips_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for path in [
+            "index.html",
+            "src/App.tsx",
+            "src/notes.txt",
+            "node_modules/lib/index.html",
+            ".git/hooks/x.html",
+            "tpl/page.inc",
+        ] {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let files = workspace_files(root, &Config::default());
+        assert_eq!(files, [root.join("index.html"), root.join("src/App.tsx")]);
+
+        let config = Config::parse("[filetypes]\ninc = \"html\"\n");
+        assert_eq!(workspace_files(root, &config).len(), 3);
+    }
+}
@@ -0,0 +1,220 @@
+//! An accessibility score for a whole project, for CI thresholds such as
+//! `wcag-lsp check --min-score 85`.
+//!
+//! Every problem is weighted by its rule's WCAG level (A 3, AA 2, AAA 1) and
+//! by its severity (error 1, warning 0.5; information and hints don't
+//! count). The score is `100 × elements / (elements + 10 × weighted
+//! problems)`, as if each weighted problem spoiled [`SPOILED_ELEMENTS`] more
+//! elements. So a large project isn't penalised for being large, and a
+//! small file isn't floored by a few warnings: ten Level A errors give 76.9
+//! in 1000 elements and 97.1 in 10000, and three Level A warnings in 21
+//! elements give 31.8. Only problems without any elements score 0.
+
+use crate::document::Document;
+use crate::rules::{Rule, WcagLevel};
+use serde::Serialize;
+use tower_lsp_server::ls_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+use tree_sitter::Node;
+
+/// How many elements one weighted problem stands for.
+const SPOILED_ELEMENTS: f64 = 10.0;
+
+/// Elements and weighted problems of one file, or of many added up.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Tally {
+    pub files: usize,
+    pub elements: usize,
+    pub weight: f64,
+}
+
+/// The score of a [`Tally`], as reported by the CLI and `wcag/score`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Score {
+    /// 0 to 100, rounded to one decimal.
+    pub score: f64,
+    pub files: usize,
+    pub elements: usize,
+    pub weighted_problems: f64,
+}
+
+impl Tally {
+    /// The tally of a file with `elements` elements and `diagnostics`, as
+    /// presented (after severity mappings).
+    pub fn of(diagnostics: &[Diagnostic], elements: usize, rules: &[Box<dyn Rule>]) -> Self {
+        Self {
+            files: 1,
+            elements,
+            weight: diagnostics.iter().map(|d| weight(d, rules)).sum(),
+        }
+    }
+
+    pub fn add(&mut self, other: Tally) {
+        self.files += other.files;
+        self.elements += other.elements;
+        self.weight += other.weight;
+    }
+
+    pub fn score(&self) -> Score {
+        let elements = self.elements as f64;
+        let spoiled = self.weight * SPOILED_ELEMENTS;
+        let score = match spoiled > 0.0 {
+            true => 100.0 * elements / (elements + spoiled),
+            false => 100.0,
+        };
+        Score {
+            score: (score * 10.0).round() / 10.0,
+            files: self.files,
+            elements: self.elements,
+            weighted_problems: self.weight,
+        }
+    }
+}
+
+impl FromIterator<Tally> for Tally {
+    fn from_iter<I: IntoIterator<Item = Tally>>(tallies: I) -> Self {
+        let mut total = Tally::default();
+        for tally in tallies {
+            total.add(tally);
+        }
+        total
+    }
+}
+
+/// How much `diagnostic` costs. Diagnostics of no known rule cost nothing.
+fn weight(diagnostic: &Diagnostic, rules: &[Box<dyn Rule>]) -> f64 {
+    let severity = match diagnostic.severity {
+        Some(DiagnosticSeverity::ERROR) => 1.0,
+        Some(DiagnosticSeverity::WARNING) | None => 0.5,
+        _ => return 0.0,
+    };
+    let level = match rule_level(diagnostic, rules) {
+        Some(WcagLevel::A) => 3.0,
+        Some(WcagLevel::AA) => 2.0,
+        Some(WcagLevel::AAA) => 1.0,
+        None => return 0.0,
+    };
+    level * severity
+}
+
+/// The WCAG level of the rule that reported `diagnostic`.
+pub(crate) fn rule_level(diagnostic: &Diagnostic, rules: &[Box<dyn Rule>]) -> Option<WcagLevel> {
+    let Some(NumberOrString::String(code)) = &diagnostic.code else {
+        return None;
+    };
+    rules
+        .iter()
+        .find(|r| r.metadata().id == code)
+        .map(|r| r.metadata().wcag_level)
+}
+
+/// The number of elements of `doc`: those of its own tree, unless it's plain
+/// JS/TS, and of its tagged templates.
+pub fn document_elements(doc: &Document) -> usize {
+    let own = match doc.file_type.is_script() {
+        true => 0,
+        false => count_elements(&doc.tree.root_node()),
+    };
+    own + doc
+        .templates
+        .as_ref()
+        .map_or(0, |templates| count_elements(&templates.tree.root_node()))
+}
+
+/// The number of HTML and JSX elements under `root`.
+pub fn count_elements(root: &Node) -> usize {
+    let own = matches!(
+        root.kind(),
+        "element" | "script_element" | "style_element" | "jsx_element" | "jsx_self_closing_element"
+    ) as usize;
+    let mut cursor = root.walk();
+    own + root
+        .children(&mut cursor)
+        .map(|child| count_elements(&child))
+        .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{self, FileType};
+    use crate::rules;
+
+    fn diagnostic(code: &str, severity: DiagnosticSeverity) -> Diagnostic {
+        Diagnostic {
+            code: Some(NumberOrString::String(code.to_string())),
+            severity: Some(severity),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_weighted_by_level_and_severity() {
+        let rules = rules::all_rules();
+        let diagnostics = [
+            // img-alt is Level A, heading-content AA.
+            diagnostic("img-alt", DiagnosticSeverity::ERROR),
+            diagnostic("heading-content", DiagnosticSeverity::WARNING),
+            diagnostic("math-alt", DiagnosticSeverity::INFORMATION),
+            diagnostic("unknown", DiagnosticSeverity::ERROR),
+        ];
+        let tally = Tally::of(&diagnostics, 200, &rules);
+        assert_eq!(tally.weight, 4.0);
+        let score = tally.score();
+        assert_eq!(score.score, 83.3);
+        assert_eq!(score.elements, 200);
+        assert_eq!(score.files, 1);
+    }
+
+    #[test]
+    fn test_totals_and_bounds() {
+        let rules = rules::all_rules();
+        let errors = vec![diagnostic("img-alt", DiagnosticSeverity::ERROR); 5];
+        let bad = Tally::of(&errors, 100, &rules);
+        assert_eq!(bad.score().score, 40.0);
+
+        let clean = Tally::of(&[], 2900, &rules);
+        assert_eq!(clean.score().score, 100.0);
+        let total: Tally = [bad, clean].into_iter().collect();
+        assert_eq!(total.files, 2);
+        assert_eq!(total.score().score, 95.2);
+        assert_eq!(Tally::default().score().score, 100.0);
+        assert_eq!(Tally::of(&errors, 0, &rules).score().score, 0.0);
+    }
+
+    #[test]
+    fn test_small_files_keep_a_score() {
+        let rules = rules::all_rules();
+        let warnings = vec![diagnostic("img-alt", DiagnosticSeverity::WARNING); 3];
+        let small = Tally::of(&warnings, 21, &rules);
+        assert_eq!(small.weight, 4.5);
+        assert_eq!(small.score().score, 31.8);
+        // One more problem still costs something.
+        let more = vec![diagnostic("img-alt", DiagnosticSeverity::WARNING); 4];
+        assert!(Tally::of(&more, 21, &rules).score().score < small.score().score);
+        assert!(Tally::of(&warnings[..1], 21, &rules).score().score > 50.0);
+    }
+
+    #[test]
+    fn test_count_elements() {
+        let count = |source: &str, file_type: FileType| {
+            let mut parser = parser::create_parser(file_type).unwrap();
+            let tree = parser.parse(source, None).unwrap();
+            count_elements(&tree.root_node())
+        };
+        assert_eq!(
+            count(
+                "<ul><li>a</li><li>b<br></li></ul><script></script>",
+                FileType::Html
+            ),
+            5
+        );
+        assert_eq!(
+            count(
+                "const A = () => <div><img src={s} /><p>x</p></div>;",
+                FileType::Tsx
+            ),
+            3
+        );
+    }
+}
@@ -1,10 +1,22 @@
-use crate::config::Config;
+use crate::cache::Cache;
+use crate::changelog;
+use crate::config::{self, Config};
+use crate::crash::{self, CrashReport};
 use crate::document::DocumentManager;
 use crate::engine;
+use crate::nav_index::{self, NavIndex};
+use crate::quickfix;
+use crate::rule_actions::{self, RuleActionContext};
 use crate::rules::{self, Rule};
+use crate::scan;
+use crate::score::{self, Score, Tally};
+use crate::workspace::{self, PackageConfigs};
 use glob_match::glob_match;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 use tower_lsp_server::jsonrpc::Result;
 use tower_lsp_server::ls_types::*;
@@ -14,8 +26,84 @@ pub struct WcagLspServer {
     pub client: Client,
     pub documents: Arc<RwLock<DocumentManager>>,
     pub config: Arc<RwLock<Config>>,
+    /// Configs of monorepo packages that have their own.
+    pub packages: Arc<RwLock<PackageConfigs>>,
     pub rules: Arc<Vec<Box<dyn Rule>>>,
     pub debounce_versions: Arc<RwLock<HashMap<String, i32>>>,
+    /// The `languageId` each open document was opened with, by URI, for
+    /// detecting its file type again later.
+    pub language_ids: Arc<RwLock<HashMap<String, String>>>,
+    /// The first workspace folder, if the client sent one.
+    pub workspace_root: Arc<RwLock<Option<PathBuf>>>,
+    /// The config file from `initializationOptions.configPath`, which
+    /// applies to every file.
+    pub config_file: Arc<RwLock<Option<PathBuf>>>,
+    /// Glob patterns of the files the config depends on, as last registered
+    /// with the client.
+    pub watched_files: Arc<RwLock<Vec<String>>>,
+    /// When the last full workspace scan finished, if one ever ran.
+    pub last_workspace_scan: Arc<RwLock<Option<SystemTime>>>,
+    /// What the client told us about itself in `initialize`.
+    pub editor: Arc<RwLock<EditorProfile>>,
+    /// Navigation landmarks of the workspace, for `consistent-nav-label`.
+    /// Filled by the workspace scan and kept up to date for open documents.
+    pub nav_index: Arc<RwLock<NavIndex>>,
+    /// Elements and weighted problems of every file linted so far, by URI,
+    /// for `wcag/score`.
+    pub scores: Arc<RwLock<HashMap<String, Tally>>>,
+    /// The rule and URI of every crash the user was told about, so a rule
+    /// that crashes on each re-lint of a file is only shown once.
+    pub reported_crashes: Arc<RwLock<HashSet<(String, String)>>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EditorProfile {
+    /// `clientInfo.name`, for `[clients]` severity mappings.
+    pub name: String,
+    /// Whether the client applies snippet syntax in code action edits.
+    pub snippet_edits: bool,
+    /// The UI locale, e.g. `de-CH`, offered by locale-dependent quick fixes.
+    pub locale: Option<String>,
+    /// Whether the client lets us register file watchers.
+    pub watch_files: bool,
+    /// Whether the client can create files in a workspace edit.
+    pub create_files: bool,
+    /// Whether the client opens URLs for `window/showDocument`.
+    pub show_document: bool,
+}
+
+impl Default for EditorProfile {
+    fn default() -> Self {
+        Self {
+            name: config::EDITOR_CLIENT.to_string(),
+            snippet_edits: false,
+            locale: None,
+            watch_files: false,
+            create_files: false,
+            show_document: false,
+        }
+    }
+}
+
+/// Response of the `wcag/status` request.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatus {
+    pub version: String,
+    pub config_path: Option<String>,
+    pub enabled_rules: usize,
+    pub total_rules: usize,
+    pub open_documents: usize,
+    /// Milliseconds since the Unix epoch.
+    pub last_workspace_scan: Option<u64>,
+}
+
+/// Response of the `wcag/scanWorkspace` request.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanSummary {
+    pub files: usize,
+    pub problems: usize,
 }
 
 impl WcagLspServer {
@@ -24,41 +112,416 @@ impl WcagLspServer {
             client,
             documents: Arc::new(RwLock::new(DocumentManager::new())),
             config: Arc::new(RwLock::new(Config::default())),
+            packages: Arc::new(RwLock::new(PackageConfigs::default())),
             rules: Arc::new(rules::all_rules()),
             debounce_versions: Arc::new(RwLock::new(HashMap::new())),
+            language_ids: Arc::new(RwLock::new(HashMap::new())),
+            workspace_root: Arc::new(RwLock::new(None)),
+            config_file: Arc::new(RwLock::new(None)),
+            watched_files: Arc::new(RwLock::new(Vec::new())),
+            last_workspace_scan: Arc::new(RwLock::new(None)),
+            editor: Arc::new(RwLock::new(EditorProfile::default())),
+            nav_index: Arc::new(RwLock::new(NavIndex::new())),
+            scores: Arc::new(RwLock::new(HashMap::new())),
+            reported_crashes: Arc::new(RwLock::new(HashSet::new())),
         }
     }
 
-    async fn diagnose(&self, uri: Uri, version: Option<i32>) {
+    /// Handler for the custom `wcag/status` request.
+    pub async fn status(&self) -> Result<ServerStatus> {
         let config = self.config.read().await;
+        let enabled_rules = self
+            .rules
+            .iter()
+            .filter(|rule| config.rule_severity(rule.as_ref()).is_some())
+            .count();
+        let config_path = config
+            .path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string());
+        drop(config);
 
-        // Check ignore patterns
-        if let Some(file_path) = uri.to_file_path() {
-            let path_str = file_path.to_string_lossy();
-            for pattern in &config.ignore_patterns {
-                if glob_match(pattern, &path_str) {
-                    drop(config);
-                    self.client.publish_diagnostics(uri, vec![], version).await;
-                    return;
+        let open_documents = self.documents.read().await.len();
+        let last_workspace_scan = self.last_workspace_scan.read().await.and_then(|t| {
+            t.duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_millis() as u64)
+        });
+
+        Ok(ServerStatus {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            config_path,
+            enabled_rules,
+            total_rules: self.rules.len(),
+            open_documents,
+            last_workspace_scan,
+        })
+    }
+
+    /// Handler for the custom `wcag/score` request: the accessibility score
+    /// of every file linted so far, that is the open documents and the files
+    /// of the last workspace scan.
+    pub async fn score(&self) -> Result<Score> {
+        let scores = self.scores.read().await;
+        Ok(scores.values().copied().collect::<Tally>().score())
+    }
+
+    /// Handler for the custom `wcag/scanWorkspace` request: lints every
+    /// supported file in the workspace that isn't open and publishes the
+    /// results. Linting runs on a worker pool off the async runtime, so
+    /// requests for open documents keep being served during the scan.
+    pub async fn scan_workspace(&self) -> Result<ScanSummary> {
+        let Some(root) = self.workspace_root.read().await.clone() else {
+            return Ok(ScanSummary::default());
+        };
+
+        // Snapshot everything the workers need, so they hold no locks.
+        let workspace_config = Arc::new(self.config.read().await.clone());
+        let open: HashSet<String> = self
+            .documents
+            .read()
+            .await
+            .versions()
+            .into_iter()
+            .map(|(uri, _)| uri)
+            .collect();
+        let packages = self.packages.read().await.clone();
+        // Walking the tree and reading package configs is file I/O.
+        let resolve = {
+            let workspace_config = workspace_config.clone();
+            let open = open.clone();
+            tokio::task::spawn_blocking(move || {
+                resolve_jobs(&root, &workspace_config, packages, &open)
+            })
+        };
+        let Ok((packages, jobs)) = resolve.await else {
+            return Ok(ScanSummary::default());
+        };
+        self.packages.write().await.merge(packages);
+        // The workers see the open documents' landmarks as of now and index
+        // the scanned files on their own copy; the shared index stays live.
+        let mut index = self.nav_index.read().await.clone();
+        index.retain(|uri| open.contains(uri));
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<(Uri, Vec<Diagnostic>, Tally)>(64);
+        let rules = self.rules.clone();
+        let client_name = self.editor.read().await.name.clone();
+        let worker = tokio::task::spawn_blocking(move || {
+            let cache = Cache::open();
+            let mut scanned = Vec::new();
+            if nav_index::is_enabled(jobs.iter().map(|(_, _, config, _)| config.as_ref())) {
+                scan::for_each_parallel(
+                    &jobs,
+                    scan::default_workers(),
+                    |(path, _, config, _)| nav_index::landmarks_in_file(path, config),
+                    |(_, uri, _, _), landmarks| {
+                        index.insert(uri.as_str(), landmarks.clone());
+                        scanned.push((uri.to_string(), landmarks));
+                    },
+                );
+            }
+            scan::for_each_parallel(
+                &jobs,
+                scan::default_workers(),
+                |(path, uri, config, package_key)| {
+                    let mut result =
+                        scan::lint_file(path, config, &rules, cache.as_ref(), package_key);
+                    if let Ok(Some((diagnostics, _))) = &mut result {
+                        diagnostics.extend(index.diagnostics_for(uri.as_str(), config));
+                        config.present(diagnostics, &client_name);
+                    }
+                    result
+                },
+                |(_, uri, _, _), result| {
+                    if let Ok(Some((diagnostics, elements))) = result {
+                        let tally = Tally::of(&diagnostics, elements, &rules);
+                        // Blocks while the publisher is behind.
+                        let _ = tx.blocking_send((uri.clone(), diagnostics, tally));
+                    }
+                },
+            );
+            scanned
+        });
+
+        let mut summary = ScanSummary::default();
+        while let Some((uri, diagnostics, tally)) = rx.recv().await {
+            // Opened while the scan ran: its live diagnostics win.
+            if self.documents.read().await.get(&uri.to_string()).is_some() {
+                continue;
+            }
+            self.scores.write().await.insert(uri.to_string(), tally);
+            summary.files += 1;
+            summary.problems += diagnostics.len();
+            self.client
+                .publish_diagnostics(uri, diagnostics, None)
+                .await;
+        }
+        if let Ok(scanned) = worker.await {
+            let docs = self.documents.read().await;
+            let mut index = self.nav_index.write().await;
+            // Files deleted since the last scan drop out; open documents are
+            // kept up to date by their own checks.
+            let known: HashSet<&str> = scanned.iter().map(|(uri, _)| uri.as_str()).collect();
+            index.retain(|uri| known.contains(uri) || docs.get(uri).is_some());
+            for (uri, landmarks) in scanned {
+                if docs.get(&uri).is_none() {
+                    index.insert(&uri, landmarks);
                 }
             }
         }
 
+        *self.last_workspace_scan.write().await = Some(SystemTime::now());
+        Ok(summary)
+    }
+
+    /// Re-reads the workspace config and forgets the package configs, then
+    /// relints every open document, e.g. after a config file changed.
+    pub async fn reload_config(&self) {
+        let config_file = self.config_file.read().await.clone();
+        let workspace_root = self.workspace_root.read().await.clone();
+        let config = match (config_file, workspace_root) {
+            (Some(path), _) => Config::from_file(&path),
+            (None, Some(root)) => Config::from_dir(&root),
+            (None, None) => Config::default(),
+        };
+        *self.config.write().await = config;
+        self.packages.write().await.clear();
+
+        let versions = self.documents.read().await.versions();
+        for (uri, version) in versions {
+            if let Ok(uri) = uri.parse::<Uri>() {
+                self.diagnose(uri, Some(version)).await;
+            }
+        }
+    }
+
+    /// Asks the client to tell us when a file the config depends on changes,
+    /// replacing the watchers registered before if they differ.
+    async fn watch_config_files(&self) {
+        if !self.editor.read().await.watch_files {
+            return;
+        }
+        let config_file = self.config_file.read().await.clone();
+        let patterns = watch_patterns(&*self.config.read().await, config_file.as_deref());
+        let mut watched = self.watched_files.write().await;
+        if *watched == patterns {
+            return;
+        }
+        if !watched.is_empty() {
+            let unregistration = Unregistration {
+                id: WATCHER_ID.to_string(),
+                method: "workspace/didChangeWatchedFiles".to_string(),
+            };
+            let _ = self
+                .client
+                .unregister_capability(vec![unregistration])
+                .await;
+        }
+        let options = DidChangeWatchedFilesRegistrationOptions {
+            watchers: patterns
+                .iter()
+                .map(|pattern| FileSystemWatcher {
+                    glob_pattern: GlobPattern::String(pattern.clone()),
+                    kind: None,
+                })
+                .collect(),
+        };
+        let registration = Registration {
+            id: WATCHER_ID.to_string(),
+            method: "workspace/didChangeWatchedFiles".to_string(),
+            register_options: serde_json::to_value(options).ok(),
+        };
+        if self
+            .client
+            .register_capability(vec![registration])
+            .await
+            .is_ok()
+        {
+            *watched = patterns;
+        } else {
+            watched.clear();
+        }
+    }
+
+    /// Loads the config of the package containing `uri`, if not done yet.
+    async fn load_package_config(&self, uri: &Uri) {
+        if let Some(path) = file_path(uri) {
+            self.packages.write().await.load(&path);
+        }
+    }
+
+    async fn diagnose(&self, uri: Uri, version: Option<i32>) {
+        self.load_package_config(&uri).await;
+        let workspace_config = self.config.read().await;
+        let packages = self.packages.read().await;
+        let config = package_config(&packages, &uri).unwrap_or(&workspace_config);
+
+        if is_ignored(config, &uri) {
+            drop(packages);
+            drop(workspace_config);
+            self.scores.write().await.remove(uri.as_str());
+            self.client.publish_diagnostics(uri, vec![], version).await;
+            return;
+        }
+
         let docs = self.documents.read().await;
         let uri_str = uri.to_string();
-        let diagnostics = if let Some(doc) = docs.get(&uri_str) {
-            engine::run_diagnostics(doc, &self.rules, &config)
+        let (mut diagnostics, crashes) = if let Some(doc) = docs.get(&uri_str) {
+            engine::run_diagnostics_checked(doc, &self.rules, config)
         } else {
-            vec![]
+            (vec![], vec![])
         };
+        if let Some(doc) = docs.get(&uri_str)
+            && nav_index::is_enabled([config])
+        {
+            let mut index = self.nav_index.write().await;
+            index.update(doc);
+            diagnostics.extend(index.diagnostics_for(&uri_str, config));
+        }
+        let elements = docs.get(&uri_str).map_or(0, score::document_elements);
         drop(docs);
-        drop(config);
+        config.present(&mut diagnostics, &self.editor.read().await.name);
+        drop(packages);
+        drop(workspace_config);
+        let tally = Tally::of(&diagnostics, elements, &self.rules);
+        self.scores.write().await.insert(uri_str, tally);
+        report_crashes(&self.client, &self.reported_crashes, crashes).await;
         self.client
             .publish_diagnostics(uri, diagnostics, version)
             .await;
     }
 }
 
+/// The id of the `workspace/didChangeWatchedFiles` registration.
+const WATCHER_ID: &str = "wcag-lsp/config-files";
+
+/// Glob patterns of the files besides the documents that lint results depend
+/// on: the config files, the manifests that make a directory a package, and
+/// the style sheets `color-contrast` reads.
+fn watch_patterns(config: &Config, config_file: Option<&Path>) -> Vec<String> {
+    let glob = |path: &Path| path.to_string_lossy().replace('\\', "/");
+    let mut patterns = match config_file {
+        Some(path) => vec![glob(path)],
+        None => [
+            "**/.wcag.toml",
+            "**/.wcag.json",
+            "**/package.json",
+            "**/Cargo.toml",
+        ]
+        .map(String::from)
+        .to_vec(),
+    };
+    if let Some(dir) = config.path.as_deref().and_then(Path::parent) {
+        patterns.extend(
+            config
+                .color_contrast
+                .stylesheets
+                .iter()
+                .map(|sheet| glob(&dir.join(sheet))),
+        );
+    }
+    patterns
+}
+
+/// Writes a crash report for each rule that panicked and tells the user, once
+/// per rule and URI; a repeat only goes to the log. The server itself keeps
+/// running; the rule just contributes no diagnostics.
+async fn report_crashes(
+    client: &Client,
+    reported: &RwLock<HashSet<(String, String)>>,
+    crashes: Vec<CrashReport>,
+) {
+    for report in crashes {
+        client
+            .log_message(MessageType::ERROR, report.to_log_entry())
+            .await;
+        let key = (report.rule_id.clone(), report.uri.clone());
+        if !reported.write().await.insert(key) {
+            continue;
+        }
+        let location = match crash::write_report(&report) {
+            Some(path) => format!("details written to {}", path.display()),
+            None => "no crash file could be written".to_string(),
+        };
+        client
+            .show_message(
+                MessageType::WARNING,
+                format!(
+                    "wcag-lsp: rule `{}` crashed on {} and was skipped ({location}). Please report this issue.",
+                    report.rule_id, report.uri
+                ),
+            )
+            .await;
+    }
+}
+
+/// The filesystem path of a `file:` URI. Untitled buffers and other virtual
+/// documents have none.
+fn file_path(uri: &Uri) -> Option<PathBuf> {
+    if !uri.scheme().as_str().eq_ignore_ascii_case("file") {
+        return None;
+    }
+    uri.to_file_path().map(|p| p.into_owned())
+}
+
+/// The config of the monorepo package containing `uri`, if it has its own.
+fn package_config<'a>(packages: &'a PackageConfigs, uri: &Uri) -> Option<&'a Config> {
+    packages.get(&file_path(uri)?)
+}
+
+/// Whether `uri` matches one of the configured ignore patterns. Only `file:`
+/// URIs have a workspace path to match against; untitled buffers and other
+/// virtual documents are never ignored.
+fn is_ignored(config: &Config, uri: &Uri) -> bool {
+    let Some(file_path) = file_path(uri) else {
+        return false;
+    };
+    let path_str = file_path.to_string_lossy();
+    config
+        .ignore_patterns
+        .iter()
+        .any(|pattern| glob_match(pattern, &path_str))
+}
+
+/// A workspace file to lint: its path, URI, config and package cache key.
+type ScanJob = (PathBuf, Uri, Arc<Config>, String);
+
+/// The workspace files a scan lints, that is neither open nor ignored, each
+/// with the config of its package. Loads the package configs into
+/// `packages`, which is returned for the caller to merge back.
+fn resolve_jobs(
+    root: &Path,
+    workspace_config: &Arc<Config>,
+    mut packages: PackageConfigs,
+    open: &HashSet<String>,
+) -> (PackageConfigs, Vec<ScanJob>) {
+    let mut package_configs: HashMap<Option<PathBuf>, Arc<Config>> = HashMap::new();
+    let mut jobs = Vec::new();
+    for path in scan::workspace_files(root, workspace_config) {
+        let Some(uri) = Uri::from_file_path(&path) else {
+            continue;
+        };
+        if open.contains(uri.as_str()) {
+            continue;
+        }
+        packages.load(&path);
+        let package_root = packages.root_of(&path).map(|p| p.to_path_buf());
+        let config = package_configs
+            .entry(package_root.clone())
+            .or_insert_with(|| match packages.get(&path) {
+                Some(config) => Arc::new(config.clone()),
+                None => workspace_config.clone(),
+            })
+            .clone();
+        if is_ignored(&config, &uri) {
+            continue;
+        }
+        let package_key = workspace::package_key(package_root.as_deref().unwrap_or(root));
+        jobs.push((path, uri, config, package_key));
+    }
+    (packages, jobs)
+}
+
 impl LanguageServer for WcagLspServer {
     async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
         // Check for custom config path from initializationOptions
@@ -73,12 +536,56 @@ impl LanguageServer for WcagLspServer {
         if let Some(config_path) = custom_config {
             let config = Config::from_file(&config_path);
             *self.config.write().await = config;
+            *self.config_file.write().await = Some(config_path);
         } else if let Some(folders) = &params.workspace_folders
             && let Some(folder) = folders.first()
             && let Some(path) = folder.uri.to_file_path()
         {
             let config = Config::from_dir(&path);
             *self.config.write().await = config;
+            *self.packages.write().await = PackageConfigs::new(Some(path.into_owned()));
+        }
+
+        let mut editor = self.editor.write().await;
+        if let Some(info) = &params.client_info {
+            editor.name = info.name.clone();
+        }
+        // Not part of the LSP spec yet; rust-analyzer's extension is the
+        // de facto way for clients to opt in.
+        editor.snippet_edits = params
+            .capabilities
+            .experimental
+            .as_ref()
+            .and_then(|e| e.get("snippetTextEdit"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        editor.locale = params.locale.clone();
+        editor.watch_files = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|w| w.did_change_watched_files.as_ref())
+            .and_then(|c| c.dynamic_registration)
+            .unwrap_or(false);
+        editor.create_files = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|w| w.workspace_edit.as_ref())
+            .and_then(|e| e.resource_operations.as_ref())
+            .is_some_and(|ops| ops.contains(&ResourceOperationKind::Create));
+        editor.show_document = params
+            .capabilities
+            .window
+            .as_ref()
+            .and_then(|w| w.show_document.as_ref())
+            .is_some_and(|s| s.support);
+        drop(editor);
+
+        if let Some(folders) = &params.workspace_folders
+            && let Some(folder) = folders.first()
+        {
+            *self.workspace_root.write().await = folder.uri.to_file_path().map(|p| p.into_owned());
         }
 
         Ok(InitializeResult {
@@ -86,6 +593,16 @@ impl LanguageServer for WcagLspServer {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
                     TextDocumentSyncKind::FULL,
                 )),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                        ..Default::default()
+                    },
+                )),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: vec![rule_actions::SHOW_DOCUMENTATION.to_string()],
+                    ..Default::default()
+                }),
                 ..Default::default()
             },
             server_info: Some(ServerInfo {
@@ -104,6 +621,21 @@ impl LanguageServer for WcagLspServer {
         self.client
             .log_message(MessageType::INFO, "wcag-lsp initialized")
             .await;
+
+        if let Some(notice) = changelog::pending_notice() {
+            self.client.show_message(MessageType::INFO, notice).await;
+        }
+        self.watch_config_files().await;
+    }
+
+    async fn did_change_watched_files(&self, _: DidChangeWatchedFilesParams) {
+        // Only files the config depends on are watched.
+        self.reload_config().await;
+        self.client
+            .log_message(MessageType::INFO, "wcag-lsp: config reloaded")
+            .await;
+        // The style sheets to watch may have changed with the config.
+        self.watch_config_files().await;
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
@@ -111,9 +643,22 @@ impl LanguageServer for WcagLspServer {
         let uri_str = uri.to_string();
         let text = params.text_document.text;
         let version = params.text_document.version;
+        let language_id = params.text_document.language_id;
+        self.language_ids
+            .write()
+            .await
+            .insert(uri_str.clone(), language_id.clone());
 
+        self.load_package_config(&uri).await;
+        let workspace_config = self.config.read().await;
+        let packages = self.packages.read().await;
+        let file_type = package_config(&packages, &uri)
+            .unwrap_or(&workspace_config)
+            .file_type_for(&uri_str, Some(&language_id), &text);
+        drop(packages);
+        drop(workspace_config);
         let mut docs = self.documents.write().await;
-        docs.open(uri_str, text, version);
+        docs.open_as(uri_str, text, version, file_type);
         drop(docs);
 
         self.diagnose(uri, Some(version)).await;
@@ -125,9 +670,25 @@ impl LanguageServer for WcagLspServer {
         if let Some(change) = params.content_changes.into_iter().last() {
             let uri_str = uri.to_string();
 
+            // Lock order matches `diagnose`: config, packages, documents.
+            self.load_package_config(&uri).await;
+            let language_id = self.language_ids.read().await.get(&uri_str).cloned();
+            let workspace_config = self.config.read().await;
+            let packages = self.packages.read().await;
             let mut docs = self.documents.write().await;
-            docs.update(&uri_str, change.text, version);
+            if docs.get(&uri_str).is_some() {
+                docs.update(&uri_str, change.text, version);
+            } else {
+                // Not tracked yet, e.g. an untitled buffer that was opened
+                // empty: retry detection now that there is content to sniff.
+                let file_type = package_config(&packages, &uri)
+                    .unwrap_or(&workspace_config)
+                    .file_type_for(&uri_str, language_id.as_deref(), &change.text);
+                docs.open_as(uri_str.clone(), change.text, version, file_type);
+            }
             drop(docs);
+            drop(packages);
+            drop(workspace_config);
 
             // Store current version for debounce
             {
@@ -139,8 +700,13 @@ impl LanguageServer for WcagLspServer {
             let debounce_versions = self.debounce_versions.clone();
             let documents = self.documents.clone();
             let config = self.config.clone();
+            let packages = self.packages.clone();
             let client = self.client.clone();
             let rules = self.rules.clone();
+            let nav_index = self.nav_index.clone();
+            let scores = self.scores.clone();
+            let reported_crashes = self.reported_crashes.clone();
+            let client_name = self.editor.read().await.name.clone();
 
             tokio::spawn(async move {
                 tokio::time::sleep(std::time::Duration::from_millis(150)).await;
@@ -155,28 +721,39 @@ impl LanguageServer for WcagLspServer {
                     return; // A newer version came in, skip
                 }
 
-                // Check ignore patterns
-                let cfg = config.read().await;
-                if let Some(file_path) = uri.to_file_path() {
-                    let path_str = file_path.to_string_lossy();
-                    for pattern in &cfg.ignore_patterns {
-                        if glob_match(pattern, &path_str) {
-                            drop(cfg);
-                            client.publish_diagnostics(uri, vec![], Some(version)).await;
-                            return;
-                        }
-                    }
+                let workspace_config = config.read().await;
+                let packages = packages.read().await;
+                let cfg = package_config(&packages, &uri).unwrap_or(&workspace_config);
+                if is_ignored(cfg, &uri) {
+                    drop(packages);
+                    drop(workspace_config);
+                    scores.write().await.remove(&uri_str);
+                    client.publish_diagnostics(uri, vec![], Some(version)).await;
+                    return;
                 }
 
                 // Run diagnostics
                 let docs = documents.read().await;
-                let diagnostics = if let Some(doc) = docs.get(&uri_str) {
-                    engine::run_diagnostics(doc, &rules, &cfg)
+                let (mut diagnostics, crashes) = if let Some(doc) = docs.get(&uri_str) {
+                    engine::run_diagnostics_checked(doc, &rules, cfg)
                 } else {
-                    vec![]
+                    (vec![], vec![])
                 };
+                if let Some(doc) = docs.get(&uri_str)
+                    && nav_index::is_enabled([cfg])
+                {
+                    let mut index = nav_index.write().await;
+                    index.update(doc);
+                    diagnostics.extend(index.diagnostics_for(&uri_str, cfg));
+                }
+                let elements = docs.get(&uri_str).map_or(0, score::document_elements);
                 drop(docs);
-                drop(cfg);
+                cfg.present(&mut diagnostics, &client_name);
+                drop(packages);
+                drop(workspace_config);
+                let tally = Tally::of(&diagnostics, elements, &rules);
+                scores.write().await.insert(uri_str, tally);
+                report_crashes(&client, &reported_crashes, crashes).await;
                 client
                     .publish_diagnostics(uri, diagnostics, Some(version))
                     .await;
@@ -184,13 +761,353 @@ impl LanguageServer for WcagLspServer {
         }
     }
 
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = &params.text_document.uri;
+        self.load_package_config(uri).await;
+        let workspace_config = self.config.read().await;
+        let packages = self.packages.read().await;
+        let config = package_config(&packages, uri).unwrap_or(&workspace_config);
+        let editor = self.editor.read().await;
+        let options = quickfix::FixOptions {
+            snippets: editor.snippet_edits,
+            locales: quickfix::candidate_locales(
+                config.fix_locale.as_deref(),
+                editor.locale.as_deref(),
+            ),
+        };
+        let mut actions = quickfix::code_actions(uri, &params.context.diagnostics, &options);
+        let docs = self.documents.read().await;
+        let workspace_root = self.workspace_root.read().await;
+        let cx = RuleActionContext {
+            document: docs.get(uri.as_str()),
+            config,
+            workspace_root: workspace_root.as_deref(),
+            create_files: editor.create_files,
+            show_document: editor.show_document,
+        };
+        actions.extend(rule_actions::code_actions(
+            uri,
+            &params.context.diagnostics,
+            &cx,
+        ));
+        Ok((!actions.is_empty()).then_some(actions))
+    }
+
+    async fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<LSPAny>> {
+        if params.command == rule_actions::SHOW_DOCUMENTATION
+            && let Some(uri) = params
+                .arguments
+                .first()
+                .and_then(|a| a.as_str())
+                .and_then(|a| a.parse::<Uri>().ok())
+        {
+            let params = ShowDocumentParams {
+                uri,
+                external: Some(true),
+                take_focus: None,
+                selection: None,
+            };
+            let _ = self.client.show_document(params).await;
+        }
+        Ok(None)
+    }
+
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri_str = params.text_document.uri.to_string();
         let mut docs = self.documents.write().await;
-        docs.close(&params.text_document.uri.to_string());
+        docs.close(&uri_str);
         drop(docs);
+        self.language_ids.write().await.remove(&uri_str);
 
         self.client
             .publish_diagnostics(params.text_document.uri, vec![], None)
             .await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_ignored_matches_file_uri() {
+        let config = Config::parse("[ignore]\npatterns = [\"**/dist/**\"]\n");
+        let uri: Uri = "file:///app/dist/index.html".parse().unwrap();
+        assert!(is_ignored(&config, &uri));
+        let uri: Uri = "file:///app/src/index.html".parse().unwrap();
+        assert!(!is_ignored(&config, &uri));
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_rules_and_documents() {
+        let (service, _socket) = tower_lsp_server::LspService::new(WcagLspServer::new);
+        let server = service.inner();
+        *server.config.write().await = Config::parse("[rules]\nimg-alt = \"off\"\n");
+        server
+            .documents
+            .write()
+            .await
+            .open("file:///a.html".to_string(), "<p></p>".to_string(), 1);
+
+        let status = server.status().await.unwrap();
+        assert_eq!(status.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(status.total_rules, rules::all_rules().len());
+        let opt_in = server.rules.iter().filter(|r| r.opt_in()).count();
+        assert_eq!(status.enabled_rules, status.total_rules - 1 - opt_in);
+        assert_eq!(status.open_documents, 1);
+        assert_eq!(status.config_path, None);
+        assert_eq!(status.last_workspace_scan, None);
+    }
+
+    #[tokio::test]
+    async fn test_scan_workspace_skips_open_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.html"), r#"<img src="x.png">"#).unwrap();
+        std::fs::write(dir.path().join("b.html"), r#"<img src="x.png">"#).unwrap();
+        std::fs::write(dir.path().join(".wcag.toml"), "").unwrap();
+
+        let (service, _socket) = tower_lsp_server::LspService::new(WcagLspServer::new);
+        let server = service.inner();
+        *server.workspace_root.write().await = Some(dir.path().to_path_buf());
+        let open = Uri::from_file_path(dir.path().join("b.html")).unwrap();
+        server
+            .documents
+            .write()
+            .await
+            .open(open.to_string(), "<p></p>".to_string(), 1);
+
+        let summary = server.scan_workspace().await.unwrap();
+        assert_eq!(summary.files, 1);
+        assert!(summary.problems >= 1);
+        assert!(server.last_workspace_scan.read().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_score_covers_scanned_and_open_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.html"), r#"<img src="x.png">"#).unwrap();
+        std::fs::write(dir.path().join(".wcag.toml"), "").unwrap();
+
+        let (service, _socket) = tower_lsp_server::LspService::new(WcagLspServer::new);
+        let server = service.inner();
+        assert_eq!(server.score().await.unwrap().score, 100.0);
+
+        *server.workspace_root.write().await = Some(dir.path().to_path_buf());
+        server.scan_workspace().await.unwrap();
+        let scanned = server.score().await.unwrap();
+        assert_eq!(scanned.files, 1);
+        assert!(scanned.score < 100.0);
+
+        let open = Uri::from_file_path(dir.path().join("b.html")).unwrap();
+        server.documents.write().await.open(
+            open.to_string(),
+            r#"<html lang="en"><head><title>T</title></head><body><main><p>Hi</p></main></body></html>"#
+                .to_string(),
+            1,
+        );
+        server.diagnose(open, Some(1)).await;
+        let both = server.score().await.unwrap();
+        assert_eq!(both.files, 2);
+        assert_eq!(both.elements, scanned.elements + 6);
+        assert_eq!(both.weighted_problems, scanned.weighted_problems);
+    }
+
+    #[tokio::test]
+    async fn test_scan_workspace_indexes_navigation() {
+        let dir = tempfile::tempdir().unwrap();
+        let nav = |label: &str| {
+            format!(
+                r#"<nav aria-label="{label}"><a href="/">Home</a><a href="/shop">Shop</a></nav>"#
+            )
+        };
+        std::fs::write(dir.path().join("a.html"), nav("Main")).unwrap();
+        std::fs::write(dir.path().join("b.html"), nav("Menu")).unwrap();
+        std::fs::write(dir.path().join(".wcag.toml"), "").unwrap();
+
+        let (service, _socket) = tower_lsp_server::LspService::new(WcagLspServer::new);
+        let server = service.inner();
+        *server.workspace_root.write().await = Some(dir.path().to_path_buf());
+        server.scan_workspace().await.unwrap();
+        assert!(server.nav_index.read().await.is_empty(), "rule is opt-in");
+
+        *server.config.write().await =
+            Config::parse("[rules]\nconsistent-nav-label = \"warning\"\n");
+        server.scan_workspace().await.unwrap();
+        let index = server.nav_index.read().await;
+        assert_eq!(index.len(), 2);
+        let b = Uri::from_file_path(dir.path().join("b.html")).unwrap();
+        let config = server.config.read().await;
+        assert_eq!(index.diagnostics_for(b.as_str(), &config).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_scan_workspace_keeps_open_documents_indexed() {
+        let dir = tempfile::tempdir().unwrap();
+        let nav = r#"<nav aria-label="Main"><a href="/">Home</a><a href="/shop">Shop</a></nav>"#;
+        std::fs::write(dir.path().join("a.html"), nav).unwrap();
+        std::fs::write(
+            dir.path().join(".wcag.toml"),
+            "[rules]\nconsistent-nav-label = \"warning\"\n",
+        )
+        .unwrap();
+
+        let (service, _socket) = tower_lsp_server::LspService::new(WcagLspServer::new);
+        let server = service.inner();
+        *server.workspace_root.write().await = Some(dir.path().to_path_buf());
+        *server.config.write().await = Config::from_dir(dir.path());
+        let open = Uri::from_file_path(dir.path().join("b.html")).unwrap();
+        let mut docs = server.documents.write().await;
+        docs.open(open.to_string(), nav.to_string(), 1);
+        server
+            .nav_index
+            .write()
+            .await
+            .update(docs.get(open.as_str()).unwrap());
+        drop(docs);
+
+        server.scan_workspace().await.unwrap();
+        let index = server.nav_index.read().await;
+        assert_eq!(
+            index.len(),
+            2,
+            "the open document's entry survives the scan"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_initialize_records_editor_profile() {
+        let (service, _socket) = tower_lsp_server::LspService::new(WcagLspServer::new);
+        let server = service.inner();
+        assert_eq!(server.editor.read().await.name, config::EDITOR_CLIENT);
+
+        let params = InitializeParams {
+            client_info: Some(ClientInfo {
+                name: "Neovim".to_string(),
+                version: None,
+            }),
+            locale: Some("de-ch".to_string()),
+            capabilities: ClientCapabilities {
+                experimental: Some(serde_json::json!({ "snippetTextEdit": true })),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        server.initialize(params).await.unwrap();
+        let editor = server.editor.read().await;
+        assert_eq!(editor.name, "Neovim");
+        assert_eq!(editor.locale.as_deref(), Some("de-ch"));
+        assert!(editor.snippet_edits);
+    }
+
+    #[test]
+    fn test_watch_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".wcag.toml"),
+            "[color-contrast]\nstylesheets = [\"theme.css\"]\n",
+        )
+        .unwrap();
+        let config = Config::from_dir(dir.path());
+        let patterns = watch_patterns(&config, None);
+        assert!(patterns.contains(&"**/.wcag.toml".to_string()));
+        assert!(patterns.contains(&"**/package.json".to_string()));
+        assert!(patterns.last().unwrap().ends_with("/theme.css"));
+
+        let file = dir.path().join(".wcag.toml");
+        let patterns = watch_patterns(&Config::default(), Some(&file));
+        assert_eq!(patterns.len(), 1);
+        assert!(patterns[0].ends_with("/.wcag.toml"));
+    }
+
+    #[tokio::test]
+    async fn test_reload_config_picks_up_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".wcag.toml"), "").unwrap();
+
+        let (service, _socket) = tower_lsp_server::LspService::new(WcagLspServer::new);
+        let server = service.inner();
+        let folder = WorkspaceFolder {
+            uri: Uri::from_file_path(dir.path()).unwrap(),
+            name: "app".to_string(),
+        };
+        let params = InitializeParams {
+            workspace_folders: Some(vec![folder]),
+            ..Default::default()
+        };
+        server.initialize(params).await.unwrap();
+        let open = Uri::from_file_path(dir.path().join("a.html")).unwrap();
+        server.documents.write().await.open(
+            open.to_string(),
+            r#"<img src="x.png">"#.to_string(),
+            1,
+        );
+        server.diagnose(open.clone(), Some(1)).await;
+        let before = server.score().await.unwrap().weighted_problems;
+        assert!(before > 0.0);
+
+        std::fs::write(
+            dir.path().join(".wcag.toml"),
+            "[severity]\nA = \"off\"\nAA = \"off\"\nAAA = \"off\"\n",
+        )
+        .unwrap();
+        server
+            .did_change_watched_files(DidChangeWatchedFilesParams { changes: vec![] })
+            .await;
+        assert!(server.config.read().await.path.is_some());
+        assert_eq!(server.score().await.unwrap().weighted_problems, 0.0);
+    }
+
+    #[test]
+    fn test_is_ignored_skips_untitled_uri() {
+        let config = Config::parse("[ignore]\npatterns = [\"**\"]\n");
+        let uri: Uri = "untitled:Untitled-1".parse().unwrap();
+        assert!(!is_ignored(&config, &uri));
+    }
+
+    #[tokio::test]
+    async fn test_redetection_keeps_language_id() {
+        let (service, _socket) = tower_lsp_server::LspService::new(WcagLspServer::new);
+        let server = service.inner();
+        let uri: Uri = "untitled:Untitled-1".parse().unwrap();
+        server
+            .did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "typescriptreact".to_string(),
+                    version: 1,
+                    text: String::new(),
+                },
+            })
+            .await;
+        // Lose track of it, as when the first detection found nothing.
+        server.documents.write().await.close(uri.as_str());
+
+        // Without the languageId, this would sniff as HTML.
+        server
+            .did_change(DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier {
+                    uri: uri.clone(),
+                    version: 2,
+                },
+                content_changes: vec![TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: "<html><img src=\"x.png\" /></html>".to_string(),
+                }],
+            })
+            .await;
+        let docs = server.documents.read().await;
+        assert_eq!(
+            docs.get(uri.as_str()).unwrap().file_type,
+            crate::parser::FileType::Tsx
+        );
+        drop(docs);
+
+        server
+            .did_close(DidCloseTextDocumentParams {
+                text_document: TextDocumentIdentifier { uri },
+            })
+            .await;
+        assert!(server.language_ids.read().await.is_empty());
+    }
+}
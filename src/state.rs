@@ -0,0 +1,56 @@
+//! Small per-user state files (last seen version, …).
+//!
+//! Lives in `$WCAG_LSP_STATE_DIR`, `$XDG_STATE_HOME/wcag-lsp`,
+//! `%LOCALAPPDATA%\wcag-lsp` or `~/.local/state/wcag-lsp`, in that order.
+
+use std::path::{Path, PathBuf};
+
+pub fn state_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("WCAG_LSP_STATE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_STATE_HOME") {
+        return Some(PathBuf::from(dir).join("wcag-lsp"));
+    }
+    if cfg!(target_os = "windows")
+        && let Some(dir) = std::env::var_os("LOCALAPPDATA")
+    {
+        return Some(PathBuf::from(dir).join("wcag-lsp"));
+    }
+    std::env::var_os("HOME").map(|home| {
+        PathBuf::from(home)
+            .join(".local")
+            .join("state")
+            .join("wcag-lsp")
+    })
+}
+
+pub fn read(dir: &Path, name: &str) -> Option<String> {
+    std::fs::read_to_string(dir.join(name))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+pub fn write(dir: &Path, name: &str, contents: &str) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join(name), contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_then_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("nested");
+        write(&nested, "last-version", "0.5.14\n").unwrap();
+        assert_eq!(read(&nested, "last-version").as_deref(), Some("0.5.14"));
+    }
+
+    #[test]
+    fn test_read_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read(dir.path(), "nope").is_none());
+    }
+}
@@ -0,0 +1,371 @@
+//! Storybook story files (`Button.stories.tsx`).
+//!
+//! A story file renders components in isolation, one story at a time, so the
+//! engine skips page-level rules for it. Stories also tend to pass every prop
+//! through `args`: `render: (args) => <img {...args} />` with
+//! `args: { alt: "Logo" }` does render an `alt`. [`drop_resolved_by_args`]
+//! resolves such spreads for Component Story Format 3 stories whose args are
+//! object literals, merged with the default export's args.
+
+use crate::jsx_spread;
+use tower_lsp_server::ls_types::{Diagnostic, NumberOrString, Position};
+use tree_sitter::Node;
+
+const STORY_SUFFIXES: &[&str] = &[
+    ".stories.tsx",
+    ".stories.jsx",
+    ".stories.ts",
+    ".stories.js",
+    ".story.tsx",
+    ".story.jsx",
+];
+
+/// Whether `uri` names a Storybook story file.
+pub fn is_story_file(uri: &str) -> bool {
+    let lower = uri.to_ascii_lowercase();
+    STORY_SUFFIXES.iter().any(|suffix| lower.ends_with(suffix))
+}
+
+/// An element in a story's render function that spreads the story's args.
+struct ArgsSpread {
+    /// From the start of the element to the end of its opening tag.
+    start: Position,
+    end: Position,
+    /// Props the args statically provide.
+    props: Vec<String>,
+}
+
+/// Removes the diagnostics of elements whose missing prop the story's args
+/// provide through a `{...args}` spread.
+pub fn drop_resolved_by_args(root: &Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let spreads = args_spreads(root, source);
+    if spreads.is_empty() {
+        return;
+    }
+    diagnostics.retain(|diagnostic| {
+        let Some(NumberOrString::String(code)) = &diagnostic.code else {
+            return true;
+        };
+        let Some((_, props)) = jsx_spread::PROP_RULES.iter().find(|(id, _)| id == code) else {
+            return true;
+        };
+        let at = diagnostic.range.start;
+        !spreads.iter().any(|spread| {
+            position_key(spread.start) <= position_key(at)
+                && position_key(at) <= position_key(spread.end)
+                && props.iter().any(|p| spread.props.iter().any(|a| a == p))
+        })
+    });
+}
+
+/// Where the elements spreading a story's args start. Their args are known,
+/// so [`drop_resolved_by_args`] settles their diagnostics.
+pub fn args_spread_starts(root: &Node, source: &str) -> Vec<Position> {
+    args_spreads(root, source)
+        .into_iter()
+        .map(|spread| spread.start)
+        .collect()
+}
+
+fn position_key(position: Position) -> (u32, u32) {
+    (position.line, position.character)
+}
+
+fn args_spreads(root: &Node, source: &str) -> Vec<ArgsSpread> {
+    let meta = default_export(root, source);
+    let meta_args = meta.map(|m| args_of(&m, source)).unwrap_or_default();
+    let meta_render = meta.and_then(|m| render_of(&m, source));
+
+    let mut spreads = Vec::new();
+    let mut cursor = root.walk();
+    for export in root.children(&mut cursor) {
+        let Some(declaration) = export
+            .child_by_field_name("declaration")
+            .filter(|_| export.kind() == "export_statement")
+        else {
+            continue;
+        };
+        let mut decl_cursor = declaration.walk();
+        for declarator in declaration.children(&mut decl_cursor) {
+            let Some(story) = declarator
+                .child_by_field_name("value")
+                .map(unwrap_type_expression)
+                .filter(|v| declarator.kind() == "variable_declarator" && v.kind() == "object")
+            else {
+                continue;
+            };
+            let Some(render) = render_of(&story, source).or(meta_render) else {
+                continue;
+            };
+            let mut props = meta_args.clone();
+            props.extend(args_of(&story, source));
+            collect_spreads(&render, source, &props, &mut spreads);
+        }
+    }
+    spreads
+}
+
+/// The object literal of `export default { … }`, or of the variable that
+/// `export default meta` names.
+fn default_export<'a>(root: &Node<'a>, source: &str) -> Option<Node<'a>> {
+    let mut cursor = root.walk();
+    let value = root
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "export_statement")
+        .find_map(|c| c.child_by_field_name("value"))
+        .map(unwrap_type_expression)?;
+    if value.kind() == "object" {
+        return Some(value);
+    }
+    if value.kind() != "identifier" {
+        return None;
+    }
+    let name = &source[value.byte_range()];
+    let mut cursor = root.walk();
+    for statement in root.children(&mut cursor) {
+        if statement.kind() != "lexical_declaration" {
+            continue;
+        }
+        let mut decl_cursor = statement.walk();
+        for declarator in statement.children(&mut decl_cursor) {
+            let is_meta = declarator
+                .child_by_field_name("name")
+                .is_some_and(|n| &source[n.byte_range()] == name);
+            if is_meta && let Some(value) = declarator.child_by_field_name("value") {
+                return Some(unwrap_type_expression(value)).filter(|v| v.kind() == "object");
+            }
+        }
+    }
+    None
+}
+
+/// Keys of the `args` object literal of a meta or story object, leaving out
+/// those set to `undefined`, `null` or an empty string (except `alt: ""`,
+/// which marks an image decorative).
+fn args_of(object: &Node, source: &str) -> Vec<String> {
+    let Some(args) = property(object, source, "args").map(unwrap_type_expression) else {
+        return Vec::new();
+    };
+    if args.kind() != "object" {
+        return Vec::new();
+    }
+    let mut keys = Vec::new();
+    let mut cursor = args.walk();
+    for entry in args.named_children(&mut cursor) {
+        match entry.kind() {
+            "shorthand_property_identifier" => keys.push(source[entry.byte_range()].to_string()),
+            "pair" => {
+                let (Some(key), Some(value)) = (
+                    entry.child_by_field_name("key"),
+                    entry.child_by_field_name("value"),
+                ) else {
+                    continue;
+                };
+                let key = source[key.byte_range()].trim_matches(['"', '\'']);
+                let value = &source[value.byte_range()];
+                let empty = matches!(value, "undefined" | "null")
+                    || (key != "alt" && matches!(value, "\"\"" | "''" | "``"));
+                if !empty {
+                    keys.push(key.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    keys
+}
+
+/// The `render` function of a meta or story object, as a property or a
+/// method.
+fn render_of<'a>(object: &Node<'a>, source: &str) -> Option<Node<'a>> {
+    if let Some(render) = property(object, source, "render") {
+        return matches!(
+            render.kind(),
+            "arrow_function" | "function_expression" | "function"
+        )
+        .then_some(render);
+    }
+    let mut cursor = object.walk();
+    object.named_children(&mut cursor).find(|method| {
+        method.kind() == "method_definition"
+            && method
+                .child_by_field_name("name")
+                .is_some_and(|n| &source[n.byte_range()] == "render")
+    })
+}
+
+/// Elements in `render` that spread its first parameter.
+fn collect_spreads(render: &Node, source: &str, props: &[String], spreads: &mut Vec<ArgsSpread>) {
+    let Some(param) = first_param(render, source) else {
+        return;
+    };
+    let Some(body) = render.child_by_field_name("body") else {
+        return;
+    };
+    visit_jsx(&body, source, param, props, spreads);
+}
+
+fn first_param<'s>(function: &Node, source: &'s str) -> Option<&'s str> {
+    // `args => …` has a single `parameter`; everything else a list.
+    if let Some(param) = function.child_by_field_name("parameter") {
+        return Some(&source[param.byte_range()]);
+    }
+    let params = function.child_by_field_name("parameters")?;
+    let mut cursor = params.walk();
+    let first = params.named_children(&mut cursor).next()?;
+    let pattern = match first.kind() {
+        "required_parameter" | "optional_parameter" => first.child_by_field_name("pattern")?,
+        _ => first,
+    };
+    (pattern.kind() == "identifier").then(|| &source[pattern.byte_range()])
+}
+
+fn visit_jsx(
+    node: &Node,
+    source: &str,
+    param: &str,
+    props: &[String],
+    spreads: &mut Vec<ArgsSpread>,
+) {
+    let tag = match node.kind() {
+        "jsx_self_closing_element" => Some(*node),
+        "jsx_element" => node.child(0).filter(|c| c.kind() == "jsx_opening_element"),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        let mut cursor = tag.walk();
+        let spreads_args = tag.children(&mut cursor).any(|attr| {
+            let mut attr_cursor = attr.walk();
+            attr.kind() == "jsx_expression"
+                && attr.named_children(&mut attr_cursor).any(|spread| {
+                    spread.kind() == "spread_element"
+                        && spread
+                            .named_child(0)
+                            .is_some_and(|arg| &source[arg.byte_range()] == param)
+                })
+        });
+        if spreads_args {
+            let start = node.start_position();
+            let end = tag.end_position();
+            spreads.push(ArgsSpread {
+                start: Position::new(start.row as u32, start.column as u32),
+                end: Position::new(end.row as u32, end.column as u32),
+                props: props.to_vec(),
+            });
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_jsx(&child, source, param, props, spreads);
+    }
+}
+
+/// The value of property `key` in an object literal.
+fn property<'a>(object: &Node<'a>, source: &str, key: &str) -> Option<Node<'a>> {
+    let mut cursor = object.walk();
+    object.named_children(&mut cursor).find_map(|pair| {
+        let name = pair.child_by_field_name("key")?;
+        let name = source[name.byte_range()].trim_matches(['"', '\'']);
+        (pair.kind() == "pair" && name == key)
+            .then(|| pair.child_by_field_name("value"))
+            .flatten()
+    })
+}
+
+/// `{ … } satisfies Meta<…>` and `{ … } as Story` → `{ … }`.
+fn unwrap_type_expression(node: Node) -> Node {
+    match node.kind() {
+        "satisfies_expression" | "as_expression" => node.child(0).unwrap_or(node),
+        _ => node,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{self, FileType};
+    use crate::rules;
+
+    fn lint(source: &str) -> Vec<Diagnostic> {
+        let mut parser = parser::create_parser(FileType::Tsx).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let root = tree.root_node();
+        let mut diagnostics: Vec<Diagnostic> = rules::all_rules()
+            .iter()
+            .flat_map(|rule| rule.check(&root, source, FileType::Tsx))
+            .collect();
+        drop_resolved_by_args(&root, source, &mut diagnostics);
+        diagnostics
+    }
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<String> {
+        diagnostics
+            .iter()
+            .filter_map(|d| match &d.code {
+                Some(NumberOrString::String(code)) => Some(code.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_is_story_file() {
+        assert!(is_story_file("file:///src/Button.stories.tsx"));
+        assert!(is_story_file("file:///src/Button.story.jsx"));
+        assert!(!is_story_file("file:///src/Button.tsx"));
+        assert!(!is_story_file("file:///src/stories.tsx"));
+    }
+
+    #[test]
+    fn test_story_args_provide_prop() {
+        let diags = lint(
+            r#"const meta = { title: "Logo" } satisfies Meta;
+export default meta;
+export const Primary: Story = {
+  args: { alt: "Logo" },
+  render: (args) => <img src="logo.png" {...args} />,
+};"#,
+        );
+        assert_eq!(codes(&diags), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_meta_args_and_render_apply_to_every_story() {
+        let diags = lint(
+            r#"export default {
+  args: { children: "Save" },
+  render(props) { return <button type="button" {...props} />; },
+};
+export const Primary = {};
+export const Empty = { args: { children: "" } };"#,
+        );
+        assert_eq!(codes(&diags), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_missing_arg_still_reported() {
+        let diags = lint(
+            r#"export const Primary = {
+  args: { src: "logo.png" },
+  render: (args) => <img {...args} />,
+};
+export const Other = {
+  args: { alt: "Logo" },
+  render: (args) => <img src="x.png" {...other} />,
+};"#,
+        );
+        assert_eq!(codes(&diags), ["img-alt", "img-alt"]);
+    }
+
+    #[test]
+    fn test_empty_arg_does_not_count() {
+        let diags = lint(
+            r#"export const Primary = {
+  args: { "aria-label": "", title: undefined },
+  render: (args) => <button {...args} />,
+};"#,
+        );
+        assert_eq!(codes(&diags), ["button-name"]);
+    }
+}
@@ -0,0 +1,161 @@
+//! HTML in the tagged template literals of JS/TS files (`` html`…` ``).
+//!
+//! Lit components and htm render their markup from `` html`…` `` and
+//! `` svg`…` `` templates rather than JSX. [`parse`] blanks everything of the
+//! file but the content of those templates, keeping every byte where it is,
+//! and parses the rest with the HTML grammar. The engine runs the rules over
+//! that tree as well, and in plain JS/TS files over that tree only, so their
+//! diagnostics land at the template's position in the file.
+//!
+//! A `${…}` substitution becomes `{{…}}`, which the rules already read as a
+//! value filled in at runtime: `alt=${alt}` is bound, and
+//! `<button>${label}</button>` has content. A substitution that holds a
+//! nested template (`` ${items.map((i) => html`<li>${i}</li>`)} ``) is blanked
+//! around it instead, so the nested markup sits in the outer one.
+
+use crate::parser::FileType;
+use crate::scan;
+use tree_sitter::{Node, Tree};
+
+/// Tags whose template literal is markup: Lit's `html` and `svg`, and the
+/// `html` htm is conventionally bound to.
+const TEMPLATE_TAGS: &[&str] = &["html", "svg"];
+
+/// The templates of a JS/TS file: `source` is the file with everything but
+/// their markup blanked, `tree` its parse.
+#[derive(Debug)]
+pub struct TaggedTemplates {
+    pub source: String,
+    pub tree: Tree,
+}
+
+/// Whether `source` may have tagged templates, a cheap check that lets a
+/// scan skip parsing the many plain JS/TS files without any.
+pub fn may_have_templates(source: &str) -> bool {
+    source.contains('`') && TEMPLATE_TAGS.iter().any(|tag| source.contains(tag))
+}
+
+/// The templates of a file of `file_type` whose parse is `tree`, or `None`
+/// if it isn't JS/TS or has none.
+pub fn parse(file_type: FileType, tree: &Tree, source: &str) -> Option<TaggedTemplates> {
+    if !file_type.is_jsx_like() && !file_type.is_script() {
+        return None;
+    }
+    let markup = template_markup(&tree.root_node(), source)?;
+    let tree = scan::parse(FileType::HtmlTemplate, &markup)?;
+    Some(TaggedTemplates {
+        source: markup,
+        tree,
+    })
+}
+
+/// `source` with everything outside its tagged templates blanked (line
+/// breaks are kept) and their substitutions masked, or `None` if it has no
+/// tagged templates.
+pub fn template_markup(root: &Node, source: &str) -> Option<String> {
+    let mut templates = Vec::new();
+    collect_templates(root, source, &mut templates);
+    if templates.is_empty() {
+        return None;
+    }
+
+    let bytes = source.as_bytes();
+    let mut out: Vec<u8> = bytes
+        .iter()
+        .map(|&b| if b == b'\n' || b == b'\r' { b } else { b' ' })
+        .collect();
+    // Outer templates come first, so a nested one is written over the
+    // blanked substitution it's in.
+    for template in &templates {
+        let content = template.start_byte() + 1..template.end_byte().saturating_sub(1);
+        out[content.clone()].copy_from_slice(&bytes[content]);
+
+        let mut cursor = template.walk();
+        for substitution in template.named_children(&mut cursor) {
+            if substitution.kind() != "template_substitution" {
+                continue;
+            }
+            let range = substitution.byte_range();
+            let nested = templates
+                .iter()
+                .any(|t| t.start_byte() >= range.start && t.end_byte() <= range.end);
+            for (i, b) in out[range.clone()].iter_mut().enumerate() {
+                *b = if bytes[range.start + i] == b'\n' || bytes[range.start + i] == b'\r' {
+                    bytes[range.start + i]
+                } else if nested {
+                    b' '
+                } else if i < 2 {
+                    b'{'
+                } else if i >= range.len() - 2 {
+                    b'}'
+                } else {
+                    // No spaces: an unquoted `alt=${alt}` must stay one value.
+                    b'_'
+                };
+            }
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+/// The `template_string` of every `` html`…` `` and `` svg`…` ``, in
+/// document order.
+fn collect_templates<'a>(node: &Node<'a>, source: &str, templates: &mut Vec<Node<'a>>) {
+    if node.kind() == "call_expression"
+        && let Some(arguments) = node.child_by_field_name("arguments")
+        && arguments.kind() == "template_string"
+        && node
+            .child_by_field_name("function")
+            .is_some_and(|f| TEMPLATE_TAGS.contains(&&source[f.byte_range()]))
+    {
+        templates.push(arguments);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_templates(&child, source, templates);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn markup(source: &str) -> Option<String> {
+        let tree = scan::parse(FileType::Tsx, source).unwrap();
+        template_markup(&tree.root_node(), source)
+    }
+
+    #[test]
+    fn test_template_content_kept_in_place() {
+        let src = "const t = html`<img src=\"a.png\">`;\nconst u = `<img>`;";
+        assert_eq!(
+            markup(src).unwrap(),
+            "               <img src=\"a.png\">  \n                  "
+        );
+    }
+
+    #[test]
+    fn test_substitutions_masked() {
+        let src = "html`<img alt=${alt}><p>${ name }</p>`";
+        assert_eq!(
+            markup(src).unwrap(),
+            "     <img alt={{__}}><p>{{_____}}</p> "
+        );
+    }
+
+    #[test]
+    fn test_nested_templates_blanked_around() {
+        let src = "html`<ul>${items.map((i) => html`<li>${i}</li>`)}</ul>`";
+        assert_eq!(
+            markup(src).unwrap(),
+            "     <ul>                        <li>{{}}</li>   </ul> "
+        );
+    }
+
+    #[test]
+    fn test_untagged_and_other_tags_ignored() {
+        assert!(markup("const s = css`a { color: red }`; const t = `<img>`;").is_none());
+        assert!(markup("const a = <img />;").is_none());
+    }
+}
@@ -14,6 +14,10 @@ pub enum UpdateError {
     Io(std::io::Error),
     Extract(String),
     Replace(String),
+    NoBackup(std::path::PathBuf),
+    SizeMismatch { expected: u64, actual: u64 },
+    TooLarge(u64),
+    ManagedInstall(PackageManager),
     UnsupportedPlatform,
 }
 
@@ -27,6 +31,19 @@ impl std::fmt::Display for UpdateError {
             Self::Io(e) => write!(f, "IO error: {e}"),
             Self::Extract(msg) => write!(f, "extract error: {msg}"),
             Self::Replace(msg) => write!(f, "replace error: {msg}"),
+            Self::NoBackup(path) => write!(f, "no previous binary found at {}", path.display()),
+            Self::SizeMismatch { expected, actual } => write!(
+                f,
+                "download size mismatch: expected {expected} bytes, got {actual}"
+            ),
+            Self::TooLarge(size) => write!(f, "asset is implausibly large ({size} bytes)"),
+            Self::ManagedInstall(pm) => write!(
+                f,
+                "wcag-lsp was installed with {}; update it with `{}` instead \
+                 (or pass --force to replace it anyway)",
+                pm.name(),
+                pm.update_command()
+            ),
             Self::UnsupportedPlatform => write!(f, "unsupported platform"),
         }
     }
@@ -67,6 +84,35 @@ pub struct GitHubRelease {
 pub struct GitHubAsset {
     pub name: String,
     pub browser_download_url: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Options
+// ---------------------------------------------------------------------------
+
+/// Release archives are a few MB; anything beyond this is not ours.
+pub const MAX_ASSET_SIZE: u64 = 200 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct UpdateOptions {
+    /// Connect timeout, and the longest we wait for the next chunk of data.
+    pub timeout: std::time::Duration,
+    /// Only report whether a newer version exists (`--check`).
+    pub check_only: bool,
+    /// Replace the binary even if a package manager owns it (`--force`).
+    pub force: bool,
+}
+
+impl Default for UpdateOptions {
+    fn default() -> Self {
+        Self {
+            timeout: std::time::Duration::from_secs(30),
+            check_only: false,
+            force: false,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -107,6 +153,167 @@ pub fn is_newer(remote_tag: &str, local_version: &str) -> Result<bool, UpdateErr
     Ok(remote_ver > local_ver)
 }
 
+/// Package managers whose installs must not be replaced behind their back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Homebrew,
+    Scoop,
+    Cargo,
+}
+
+impl PackageManager {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Homebrew => "Homebrew",
+            Self::Scoop => "Scoop",
+            Self::Cargo => "cargo install",
+        }
+    }
+
+    pub fn update_command(&self) -> &'static str {
+        match self {
+            Self::Homebrew => "brew upgrade wcag-lsp",
+            Self::Scoop => "scoop update wcag-lsp",
+            Self::Cargo => "cargo install --git https://github.com/maxischmaxi/wcag-lsp --force",
+        }
+    }
+}
+
+/// Detects a package-manager-managed install from the (resolved) executable
+/// path: Homebrew's Cellar, Scoop's `apps` dir, or cargo's `bin` dir.
+pub fn managed_by(exe: &std::path::Path) -> Option<PackageManager> {
+    let components: Vec<String> = exe
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_ascii_lowercase())
+        .collect();
+    let has_pair = |a: &str, b: &str| components.windows(2).any(|w| w[0] == a && w[1] == b);
+
+    if components.iter().any(|c| c == "cellar" || c == "linuxbrew") {
+        return Some(PackageManager::Homebrew);
+    }
+    if has_pair("scoop", "apps") {
+        return Some(PackageManager::Scoop);
+    }
+    if has_pair(".cargo", "bin") {
+        return Some(PackageManager::Cargo);
+    }
+    if let Some(cargo_home) = std::env::var_os("CARGO_HOME")
+        && exe.starts_with(std::path::Path::new(&cargo_home).join("bin"))
+    {
+        return Some(PackageManager::Cargo);
+    }
+    None
+}
+
+/// Refuses to touch a binary a package manager owns unless `force` is set.
+fn ensure_self_managed(force: bool) -> Result<(), UpdateError> {
+    if force {
+        return Ok(());
+    }
+    let exe = std::env::current_exe()?;
+    // Homebrew links `bin/wcag-lsp` into the Cellar; look at the real file.
+    let exe = std::fs::canonicalize(&exe).unwrap_or(exe);
+    match managed_by(&exe) {
+        Some(pm) => Err(UpdateError::ManagedInstall(pm)),
+        None => Ok(()),
+    }
+}
+
+/// Validates a downloaded size against the size GitHub reported for the asset.
+/// An `expected` of 0 means the API didn't report a size.
+pub fn check_size(actual: u64, expected: u64) -> Result<(), UpdateError> {
+    if actual > MAX_ASSET_SIZE {
+        return Err(UpdateError::TooLarge(actual));
+    }
+    if expected != 0 && actual != expected {
+        return Err(UpdateError::SizeMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+/// Staging file for a partially downloaded asset, kept across runs so an
+/// interrupted download can be resumed.
+pub fn partial_path(tag: &str, asset_name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("wcag-lsp-{tag}-{asset_name}.part"))
+}
+
+// ---------------------------------------------------------------------------
+// Download
+// ---------------------------------------------------------------------------
+
+/// Downloads `url` into `dest`, resuming from the bytes already in `dest` with
+/// a `Range` request. Servers that ignore the range get a fresh download, as
+/// do ranges the server can't satisfy.
+async fn download_resumable(
+    http: &reqwest::Client,
+    url: &str,
+    dest: &std::path::Path,
+    expected_size: u64,
+    timeout: std::time::Duration,
+) -> Result<Vec<u8>, UpdateError> {
+    let existing = std::fs::metadata(dest).map(|m| m.len()).unwrap_or(0);
+    let resume_from = if expected_size != 0 && existing >= expected_size {
+        // Stale or complete-but-unverified staging file: start over.
+        0
+    } else {
+        existing
+    };
+
+    let mut request = http.get(url);
+    if resume_from > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={resume_from}-"));
+    }
+    let mut response = request.send().await?;
+    if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // The staging file is already complete, or from another asset: drop
+        // it and download again from the start.
+        let _ = std::fs::remove_file(dest);
+        response = http.get(url).send().await?;
+    }
+    let mut response = response.error_for_status()?;
+
+    let resumed = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    if resumed {
+        println!("Resuming download at {resume_from} bytes...");
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(dest)?;
+
+    let mut written = if resumed { resume_from } else { 0 };
+    loop {
+        let chunk = tokio::time::timeout(timeout, response.chunk())
+            .await
+            .map_err(|_| {
+                UpdateError::Io(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "download stalled",
+                ))
+            })??;
+        let Some(chunk) = chunk else {
+            break;
+        };
+        written += chunk.len() as u64;
+        if written > MAX_ASSET_SIZE {
+            return Err(UpdateError::TooLarge(written));
+        }
+        file.write_all(&chunk)?;
+    }
+    file.flush()?;
+    drop(file);
+
+    let bytes = std::fs::read(dest)?;
+    if let Err(e) = check_size(bytes.len() as u64, expected_size) {
+        // Don't resume from a corrupt staging file next time.
+        let _ = std::fs::remove_file(dest);
+        return Err(e);
+    }
+    Ok(bytes)
+}
+
 // ---------------------------------------------------------------------------
 // Extract
 // ---------------------------------------------------------------------------
@@ -173,7 +380,24 @@ pub fn extract_binary(archive_bytes: &[u8]) -> Result<Vec<u8>, UpdateError> {
 // Replace
 // ---------------------------------------------------------------------------
 
+/// Where the previous binary is kept after an update: `wcag-lsp.bak` next to
+/// the running executable.
+pub fn backup_path(exe: &std::path::Path) -> std::path::PathBuf {
+    exe.with_file_name("wcag-lsp.bak")
+}
+
+/// Copy `exe` to its backup location, overwriting an older backup.
+pub fn backup_binary(exe: &std::path::Path) -> Result<std::path::PathBuf, UpdateError> {
+    let backup = backup_path(exe);
+    std::fs::copy(exe, &backup)?;
+    Ok(backup)
+}
+
 pub fn replace_binary(binary_data: &[u8]) -> Result<(), UpdateError> {
+    // Keep the current binary around so a bad release can be rolled back.
+    let exe = std::env::current_exe()?;
+    backup_binary(&exe)?;
+
     let dir = std::env::temp_dir();
     let tmp_path = dir.join("wcag-lsp-update-tmp");
 
@@ -201,7 +425,17 @@ pub fn replace_binary(binary_data: &[u8]) -> Result<(), UpdateError> {
 // Orchestration
 // ---------------------------------------------------------------------------
 
-pub async fn self_update() -> Result<(), UpdateError> {
+async fn fetch_latest_release(http: &reqwest::Client) -> Result<GitHubRelease, UpdateError> {
+    Ok(http
+        .get("https://api.github.com/repos/maxischmaxi/wcag-lsp/releases/latest")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+pub async fn self_update(options: &UpdateOptions) -> Result<(), UpdateError> {
     let target = current_target()?;
     let expected_asset = asset_name_for_target(target);
     let local_version = env!("CARGO_PKG_VERSION");
@@ -211,21 +445,24 @@ pub async fn self_update() -> Result<(), UpdateError> {
 
     let http = reqwest::Client::builder()
         .user_agent("wcag-lsp-updater")
+        .connect_timeout(options.timeout)
+        .read_timeout(options.timeout)
         .build()?;
 
-    let release: GitHubRelease = http
-        .get("https://api.github.com/repos/maxischmaxi/wcag-lsp/releases/latest")
-        .send()
-        .await?
-        .error_for_status()?
-        .json()
-        .await?;
+    let release = fetch_latest_release(&http).await?;
 
     if !is_newer(&release.tag_name, local_version)? {
         println!("Already up to date.");
         return Ok(());
     }
 
+    if options.check_only {
+        println!("Update available: {}", release.tag_name);
+        return Ok(());
+    }
+
+    ensure_self_managed(options.force)?;
+
     println!("Updating to {}...", release.tag_name);
 
     let asset = release
@@ -233,22 +470,46 @@ pub async fn self_update() -> Result<(), UpdateError> {
         .iter()
         .find(|a| a.name == expected_asset)
         .ok_or_else(|| UpdateError::AssetNotFound(expected_asset.clone()))?;
+    if asset.size > MAX_ASSET_SIZE {
+        return Err(UpdateError::TooLarge(asset.size));
+    }
 
-    let archive_bytes = http
-        .get(&asset.browser_download_url)
-        .send()
-        .await?
-        .error_for_status()?
-        .bytes()
-        .await?;
+    let staging = partial_path(&release.tag_name, &asset.name);
+    let archive_bytes = download_resumable(
+        &http,
+        &asset.browser_download_url,
+        &staging,
+        asset.size,
+        options.timeout,
+    )
+    .await?;
 
     let binary_data = extract_binary(&archive_bytes)?;
     replace_binary(&binary_data)?;
+    let _ = std::fs::remove_file(&staging);
 
     println!("Updated to {}.", release.tag_name);
     Ok(())
 }
 
+pub fn rollback(force: bool) -> Result<(), UpdateError> {
+    ensure_self_managed(force)?;
+    let exe = std::env::current_exe()?;
+    let backup = backup_path(&exe);
+    if !backup.is_file() {
+        return Err(UpdateError::NoBackup(backup));
+    }
+
+    println!("Restoring previous binary from {}...", backup.display());
+    self_replace::self_replace(&backup).map_err(|e| UpdateError::Replace(e.to_string()))?;
+
+    // The backup is consumed; a second rollback would be a no-op anyway.
+    let _ = std::fs::remove_file(&backup);
+
+    println!("Rolled back to the previous version.");
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -300,6 +561,138 @@ mod tests {
         assert_eq!(name, "wcag-lsp-x86_64-pc-windows-msvc.zip");
     }
 
+    #[test]
+    fn test_managed_by_homebrew() {
+        let exe = std::path::Path::new("/opt/homebrew/Cellar/wcag-lsp/0.5.14/bin/wcag-lsp");
+        assert_eq!(managed_by(exe), Some(PackageManager::Homebrew));
+        let exe = std::path::Path::new("/home/linuxbrew/.linuxbrew/bin/wcag-lsp");
+        assert_eq!(managed_by(exe), Some(PackageManager::Homebrew));
+    }
+
+    #[test]
+    fn test_managed_by_scoop() {
+        let exe = std::path::Path::new("/c/Users/me/scoop/apps/wcag-lsp/current/wcag-lsp.exe");
+        assert_eq!(managed_by(exe), Some(PackageManager::Scoop));
+    }
+
+    #[test]
+    fn test_managed_by_cargo() {
+        let exe = std::path::Path::new("/home/me/.cargo/bin/wcag-lsp");
+        assert_eq!(managed_by(exe), Some(PackageManager::Cargo));
+    }
+
+    #[test]
+    fn test_managed_by_none() {
+        let exe = std::path::Path::new("/home/me/.local/bin/wcag-lsp");
+        assert_eq!(managed_by(exe), None);
+    }
+
+    #[test]
+    fn test_managed_install_message_suggests_command() {
+        let msg = UpdateError::ManagedInstall(PackageManager::Homebrew).to_string();
+        assert!(msg.contains("brew upgrade wcag-lsp"));
+    }
+
+    #[test]
+    fn test_check_size() {
+        assert!(check_size(100, 100).is_ok());
+        assert!(check_size(100, 0).is_ok());
+        assert!(matches!(
+            check_size(99, 100),
+            Err(UpdateError::SizeMismatch {
+                expected: 100,
+                actual: 99
+            })
+        ));
+        assert!(matches!(
+            check_size(MAX_ASSET_SIZE + 1, 0),
+            Err(UpdateError::TooLarge(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_download_restarts_on_unsatisfiable_range() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let url = format!("http://{}/asset", listener.local_addr().unwrap());
+        // Answers a ranged request with 416 and a plain one with the asset;
+        // returns whether each request had a range.
+        let server = std::thread::spawn(move || {
+            let mut ranged = Vec::new();
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut range = false;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    range |= line.to_ascii_lowercase().starts_with("range:");
+                }
+                let response = if range {
+                    "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                } else {
+                    "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nfresh"
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+                ranged.push(range);
+            }
+            ranged
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("asset.part");
+        std::fs::write(&dest, b"stale bytes").unwrap();
+        let http = reqwest::Client::builder().no_proxy().build().unwrap();
+        let bytes = download_resumable(&http, &url, &dest, 0, std::time::Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(bytes, b"fresh");
+        assert_eq!(std::fs::read(&dest).unwrap(), b"fresh");
+        assert_eq!(server.join().unwrap(), [true, false]);
+    }
+
+    #[test]
+    fn test_partial_path_is_per_release() {
+        let a = partial_path("v0.6.0", "wcag-lsp-x86_64-unknown-linux-musl.tar.gz");
+        let b = partial_path("v0.6.1", "wcag-lsp-x86_64-unknown-linux-musl.tar.gz");
+        assert_ne!(a, b);
+        assert!(a.to_string_lossy().ends_with(".part"));
+    }
+
+    #[test]
+    fn test_asset_size_defaults_to_zero() {
+        let asset: GitHubAsset =
+            serde_json::from_str(r#"{"name": "a", "browser_download_url": "u"}"#).unwrap();
+        assert_eq!(asset.size, 0);
+    }
+
+    #[test]
+    fn test_backup_path_next_to_exe() {
+        let exe = std::path::Path::new("/usr/local/bin/wcag-lsp");
+        assert_eq!(
+            backup_path(exe),
+            std::path::PathBuf::from("/usr/local/bin/wcag-lsp.bak")
+        );
+    }
+
+    #[test]
+    fn test_backup_binary_copies_and_overwrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("wcag-lsp");
+        std::fs::write(&exe, b"v1").unwrap();
+        let backup = backup_binary(&exe).unwrap();
+        assert_eq!(std::fs::read(&backup).unwrap(), b"v1");
+
+        std::fs::write(&exe, b"v2").unwrap();
+        backup_binary(&exe).unwrap();
+        assert_eq!(std::fs::read(&backup).unwrap(), b"v2");
+    }
+
     #[cfg(not(target_os = "windows"))]
     #[test]
     fn test_extract_binary_from_tar_gz() {
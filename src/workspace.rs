@@ -0,0 +1,222 @@
+//! Package boundaries in monorepos.
+//!
+//! A directory containing one of [`PACKAGE_MARKERS`] is a package. A package
+//! with its own `.wcag.toml`/`.wcag.json` is linted with that config instead
+//! of the workspace one; configs are not merged. Packages are also the unit
+//! for `check --package` and for the on-disk cache layout.
+
+use crate::config::Config;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Files that mark the root of a package.
+pub const PACKAGE_MARKERS: &[&str] = &["package.json", "Cargo.toml"];
+
+/// The nearest directory at or above `path` that contains a package marker,
+/// not looking further up than `workspace_root`.
+pub fn package_root(path: &Path, workspace_root: Option<&Path>) -> Option<PathBuf> {
+    let path = std::path::absolute(path).ok()?;
+    let start = if path.is_dir() { &path } else { path.parent()? };
+    for dir in start.ancestors() {
+        if PACKAGE_MARKERS.iter().any(|m| dir.join(m).is_file()) {
+            return Some(dir.to_path_buf());
+        }
+        if workspace_root.is_some_and(|root| dir == root) {
+            break;
+        }
+    }
+    None
+}
+
+/// A stable, filesystem-safe name for a package, e.g. `web-1f0c…`. Two
+/// packages with the same directory name still get different keys.
+pub fn package_key(root: &Path) -> String {
+    let name: String = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{name}-{:016x}", fnv1a(root.to_string_lossy().as_bytes()))
+}
+
+/// FNV-1a: unlike `DefaultHasher`, stable across Rust releases.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Lazily loaded per-package configs for one workspace.
+#[derive(Debug, Default, Clone)]
+pub struct PackageConfigs {
+    /// `None` disables package lookup, e.g. when the user passed an explicit
+    /// config file.
+    workspace_root: Option<PathBuf>,
+    /// Directory → package root containing it.
+    roots: HashMap<PathBuf, Option<PathBuf>>,
+    /// Package root → its own config, `None` if it has none.
+    configs: HashMap<PathBuf, Option<Config>>,
+}
+
+impl PackageConfigs {
+    pub fn new(workspace_root: Option<PathBuf>) -> Self {
+        Self {
+            workspace_root: workspace_root.and_then(|r| std::path::absolute(r).ok()),
+            ..Default::default()
+        }
+    }
+
+    /// Finds the package containing `path` and loads its config, once.
+    pub fn load(&mut self, path: &Path) {
+        let Some(workspace_root) = self.workspace_root.clone() else {
+            return;
+        };
+        let Some(dir) = parent_dir(path).filter(|d| d.starts_with(&workspace_root)) else {
+            return;
+        };
+        let root = self
+            .roots
+            .entry(dir.clone())
+            .or_insert_with(|| package_root(&dir, Some(&workspace_root)))
+            .clone();
+        // The workspace root's own config is the workspace config.
+        if let Some(root) = root.filter(|r| *r != workspace_root) {
+            self.configs
+                .entry(root.clone())
+                .or_insert_with(|| Config::find_in(&root));
+        }
+    }
+
+    /// The config of the package containing `path`, if that package has one.
+    /// Only answers for paths passed to [`PackageConfigs::load`] before.
+    pub fn get(&self, path: &Path) -> Option<&Config> {
+        let root = self.roots.get(&parent_dir(path)?)?.as_ref()?;
+        self.configs.get(root)?.as_ref()
+    }
+
+    /// The package root containing `path`, if it was loaded.
+    pub fn root_of(&self, path: &Path) -> Option<&Path> {
+        self.roots.get(&parent_dir(path)?)?.as_deref()
+    }
+
+    /// Adopts what `other` loaded, keeping entries this one already has.
+    /// `other` must be a copy of this one, e.g. loaded off a lock.
+    pub fn merge(&mut self, other: PackageConfigs) {
+        for (dir, root) in other.roots {
+            self.roots.entry(dir).or_insert(root);
+        }
+        for (root, config) in other.configs {
+            self.configs.entry(root).or_insert(config);
+        }
+    }
+
+    /// Forgets everything loaded so far, e.g. after a config file changed.
+    pub fn clear(&mut self) {
+        self.roots.clear();
+        self.configs.clear();
+    }
+}
+
+fn parent_dir(path: &Path) -> Option<PathBuf> {
+    let path = std::path::absolute(path).ok()?;
+    if path.is_dir() {
+        Some(path)
+    } else {
+        path.parent().map(Path::to_path_buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Severity;
+
+    /// `root/` with `package.json`, `packages/web/` with its own config and
+    /// `packages/docs/` without one.
+    fn monorepo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("package.json"), "{}").unwrap();
+        std::fs::create_dir_all(root.join("packages/web/src")).unwrap();
+        std::fs::write(root.join("packages/web/package.json"), "{}").unwrap();
+        std::fs::write(
+            root.join("packages/web/.wcag.toml"),
+            "[severity]\nAA = \"error\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("packages/docs")).unwrap();
+        std::fs::write(root.join("packages/docs/Cargo.toml"), "").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_package_root_finds_nearest_marker() {
+        let dir = monorepo();
+        let root = dir.path();
+        let file = root.join("packages/web/src/index.html");
+        assert_eq!(
+            package_root(&file, Some(root)),
+            Some(root.join("packages/web"))
+        );
+        assert_eq!(
+            package_root(&root.join("packages/docs/a.html"), Some(root)),
+            Some(root.join("packages/docs"))
+        );
+        assert_eq!(
+            package_root(&root.join("index.html"), Some(root)),
+            Some(root.to_path_buf())
+        );
+    }
+
+    #[test]
+    fn test_package_root_stops_at_workspace_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+        let inner = dir.path().join("inner");
+        std::fs::create_dir(&inner).unwrap();
+        assert_eq!(package_root(&inner.join("a.html"), Some(&inner)), None);
+    }
+
+    #[test]
+    fn test_package_configs_use_own_config() {
+        let dir = monorepo();
+        let root = dir.path();
+        let mut packages = PackageConfigs::new(Some(root.to_path_buf()));
+
+        let web = root.join("packages/web/src/index.html");
+        let docs = root.join("packages/docs/index.html");
+        packages.load(&web);
+        packages.load(&docs);
+
+        let config = packages.get(&web).expect("web has its own config");
+        assert_eq!(config.severity_aa, Some(Severity::Error));
+        assert!(packages.get(&docs).is_none(), "docs falls back");
+        assert_eq!(
+            packages.root_of(&docs),
+            Some(root.join("packages/docs").as_path())
+        );
+    }
+
+    #[test]
+    fn test_package_configs_disabled_without_root() {
+        let dir = monorepo();
+        let web = dir.path().join("packages/web/src/index.html");
+        let mut packages = PackageConfigs::new(None);
+        packages.load(&web);
+        assert!(packages.get(&web).is_none());
+    }
+
+    #[test]
+    fn test_package_key_is_stable_and_distinct() {
+        let a = package_key(Path::new("/repo/apps/web"));
+        let b = package_key(Path::new("/repo/packages/web"));
+        assert!(a.starts_with("web-"));
+        assert_ne!(a, b);
+        assert_eq!(a, package_key(Path::new("/repo/apps/web")));
+    }
+}
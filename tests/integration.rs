@@ -1,7 +1,8 @@
-use tower_lsp_server::ls_types::NumberOrString;
-use wcag_lsp::config::Config;
+use tower_lsp_server::ls_types::{DiagnosticSeverity, NumberOrString};
+use wcag_lsp::config::{Config, SpreadPolicy};
 use wcag_lsp::document::DocumentManager;
 use wcag_lsp::engine;
+use wcag_lsp::parser::FileType;
 use wcag_lsp::rules;
 
 #[test]
@@ -166,6 +167,700 @@ fn test_vue_analysis() {
     );
 }
 
+#[test]
+fn test_angular_template_bindings_and_events() {
+    let mut mgr = DocumentManager::new();
+    let html = r#"<ul>
+  <li *ngFor="let user of users; trackBy: byId">
+    <img [src]="user.avatar" [attr.alt]="user.name">
+    <div (click)="select(user)">{{ user.name }}</div>
+  </li>
+</ul>
+@if (more) {
+  <img src="more.png">
+}
+"#;
+
+    let doc = mgr
+        .open(
+            "file:///src/app/users.component.html".to_string(),
+            html.to_string(),
+            1,
+        )
+        .unwrap();
+    assert_eq!(doc.file_type, FileType::Angular);
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+    let found: Vec<_> = diagnostics
+        .iter()
+        .map(|d| {
+            let Some(NumberOrString::String(code)) = &d.code else {
+                panic!("rule id expected");
+            };
+            (code.as_str(), d.range.start.line)
+        })
+        .collect();
+
+    // No page-title for a component template, no img-alt for the bound alt.
+    assert!(
+        found.contains(&("click-events-have-key-events", 3)),
+        "found: {found:?}"
+    );
+    assert!(found.contains(&("img-alt", 7)), "found: {found:?}");
+    assert!(
+        !found
+            .iter()
+            .any(|(code, line)| *code == "img-alt" && *line == 2)
+    );
+    assert!(!found.iter().any(|(code, _)| *code == "page-title"));
+}
+
+#[test]
+fn test_php_lints_markup_around_code_blocks() {
+    let mut mgr = DocumentManager::new();
+    let php = r#"<?php
+$items = get_posts(['post_type' => 'product']);
+?>
+<ul>
+<?php foreach ($items as $item): ?>
+  <li><img src="<?= esc_url($item->thumb) ?>" alt="<?= esc_attr($item->title) ?>"></li>
+<?php endforeach; ?>
+</ul>
+<img src="<?php echo $logo; ?>">
+"#;
+
+    let doc = mgr
+        .open("file:///theme/products.php".to_string(), php.to_string(), 1)
+        .unwrap();
+    assert_eq!(doc.file_type, FileType::Php);
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+    let img_alt: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.code == Some(NumberOrString::String("img-alt".to_string())))
+        .collect();
+
+    assert_eq!(img_alt.len(), 1, "found: {diagnostics:?}");
+    assert_eq!(img_alt[0].range.start.line, 8);
+}
+
+#[test]
+fn test_wordpress_template_tags_count_as_content() {
+    let mut mgr = DocumentManager::new();
+    let php = r#"<?php while ( have_posts() ) : the_post(); ?>
+<article>
+  <h2><a href="<?php the_permalink(); ?>"><?php the_title(); ?></a></h2>
+  <img src="<?php echo esc_url( $thumb ); ?>" alt="<?php echo esc_attr( $alt ); ?>">
+  <button type="button" aria-expanded="<?php echo $open ? 'true' : 'false'; ?>"><?php esc_html_e( 'Share', 'theme' ); ?></button>
+  <a href="<?php echo esc_url( $next ); ?>"><?php if ( $next ) : ?></a>
+</article>
+<?php endwhile; ?>
+"#;
+
+    let doc = mgr
+        .open(
+            "file:///wp-content/themes/site/archive.php".to_string(),
+            php.to_string(),
+            1,
+        )
+        .unwrap();
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+    let found: Vec<_> = diagnostics
+        .iter()
+        .map(|d| {
+            let Some(NumberOrString::String(code)) = &d.code else {
+                panic!("rule id expected");
+            };
+            (code.as_str(), d.range.start.line)
+        })
+        .filter(|(code, _)| !matches!(*code, "page-title" | "heading-order"))
+        .collect();
+
+    // Only the link whose block prints nothing is empty.
+    assert_eq!(found, [("anchor-content", 5)]);
+}
+
+#[test]
+fn test_blade_lints_static_markup() {
+    let mut mgr = DocumentManager::new();
+    let blade = r#"<!DOCTYPE html>
+<html lang="{{ str_replace('_', '-', app()->getLocale()) }}">
+<head><title>{{ config('app.name') }}</title></head>
+<body>
+{{-- <img src="commented-out.png"> --}}
+@if ($user->avatar && count($items) < 10)
+  <img src="{{ $user->avatar }}" alt="{{ $user->name }}">
+@endif
+<x-button :disabled="$busy" {{ $attributes->merge(['class' => 'btn']) }}>{{ __('Save') }}</x-button>
+<button>{{ $label }}</button>
+<img src="{{ asset('logo.png') }}">
+</body>
+</html>
+"#;
+
+    let doc = mgr
+        .open(
+            "file:///resources/views/welcome.blade.php".to_string(),
+            blade.to_string(),
+            1,
+        )
+        .unwrap();
+    assert_eq!(doc.file_type, FileType::Blade);
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+
+    assert_eq!(diagnostics.len(), 1, "found: {diagnostics:?}");
+    assert_eq!(
+        diagnostics[0].code,
+        Some(NumberOrString::String("img-alt".to_string()))
+    );
+    assert_eq!(diagnostics[0].range.start.line, 10);
+}
+
+#[test]
+fn test_erb_lints_markup_around_ruby() {
+    let mut mgr = DocumentManager::new();
+    let erb = r#"<!DOCTYPE html>
+<html lang="<%= I18n.locale %>">
+<head><title><%= content_for(:title) || "Shop" %></title></head>
+<body>
+<%# <img src="commented-out.png"> %>
+<% @products.each do |product| %>
+  <% if product.price < 10 %>
+    <%= image_tag product.photo %>
+    <img src="<%= product.thumb_url %>" alt="<%= product.name %>">
+  <% end %>
+<% end %>
+<button type="submit"><%= t(".buy") %></button>
+<img src="<%= asset_path("logo.png") %>">
+</body>
+</html>
+"#;
+
+    let doc = mgr
+        .open(
+            "file:///app/views/products/index.html.erb".to_string(),
+            erb.to_string(),
+            1,
+        )
+        .unwrap();
+    assert_eq!(doc.file_type, FileType::Erb);
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+
+    assert_eq!(diagnostics.len(), 1, "found: {diagnostics:?}");
+    assert_eq!(
+        diagnostics[0].code,
+        Some(NumberOrString::String("img-alt".to_string()))
+    );
+    assert_eq!(diagnostics[0].range.start.line, 12);
+}
+
+#[test]
+fn test_liquid_section_lints_markup_around_tags() {
+    let mut mgr = DocumentManager::new();
+    let liquid = r#"{% comment %}Featured products{% endcomment %}
+<section class="{% if section.settings.full_width %}full{% endif %}">
+  <p>{{ section.settings.title | escape }}</p>
+  {%- for product in collections.frontpage.products limit: 4 -%}
+    <a href="{{ product.url }}">
+      <img src="{{ product.featured_image | image_url: width: 400 }}" alt="{{ product.title }}">
+    </a>
+  {%- endfor -%}
+  <button type="button">{{ 'products.load_more' | t }}</button>
+  <img src="{{ 'badge.png' | asset_url }}">
+</section>
+{% schema %}
+{ "name": "Featured <products>", "settings": [] }
+{% endschema %}
+"#;
+
+    let doc = mgr
+        .open(
+            "file:///theme/sections/featured.liquid".to_string(),
+            liquid.to_string(),
+            1,
+        )
+        .unwrap();
+    assert_eq!(doc.file_type, FileType::Liquid);
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+
+    assert_eq!(diagnostics.len(), 1, "found: {diagnostics:?}");
+    assert_eq!(
+        diagnostics[0].code,
+        Some(NumberOrString::String("img-alt".to_string()))
+    );
+    assert_eq!(diagnostics[0].range.start.line, 9);
+}
+
+#[test]
+fn test_jekyll_page_mapped_to_liquid() {
+    let mut config = Config::default();
+    config
+        .file_types
+        .insert("html".to_string(), FileType::Liquid);
+    let jekyll = r#"---
+layout: default
+title: <Team>
+---
+<h1>{{ page.title }}</h1>
+{% for member in site.data.team %}
+  <img src="{{ member.photo | relative_url }}" alt="{{ member.name }}">
+{% endfor %}
+{% highlight html %}
+<img src="example.png">
+{% endhighlight %}
+<img src="{{ '/logo.png' | relative_url }}">
+"#;
+
+    let uri = "file:///site/about.html";
+    let file_type = config.file_type_for(uri, Some("html"), jekyll);
+    assert_eq!(file_type, FileType::Liquid);
+    let mut mgr = DocumentManager::new();
+    let doc = mgr
+        .open_as(uri.to_string(), jekyll.to_string(), 1, file_type)
+        .unwrap();
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &config);
+
+    assert_eq!(diagnostics.len(), 1, "found: {diagnostics:?}");
+    assert_eq!(
+        diagnostics[0].code,
+        Some(NumberOrString::String("img-alt".to_string()))
+    );
+    assert_eq!(diagnostics[0].range.start.line, 11);
+}
+
+#[test]
+fn test_markdown_raw_html_linted_in_place() {
+    let mut mgr = DocumentManager::new();
+    let markdown = r#"# wcag-lsp
+
+<p align="center">
+  <img src="docs/logo.svg" width="120">
+</p>
+
+Install it, then add a badge: <a href="https://ci.example.com"><img src="badge.svg"></a>
+
+```html
+<img src="no-alt-in-a-code-sample.png">
+```
+
+Use `<img alt="">` for decorative images, see <https://www.w3.org/WAI/tutorials/images/>.
+
+<h3>Details</h3>
+"#;
+
+    let doc = mgr
+        .open(
+            "file:///repo/README.md".to_string(),
+            markdown.to_string(),
+            1,
+        )
+        .unwrap();
+    assert_eq!(doc.file_type, FileType::Markdown);
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+
+    let found: Vec<(String, u32, u32)> = diagnostics
+        .iter()
+        .map(|d| match &d.code {
+            Some(NumberOrString::String(code)) => {
+                (code.clone(), d.range.start.line, d.range.start.character)
+            }
+            _ => panic!("diagnostic without a code"),
+        })
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("img-alt".to_string(), 3, 2),
+            ("img-alt".to_string(), 6, 63),
+        ]
+    );
+}
+
+#[test]
+fn test_lit_templates_linted_in_place() {
+    let mut mgr = DocumentManager::new();
+    let component = r#"import { LitElement, html } from "lit";
+
+export class UserCard extends LitElement {
+  render() {
+    return html`
+      <img src=${this.avatar}>
+      <img src=${this.avatar} alt=${this.name}>
+      <button ?disabled=${this.busy} @click=${this.save}></button>
+      <ul>${this.tags.map((tag) => html`<li><a href="/tags/${tag}"></a></li>`)}</ul>
+    `;
+  }
+
+  get size() {
+    return <number>this.tags.length;
+  }
+}
+"#;
+
+    let doc = mgr
+        .open(
+            "file:///src/user-card.ts".to_string(),
+            component.to_string(),
+            1,
+        )
+        .unwrap();
+    // Parsed as TypeScript, not TSX: the `<number>` assertion isn't a tag.
+    assert_eq!(doc.file_type, FileType::TypeScript);
+    assert!(!doc.tree.root_node().has_error());
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+
+    let found: Vec<(String, u32, u32)> = diagnostics
+        .iter()
+        .map(|d| match &d.code {
+            Some(NumberOrString::String(code)) => {
+                (code.clone(), d.range.start.line, d.range.start.character)
+            }
+            _ => panic!("diagnostic without a code"),
+        })
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("anchor-content".to_string(), 8, 44),
+            ("button-name".to_string(), 7, 6),
+            ("img-alt".to_string(), 5, 6),
+        ]
+    );
+}
+
+#[test]
+fn test_mjml_components_checked_as_html() {
+    let mut mgr = DocumentManager::new();
+    let mjml = r#"<mjml lang="en">
+  <mj-head>
+    <mj-title>Welcome</mj-title>
+  </mj-head>
+  <mj-body>
+    <mj-section>
+      <mj-column>
+        <mj-image src="logo.png" />
+        <mj-image src="hero.png" alt="Our new office" />
+        <mj-text><img src="badge.png"></mj-text>
+        <mj-button href="/start"></mj-button>
+        <mj-button href="/go">Get started</mj-button>
+        <mj-table>
+          <tr><td>Plan</td><td>Price</td></tr>
+        </mj-table>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>
+"#;
+
+    let doc = mgr
+        .open(
+            "file:///emails/welcome.mjml".to_string(),
+            mjml.to_string(),
+            1,
+        )
+        .unwrap();
+    assert_eq!(doc.file_type, FileType::Mjml);
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+    let mut found: Vec<_> = diagnostics
+        .iter()
+        .map(|d| {
+            let Some(NumberOrString::String(code)) = &d.code else {
+                panic!("rule id expected");
+            };
+            (code.as_str(), d.range.start.line)
+        })
+        .collect();
+    found.sort();
+
+    // `<mjml lang>` and `<mj-title>` satisfy html-lang and page-title.
+    assert_eq!(
+        found,
+        [
+            ("button-name", 10),
+            ("img-alt", 7),
+            ("img-alt", 9),
+            ("table-header", 12)
+        ]
+    );
+}
+
+#[test]
+fn test_storybook_story_skips_page_rules_and_resolves_args() {
+    let mut mgr = DocumentManager::new();
+    let tsx = r#"import type { Meta, StoryObj } from "@storybook/react";
+
+const meta = { title: "Avatar", args: { src: "avatar.png" } } satisfies Meta;
+export default meta;
+
+export const Named: StoryObj = {
+  args: { alt: "Ada Lovelace" },
+  render: (args) => (
+    <div>
+      <h3 id="avatar">Profile</h3>
+      <img {...args} />
+    </div>
+  ),
+};
+
+export const Unnamed: StoryObj = {
+  render: (args) => (
+    <div>
+      <h3 id="avatar">Profile</h3>
+      <img {...args} />
+    </div>
+  ),
+};
+"#;
+
+    let doc = mgr
+        .open(
+            "file:///src/Avatar.stories.tsx".to_string(),
+            tsx.to_string(),
+            1,
+        )
+        .unwrap();
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+    let found: Vec<_> = diagnostics
+        .iter()
+        .map(|d| {
+            let Some(NumberOrString::String(code)) = &d.code else {
+                panic!("rule id expected");
+            };
+            (code.as_str(), d.range.start.line)
+        })
+        .collect();
+
+    // Only the story without an `alt` arg is missing one; the h3 and the
+    // repeated id are page-level concerns.
+    assert_eq!(found, [("img-alt", 19)]);
+}
+
+#[test]
+fn test_jsx_spread_props_policy() {
+    let mut mgr = DocumentManager::new();
+    let tsx = r#"const logoProps = { src: "logo.svg", alt: "Acme" };
+
+export function Header(props) {
+  return (
+    <header>
+      <img {...logoProps} />
+      <img src="banner.png" {...props} />
+    </header>
+  );
+}
+"#;
+
+    let doc = mgr
+        .open("file:///src/Header.tsx".to_string(), tsx.to_string(), 1)
+        .unwrap();
+    let rules = rules::all_rules();
+    let found = |config: &Config| -> Vec<_> {
+        engine::run_diagnostics(doc, &rules, config)
+            .into_iter()
+            .map(|d| (d.range.start.line, d.severity))
+            .collect()
+    };
+
+    // The logo's alt comes from the object it spreads; the banner's may come
+    // from the component's props.
+    assert_eq!(
+        found(&Config::default()),
+        [(6, Some(DiagnosticSeverity::HINT))]
+    );
+
+    let report = Config {
+        spread_props: SpreadPolicy::Report,
+        ..Config::default()
+    };
+    assert_eq!(found(&report).len(), 2);
+    let skip = Config {
+        spread_props: SpreadPolicy::Skip,
+        ..Config::default()
+    };
+    assert!(found(&skip).is_empty());
+}
+
+#[test]
+fn test_mapped_components_checked_as_their_elements() {
+    let mut mgr = DocumentManager::new();
+    let tsx = r#"import { Avatar, IconButton } from "./ui";
+
+export const Profile = ({ user }) => (
+  <section>
+    <Avatar source={user.photo} />
+    <Avatar source={user.photo} imageAlt={user.name} />
+    <IconButton icon="close" onClick={close}></IconButton>
+  </section>
+);
+"#;
+
+    let doc = mgr
+        .open("file:///src/Profile.tsx".to_string(), tsx.to_string(), 1)
+        .unwrap();
+    let config = Config::parse(
+        r#"
+[components]
+IconButton = "button"
+Avatar = { element = "img", attributes = { imageAlt = "alt", source = "src" } }
+"#,
+    );
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &config);
+    let found: Vec<(&str, u32, u32, u32)> = diagnostics
+        .iter()
+        .map(|d| {
+            let Some(NumberOrString::String(code)) = &d.code else {
+                panic!("rule id expected");
+            };
+            (
+                code.as_str(),
+                d.range.start.line,
+                d.range.start.character,
+                d.range.end.character,
+            )
+        })
+        .collect();
+    assert_eq!(found, [("button-name", 6, 4, 58), ("img-alt", 4, 4, 34)]);
+
+    // Without the mapping, the components aren't checked.
+    assert!(engine::run_diagnostics(doc, &rules, &Config::default()).is_empty());
+}
+
+#[test]
+fn test_mapped_custom_elements_checked_as_their_elements() {
+    let mut mgr = DocumentManager::new();
+    let ts = r#"class SearchBox extends LitElement {
+  render() {
+    return html`<form>
+      <x-input type="search" name="q"></x-input>
+      <fancy-button><a href="/help">Help</a></fancy-button>
+    </form>`;
+  }
+}
+"#;
+
+    let doc = mgr
+        .open("file:///src/search-box.ts".to_string(), ts.to_string(), 1)
+        .unwrap();
+    let config = Config::parse(
+        r#"
+[components]
+x-input = "input"
+fancy-button = "button"
+"#,
+    );
+    let rules = rules::all_rules();
+    let mut found: Vec<(String, u32, u32)> = engine::run_diagnostics(doc, &rules, &config)
+        .iter()
+        .map(|d| {
+            let Some(NumberOrString::String(code)) = &d.code else {
+                panic!("rule id expected");
+            };
+            (code.clone(), d.range.start.line, d.range.start.character)
+        })
+        .collect();
+    found.sort();
+    assert_eq!(
+        found,
+        [
+            ("form-label".to_string(), 3, 6),
+            ("nested-interactive".to_string(), 4, 20),
+        ]
+    );
+
+    // Without the mapping, the custom elements aren't checked.
+    assert!(engine::run_diagnostics(doc, &rules, &Config::default()).is_empty());
+}
+
+#[test]
+fn test_component_patterns_and_styled_components_checked() {
+    let mut mgr = DocumentManager::new();
+    let tsx = r#"import styled from "styled-components";
+
+const Button = styled.button`padding: 0;`;
+
+export const Card = () => (
+  <BaseDiv onClick={open}>
+    <Button><a href="/more">More</a></Button>
+  </BaseDiv>
+);
+"#;
+
+    let doc = mgr
+        .open("file:///src/Card.tsx".to_string(), tsx.to_string(), 1)
+        .unwrap();
+    let config = Config::parse(
+        r#"
+[components]
+"Base*" = "*"
+
+[jsx]
+styled = true
+"#,
+    );
+    let rules = rules::all_rules();
+    let mut found: Vec<(String, u32)> = engine::run_diagnostics(doc, &rules, &config)
+        .iter()
+        .map(|d| {
+            let Some(NumberOrString::String(code)) = &d.code else {
+                panic!("rule id expected");
+            };
+            (code.clone(), d.range.start.line)
+        })
+        .collect();
+    found.sort();
+    assert_eq!(
+        found,
+        [
+            ("click-events-have-key-events".to_string(), 5),
+            ("nested-interactive".to_string(), 6),
+        ]
+    );
+
+    // Without the settings, the components aren't checked.
+    assert!(engine::run_diagnostics(doc, &rules, &Config::default()).is_empty());
+}
+
+#[test]
+fn test_vue_sfc_lints_only_the_template() {
+    let mut mgr = DocumentManager::new();
+    let vue = r#"<script setup>
+const html = '<img src="in-script.png">'
+</script>
+
+<template>
+  <img src="in-template.png" />
+</template>
+
+<docs>
+Usage: <img src="in-docs.png">
+</docs>
+"#;
+
+    let doc = mgr
+        .open("file:///Comp.vue".to_string(), vue.to_string(), 1)
+        .unwrap();
+    let rules = rules::all_rules();
+    let diagnostics = engine::run_diagnostics(doc, &rules, &Config::default());
+
+    assert_eq!(diagnostics.len(), 1, "found: {diagnostics:?}");
+    assert_eq!(
+        diagnostics[0].code,
+        Some(NumberOrString::String("img-alt".to_string()))
+    );
+    assert_eq!(diagnostics[0].range.start.line, 5);
+    assert_eq!(diagnostics[0].range.start.character, 2);
+}
+
 #[test]
 fn test_config_disables_rule() {
     let mut mgr = DocumentManager::new();
@@ -239,9 +934,11 @@ fn test_clean_html_no_diagnostics() {
 <html lang="en">
 <head><title>Clean Page</title></head>
 <body>
-  <h1>Welcome</h1>
-  <img src="photo.jpg" alt="A beautiful sunset">
-  <a href="/">Home</a>
+  <main>
+    <h1>Welcome</h1>
+    <img src="photo.jpg" alt="A beautiful sunset">
+    <a href="/">Home</a>
+  </main>
 </body>
 </html>"#;
 
@@ -382,3 +1079,30 @@ fn test_inline_disable_line_suppresses_only_current_line() {
     );
     assert_eq!(img_alt_diags[0].range.start.line, 3);
 }
+
+#[test]
+fn test_dom_snapshot_runs_dynamic_rules_only_when_asked() {
+    let snapshot = r#"<html lang="en"><head><title>Shop</title></head>
+<body><main><h1>Cart</h1><div id="app"></div><div id="app"></div>
+<div class="toast-container"><div class="toast">Added to cart</div></div></main></body></html>"#;
+    let rules = rules::all_rules();
+    let config = Config::default();
+    let codes = |dynamic: bool| -> Vec<String> {
+        let (diagnostics, _) =
+            wcag_lsp::scan::lint_snapshot("snapshot.html", snapshot, dynamic, &rules, &config);
+        diagnostics
+            .iter()
+            .filter_map(|d| match &d.code {
+                Some(NumberOrString::String(s)) => Some(s.clone()),
+                _ => None,
+            })
+            .collect()
+    };
+
+    assert_eq!(codes(false), ["no-duplicate-id"]);
+    assert_eq!(codes(true), ["live-region", "no-duplicate-id"]);
+
+    let (source_diagnostics, _) =
+        wcag_lsp::scan::lint_source("page.html", snapshot, FileType::Html, &rules, &config);
+    assert_eq!(source_diagnostics.len(), 1);
+}